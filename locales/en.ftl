@@ -1,6 +1,7 @@
 brand = Oeee Cafe
 email-from-address = Oeee Cafe <noreply@oeee.cafe>
 error-404 = Page not found.
+error-410 = This page has been deleted.
 error-403 = You do not have permission to access this page.
 
 about = Oeee Cafe is an oekaki board.
@@ -29,6 +30,7 @@ notification-mention = Mention
 notification-post-reply = Post reply
 notification-comment-reply = Comment reply
 notification-community-post = New community post
+notification-follower-moved = Follower moved
 
 # Notification action messages with FluentArgs for proper word order in different languages
 notification-action-commented-on = commented on { $postTitle }
@@ -42,9 +44,16 @@ notification-action-replied-to-post-with-title = replied to your post { $postTit
 notification-action-replied-to-comment = replied to your comment on { $postTitle }
 notification-action-posted-in-community = posted in your community
 notification-action-posted-in-community-with-title = posted in your community: { $postTitle }
+notification-action-follower-moved = moved to a new account, which now follows you instead
 
 notification-mark-all-read = Mark all as read
+notification-filter-all = All
+loading-more-notifications = Loading more notifications...
 notification-mark-read = Mark as read
+notification-and-others = { $count ->
+    [one] and 1 other
+   *[other] and { $count } others
+}
 notification-delete = Delete
 notification-delete-confirm = Are you sure you want to delete this notification?
 
@@ -65,18 +74,29 @@ push-notification-comment-reply-body = { $name } replied to your comment
 push-notification-community-post-title = New community post
 push-notification-community-post-body = { $name } posted in your community
 push-notification-community-post-body-with-title = { $name } posted in your community: { $title }
+push-notification-follower-moved-title = Follower moved
+push-notification-follower-moved-body = { $name } moved to a new account, which now follows you instead
 push-notification-community-invite-title = Community Invitation
 push-notification-community-invite-body = { $inviter } invited you to join @{ $community }
 push-notification-invite-accepted-title = Invitation Accepted
 push-notification-invite-accepted-body = { $accepter } accepted your invitation to join @{ $community }
 push-notification-invite-declined-title = Invitation Declined
 push-notification-invite-declined-body = { $decliner } declined your invitation to join @{ $community }
+push-notification-join-request-approved-title = Join Request Approved
+push-notification-join-request-approved-body = You are now a member of @{ $community }
+push-notification-join-request-rejected-title = Join Request Declined
+push-notification-join-request-rejected-body = Your request to join @{ $community } was declined
+push-notification-post-approved-title = Post Approved
+push-notification-post-approved-body = Your post in @{ $community } is now published
+push-notification-post-rejected-title = Post Not Approved
+push-notification-post-rejected-body = Your post in @{ $community } was not approved and is back in your drafts
 
 sign-in = Sign in
 sign-up = Sign up
 sign-out = Sign out
 
 message-incorrect-credentials = Incorrect credentials.
+message-too-many-attempts = Too many attempts. Please try again later.
 login-name-conflict-error = This username is already taken by a community. Please choose a different username.
 
 forgot-password = Forgot password?
@@ -118,8 +138,17 @@ community-stats = Community stats
 community-stats-posts = posts
 community-stats-contributors = contributors
 community-stats-comments = comments
+community-leaderboard = Top contributors
+community-leaderboard-week = This week
+community-leaderboard-month = This month
+community-leaderboard-all-time = All time
+community-leaderboard-score = { $score } points
+community-leaderboard-counts = { $posts } posts · { $reactions } reactions · { $comments } comments received
+community-leaderboard-your-rank = Your rank: #{ $rank } ({ $score } points)
+community-leaderboard-empty = No contributions in this period yet.
 
 edit = Edit
+comment-edited = (edited)
 edit-community-button = Move to Community
 save = Save
 cancel = Cancel
@@ -127,6 +156,9 @@ delete = Delete
 
 post-created-at = Created
 post-published-at = Published
+post-edited = Edited
+post-history = Edit history
+post-history-empty = This post hasn't been edited.
 post-duration = Duration
 post-replay = Replay
 post-author = Author
@@ -139,6 +171,11 @@ post-no-comments = There are no comments.
 post-no-comments-signin = Sign in to add a new comment.
 post-comment = Post comment
 post-publish = Publish
+post-schedule = Schedule
+post-scheduled-for = Scheduled for
+post-schedule-hint = Pick a time and press Schedule to publish later. Times are in KST.
+post-submitted-for-approval = Your post was submitted. It will appear once the community's owner or a moderator approves it.
+post-pending-approval = Submitted for review
 post-move-community = Move to this community
 post-move-community-title = Move Post to Community
 post-move-post-to-move = Post to Move
@@ -158,6 +195,8 @@ post-delete-confirm = Are you sure you want to delete this post?
 post-relay-enabled-notice = You can continue drawing on this canvas. Click on the image!
 post-relay = Relay
 post-reply-with-drawing = Reply with Drawing
+post-translate = Translate
+post-translation-failed = Translation failed. Please try again later.
 post-reply-select-tool = Select tool and canvas size
 post-child-posts = Replies
 post-replying-to = Replying to:
@@ -168,6 +207,12 @@ post-reaction = reaction
 post-reactions = reactions
 post-share = Share
 post-share-copied = Link copied to clipboard!
+post-boost = Boost
+post-unboost = Undo boost
+report-object = Report
+report-object-comment = What is wrong with it? (optional)
+report-object-submit = Send report
+report-object-sent = Reported. Thank you.
 post-report = Report
 post-report-title = Report this post
 post-report-description-label = Reason for reporting
@@ -176,6 +221,57 @@ post-report-submit = Submit Report
 post-report-success = Post reported successfully. Thank you for helping keep our community safe.
 post-report-error = Failed to submit report. Please try again.
 post-report-confirm = Are you sure you want to report this post?
+post-report-reason-label = Category
+post-report-reason-spam = Spam
+post-report-reason-harassment = Harassment
+post-report-reason-sexual-content = Sexual content
+post-report-reason-violence = Violence
+post-report-reason-copyright = Copyright infringement
+post-report-reason-other = Other
+admin-reports = Reports
+admin-reports-empty = There are no pending reports.
+admin-reports-reporter = Reporter
+admin-reports-reason = Reason
+admin-reports-detail = Detail
+admin-reports-post = Post
+admin-reports-reported-at = Reported at
+admin-reports-resolve = Resolve
+admin-reports-dismiss = Dismiss
+admin-reports-objects = Reported comments and federated content
+admin-reports-objects-targets = Reported objects
+admin-reports-objects-remote = from another instance
+admin-announcements = Announcements
+admin-announcements-new = New announcement
+admin-announcements-message = Message
+admin-announcements-level = Level
+admin-announcements-level-info = Info
+admin-announcements-level-warning = Warning
+admin-announcements-level-critical = Critical
+admin-announcements-starts-at = Starts at
+admin-announcements-ends-at = Ends at
+admin-announcements-time-help = Times are in KST. Leave the start empty to show it right away, and the end empty to keep it until deleted.
+admin-announcements-dismissible = Users can dismiss it
+admin-announcements-create = Create announcement
+admin-announcements-delete = Delete
+admin-announcements-empty = There are no announcements.
+admin-announcements-content-help = Text for each language is optional; the message is shown in languages left empty.
+admin-announcements-content-ko = Korean
+admin-announcements-content-en = English
+admin-announcements-content-ja = Japanese
+admin-announcements-content-zh = Chinese
+admin-announcements-active = Active
+admin-announcements-activate = Activate
+admin-announcements-deactivate = Deactivate
+admin-community-categories = Community categories
+admin-community-categories-new = New category
+admin-community-categories-name = Name
+admin-community-categories-slug = Slug
+admin-community-categories-position = Order
+admin-community-categories-help = The slug appears in links and may only use a-z, 0-9 and -. Categories with a lower order are listed first.
+admin-community-categories-create = Create category
+admin-community-categories-delete = Delete
+admin-community-categories-empty = There are no categories.
+announcement-dismiss = Dismiss
 
 allow-relay = Allow relay posts
 sensitive = Sensitive
@@ -187,12 +283,25 @@ draft-empty = You don't have any drafts yet
 
 # Hashtags
 trending-hashtags = Trending Hashtags
+trending-hashtags-description = Hashtags used in the most drawings over the last 24 hours
+trending-hashtags-updated = Updated
+trending-hashtags-delta = Drawings in the last hour compared with the hour before
+trending-hashtags-empty = Nothing is trending right now
 hashtag-post-count = posts
 hashtag-no-posts = No posts with this hashtag yet
+hashtag-follow = Follow
+hashtag-unfollow = Unfollow
+hashtag-following = Followed hashtags
+hashtag-following-empty = You are not following any hashtags yet
 hashtag-discovery = Discover Hashtags
 hashtag-search-placeholder = Search hashtags...
 search = Search
 search-results-for = Search results for
+search-posts = Search Drawings
+search-posts-placeholder = Search titles and descriptions...
+search-no-results = No drawings matched your search
+search-previous = Previous
+search-next = Next
 no-hashtags-found = No hashtags found
 sort-by = Sort by
 sort-trending = Trending
@@ -211,6 +320,8 @@ collaborate-max-participants-label = Max Participants
 collaborate-participants = participants
 collaborate-public-session-label = Show in public lobby
 collaborate-public-session-description = Uncheck to create a private session (link-only access)
+collaborate-record-replay-label = Record a replay
+collaborate-record-replay-description = The saved drawing will include a replay of everyone's strokes
 collaborate-create-button = Create Session
 collaborate-active-sessions = Active Public Sessions
 collaborate-no-sessions = No active public sessions. Create one to get started!
@@ -255,17 +366,41 @@ community-badge-unlisted = Unlisted
 community-badge-private = Private
 
 community-members = Members
-community-invite-user = Invite user
-community-invite-username = Username to invite
+community-invite-user = Invite users
+community-invite-username = Usernames to invite
+community-invite-username-hint = Separate several usernames with commas or spaces
 community-invite-button = Send invitation
-community-invite-success = Invitation sent successfully
+community-invite-invited-users = Invitations sent to { $names }
 community-invite-error = Failed to send invitation
 community-invite-user-not-found = User not found
-community-invite-already-member = User is already a member
-community-invite-already-invited = User already has a pending invitation
+community-invite-users-already-members = Already members: { $names }
+community-invite-users-already-invited = Already have a pending invitation: { $names }
+community-invite-users-not-found = Users not found: { $names }
+community-invite-too-many = You can invite at most { $max } users at once
 private-community-no-access = You do not have access to this private community.
 community-remove-member = Remove member
 community-remove-member-confirm = Are you sure you want to remove this member?
+community-member-promote = Make moderator
+community-member-demote = Remove moderator
+community-join-requests = Join requests
+community-join-requests-pending = Pending join requests
+community-accepts-join-requests = Accept join requests
+community-accepts-join-requests-description = Logged-in users can ask to join. Owners and moderators approve or reject each request from the members page.
+community-join-request-button = Request to join
+community-join-request-pending = Your request to join is waiting for review.
+community-join-request-sent = Your request to join has been sent.
+community-join-request-requested-at = Requested
+community-join-request-approve = Approve
+community-join-request-reject = Reject
+community-join-request-reject-confirm = Are you sure you want to reject this join request?
+community-post-approval = Post Approval
+community-require-post-approval = Review new posts before they are published
+community-require-post-approval-description = Posts by members wait in a review queue on the community page until the owner or a moderator approves them. Owners and moderators publish directly.
+community-pending-posts = Posts Awaiting Review
+community-pending-posts-description = Only you and the other moderators can see these posts until they are approved.
+community-pending-post-approve = Approve
+community-pending-post-reject = Reject
+community-pending-post-reject-confirm = Reject this post? It will go back to the author's drafts.
 community-retract-invitation = Cancel invitation
 community-retract-invitation-confirm = Are you sure you want to retract this invitation?
 community-member-role = Role
@@ -277,6 +412,55 @@ community-leave = Leave Community
 community-leave-confirm = Are you sure you want to leave this community?
 community-left-success = You have left the community
 community-owner-cannot-leave = Owners cannot leave. Delete the community instead.
+community-transfer = Transfer ownership
+community-transfer-description = The new owner must confirm by following a link sent to their verified email address. You will stay on as a moderator.
+community-transfer-button = Propose transfer
+community-transfer-confirm = Transfer ownership of this community?
+community-transfer-pending = Waiting for confirmation from
+community-transfer-cancel = Cancel transfer
+community-transfer-sent = A confirmation link has been emailed to the proposed owner.
+community-transfer-no-email = This user has no verified email address.
+community-transfer-accept = Accept ownership
+community-transfer-accept-description = You have been asked to become the owner of this community.
+community-transfer-confirmed = You are now the owner of this community.
+community-transfer-cancelled = The ownership transfer has been cancelled.
+community-transfer-email-subject = { $owner } wants to hand over { $community } to you
+community-transfer-email-body = { $owner } has proposed that you become the owner of the community { $community }. Open the link below to accept or decline. The link expires in 72 hours.
+community-invite-links = Invite links
+community-invite-links-description = Anyone with a link can join this community as a member. Leave a field empty for no limit.
+community-invite-link-max-uses = Maximum uses
+community-invite-link-expires-in-days = Expires after (days)
+community-invite-link-create = Create invite link
+community-invite-link = Link
+community-invite-link-uses = Uses
+community-invite-link-expires-at = Expires
+community-invite-link-inactive = inactive
+community-invite-link-never-expires = Never
+community-invite-link-revoke = Revoke
+community-invite-link-revoke-confirm = Revoke this invite link?
+community-invite-link-invalid = Enter a positive number of uses and at most 365 days.
+community-invite-link-created = The invite link has been created.
+community-invite-link-unusable = This invite link has expired or has reached its use limit.
+community-invite-link-joined = You have joined the community.
+community-invite-link-join-title = Join community
+community-invite-link-already-member = You are already a member of this community.
+community-invite-link-join-description = You have been invited to join this community.
+community-invite-link-join = Join
+community-invite-link-login-to-join = Log in to join
+community-archived-notice = This community has been archived after a long period without new posts. It no longer appears in community listings.
+community-unarchive-button = Restore community
+community-archive-email-subject = { $community } will be archived soon
+community-archive-email-body = The community { $community } has had no new posts for a long time and will be archived in { $days } days. Archived communities no longer appear in community listings. Publishing a post before then keeps it active, and you can restore it from the community page at any time.
+community-sensitive = Sensitive community
+community-sensitive-description = All posts in this community are treated as sensitive. Visitors must confirm before entering.
+community-sensitive-interstitial = This community contains sensitive content. Do you want to continue?
+community-sensitive-interstitial-settings = To skip this warning, enable showing sensitive content in your
+community-sensitive-enter = Enter
+community-sensitive-leave = Go back
+community-pinned-posts = Pinned posts
+community-pin-post = Pin
+community-unpin-post = Unpin
+community-federated-posts = From the fediverse
 
 community-delete-danger-zone = Danger Zone
 community-delete-warning = Deleting a community is permanent and cannot be undone. All posts in this community will also be deleted.
@@ -294,6 +478,8 @@ invitation-reject = Reject
 invitation-accepted = Invitation accepted
 invitation-rejected = Invitation rejected
 invitation-sent-at = Sent
+invitation-expires-at = Expires
+invitation-expires-soon = expiring soon
 
 community-drawing-tool = Tool
 community-drawing-width = Width
@@ -305,6 +491,18 @@ community-drawing-portrait = Portrait
 community-no-posts = No posts.
 community-drawing-post-error = There was an error while submitting. Try again.
 community-slug-conflict-error = A community with this ID already exists. Please choose a different ID.
+community-posting-window = Posting hours
+community-posting-window-description = Members can only publish during this daily window. Owners and moderators can always post. Leave both times empty to allow posting at any time.
+community-posting-window-timezone = Timezone
+community-posting-window-opens-at = Opens at
+community-posting-window-closes-at = Closes at
+community-posting-window-invalid = Please enter a valid timezone and two different times.
+community-default-hashtags = Default hashtags
+community-default-hashtags-description = These hashtags are added to every post published in this community. Authors can remove them by editing the post.
+community-categories = Categories
+community-categories-description = Choose the categories your community is listed under on the communities page.
+community-categories-all = All
+community-posting-window-closed = This community is not accepting posts right now. Posting opens at { $opens_at }.
 
 profile-link = Links
 profile-manage = Manage
@@ -316,7 +514,14 @@ profile-public-community-posts = Posts from public communities
 profile-public-community-posts-nil = There are no posts from public communities.
 profile-private-community-posts = Posts from private communities (Not shown in public profile)
 profile-private-community-posts-nil = There are no posts from private communities.
+profile-pinned-posts = Pinned
+profile-pin-post = Pin to profile
+profile-unpin-post = Unpin from profile
+profile-pin-limit-reached = You can pin up to 5 posts.
 profile-settings = Profile settings
+profile-avatar = Avatar
+profile-avatar-upload = Upload avatar
+profile-avatar-help = PNG or JPEG, up to 1 MB.
 profile-link-management = Manage links
 profile-link-requires-verified-email = You can add links after you verify your email.
 profile-link-order = Order
@@ -384,19 +589,82 @@ account-change-email-verify = Verify email
 account-change-email-error-token-mismatch = The verification code is incorrect.
 account-change-email-error-token-expired = The verification code is expired.
 account-change-email-error-already-verified = This email is already verified.
+account-change-email-error-domain-blocked = This email domain cannot be used.
 account-change-email-success = Email verified!
 account-change-email-subject = Oeee Cafe email verification code
+account-export-email-subject = Your Oeee Cafe data export is ready
+account-export-email-body = Your data export is ready. Download it from the link below within 24 hours:
+community-export-email-subject = Your export of { $community } is ready
 account-delete = Delete Account
-account-delete-warning = Deleting your account will permanently remove all your data. This action cannot be undone. If you own any communities, you must transfer ownership or delete them first.
-account-delete-confirm = Are you sure you want to delete your account? This action cannot be undone.
+account-delete-warning = Your account will be deleted after { $days } days. Until then you can restore it by signing in on the account restore page. After that, all your data is removed permanently. If you own any communities, you must transfer ownership or delete them first.
+account-delete-details = You will be signed out everywhere and your published posts will be hidden right away, and servers following you will be told your account is gone. Once the deletion is final, your posts and comments will be removed and your drawings and replays will be deleted from storage.
+account-delete-cancel = Keep my account
+account-delete-confirm = Are you sure you want to delete your account?
 account-delete-button = Delete Account
+account-delete-scheduled = Your account will be deleted in { $days } days. You can restore it until then.
+account-delete-email-subject = Your account is scheduled for deletion
+account-delete-email-body = Your account will be deleted on { $date }. If you change your mind, you can restore it before then at the link below.
+account-delete-restore = Restore Account
+account-delete-restore-description = Sign in to restore an account that is scheduled for deletion. Your hidden posts will be visible again.
+account-delete-restore-button = Restore Account
+account-delete-restore-totp = Only needed if the account has two-factor authentication.
+account-delete-cancelled = Your account has been restored. You can sign in again.
+account-delete-cancel-failed = No account awaiting deletion matches that login name and password.
 account-language-settings = Language settings
 account-language-auto = Auto
 account-language-current = Current language
+account-language-fallbacks = Fallback languages
+account-language-fallbacks-help = Comma-separated language codes (ko, ja, en, zh, es) to try, in order, when a message is missing in your language. English is always used last.
 account-language-settings-save = Save language
 account-content-settings = Content settings
 account-show-sensitive-content = Show sensitive content
 account-content-settings-save = Save content settings
+account-feed-settings = Home feed
+account-feed-algorithm-chronological = Newest first
+account-feed-algorithm-engagement = Most engaging first
+account-feed-algorithm-following-first = People I follow first
+account-feed-settings-save = Save feed settings
+account-feed-settings-saved = Feed settings saved.
+account-follower-settings = Followers
+account-is-locked = Approve new followers manually
+account-is-locked-help = Followers from other servers have to be approved before they can follow you.
+account-follower-settings-save = Save follower settings
+follow-requests = Follow requests
+follow-requests-none = You have no pending follow requests
+follow-request-accept = Accept
+follow-request-reject = Reject
+follow-request-accepted = Follow request accepted
+follow-request-rejected = Follow request rejected
+account-default-co-moderators = Default co-moderators
+account-default-co-moderators-description = These users become moderators of every community you create. Private communities add them right away; other communities send them a moderator invitation.
+account-default-co-moderators-login-names = Login names (separated by spaces or commas)
+account-default-co-moderators-save = Save co-moderators
+account-notification-preferences = Push notifications
+account-notification-preferences-description = Choose which notifications are sent to your devices.
+account-notification-preferences-save = Save notification settings
+account-notification-preferences-saved = Notification settings saved
+totp = Two-factor authentication
+totp-description = Require a code from an authenticator app in addition to your password when signing in.
+totp-enable = Set up two-factor authentication
+totp-disable = Disable two-factor authentication
+totp-status-enabled = Two-factor authentication is enabled. Enter your password to disable it.
+totp-setup-scan = Scan this QR code with your authenticator app, then enter the 6-digit code it shows.
+totp-setup-manual = Or enter this key manually:
+totp-code = Authentication code
+totp-verify = Verify
+totp-challenge-description = Enter the 6-digit code from your authenticator app, or one of your backup codes.
+totp-backup-codes = Backup codes
+totp-backup-codes-description = Store these codes somewhere safe. Each can be used once to sign in if you lose access to your authenticator app. They will not be shown again.
+totp-enabled = Two-factor authentication has been enabled.
+totp-disabled = Two-factor authentication has been disabled.
+totp-error-invalid-code = The authentication code is incorrect.
+totp-error-unavailable = Two-factor authentication is not available on this server.
+oauth-authorize-title = Authorize app
+oauth-authorize-description = { $client } wants to access your account. It will be able to do anything you can do while signed in.
+oauth-authorize-approve = Authorize
+oauth-authorize-deny = Deny
+account-default-co-moderators-user-not-found = User @{ $login_name } does not exist.
+account-default-co-moderators-self = You cannot add yourself as a co-moderator.
 
 cucumber-save = Save
 cucumber-undo = Undo
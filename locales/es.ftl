@@ -0,0 +1,706 @@
+brand = Oeee Cafe
+email-from-address = Oeee Cafe <noreply@oeee.cafe>
+error-404 = Página no encontrada.
+error-410 = Esta página ha sido eliminada.
+error-403 = No tienes permiso para acceder a esta página.
+
+about = Oeee Cafe es un tablón de oekaki.
+user-contributors = Usuarios que hicieron Oeee Cafe juntos
+
+timeline = Cronología
+timeline-public = Cronología pública
+timeline-my = Mi cronología
+timeline-empty = Tu cronología está vacía. ¡Descubre y sigue a otras personas en las <a href="/communities">comunidades</a>!
+
+home = Inicio
+draw = ¡Dibujar!
+collaborate = Colaborar
+drafts = Borradores
+profile = Perfil
+community = Comunidad
+notifications = Notificaciones
+
+no-notifications = Todavía no hay notificaciones.
+notification-comment = Nuevo comentario
+notification-reaction = Nueva reacción
+notification-follow = Nuevo seguidor
+notification-guestbook-entry = Nueva entrada en el libro de visitas
+notification-guestbook-reply = Nueva respuesta en el libro de visitas
+notification-mention = Mención
+notification-post-reply = Respuesta a publicación
+notification-comment-reply = Respuesta a comentario
+notification-community-post = Nueva publicación en la comunidad
+notification-follower-moved = Seguidor mudado
+
+# Notification action messages with FluentArgs for proper word order in different languages
+notification-action-commented-on = comentó en { $postTitle }
+notification-action-reacted-to-post = reaccionó con { $emoji } a { $postTitle }
+notification-action-followed-you = empezó a seguirte
+notification-action-wrote-guestbook = escribió en tu libro de visitas
+notification-action-replied-guestbook = respondió a tu entrada del libro de visitas
+notification-action-mentioned-in-comment = te mencionó en un comentario en { $postTitle }
+notification-action-replied-to-post = respondió a tu publicación
+notification-action-replied-to-post-with-title = respondió a tu publicación { $postTitle }
+notification-action-replied-to-comment = respondió a tu comentario en { $postTitle }
+notification-action-posted-in-community = publicó en tu comunidad
+notification-action-posted-in-community-with-title = publicó en tu comunidad: { $postTitle }
+notification-action-follower-moved = se mudó a una cuenta nueva, que ahora te sigue en su lugar
+
+notification-mark-all-read = Marcar todo como leído
+notification-filter-all = Todas
+loading-more-notifications = Cargando más notificaciones...
+notification-mark-read = Marcar como leída
+notification-and-others = { $count ->
+    [one] y 1 más
+   *[other] y { $count } más
+}
+notification-delete = Eliminar
+notification-delete-confirm = ¿Seguro que quieres eliminar esta notificación?
+
+# Push notification messages with variables
+push-notification-comment-title = Nuevo comentario de { $name }
+push-notification-comment-body = { $name } comentó en tu publicación
+push-notification-reaction-title = { $name } reaccionó a tu publicación
+push-notification-reaction-body = { $name } reaccionó con { $emoji }
+push-notification-follow-title = Nuevo seguidor
+push-notification-follow-body = { $name } empezó a seguirte
+push-notification-guestbook-entry-title = { $name } firmó tu libro de visitas
+push-notification-guestbook-reply-title = { $name } respondió a tu entrada del libro de visitas
+push-notification-mention-title = { $name } te mencionó
+push-notification-mention-body = { $name } te mencionó en un comentario
+push-notification-post-reply-title = { $name } respondió a tu publicación
+push-notification-comment-reply-title = { $name } respondió a tu comentario
+push-notification-comment-reply-body = { $name } respondió a tu comentario
+push-notification-community-post-title = Nueva publicación en la comunidad
+push-notification-community-post-body = { $name } publicó en tu comunidad
+push-notification-community-post-body-with-title = { $name } publicó en tu comunidad: { $title }
+push-notification-follower-moved-title = Seguidor mudado
+push-notification-follower-moved-body = { $name } se mudó a una cuenta nueva, que ahora te sigue en su lugar
+push-notification-community-invite-title = Invitación a una comunidad
+push-notification-community-invite-body = { $inviter } te invitó a unirte a @{ $community }
+push-notification-invite-accepted-title = Invitación aceptada
+push-notification-invite-accepted-body = { $accepter } aceptó tu invitación para unirse a @{ $community }
+push-notification-invite-declined-title = Invitación rechazada
+push-notification-invite-declined-body = { $decliner } rechazó tu invitación para unirse a @{ $community }
+push-notification-join-request-approved-title = Solicitud aprobada
+push-notification-join-request-approved-body = Ahora eres miembro de @{ $community }
+push-notification-join-request-rejected-title = Solicitud rechazada
+push-notification-join-request-rejected-body = Tu solicitud para unirte a @{ $community } fue rechazada
+push-notification-post-approved-title = Publicación aprobada
+push-notification-post-approved-body = Tu publicación en @{ $community } ya está publicada
+push-notification-post-rejected-title = Publicación no aprobada
+push-notification-post-rejected-body = Tu publicación en @{ $community } no fue aprobada y volvió a tus borradores
+
+sign-in = Iniciar sesión
+sign-up = Registrarse
+sign-out = Cerrar sesión
+
+message-incorrect-credentials = Credenciales incorrectas.
+message-too-many-attempts = Demasiados intentos. Inténtalo de nuevo más tarde.
+login-name-conflict-error = Este nombre de usuario ya lo usa una comunidad. Elige otro nombre de usuario.
+
+forgot-password = ¿Olvidaste tu contraseña?
+
+password-reset-title = Restablecer contraseña
+password-reset-description = Introduce tu dirección de correo electrónico y te enviaremos un enlace para restablecer tu contraseña.
+password-reset-send-email = Enviar enlace para restablecer la contraseña
+password-reset-sent-title = Enlace enviado
+password-reset-sent-message = Si existe una cuenta con esa dirección de correo, te hemos enviado un enlace para restablecer la contraseña.
+password-reset-sent-email = Correo electrónico
+password-reset-check-email = Revisa tu correo y haz clic en el enlace para restablecer tu contraseña.
+password-reset-verify-title = Nueva contraseña
+password-reset-token = Código de verificación
+password-reset-submit = Restablecer contraseña
+password-reset-success = Tu contraseña se ha restablecido. Ya puedes iniciar sesión con la nueva contraseña.
+password-reset-error-mismatch = Las contraseñas no coinciden.
+password-reset-error-invalid-token = El enlace para restablecer la contraseña no es válido o ha caducado. Solicita uno nuevo.
+password-reset-email-subject = Enlace para restablecer tu contraseña de Oeee Cafe
+password-reset-email-body = Haz clic en el siguiente enlace para restablecer tu contraseña:
+back-to-login = Volver a iniciar sesión
+
+account = Cuenta
+email-not-verified = Correo no verificado
+email = Correo electrónico
+login-name = Nombre de usuario
+password = Contraseña
+password-repeat = Contraseña (de nuevo)
+display-name = Nombre visible
+
+participating-community = Comunidades en las que participas
+latest-active-public-community = Comunidades públicas con actividad reciente
+recent-drawings = Publicaciones recientes
+recent-comments = Comentarios recientes
+recent-comments-from-public-communities = Comentarios recientes de comunidades públicas
+view-all-comments = Ver todos los comentarios
+no-comments-yet = Todavía no hay comentarios
+
+community-stats = Estadísticas de la comunidad
+community-stats-posts = publicaciones
+community-stats-contributors = participantes
+community-stats-comments = comentarios
+community-leaderboard = Principales participantes
+community-leaderboard-week = Esta semana
+community-leaderboard-month = Este mes
+community-leaderboard-all-time = Siempre
+community-leaderboard-score = { $score } puntos
+community-leaderboard-counts = { $posts } publicaciones · { $reactions } reacciones · { $comments } comentarios recibidos
+community-leaderboard-your-rank = Tu posición: n.º { $rank } ({ $score } puntos)
+community-leaderboard-empty = Aún no hay participaciones en este periodo.
+
+edit = Editar
+comment-edited = (editado)
+edit-community-button = Mover a una comunidad
+save = Guardar
+cancel = Cancelar
+delete = Eliminar
+
+post-created-at = Creado
+post-published-at = Publicado
+post-edited = Editado
+post-history = Historial de ediciones
+post-history-empty = Esta publicación no ha sido editada.
+post-duration = Duración
+post-replay = Repetición
+post-author = Autor
+post-collaborative-participants = Participantes del dibujo colaborativo
+post-title = Título
+post-no-title = Sin título
+post-description = Descripción
+post-comments = Comentarios
+post-no-comments = No hay comentarios.
+post-no-comments-signin = Inicia sesión para añadir un comentario.
+post-comment = Publicar comentario
+post-publish = Publicar
+post-schedule = Programar
+post-scheduled-for = Programado para
+post-schedule-hint = Elige una hora y pulsa Programar para publicar más tarde. Las horas están en KST.
+post-submitted-for-approval = Tu publicación se envió. Aparecerá cuando el propietario o un moderador de la comunidad la apruebe.
+post-pending-approval = Enviada a revisión
+post-move-community = Mover a esta comunidad
+post-move-community-title = Mover la publicación a una comunidad
+post-move-post-to-move = Publicación que se moverá
+post-current-community = Comunidad actual
+post-move-search-placeholder = Buscar comunidades...
+post-move-available = Comunidades disponibles
+post-move-confirm = ¿Seguro que quieres mover esta publicación a «{ $communityName }»?
+post-move-no-results = No se encontraron comunidades que coincidan con la búsqueda.
+post-personal-post = Publicación personal
+post-personal-post-description = Mover esta publicación a tu perfil personal (fuera de cualquier comunidad)
+post-move-confirm-personal = ¿Seguro que quieres mover esta publicación a tu perfil personal?
+post-move-to-personal = Mover a personal
+move-section-unlisted = Comunidades en las que has publicado
+move-section-public-participated = Comunidades públicas en las que has publicado
+move-section-public-other = Otras comunidades públicas
+post-delete-confirm = ¿Seguro que quieres eliminar esta publicación?
+post-relay-enabled-notice = Puedes seguir dibujando en este lienzo. ¡Haz clic en la imagen!
+post-relay = Relevo
+post-reply-with-drawing = Responder con un dibujo
+post-translate = Traducir
+post-translation-failed = No se pudo traducir. Inténtalo de nuevo más tarde.
+post-reply-select-tool = Elige la herramienta y el tamaño del lienzo
+post-child-posts = Respuestas
+post-replying-to = En respuesta a:
+post-hashtags = Etiquetas
+post-hashtags-placeholder = arte, dibujo, boceto
+post-hashtags-hint = Separa las etiquetas con comas o espacios
+post-reaction = reacción
+post-reactions = reacciones
+post-share = Compartir
+post-share-copied = ¡Enlace copiado al portapapeles!
+post-boost = Impulsar
+post-unboost = Deshacer impulso
+report-object = Denunciar
+report-object-comment = ¿Qué problema tiene? (opcional)
+report-object-submit = Enviar denuncia
+report-object-sent = Denunciado. Gracias.
+post-report = Denunciar
+post-report-title = Denunciar esta publicación
+post-report-description-label = Motivo de la denuncia
+post-report-description-placeholder = Describe por qué denuncias esta publicación...
+post-report-submit = Enviar denuncia
+post-report-success = Publicación denunciada. Gracias por ayudar a mantener segura nuestra comunidad.
+post-report-error = No se pudo enviar la denuncia. Inténtalo de nuevo.
+post-report-confirm = ¿Seguro que quieres denunciar esta publicación?
+post-report-reason-label = Categoría
+post-report-reason-spam = Spam
+post-report-reason-harassment = Acoso
+post-report-reason-sexual-content = Contenido sexual
+post-report-reason-violence = Violencia
+post-report-reason-copyright = Infracción de derechos de autor
+post-report-reason-other = Otro
+admin-reports = Denuncias
+admin-reports-empty = No hay denuncias pendientes.
+admin-reports-reporter = Denunciante
+admin-reports-reason = Motivo
+admin-reports-detail = Detalle
+admin-reports-post = Publicación
+admin-reports-reported-at = Fecha de la denuncia
+admin-reports-resolve = Resolver
+admin-reports-dismiss = Descartar
+admin-reports-objects = Comentarios y contenido federado denunciados
+admin-reports-objects-targets = Objetos denunciados
+admin-reports-objects-remote = de otra instancia
+admin-announcements = Avisos
+admin-announcements-new = Nuevo aviso
+admin-announcements-message = Mensaje
+admin-announcements-level = Nivel
+admin-announcements-level-info = Información
+admin-announcements-level-warning = Advertencia
+admin-announcements-level-critical = Crítico
+admin-announcements-starts-at = Empieza
+admin-announcements-ends-at = Termina
+admin-announcements-time-help = Las horas están en KST. Deja vacío el inicio para mostrarlo de inmediato, y el final para mantenerlo hasta que se elimine.
+admin-announcements-dismissible = Los usuarios pueden cerrarlo
+admin-announcements-create = Crear aviso
+admin-announcements-delete = Eliminar
+admin-announcements-empty = No hay avisos.
+admin-announcements-content-help = El texto de cada idioma es opcional; en los idiomas vacíos se muestra el mensaje.
+admin-announcements-content-ko = Coreano
+admin-announcements-content-en = Inglés
+admin-announcements-content-ja = Japonés
+admin-announcements-content-zh = Chino
+admin-announcements-active = Activo
+admin-announcements-activate = Activar
+admin-announcements-deactivate = Desactivar
+admin-community-categories = Categorías de comunidades
+admin-community-categories-new = Nueva categoría
+admin-community-categories-name = Nombre
+admin-community-categories-slug = Slug
+admin-community-categories-position = Orden
+admin-community-categories-help = El slug aparece en los enlaces y solo admite a-z, 0-9 y -. Las categorías con un orden menor se muestran primero.
+admin-community-categories-create = Crear categoría
+admin-community-categories-delete = Eliminar
+admin-community-categories-empty = No hay categorías.
+announcement-dismiss = Cerrar
+
+allow-relay = Permitir publicaciones de relevo
+sensitive = Sensible
+draft-post = Borrador
+
+# Draft Management
+draft-posts-count = borradores
+draft-empty = Todavía no tienes borradores
+
+# Hashtags
+trending-hashtags = Etiquetas en tendencia
+trending-hashtags-description = Etiquetas usadas en más dibujos durante las últimas 24 horas
+trending-hashtags-updated = Actualizado
+trending-hashtags-delta = Dibujos de la última hora comparados con la hora anterior
+trending-hashtags-empty = No hay nada en tendencia ahora mismo
+hashtag-post-count = publicaciones
+hashtag-no-posts = Todavía no hay publicaciones con esta etiqueta
+hashtag-follow = Seguir
+hashtag-unfollow = Dejar de seguir
+hashtag-following = Etiquetas seguidas
+hashtag-following-empty = Todavía no sigues ninguna etiqueta
+hashtag-discovery = Descubrir etiquetas
+hashtag-search-placeholder = Buscar etiquetas...
+search = Buscar
+search-results-for = Resultados de búsqueda para
+search-posts = Buscar dibujos
+search-posts-placeholder = Buscar en títulos y descripciones...
+search-no-results = Ningún dibujo coincide con la búsqueda
+search-previous = Anterior
+search-next = Siguiente
+no-hashtags-found = No se encontraron etiquetas
+sort-by = Ordenar por
+sort-trending = En tendencia
+sort-popular = Populares
+sort-recent = Recientes
+sort-alphabetical = A-Z
+
+# Collaborative drawing
+collaborate-title = Sesiones de dibujo colaborativo
+collaborate-create-session = Crear una sesión
+collaborate-community-label = Comunidad
+collaborate-canvas-size-label = Tamaño del lienzo
+collaborate-session-title-label = Título de la sesión (opcional)
+collaborate-session-title-placeholder = Escribe un título para tu sesión
+collaborate-max-participants-label = Máximo de participantes
+collaborate-participants = participantes
+collaborate-public-session-label = Mostrar en la sala pública
+collaborate-public-session-description = Desmárcalo para crear una sesión privada (acceso solo con el enlace)
+collaborate-record-replay-label = Grabar una repetición
+collaborate-record-replay-description = El dibujo guardado incluirá una repetición de los trazos de todos
+collaborate-create-button = Crear sesión
+collaborate-active-sessions = Sesiones públicas activas
+collaborate-no-sessions = No hay sesiones públicas activas. ¡Crea una para empezar!
+collaborate-session-by = por @
+collaborate-session-participants-singular = 1 participante
+collaborate-session-participants-plural = participantes
+collaborate-session-created = Creada
+collaborate-join-session = Unirse a la sesión
+
+welcome = ¡Te damos la bienvenida, { $name }!
+
+comment-created-at = Comentado el
+
+my-communities = Mis comunidades
+my-communities-nil = No has creado ninguna comunidad.
+
+latest-active-communities = Comunidades con actividad reciente
+posts-from-public-communities = Publicaciones de comunidades públicas
+official-communities = Comunidades oficiales
+official-communities-nil = No hay comunidades oficiales.
+active-communities-nil = No hay comunidades activas.
+no-active-communities = No hay comunidades activas en este momento.
+no-posts-in-community = Todavía no hay publicaciones en esta comunidad.
+loading-more-posts = Cargando más publicaciones...
+create-community = Crear comunidad
+new-community = Nueva comunidad
+community-name = Nombre de la comunidad
+community-description = Descripción de la comunidad
+private-community = Comunidad privada
+private-community-description = Solo quien tenga el enlace de la comunidad podrá encontrarla.
+
+community-visibility = Visibilidad
+visibility-public = Pública
+visibility-public-description = Cualquiera puede encontrar y ver esta comunidad
+visibility-unlisted = No listada
+visibility-unlisted-description = Solo quien tenga el enlace puede encontrarla
+visibility-private = Privada
+visibility-private-description = Solo los miembros pueden verla
+visibility-cannot-change = Las comunidades privadas no pueden cambiar de visibilidad
+
+community-badge-unlisted = No listada
+community-badge-private = Privada
+
+community-members = Miembros
+community-invite-user = Invitar usuarios
+community-invite-username = Nombres de usuario a invitar
+community-invite-username-hint = Separa varios nombres de usuario con comas o espacios
+community-invite-button = Enviar invitación
+community-invite-invited-users = Invitaciones enviadas a { $names }
+community-invite-error = No se pudo enviar la invitación
+community-invite-user-not-found = Usuario no encontrado
+community-invite-users-already-members = Ya son miembros: { $names }
+community-invite-users-already-invited = Ya tienen una invitación pendiente: { $names }
+community-invite-users-not-found = Usuarios no encontrados: { $names }
+community-invite-too-many = Puedes invitar como máximo a { $max } usuarios a la vez
+private-community-no-access = No tienes acceso a esta comunidad privada.
+community-remove-member = Expulsar miembro
+community-remove-member-confirm = ¿Seguro que quieres expulsar a este miembro?
+community-member-promote = Hacer moderador
+community-member-demote = Quitar moderador
+community-join-requests = Solicitudes de ingreso
+community-join-requests-pending = Solicitudes de ingreso pendientes
+community-accepts-join-requests = Aceptar solicitudes de ingreso
+community-accepts-join-requests-description = Los usuarios con sesión iniciada pueden pedir unirse. El propietario y los moderadores aprueban o rechazan cada solicitud desde la página de miembros.
+community-join-request-button = Solicitar unirse
+community-join-request-pending = Tu solicitud para unirte está pendiente de revisión.
+community-join-request-sent = Se ha enviado tu solicitud para unirte.
+community-join-request-requested-at = Solicitado
+community-join-request-approve = Aprobar
+community-join-request-reject = Rechazar
+community-join-request-reject-confirm = ¿Seguro que quieres rechazar esta solicitud de ingreso?
+community-post-approval = Aprobación de publicaciones
+community-require-post-approval = Revisar las publicaciones nuevas antes de publicarlas
+community-require-post-approval-description = Las publicaciones de los miembros esperan en una cola de revisión en la página de la comunidad hasta que el propietario o un moderador las apruebe. El propietario y los moderadores publican directamente.
+community-pending-posts = Publicaciones pendientes de revisión
+community-pending-posts-description = Solo el propietario y los moderadores pueden ver estas publicaciones hasta que se aprueben.
+community-pending-post-approve = Aprobar
+community-pending-post-reject = Rechazar
+community-pending-post-reject-confirm = ¿Rechazar esta publicación? Volverá a los borradores del autor.
+community-retract-invitation = Cancelar invitación
+community-retract-invitation-confirm = ¿Seguro que quieres retirar esta invitación?
+community-member-role = Rol
+community-member-joined = Se unió
+community-member-role-owner = Propietario
+community-member-role-moderator = Moderador
+community-member-role-member = Miembro
+community-leave = Abandonar la comunidad
+community-leave-confirm = ¿Seguro que quieres abandonar esta comunidad?
+community-left-success = Has abandonado la comunidad
+community-owner-cannot-leave = El propietario no puede abandonar la comunidad. Elimínala en su lugar.
+community-transfer = Transferir la propiedad
+community-transfer-description = El nuevo propietario debe confirmarlo mediante un enlace enviado a su correo verificado. Tú seguirás como moderador.
+community-transfer-button = Proponer transferencia
+community-transfer-confirm = ¿Transferir la propiedad de esta comunidad?
+community-transfer-pending = Esperando la confirmación de
+community-transfer-cancel = Cancelar transferencia
+community-transfer-sent = Se ha enviado un enlace de confirmación al propietario propuesto.
+community-transfer-no-email = Este usuario no tiene un correo verificado.
+community-transfer-accept = Aceptar la propiedad
+community-transfer-accept-description = Te han pedido que seas el propietario de esta comunidad.
+community-transfer-confirmed = Ahora eres el propietario de esta comunidad.
+community-transfer-cancelled = Se ha cancelado la transferencia de propiedad.
+community-transfer-email-subject = { $owner } quiere cederte { $community }
+community-transfer-email-body = { $owner } ha propuesto que seas el propietario de la comunidad { $community }. Abre el siguiente enlace para aceptar o rechazar. El enlace caduca en 72 horas.
+community-invite-links = Enlaces de invitación
+community-invite-links-description = Cualquiera con un enlace puede unirse a esta comunidad como miembro. Deja un campo vacío para no poner límite.
+community-invite-link-max-uses = Usos máximos
+community-invite-link-expires-in-days = Caduca tras (días)
+community-invite-link-create = Crear enlace de invitación
+community-invite-link = Enlace
+community-invite-link-uses = Usos
+community-invite-link-expires-at = Caduca
+community-invite-link-inactive = inactivo
+community-invite-link-never-expires = Nunca
+community-invite-link-revoke = Revocar
+community-invite-link-revoke-confirm = ¿Revocar este enlace de invitación?
+community-invite-link-invalid = Introduce un número de usos positivo y como máximo 365 días.
+community-invite-link-created = Se ha creado el enlace de invitación.
+community-invite-link-unusable = Este enlace de invitación ha caducado o ha alcanzado su límite de usos.
+community-invite-link-joined = Te has unido a la comunidad.
+community-invite-link-join-title = Unirse a la comunidad
+community-invite-link-already-member = Ya eres miembro de esta comunidad.
+community-invite-link-join-description = Te han invitado a unirte a esta comunidad.
+community-invite-link-join = Unirse
+community-invite-link-login-to-join = Inicia sesión para unirte
+community-archived-notice = Esta comunidad se ha archivado tras un largo periodo sin publicaciones nuevas. Ya no aparece en los listados de comunidades.
+community-unarchive-button = Restaurar comunidad
+community-archive-email-subject = { $community } se archivará pronto
+community-archive-email-body = La comunidad { $community } lleva mucho tiempo sin publicaciones nuevas y se archivará dentro de { $days } días. Las comunidades archivadas ya no aparecen en los listados de comunidades. Si publicas algo antes, seguirá activa, y puedes restaurarla desde la página de la comunidad en cualquier momento.
+community-sensitive = Comunidad sensible
+community-sensitive-description = Todas las publicaciones de esta comunidad se tratan como sensibles. Los visitantes deben confirmar antes de entrar.
+community-sensitive-interstitial = Esta comunidad contiene contenido sensible. ¿Quieres continuar?
+community-sensitive-interstitial-settings = Para omitir esta advertencia, activa la opción de mostrar contenido sensible en tu
+community-sensitive-enter = Entrar
+community-sensitive-leave = Volver
+community-pinned-posts = Publicaciones fijadas
+community-pin-post = Fijar
+community-unpin-post = Desfijar
+community-federated-posts = Desde el fediverso
+
+community-delete-danger-zone = Zona de peligro
+community-delete-warning = Eliminar una comunidad es permanente y no se puede deshacer. También se eliminarán todas las publicaciones de la comunidad.
+community-delete-confirm-prompt = Escribe el ID de la comunidad para confirmar la eliminación
+community-delete-final-confirm = ¿Seguro que quieres eliminar esta comunidad? Esta acción no se puede deshacer.
+community-delete-button = Eliminar comunidad
+
+invitations = Invitaciones
+invitations-pending = Invitaciones pendientes
+invitations-none = No tienes invitaciones pendientes
+invitation-from = Invitación de
+invitation-to-community = para unirte a
+invitation-accept = Aceptar
+invitation-reject = Rechazar
+invitation-accepted = Invitación aceptada
+invitation-rejected = Invitación rechazada
+invitation-sent-at = Enviada
+invitation-expires-at = Caduca
+invitation-expires-soon = caduca pronto
+
+community-drawing-tool = Herramienta
+community-drawing-width = Ancho
+community-drawing-height = Alto
+community-drawing-new = Nueva publicación
+community-drawing-orientation = Orientación
+community-drawing-landscape = Horizontal
+community-drawing-portrait = Vertical
+community-no-posts = No hay publicaciones.
+community-drawing-post-error = Se produjo un error al enviar. Inténtalo de nuevo.
+community-slug-conflict-error = Ya existe una comunidad con este ID. Elige otro ID.
+community-posting-window = Horario de publicación
+community-posting-window-description = Los miembros solo pueden publicar dentro de este horario diario. Los propietarios y moderadores pueden publicar siempre. Deja ambas horas vacías para permitir publicar en cualquier momento.
+community-posting-window-timezone = Zona horaria
+community-posting-window-opens-at = Abre a las
+community-posting-window-closes-at = Cierra a las
+community-posting-window-invalid = Introduce una zona horaria válida y dos horas distintas.
+community-default-hashtags = Hashtags predeterminados
+community-default-hashtags-description = Estos hashtags se añaden a cada publicación de esta comunidad. Los autores pueden quitarlos editando la publicación.
+community-categories = Categorías
+community-categories-description = Elige las categorías en las que aparece tu comunidad en la página de comunidades.
+community-categories-all = Todas
+community-posting-window-closed = Esta comunidad no acepta publicaciones ahora mismo. Se podrá publicar a partir de las { $opens_at }.
+
+profile-link = Enlaces
+profile-manage = Gestionar
+profile-banner = Banners
+profile-draw-banner = Dibujar un banner
+profile-guestbook = Libro de visitas
+profile-following = Siguiendo
+profile-public-community-posts = Publicaciones en comunidades públicas
+profile-public-community-posts-nil = No hay publicaciones en comunidades públicas.
+profile-private-community-posts = Publicaciones en comunidades privadas (no se muestran en el perfil público)
+profile-private-community-posts-nil = No hay publicaciones en comunidades privadas.
+profile-pinned-posts = Fijadas
+profile-pin-post = Fijar en el perfil
+profile-unpin-post = Desfijar del perfil
+profile-pin-limit-reached = Puedes fijar hasta 5 publicaciones.
+profile-settings = Ajustes del perfil
+profile-avatar = Avatar
+profile-avatar-upload = Subir avatar
+profile-avatar-help = PNG o JPEG, hasta 1 MB.
+profile-link-management = Gestionar enlaces
+profile-link-requires-verified-email = Podrás añadir enlaces cuando verifiques tu correo.
+profile-link-order = Orden
+profile-link-move-up = Subir
+profile-link-move-down = Bajar
+profile-link-delete = Eliminar
+profile-link-delete-confirm = ¿Seguro que quieres eliminar este enlace?
+profile-link-add = Añadir
+profile-link-description = Descripción
+profile-report = Denunciar
+profile-report-title = Denunciar este perfil
+profile-report-description-label = Motivo de la denuncia
+profile-report-description-placeholder = Describe por qué denuncias este perfil...
+profile-report-submit = Enviar denuncia
+profile-report-success = Perfil denunciado. Gracias por ayudar a mantener segura nuestra comunidad.
+profile-report-error = No se pudo enviar la denuncia. Inténtalo de nuevo.
+
+banner-management-title = Gestión de banners
+banner-management-description = Gestiona los banners de tu perfil. Elige qué banner se muestra en tu perfil.
+banner-management-link = Gestionar tus banners
+banner-management-no-banners = Todavía no tienes banners.
+banner-management-create-first = Crea tu primer banner
+banner-management-create-new = Crear un banner
+banner-management-created = Creado
+banner-management-active-badge = Activo
+banner-management-set-active = Usar como activo
+banner-management-delete = Eliminar
+banner-management-delete-confirm = ¿Seguro que quieres eliminar este banner? Esta acción no se puede deshacer.
+banner-management-activate-error = No se pudo activar el banner. Inténtalo de nuevo.
+banner-management-delete-error = No se pudo eliminar el banner. No puedes eliminar tu banner activo.
+banner-management-back-to-settings = Volver a los ajustes
+banner-draw-error = Se produjo un error al publicar tu banner. Inténtalo de nuevo.
+
+follow = Seguir
+unfollow = Dejar de seguir
+
+guestbook = Libro de visitas
+guestbook-write = Escribir en el libro de visitas
+guestbook-empty = El libro de visitas está vacío.
+guestbook-delete = Eliminar
+guestbook-delete-confirm = ¿Seguro que quieres eliminar esta entrada del libro de visitas?
+guestbook-reply = Responder
+guestbook-reply-write = Escribir respuesta
+
+account-info = Información de la cuenta
+account-created-at = Se unió el
+account-info-edit = Editar la información de la cuenta
+account-info-edit-success = Has cambiado la información de tu cuenta.
+account-change-password = Cambiar contraseña
+account-change-password-current = Contraseña actual
+account-change-password-new = Nueva contraseña
+account-change-password-new-repeat = Nueva contraseña (de nuevo)
+account-change-password-error-incorrect-current = La contraseña que has introducido es incorrecta.
+account-change-password-error-mismatch = Las contraseñas no coinciden.
+account-change-password-error-new-mismatch = Las nuevas contraseñas no coinciden.
+account-change-password-error-too-short = La contraseña debe tener más de 8 caracteres.
+account-change-password-success = Has cambiado tu contraseña.
+account-change-email = Cambiar correo electrónico
+account-change-email-verified-at = Verificado el
+account-change-email-email = Correo electrónico
+account-verify-email-request = Solicitar verificación del correo
+account-email-not-verified-warning = Tu correo no está verificado. No podrás recuperar tu cuenta si pierdes el acceso a ella.
+account-change-email-token = Código de verificación
+account-change-email-verify = Verificar correo
+account-change-email-error-token-mismatch = El código de verificación es incorrecto.
+account-change-email-error-token-expired = El código de verificación ha caducado.
+account-change-email-error-already-verified = Este correo ya está verificado.
+account-change-email-error-domain-blocked = No se puede usar este dominio de correo.
+account-change-email-success = ¡Correo verificado!
+account-change-email-subject = Código de verificación de correo de Oeee Cafe
+account-export-email-subject = Tu exportación de datos de Oeee Cafe está lista
+account-export-email-body = Tu exportación de datos está lista. Descárgala desde el siguiente enlace en las próximas 24 horas:
+community-export-email-subject = Tu exportación de { $community } está lista
+account-delete = Eliminar cuenta
+account-delete-warning = Tu cuenta se eliminará dentro de { $days } días. Hasta entonces puedes restaurarla iniciando sesión en la página de restauración de cuenta. Después, todos tus datos se borrarán de forma permanente. Si eres propietario de alguna comunidad, primero debes transferir su propiedad o eliminarla.
+account-delete-details = Se cerrará tu sesión en todos los dispositivos, tus publicaciones se ocultarán de inmediato y se avisará a los servidores que te siguen de que tu cuenta ya no existe. Cuando la eliminación sea definitiva, se eliminarán tus publicaciones y comentarios y se borrarán del almacenamiento tus dibujos y repeticiones.
+account-delete-cancel = Conservar mi cuenta
+account-delete-confirm = ¿Seguro que quieres eliminar tu cuenta?
+account-delete-button = Eliminar cuenta
+account-delete-scheduled = Tu cuenta se eliminará dentro de { $days } días. Puedes restaurarla hasta entonces.
+account-delete-email-subject = Tu cuenta está programada para eliminarse
+account-delete-email-body = Tu cuenta se eliminará el { $date }. Si cambias de opinión, puedes restaurarla antes en el enlace de abajo.
+account-delete-restore = Restaurar cuenta
+account-delete-restore-description = Inicia sesión para restaurar una cuenta programada para eliminarse. Tus publicaciones ocultas volverán a mostrarse.
+account-delete-restore-button = Restaurar cuenta
+account-delete-restore-totp = Solo es necesario si la cuenta tiene la autenticación de dos factores activada.
+account-delete-cancelled = Tu cuenta ha sido restaurada. Ya puedes volver a iniciar sesión.
+account-delete-cancel-failed = Ninguna cuenta pendiente de eliminación coincide con ese nombre de usuario y contraseña.
+account-language-settings = Ajustes de idioma
+account-language-auto = Automático
+account-language-current = Idioma actual
+account-language-fallbacks = Idiomas alternativos
+account-language-fallbacks-help = Códigos de idioma separados por comas (ko, ja, en, zh, es) que se probarán, en orden, cuando falte un texto en tu idioma. Siempre se usa el inglés en último lugar.
+account-language-settings-save = Guardar idioma
+account-content-settings = Ajustes de contenido
+account-show-sensitive-content = Mostrar contenido sensible
+account-content-settings-save = Guardar ajustes de contenido
+account-feed-settings = Feed de inicio
+account-feed-algorithm-chronological = Más recientes primero
+account-feed-algorithm-engagement = Más populares primero
+account-feed-algorithm-following-first = Primero las personas que sigo
+account-feed-settings-save = Guardar ajustes del feed
+account-feed-settings-saved = Ajustes del feed guardados.
+account-follower-settings = Seguidores
+account-is-locked = Aprobar manualmente a los nuevos seguidores
+account-is-locked-help = Los seguidores de otros servidores deben ser aprobados antes de poder seguirte.
+account-follower-settings-save = Guardar ajustes de seguidores
+follow-requests = Solicitudes de seguimiento
+follow-requests-none = No tienes solicitudes de seguimiento pendientes
+follow-request-accept = Aceptar
+follow-request-reject = Rechazar
+follow-request-accepted = Solicitud de seguimiento aceptada
+follow-request-rejected = Solicitud de seguimiento rechazada
+account-default-co-moderators = Comoderadores predeterminados
+account-default-co-moderators-description = Estos usuarios serán moderadores de todas las comunidades que crees. Las comunidades privadas los añaden de inmediato; las demás les envían una invitación de moderador.
+account-default-co-moderators-login-names = Nombres de usuario (separados por espacios o comas)
+account-default-co-moderators-save = Guardar comoderadores
+account-notification-preferences = Notificaciones push
+account-notification-preferences-description = Elige qué notificaciones se envían a tus dispositivos.
+account-notification-preferences-save = Guardar ajustes de notificaciones
+account-notification-preferences-saved = Ajustes de notificaciones guardados
+totp = Autenticación en dos pasos
+totp-description = Pide un código de una aplicación de autenticación además de tu contraseña al iniciar sesión.
+totp-enable = Configurar la autenticación en dos pasos
+totp-disable = Desactivar la autenticación en dos pasos
+totp-status-enabled = La autenticación en dos pasos está activada. Introduce tu contraseña para desactivarla.
+totp-setup-scan = Escanea este código QR con tu aplicación de autenticación y luego introduce el código de 6 dígitos que muestra.
+totp-setup-manual = O introduce esta clave manualmente:
+totp-code = Código de autenticación
+totp-verify = Verificar
+totp-challenge-description = Introduce el código de 6 dígitos de tu aplicación de autenticación o uno de tus códigos de respaldo.
+totp-backup-codes = Códigos de respaldo
+totp-backup-codes-description = Guarda estos códigos en un lugar seguro. Cada uno sirve una vez para iniciar sesión si pierdes el acceso a tu aplicación de autenticación. No se volverán a mostrar.
+totp-enabled = Se ha activado la autenticación en dos pasos.
+totp-disabled = Se ha desactivado la autenticación en dos pasos.
+totp-error-invalid-code = El código de autenticación es incorrecto.
+totp-error-unavailable = La autenticación en dos pasos no está disponible en este servidor.
+oauth-authorize-title = Autorizar aplicación
+oauth-authorize-description = { $client } quiere acceder a tu cuenta. Podrá hacer todo lo que tú puedes hacer con la sesión iniciada.
+oauth-authorize-approve = Autorizar
+oauth-authorize-deny = Denegar
+account-default-co-moderators-user-not-found = El usuario @{ $login_name } no existe.
+account-default-co-moderators-self = No puedes añadirte a ti mismo como comoderador.
+
+cucumber-save = Guardar
+cucumber-undo = Deshacer
+cucumber-redo = Rehacer
+
+privacy-policy = Política de privacidad
+privacy-content = Respetamos tu privacidad. De forma predeterminada, no recopilamos ninguna información personal de los usuarios de Oeee Cafe.
+
+  Dirección de correo electrónico: Solo recopilamos tu dirección de correo si la proporcionas voluntariamente al crear una cuenta o actualizar tu perfil. Tu correo se usa únicamente para fines relacionados con la cuenta, como recuperar la contraseña y verificar la cuenta.
+
+  Uso de los datos: Cualquier información que proporciones se usa exclusivamente para operar y mejorar el servicio de Oeee Cafe. No vendemos, alquilamos ni compartimos tu información personal con terceros.
+
+  Seguridad de los datos: Tomamos medidas razonables para proteger tu información frente a accesos o divulgaciones no autorizados.
+
+  Tus derechos: Puedes acceder a tu cuenta y a los datos asociados, modificarlos o eliminarlos en cualquier momento desde los ajustes de tu cuenta.
+
+  Si tienes alguna pregunta sobre esta política de privacidad, ponte en contacto con nosotros.
+
+community-guidelines = Normas de la comunidad
+community-guidelines-content = { brand } se compromete a ofrecer un entorno seguro, acogedor y respetuoso para todos los usuarios. No toleramos en absoluto el contenido inaceptable ni el comportamiento abusivo.
+
+  Contenido y comportamiento prohibidos:
+  • Acoso, amenazas o intimidación a otros usuarios
+  • Discurso de odio o discriminación por raza, etnia, religión, género, orientación sexual, discapacidad o cualquier otra característica protegida
+  • Contenido sexual que involucre a menores o contenido sexual no consentido
+  • Contenido o actividades ilegales, entre otros: incitación a la violencia, distribución de sustancias controladas, infracción de derechos de autor
+  • Spam, malware o intentos de phishing
+  • Suplantación de identidad o fraude de identidad
+  • Doxxing o difusión de información privada de otras personas sin su consentimiento
+
+  Aplicación:
+  Las infracciones de estas normas pueden conllevar la cancelación inmediata y permanente de la cuenta sin previo aviso. Nos reservamos el derecho de denunciar las actividades ilegales ante las autoridades.
+
+  Denuncias:
+  Si encuentras contenido o comportamientos que infrinjan estas normas, denúncialos de inmediato. Nos tomamos en serio todas las denuncias y las investigaremos con rapidez.
+
+  Al usar { brand }, aceptas cumplir estas normas de la comunidad. Nos reservamos el derecho de actualizarlas en cualquier momento.
+
+personal-post = Publicación personal
@@ -1,6 +1,7 @@
 brand = OEEEカフェ
 email-from-address = OEEEカフェ <noreply@oeee.cafe>
 error-404 = ページが見つかりません.
+error-410 = このページは削除されました。
 error-403 = このページにアクセスする権限がありません。
 
 about = お絵かきの発音はOEEEカフェに似ています。
@@ -31,6 +32,7 @@ notification-mention = メンション
 notification-post-reply = 絵の返信
 notification-comment-reply = コメントの返信
 notification-community-post = 新しいコミュニティ投稿
+notification-follower-moved = フォロワーの引っ越し
 
 # Notification action messages with FluentArgs for proper word order in different languages
 notification-action-commented-on = { $postTitle }にコメントを残しました
@@ -44,9 +46,13 @@ notification-action-replied-to-post-with-title = { $postTitle }に返信を描
 notification-action-replied-to-comment = { $postTitle }のコメントに返信しました
 notification-action-posted-in-community = さんがコミュニティに絵を描きました
 notification-action-posted-in-community-with-title = さんがコミュニティに絵を描きました: { $postTitle }
+notification-action-follower-moved = さんが新しいアカウントに引っ越しました。今後は新しいアカウントがあなたをフォローします
 
 notification-mark-all-read = すべて既読にする
+notification-filter-all = すべて
+loading-more-notifications = さらに通知を読み込み中...
 notification-mark-read = 既読にする
+notification-and-others = ほか{ $count }人
 notification-delete = 削除
 notification-delete-confirm = 本当にこの通知を削除しますか？
 
@@ -67,18 +73,29 @@ push-notification-comment-reply-body = { $name }さんがコメントに返信
 push-notification-community-post-title = 新しいコミュニティ投稿
 push-notification-community-post-body = { $name }さんがコミュニティに絵を描きました
 push-notification-community-post-body-with-title = { $name }さんがコミュニティに絵を描きました: { $title }
+push-notification-follower-moved-title = フォロワーの引っ越し
+push-notification-follower-moved-body = { $name }さんが新しいアカウントに引っ越しました。今後は新しいアカウントがあなたをフォローします
 push-notification-community-invite-title = コミュニティ招待
 push-notification-community-invite-body = { $inviter }さんが@{ $community }に招待しました
 push-notification-invite-accepted-title = 招待を承認しました
 push-notification-invite-accepted-body = { $accepter }さんが@{ $community }への招待を承認しました
 push-notification-invite-declined-title = 招待を拒否しました
 push-notification-invite-declined-body = { $decliner }さんが@{ $community }への招待を拒否しました
+push-notification-join-request-approved-title = 参加リクエスト承認
+push-notification-join-request-approved-body = @{ $community }のメンバーになりました
+push-notification-join-request-rejected-title = 参加リクエスト拒否
+push-notification-join-request-rejected-body = @{ $community }への参加リクエストが拒否されました
+push-notification-post-approved-title = 投稿承認
+push-notification-post-approved-body = @{ $community }への投稿が公開されました
+push-notification-post-rejected-title = 投稿非承認
+push-notification-post-rejected-body = @{ $community }への投稿は承認されず、一時保管箱に戻されました
 
 sign-in = ログイン
 sign-up = 会員登録
 sign-out = ログアウト
 
 message-incorrect-credentials = IDまたはパスワードが間違っています。
+message-too-many-attempts = 試行回数が多すぎます。しばらくしてからもう一度お試しください。
 login-name-conflict-error = このIDはすでにコミュニティで使用されています。別のIDを選択してください。
 
 forgot-password = パスワードをお忘れですか？
@@ -120,8 +137,17 @@ community-stats = コミュニティ統計
 community-stats-posts = 投稿
 community-stats-contributors = 寄稿者
 community-stats-comments = コメント
+community-leaderboard = トップ貢献者
+community-leaderboard-week = 今週
+community-leaderboard-month = 今月
+community-leaderboard-all-time = 全期間
+community-leaderboard-score = { $score }ポイント
+community-leaderboard-counts = 投稿 { $posts }件 · 受け取ったリアクション { $reactions }件 · 受け取ったコメント { $comments }件
+community-leaderboard-your-rank = あなたの順位: { $rank }位（{ $score }ポイント）
+community-leaderboard-empty = この期間の貢献はまだありません。
 
 edit = 修正
+comment-edited = (編集済み)
 edit-community-button = コミュニティに移動
 save = 保存
 cancel = キャンセル
@@ -129,6 +155,9 @@ delete = 削除
 
 post-created-at = 絵を描いた時刻
 post-published-at = 発行時刻
+post-edited = 編集済み
+post-history = 編集履歴
+post-history-empty = この投稿は編集されていません。
 post-duration = 所要時間
 post-replay = リプレイ
 post-author = 作家
@@ -141,6 +170,11 @@ post-no-comments = コメントはありません。
 post-no-comments-signin = ログインしてコメントを書いてみてください。
 post-comment = コメント
 post-publish = 公開
+post-schedule = 予約投稿
+post-scheduled-for = 予約時刻
+post-schedule-hint = 時刻を選んで予約投稿を押すと、後で公開されます。時刻は韓国標準時です。
+post-submitted-for-approval = 投稿を送信しました。コミュニティのオーナーかモデレーターが承認すると公開されます。
+post-pending-approval = 審査待ち
 post-move-community = このコミュニティに移動する
 post-move-community-title = コミュニティに投稿を移動
 post-move-post-to-move = 移動する投稿
@@ -160,6 +194,8 @@ post-delete-confirm = 本当にこの画像を削除しますか？
 post-relay-enabled-notice = この画像は継続して描くことができます。画像をクリックしてみてください！
 post-relay = リレー
 post-reply-with-drawing = 絵で返信
+post-translate = 翻訳する
+post-translation-failed = 翻訳に失敗しました。後でもう一度お試しください。
 post-reply-select-tool = ツールとキャンバスサイズを選択
 post-child-posts = 返信
 post-replying-to = 返信先:
@@ -170,6 +206,12 @@ post-reaction = リアクション
 post-reactions = リアクション
 post-share = 共有
 post-share-copied = リンクがクリップボードにコピーされました！
+post-boost = ブースト
+post-unboost = ブーストを取り消す
+report-object = 通報
+report-object-comment = 何が問題ですか？（任意）
+report-object-submit = 通報を送信
+report-object-sent = 通報しました。ありがとうございます。
 post-report = 報告
 post-report-title = この投稿を報告
 post-report-description-label = 報告理由
@@ -178,6 +220,57 @@ post-report-submit = 報告する
 post-report-success = 投稿が報告されました。コミュニティの安全維持にご協力いただきありがとうございます。
 post-report-error = 報告の送信に失敗しました。もう一度お試しください。
 post-report-confirm = この投稿を報告してもよろしいですか？
+post-report-reason-label = 分類
+post-report-reason-spam = スパム
+post-report-reason-harassment = 嫌がらせ
+post-report-reason-sexual-content = 性的なコンテンツ
+post-report-reason-violence = 暴力
+post-report-reason-copyright = 著作権侵害
+post-report-reason-other = その他
+admin-reports = 報告
+admin-reports-empty = 保留中の報告はありません。
+admin-reports-reporter = 報告者
+admin-reports-reason = 理由
+admin-reports-detail = 詳細
+admin-reports-post = 投稿
+admin-reports-reported-at = 報告日時
+admin-reports-resolve = 対応済み
+admin-reports-dismiss = 却下
+admin-reports-objects = 通報されたコメントと連合コンテンツ
+admin-reports-objects-targets = 通報対象
+admin-reports-objects-remote = 他のインスタンスから
+admin-announcements = お知らせ
+admin-announcements-new = 新しいお知らせ
+admin-announcements-message = 内容
+admin-announcements-level = レベル
+admin-announcements-level-info = 案内
+admin-announcements-level-warning = 注意
+admin-announcements-level-critical = 緊急
+admin-announcements-starts-at = 開始日時
+admin-announcements-ends-at = 終了日時
+admin-announcements-time-help = 日時は韓国時間です。開始日時を空にするとすぐに表示され、終了日時を空にすると削除するまで表示されます。
+admin-announcements-dismissible = ユーザーが閉じられる
+admin-announcements-create = お知らせを作成
+admin-announcements-delete = 削除
+admin-announcements-empty = お知らせはありません。
+admin-announcements-content-help = 言語ごとの内容は任意です。空の言語には基本の内容が表示されます。
+admin-announcements-content-ko = 韓国語
+admin-announcements-content-en = 英語
+admin-announcements-content-ja = 日本語
+admin-announcements-content-zh = 中国語
+admin-announcements-active = 有効
+admin-announcements-activate = 有効にする
+admin-announcements-deactivate = 無効にする
+admin-community-categories = コミュニティカテゴリ
+admin-community-categories-new = 新しいカテゴリ
+admin-community-categories-name = 名前
+admin-community-categories-slug = スラッグ
+admin-community-categories-position = 順序
+admin-community-categories-help = スラッグはリンクに使われ、a-z、0-9、- のみ使用できます。順序の小さいカテゴリから表示されます。
+admin-community-categories-create = カテゴリを作成
+admin-community-categories-delete = 削除
+admin-community-categories-empty = カテゴリはありません。
+announcement-dismiss = 閉じる
 
 allow-relay = リレー描画を許可
 sensitive = 敏感な内容
@@ -189,12 +282,25 @@ draft-empty = まだ下書きがありません
 
 # Hashtags
 trending-hashtags = トレンドハッシュタグ
+trending-hashtags-description = 過去24時間で最も多くの絵に使われたハッシュタグ
+trending-hashtags-updated = 更新
+trending-hashtags-delta = 直近1時間の絵の数とその前の1時間との差
+trending-hashtags-empty = 現在トレンドのハッシュタグはありません
 hashtag-post-count = 件の投稿
 hashtag-no-posts = このハッシュタグがついた投稿はまだありません
+hashtag-follow = フォロー
+hashtag-unfollow = フォロー解除
+hashtag-following = フォロー中のハッシュタグ
+hashtag-following-empty = まだハッシュタグをフォローしていません
 hashtag-discovery = ハッシュタグを探す
 hashtag-search-placeholder = ハッシュタグを検索...
 search = 検索
 search-results-for = 検索結果
+search-posts = 絵を検索
+search-posts-placeholder = タイトルと説明を検索...
+search-no-results = 検索結果がありません
+search-previous = 前へ
+search-next = 次へ
 no-hashtags-found = ハッシュタグが見つかりませんでした
 sort-by = 並べ替え
 sort-trending = トレンド
@@ -213,6 +319,8 @@ collaborate-max-participants-label = 最大参加者数
 collaborate-participants = 人
 collaborate-public-session-label = 公開ロビーに表示
 collaborate-public-session-description = チェックを外すとプライベート セッション (リンクのみアクセス) になります
+collaborate-record-replay-label = リプレイを記録する
+collaborate-record-replay-description = 保存した絵に参加者全員の描画のリプレイが付きます
 collaborate-create-button = セッションを作成
 collaborate-active-sessions = アクティブな公開セッション
 collaborate-no-sessions = アクティブな公開セッションはありません。作成して始めましょう！
@@ -259,15 +367,39 @@ community-badge-private = プライベート
 community-members = メンバー
 community-invite-user = ユーザーを招待
 community-invite-username = 招待するユーザー名
+community-invite-username-hint = 複数のユーザー名はカンマまたはスペースで区切ってください
 community-invite-button = 招待を送る
-community-invite-success = 招待を送りました
+community-invite-invited-users = { $names } に招待を送りました
 community-invite-error = 招待の送信に失敗しました
 community-invite-user-not-found = ユーザーが見つかりません
-community-invite-already-member = すでにメンバーです
-community-invite-already-invited = すでに保留中の招待があります
+community-invite-users-already-members = すでにメンバーです: { $names }
+community-invite-users-already-invited = すでに保留中の招待があります: { $names }
+community-invite-users-not-found = ユーザーが見つかりません: { $names }
+community-invite-too-many = 一度に招待できるのは { $max } 人までです
 private-community-no-access = このプライベートコミュニティにアクセスできません。
 community-remove-member = メンバーを削除
 community-remove-member-confirm = 本当にこのメンバーを削除しますか？
+community-member-promote = モデレーターにする
+community-member-demote = モデレーターを解除
+community-join-requests = 参加リクエスト
+community-join-requests-pending = 保留中の参加リクエスト
+community-accepts-join-requests = 参加リクエストを受け付ける
+community-accepts-join-requests-description = ログインユーザーが参加をリクエストできます。オーナーとモデレーターがメンバーページで承認または拒否します。
+community-join-request-button = 参加をリクエスト
+community-join-request-pending = 参加リクエストは審査待ちです。
+community-join-request-sent = 参加リクエストを送信しました。
+community-join-request-requested-at = リクエスト日
+community-join-request-approve = 承認
+community-join-request-reject = 拒否
+community-join-request-reject-confirm = この参加リクエストを拒否してもよろしいですか？
+community-post-approval = 投稿の承認
+community-require-post-approval = 新しい投稿を公開前に審査する
+community-require-post-approval-description = メンバーの投稿は、オーナーかモデレーターが承認するまでコミュニティページの審査キューで待機します。オーナーとモデレーターの投稿はそのまま公開されます。
+community-pending-posts = 審査待ちの投稿
+community-pending-posts-description = 承認されるまで、これらの投稿はオーナーとモデレーターにしか表示されません。
+community-pending-post-approve = 承認
+community-pending-post-reject = 却下
+community-pending-post-reject-confirm = この投稿を却下しますか？投稿者の一時保管箱に戻されます。
 community-retract-invitation = 招待を取り消す
 community-retract-invitation-confirm = 本当にこの招待を取り消しますか？
 community-member-role = 役割
@@ -279,6 +411,55 @@ community-leave = コミュニティを退出
 community-leave-confirm = 本当にこのコミュニティを退出しますか？
 community-left-success = コミュニティを退出しました
 community-owner-cannot-leave = オーナーは退出できません。代わりにコミュニティを削除してください。
+community-transfer = オーナー権の移譲
+community-transfer-description = 新しいオーナーは、認証済みメールアドレスに送られたリンクから承認する必要があります。あなたはモデレーターとして残ります。
+community-transfer-button = 移譲を申請
+community-transfer-confirm = このコミュニティのオーナー権を移譲しますか？
+community-transfer-pending = 承認待ち:
+community-transfer-cancel = 移譲を取り消す
+community-transfer-sent = 新しいオーナーに確認リンクをメールで送信しました。
+community-transfer-no-email = このユーザーには認証済みのメールアドレスがありません。
+community-transfer-accept = オーナー権を受け取る
+community-transfer-accept-description = このコミュニティのオーナーになるよう依頼されています。
+community-transfer-confirmed = あなたはこのコミュニティのオーナーになりました。
+community-transfer-cancelled = オーナー権の移譲を取り消しました。
+community-transfer-email-subject = { $owner }さんが{ $community }をあなたに譲ろうとしています
+community-transfer-email-body = { $owner }さんが、コミュニティ{ $community }のオーナーになるようあなたに依頼しました。下のリンクを開いて承認または辞退してください。リンクの有効期限は72時間です。
+community-invite-links = 招待リンク
+community-invite-links-description = リンクを知っている人は誰でもメンバーとしてこのコミュニティに参加できます。制限しない場合は空欄にしてください。
+community-invite-link-max-uses = 最大使用回数
+community-invite-link-expires-in-days = 有効期間(日)
+community-invite-link-create = 招待リンクを作成
+community-invite-link = リンク
+community-invite-link-uses = 使用回数
+community-invite-link-expires-at = 有効期限
+community-invite-link-inactive = 無効
+community-invite-link-never-expires = なし
+community-invite-link-revoke = 取り消す
+community-invite-link-revoke-confirm = この招待リンクを取り消しますか?
+community-invite-link-invalid = 使用回数は正の数、期間は365日以内で入力してください。
+community-invite-link-created = 招待リンクを作成しました。
+community-invite-link-unusable = この招待リンクは期限切れか、使用回数の上限に達しています。
+community-invite-link-joined = コミュニティに参加しました。
+community-invite-link-join-title = コミュニティに参加
+community-invite-link-already-member = すでにこのコミュニティのメンバーです。
+community-invite-link-join-description = このコミュニティに招待されています。
+community-invite-link-join = 参加する
+community-invite-link-login-to-join = ログインして参加
+community-archived-notice = 長期間新しい投稿がなかったため、このコミュニティはアーカイブされました。コミュニティ一覧には表示されません。
+community-unarchive-button = コミュニティを復元
+community-archive-email-subject = { $community }はまもなくアーカイブされます
+community-archive-email-body = コミュニティ{ $community }には長期間新しい投稿がないため、{ $days }日後にアーカイブされます。アーカイブされたコミュニティはコミュニティ一覧に表示されません。それまでに投稿すればアクティブなままになり、アーカイブ後もコミュニティページからいつでも復元できます。
+community-sensitive = センシティブなコミュニティ
+community-sensitive-description = このコミュニティのすべての投稿はセンシティブなコンテンツとして扱われます。訪問者は入る前に確認が必要です。
+community-sensitive-interstitial = このコミュニティにはセンシティブなコンテンツが含まれています。続行しますか？
+community-sensitive-interstitial-settings = この警告を表示しないようにするには、次の画面でセンシティブなコンテンツの表示を有効にしてください：
+community-sensitive-enter = 入る
+community-sensitive-leave = 戻る
+community-pinned-posts = 固定されたお絵かき
+community-pin-post = 固定
+community-unpin-post = 固定解除
+community-federated-posts = フェディバースから
 
 community-delete-danger-zone = 危険ゾーン
 community-delete-warning = コミュニティを削除すると、元に戻すことができません。このコミュニティのすべての投稿も削除されます。
@@ -296,6 +477,8 @@ invitation-reject = 拒否
 invitation-accepted = 招待を承諾しました
 invitation-rejected = 招待を拒否しました
 invitation-sent-at = 送信日時
+invitation-expires-at = 有効期限
+invitation-expires-soon = まもなく期限切れ
 
 community-drawing-tool = ツール
 community-drawing-width = 幅
@@ -307,6 +490,18 @@ community-drawing-portrait = 縦向き
 community-no-posts = 公開された写真はありません。
 community-drawing-post-error = 写真の公開中にエラーが発生しました。 再送信してください。
 community-slug-conflict-error = このIDを持つコミュニティは既に存在します。別のIDを選択してください。
+community-posting-window = 投稿可能時間
+community-posting-window-description = メンバーは毎日この時間帯にのみ投稿できます。オーナーとモデレーターはいつでも投稿できます。両方の時刻を空欄にすると、いつでも投稿できます。
+community-posting-window-timezone = タイムゾーン
+community-posting-window-opens-at = 開始時刻
+community-posting-window-closes-at = 終了時刻
+community-posting-window-invalid = 有効なタイムゾーンと異なる2つの時刻を入力してください。
+community-default-hashtags = デフォルトのハッシュタグ
+community-default-hashtags-description = このコミュニティに投稿されるすべての投稿にこのハッシュタグが付きます。投稿者は投稿を編集して外せます。
+community-categories = カテゴリ
+community-categories-description = コミュニティ一覧ページでこのコミュニティを表示するカテゴリを選んでください。
+community-categories-all = すべて
+community-posting-window-closed = このコミュニティは現在投稿を受け付けていません。{ $opens_at }から投稿できます。
 
 profile-link = リンク
 profile-manage = 管理
@@ -318,7 +513,14 @@ profile-public-community-posts = 公開コミュニティで描かれた図
 profile-public-community-posts-nil = 公開コミュニティで描いた絵はありません。
 profile-private-community-posts = プライベートコミュニティで描かれた図 (プロフィールに公開されていない)
 profile-private-community-posts-nil = プライベートコミュニティで描いた絵はありません。
+profile-pinned-posts = 固定された絵
+profile-pin-post = プロフィールに固定
+profile-unpin-post = プロフィールの固定を解除
+profile-pin-limit-reached = 固定できるのは最大5件までです。
 profile-settings = プロフィール設定
+profile-avatar = アバター
+profile-avatar-upload = アバターをアップロード
+profile-avatar-help = PNGまたはJPEG、最大1MBまで。
 profile-link-management = リンク管理
 profile-link-requires-verified-email = メール認証を完了するまでリンクを追加できます。
 profile-link-order = 順序
@@ -386,19 +588,82 @@ account-change-email-verify = メール認証
 account-change-email-error-token-mismatch = 認証コードが一致しません。
 account-change-email-error-token-expired = 認証コードが期限切れです。
 account-change-email-error-already-verified = すでに認証されているメールアドレス。
+account-change-email-error-domain-blocked = このメールドメインは使用できません。
 account-change-email-success = メールアドレスが認証されました。
 account-change-email-subject = OEEEカフェメールアドレス認証コード
+account-export-email-subject = OEEEカフェのデータエクスポートの準備ができました
+account-export-email-body = データエクスポートの準備ができました。24時間以内に下のリンクからダウンロードしてください:
+community-export-email-subject = { $community }のエクスポートの準備ができました
 account-delete = アカウント削除
-account-delete-warning = アカウントを削除すると、すべてのデータが完全に削除されます。この操作は元に戻せません。コミュニティを所有している場合は、先に所有権を譲渡するか削除する必要があります。
-account-delete-confirm = 本当にアカウントを削除しますか？この操作は元に戻せません。
+account-delete-warning = アカウントは{ $days }日後に削除されます。それまではアカウント復元ページでログインすると復元できます。その後、すべてのデータが完全に削除されます。コミュニティを所有している場合は、先に所有権を譲渡するか削除する必要があります。
+account-delete-details = すべての端末からログアウトされ、公開済みの投稿はすぐに非表示になり、あなたをフォローしているサーバーにもアカウントの削除が通知されます。削除が確定すると、投稿とコメントは削除され、絵とリプレイはストレージから消去されます。
+account-delete-cancel = アカウントを残す
+account-delete-confirm = 本当にアカウントを削除しますか？
 account-delete-button = アカウントを削除
+account-delete-scheduled = アカウントは{ $days }日後に削除されます。それまでは復元できます。
+account-delete-email-subject = アカウントの削除が予定されました
+account-delete-email-body = アカウントは{ $date }に削除されます。気が変わった場合は、それまでに下のリンクから復元できます。
+account-delete-restore = アカウントを復元
+account-delete-restore-description = 削除予定のアカウントにログインして復元します。非表示になった投稿も再び表示されます。
+account-delete-restore-button = アカウントを復元
+account-delete-restore-totp = 二段階認証を有効にしているアカウントのみ必要です。
+account-delete-cancelled = アカウントが復元されました。再びログインできます。
+account-delete-cancel-failed = そのログイン名とパスワードに一致する削除予定のアカウントはありません。
 account-language-settings = 言語設定
 account-language-auto = 自動
 account-language-current = 現在の言語
+account-language-fallbacks = 代替言語
+account-language-fallbacks-help = 選択した言語にないメッセージを順に探す言語コード（ko, ja, en, zh, es）をカンマ区切りで入力してください。最後には常に英語が使われます。
 account-language-settings-save = 言語設定の保存
 account-content-settings = コンテンツ設定
 account-show-sensitive-content = センシティブなコンテンツを表示
 account-content-settings-save = コンテンツ設定の保存
+account-feed-settings = ホームフィード
+account-feed-algorithm-chronological = 新しい順
+account-feed-algorithm-engagement = 反応が多い順
+account-feed-algorithm-following-first = フォロー中の人を優先
+account-feed-settings-save = フィード設定の保存
+account-feed-settings-saved = フィード設定を保存しました。
+account-follower-settings = フォロワー
+account-is-locked = 新しいフォロワーを手動で承認する
+account-is-locked-help = 他のサーバーのユーザーは、承認されるまであなたをフォローできません。
+account-follower-settings-save = フォロワー設定を保存
+follow-requests = フォローリクエスト
+follow-requests-none = 保留中のフォローリクエストはありません
+follow-request-accept = 承認
+follow-request-reject = 拒否
+follow-request-accepted = フォローリクエストを承認しました
+follow-request-rejected = フォローリクエストを拒否しました
+account-default-co-moderators = デフォルトの共同モデレーター
+account-default-co-moderators-description = 作成するすべてのコミュニティで、これらのユーザーがモデレーターになります。非公開コミュニティではすぐに追加され、それ以外のコミュニティではモデレーターへの招待が送信されます。
+account-default-co-moderators-login-names = ログイン名（スペースまたはカンマ区切り）
+account-default-co-moderators-save = 共同モデレーターを保存
+account-notification-preferences = プッシュ通知
+account-notification-preferences-description = 端末に送る通知を選択してください。
+account-notification-preferences-save = 通知設定を保存
+account-notification-preferences-saved = 通知設定を保存しました
+totp = 二段階認証
+totp-description = ログイン時にパスワードに加えて認証アプリのコードを要求します。
+totp-enable = 二段階認証を設定
+totp-disable = 二段階認証を無効にする
+totp-status-enabled = 二段階認証が有効です。無効にするにはパスワードを入力してください。
+totp-setup-scan = 認証アプリでこのQRコードを読み取り、表示される6桁のコードを入力してください。
+totp-setup-manual = または、このキーを手動で入力してください:
+totp-code = 認証コード
+totp-verify = 確認
+totp-challenge-description = 認証アプリの6桁のコード、またはバックアップコードのいずれかを入力してください。
+totp-backup-codes = バックアップコード
+totp-backup-codes-description = これらのコードを安全な場所に保管してください。認証アプリを使えなくなったとき、各コードで一度だけログインできます。再表示されません。
+totp-enabled = 二段階認証を有効にしました。
+totp-disabled = 二段階認証を無効にしました。
+totp-error-invalid-code = 認証コードが正しくありません。
+totp-error-unavailable = このサーバーでは二段階認証を利用できません。
+oauth-authorize-title = アプリの認可
+oauth-authorize-description = { $client } があなたのアカウントへのアクセスを求めています。ログイン中にできることはすべて行えるようになります。
+oauth-authorize-approve = 認可する
+oauth-authorize-deny = 拒否する
+account-default-co-moderators-user-not-found = ユーザー @{ $login_name } は存在しません。
+account-default-co-moderators-self = 自分自身を共同モデレーターに追加することはできません。
 
 cucumber-save = 保存
 cucumber-undo = 元に戻す
@@ -1,6 +1,7 @@
 brand = 오이카페
 email-from-address = 오이카페 <noreply@oeee.cafe>
 error-404 = 페이지를 찾을 수 없습니다.
+error-410 = 삭제된 페이지입니다.
 error-403 = 이 페이지에 접근할 권한이 없습니다.
 
 about = 오에카키는 그림을 그릴 수 있는 게시판입니다.
@@ -31,6 +32,7 @@ notification-mention = 멘션
 notification-post-reply = 그림 답글
 notification-comment-reply = 댓글 답글
 notification-community-post = 새 커뮤니티 게시물
+notification-follower-moved = 팔로워 계정 이전
 
 # Notification action messages with FluentArgs for proper word order in different languages
 notification-action-commented-on = { $postTitle }에 댓글을 남겼습니다
@@ -44,9 +46,13 @@ notification-action-replied-to-post-with-title = { $postTitle }에 답글을 그
 notification-action-replied-to-comment = { $postTitle }의 댓글에 답글을 남겼습니다
 notification-action-posted-in-community = 님이 커뮤니티에 그림을 그렸습니다
 notification-action-posted-in-community-with-title = 님이 커뮤니티에 그림을 그렸습니다: { $postTitle }
+notification-action-follower-moved = 님이 새 계정으로 이전했습니다. 이제 새 계정이 회원님을 팔로우합니다
 
 notification-mark-all-read = 모두 읽음으로 표시
+notification-filter-all = 전체
+loading-more-notifications = 알림 더 불러오는 중...
 notification-mark-read = 읽음으로 표시
+notification-and-others = 외 { $count }명
 notification-delete = 삭제
 notification-delete-confirm = 정말 이 알림을 삭제하시겠습니까?
 
@@ -67,18 +73,29 @@ push-notification-comment-reply-body = { $name }님이 댓글에 답글을 남
 push-notification-community-post-title = 새 커뮤니티 게시물
 push-notification-community-post-body = { $name }님이 커뮤니티에 그림을 그렸습니다
 push-notification-community-post-body-with-title = { $name }님이 커뮤니티에 그림을 그렸습니다: { $title }
+push-notification-follower-moved-title = 팔로워 계정 이전
+push-notification-follower-moved-body = { $name }님이 새 계정으로 이전했습니다. 이제 새 계정이 회원님을 팔로우합니다
 push-notification-community-invite-title = 커뮤니티 초대
 push-notification-community-invite-body = { $inviter }님이 @{ $community }에 초대했습니다
 push-notification-invite-accepted-title = 초대 수락됨
 push-notification-invite-accepted-body = { $accepter }님이 @{ $community } 초대를 수락했습니다
 push-notification-invite-declined-title = 초대 거절됨
 push-notification-invite-declined-body = { $decliner }님이 @{ $community } 초대를 거절했습니다
+push-notification-join-request-approved-title = 가입 신청 승인
+push-notification-join-request-approved-body = 이제 @{ $community } 커뮤니티의 멤버입니다
+push-notification-join-request-rejected-title = 가입 신청 거절
+push-notification-join-request-rejected-body = @{ $community } 가입 신청이 거절되었습니다
+push-notification-post-approved-title = 게시물 승인
+push-notification-post-approved-body = @{ $community } 커뮤니티에 올린 게시물이 게시되었습니다
+push-notification-post-rejected-title = 게시물 반려
+push-notification-post-rejected-body = @{ $community } 커뮤니티에 올린 게시물이 승인되지 않아 임시 보관함으로 돌아갔습니다
 
 sign-in = 로그인
 sign-up = 회원가입
 sign-out = 로그아웃
 
 message-incorrect-credentials = 아이디 또는 비밀번호가 틀렸습니다.
+message-too-many-attempts = 시도 횟수가 너무 많습니다. 잠시 후 다시 시도해 주세요.
 login-name-conflict-error = 이미 커뮤니티가 사용 중인 아이디입니다. 다른 아이디를 선택해 주세요.
 
 forgot-password = 비밀번호를 잊으셨나요?
@@ -120,8 +137,17 @@ community-stats = 커뮤니티 통계
 community-stats-posts = 게시물
 community-stats-contributors = 기여자
 community-stats-comments = 댓글
+community-leaderboard = 활발한 참여자
+community-leaderboard-week = 이번 주
+community-leaderboard-month = 이번 달
+community-leaderboard-all-time = 전체 기간
+community-leaderboard-score = { $score }점
+community-leaderboard-counts = 그림 { $posts }개 · 받은 반응 { $reactions }개 · 받은 댓글 { $comments }개
+community-leaderboard-your-rank = 내 순위: { $rank }위 ({ $score }점)
+community-leaderboard-empty = 이 기간에는 아직 참여 기록이 없습니다.
 
 edit = 수정
+comment-edited = (수정됨)
 edit-community-button = 커뮤니티로 이동
 save = 저장
 cancel = 취소
@@ -129,6 +155,9 @@ delete = 삭제
 
 post-created-at = 그린 시각
 post-published-at = 발행 시각
+post-edited = 수정됨
+post-history = 수정 기록
+post-history-empty = 수정된 적이 없는 글입니다.
 post-duration = 소요 시간
 post-replay = 리플레이
 post-author = 그린이
@@ -141,6 +170,11 @@ post-no-comments = 댓글이 없습니다.
 post-no-comments-signin = 로그인하고 댓글을 달아 보세요.
 post-comment = 댓글 달기
 post-publish = 게시
+post-schedule = 예약 게시
+post-scheduled-for = 예약 시각
+post-schedule-hint = 시각을 고른 뒤 예약 게시를 누르면 나중에 게시됩니다. 시각은 한국 표준시 기준입니다.
+post-submitted-for-approval = 게시물이 제출되었습니다. 커뮤니티 소유자나 운영자가 승인하면 게시됩니다.
+post-pending-approval = 검토 요청됨
 post-move-community = 이 커뮤니티로 이동
 post-move-community-title = 다른 커뮤니티로 게시물 이동
 post-move-post-to-move = 이동할 게시물
@@ -160,6 +194,8 @@ post-delete-confirm = 정말 이 그림을 삭제하시겠습니까?
 post-relay-enabled-notice = 이 그림은 이어 그릴수 있습니다. 그림을 눌러 보세요!
 post-relay = 이어그리기
 post-reply-with-drawing = 그림으로 답글
+post-translate = 번역하기
+post-translation-failed = 번역하지 못했습니다. 잠시 후 다시 시도해 주세요.
 post-reply-select-tool = 도구와 캔버스 크기 선택
 post-child-posts = 답글
 post-replying-to = 답글 대상:
@@ -170,6 +206,12 @@ post-reaction = 반응
 post-reactions = 반응
 post-share = 공유
 post-share-copied = 링크가 클립보드에 복사되었습니다!
+post-boost = 부스트
+post-unboost = 부스트 취소
+report-object = 신고
+report-object-comment = 어떤 문제가 있나요? (선택)
+report-object-submit = 신고 보내기
+report-object-sent = 신고했습니다. 감사합니다.
 post-report = 신고
 post-report-title = 이 게시물 신고하기
 post-report-description-label = 신고 사유
@@ -178,6 +220,57 @@ post-report-submit = 신고하기
 post-report-success = 게시물이 신고되었습니다. 커뮤니티를 안전하게 유지하는 데 도움을 주셔서 감사합니다.
 post-report-error = 신고 제출에 실패했습니다. 다시 시도해주세요.
 post-report-confirm = 이 게시물을 신고하시겠습니까?
+post-report-reason-label = 분류
+post-report-reason-spam = 스팸
+post-report-reason-harassment = 괴롭힘
+post-report-reason-sexual-content = 성적인 콘텐츠
+post-report-reason-violence = 폭력
+post-report-reason-copyright = 저작권 침해
+post-report-reason-other = 기타
+admin-reports = 신고
+admin-reports-empty = 대기 중인 신고가 없습니다.
+admin-reports-reporter = 신고자
+admin-reports-reason = 사유
+admin-reports-detail = 상세
+admin-reports-post = 게시물
+admin-reports-reported-at = 신고 일시
+admin-reports-resolve = 처리
+admin-reports-dismiss = 기각
+admin-reports-objects = 신고된 댓글과 연합 콘텐츠
+admin-reports-objects-targets = 신고 대상
+admin-reports-objects-remote = 다른 인스턴스에서 받음
+admin-announcements = 공지
+admin-announcements-new = 새 공지
+admin-announcements-message = 내용
+admin-announcements-level = 수준
+admin-announcements-level-info = 안내
+admin-announcements-level-warning = 주의
+admin-announcements-level-critical = 긴급
+admin-announcements-starts-at = 시작 시각
+admin-announcements-ends-at = 종료 시각
+admin-announcements-time-help = 시각은 한국 시간 기준입니다. 시작 시각을 비우면 바로 표시되고, 종료 시각을 비우면 삭제할 때까지 표시됩니다.
+admin-announcements-dismissible = 사용자가 닫을 수 있음
+admin-announcements-create = 공지 만들기
+admin-announcements-delete = 삭제
+admin-announcements-empty = 공지가 없습니다.
+admin-announcements-content-help = 언어별 내용은 선택 사항이며, 비워 둔 언어에는 기본 내용이 표시됩니다.
+admin-announcements-content-ko = 한국어
+admin-announcements-content-en = 영어
+admin-announcements-content-ja = 일본어
+admin-announcements-content-zh = 중국어
+admin-announcements-active = 활성
+admin-announcements-activate = 활성화
+admin-announcements-deactivate = 비활성화
+admin-community-categories = 커뮤니티 카테고리
+admin-community-categories-new = 새 카테고리
+admin-community-categories-name = 이름
+admin-community-categories-slug = 슬러그
+admin-community-categories-position = 순서
+admin-community-categories-help = 슬러그는 링크에 쓰이며 a-z, 0-9, -만 사용할 수 있습니다. 순서가 낮은 카테고리가 먼저 표시됩니다.
+admin-community-categories-create = 카테고리 만들기
+admin-community-categories-delete = 삭제
+admin-community-categories-empty = 카테고리가 없습니다.
+announcement-dismiss = 닫기
 
 allow-relay = 릴레이 그림 허용
 sensitive = 민감한 내용
@@ -189,12 +282,25 @@ draft-empty = 아직 임시 보관된 그림이 없습니다
 
 # Hashtags
 trending-hashtags = 인기 해시태그
+trending-hashtags-description = 지난 24시간 동안 가장 많은 그림에 쓰인 해시태그
+trending-hashtags-updated = 갱신
+trending-hashtags-delta = 지난 1시간 동안의 그림 수와 그 전 1시간의 차이
+trending-hashtags-empty = 지금은 인기 해시태그가 없습니다
 hashtag-post-count = 개의 게시물
 hashtag-no-posts = 이 해시태그가 달린 게시물이 아직 없습니다
+hashtag-follow = 팔로우
+hashtag-unfollow = 언팔로우
+hashtag-following = 팔로우한 해시태그
+hashtag-following-empty = 아직 팔로우한 해시태그가 없습니다
 hashtag-discovery = 해시태그 찾아보기
 hashtag-search-placeholder = 해시태그 검색...
 search = 검색
 search-results-for = 검색 결과
+search-posts = 그림 검색
+search-posts-placeholder = 제목과 설명 검색...
+search-no-results = 검색 결과가 없습니다
+search-previous = 이전
+search-next = 다음
 no-hashtags-found = 해시태그를 찾을 수 없습니다
 sort-by = 정렬
 sort-trending = 인기순
@@ -213,6 +319,8 @@ collaborate-max-participants-label = 최대 참가자 수
 collaborate-participants = 명
 collaborate-public-session-label = 공개 로비에 표시
 collaborate-public-session-description = 체크를 해제하면 비공개 세션 (링크로만 접근)이 됩니다
+collaborate-record-replay-label = 리플레이 기록하기
+collaborate-record-replay-description = 저장된 그림에 모든 참여자의 그리기 과정 리플레이가 포함됩니다
 collaborate-create-button = 세션 생성
 collaborate-active-sessions = 활성 공개 세션
 collaborate-no-sessions = 활성 공개 세션이 없습니다. 새로 만들어 시작하세요!
@@ -259,15 +367,39 @@ community-badge-private = 비공개
 community-members = 멤버
 community-invite-user = 사용자 초대
 community-invite-username = 초대할 사용자 이름
+community-invite-username-hint = 여러 사용자 이름은 쉼표나 공백으로 구분하세요
 community-invite-button = 초대 보내기
-community-invite-success = 초대를 보냈습니다
+community-invite-invited-users = { $names }에게 초대를 보냈습니다
 community-invite-error = 초대를 보내지 못했습니다
 community-invite-user-not-found = 사용자를 찾을 수 없습니다
-community-invite-already-member = 이미 멤버입니다
-community-invite-already-invited = 이미 대기 중인 초대가 있습니다
+community-invite-users-already-members = 이미 멤버입니다: { $names }
+community-invite-users-already-invited = 이미 대기 중인 초대가 있습니다: { $names }
+community-invite-users-not-found = 사용자를 찾을 수 없습니다: { $names }
+community-invite-too-many = 한 번에 최대 { $max }명까지 초대할 수 있습니다
 private-community-no-access = 이 비공개 커뮤니티에 접근할 수 없습니다.
 community-remove-member = 멤버 제거
 community-remove-member-confirm = 정말 이 멤버를 제거하시겠습니까?
+community-member-promote = 운영자로 지정
+community-member-demote = 운영자 해제
+community-join-requests = 가입 신청
+community-join-requests-pending = 대기 중인 가입 신청
+community-accepts-join-requests = 가입 신청 받기
+community-accepts-join-requests-description = 로그인한 사용자가 가입을 신청할 수 있습니다. 소유자와 운영자가 멤버 페이지에서 신청을 승인하거나 거절합니다.
+community-join-request-button = 가입 신청
+community-join-request-pending = 가입 신청이 검토를 기다리고 있습니다.
+community-join-request-sent = 가입 신청을 보냈습니다.
+community-join-request-requested-at = 신청일
+community-join-request-approve = 승인
+community-join-request-reject = 거절
+community-join-request-reject-confirm = 이 가입 신청을 거절하시겠습니까?
+community-post-approval = 게시물 승인
+community-require-post-approval = 새 게시물을 게시 전에 검토하기
+community-require-post-approval-description = 멤버의 게시물은 소유자나 운영자가 승인할 때까지 커뮤니티 페이지의 검토 대기열에 머뭅니다. 소유자와 운영자의 게시물은 바로 게시됩니다.
+community-pending-posts = 검토 대기 중인 게시물
+community-pending-posts-description = 승인되기 전까지 이 게시물은 소유자와 운영자에게만 보입니다.
+community-pending-post-approve = 승인
+community-pending-post-reject = 반려
+community-pending-post-reject-confirm = 이 게시물을 반려하시겠습니까? 작성자의 임시 보관함으로 돌아갑니다.
 community-retract-invitation = 초대 취소
 community-retract-invitation-confirm = 정말 이 초대를 취소하시겠습니까?
 community-member-role = 역할
@@ -279,6 +411,55 @@ community-leave = 커뮤니티 나가기
 community-leave-confirm = 정말 이 커뮤니티를 나가시겠습니까?
 community-left-success = 커뮤니티에서 나왔습니다
 community-owner-cannot-leave = 소유자는 나갈 수 없습니다. 대신 커뮤니티를 삭제하세요.
+community-transfer = 소유권 이전
+community-transfer-description = 새 소유자가 인증된 이메일 주소로 전송된 링크를 통해 확인해야 합니다. 기존 소유자는 모더레이터로 남습니다.
+community-transfer-button = 이전 요청
+community-transfer-confirm = 이 커뮤니티의 소유권을 이전하시겠습니까?
+community-transfer-pending = 다음 사용자의 확인을 기다리는 중:
+community-transfer-cancel = 이전 취소
+community-transfer-sent = 새 소유자에게 확인 링크를 이메일로 보냈습니다.
+community-transfer-no-email = 이 사용자에게는 인증된 이메일 주소가 없습니다.
+community-transfer-accept = 소유권 수락
+community-transfer-accept-description = 이 커뮤니티의 소유자가 되어 달라는 요청을 받았습니다.
+community-transfer-confirmed = 이제 이 커뮤니티의 소유자입니다.
+community-transfer-cancelled = 소유권 이전이 취소되었습니다.
+community-transfer-email-subject = { $owner }님이 { $community } 커뮤니티를 넘기려고 합니다
+community-transfer-email-body = { $owner }님이 { $community } 커뮤니티의 소유자가 되어 달라고 요청했습니다. 아래 링크를 열어 수락하거나 거절하세요. 링크는 72시간 후에 만료됩니다.
+community-invite-links = 초대 링크
+community-invite-links-description = 링크가 있는 누구나 이 커뮤니티에 멤버로 가입할 수 있습니다. 제한이 없으면 비워 두세요.
+community-invite-link-max-uses = 최대 사용 횟수
+community-invite-link-expires-in-days = 만료 기간(일)
+community-invite-link-create = 초대 링크 만들기
+community-invite-link = 링크
+community-invite-link-uses = 사용 횟수
+community-invite-link-expires-at = 만료
+community-invite-link-inactive = 비활성
+community-invite-link-never-expires = 없음
+community-invite-link-revoke = 취소
+community-invite-link-revoke-confirm = 이 초대 링크를 취소할까요?
+community-invite-link-invalid = 사용 횟수는 양수로, 기간은 365일 이하로 입력해 주세요.
+community-invite-link-created = 초대 링크를 만들었습니다.
+community-invite-link-unusable = 이 초대 링크는 만료되었거나 사용 횟수를 모두 사용했습니다.
+community-invite-link-joined = 커뮤니티에 가입했습니다.
+community-invite-link-join-title = 커뮤니티 가입
+community-invite-link-already-member = 이미 이 커뮤니티의 멤버입니다.
+community-invite-link-join-description = 이 커뮤니티에 초대받았습니다.
+community-invite-link-join = 가입하기
+community-invite-link-login-to-join = 로그인하고 가입하기
+community-archived-notice = 오랫동안 새 글이 없어 보관된 커뮤니티입니다. 커뮤니티 목록에 더 이상 표시되지 않습니다.
+community-unarchive-button = 커뮤니티 복원
+community-archive-email-subject = { $community } 커뮤니티가 곧 보관됩니다
+community-archive-email-body = { $community } 커뮤니티에 오랫동안 새 글이 없어 { $days }일 후에 보관됩니다. 보관된 커뮤니티는 커뮤니티 목록에 표시되지 않습니다. 그 전에 글을 올리면 계속 활성 상태로 유지되며, 보관된 뒤에도 언제든지 커뮤니티 페이지에서 복원할 수 있습니다.
+community-sensitive = 민감한 커뮤니티
+community-sensitive-description = 이 커뮤니티의 모든 게시물은 민감한 콘텐츠로 취급됩니다. 방문자는 입장 전에 확인해야 합니다.
+community-sensitive-interstitial = 이 커뮤니티에는 민감한 콘텐츠가 포함되어 있습니다. 계속하시겠습니까?
+community-sensitive-interstitial-settings = 이 경고를 건너뛰려면 다음에서 민감한 콘텐츠 표시를 켜세요:
+community-sensitive-enter = 입장
+community-sensitive-leave = 돌아가기
+community-pinned-posts = 고정된 그림
+community-pin-post = 고정
+community-unpin-post = 고정 해제
+community-federated-posts = 연합우주에서
 
 community-delete-danger-zone = 위험 구역
 community-delete-warning = 커뮤니티를 삭제하면 되돌릴 수 없습니다. 이 커뮤니티의 모든 게시물도 함께 삭제됩니다.
@@ -296,6 +477,8 @@ invitation-reject = 거절
 invitation-accepted = 초대를 수락했습니다
 invitation-rejected = 초대를 거절했습니다
 invitation-sent-at = 보낸 시각
+invitation-expires-at = 만료 시각
+invitation-expires-soon = 곧 만료
 
 community-drawing-tool = 그림판
 community-drawing-width = 너비
@@ -307,6 +490,18 @@ community-drawing-portrait = 세로
 community-no-posts = 게시된 그림이 없습니다.
 community-drawing-post-error = 그림을 게시하는 도중 오류가 발생했습니다. 다시 제출해 보세요.
 community-slug-conflict-error = 이 ID를 가진 커뮤니티가 이미 존재합니다. 다른 ID를 선택해 주세요.
+community-posting-window = 게시 가능 시간
+community-posting-window-description = 멤버는 매일 이 시간대에만 게시할 수 있습니다. 소유자와 모더레이터는 언제든지 게시할 수 있습니다. 두 시간을 모두 비워 두면 언제든지 게시할 수 있습니다.
+community-posting-window-timezone = 시간대
+community-posting-window-opens-at = 시작 시각
+community-posting-window-closes-at = 종료 시각
+community-posting-window-invalid = 올바른 시간대와 서로 다른 두 시각을 입력해 주세요.
+community-default-hashtags = 기본 해시태그
+community-default-hashtags-description = 이 커뮤니티에 게시되는 모든 글에 이 해시태그가 추가됩니다. 작성자는 글을 수정해 지울 수 있습니다.
+community-categories = 카테고리
+community-categories-description = 커뮤니티 페이지에서 이 커뮤니티가 표시될 카테고리를 선택하세요.
+community-categories-all = 전체
+community-posting-window-closed = 이 커뮤니티는 지금 게시물을 받지 않습니다. { $opens_at }부터 게시할 수 있습니다.
 
 profile-link = 링크
 profile-manage = 관리
@@ -318,7 +513,14 @@ profile-public-community-posts = 공개 커뮤니티에서 그린 그림
 profile-public-community-posts-nil = 공개 커뮤니티에서 그린 그림이 없습니다.
 profile-private-community-posts = 비공개 커뮤니티에서 그린 그림 (프로필에 공개되지 않음)
 profile-private-community-posts-nil = 비공개 커뮤니티에서 그린 그림이 없습니다.
+profile-pinned-posts = 고정된 그림
+profile-pin-post = 프로필에 고정
+profile-unpin-post = 프로필 고정 해제
+profile-pin-limit-reached = 최대 5개까지 고정할 수 있습니다.
 profile-settings = 프로필 설정
+profile-avatar = 아바타
+profile-avatar-upload = 아바타 업로드
+profile-avatar-help = PNG 또는 JPEG, 최대 1MB.
 profile-link-management = 링크 관리
 profile-link-requires-verified-email = 이메일 인증을 완료해야 링크를 추가할 수 있습니다.
 profile-link-order = 순서
@@ -386,19 +588,82 @@ account-change-email-verify = 이메일 인증
 account-change-email-error-token-mismatch = 인증 코드가 일치하지 않습니다.
 account-change-email-error-token-expired = 인증 코드가 만료되었습니다.
 account-change-email-error-already-verified = 이미 인증된 이메일 주소입니다.
+account-change-email-error-domain-blocked = 이 이메일 도메인은 사용할 수 없습니다.
 account-change-email-success = 이메일 주소가 인증되었습니다.
 account-change-email-subject = 오이카페 이메일 주소 인증 코드
+account-export-email-subject = 오이카페 데이터 내보내기가 준비되었습니다
+account-export-email-body = 데이터 내보내기가 준비되었습니다. 24시간 안에 아래 링크에서 내려받으세요:
+community-export-email-subject = { $community } 커뮤니티 내보내기가 준비되었습니다
 account-delete = 계정 삭제
-account-delete-warning = 계정을 삭제하면 모든 데이터가 삭제되며, 이 작업은 되돌릴 수 없습니다. 커뮤니티를 소유하고 있는 경우 먼저 소유권을 이전하거나 커뮤니티를 삭제해야 합니다.
-account-delete-confirm = 정말로 계정을 삭제하시겠습니까? 이 작업은 되돌릴 수 없습니다.
+account-delete-warning = 계정은 { $days }일 후에 삭제됩니다. 그 전까지는 계정 복구 페이지에서 로그인하여 복구할 수 있으며, 그 후에는 모든 데이터가 영구적으로 삭제됩니다. 커뮤니티를 소유하고 있는 경우 먼저 소유권을 이전하거나 커뮤니티를 삭제해야 합니다.
+account-delete-details = 모든 기기에서 로그아웃되고 공개된 게시물이 즉시 숨겨지며, 나를 팔로우하는 다른 서버에도 계정 삭제가 알려집니다. 삭제가 확정되면 게시물과 댓글이 삭제되고, 그림과 리플레이 파일이 저장소에서 지워집니다.
+account-delete-cancel = 계정 유지하기
+account-delete-confirm = 정말로 계정을 삭제하시겠습니까?
 account-delete-button = 계정 삭제
+account-delete-scheduled = 계정이 { $days }일 후에 삭제됩니다. 그 전까지는 계정을 복구할 수 있습니다.
+account-delete-email-subject = 계정 삭제가 예약되었습니다
+account-delete-email-body = 계정이 { $date }에 삭제됩니다. 마음이 바뀌었다면 그 전에 아래 링크에서 계정을 복구할 수 있습니다.
+account-delete-restore = 계정 복구
+account-delete-restore-description = 삭제 예정인 계정에 로그인하여 복구합니다. 숨겨진 게시물도 다시 보이게 됩니다.
+account-delete-restore-button = 계정 복구
+account-delete-restore-totp = 2단계 인증을 사용하는 계정만 입력하면 됩니다.
+account-delete-cancelled = 계정이 복구되었습니다. 다시 로그인할 수 있습니다.
+account-delete-cancel-failed = 해당 아이디와 비밀번호로 삭제 예정인 계정을 찾을 수 없습니다.
 account-language-settings = 언어 설정
 account-language-auto = 자동
 account-language-current = 현재 언어
+account-language-fallbacks = 대체 언어
+account-language-fallbacks-help = 선택한 언어에 없는 문구를 순서대로 찾아볼 언어 코드(ko, ja, en, zh, es)를 쉼표로 구분해 입력하세요. 마지막에는 항상 영어가 사용됩니다.
 account-language-settings-save = 언어 설정 저장
 account-content-settings = 콘텐츠 설정
 account-show-sensitive-content = 민감한 콘텐츠 표시
 account-content-settings-save = 콘텐츠 설정 저장
+account-feed-settings = 홈 피드
+account-feed-algorithm-chronological = 최신순
+account-feed-algorithm-engagement = 반응 많은 순
+account-feed-algorithm-following-first = 팔로우한 사람 먼저
+account-feed-settings-save = 피드 설정 저장
+account-feed-settings-saved = 피드 설정을 저장했습니다.
+account-follower-settings = 팔로워
+account-is-locked = 새 팔로워를 직접 승인
+account-is-locked-help = 다른 서버의 사용자는 승인을 받아야 나를 팔로우할 수 있습니다.
+account-follower-settings-save = 팔로워 설정 저장
+follow-requests = 팔로우 요청
+follow-requests-none = 대기 중인 팔로우 요청이 없습니다
+follow-request-accept = 수락
+follow-request-reject = 거절
+follow-request-accepted = 팔로우 요청을 수락했습니다
+follow-request-rejected = 팔로우 요청을 거절했습니다
+account-default-co-moderators = 기본 공동 운영자
+account-default-co-moderators-description = 새로 만드는 모든 커뮤니티에 이 사용자들이 운영자로 지정됩니다. 비공개 커뮤니티에는 바로 추가되고, 그 외 커뮤니티에는 운영자 초대가 발송됩니다.
+account-default-co-moderators-login-names = 로그인 이름 (공백 또는 쉼표로 구분)
+account-default-co-moderators-save = 공동 운영자 저장
+account-notification-preferences = 푸시 알림
+account-notification-preferences-description = 기기로 보낼 알림을 선택하세요.
+account-notification-preferences-save = 알림 설정 저장
+account-notification-preferences-saved = 알림 설정을 저장했습니다
+totp = 2단계 인증
+totp-description = 로그인할 때 비밀번호와 함께 인증 앱의 코드를 요구합니다.
+totp-enable = 2단계 인증 설정
+totp-disable = 2단계 인증 해제
+totp-status-enabled = 2단계 인증이 활성화되어 있습니다. 해제하려면 비밀번호를 입력하세요.
+totp-setup-scan = 인증 앱으로 이 QR 코드를 스캔한 뒤 표시되는 6자리 코드를 입력하세요.
+totp-setup-manual = 또는 이 키를 직접 입력하세요:
+totp-code = 인증 코드
+totp-verify = 확인
+totp-challenge-description = 인증 앱의 6자리 코드 또는 백업 코드 중 하나를 입력하세요.
+totp-backup-codes = 백업 코드
+totp-backup-codes-description = 이 코드들을 안전한 곳에 보관하세요. 인증 앱을 사용할 수 없을 때 각 코드로 한 번씩 로그인할 수 있습니다. 다시 표시되지 않습니다.
+totp-enabled = 2단계 인증이 활성화되었습니다.
+totp-disabled = 2단계 인증이 해제되었습니다.
+totp-error-invalid-code = 인증 코드가 올바르지 않습니다.
+totp-error-unavailable = 이 서버에서는 2단계 인증을 사용할 수 없습니다.
+oauth-authorize-title = 앱 권한 부여
+oauth-authorize-description = { $client }에서 계정에 접근하려고 합니다. 로그인한 상태에서 할 수 있는 모든 작업을 할 수 있게 됩니다.
+oauth-authorize-approve = 권한 부여
+oauth-authorize-deny = 거부
+account-default-co-moderators-user-not-found = @{ $login_name } 사용자가 존재하지 않습니다.
+account-default-co-moderators-self = 자기 자신을 공동 운영자로 추가할 수 없습니다.
 
 cucumber-save = 저장
 cucumber-undo = 실행 취소
@@ -1,6 +1,7 @@
 brand = 黄瓜咖啡馆
 email-from-address = 黄瓜咖啡馆 <noreply@oeee.cafe>
 error-404 = 页面未找到
+error-410 = 此页面已被删除
 error-403 = 您无权访问此页面。
 
 about = 黄瓜画板是一个可以画画的留言板。
@@ -31,6 +32,7 @@ notification-mention = 提及
 notification-post-reply = 作品回复
 notification-comment-reply = 评论回复
 notification-community-post = 新社区作品
+notification-follower-moved = 关注者已迁移
 
 # Notification action messages with FluentArgs for proper word order in different languages
 notification-action-commented-on = 评论了{ $postTitle }
@@ -44,9 +46,13 @@ notification-action-replied-to-post-with-title = 回复了您的作品{ $postTit
 notification-action-replied-to-comment = 回复了{ $postTitle }的评论
 notification-action-posted-in-community = 在您的社区发布了作品
 notification-action-posted-in-community-with-title = 在您的社区发布了作品：{ $postTitle }
+notification-action-follower-moved = 已迁移到新账号，现在由新账号关注您
 
 notification-mark-all-read = 全部标记为已读
+notification-filter-all = 全部
+loading-more-notifications = 加载更多通知...
 notification-mark-read = 标记为已读
+notification-and-others = 等 { $count } 人
 notification-delete = 删除
 notification-delete-confirm = 确定要删除此通知吗？
 
@@ -67,18 +73,29 @@ push-notification-comment-reply-body = { $name }回复了您的评论
 push-notification-community-post-title = 新社区作品
 push-notification-community-post-body = { $name }在您的社区发布了作品
 push-notification-community-post-body-with-title = { $name }在您的社区发布了作品：{ $title }
+push-notification-follower-moved-title = 关注者已迁移
+push-notification-follower-moved-body = { $name }已迁移到新账号，现在由新账号关注您
 push-notification-community-invite-title = 社区邀请
 push-notification-community-invite-body = { $inviter }邀请您加入@{ $community }
 push-notification-invite-accepted-title = 邀请已接受
 push-notification-invite-accepted-body = { $accepter }接受了您加入@{ $community }的邀请
 push-notification-invite-declined-title = 邀请已拒绝
 push-notification-invite-declined-body = { $decliner }拒绝了您加入@{ $community }的邀请
+push-notification-join-request-approved-title = 加入申请已批准
+push-notification-join-request-approved-body = 您现在是@{ $community }的成员了
+push-notification-join-request-rejected-title = 加入申请被拒绝
+push-notification-join-request-rejected-body = 您加入@{ $community }的申请被拒绝了
+push-notification-post-approved-title = 帖子已批准
+push-notification-post-approved-body = 您在@{ $community }的帖子已发布
+push-notification-post-rejected-title = 帖子未获批准
+push-notification-post-rejected-body = 您在@{ $community }的帖子未获批准，已退回草稿箱
 
 sign-in = 登录
 sign-up = 注册
 sign-out = 退出
 
 message-incorrect-credentials = 用户名或密码错误。
+message-too-many-attempts = 尝试次数过多，请稍后再试。
 login-name-conflict-error = 此用户名已被社区使用。请选择其他用户名。
 
 forgot-password = 忘记密码？
@@ -120,8 +137,17 @@ community-stats = 社区统计
 community-stats-posts = 帖子
 community-stats-contributors = 贡献者
 community-stats-comments = 评论
+community-leaderboard = 活跃贡献者
+community-leaderboard-week = 本周
+community-leaderboard-month = 本月
+community-leaderboard-all-time = 全部时间
+community-leaderboard-score = { $score } 分
+community-leaderboard-counts = 作品 { $posts } 个 · 收到回应 { $reactions } 个 · 收到评论 { $comments } 条
+community-leaderboard-your-rank = 您的排名：第 { $rank } 名（{ $score } 分）
+community-leaderboard-empty = 此期间暂无贡献。
 
 edit = 编辑
+comment-edited = (已编辑)
 edit-community-button = 移至社区
 save = 保存
 cancel = 取消
@@ -129,6 +155,9 @@ delete = 删除
 
 post-created-at = 创作时间
 post-published-at = 发布时间
+post-edited = 已编辑
+post-history = 编辑记录
+post-history-empty = 此帖子尚未被编辑。
 post-duration = 用时
 post-replay = 回放
 post-author = 作者
@@ -141,6 +170,11 @@ post-no-comments = 暂无评论
 post-no-comments-signin = 登录后发表评论
 post-comment = 发表评论
 post-publish = 发布
+post-schedule = 定时发布
+post-scheduled-for = 定时发布时间
+post-schedule-hint = 选择时间后点击定时发布即可稍后发布。时间以韩国标准时间为准。
+post-submitted-for-approval = 帖子已提交。社区所有者或版主批准后即会发布。
+post-pending-approval = 已提交审核
 post-move-community = 移动到此社区
 post-move-community-title = 移动帖子到社区
 post-move-post-to-move = 要移动的帖子
@@ -160,6 +194,8 @@ post-delete-confirm = 确定要删除这幅画吗？
 post-relay-enabled-notice = 这幅画可以接续创作。点击画作试试看！
 post-relay = 接力
 post-reply-with-drawing = 用画回复
+post-translate = 翻译
+post-translation-failed = 翻译失败，请稍后再试。
 post-reply-select-tool = 选择工具和画布尺寸
 post-child-posts = 回复
 post-replying-to = 回复给:
@@ -170,6 +206,12 @@ post-reaction = 反应
 post-reactions = 反应
 post-share = 分享
 post-share-copied = 链接已复制到剪贴板！
+post-boost = 转嘟
+post-unboost = 取消转嘟
+report-object = 举报
+report-object-comment = 有什么问题？（可选）
+report-object-submit = 提交举报
+report-object-sent = 已举报，谢谢。
 post-report = 举报
 post-report-title = 举报此帖子
 post-report-description-label = 举报原因
@@ -178,6 +220,57 @@ post-report-submit = 提交举报
 post-report-success = 帖子已被举报。感谢您帮助维护社区安全。
 post-report-error = 提交举报失败。请重试。
 post-report-confirm = 您确定要举报此帖子吗？
+post-report-reason-label = 类别
+post-report-reason-spam = 垃圾信息
+post-report-reason-harassment = 骚扰
+post-report-reason-sexual-content = 色情内容
+post-report-reason-violence = 暴力
+post-report-reason-copyright = 侵犯版权
+post-report-reason-other = 其他
+admin-reports = 举报
+admin-reports-empty = 没有待处理的举报。
+admin-reports-reporter = 举报者
+admin-reports-reason = 原因
+admin-reports-detail = 详情
+admin-reports-post = 帖子
+admin-reports-reported-at = 举报时间
+admin-reports-resolve = 处理
+admin-reports-dismiss = 驳回
+admin-reports-objects = 被举报的评论和联邦内容
+admin-reports-objects-targets = 举报对象
+admin-reports-objects-remote = 来自其他实例
+admin-announcements = 公告
+admin-announcements-new = 新公告
+admin-announcements-message = 内容
+admin-announcements-level = 级别
+admin-announcements-level-info = 信息
+admin-announcements-level-warning = 警告
+admin-announcements-level-critical = 紧急
+admin-announcements-starts-at = 开始时间
+admin-announcements-ends-at = 结束时间
+admin-announcements-time-help = 时间为韩国时间。开始时间留空则立即显示，结束时间留空则一直显示到删除为止。
+admin-announcements-dismissible = 用户可以关闭
+admin-announcements-create = 创建公告
+admin-announcements-delete = 删除
+admin-announcements-empty = 没有公告。
+admin-announcements-content-help = 各语言的内容为可选项，留空的语言将显示默认内容。
+admin-announcements-content-ko = 韩语
+admin-announcements-content-en = 英语
+admin-announcements-content-ja = 日语
+admin-announcements-content-zh = 中文
+admin-announcements-active = 启用
+admin-announcements-activate = 启用
+admin-announcements-deactivate = 停用
+admin-community-categories = 社区分类
+admin-community-categories-new = 新分类
+admin-community-categories-name = 名称
+admin-community-categories-slug = 标识
+admin-community-categories-position = 顺序
+admin-community-categories-help = 标识用于链接，只能使用 a-z、0-9 和 -。顺序较小的分类排在前面。
+admin-community-categories-create = 创建分类
+admin-community-categories-delete = 删除
+admin-community-categories-empty = 没有分类。
+announcement-dismiss = 关闭
 
 allow-relay = 允许接续创作
 sensitive = 敏感内容
@@ -189,12 +282,25 @@ draft-empty = 您还没有草稿
 
 # Hashtags
 trending-hashtags = 热门标签
+trending-hashtags-description = 过去24小时内被最多绘画使用的标签
+trending-hashtags-updated = 更新于
+trending-hashtags-delta = 最近一小时的绘画数与前一小时的差值
+trending-hashtags-empty = 目前没有热门标签
 hashtag-post-count = 篇作品
 hashtag-no-posts = 暂无使用此标签的作品
+hashtag-follow = 关注
+hashtag-unfollow = 取消关注
+hashtag-following = 已关注的话题标签
+hashtag-following-empty = 你还没有关注任何话题标签
 hashtag-discovery = 发现标签
 hashtag-search-placeholder = 搜索标签...
 search = 搜索
 search-results-for = 搜索结果
+search-posts = 搜索画作
+search-posts-placeholder = 搜索标题和描述...
+search-no-results = 没有找到匹配的画作
+search-previous = 上一页
+search-next = 下一页
 no-hashtags-found = 未找到标签
 sort-by = 排序
 sort-trending = 热门
@@ -213,6 +319,8 @@ collaborate-max-participants-label = 最大参与者数
 collaborate-participants = 人
 collaborate-public-session-label = 显示在公共大厅
 collaborate-public-session-description = 取消选中创建私人会话 (仅链接访问)
+collaborate-record-replay-label = 录制回放
+collaborate-record-replay-description = 保存的作品将包含所有参与者笔画的回放
 collaborate-create-button = 创建会话
 collaborate-active-sessions = 活跃的公共会话
 collaborate-no-sessions = 没有活跃的公共会话。创建一个开始吧！
@@ -258,16 +366,40 @@ community-badge-private = 私密
 
 community-members = 成员
 community-invite-user = 邀请用户
-community-invite-username = 邀请用户名
+community-invite-username = 要邀请的用户名
+community-invite-username-hint = 多个用户名请用逗号或空格分隔
 community-invite-button = 发送邀请
-community-invite-success = 邀请已发送
+community-invite-invited-users = 已向 { $names } 发送邀请
 community-invite-error = 发送邀请失败
 community-invite-user-not-found = 未找到用户
-community-invite-already-member = 该用户已是成员
-community-invite-already-invited = 该用户已有待处理的邀请
+community-invite-users-already-members = 已是成员：{ $names }
+community-invite-users-already-invited = 已有待处理的邀请：{ $names }
+community-invite-users-not-found = 未找到用户：{ $names }
+community-invite-too-many = 一次最多可以邀请 { $max } 位用户
 private-community-no-access = 您无权访问此私有社区。
 community-remove-member = 移除成员
 community-remove-member-confirm = 确定要移除此成员吗？
+community-member-promote = 设为版主
+community-member-demote = 取消版主
+community-join-requests = 加入申请
+community-join-requests-pending = 待处理的加入申请
+community-accepts-join-requests = 接受加入申请
+community-accepts-join-requests-description = 已登录用户可以申请加入。所有者和版主在成员页面批准或拒绝申请。
+community-join-request-button = 申请加入
+community-join-request-pending = 您的加入申请正在等待审核。
+community-join-request-sent = 已发送加入申请。
+community-join-request-requested-at = 申请时间
+community-join-request-approve = 批准
+community-join-request-reject = 拒绝
+community-join-request-reject-confirm = 确定要拒绝此加入申请吗？
+community-post-approval = 帖子审核
+community-require-post-approval = 新帖子发布前需要审核
+community-require-post-approval-description = 成员的帖子会在社区页面的审核队列中等待，直到所有者或版主批准。所有者和版主的帖子直接发布。
+community-pending-posts = 待审核的帖子
+community-pending-posts-description = 在获得批准之前，这些帖子仅对所有者和版主可见。
+community-pending-post-approve = 批准
+community-pending-post-reject = 拒绝
+community-pending-post-reject-confirm = 确定要拒绝此帖子吗？它将退回作者的草稿箱。
 community-retract-invitation = 撤回邀请
 community-retract-invitation-confirm = 确定要撤回此邀请吗？
 community-member-role = 角色
@@ -279,6 +411,55 @@ community-leave = 退出社区
 community-leave-confirm = 您确定要退出此社区吗？
 community-left-success = 您已退出社区
 community-owner-cannot-leave = 所有者无法退出。请删除社区。
+community-transfer = 转让所有权
+community-transfer-description = 新所有者需要通过发送到其已验证邮箱的链接进行确认。你将保留版主身份。
+community-transfer-button = 发起转让
+community-transfer-confirm = 确定要转让此社区的所有权吗？
+community-transfer-pending = 等待以下用户确认：
+community-transfer-cancel = 取消转让
+community-transfer-sent = 已通过邮件向新所有者发送确认链接。
+community-transfer-no-email = 该用户没有已验证的邮箱地址。
+community-transfer-accept = 接受所有权
+community-transfer-accept-description = 你被邀请成为此社区的所有者。
+community-transfer-confirmed = 你现在是此社区的所有者。
+community-transfer-cancelled = 所有权转让已取消。
+community-transfer-email-subject = { $owner } 想将 { $community } 转让给你
+community-transfer-email-body = { $owner } 邀请你成为社区 { $community } 的所有者。请打开下面的链接接受或拒绝。链接将在 72 小时后失效。
+community-invite-links = 邀请链接
+community-invite-links-description = 任何拥有链接的人都可以作为成员加入此社区。留空表示不限制。
+community-invite-link-max-uses = 最大使用次数
+community-invite-link-expires-in-days = 有效期(天)
+community-invite-link-create = 创建邀请链接
+community-invite-link = 链接
+community-invite-link-uses = 使用次数
+community-invite-link-expires-at = 过期时间
+community-invite-link-inactive = 已失效
+community-invite-link-never-expires = 永不
+community-invite-link-revoke = 撤销
+community-invite-link-revoke-confirm = 要撤销此邀请链接吗?
+community-invite-link-invalid = 使用次数须为正数,有效期最多 365 天。
+community-invite-link-created = 邀请链接已创建。
+community-invite-link-unusable = 此邀请链接已过期或已达到使用上限。
+community-invite-link-joined = 你已加入该社区。
+community-invite-link-join-title = 加入社区
+community-invite-link-already-member = 你已经是此社区的成员。
+community-invite-link-join-description = 你受邀加入此社区。
+community-invite-link-join = 加入
+community-invite-link-login-to-join = 登录后加入
+community-archived-notice = 该社区因长时间没有新帖子已被归档，不再显示在社区列表中。
+community-unarchive-button = 恢复社区
+community-archive-email-subject = { $community } 即将被归档
+community-archive-email-body = 社区 { $community } 已长时间没有新帖子，将在 { $days } 天后被归档。归档的社区不会显示在社区列表中。在此之前发布帖子即可保持活跃，归档后也可以随时在社区页面恢复。
+community-sensitive = 敏感社区
+community-sensitive-description = 此社区的所有帖子都被视为敏感内容。访客进入前需要确认。
+community-sensitive-interstitial = 此社区包含敏感内容。是否继续？
+community-sensitive-interstitial-settings = 如需跳过此警告，请在以下页面中开启显示敏感内容：
+community-sensitive-enter = 进入
+community-sensitive-leave = 返回
+community-pinned-posts = 置顶作品
+community-pin-post = 置顶
+community-unpin-post = 取消置顶
+community-federated-posts = 来自联邦宇宙
 
 community-delete-danger-zone = 危险区域
 community-delete-warning = 删除社区是永久性的，无法撤销。此社区中的所有作品也将被删除。
@@ -296,6 +477,8 @@ invitation-reject = 拒绝
 invitation-accepted = 已接受邀请
 invitation-rejected = 已拒绝邀请
 invitation-sent-at = 发送时间
+invitation-expires-at = 过期时间
+invitation-expires-soon = 即将过期
 
 community-drawing-tool = 画板
 community-drawing-width = 宽度
@@ -307,6 +490,18 @@ community-drawing-portrait = 纵向
 community-no-posts = 暂无作品
 community-drawing-post-error = 发布作品时出错，请重试
 community-slug-conflict-error = 已存在使用此ID的社区，请选择不同的ID。
+community-posting-window = 发帖时间
+community-posting-window-description = 成员每天只能在此时间段内发布。所有者和版主可以随时发布。两个时间都留空则允许随时发布。
+community-posting-window-timezone = 时区
+community-posting-window-opens-at = 开始时间
+community-posting-window-closes-at = 结束时间
+community-posting-window-invalid = 请输入有效的时区和两个不同的时间。
+community-default-hashtags = 默认话题标签
+community-default-hashtags-description = 发布到本社区的每篇帖子都会添加这些话题标签。作者可以通过编辑帖子将其删除。
+community-categories = 分类
+community-categories-description = 选择本社区在社区页面中所属的分类。
+community-categories-all = 全部
+community-posting-window-closed = 该社区目前不接受投稿。将于 { $opens_at } 开放发布。
 
 profile-link = 链接
 profile-manage = 管理
@@ -318,7 +513,14 @@ profile-public-community-posts = 公开社区的作品
 profile-public-community-posts-nil = 暂无公开社区的作品
 profile-private-community-posts = 私密社区的作品（个人资料中不显示）
 profile-private-community-posts-nil = 暂无私密社区的作品
+profile-pinned-posts = 置顶作品
+profile-pin-post = 置顶到个人主页
+profile-unpin-post = 取消置顶
+profile-pin-limit-reached = 最多可以置顶 5 个作品。
 profile-settings = 个人资料设置
+profile-avatar = 头像
+profile-avatar-upload = 上传头像
+profile-avatar-help = PNG 或 JPEG，最大 1MB。
 profile-link-management = 链接管理
 profile-link-requires-verified-email = 需要完成邮箱验证才能添加链接
 profile-link-order = 顺序
@@ -386,19 +588,82 @@ account-change-email-verify = 验证邮箱
 account-change-email-error-token-mismatch = 验证码不匹配
 account-change-email-error-token-expired = 验证码已过期
 account-change-email-error-already-verified = 此邮箱地址已验证
+account-change-email-error-domain-blocked = 无法使用此邮箱域名。
 account-change-email-success = 邮箱地址已验证
 account-change-email-subject = 黄瓜咖啡馆邮箱验证码
+account-export-email-subject = 黄瓜咖啡馆数据导出已准备就绪
+account-export-email-body = 您的数据导出已准备就绪。请在24小时内通过以下链接下载:
+community-export-email-subject = 社区 { $community } 的导出已就绪
 account-delete = 删除账户
-account-delete-warning = 删除账户将永久删除您的所有数据。此操作无法撤销。如果您拥有任何社区，必须先转让所有权或删除它们。
-account-delete-confirm = 您确定要删除账户吗？此操作无法撤销。
+account-delete-warning = 您的账户将在 { $days } 天后被删除。在此之前，您可以在账户恢复页面登录以恢复账户。之后，您的所有数据将被永久删除。如果您拥有任何社区，必须先转让所有权或删除它们。
+account-delete-details = 你将在所有设备上退出登录，已发布的帖子会立即隐藏，关注你的服务器也会收到账号已删除的通知。删除生效后，帖子和评论将被删除，画作和回放文件将从存储中清除。
+account-delete-cancel = 保留我的账号
+account-delete-confirm = 您确定要删除账户吗？
 account-delete-button = 删除账户
+account-delete-scheduled = 您的账户将在 { $days } 天后被删除。在此之前可以恢复。
+account-delete-email-subject = 您的账户已预定删除
+account-delete-email-body = 您的账户将于 { $date } 被删除。如果改变主意，可以在此之前通过下方链接恢复账户。
+account-delete-restore = 恢复账户
+account-delete-restore-description = 登录以恢复预定删除的账户。被隐藏的帖子将重新显示。
+account-delete-restore-button = 恢复账户
+account-delete-restore-totp = 仅在账户启用了双重验证时需要填写。
+account-delete-cancelled = 您的账户已恢复，可以重新登录。
+account-delete-cancel-failed = 没有与该登录名和密码匹配的待删除账户。
 account-language-settings = 语言设置
 account-language-auto = 自动
 account-language-current = 当前语言
+account-language-fallbacks = 备用语言
+account-language-fallbacks-help = 当所选语言缺少某条文本时，按顺序尝试的语言代码（ko, ja, en, zh, es），用逗号分隔。最后总是使用英语。
 account-language-settings-save = 保存语言设置
 account-content-settings = 内容设置
 account-show-sensitive-content = 显示敏感内容
 account-content-settings-save = 保存内容设置
+account-feed-settings = 首页动态
+account-feed-algorithm-chronological = 最新优先
+account-feed-algorithm-engagement = 互动最多优先
+account-feed-algorithm-following-first = 关注的人优先
+account-feed-settings-save = 保存动态设置
+account-feed-settings-saved = 动态设置已保存。
+account-follower-settings = 关注者
+account-is-locked = 手动批准新关注者
+account-is-locked-help = 其他服务器的用户需要经过批准才能关注你。
+account-follower-settings-save = 保存关注者设置
+follow-requests = 关注请求
+follow-requests-none = 没有待处理的关注请求
+follow-request-accept = 接受
+follow-request-reject = 拒绝
+follow-request-accepted = 已接受关注请求
+follow-request-rejected = 已拒绝关注请求
+account-default-co-moderators = 默认协同版主
+account-default-co-moderators-description = 这些用户将成为你创建的每个社区的版主。私密社区会直接添加他们，其他社区会向他们发送版主邀请。
+account-default-co-moderators-login-names = 登录名（用空格或逗号分隔）
+account-default-co-moderators-save = 保存协同版主
+account-notification-preferences = 推送通知
+account-notification-preferences-description = 选择要发送到你的设备的通知。
+account-notification-preferences-save = 保存通知设置
+account-notification-preferences-saved = 通知设置已保存
+totp = 两步验证
+totp-description = 登录时除密码外还需要输入身份验证应用中的验证码。
+totp-enable = 设置两步验证
+totp-disable = 关闭两步验证
+totp-status-enabled = 两步验证已开启。输入密码即可关闭。
+totp-setup-scan = 请用身份验证应用扫描此二维码，然后输入显示的6位验证码。
+totp-setup-manual = 或手动输入此密钥：
+totp-code = 验证码
+totp-verify = 验证
+totp-challenge-description = 请输入身份验证应用中的6位验证码，或任意一个备用码。
+totp-backup-codes = 备用码
+totp-backup-codes-description = 请将这些备用码保存在安全的地方。无法使用身份验证应用时，每个备用码可用于登录一次。它们不会再次显示。
+totp-enabled = 两步验证已开启。
+totp-disabled = 两步验证已关闭。
+totp-error-invalid-code = 验证码不正确。
+totp-error-unavailable = 此服务器不支持两步验证。
+oauth-authorize-title = 授权应用
+oauth-authorize-description = { $client } 请求访问你的账号。它将能够执行你登录后可以进行的所有操作。
+oauth-authorize-approve = 授权
+oauth-authorize-deny = 拒绝
+account-default-co-moderators-user-not-found = 用户 @{ $login_name } 不存在。
+account-default-co-moderators-self = 不能将自己添加为协同版主。
 
 cucumber-save = 保存
 cucumber-undo = 撤销
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_ap_likes_post_id;
+DROP TABLE IF EXISTS ap_likes;
@@ -0,0 +1,22 @@
+-- Track ActivityPub Like activities that map onto ❤️ reactions
+CREATE TABLE ap_likes (
+    iri TEXT PRIMARY KEY NOT NULL,
+    reaction_iri TEXT NOT NULL,
+    post_id UUID NOT NULL,
+    actor_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    CONSTRAINT ap_likes_reaction_iri_fk
+        FOREIGN KEY (reaction_iri) REFERENCES reactions(iri) ON DELETE CASCADE,
+
+    CONSTRAINT ap_likes_post_id_fk
+        FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+
+    CONSTRAINT ap_likes_actor_id_fk
+        FOREIGN KEY (actor_id) REFERENCES actors(id) ON DELETE CASCADE,
+
+    CONSTRAINT ap_likes_unique_reaction
+        UNIQUE (reaction_iri)
+);
+
+CREATE INDEX idx_ap_likes_post_id ON ap_likes(post_id);
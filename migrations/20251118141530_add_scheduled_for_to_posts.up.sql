@@ -0,0 +1,4 @@
+ALTER TABLE posts ADD COLUMN scheduled_for TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_posts_scheduled_for ON posts(scheduled_for)
+    WHERE scheduled_for IS NOT NULL AND published_at IS NULL;
@@ -0,0 +1,21 @@
+-- Posts featured at the top of a community, in owner-chosen order
+CREATE TABLE community_pinned_posts (
+    community_id UUID NOT NULL,
+    post_id UUID NOT NULL,
+    position INTEGER NOT NULL,
+    pinned_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    PRIMARY KEY (community_id, post_id),
+
+    CONSTRAINT community_pinned_posts_community_id_fk
+        FOREIGN KEY (community_id) REFERENCES communities(id) ON DELETE CASCADE,
+
+    CONSTRAINT community_pinned_posts_post_id_fk
+        FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+
+    CONSTRAINT community_pinned_posts_position_range
+        CHECK (position BETWEEN 1 AND 5),
+
+    CONSTRAINT community_pinned_posts_unique_position
+        UNIQUE (community_id, position)
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_federated_posts_community_id_published_at;
+DROP TABLE IF EXISTS federated_posts;
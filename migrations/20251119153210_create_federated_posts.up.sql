@@ -0,0 +1,24 @@
+-- Remote posts announced to this instance and surfaced in a local community
+CREATE TABLE federated_posts (
+    iri TEXT PRIMARY KEY NOT NULL,
+    announce_iri TEXT NOT NULL,
+    community_id UUID NOT NULL,
+    actor_id UUID NOT NULL,
+    url TEXT,
+    content_html TEXT NOT NULL DEFAULT '',
+    image_url TEXT,
+    image_width INTEGER,
+    image_height INTEGER,
+    is_sensitive BOOLEAN NOT NULL DEFAULT FALSE,
+    published_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    CONSTRAINT federated_posts_community_id_fk
+        FOREIGN KEY (community_id) REFERENCES communities(id) ON DELETE CASCADE,
+
+    CONSTRAINT federated_posts_actor_id_fk
+        FOREIGN KEY (actor_id) REFERENCES actors(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_federated_posts_community_id_published_at
+    ON federated_posts(community_id, published_at DESC);
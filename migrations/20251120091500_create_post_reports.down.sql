@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_post_reports_status_created_at;
+DROP INDEX IF EXISTS idx_post_reports_one_pending;
+DROP TABLE IF EXISTS post_reports;
+DROP TYPE IF EXISTS post_report_status;
+DROP TYPE IF EXISTS post_report_reason;
+ALTER TABLE users DROP COLUMN IF EXISTS is_admin;
@@ -0,0 +1,46 @@
+-- Site administrators who can work the moderation queue
+ALTER TABLE users ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TYPE post_report_reason AS ENUM (
+    'spam',
+    'harassment',
+    'sexual_content',
+    'violence',
+    'copyright',
+    'other'
+);
+
+CREATE TYPE post_report_status AS ENUM (
+    'pending',
+    'resolved',
+    'dismissed'
+);
+
+CREATE TABLE post_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    reporter_id UUID NOT NULL,
+    post_id UUID NOT NULL,
+    reason post_report_reason NOT NULL,
+    detail TEXT NOT NULL DEFAULT '',
+    status post_report_status NOT NULL DEFAULT 'pending',
+    resolved_by UUID,
+    resolved_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    CONSTRAINT post_reports_reporter_id_fk
+        FOREIGN KEY (reporter_id) REFERENCES users(id) ON DELETE CASCADE,
+
+    CONSTRAINT post_reports_post_id_fk
+        FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+
+    CONSTRAINT post_reports_resolved_by_fk
+        FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- A reporter can only have one open report per post
+CREATE UNIQUE INDEX idx_post_reports_one_pending
+    ON post_reports(reporter_id, post_id)
+    WHERE status = 'pending';
+
+CREATE INDEX idx_post_reports_status_created_at ON post_reports(status, created_at);
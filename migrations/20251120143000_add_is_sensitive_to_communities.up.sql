@@ -0,0 +1 @@
+ALTER TABLE communities ADD COLUMN is_sensitive BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,16 @@
+-- Posts a user has featured at the top of their profile
+CREATE TABLE pinned_posts (
+    user_id UUID NOT NULL,
+    post_id UUID NOT NULL,
+    pinned_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    PRIMARY KEY (user_id, post_id),
+
+    CONSTRAINT pinned_posts_user_id_fk
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+
+    CONSTRAINT pinned_posts_post_id_fk
+        FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_pinned_posts_user_id_pinned_at ON pinned_posts(user_id, pinned_at);
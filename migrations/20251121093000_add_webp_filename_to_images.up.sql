@@ -0,0 +1 @@
+ALTER TABLE images ADD COLUMN webp_filename TEXT;
@@ -0,0 +1,10 @@
+CREATE TABLE community_posting_windows (
+    community_id UUID PRIMARY KEY REFERENCES communities(id) ON DELETE CASCADE,
+    -- IANA name, e.g. 'Asia/Seoul'
+    timezone TEXT NOT NULL,
+    -- Daily window in local time; closes_at before opens_at wraps past midnight
+    opens_at TIME NOT NULL,
+    closes_at TIME NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT community_posting_windows_not_empty CHECK (opens_at <> closes_at)
+);
@@ -0,0 +1,5 @@
+-- Drop community_ownership_transfers table
+DROP TABLE IF EXISTS community_ownership_transfers;
+
+-- Drop community_ownership_transfer_status enum
+DROP TYPE IF EXISTS community_ownership_transfer_status;
@@ -0,0 +1,18 @@
+CREATE TYPE community_ownership_transfer_status AS ENUM ('pending', 'confirmed', 'cancelled');
+
+CREATE TABLE community_ownership_transfers (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+  community_id uuid NOT NULL REFERENCES communities(id) ON DELETE CASCADE,
+  current_owner_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  proposed_owner_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  token uuid NOT NULL UNIQUE,
+  expires_at timestamptz NOT NULL,
+  status community_ownership_transfer_status NOT NULL DEFAULT 'pending',
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- At most one pending transfer per community
+CREATE UNIQUE INDEX idx_community_ownership_transfers_pending
+  ON community_ownership_transfers(community_id)
+  WHERE status = 'pending';
@@ -0,0 +1,8 @@
+CREATE TABLE hashtag_follows (
+  user_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  hashtag_id uuid NOT NULL REFERENCES hashtags(id) ON DELETE CASCADE,
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (user_id, hashtag_id)
+);
+
+CREATE INDEX idx_hashtag_follows_hashtag_id ON hashtag_follows(hashtag_id);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_images_replay_not_archived;
+ALTER TABLE images DROP COLUMN IF EXISTS replay_archived_at;
@@ -0,0 +1,5 @@
+-- Set once the replay has been moved to cold storage under the replay-cold/ prefix
+ALTER TABLE images ADD COLUMN replay_archived_at TIMESTAMPTZ;
+
+CREATE INDEX idx_images_replay_not_archived ON images(created_at)
+WHERE replay_filename IS NOT NULL AND replay_archived_at IS NULL;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_communities_search_vector;
+ALTER TABLE communities DROP COLUMN IF EXISTS search_vector;
@@ -0,0 +1,7 @@
+-- Same 'simple' configuration as posts.search_vector so CJK names are not stemmed
+ALTER TABLE communities ADD COLUMN search_vector tsvector
+    GENERATED ALWAYS AS (
+        to_tsvector('simple', name || ' ' || slug || ' ' || description)
+    ) STORED;
+
+CREATE INDEX idx_communities_search_vector ON communities USING GIN (search_vector);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS user_default_co_moderators;
+ALTER TABLE community_invitations DROP COLUMN IF EXISTS role;
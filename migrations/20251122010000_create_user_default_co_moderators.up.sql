@@ -0,0 +1,11 @@
+-- Invitations can now offer a role other than plain membership
+ALTER TABLE community_invitations ADD COLUMN role community_member_role NOT NULL DEFAULT 'member';
+
+-- Users added as moderators to every community the owner creates
+CREATE TABLE user_default_co_moderators (
+  user_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  moderator_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (user_id, moderator_id),
+  CHECK (user_id <> moderator_id)
+);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_comment_reactions_actor_id;
+DROP INDEX IF EXISTS idx_comment_reactions_comment_id;
+DROP TABLE IF EXISTS comment_reactions;
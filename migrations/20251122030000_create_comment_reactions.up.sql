@@ -0,0 +1,22 @@
+CREATE TABLE comment_reactions (
+    iri TEXT PRIMARY KEY NOT NULL,
+    comment_id UUID NOT NULL,
+    actor_id UUID NOT NULL,
+    emoji TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    CONSTRAINT comment_reactions_comment_id_fk
+        FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+
+    CONSTRAINT comment_reactions_actor_id_fk
+        FOREIGN KEY (actor_id) REFERENCES actors(id) ON DELETE CASCADE,
+
+    CONSTRAINT comment_reactions_emoji_check
+        CHECK (emoji IS NOT NULL AND length(emoji) > 0),
+
+    CONSTRAINT comment_reactions_unique_reaction
+        UNIQUE (comment_id, actor_id, emoji)
+);
+
+CREATE INDEX idx_comment_reactions_comment_id ON comment_reactions(comment_id);
+CREATE INDEX idx_comment_reactions_actor_id ON comment_reactions(actor_id);
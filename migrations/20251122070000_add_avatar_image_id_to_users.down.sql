@@ -0,0 +1,12 @@
+ALTER TABLE users DROP COLUMN avatar_image_id;
+
+-- Avatar images are only referenced from users.avatar_image_id
+DELETE FROM images WHERE tool = 'upload';
+
+-- Remove upload from tool enum
+CREATE TYPE tool_new AS ENUM ('neo', 'tegaki', 'cucumber', 'neo-cucumber');
+
+ALTER TABLE images ALTER COLUMN tool TYPE tool_new USING tool::text::tool_new;
+
+DROP TYPE tool;
+ALTER TYPE tool_new RENAME TO tool;
@@ -0,0 +1,4 @@
+-- Uploaded (not drawn) images, such as avatars
+ALTER TYPE tool ADD VALUE 'upload';
+
+ALTER TABLE users ADD COLUMN avatar_image_id UUID REFERENCES images(id) ON DELETE SET NULL;
@@ -0,0 +1,25 @@
+DROP INDEX IF EXISTS idx_guestbook_entries_iri;
+DROP INDEX IF EXISTS idx_guestbook_entries_author_actor_id;
+
+-- Entries written by remote actors have no local author to restore
+DELETE FROM guestbook_entries
+WHERE author_actor_id IN (SELECT id FROM actors WHERE user_id IS NULL);
+
+ALTER TABLE guestbook_entries ADD COLUMN author_id uuid;
+
+UPDATE guestbook_entries
+SET author_id = (
+    SELECT a.user_id
+    FROM actors a
+    WHERE a.id = guestbook_entries.author_actor_id
+);
+
+ALTER TABLE guestbook_entries ALTER COLUMN author_id SET NOT NULL;
+
+ALTER TABLE guestbook_entries ADD CONSTRAINT guestbook_entries_author_id_fkey
+    FOREIGN KEY (author_id) REFERENCES users(id);
+
+ALTER TABLE guestbook_entries DROP CONSTRAINT fk_guestbook_entries_author_actor_id;
+ALTER TABLE guestbook_entries DROP COLUMN author_actor_id;
+ALTER TABLE guestbook_entries DROP COLUMN content_html;
+ALTER TABLE guestbook_entries DROP COLUMN iri;
@@ -0,0 +1,24 @@
+-- Guestbook entries can be written by remote actors, so reference the author's actor
+-- instead of a local user, and keep the IRI and HTML of federated entries
+
+ALTER TABLE guestbook_entries ADD COLUMN author_actor_id uuid;
+ALTER TABLE guestbook_entries ADD COLUMN content_html TEXT;
+ALTER TABLE guestbook_entries ADD COLUMN iri TEXT;
+
+UPDATE guestbook_entries
+SET author_actor_id = (
+    SELECT a.id
+    FROM actors a
+    WHERE a.user_id = guestbook_entries.author_id
+);
+
+ALTER TABLE guestbook_entries ALTER COLUMN author_actor_id SET NOT NULL;
+
+ALTER TABLE guestbook_entries ADD CONSTRAINT fk_guestbook_entries_author_actor_id
+    FOREIGN KEY (author_actor_id) REFERENCES actors(id) ON DELETE CASCADE;
+
+ALTER TABLE guestbook_entries DROP CONSTRAINT guestbook_entries_author_id_fkey;
+ALTER TABLE guestbook_entries DROP COLUMN author_id;
+
+CREATE INDEX idx_guestbook_entries_author_actor_id ON guestbook_entries(author_actor_id);
+CREATE UNIQUE INDEX idx_guestbook_entries_iri ON guestbook_entries(iri) WHERE iri IS NOT NULL;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS activity_deliveries;
+DROP TYPE IF EXISTS activity_delivery_status;
@@ -0,0 +1,24 @@
+CREATE TYPE activity_delivery_status AS ENUM (
+    'pending',   -- Queued, not attempted yet
+    'retrying',  -- Last attempt failed, another one is scheduled
+    'delivered', -- The inbox answered with a 2xx status
+    'failed'     -- Retries are exhausted
+);
+
+CREATE TABLE activity_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    activity_iri TEXT NOT NULL,
+    -- IRI of the activity's object, used to look deliveries up by post
+    object_iri TEXT,
+    inbox_url TEXT NOT NULL,
+    status activity_delivery_status NOT NULL DEFAULT 'pending',
+    last_status_code INTEGER,
+    last_error TEXT,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ,
+    UNIQUE (activity_iri, inbox_url)
+);
+
+CREATE INDEX idx_activity_deliveries_object_iri ON activity_deliveries(object_iri);
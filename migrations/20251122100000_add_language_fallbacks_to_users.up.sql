@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN language_fallbacks preferred_language[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,13 @@
+ALTER TABLE users ADD COLUMN is_locked BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE follow_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    follower_actor_id UUID NOT NULL REFERENCES actors(id) ON DELETE CASCADE,
+    following_actor_id UUID NOT NULL REFERENCES actors(id) ON DELETE CASCADE,
+    -- IRI of the incoming Follow, echoed back in the Accept or Reject
+    activity_iri TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (follower_actor_id, following_actor_id)
+);
+
+CREATE INDEX idx_follow_requests_following_actor_id ON follow_requests(following_actor_id);
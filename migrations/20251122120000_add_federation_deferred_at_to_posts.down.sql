@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_posts_federation_deferred_at;
+ALTER TABLE posts DROP COLUMN IF EXISTS federation_deferred_at;
@@ -0,0 +1,4 @@
+ALTER TABLE posts ADD COLUMN federation_deferred_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_posts_federation_deferred_at ON posts(federation_deferred_at)
+    WHERE federation_deferred_at IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Downscaled copies of drawings, keyed by the original's content-addressed filename
+CREATE TABLE image_thumbnails (
+    image_filename TEXT NOT NULL,
+    max_size INTEGER NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    thumbnail_filename TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (image_filename, max_size)
+);
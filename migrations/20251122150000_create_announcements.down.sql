@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS announcement_dismissals;
+DROP INDEX IF EXISTS idx_announcements_starts_at;
+DROP TABLE IF EXISTS announcements;
+DROP TYPE IF EXISTS announcement_level;
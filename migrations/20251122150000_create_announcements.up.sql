@@ -0,0 +1,35 @@
+CREATE TYPE announcement_level AS ENUM (
+    'info',
+    'warning',
+    'critical'
+);
+
+-- Site-wide messages shown at the top of every page
+CREATE TABLE announcements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    message TEXT NOT NULL,
+    level announcement_level NOT NULL DEFAULT 'info',
+    starts_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE,
+    dismissible BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    CONSTRAINT announcements_created_by_fk
+        FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_announcements_starts_at ON announcements(starts_at);
+
+CREATE TABLE announcement_dismissals (
+    announcement_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    dismissed_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (announcement_id, user_id),
+
+    CONSTRAINT announcement_dismissals_announcement_id_fk
+        FOREIGN KEY (announcement_id) REFERENCES announcements(id) ON DELETE CASCADE,
+
+    CONSTRAINT announcement_dismissals_user_id_fk
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,23 @@
+-- Ordered image slots of a post. posts.image_id stays as the cover and is
+-- always in position 0.
+CREATE TABLE post_images (
+    post_id UUID NOT NULL,
+    image_id UUID NOT NULL,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (post_id, image_id),
+
+    CONSTRAINT post_images_post_id_fk
+        FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+
+    CONSTRAINT post_images_image_id_fk
+        FOREIGN KEY (image_id) REFERENCES images(id) ON DELETE CASCADE,
+
+    CONSTRAINT post_images_position_unique UNIQUE (post_id, position),
+    CONSTRAINT post_images_position_range CHECK (position >= 0 AND position < 10)
+);
+
+INSERT INTO post_images (post_id, image_id, position)
+SELECT id, image_id, 0
+FROM posts
+WHERE image_id IS NOT NULL;
@@ -0,0 +1,5 @@
+DELETE FROM password_reset_challenges;
+
+ALTER TABLE password_reset_challenges DROP COLUMN token_hash;
+ALTER TABLE password_reset_challenges ADD COLUMN token uuid NOT NULL UNIQUE;
+CREATE INDEX idx_password_reset_challenges_token ON password_reset_challenges(token);
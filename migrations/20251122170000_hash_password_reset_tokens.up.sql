@@ -0,0 +1,8 @@
+-- Store only an HMAC of each password reset token, so a database leak can't
+-- be used to reset passwords. Outstanding links can't be converted and are
+-- dropped; they expire within minutes anyway.
+DELETE FROM password_reset_challenges;
+
+DROP INDEX IF EXISTS idx_password_reset_challenges_token;
+ALTER TABLE password_reset_challenges DROP COLUMN token;
+ALTER TABLE password_reset_challenges ADD COLUMN token_hash bytea NOT NULL UNIQUE;
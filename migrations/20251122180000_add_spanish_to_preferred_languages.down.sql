@@ -0,0 +1,21 @@
+-- Create a new enum type without 'es'
+CREATE TYPE preferred_language_new AS ENUM ('ko', 'ja', 'en', 'zh');
+
+-- Update existing values (if any 'es' exists, convert to default 'en')
+ALTER TABLE users
+    ALTER COLUMN preferred_language TYPE preferred_language_new
+    USING (CASE WHEN preferred_language::text = 'es'
+                THEN 'en'::preferred_language_new
+                ELSE preferred_language::text::preferred_language_new
+           END);
+
+-- Spanish fallbacks are simply dropped
+ALTER TABLE users ALTER COLUMN language_fallbacks DROP DEFAULT;
+ALTER TABLE users
+    ALTER COLUMN language_fallbacks TYPE preferred_language_new[]
+    USING array_remove(language_fallbacks::text[], 'es')::preferred_language_new[];
+ALTER TABLE users ALTER COLUMN language_fallbacks SET DEFAULT '{}';
+
+-- Drop old type and rename new type
+DROP TYPE preferred_language;
+ALTER TYPE preferred_language_new RENAME TO preferred_language;
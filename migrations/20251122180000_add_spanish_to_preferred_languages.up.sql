@@ -0,0 +1 @@
+ALTER TYPE preferred_language ADD VALUE 'es';
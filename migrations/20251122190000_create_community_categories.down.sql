@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_community_category_assignments_category_id;
+DROP TABLE IF EXISTS community_category_assignments;
+DROP TABLE IF EXISTS community_categories;
@@ -0,0 +1,26 @@
+-- Admin-curated categories communities can be listed under
+CREATE TABLE community_categories (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    CONSTRAINT community_categories_slug_format CHECK (slug ~ '^[a-z0-9-]+$')
+);
+
+CREATE TABLE community_category_assignments (
+    community_id UUID NOT NULL,
+    category_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (community_id, category_id),
+
+    CONSTRAINT community_category_assignments_community_id_fk
+        FOREIGN KEY (community_id) REFERENCES communities(id) ON DELETE CASCADE,
+
+    CONSTRAINT community_category_assignments_category_id_fk
+        FOREIGN KEY (category_id) REFERENCES community_categories(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_community_category_assignments_category_id
+    ON community_category_assignments(category_id);
@@ -0,0 +1,13 @@
+-- Hashtags linked to every post published into a community
+CREATE TABLE community_default_hashtags (
+    community_id UUID NOT NULL,
+    hashtag_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (community_id, hashtag_id),
+
+    CONSTRAINT community_default_hashtags_community_id_fk
+        FOREIGN KEY (community_id) REFERENCES communities(id) ON DELETE CASCADE,
+
+    CONSTRAINT community_default_hashtags_hashtag_id_fk
+        FOREIGN KEY (hashtag_id) REFERENCES hashtags(id) ON DELETE CASCADE
+);
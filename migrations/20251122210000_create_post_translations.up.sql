@@ -0,0 +1,12 @@
+-- Machine translations of post content, reused until the content changes
+CREATE TABLE post_translations (
+    post_id UUID NOT NULL,
+    target_lang preferred_language NOT NULL,
+    source_content TEXT NOT NULL,
+    translated_content TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (post_id, target_lang),
+
+    CONSTRAINT post_translations_post_id_fk
+        FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS collaborative_session_recording_chunks;
+ALTER TABLE collaborative_sessions DROP COLUMN record_replay;
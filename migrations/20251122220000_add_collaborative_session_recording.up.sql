@@ -0,0 +1,13 @@
+-- Opt-in recording of a collaborative session's drawing ops for replay
+ALTER TABLE collaborative_sessions ADD COLUMN record_replay BOOLEAN NOT NULL DEFAULT false;
+
+-- Recorded ops are flushed here in chunks while the session runs, then
+-- assembled into a replay file when the session is saved
+CREATE TABLE collaborative_session_recording_chunks (
+    session_id UUID NOT NULL REFERENCES collaborative_sessions(id) ON DELETE CASCADE,
+    chunk_index BIGINT NOT NULL,
+    ops BYTEA NOT NULL,
+    op_count INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (session_id, chunk_index)
+);
@@ -0,0 +1 @@
+ALTER TABLE federated_posts DROP COLUMN deleted_at;
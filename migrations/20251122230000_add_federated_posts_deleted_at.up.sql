@@ -0,0 +1,2 @@
+-- Posts mirrored from a community's group inbox are soft-deleted when their author deletes them
+ALTER TABLE federated_posts ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
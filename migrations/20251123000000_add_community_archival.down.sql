@@ -0,0 +1,2 @@
+ALTER TABLE communities DROP COLUMN archived_at;
+ALTER TABLE communities DROP COLUMN archive_notice_sent_at;
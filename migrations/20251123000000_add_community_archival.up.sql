@@ -0,0 +1,4 @@
+-- Inactive communities can be archived automatically: the owner is warned first,
+-- and archived communities drop out of discovery until the owner restores them
+ALTER TABLE communities ADD COLUMN archive_notice_sent_at TIMESTAMPTZ;
+ALTER TABLE communities ADD COLUMN archived_at TIMESTAMPTZ;
@@ -0,0 +1,3 @@
+ALTER TABLE users DROP COLUMN feed_algorithm;
+
+DROP TYPE feed_algorithm;
@@ -0,0 +1,3 @@
+CREATE TYPE feed_algorithm AS ENUM ('chronological', 'engagement', 'following_first');
+
+ALTER TABLE users ADD COLUMN feed_algorithm feed_algorithm NOT NULL DEFAULT 'chronological';
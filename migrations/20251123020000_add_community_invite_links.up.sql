@@ -0,0 +1,12 @@
+CREATE TABLE community_invite_links (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+  community_id uuid NOT NULL REFERENCES communities(id) ON DELETE CASCADE,
+  created_by uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  max_uses integer CHECK (max_uses IS NULL OR max_uses > 0),
+  use_count integer NOT NULL DEFAULT 0,
+  expires_at timestamptz,
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_community_invite_links_community_id
+  ON community_invite_links(community_id);
@@ -0,0 +1,3 @@
+DROP TABLE join_requests;
+DROP TYPE join_request_status;
+ALTER TABLE communities DROP COLUMN accepts_join_requests;
@@ -0,0 +1,18 @@
+ALTER TABLE communities ADD COLUMN accepts_join_requests boolean NOT NULL DEFAULT false;
+
+CREATE TYPE join_request_status AS ENUM ('pending', 'approved', 'rejected');
+
+CREATE TABLE join_requests (
+  id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+  community_id uuid NOT NULL REFERENCES communities(id) ON DELETE CASCADE,
+  requester_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  status join_request_status NOT NULL DEFAULT 'pending',
+  reviewed_by uuid REFERENCES users(id) ON DELETE SET NULL,
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_join_requests_community_id ON join_requests(community_id);
+CREATE UNIQUE INDEX idx_join_requests_pending
+  ON join_requests(community_id, requester_id)
+  WHERE status = 'pending';
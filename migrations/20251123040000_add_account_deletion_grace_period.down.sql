@@ -0,0 +1,12 @@
+-- Enum values can't be dropped, so rebuild the type without 'account_deletion'
+UPDATE posts SET deletion_reason = 'user_deleted' WHERE deletion_reason = 'account_deletion';
+
+CREATE TYPE post_deletion_reason_new AS ENUM ('user_deleted', 'cascade', 'moderation');
+ALTER TABLE posts
+    ALTER COLUMN deletion_reason TYPE post_deletion_reason_new
+    USING deletion_reason::text::post_deletion_reason_new;
+DROP TYPE post_deletion_reason;
+ALTER TYPE post_deletion_reason_new RENAME TO post_deletion_reason;
+
+DROP INDEX idx_users_deletion_pending;
+ALTER TABLE users DROP COLUMN deletion_finalized_at;
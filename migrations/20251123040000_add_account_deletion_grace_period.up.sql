@@ -0,0 +1,11 @@
+-- Accounts stay restorable until deleted_at; this records when their data was removed
+ALTER TABLE users ADD COLUMN deletion_finalized_at TIMESTAMPTZ;
+
+-- Accounts deleted before the grace period existed were removed right away
+UPDATE users SET deletion_finalized_at = deleted_at WHERE deleted_at IS NOT NULL;
+
+CREATE INDEX idx_users_deletion_pending ON users(deleted_at)
+  WHERE deleted_at IS NOT NULL AND deletion_finalized_at IS NULL;
+
+-- Published posts are hidden with this reason while the account awaits deletion
+ALTER TYPE post_deletion_reason ADD VALUE 'account_deletion';
@@ -0,0 +1,3 @@
+DROP INDEX idx_posts_pending_approval;
+ALTER TABLE posts DROP COLUMN pending_approval_at;
+ALTER TABLE communities DROP COLUMN require_post_approval;
@@ -0,0 +1,9 @@
+ALTER TABLE communities ADD COLUMN require_post_approval boolean NOT NULL DEFAULT false;
+
+-- Set while a submitted post waits for the community's owner or a moderator;
+-- the post stays unpublished until it is approved
+ALTER TABLE posts ADD COLUMN pending_approval_at timestamptz;
+
+CREATE INDEX idx_posts_pending_approval
+  ON posts(community_id, pending_approval_at)
+  WHERE pending_approval_at IS NOT NULL;
@@ -0,0 +1,2 @@
+DROP INDEX idx_community_invitations_pending_expires_at;
+ALTER TABLE community_invitations DROP COLUMN expires_at;
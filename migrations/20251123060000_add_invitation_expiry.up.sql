@@ -0,0 +1,9 @@
+-- Pending invitations can't be accepted after this; existing ones get the default
+-- 30 days from when they were sent
+ALTER TABLE community_invitations ADD COLUMN expires_at timestamptz;
+UPDATE community_invitations SET expires_at = created_at + INTERVAL '30 days';
+ALTER TABLE community_invitations ALTER COLUMN expires_at SET NOT NULL;
+
+CREATE INDEX idx_community_invitations_pending_expires_at
+  ON community_invitations(expires_at)
+  WHERE status = 'pending';
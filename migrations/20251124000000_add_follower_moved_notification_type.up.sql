@@ -0,0 +1,2 @@
+-- Add follower_moved to notification_type enum
+ALTER TYPE notification_type ADD VALUE 'follower_moved';
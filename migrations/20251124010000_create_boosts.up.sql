@@ -0,0 +1,13 @@
+-- Announces sent by local actors, kept so they can be undone
+CREATE TABLE boosts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    iri TEXT NOT NULL UNIQUE,
+    actor_id UUID NOT NULL REFERENCES actors(id) ON DELETE CASCADE,
+    object_iri TEXT NOT NULL,
+    -- Set when the boosted object is a local post
+    post_id UUID REFERENCES posts(id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (actor_id, object_iri)
+);
+
+CREATE INDEX idx_boosts_post_id ON boosts(post_id) WHERE post_id IS NOT NULL;
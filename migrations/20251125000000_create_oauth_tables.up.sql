@@ -0,0 +1,36 @@
+-- OAuth2 authorization server for native clients. Clients are public (no
+-- secret) and must use PKCE; codes and tokens are stored only as SHA-256
+-- hashes.
+CREATE TABLE oauth_clients (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    client_id text NOT NULL UNIQUE,
+    name text NOT NULL,
+    redirect_uris text[] NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE oauth_codes (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    code_hash bytea NOT NULL UNIQUE,
+    client_id uuid NOT NULL REFERENCES oauth_clients(id) ON DELETE CASCADE,
+    user_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    redirect_uri text NOT NULL,
+    code_challenge text NOT NULL,
+    expires_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT NOW()
+);
+
+
+CREATE TABLE oauth_tokens (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    client_id uuid NOT NULL REFERENCES oauth_clients(id) ON DELETE CASCADE,
+    user_id uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    access_token_hash bytea NOT NULL UNIQUE,
+    refresh_token_hash bytea NOT NULL UNIQUE,
+    access_expires_at timestamptz NOT NULL,
+    refresh_expires_at timestamptz NOT NULL,
+    revoked_at timestamptz,
+    created_at timestamptz NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_oauth_tokens_user_id ON oauth_tokens(user_id);
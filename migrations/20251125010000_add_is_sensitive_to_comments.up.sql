@@ -0,0 +1,2 @@
+-- Remote replies can be marked sensitive by their origin server
+ALTER TABLE comments ADD COLUMN is_sensitive boolean NOT NULL DEFAULT false;
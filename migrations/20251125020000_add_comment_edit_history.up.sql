@@ -0,0 +1,12 @@
+-- Comments can be edited for a short while after posting; each edit keeps
+-- the content it replaced
+ALTER TABLE comments ADD COLUMN edited_at timestamptz;
+
+CREATE TABLE comment_history (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    comment_id uuid NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    content text NOT NULL,
+    edited_at timestamptz NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_comment_history_comment_id ON comment_history(comment_id, edited_at);
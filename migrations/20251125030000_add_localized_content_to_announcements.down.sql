@@ -0,0 +1,5 @@
+ALTER TABLE announcements DROP COLUMN is_active;
+ALTER TABLE announcements DROP COLUMN content_zh;
+ALTER TABLE announcements DROP COLUMN content_ja;
+ALTER TABLE announcements DROP COLUMN content_en;
+ALTER TABLE announcements DROP COLUMN content_ko;
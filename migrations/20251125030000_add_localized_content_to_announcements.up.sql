@@ -0,0 +1,8 @@
+-- Per-language text of announcements; empty falls back to message
+ALTER TABLE announcements ADD COLUMN content_ko TEXT NOT NULL DEFAULT '';
+ALTER TABLE announcements ADD COLUMN content_en TEXT NOT NULL DEFAULT '';
+ALTER TABLE announcements ADD COLUMN content_ja TEXT NOT NULL DEFAULT '';
+ALTER TABLE announcements ADD COLUMN content_zh TEXT NOT NULL DEFAULT '';
+
+-- Inactive announcements are kept but never shown
+ALTER TABLE announcements ADD COLUMN is_active BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_reports_status_created_at;
+DROP TABLE IF EXISTS reports;
@@ -0,0 +1,22 @@
+-- Reports about ActivityPub objects (comments, remote posts, actors), filed by local
+-- users or received as Flag activities from other instances
+CREATE TABLE reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- The Flag activity sent to or received from the origin instance, if any
+    iri TEXT UNIQUE,
+    reporter_actor_id UUID NOT NULL,
+    object_iris TEXT[] NOT NULL,
+    comment TEXT NOT NULL DEFAULT '',
+    status post_report_status NOT NULL DEFAULT 'pending',
+    resolved_by UUID,
+    resolved_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+
+    CONSTRAINT reports_reporter_actor_id_fk
+        FOREIGN KEY (reporter_actor_id) REFERENCES actors(id) ON DELETE CASCADE,
+
+    CONSTRAINT reports_resolved_by_fk
+        FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_reports_status_created_at ON reports(status, created_at);
@@ -0,0 +1,12 @@
+-- Each edit of a post's title or content keeps the version it replaced
+ALTER TABLE posts ADD COLUMN edited_at timestamptz;
+
+CREATE TABLE post_revisions (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id uuid NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    title text,
+    content text,
+    edited_at timestamptz NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_post_revisions_post_id ON post_revisions(post_id, edited_at);
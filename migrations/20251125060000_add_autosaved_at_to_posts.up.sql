@@ -0,0 +1,2 @@
+-- When the drawing editor last saved a snapshot of a draft's canvas
+ALTER TABLE posts ADD COLUMN autosaved_at timestamptz;
@@ -0,0 +1,2 @@
+-- When publishing a scheduled post last failed; the post goes back to being a draft
+ALTER TABLE posts ADD COLUMN schedule_failed_at timestamptz;
@@ -0,0 +1,3 @@
+-- Set when a moderator approves a post that then waits for its community's
+-- posting window, so it isn't reviewed a second time when it goes out
+ALTER TABLE posts ADD COLUMN approved_at timestamptz;
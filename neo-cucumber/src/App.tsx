@@ -6,6 +6,7 @@ import { InitializationErrorModal } from "./components/modals/InitializationErro
 import { LoadingModal } from "./components/modals/LoadingModal";
 import { AuthErrorModal } from "./components/modals/AuthErrorModal";
 import { RoomFullModal } from "./components/modals/RoomFullModal";
+import { KickedModal } from "./components/modals/KickedModal";
 import { ConnectionStatusModal } from "./components/modals/ConnectionStatusModal";
 import { SessionEndingModal } from "./components/modals/SessionEndingModal";
 import { SessionHeader } from "./components/SessionHeader";
@@ -111,6 +112,7 @@ function App() {
     currentUserCount: number;
     maxUsers: number;
   } | null>(null);
+  const [kicked, setKicked] = useState(false);
 
   const [isSaving, setIsSaving] = useState(false);
   const [sessionEnded] = useState(false);
@@ -303,6 +305,8 @@ function App() {
     isCatchingUpRef,
     setConnectionState,
     setIsCatchingUp,
+    setRoomFullError,
+    setKicked,
     createUserEngine,
     handleLocalDrawingChange,
     addSnapshotToHistory,
@@ -361,7 +365,7 @@ function App() {
 
   // Function to handle manual reconnection
   const handleManualReconnect = useCallback(() => {
-    window.location.reload();
+    connectWebSocketRef.current();
   }, []);
 
   // Function to save collaborative drawing to gallery
@@ -670,6 +674,11 @@ function App() {
           onRetry={() => window.location.reload()}
         />
 
+        <KickedModal
+          isOpen={kicked}
+          onGoToLobby={() => (window.location.href = "/collaborate")}
+        />
+
         {/* Session Header */}
         {canvasMeta && (
           <SessionHeader
@@ -690,6 +699,7 @@ function App() {
               wsRef={wsRef}
               userId={userIdRef.current}
               participants={participants}
+              canKick={!!isOwner}
               onChatMessage={handleChatMessage}
               onMinimizedChange={setIsChatMinimized}
               onAddMessage={handleChatAddMessage}
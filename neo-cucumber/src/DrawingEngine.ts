@@ -278,6 +278,13 @@ export class DrawingEngine {
     this.updateDOMCanvas("foreground");
   }
 
+  // Wipe both layers, e.g. before the full history is replayed
+  public clear() {
+    this.layers.background.fill(0);
+    this.layers.foreground.fill(0);
+    this.updateAllDOMCanvasesImmediate();
+  }
+
   // Pan offset management - applies to all canvases
   public updatePanOffset(
     deltaX: number,
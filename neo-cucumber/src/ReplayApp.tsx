@@ -0,0 +1,280 @@
+import { useCallback, useEffect, useMemo, useRef, useState } from "react";
+import "./App.css";
+import { Trans } from "@lingui/react/macro";
+import { DrawingEngine } from "./DrawingEngine";
+import { decodeMessage, type DecodedMessage } from "./utils/binaryProtocol";
+
+// Replay file layout, written by the server when a recorded session is saved:
+// "NCR1", width (u16 LE), height (u16 LE), then [length (u32 LE)][op] repeated
+const REPLAY_MAGIC = "NCR1";
+const REPLAY_HEADER_SIZE = 8;
+
+// Ops applied per animation frame at 1x speed
+const OPS_PER_FRAME = 4;
+const SPEEDS = [1, 2, 4, 8, 16];
+
+// Same stacking as the live session: earlier participants end up on top
+const LAYER_Z_INDEX_BASE = 1000;
+const LAYER_Z_INDEX_USER_SEPARATION = 100;
+const LAYER_Z_INDEX_FOREGROUND_OFFSET = 40;
+
+interface ParsedReplay {
+  width: number;
+  height: number;
+  ops: DecodedMessage[];
+}
+
+function parseReplay(data: ArrayBuffer): ParsedReplay {
+  const bytes = new Uint8Array(data);
+  const view = new DataView(data);
+  const magic = new TextDecoder().decode(bytes.slice(0, 4));
+  if (bytes.length < REPLAY_HEADER_SIZE || magic !== REPLAY_MAGIC) {
+    throw new Error("Not a collaborative replay file");
+  }
+
+  const width = view.getUint16(4, true);
+  const height = view.getUint16(6, true);
+  const ops: DecodedMessage[] = [];
+
+  let offset = REPLAY_HEADER_SIZE;
+  while (offset + 4 <= bytes.length) {
+    const length = view.getUint32(offset, true);
+    offset += 4;
+    if (offset + length > bytes.length) break;
+    const message = decodeMessage(data.slice(offset, offset + length));
+    if (message) ops.push(message);
+    offset += length;
+  }
+
+  return { width, height, ops };
+}
+
+function ReplayApp() {
+  const replayUrl = useMemo(
+    () => new URLSearchParams(window.location.search).get("src"),
+    []
+  );
+
+  const containerRef = useRef<HTMLDivElement>(null);
+  const enginesRef = useRef<Map<string, DrawingEngine>>(new Map());
+  const positionRef = useRef(0);
+  const rafRef = useRef<number | null>(null);
+
+  const [replay, setReplay] = useState<ParsedReplay | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [isPlaying, setIsPlaying] = useState(true);
+  const [speed, setSpeed] = useState(1);
+  const [progress, setProgress] = useState(0);
+
+  useEffect(() => {
+    if (!replayUrl) {
+      setError("Missing replay source");
+      return;
+    }
+    fetch(replayUrl)
+      .then((response) => {
+        if (!response.ok) {
+          throw new Error(`Failed to load replay: ${response.status}`);
+        }
+        return response.arrayBuffer();
+      })
+      .then((data) => setReplay(parseReplay(data)))
+      .catch((e: unknown) =>
+        setError(e instanceof Error ? e.message : String(e))
+      );
+  }, [replayUrl]);
+
+  // Each participant draws on their own pair of layers, as in the live session
+  const getEngine = useCallback(
+    (userId: string) => {
+      const existing = enginesRef.current.get(userId);
+      if (existing || !replay || !containerRef.current) return existing;
+
+      const userIndex = enginesRef.current.size;
+      const engine = new DrawingEngine(replay.width, replay.height);
+      const canvases = (["background", "foreground"] as const).map(
+        (layer) => {
+          const canvas = document.createElement("canvas");
+          canvas.width = replay.width;
+          canvas.height = replay.height;
+          canvas.className = "absolute top-0 left-0";
+          canvas.style.imageRendering = "pixelated";
+          canvas.style.zIndex = String(
+            LAYER_Z_INDEX_BASE -
+              userIndex * LAYER_Z_INDEX_USER_SEPARATION +
+              (layer === "foreground" ? LAYER_Z_INDEX_FOREGROUND_OFFSET : 0)
+          );
+          containerRef.current!.appendChild(canvas);
+          return canvas;
+        }
+      );
+      engine.attachDOMCanvases(canvases[0], canvases[1]);
+      enginesRef.current.set(userId, engine);
+      return engine;
+    },
+    [replay]
+  );
+
+  const applyOp = useCallback(
+    (op: DecodedMessage) => {
+      switch (op.type) {
+        case "join":
+          getEngine(op.userId);
+          break;
+        case "drawLine": {
+          const engine = getEngine(op.userId);
+          if (!engine) break;
+          engine.drawLine(
+            engine.layers[op.layer],
+            op.fromX,
+            op.fromY,
+            op.toX,
+            op.toY,
+            op.brushSize,
+            op.brushType,
+            op.color.r,
+            op.color.g,
+            op.color.b,
+            op.color.a
+          );
+          engine.queueLayerUpdate(op.layer);
+          break;
+        }
+        case "drawPoint": {
+          const engine = getEngine(op.userId);
+          if (!engine) break;
+          engine.drawLine(
+            engine.layers[op.layer],
+            op.x,
+            op.y,
+            op.x,
+            op.y,
+            op.brushSize,
+            op.brushType,
+            op.color.r,
+            op.color.g,
+            op.color.b,
+            op.color.a
+          );
+          engine.queueLayerUpdate(op.layer);
+          break;
+        }
+        case "fill": {
+          const engine = getEngine(op.userId);
+          if (!engine) break;
+          engine.doFloodFill(
+            engine.layers[op.layer],
+            op.x,
+            op.y,
+            op.color.r,
+            op.color.g,
+            op.color.b,
+            op.color.a
+          );
+          engine.queueLayerUpdate(op.layer);
+          break;
+        }
+        default:
+          break;
+      }
+    },
+    [getEngine]
+  );
+
+  useEffect(() => {
+    if (!replay || !isPlaying) return;
+
+    const step = () => {
+      const end = Math.min(
+        positionRef.current + OPS_PER_FRAME * speed,
+        replay.ops.length
+      );
+      for (; positionRef.current < end; positionRef.current++) {
+        applyOp(replay.ops[positionRef.current]);
+      }
+      setProgress(positionRef.current);
+
+      if (positionRef.current < replay.ops.length) {
+        rafRef.current = requestAnimationFrame(step);
+      } else {
+        setIsPlaying(false);
+      }
+    };
+    rafRef.current = requestAnimationFrame(step);
+
+    return () => {
+      if (rafRef.current !== null) cancelAnimationFrame(rafRef.current);
+    };
+  }, [replay, isPlaying, speed, applyOp]);
+
+  const restart = useCallback(() => {
+    enginesRef.current.forEach((engine) => engine.dispose());
+    enginesRef.current.clear();
+    containerRef.current?.replaceChildren();
+    positionRef.current = 0;
+    setProgress(0);
+    setIsPlaying(true);
+  }, []);
+
+  const skipToEnd = useCallback(() => {
+    if (!replay) return;
+    for (; positionRef.current < replay.ops.length; positionRef.current++) {
+      applyOp(replay.ops[positionRef.current]);
+    }
+    setProgress(positionRef.current);
+    setIsPlaying(false);
+  }, [replay, applyOp]);
+
+  if (error) {
+    return <div className="p-4">{error}</div>;
+  }
+
+  if (!replay) {
+    return (
+      <div className="p-4">
+        <Trans>Loading replay...</Trans>
+      </div>
+    );
+  }
+
+  const isFinished = progress >= replay.ops.length;
+
+  return (
+    <div className="flex flex-col items-center gap-2 p-2">
+      <div
+        ref={containerRef}
+        className="relative bg-white"
+        style={{ width: replay.width, height: replay.height }}
+      />
+      <div className="flex items-center gap-2">
+        {isFinished ? (
+          <button type="button" onClick={restart}>
+            <Trans>Replay again</Trans>
+          </button>
+        ) : (
+          <button type="button" onClick={() => setIsPlaying(!isPlaying)}>
+            {isPlaying ? <Trans>Pause</Trans> : <Trans>Play</Trans>}
+          </button>
+        )}
+        <select
+          value={speed}
+          onChange={(e) => setSpeed(Number(e.target.value))}
+        >
+          {SPEEDS.map((value) => (
+            <option key={value} value={value}>
+              {value}x
+            </option>
+          ))}
+        </select>
+        <button type="button" onClick={skipToEnd} disabled={isFinished}>
+          <Trans>Skip to end</Trans>
+        </button>
+        <span>
+          {progress} / {replay.ops.length}
+        </span>
+      </div>
+    </div>
+  );
+}
+
+export default ReplayApp;
@@ -1,5 +1,5 @@
 import { useState, useEffect, useRef, useCallback } from "react";
-import { encodeChat } from "../utils/binaryProtocol";
+import { encodeChat, encodeKick } from "../utils/binaryProtocol";
 import { Trans, useLingui } from "@lingui/react/macro";
 import { Icon } from "@iconify/react";
 import { getUserColors } from "../utils/userColors";
@@ -23,6 +23,7 @@ interface ChatProps {
   wsRef: React.RefObject<WebSocket | null>;
   userId: string;
   participants: Map<string, Participant>;
+  canKick: boolean;
   onChatMessage: (message: ChatMessage) => void;
   onMinimizedChange?: (isMinimized: boolean) => void;
   onAddMessage?: (
@@ -41,6 +42,7 @@ export const Chat = ({
   wsRef,
   userId,
   participants,
+  canKick,
   onChatMessage,
   onMinimizedChange,
   onAddMessage,
@@ -104,6 +106,21 @@ export const Chat = ({
     }
   }, [wsRef, userId, inputValue]);
 
+  const kickParticipant = useCallback(
+    (participant: Participant) => {
+      const ws = wsRef.current;
+      if (!ws || ws.readyState !== WebSocket.OPEN) {
+        return;
+      }
+      const { username } = participant;
+      if (!window.confirm(t`Remove ${username} from this session?`)) {
+        return;
+      }
+      ws.send(encodeKick(userId, participant.userId));
+    },
+    [wsRef, userId, t]
+  );
+
   // Handle IME composition events
   const handleCompositionStart = useCallback(() => {
     setIsComposing(true);
@@ -194,6 +211,19 @@ export const Chat = ({
                   style={getUserStyle(participant.username)}
                 >
                   {participant.username}
+                  {canKick && participant.userId !== userId && (
+                    <button
+                      onClick={() => kickParticipant(participant)}
+                      className="ml-1 text-main hover:text-highlight cursor-pointer"
+                      title={t`Remove from session`}
+                    >
+                      <Icon
+                        icon="material-symbols:close"
+                        width={12}
+                        height={12}
+                      />
+                    </button>
+                  )}
                 </div>
               ))}
             {participants.size === 0 && (
@@ -0,0 +1,29 @@
+import { Trans } from "@lingui/react/macro";
+import { ModalWrapper } from "./ModalWrapper";
+
+interface KickedModalProps {
+  isOpen: boolean;
+  onGoToLobby: () => void;
+}
+
+export const KickedModal = ({ isOpen, onGoToLobby }: KickedModalProps) => {
+  return (
+    <ModalWrapper isOpen={isOpen}>
+      <h2 className="text-highlight mt-0 mb-4 text-xl font-bold">
+        <Trans>Removed from Session</Trans>
+      </h2>
+      <p className="mb-6 leading-relaxed">
+        <Trans>
+          The session owner removed you from this session. You can't rejoin it
+          for a while.
+        </Trans>
+      </p>
+      <button
+        onClick={onGoToLobby}
+        className="bg-highlight text-white border-0 px-6 py-3 rounded cursor-pointer text-base font-sans transition-colors hover:bg-orange-600"
+      >
+        <Trans>Go to Lobby</Trans>
+      </button>
+    </ModalWrapper>
+  );
+};
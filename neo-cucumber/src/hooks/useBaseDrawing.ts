@@ -46,6 +46,15 @@ interface DrawingEventCallbacks {
     opacity: number
   ) => void;
   onPointerUp?: () => void;
+  onPointerHover?: (
+    x: number,
+    y: number,
+    overCanvas: boolean,
+    brushType: "solid" | "halftone" | "eraser" | "fill" | "pan",
+    r: number,
+    g: number,
+    b: number
+  ) => void;
 }
 
 export const useBaseDrawing = (
@@ -392,10 +401,40 @@ export const useBaseDrawing = (
 
       if (shouldCleanup) {
         cleanupPointerState(e.pointerId);
+        reportHover(e, false);
       }
     };
 
+    // Share where the pointer rests between strokes; strokes carry their own positions
+    const reportHover = (e: PointerEvent, overApp: boolean) => {
+      if (!callbacks?.onPointerHover || e.pointerType === "touch") return;
+
+      const canvas = canvasRef.current;
+      const coords = getCanvasCoordinates(e.clientX, e.clientY);
+      const overCanvas =
+        overApp &&
+        !!canvas &&
+        coords.x >= 0 &&
+        coords.y >= 0 &&
+        coords.x < canvas.width &&
+        coords.y < canvas.height;
+      const { color, brushType } = currentDrawingStateRef.current;
+      callbacks.onPointerHover(
+        coords.x,
+        coords.y,
+        overCanvas,
+        brushType,
+        parseInt(color.slice(1, 3), 16),
+        parseInt(color.slice(3, 5), 16),
+        parseInt(color.slice(5, 7), 16)
+      );
+    };
+
     const handlePointerMove = (e: PointerEvent) => {
+      if (drawingStateRef.current.activePointerId === null) {
+        reportHover(e, true);
+        return;
+      }
       if (drawingStateRef.current.activePointerId !== e.pointerId) return;
 
       if (drawingStateRef.current.isPanning) {
@@ -7,8 +7,12 @@ import {
   encodeDrawPoint,
   encodeFill,
   encodePointerUp,
+  encodePresence,
 } from "../utils/binaryProtocol";
 
+// Matches the rate the server relays presence at
+const PRESENCE_THROTTLE_MS = 50;
+
 export const useDrawing = (
   canvasRef: React.RefObject<HTMLCanvasElement | null>,
   appRef: React.RefObject<HTMLDivElement | null>,
@@ -31,6 +35,7 @@ export const useDrawing = (
   const sendSnapshotRef = useRef<(() => Promise<void>) | undefined>(undefined);
   const isCatchingUpRef = useRef(isCatchingUp);
   const connectionStateRef = useRef(connectionState);
+  const lastPresenceRef = useRef({ time: 0, overCanvas: false });
 
   useEffect(() => {
     isCatchingUpRef.current = isCatchingUp;
@@ -230,6 +235,33 @@ export const useDrawing = (
         pendingSnapshotRequestRef.current = false;
       }
     }, [userIdRef, wsRef, sendOrQueueMessage]),
+
+    onPointerHover: useCallback((
+      x: number,
+      y: number,
+      overCanvas: boolean,
+      brushType: "solid" | "halftone" | "eraser" | "fill" | "pan",
+      r: number,
+      g: number,
+      b: number
+    ) => {
+      const ws = wsRef?.current;
+      if (!userIdRef?.current || !ws || ws.readyState !== WebSocket.OPEN) return;
+
+      // Presence is ephemeral, so it's dropped rather than queued. Leaving the
+      // canvas always goes out so the cursor doesn't linger for others.
+      const now = Date.now();
+      const last = lastPresenceRef.current;
+      if (!overCanvas && !last.overCanvas) return;
+      if (overCanvas && now - last.time < PRESENCE_THROTTLE_MS) return;
+      lastPresenceRef.current = { time: now, overCanvas };
+
+      try {
+        ws.send(encodePresence(userIdRef.current, x, y, overCanvas, brushType, r, g, b));
+      } catch (error) {
+        console.error("Failed to encode/send presence event:", error);
+      }
+    }, [userIdRef, wsRef]),
   };
 
   // Drawing disabled when catching up or disconnected
@@ -2,6 +2,7 @@ import { useCallback, useRef, useEffect } from "react";
 import {
   decodeMessage,
   encodeJoin,
+  readSequence,
   type DecodedMessage,
 } from "../utils/binaryProtocol";
 import { type CollaborationMeta } from "../types/collaboration";
@@ -9,6 +10,14 @@ import { DrawingEngine } from "../DrawingEngine";
 
 export type ConnectionState = "disconnected" | "connecting" | "connected";
 
+// Close code the server uses when the session has no room for another connection
+const SESSION_FULL_CLOSE_CODE = 4001;
+// Close code the server uses when the session owner removed this user
+const KICKED_CLOSE_CODE = 4002;
+// Dropped connections are retried with exponential backoff, starting here
+const RECONNECT_BASE_DELAY_MS = 1000;
+const MAX_RECONNECT_ATTEMPTS = 5;
+
 interface Participant {
   userId: string;
   username: string;
@@ -34,6 +43,10 @@ interface WebSocketHookParams {
   isCatchingUpRef: React.RefObject<boolean>;
   setConnectionState: (state: ConnectionState) => void;
   setIsCatchingUp: (catching: boolean) => void;
+  setRoomFullError: (
+    error: { currentUserCount: number; maxUsers: number } | null
+  ) => void;
+  setKicked: (kicked: boolean) => void;
   createUserEngine: (userId: string, username?: string) => void;
   handleLocalDrawingChange: () => void;
   addSnapshotToHistory: (
@@ -74,6 +87,8 @@ export const useWebSocket = ({
   isCatchingUpRef,
   setConnectionState,
   setIsCatchingUp,
+  setRoomFullError,
+  setKicked,
   createUserEngine,
   handleLocalDrawingChange,
   addSnapshotToHistory,
@@ -88,6 +103,10 @@ export const useWebSocket = ({
   const wsRef = useRef<WebSocket | null>(null);
   const messageQueueRef = useRef<DecodedMessage[]>([]);
   const isConnectingRef = useRef(false);
+  // Last op sequence number seen, sent on reconnect to replay only missed ops
+  const lastSequenceRef = useRef<number | null>(null);
+  const reconnectAttemptsRef = useRef(0);
+  const reconnectTimeoutRef = useRef<number | null>(null);
 
   // Keep handleSnapshotRequest ref to avoid dependency issues
   const handleSnapshotRequestRef = useRef(handleSnapshotRequest);
@@ -164,6 +183,12 @@ export const useWebSocket = ({
     // Set connecting flag
     isConnectingRef.current = true;
 
+    // A manual reconnect supersedes any pending automatic one
+    if (reconnectTimeoutRef.current) {
+      clearTimeout(reconnectTimeoutRef.current);
+      reconnectTimeoutRef.current = null;
+    }
+
     // Clean up any existing connection
     if (wsRef.current) {
       console.log("Cleaning up existing WebSocket connection");
@@ -186,7 +211,11 @@ export const useWebSocket = ({
     console.log("Using initialized user ID:", userIdRef.current);
 
     try {
-      const wsUrl = getWebSocketUrl();
+      const url = new URL(getWebSocketUrl());
+      if (lastSequenceRef.current !== null) {
+        url.searchParams.set("resume_from", String(lastSequenceRef.current));
+      }
+      const wsUrl = url.toString();
       console.log("Creating WebSocket connection to:", wsUrl);
       const ws = new WebSocket(wsUrl);
       wsRef.current = ws;
@@ -210,6 +239,7 @@ export const useWebSocket = ({
       });
       setConnectionState("connected");
       isConnectingRef.current = false;
+      reconnectAttemptsRef.current = 0;
 
       // Don't add current user here - wait for server LAYERS message
       // This ensures all clients get consistent participant order from server
@@ -253,6 +283,10 @@ export const useWebSocket = ({
 
         // Handle binary messages (can be ArrayBuffer or Blob)
         if (event.data instanceof ArrayBuffer) {
+          const sequence = readSequence(event.data);
+          if (sequence !== null) {
+            lastSequenceRef.current = sequence;
+          }
           const message = decodeMessage(event.data);
           if (!message) {
             return;
@@ -278,6 +312,10 @@ export const useWebSocket = ({
           }
         } else if (event.data instanceof Blob) {
           const arrayBuffer = await event.data.arrayBuffer();
+          const sequence = readSequence(arrayBuffer);
+          if (sequence !== null) {
+            lastSequenceRef.current = sequence;
+          }
           const message = decodeMessage(arrayBuffer);
           if (!message) {
             return;
@@ -331,7 +369,35 @@ export const useWebSocket = ({
       });
       setConnectionState("disconnected");
       isConnectingRef.current = false;
-      // No automatic reconnection - user must manually reconnect
+
+      if (event.code === SESSION_FULL_CLOSE_CODE) {
+        try {
+          const { currentUserCount, maxUsers } = JSON.parse(event.reason);
+          setRoomFullError({ currentUserCount, maxUsers });
+        } catch {
+          setRoomFullError({ currentUserCount: 0, maxUsers: 0 });
+        }
+      } else if (event.code === KICKED_CLOSE_CODE) {
+        setKicked(true);
+      } else if (
+        shouldConnectRef.current &&
+        wsRef.current === ws &&
+        reconnectAttemptsRef.current < MAX_RECONNECT_ATTEMPTS
+      ) {
+        // Reconnect and catch up from the last sequence seen; after too many
+        // failures the user must reconnect manually
+        const delay =
+          RECONNECT_BASE_DELAY_MS * 2 ** reconnectAttemptsRef.current;
+        reconnectAttemptsRef.current += 1;
+        console.log(
+          `Reconnecting in ${delay}ms (attempt ${reconnectAttemptsRef.current})`
+        );
+        wsRef.current = null;
+        reconnectTimeoutRef.current = window.setTimeout(() => {
+          reconnectTimeoutRef.current = null;
+          connectWebSocketRef.current();
+        }, delay);
+      }
     };
 
     // Process all queued messages immediately during catch-up
@@ -635,6 +701,27 @@ export const useWebSocket = ({
             break;
           }
 
+          case "presence": {
+            if (message.userId === userIdRef.current) break;
+            if (message.visible) {
+              const participant = participantsRef.current?.get(message.userId);
+              const username =
+                participant?.username ||
+                userEnginesRef.current?.get(message.userId)?.username;
+              if (username) {
+                createOrUpdateCursor(
+                  message.userId,
+                  message.x,
+                  message.y,
+                  username
+                );
+              }
+            } else {
+              hideCursor(message.userId);
+            }
+            break;
+          }
+
           case "join": {
             console.log("User joined:", {
               userId: message.userId.substring(0, 8),
@@ -851,6 +938,21 @@ export const useWebSocket = ({
             break;
           }
 
+          case "resync": {
+            console.log("Resync received:", {
+              fullReload: message.fullReload,
+              sequence: message.sequence,
+            });
+
+            // Too far behind to replay; the full history follows, so start
+            // from blank canvases
+            if (message.fullReload) {
+              drawingEngineRef.current?.clear();
+              userEnginesRef.current?.forEach(({ engine }) => engine.clear());
+            }
+            break;
+          }
+
           case "endSession": {
             console.log("Session ended:", {
               userId: message.userId.substring(0, 8),
@@ -880,6 +982,8 @@ export const useWebSocket = ({
     canvasMeta,
     setConnectionState,
     setIsCatchingUp,
+    setRoomFullError,
+    setKicked,
     createUserEngine,
     handleLocalDrawingChange,
     addSnapshotToHistory,
@@ -900,9 +1004,19 @@ export const useWebSocket = ({
     userIdRef,
   ]);
 
+  // Reconnect timers call the latest connectWebSocket
+  const connectWebSocketRef = useRef(connectWebSocket);
+  useEffect(() => {
+    connectWebSocketRef.current = connectWebSocket;
+  }, [connectWebSocket]);
+
   // Cleanup WebSocket on unmount
   useEffect(() => {
     return () => {
+      if (reconnectTimeoutRef.current) {
+        clearTimeout(reconnectTimeoutRef.current);
+        reconnectTimeoutRef.current = null;
+      }
       if (wsRef.current) {
         wsRef.current.close();
         wsRef.current = null;
@@ -1 +1 @@
-/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"This collaborative session has ended due to inactivity. As the owner, you can save it to the gallery or download it as a PNG.\"],\"/iK4an\":[\"💾 Save as PNG\"],\"0iNLjx\":[\"Flip\"],\"2BB/kB\":[\"🔄 Saving...\"],\"2FGWTy\":[\"Eraser\"],\"3MIwIy\":[\"Parent Post: \",[\"0\"]],\"4Q6lF1\":[\"(you)\"],\"4f2zr3\":[\"Go to Lobby\"],\"4ny0eJ\":[\"You'll be redirected to the post page shortly.\"],\"5bi1la\":[\"Solid\"],\"5hw/d6\":[\"Return to Lobby\"],\"6gRgw8\":[\"Retry\"],\"8b05T4\":[\"Save as PNG\"],\"8pzvKh\":[\"Failed to save session. Please try downloading as PNG instead.\"],\"9q0OPd\":[\"Custom color picker\"],\"9uI/rE\":[\"Undo\"],\"9xQ0Xz\":[\"Connection lost. Your work is saved locally.\"],\"9zb2WA\":[\"Connecting\"],\"AHZflp\":[\"Chat\"],\"AZyf4e\":[\"Initialization Failed\"],\"Be6WYe\":[\"Save Drawing\"],\"Cj2Gtd\":[\"Size\"],\"DIyyQk\":[\"Oeee Cafe\"],\"DMJlPw\":[\"💾 Download PNG\"],\"GNLePT\":[\"💾 Saving...\"],\"H3oH0g\":[\"Redo\"],\"HjORs9\":[\"Initializing collaboration session...\"],\"J28zul\":[\"Connecting...\"],\"J9nTy/\":[\"Save to Gallery\"],\"JlFcis\":[\"Send\"],\"KsKWyh\":[\"Community: \",[\"0\"]],\"M5rEN5\":[\"Session Expired\"],\"MJ0qUX\":[\"Show/hide foreground layer\"],\"MZHPuB\":[\"Participants\"],\"OHgs6h\":[\"Session is ending. The drawing is being saved to the gallery...\"],\"OfhWJH\":[\"Reset\"],\"OktlpZ\":[\"Failed to save drawing. Please try again.\"],\"PD9mEt\":[\"Type a message...\"],\"PI7Uvl\":[\"This collaborative session has ended due to inactivity. The session has already been saved to the gallery, but you can download it as a PNG.\"],\"Q7wnjt\":[\"This session is full (\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\" users). Only the first \",[\"maxUsers\"],\" users can join a session.\"],\"QHcLEN\":[\"Connected\"],\"QQ4kOo\":[\"Halftone\"],\"RHf7bi\":[\"This session has already been saved. You can only download it as a PNG.\"],\"Rc2wVJ\":[\"Edit palette color \",[\"0\"]],\"SnKtc6\":[\"Palette color \",[\"0\"]],\"TeZ01t\":[\"Download Replay\"],\"TuM2Yu\":[\"Fill\"],\"Vi2Pqx\":[\"by\"],\"XvjC4F\":[\"Saving...\"],\"Z3FXyt\":[\"Loading...\"],\"Z715sW\":[\"Left click to select layer, right click to toggle visibility\"],\"Z8lGw6\":[\"Share\"],\"ZS/6zt\":[\"This collaborative session has ended due to inactivity. You can save your work locally as a PNG before leaving.\"],\"ZV5ykW\":[\"Download PNG\"],\"ZYFhLg\":[\"Custom color picker - edits selected palette color\"],\"ZYnwzF\":[\"Disconnected\"],\"abs0zu\":[\"Show/hide background layer\"],\"aoLy25\":[\"Opacity\"],\"fFoXXe\":[\"Authentication Failed\"],\"gcoiFh\":[\"Reconnect\"],\"h5p+K4\":[\"Session Full\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 Save to Gallery\"],\"lDWsIx\":[\"LOADING...\"],\"mB1Pom\":[\"Palette color \",[\"0\"],\" (selected - edit with color picker below)\"],\"n+L8Wp\":[\"This session is full (\",[\"0\"],\"/\",[\"1\"],\" users). Only the first \",[\"2\"],\" users can join a session.\"],\"nGXTVR\":[\"Offline Drawing (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"Unable to authenticate your session. Either the session doesn't exist, or it has expired. Please return to the lobby.\"],\"oeM2Z1\":[\"Save drawing to gallery\"],\"pqT522\":[\"Failed to download replay file.\"],\"rGVpPc\":[\"Toggle horizontal flip\"],\"vjgq8y\":[\"Pan\"],\"wSPlwJ\":[\"📄 Download PNG\"],\"yQE2r9\":[\"Loading\"]}")as Messages;
\ No newline at end of file
+/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"This collaborative session has ended due to inactivity. As the owner, you can save it to the gallery or download it as a PNG.\"],\"/iK4an\":[\"💾 Save as PNG\"],\"/n9Xmv\":[\"The session owner removed you from this session. You can't rejoin it for a while.\"],\"0iNLjx\":[\"Flip\"],\"2BB/kB\":[\"🔄 Saving...\"],\"2FGWTy\":[\"Eraser\"],\"3MIwIy\":[\"Parent Post: \",[\"0\"]],\"4IArQf\":[\"Removed from Session\"],\"4Q6lF1\":[\"(you)\"],\"4f2zr3\":[\"Go to Lobby\"],\"4ny0eJ\":[\"You'll be redirected to the post page shortly.\"],\"5bi1la\":[\"Solid\"],\"5hw/d6\":[\"Return to Lobby\"],\"6gRgw8\":[\"Retry\"],\"8b05T4\":[\"Save as PNG\"],\"8pzvKh\":[\"Failed to save session. Please try downloading as PNG instead.\"],\"9q0OPd\":[\"Custom color picker\"],\"9uI/rE\":[\"Undo\"],\"9xQ0Xz\":[\"Connection lost. Your work is saved locally.\"],\"9zb2WA\":[\"Connecting\"],\"AHZflp\":[\"Chat\"],\"AZyf4e\":[\"Initialization Failed\"],\"Be6WYe\":[\"Save Drawing\"],\"Cj2Gtd\":[\"Size\"],\"DIyyQk\":[\"Oeee Cafe\"],\"DMJlPw\":[\"💾 Download PNG\"],\"GNLePT\":[\"💾 Saving...\"],\"H3oH0g\":[\"Redo\"],\"HjORs9\":[\"Initializing collaboration session...\"],\"J28zul\":[\"Connecting...\"],\"J9nTy/\":[\"Save to Gallery\"],\"JZ/4MB\":[\"Remove from session\"],\"JlFcis\":[\"Send\"],\"KsKWyh\":[\"Community: \",[\"0\"]],\"M5rEN5\":[\"Session Expired\"],\"MJ0qUX\":[\"Show/hide foreground layer\"],\"MZHPuB\":[\"Participants\"],\"OHgs6h\":[\"Session is ending. The drawing is being saved to the gallery...\"],\"OfhWJH\":[\"Reset\"],\"OktlpZ\":[\"Failed to save drawing. Please try again.\"],\"PD9mEt\":[\"Type a message...\"],\"PI7Uvl\":[\"This collaborative session has ended due to inactivity. The session has already been saved to the gallery, but you can download it as a PNG.\"],\"Q7wnjt\":[\"This session is full (\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\" users). Only the first \",[\"maxUsers\"],\" users can join a session.\"],\"QHcLEN\":[\"Connected\"],\"QQ4kOo\":[\"Halftone\"],\"RHf7bi\":[\"This session has already been saved. You can only download it as a PNG.\"],\"Rc2wVJ\":[\"Edit palette color \",[\"0\"]],\"SnKtc6\":[\"Palette color \",[\"0\"]],\"TeZ01t\":[\"Download Replay\"],\"TuM2Yu\":[\"Fill\"],\"Vi2Pqx\":[\"by\"],\"XvjC4F\":[\"Saving...\"],\"Z3FXyt\":[\"Loading...\"],\"Z715sW\":[\"Left click to select layer, right click to toggle visibility\"],\"Z8lGw6\":[\"Share\"],\"ZS/6zt\":[\"This collaborative session has ended due to inactivity. You can save your work locally as a PNG before leaving.\"],\"ZV5ykW\":[\"Download PNG\"],\"ZYFhLg\":[\"Custom color picker - edits selected palette color\"],\"ZYnwzF\":[\"Disconnected\"],\"abs0zu\":[\"Show/hide background layer\"],\"aoLy25\":[\"Opacity\"],\"fFoXXe\":[\"Authentication Failed\"],\"gcoiFh\":[\"Reconnect\"],\"h5p+K4\":[\"Session Full\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 Save to Gallery\"],\"lDWsIx\":[\"LOADING...\"],\"mB1Pom\":[\"Palette color \",[\"0\"],\" (selected - edit with color picker below)\"],\"n+L8Wp\":[\"This session is full (\",[\"0\"],\"/\",[\"1\"],\" users). Only the first \",[\"2\"],\" users can join a session.\"],\"nGXTVR\":[\"Offline Drawing (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"Unable to authenticate your session. Either the session doesn't exist, or it has expired. Please return to the lobby.\"],\"oeM2Z1\":[\"Save drawing to gallery\"],\"pqT522\":[\"Failed to download replay file.\"],\"rGVpPc\":[\"Toggle horizontal flip\"],\"vjgq8y\":[\"Pan\"],\"wSPlwJ\":[\"📄 Download PNG\"],\"y8hvHB\":[\"Remove {username} from this session?\"],\"yQE2r9\":[\"Loading\"]}")as Messages;
\ No newline at end of file
@@ -1 +1 @@
-/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"非アクティブのためコラボレーションセッションが終了しました。オーナーとして、ギャラリーに保存するか PNG としてダウンロードできます。\"],\"/iK4an\":[\"💾 PNG として保存\"],\"0iNLjx\":[\"左右反転\"],\"2BB/kB\":[\"🔄 保存中...\"],\"2FGWTy\":[\"消しゴム\"],\"3MIwIy\":[\"親投稿: \",[\"0\"]],\"4Q6lF1\":[\"（あなた）\"],\"4f2zr3\":[\"ロビーに戻る\"],\"4ny0eJ\":[\"まもなく投稿ページにリダイレクトされます。\"],\"5bi1la\":[\"ソリッド\"],\"5hw/d6\":[\"ロビーに戻る\"],\"6gRgw8\":[\"再試行\"],\"8b05T4\":[\"PNG として保存\"],\"8pzvKh\":[\"セッションの保存に失敗しました。代わりに PNG としてダウンロードしてください。\"],\"9q0OPd\":[\"カスタムカラーピッカー\"],\"9uI/rE\":[\"元に戻す\"],\"9xQ0Xz\":[\"接続が失われました。作業はローカルに保存されました。\"],\"9zb2WA\":[\"接続中\"],\"AHZflp\":[\"チャット\"],\"AZyf4e\":[\"初期化に失敗しました\"],\"Be6WYe\":[\"描画を保存\"],\"Cj2Gtd\":[\"サイズ\"],\"DIyyQk\":[\"OEEEカフェ\"],\"DMJlPw\":[\"💾 PNG をダウンロード\"],\"GNLePT\":[\"💾 保存中...\"],\"H3oH0g\":[\"やり直し\"],\"HjORs9\":[\"コラボレーションセッションを初期化中...\"],\"J28zul\":[\"接続中...\"],\"J9nTy/\":[\"ギャラリーに保存\"],\"JlFcis\":[\"送信\"],\"KsKWyh\":[\"コミュニティ: \",[\"0\"]],\"M5rEN5\":[\"セッションが期限切れです\"],\"MJ0qUX\":[\"前景レイヤーの表示/非表示\"],\"MZHPuB\":[\"参加者\"],\"OHgs6h\":[\"セッションが終了します。描画をギャラリーに保存中...\"],\"OfhWJH\":[\"リセット\"],\"OktlpZ\":[\"描画の保存に失敗しました。もう一度お試しください。\"],\"PD9mEt\":[\"メッセージを入力...\"],\"PI7Uvl\":[\"非アクティブのためコラボレーションセッションが終了しました。セッションはすでにギャラリーに保存されていますが、PNG としてダウンロードできます。\"],\"Q7wnjt\":[\"このセッションは満員です（\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\"ユーザー）。最初の\",[\"maxUsers\"],\"ユーザーのみがセッションに参加できます。\"],\"QHcLEN\":[\"接続済み\"],\"QQ4kOo\":[\"ハーフトーン\"],\"RHf7bi\":[\"このセッションはすでに保存されています。PNG としてのみダウンロードできます。\"],\"Rc2wVJ\":[\"パレットカラー \",[\"0\"],\" を編集\"],\"SnKtc6\":[\"パレットカラー \",[\"0\"]],\"TeZ01t\":[\"リプレイをダウンロード\"],\"TuM2Yu\":[\"塗りつぶし\"],\"Vi2Pqx\":[\"作成者:\"],\"XvjC4F\":[\"保存中...\"],\"Z3FXyt\":[\"読み込み中...\"],\"Z715sW\":[\"左クリックでレイヤーを選択、右クリックで表示切り替え\"],\"Z8lGw6\":[\"共有\"],\"ZS/6zt\":[\"非アクティブのためコラボレーションセッションが終了しました。退出前にローカルに PNG として保存できます。\"],\"ZV5ykW\":[\"PNG をダウンロード\"],\"ZYFhLg\":[\"カスタムカラーピッカー - 選択されたパレットカラーを編集\"],\"ZYnwzF\":[\"切断済み\"],\"abs0zu\":[\"背景レイヤーの表示/非表示\"],\"aoLy25\":[\"不透明度\"],\"fFoXXe\":[\"認証に失敗しました\"],\"gcoiFh\":[\"再接続\"],\"h5p+K4\":[\"セッション満員\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 ギャラリーに保存\"],\"lDWsIx\":[\"読み込み中...\"],\"mB1Pom\":[\"パレットカラー \",[\"0\"],\" (選択済み - 下のカラーピッカーで編集)\"],\"n+L8Wp\":[\"このセッションは満員です（\",[\"0\"],\"/\",[\"1\"],\"ユーザー）。最初の\",[\"2\"],\"ユーザーのみがセッションに参加できます。\"],\"nGXTVR\":[\"オフライン描画 (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"セッションを認証できません。セッションが存在しないか、期限切れです。ロビーに戻ってください。\"],\"oeM2Z1\":[\"描画をギャラリーに保存\"],\"pqT522\":[\"リプレイファイルのダウンロードに失敗しました。\"],\"rGVpPc\":[\"左右反転を切り替え\"],\"vjgq8y\":[\"パン\"],\"wSPlwJ\":[\"📄 PNG をダウンロード\"],\"yQE2r9\":[\"読み込み中\"]}")as Messages;
\ No newline at end of file
+/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"非アクティブのためコラボレーションセッションが終了しました。オーナーとして、ギャラリーに保存するか PNG としてダウンロードできます。\"],\"/iK4an\":[\"💾 PNG として保存\"],\"/n9Xmv\":[\"セッションのオーナーによってこのセッションから退出させられました。しばらくの間は再参加できません。\"],\"0iNLjx\":[\"左右反転\"],\"2BB/kB\":[\"🔄 保存中...\"],\"2FGWTy\":[\"消しゴム\"],\"3MIwIy\":[\"親投稿: \",[\"0\"]],\"4IArQf\":[\"セッションから退出させられました\"],\"4Q6lF1\":[\"（あなた）\"],\"4f2zr3\":[\"ロビーに戻る\"],\"4ny0eJ\":[\"まもなく投稿ページにリダイレクトされます。\"],\"5bi1la\":[\"ソリッド\"],\"5hw/d6\":[\"ロビーに戻る\"],\"6gRgw8\":[\"再試行\"],\"8b05T4\":[\"PNG として保存\"],\"8pzvKh\":[\"セッションの保存に失敗しました。代わりに PNG としてダウンロードしてください。\"],\"9q0OPd\":[\"カスタムカラーピッカー\"],\"9uI/rE\":[\"元に戻す\"],\"9xQ0Xz\":[\"接続が失われました。作業はローカルに保存されました。\"],\"9zb2WA\":[\"接続中\"],\"AHZflp\":[\"チャット\"],\"AZyf4e\":[\"初期化に失敗しました\"],\"Be6WYe\":[\"描画を保存\"],\"Cj2Gtd\":[\"サイズ\"],\"DIyyQk\":[\"OEEEカフェ\"],\"DMJlPw\":[\"💾 PNG をダウンロード\"],\"GNLePT\":[\"💾 保存中...\"],\"H3oH0g\":[\"やり直し\"],\"HjORs9\":[\"コラボレーションセッションを初期化中...\"],\"J28zul\":[\"接続中...\"],\"J9nTy/\":[\"ギャラリーに保存\"],\"JZ/4MB\":[\"セッションから退出させる\"],\"JlFcis\":[\"送信\"],\"KsKWyh\":[\"コミュニティ: \",[\"0\"]],\"M5rEN5\":[\"セッションが期限切れです\"],\"MJ0qUX\":[\"前景レイヤーの表示/非表示\"],\"MZHPuB\":[\"参加者\"],\"OHgs6h\":[\"セッションが終了します。描画をギャラリーに保存中...\"],\"OfhWJH\":[\"リセット\"],\"OktlpZ\":[\"描画の保存に失敗しました。もう一度お試しください。\"],\"PD9mEt\":[\"メッセージを入力...\"],\"PI7Uvl\":[\"非アクティブのためコラボレーションセッションが終了しました。セッションはすでにギャラリーに保存されていますが、PNG としてダウンロードできます。\"],\"Q7wnjt\":[\"このセッションは満員です（\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\"ユーザー）。最初の\",[\"maxUsers\"],\"ユーザーのみがセッションに参加できます。\"],\"QHcLEN\":[\"接続済み\"],\"QQ4kOo\":[\"ハーフトーン\"],\"RHf7bi\":[\"このセッションはすでに保存されています。PNG としてのみダウンロードできます。\"],\"Rc2wVJ\":[\"パレットカラー \",[\"0\"],\" を編集\"],\"SnKtc6\":[\"パレットカラー \",[\"0\"]],\"TeZ01t\":[\"リプレイをダウンロード\"],\"TuM2Yu\":[\"塗りつぶし\"],\"Vi2Pqx\":[\"作成者:\"],\"XvjC4F\":[\"保存中...\"],\"Z3FXyt\":[\"読み込み中...\"],\"Z715sW\":[\"左クリックでレイヤーを選択、右クリックで表示切り替え\"],\"Z8lGw6\":[\"共有\"],\"ZS/6zt\":[\"非アクティブのためコラボレーションセッションが終了しました。退出前にローカルに PNG として保存できます。\"],\"ZV5ykW\":[\"PNG をダウンロード\"],\"ZYFhLg\":[\"カスタムカラーピッカー - 選択されたパレットカラーを編集\"],\"ZYnwzF\":[\"切断済み\"],\"abs0zu\":[\"背景レイヤーの表示/非表示\"],\"aoLy25\":[\"不透明度\"],\"fFoXXe\":[\"認証に失敗しました\"],\"gcoiFh\":[\"再接続\"],\"h5p+K4\":[\"セッション満員\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 ギャラリーに保存\"],\"lDWsIx\":[\"読み込み中...\"],\"mB1Pom\":[\"パレットカラー \",[\"0\"],\" (選択済み - 下のカラーピッカーで編集)\"],\"n+L8Wp\":[\"このセッションは満員です（\",[\"0\"],\"/\",[\"1\"],\"ユーザー）。最初の\",[\"2\"],\"ユーザーのみがセッションに参加できます。\"],\"nGXTVR\":[\"オフライン描画 (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"セッションを認証できません。セッションが存在しないか、期限切れです。ロビーに戻ってください。\"],\"oeM2Z1\":[\"描画をギャラリーに保存\"],\"pqT522\":[\"リプレイファイルのダウンロードに失敗しました。\"],\"rGVpPc\":[\"左右反転を切り替え\"],\"vjgq8y\":[\"パン\"],\"wSPlwJ\":[\"📄 PNG をダウンロード\"],\"y8hvHB\":[\"{username}さんをこのセッションから退出させますか？\"],\"yQE2r9\":[\"読み込み中\"]}")as Messages;
\ No newline at end of file
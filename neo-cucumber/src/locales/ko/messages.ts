@@ -1 +1 @@
-/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"비활성으로 인해 이 협업 세션이 종료되었습니다. 소유자로서 갤러리에 저장하거나 PNG로 다운로드할 수 있습니다.\"],\"/iK4an\":[\"💾 PNG로 저장\"],\"0iNLjx\":[\"좌우 반전\"],\"2BB/kB\":[\"🔄 저장 중...\"],\"2FGWTy\":[\"지우개\"],\"3MIwIy\":[\"부모 게시물: \",[\"0\"]],\"4Q6lF1\":[\"(나)\"],\"4f2zr3\":[\"로비로 가기\"],\"4ny0eJ\":[\"곧 게시물 페이지로 리디렉션됩니다.\"],\"5bi1la\":[\"실선\"],\"5hw/d6\":[\"로비로 돌아가기\"],\"6gRgw8\":[\"다시 시도\"],\"8b05T4\":[\"PNG로 저장\"],\"8pzvKh\":[\"세션 저장에 실패했습니다. 대신 PNG로 다운로드해 보세요.\"],\"9q0OPd\":[\"사용자 정의 색상 선택기\"],\"9uI/rE\":[\"실행 취소\"],\"9xQ0Xz\":[\"연결이 끊어졌습니다. 작업이 로컬에 저장되었습니다.\"],\"9zb2WA\":[\"연결 중\"],\"AHZflp\":[\"채팅\"],\"AZyf4e\":[\"초기화 실패\"],\"Be6WYe\":[\"그림 저장\"],\"Cj2Gtd\":[\"크기\"],\"DIyyQk\":[\"오이카페\"],\"DMJlPw\":[\"💾 PNG 다운로드\"],\"GNLePT\":[\"💾 저장 중...\"],\"H3oH0g\":[\"다시 실행\"],\"HjORs9\":[\"협업 세션 초기화 중...\"],\"J28zul\":[\"연결 중...\"],\"J9nTy/\":[\"갤러리에 저장\"],\"JlFcis\":[\"전송\"],\"KsKWyh\":[\"커뮤니티: \",[\"0\"]],\"M5rEN5\":[\"세션 만료\"],\"MJ0qUX\":[\"전경 레이어 표시/숨기기\"],\"MZHPuB\":[\"참여자\"],\"OHgs6h\":[\"세션이 종료됩니다. 그림을 갤러리에 저장 중...\"],\"OfhWJH\":[\"초기화\"],\"OktlpZ\":[\"그림 저장에 실패했습니다. 다시 시도해 주세요.\"],\"PD9mEt\":[\"메시지 입력...\"],\"PI7Uvl\":[\"비활성으로 인해 이 협업 세션이 종료되었습니다. 세션이 이미 갤러리에 저장되었지만 PNG로 다운로드할 수 있습니다.\"],\"Q7wnjt\":[\"이 세션이 가득 찼습니다 (\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\"명). 처음 \",[\"maxUsers\"],\"명의 사용자만 세션에 참여할 수 있습니다.\"],\"QHcLEN\":[\"연결됨\"],\"QQ4kOo\":[\"하프톤\"],\"RHf7bi\":[\"이 세션은 이미 저장되었습니다. PNG로만 다운로드할 수 있습니다.\"],\"Rc2wVJ\":[\"팔레트 색상 \",[\"0\"],\" 편집\"],\"SnKtc6\":[\"팔레트 색상 \",[\"0\"]],\"TeZ01t\":[\"리플레이 다운로드\"],\"TuM2Yu\":[\"채우기\"],\"Vi2Pqx\":[\"작성자:\"],\"XvjC4F\":[\"저장 중...\"],\"Z3FXyt\":[\"로딩 중...\"],\"Z715sW\":[\"왼쪽 클릭으로 레이어 선택, 오른쪽 클릭으로 표시 전환\"],\"Z8lGw6\":[\"공유\"],\"ZS/6zt\":[\"비활성으로 인해 이 협업 세션이 종료되었습니다. 떠나기 전에 작업을 PNG로 로컬에 저장할 수 있습니다.\"],\"ZV5ykW\":[\"PNG 다운로드\"],\"ZYFhLg\":[\"사용자 정의 색상 선택기 - 선택된 팔레트 색상 편집\"],\"ZYnwzF\":[\"연결 끊어짐\"],\"abs0zu\":[\"배경 레이어 표시/숨기기\"],\"aoLy25\":[\"불투명도\"],\"fFoXXe\":[\"인증 실패\"],\"gcoiFh\":[\"재연결\"],\"h5p+K4\":[\"세션 만석\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 갤러리에 저장\"],\"lDWsIx\":[\"로딩 중...\"],\"mB1Pom\":[\"팔레트 색상 \",[\"0\"],\" (선택됨 - 아래 색상 선택기로 편집)\"],\"n+L8Wp\":[\"이 세션이 가득참 (\",[\"0\"],\"/\",[\"1\"],\" 사용자). 처음 \",[\"2\"],\"명의 사용자만 세션에 참여할 수 있습니다.\"],\"nGXTVR\":[\"오프라인 그리기 (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"세션을 인증할 수 없습니다. 세션이 존재하지 않거나 만료되었습니다. 로비로 돌아가 주세요.\"],\"oeM2Z1\":[\"그림을 갤러리에 저장\"],\"pqT522\":[\"리플레이 파일 다운로드에 실패했습니다.\"],\"rGVpPc\":[\"좌우 반전 전환\"],\"vjgq8y\":[\"이동\"],\"wSPlwJ\":[\"📄 PNG 다운로드\"],\"yQE2r9\":[\"로딩 중\"]}")as Messages;
\ No newline at end of file
+/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"비활성으로 인해 이 협업 세션이 종료되었습니다. 소유자로서 갤러리에 저장하거나 PNG로 다운로드할 수 있습니다.\"],\"/iK4an\":[\"💾 PNG로 저장\"],\"/n9Xmv\":[\"세션 소유자가 회원님을 이 세션에서 내보냈습니다. 한동안 다시 참여할 수 없습니다.\"],\"0iNLjx\":[\"좌우 반전\"],\"2BB/kB\":[\"🔄 저장 중...\"],\"2FGWTy\":[\"지우개\"],\"3MIwIy\":[\"부모 게시물: \",[\"0\"]],\"4IArQf\":[\"세션에서 내보내짐\"],\"4Q6lF1\":[\"(나)\"],\"4f2zr3\":[\"로비로 가기\"],\"4ny0eJ\":[\"곧 게시물 페이지로 리디렉션됩니다.\"],\"5bi1la\":[\"실선\"],\"5hw/d6\":[\"로비로 돌아가기\"],\"6gRgw8\":[\"다시 시도\"],\"8b05T4\":[\"PNG로 저장\"],\"8pzvKh\":[\"세션 저장에 실패했습니다. 대신 PNG로 다운로드해 보세요.\"],\"9q0OPd\":[\"사용자 정의 색상 선택기\"],\"9uI/rE\":[\"실행 취소\"],\"9xQ0Xz\":[\"연결이 끊어졌습니다. 작업이 로컬에 저장되었습니다.\"],\"9zb2WA\":[\"연결 중\"],\"AHZflp\":[\"채팅\"],\"AZyf4e\":[\"초기화 실패\"],\"Be6WYe\":[\"그림 저장\"],\"Cj2Gtd\":[\"크기\"],\"DIyyQk\":[\"오이카페\"],\"DMJlPw\":[\"💾 PNG 다운로드\"],\"GNLePT\":[\"💾 저장 중...\"],\"H3oH0g\":[\"다시 실행\"],\"HjORs9\":[\"협업 세션 초기화 중...\"],\"J28zul\":[\"연결 중...\"],\"J9nTy/\":[\"갤러리에 저장\"],\"JZ/4MB\":[\"세션에서 내보내기\"],\"JlFcis\":[\"전송\"],\"KsKWyh\":[\"커뮤니티: \",[\"0\"]],\"M5rEN5\":[\"세션 만료\"],\"MJ0qUX\":[\"전경 레이어 표시/숨기기\"],\"MZHPuB\":[\"참여자\"],\"OHgs6h\":[\"세션이 종료됩니다. 그림을 갤러리에 저장 중...\"],\"OfhWJH\":[\"초기화\"],\"OktlpZ\":[\"그림 저장에 실패했습니다. 다시 시도해 주세요.\"],\"PD9mEt\":[\"메시지 입력...\"],\"PI7Uvl\":[\"비활성으로 인해 이 협업 세션이 종료되었습니다. 세션이 이미 갤러리에 저장되었지만 PNG로 다운로드할 수 있습니다.\"],\"Q7wnjt\":[\"이 세션이 가득 찼습니다 (\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\"명). 처음 \",[\"maxUsers\"],\"명의 사용자만 세션에 참여할 수 있습니다.\"],\"QHcLEN\":[\"연결됨\"],\"QQ4kOo\":[\"하프톤\"],\"RHf7bi\":[\"이 세션은 이미 저장되었습니다. PNG로만 다운로드할 수 있습니다.\"],\"Rc2wVJ\":[\"팔레트 색상 \",[\"0\"],\" 편집\"],\"SnKtc6\":[\"팔레트 색상 \",[\"0\"]],\"TeZ01t\":[\"리플레이 다운로드\"],\"TuM2Yu\":[\"채우기\"],\"Vi2Pqx\":[\"작성자:\"],\"XvjC4F\":[\"저장 중...\"],\"Z3FXyt\":[\"로딩 중...\"],\"Z715sW\":[\"왼쪽 클릭으로 레이어 선택, 오른쪽 클릭으로 표시 전환\"],\"Z8lGw6\":[\"공유\"],\"ZS/6zt\":[\"비활성으로 인해 이 협업 세션이 종료되었습니다. 떠나기 전에 작업을 PNG로 로컬에 저장할 수 있습니다.\"],\"ZV5ykW\":[\"PNG 다운로드\"],\"ZYFhLg\":[\"사용자 정의 색상 선택기 - 선택된 팔레트 색상 편집\"],\"ZYnwzF\":[\"연결 끊어짐\"],\"abs0zu\":[\"배경 레이어 표시/숨기기\"],\"aoLy25\":[\"불투명도\"],\"fFoXXe\":[\"인증 실패\"],\"gcoiFh\":[\"재연결\"],\"h5p+K4\":[\"세션 만석\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 갤러리에 저장\"],\"lDWsIx\":[\"로딩 중...\"],\"mB1Pom\":[\"팔레트 색상 \",[\"0\"],\" (선택됨 - 아래 색상 선택기로 편집)\"],\"n+L8Wp\":[\"이 세션이 가득참 (\",[\"0\"],\"/\",[\"1\"],\" 사용자). 처음 \",[\"2\"],\"명의 사용자만 세션에 참여할 수 있습니다.\"],\"nGXTVR\":[\"오프라인 그리기 (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"세션을 인증할 수 없습니다. 세션이 존재하지 않거나 만료되었습니다. 로비로 돌아가 주세요.\"],\"oeM2Z1\":[\"그림을 갤러리에 저장\"],\"pqT522\":[\"리플레이 파일 다운로드에 실패했습니다.\"],\"rGVpPc\":[\"좌우 반전 전환\"],\"vjgq8y\":[\"이동\"],\"wSPlwJ\":[\"📄 PNG 다운로드\"],\"y8hvHB\":[\"{username}님을 이 세션에서 내보낼까요?\"],\"yQE2r9\":[\"로딩 중\"]}")as Messages;
\ No newline at end of file
@@ -1 +1 @@
-/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"由于无活动，此协作会话已结束。作为所有者，您可以将其保存到画廊或下载为 PNG。\"],\"/iK4an\":[\"💾 保存为 PNG\"],\"0iNLjx\":[\"水平翻转\"],\"2BB/kB\":[\"🔄 保存中...\"],\"2FGWTy\":[\"橡皮擦\"],\"3MIwIy\":[\"父帖子: \",[\"0\"]],\"4Q6lF1\":[\"(您)\"],\"4f2zr3\":[\"去大厅\"],\"4ny0eJ\":[\"您将稍后被重定向到帖子页面。\"],\"5bi1la\":[\"实心\"],\"5hw/d6\":[\"返回大厅\"],\"6gRgw8\":[\"重试\"],\"8b05T4\":[\"保存为 PNG\"],\"8pzvKh\":[\"保存会话失败。请尝试下载为 PNG。\"],\"9q0OPd\":[\"自定义色彩选择器\"],\"9uI/rE\":[\"撤销\"],\"9xQ0Xz\":[\"连接丢失。您的作品已本地保存。\"],\"9zb2WA\":[\"连接中\"],\"AHZflp\":[\"聊天\"],\"AZyf4e\":[\"初始化失败\"],\"Be6WYe\":[\"保存绘画\"],\"Cj2Gtd\":[\"大小\"],\"DIyyQk\":[\"黄瓜咖啡馆\"],\"DMJlPw\":[\"💾 下载 PNG\"],\"GNLePT\":[\"💾 保存中...\"],\"H3oH0g\":[\"重做\"],\"HjORs9\":[\"正在初始化协作会话...\"],\"J28zul\":[\"连接中...\"],\"J9nTy/\":[\"保存到画廊\"],\"JlFcis\":[\"发送\"],\"KsKWyh\":[\"社区: \",[\"0\"]],\"M5rEN5\":[\"会话过期\"],\"MJ0qUX\":[\"显示/隐藏前景图层\"],\"MZHPuB\":[\"参与者\"],\"OHgs6h\":[\"会话即将结束。正在保存绘画到画廊...\"],\"OfhWJH\":[\"重置\"],\"OktlpZ\":[\"保存绘画失败。请重试。\"],\"PD9mEt\":[\"输入消息...\"],\"PI7Uvl\":[\"由于无活动，此协作会话已结束。会话已保存到画廊，但您可以将其下载为 PNG。\"],\"Q7wnjt\":[\"此会话已满（\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\" 位用户）。只有前 \",[\"maxUsers\"],\" 位用户可以加入会话。\"],\"QHcLEN\":[\"已连接\"],\"QQ4kOo\":[\"半调\"],\"RHf7bi\":[\"此会话已保存。您只能将其下载为 PNG。\"],\"Rc2wVJ\":[\"编辑调色板颜色 \",[\"0\"]],\"SnKtc6\":[\"调色板颜色 \",[\"0\"]],\"TeZ01t\":[\"下载回放\"],\"TuM2Yu\":[\"填充\"],\"Vi2Pqx\":[\"作者:\"],\"XvjC4F\":[\"保存中...\"],\"Z3FXyt\":[\"加载中...\"],\"Z715sW\":[\"左键选择图层，右键切换可见性\"],\"Z8lGw6\":[\"分享\"],\"ZS/6zt\":[\"由于无活动，此协作会话已结束。您可以在离开前将您的作品保存为 PNG。\"],\"ZV5ykW\":[\"下载 PNG\"],\"ZYFhLg\":[\"自定义色彩选择器 - 编辑选中的调色板颜色\"],\"ZYnwzF\":[\"已断开\"],\"abs0zu\":[\"显示/隐藏背景图层\"],\"aoLy25\":[\"不透明度\"],\"fFoXXe\":[\"验证失败\"],\"gcoiFh\":[\"重新连接\"],\"h5p+K4\":[\"会话已满\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 保存到画廊\"],\"lDWsIx\":[\"加载中...\"],\"mB1Pom\":[\"调色板颜色 \",[\"0\"],\" (已选中 - 使用下方色彩选择器编辑)\"],\"n+L8Wp\":[\"此会话已满（\",[\"0\"],\"/\",[\"1\"],\" 用户）。只有前 \",[\"2\"],\" 位用户可以加入会话。\"],\"nGXTVR\":[\"离线绘画 (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"无法验证您的会话。会话不存在或已过期。请返回大厅。\"],\"oeM2Z1\":[\"保存绘画到画廊\"],\"pqT522\":[\"下载回放文件失败。\"],\"rGVpPc\":[\"切换水平翻转\"],\"vjgq8y\":[\"平移\"],\"wSPlwJ\":[\"📄 下载 PNG\"],\"yQE2r9\":[\"加载中\"]}")as Messages;
\ No newline at end of file
+/*eslint-disable*/import type{Messages}from"@lingui/core";export const messages=JSON.parse("{\"/ggvcx\":[\"由于无活动，此协作会话已结束。作为所有者，您可以将其保存到画廊或下载为 PNG。\"],\"/iK4an\":[\"💾 保存为 PNG\"],\"/n9Xmv\":[\"会话所有者已将你移出此会话。你在一段时间内无法重新加入。\"],\"0iNLjx\":[\"水平翻转\"],\"2BB/kB\":[\"🔄 保存中...\"],\"2FGWTy\":[\"橡皮擦\"],\"3MIwIy\":[\"父帖子: \",[\"0\"]],\"4IArQf\":[\"已被移出会话\"],\"4Q6lF1\":[\"(您)\"],\"4f2zr3\":[\"去大厅\"],\"4ny0eJ\":[\"您将稍后被重定向到帖子页面。\"],\"5bi1la\":[\"实心\"],\"5hw/d6\":[\"返回大厅\"],\"6gRgw8\":[\"重试\"],\"8b05T4\":[\"保存为 PNG\"],\"8pzvKh\":[\"保存会话失败。请尝试下载为 PNG。\"],\"9q0OPd\":[\"自定义色彩选择器\"],\"9uI/rE\":[\"撤销\"],\"9xQ0Xz\":[\"连接丢失。您的作品已本地保存。\"],\"9zb2WA\":[\"连接中\"],\"AHZflp\":[\"聊天\"],\"AZyf4e\":[\"初始化失败\"],\"Be6WYe\":[\"保存绘画\"],\"Cj2Gtd\":[\"大小\"],\"DIyyQk\":[\"黄瓜咖啡馆\"],\"DMJlPw\":[\"💾 下载 PNG\"],\"GNLePT\":[\"💾 保存中...\"],\"H3oH0g\":[\"重做\"],\"HjORs9\":[\"正在初始化协作会话...\"],\"J28zul\":[\"连接中...\"],\"J9nTy/\":[\"保存到画廊\"],\"JZ/4MB\":[\"移出会话\"],\"JlFcis\":[\"发送\"],\"KsKWyh\":[\"社区: \",[\"0\"]],\"M5rEN5\":[\"会话过期\"],\"MJ0qUX\":[\"显示/隐藏前景图层\"],\"MZHPuB\":[\"参与者\"],\"OHgs6h\":[\"会话即将结束。正在保存绘画到画廊...\"],\"OfhWJH\":[\"重置\"],\"OktlpZ\":[\"保存绘画失败。请重试。\"],\"PD9mEt\":[\"输入消息...\"],\"PI7Uvl\":[\"由于无活动，此协作会话已结束。会话已保存到画廊，但您可以将其下载为 PNG。\"],\"Q7wnjt\":[\"此会话已满（\",[\"currentUserCount\"],\"/\",[\"maxUsers\"],\" 位用户）。只有前 \",[\"maxUsers\"],\" 位用户可以加入会话。\"],\"QHcLEN\":[\"已连接\"],\"QQ4kOo\":[\"半调\"],\"RHf7bi\":[\"此会话已保存。您只能将其下载为 PNG。\"],\"Rc2wVJ\":[\"编辑调色板颜色 \",[\"0\"]],\"SnKtc6\":[\"调色板颜色 \",[\"0\"]],\"TeZ01t\":[\"下载回放\"],\"TuM2Yu\":[\"填充\"],\"Vi2Pqx\":[\"作者:\"],\"XvjC4F\":[\"保存中...\"],\"Z3FXyt\":[\"加载中...\"],\"Z715sW\":[\"左键选择图层，右键切换可见性\"],\"Z8lGw6\":[\"分享\"],\"ZS/6zt\":[\"由于无活动，此协作会话已结束。您可以在离开前将您的作品保存为 PNG。\"],\"ZV5ykW\":[\"下载 PNG\"],\"ZYFhLg\":[\"自定义色彩选择器 - 编辑选中的调色板颜色\"],\"ZYnwzF\":[\"已断开\"],\"abs0zu\":[\"显示/隐藏背景图层\"],\"aoLy25\":[\"不透明度\"],\"fFoXXe\":[\"验证失败\"],\"gcoiFh\":[\"重新连接\"],\"h5p+K4\":[\"会话已满\"],\"jZlrte\":[\"Color\"],\"kAj690\":[\"💾 保存到画廊\"],\"lDWsIx\":[\"加载中...\"],\"mB1Pom\":[\"调色板颜色 \",[\"0\"],\" (已选中 - 使用下方色彩选择器编辑)\"],\"n+L8Wp\":[\"此会话已满（\",[\"0\"],\"/\",[\"1\"],\" 用户）。只有前 \",[\"2\"],\" 位用户可以加入会话。\"],\"nGXTVR\":[\"离线绘画 (\",[\"canvasWidth\"],\" × \",[\"canvasHeight\"],\")\"],\"nP5Qxw\":[\"无法验证您的会话。会话不存在或已过期。请返回大厅。\"],\"oeM2Z1\":[\"保存绘画到画廊\"],\"pqT522\":[\"下载回放文件失败。\"],\"rGVpPc\":[\"切换水平翻转\"],\"vjgq8y\":[\"平移\"],\"wSPlwJ\":[\"📄 下载 PNG\"],\"y8hvHB\":[\"要将 {username} 移出此会话吗？\"],\"yQE2r9\":[\"加载中\"]}")as Messages;
\ No newline at end of file
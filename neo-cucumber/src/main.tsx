@@ -2,6 +2,7 @@ import { StrictMode } from "react";
 import { createRoot } from "react-dom/client";
 import App from "./App.tsx";
 import OfflineApp from "./OfflineApp.tsx";
+import ReplayApp from "./ReplayApp.tsx";
 import { I18nProvider } from "@lingui/react";
 import { i18n } from "@lingui/core";
 import * as Sentry from "@sentry/react";
@@ -34,10 +35,13 @@ const isOfflineMode =
   window.location.pathname.includes('/draw') ||
   window.location.search.includes('offline=true');
 
+// Recorded sessions are played back without connecting to a session
+const isReplayMode = window.location.pathname.startsWith('/collaborate/replay');
+
 createRoot(document.getElementById("root")!).render(
   <StrictMode>
     <I18nProvider i18n={i18n} defaultComponent={DefaultI18n}>
-      {isOfflineMode ? <OfflineApp /> : <App />}
+      {isReplayMode ? <ReplayApp /> : isOfflineMode ? <OfflineApp /> : <App />}
     </I18nProvider>
   </StrictMode>
 );
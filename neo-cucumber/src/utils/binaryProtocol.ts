@@ -17,6 +17,11 @@ export const MSG_TYPE = {
   END_SESSION: 0x07,
   SESSION_EXPIRED: 0x08,
   LEAVE: 0x09,
+  SPECTATOR_COUNT: 0x0a,
+  KICK: 0x0b,
+  PRESENCE: 0x0c,
+  SEQUENCED: 0x0d,
+  RESYNC: 0x0e,
 
   // Client messages (>= 0x10) - server just broadcasts
   DRAW_LINE: 0x10,
@@ -364,6 +369,54 @@ export function encodeEndSession(userId: string, postUrl: string): ArrayBuffer {
   return buffer.buffer;
 }
 
+/**
+ * Encode KICK message (0x0B), honored only when sent by the session owner
+ * Format: [0x0B][UUID:16][targetUUID:16]
+ */
+export function encodeKick(userId: string, targetUserId: string): ArrayBuffer {
+  const buffer = new Uint8Array(33);
+
+  buffer[0] = MSG_TYPE.KICK;
+  buffer.set(uuidToBytes(userId), 1);
+  buffer.set(uuidToBytes(targetUserId), 17);
+
+  return buffer.buffer;
+}
+
+// Tools reported in PRESENCE messages, indexed by their wire value
+const PRESENCE_TOOLS = ["solid", "halftone", "eraser", "fill", "pan"] as const;
+export type PresenceTool = (typeof PRESENCE_TOOLS)[number];
+
+/**
+ * Encode PRESENCE message (0x0C), relayed live and never stored
+ * Format: [0x0C][UUID:16][x:2][y:2][flags:1][tool:1][r:1][g:1][b:1]
+ * Flag bit 0 is set while the pointer is over the canvas
+ */
+export function encodePresence(
+  userId: string,
+  x: number,
+  y: number,
+  visible: boolean,
+  tool: PresenceTool,
+  r: number,
+  g: number,
+  b: number
+): ArrayBuffer {
+  const buffer = new Uint8Array(26);
+
+  buffer[0] = MSG_TYPE.PRESENCE;
+  buffer.set(uuidToBytes(userId), 1);
+  writeInt16LE(buffer, 17, Math.round(x));
+  writeInt16LE(buffer, 19, Math.round(y));
+  buffer[21] = visible ? 1 : 0;
+  buffer[22] = PRESENCE_TOOLS.indexOf(tool);
+  buffer[23] = r;
+  buffer[24] = g;
+  buffer[25] = b;
+
+  return buffer.buffer;
+}
+
 /**
  * Encode POINTER_UP message (0x13)
  * Format: [0x13][UUID:16][x:2][y:2][button:1][pointerType:1]
@@ -487,6 +540,27 @@ export interface LeaveMessage {
   timestamp: number;
 }
 
+export interface PresenceMessage {
+  type: "presence";
+  userId: string;
+  x: number;
+  y: number;
+  visible: boolean;
+  tool: PresenceTool;
+  color: { r: number; g: number; b: number };
+}
+
+export interface SpectatorCountMessage {
+  type: "spectatorCount";
+  count: number;
+}
+
+export interface ResyncMessage {
+  type: "resync";
+  fullReload: boolean;
+  sequence: number;
+}
+
 export type DecodedMessage =
   | JoinMessage
   | LayersMessage
@@ -499,7 +573,24 @@ export type DecodedMessage =
   | PointerUpMessage
   | EndSessionMessage
   | SessionExpiredMessage
-  | LeaveMessage;
+  | LeaveMessage
+  | PresenceMessage
+  | SpectatorCountMessage
+  | ResyncMessage;
+
+/**
+ * Read the op sequence number carried by SEQUENCED and RESYNC messages
+ */
+export function readSequence(data: ArrayBuffer): number | null {
+  const buffer = new Uint8Array(data);
+  if (buffer[0] === MSG_TYPE.SEQUENCED && buffer.length >= 9) {
+    return readUint64LE(buffer, 1);
+  }
+  if (buffer[0] === MSG_TYPE.RESYNC && buffer.length >= 10) {
+    return readUint64LE(buffer, 2);
+  }
+  return null;
+}
 
 /**
  * Decode SNAPSHOT message specifically
@@ -733,6 +824,41 @@ export function decodeMessage(data: ArrayBuffer): DecodedMessage | null {
       };
     }
 
+    case MSG_TYPE.SPECTATOR_COUNT: {
+      // Format: [0x0A][count:4]
+      if (buffer.length < 5) return null;
+      return {
+        type: "spectatorCount",
+        count: readUint32LE(buffer, 1),
+      };
+    }
+
+    case MSG_TYPE.PRESENCE:
+      if (buffer.length < 26) return null;
+      return {
+        type: "presence",
+        userId: bytesToUuid(buffer.slice(1, 17)),
+        x: readInt16LE(buffer, 17),
+        y: readInt16LE(buffer, 19),
+        visible: (buffer[21] & 1) === 1,
+        tool: PRESENCE_TOOLS[buffer[22]] ?? "solid",
+        color: { r: buffer[23], g: buffer[24], b: buffer[25] },
+      };
+
+    case MSG_TYPE.SEQUENCED:
+      // Format: [0x0D][sequence:8][op:variable]
+      if (buffer.length < 10) return null;
+      return decodeMessage(data.slice(9));
+
+    case MSG_TYPE.RESYNC:
+      // Format: [0x0E][fullReload:1][sequence:8]
+      if (buffer.length < 10) return null;
+      return {
+        type: "resync",
+        fullReload: buffer[1] === 1,
+        sequence: readUint64LE(buffer, 2),
+      };
+
     default:
       return null;
   }
@@ -0,0 +1,57 @@
+use redis::AsyncCommands;
+
+use crate::models::announcement::Announcement;
+use crate::redis::RedisPool;
+
+const CURRENT_KEY: &str = "oeee:announcements:current";
+// Entries are dropped whenever an admin changes announcements; this only bounds a missed drop
+const CURRENT_TTL: u64 = 3600;
+
+/// Caches the active announcements that haven't ended yet, so rendering the
+/// banner doesn't query the database on every page. Scheduled announcements
+/// are included and filtered by time when read.
+pub struct AnnouncementCache {
+    pool: RedisPool,
+}
+
+impl AnnouncementCache {
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn load(
+        &self,
+    ) -> Result<Option<Vec<Announcement>>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let value: Option<String> = conn.get(CURRENT_KEY).await?;
+        Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    pub async fn store(
+        &self,
+        announcements: &[Announcement],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        conn.set_ex::<_, _, ()>(
+            CURRENT_KEY,
+            serde_json::to_string(announcements)?,
+            CURRENT_TTL,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Drop the cached announcements so the next page reloads them. Failures
+    /// are only logged, since the entry expires on its own.
+    pub async fn invalidate(&self) {
+        let result: Result<(), Box<dyn std::error::Error + Send + Sync>> = async {
+            let mut conn = self.pool.get().await?;
+            conn.del::<_, ()>(CURRENT_KEY).await?;
+            Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to invalidate announcement cache: {:?}", e);
+        }
+    }
+}
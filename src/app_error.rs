@@ -11,14 +11,20 @@ pub mod error_codes {
     pub const INTERNAL_ERROR: &str = "INTERNAL_ERROR";
     pub const UNAUTHORIZED: &str = "UNAUTHORIZED";
     pub const NOT_FOUND: &str = "NOT_FOUND";
+    pub const GONE: &str = "GONE";
     pub const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
     pub const INVALID_CREDENTIALS: &str = "INVALID_CREDENTIALS";
     pub const EMAIL_ALREADY_EXISTS: &str = "EMAIL_ALREADY_EXISTS";
     pub const USERNAME_ALREADY_EXISTS: &str = "USERNAME_ALREADY_EXISTS";
     pub const EMAIL_ALREADY_VERIFIED: &str = "EMAIL_ALREADY_VERIFIED";
+    pub const EMAIL_DOMAIN_NOT_ALLOWED: &str = "EMAIL_DOMAIN_NOT_ALLOWED";
     pub const INVALID_VERIFICATION_CODE: &str = "INVALID_VERIFICATION_CODE";
+    pub const TOTP_REQUIRED: &str = "TOTP_REQUIRED";
+    pub const INVALID_TOTP_CODE: &str = "INVALID_TOTP_CODE";
     pub const FORBIDDEN: &str = "FORBIDDEN";
     pub const CONFLICT: &str = "CONFLICT";
+    pub const RATE_LIMITED: &str = "RATE_LIMITED";
+    pub const DATA_INTEGRITY: &str = "DATA_INTEGRITY";
 }
 
 /// Check if an error should be filtered from Sentry reporting.
@@ -56,7 +62,11 @@ pub enum AppError {
     InvalidCommunityId(String),
     Unauthorized,
     NotFound(String),
+    // The resource existed but has been deleted
+    Gone(String),
     DatabaseError(String),
+    // A row we expected to be well-formed is missing a core field
+    DataIntegrity(String),
 }
 
 // Tell axum how to convert `AppError` into a response.
@@ -127,12 +137,27 @@ impl IntoResponse for AppError {
                 format!("{} not found", resource),
                 true,
             ),
+            AppError::Gone(resource) => (
+                StatusCode::GONE,
+                error_codes::GONE,
+                format!("{} has been deleted", resource),
+                false,
+            ),
             AppError::DatabaseError(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 error_codes::INTERNAL_ERROR,
                 format!("Database error: {}", msg),
                 true,
             ),
+            AppError::DataIntegrity(msg) => {
+                tracing::error!("Data integrity error: {}", msg);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_codes::DATA_INTEGRITY,
+                    format!("Data integrity error: {}", msg),
+                    true,
+                )
+            }
         };
 
         // Capture non-anyhow errors as messages (no backtrace available since they're just strings)
@@ -164,7 +189,9 @@ impl fmt::Display for AppError {
             AppError::InvalidCommunityId(msg) => write!(f, "Invalid community ID: {}", msg),
             AppError::Unauthorized => write!(f, "Unauthorized"),
             AppError::NotFound(resource) => write!(f, "{} not found", resource),
+            AppError::Gone(resource) => write!(f, "{} has been deleted", resource),
             AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+            AppError::DataIntegrity(msg) => write!(f, "Data integrity error: {}", msg),
         }
     }
 }
@@ -5,6 +5,7 @@ use oeee_cafe::{
         actor::{backfill_actors_for_existing_communities, backfill_actors_for_existing_users},
         community::get_communities,
         device::get_user_devices,
+        oauth::create_oauth_client,
         user::{find_user_by_id, find_user_by_login_name, update_password},
     },
     push::PushService,
@@ -39,6 +40,12 @@ enum Commands {
     BackfillCommunityActors,
     /// Send a test push notification to a user
     SendTestPush { login_name: String },
+    /// Register a native app that signs users in through OAuth2 with PKCE
+    CreateOauthClient {
+        name: String,
+        #[arg(required = true)]
+        redirect_uris: Vec<String>,
+    },
 }
 
 #[tokio::main]
@@ -144,6 +151,15 @@ async fn main() -> Result<()> {
                 created_count
             );
         }
+        Commands::CreateOauthClient {
+            name,
+            redirect_uris,
+        } => {
+            let client = create_oauth_client(&mut tx, name, redirect_uris).await?;
+            tx.commit().await?;
+            println!("✅ Created OAuth client '{}'", client.name);
+            println!("client_id: {}", client.client_id);
+        }
         Commands::SendTestPush { login_name } => {
             println!("Looking up user '{}'...", login_name);
             let user = find_user_by_login_name(&mut tx, login_name).await?;
@@ -178,6 +194,7 @@ async fn main() -> Result<()> {
                     match push_service
                         .send_notification_to_user(
                             user.id,
+                            None,
                             "Test Push Notification",
                             "This is a test notification from the CLI",
                             Some(1),
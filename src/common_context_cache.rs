@@ -0,0 +1,220 @@
+use std::future::Future;
+
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use uuid::Uuid;
+
+use crate::models::notification::NotificationEvent;
+use crate::redis::RedisPool;
+
+const COUNTS_PREFIX: &str = "oeee:common_ctx:";
+// Short enough that a write path we don't invalidate from is only briefly stale
+const COUNTS_TTL: u64 = 60;
+
+type StoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The per-user badge counts shown in the page header
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct CachedCommonCounts {
+    pub draft_post_count: i64,
+    pub unread_notification_count: i64,
+}
+
+/// Caches each signed-in user's header counts so pages don't recount drafts
+/// and notifications on every request
+pub struct CommonContextCache {
+    pool: RedisPool,
+}
+
+impl CommonContextCache {
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    fn key(user_id: Uuid) -> String {
+        format!("{}{}", COUNTS_PREFIX, user_id)
+    }
+
+    pub async fn load(&self, user_id: Uuid) -> Result<Option<CachedCommonCounts>, StoreError> {
+        let mut conn = self.pool.get().await?;
+        let value: Option<String> = conn.get(Self::key(user_id)).await?;
+        Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    pub async fn store(
+        &self,
+        user_id: Uuid,
+        counts: &CachedCommonCounts,
+    ) -> Result<(), StoreError> {
+        let mut conn = self.pool.get().await?;
+        conn.set_ex::<_, _, ()>(
+            Self::key(user_id),
+            serde_json::to_string(counts)?,
+            COUNTS_TTL,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Drop a user's cached counts so the next page recounts them. Failures are
+    /// only logged, since entries expire on their own.
+    pub async fn invalidate(&self, user_id: Uuid) {
+        let result: Result<(), StoreError> = async {
+            let mut conn = self.pool.get().await?;
+            conn.del::<_, ()>(Self::key(user_id)).await?;
+            Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to invalidate common context cache for user {}: {:?}",
+                user_id,
+                e
+            );
+        }
+    }
+}
+
+/// Where header counts are cached between requests
+#[async_trait::async_trait]
+pub trait CountsStore: Send + Sync {
+    async fn load(&self, user_id: Uuid) -> Result<Option<CachedCommonCounts>, StoreError>;
+    async fn store(&self, user_id: Uuid, counts: &CachedCommonCounts) -> Result<(), StoreError>;
+    async fn invalidate(&self, user_id: Uuid);
+}
+
+#[async_trait::async_trait]
+impl CountsStore for CommonContextCache {
+    async fn load(&self, user_id: Uuid) -> Result<Option<CachedCommonCounts>, StoreError> {
+        CommonContextCache::load(self, user_id).await
+    }
+
+    async fn store(&self, user_id: Uuid, counts: &CachedCommonCounts) -> Result<(), StoreError> {
+        CommonContextCache::store(self, user_id, counts).await
+    }
+
+    async fn invalidate(&self, user_id: Uuid) {
+        CommonContextCache::invalidate(self, user_id).await
+    }
+}
+
+/// A user's cached counts, or freshly counted ones that are cached for the next
+/// request. An unreachable cache only costs the recount.
+pub async fn load_or_count<S, F, Fut>(store: &S, user_id: Uuid, count: F) -> CachedCommonCounts
+where
+    S: CountsStore,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = CachedCommonCounts>,
+{
+    match store.load(user_id).await {
+        Ok(Some(counts)) => return counts,
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to load common context cache: {:?}", e),
+    }
+
+    let counts = count().await;
+    if let Err(e) = store.store(user_id, &counts).await {
+        tracing::warn!("Failed to store common context cache: {:?}", e);
+    }
+    counts
+}
+
+/// Drop the recipient's cached counts whenever a notification is created.
+/// Every notification goes through `create_notification`, which publishes here.
+pub async fn invalidate_on_notification_events(
+    pool: RedisPool,
+    mut receiver: Receiver<NotificationEvent>,
+) {
+    let cache = CommonContextCache::new(pool);
+    loop {
+        match receiver.recv().await {
+            Ok(event) => cache.invalidate(event.recipient_id).await,
+            // Missed events only leave counts stale until the TTL runs out
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    "Common context cache missed {} notification events",
+                    skipped
+                );
+            }
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicI64, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        counts: Mutex<HashMap<Uuid, CachedCommonCounts>>,
+    }
+
+    #[async_trait::async_trait]
+    impl CountsStore for MemoryStore {
+        async fn load(&self, user_id: Uuid) -> Result<Option<CachedCommonCounts>, StoreError> {
+            Ok(self.counts.lock().unwrap().get(&user_id).copied())
+        }
+
+        async fn store(
+            &self,
+            user_id: Uuid,
+            counts: &CachedCommonCounts,
+        ) -> Result<(), StoreError> {
+            self.counts.lock().unwrap().insert(user_id, *counts);
+            Ok(())
+        }
+
+        async fn invalidate(&self, user_id: Uuid) {
+            self.counts.lock().unwrap().remove(&user_id);
+        }
+    }
+
+    /// Mirrors `CommonContext::build`, counting from `unread` on a miss
+    async fn unread_count(store: &MemoryStore, user_id: Uuid, unread: &AtomicI64) -> i64 {
+        load_or_count(store, user_id, || async {
+            CachedCommonCounts {
+                draft_post_count: 0,
+                unread_notification_count: unread.load(Ordering::SeqCst),
+            }
+        })
+        .await
+        .unread_notification_count
+    }
+
+    #[tokio::test]
+    async fn cached_count_updates_after_notification_is_marked_read() {
+        let store = MemoryStore::default();
+        let user_id = Uuid::new_v4();
+        let unread = AtomicI64::new(3);
+
+        assert_eq!(unread_count(&store, user_id, &unread).await, 3);
+
+        // Later requests are answered from the cache without recounting
+        unread.store(2, Ordering::SeqCst);
+        assert_eq!(unread_count(&store, user_id, &unread).await, 3);
+
+        // Marking a notification read invalidates, as the notification handlers do
+        store.invalidate(user_id).await;
+        assert_eq!(unread_count(&store, user_id, &unread).await, 2);
+    }
+
+    #[tokio::test]
+    async fn counts_are_cached_per_user() {
+        let store = MemoryStore::default();
+        let (user_id, other_user_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert_eq!(unread_count(&store, user_id, &AtomicI64::new(1)).await, 1);
+        assert_eq!(
+            unread_count(&store, other_user_id, &AtomicI64::new(5)).await,
+            5
+        );
+
+        store.invalidate(other_user_id).await;
+        assert_eq!(unread_count(&store, user_id, &AtomicI64::new(9)).await, 1);
+    }
+}
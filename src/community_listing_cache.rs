@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::community::PublicCommunity;
+use crate::redis::RedisPool;
+
+const VERSION_KEY: &str = "oeee:communities:listing:version";
+const LISTING_PREFIX: &str = "oeee:communities:listing:";
+const LISTING_TTL: u64 = 600; // 10 minutes
+
+/// The viewer-independent part of the communities page
+#[derive(Serialize, Deserialize)]
+pub struct CachedCommunityListing {
+    pub public_communities: Vec<PublicCommunity>,
+    pub members_count: HashMap<Uuid, Option<i64>>,
+}
+
+/// Caches the public community listing under a version stamp. Anything that
+/// changes the listing bumps the version, which leaves older entries to expire.
+pub struct CommunityListingCache {
+    pool: RedisPool,
+}
+
+impl CommunityListingCache {
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn version(&self) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let version: Option<u64> = conn.get(VERSION_KEY).await?;
+        Ok(version.unwrap_or(0))
+    }
+
+    pub async fn load(
+        &self,
+        version: u64,
+    ) -> Result<Option<CachedCommunityListing>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let value: Option<String> = conn.get(format!("{}{}", LISTING_PREFIX, version)).await?;
+        Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    /// Store a listing built while `version` was current. If the version was
+    /// bumped in the meantime the entry is simply never read.
+    pub async fn store(
+        &self,
+        version: u64,
+        listing: &CachedCommunityListing,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        conn.set_ex::<_, _, ()>(
+            format!("{}{}", LISTING_PREFIX, version),
+            serde_json::to_string(listing)?,
+            LISTING_TTL,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Mark every cached listing as outdated. Failures are only logged, since
+    /// entries expire on their own.
+    pub async fn invalidate(&self) {
+        let result: Result<(), Box<dyn std::error::Error + Send + Sync>> = async {
+            let mut conn = self.pool.get().await?;
+            conn.incr::<_, _, ()>(VERSION_KEY, 1).await?;
+            Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to invalidate community listing cache: {:?}", e);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use chrono::{DateTime, TimeDelta, Utc};
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DurationSeconds};
@@ -47,6 +48,264 @@ pub struct AppConfig {
     // FCM configuration (V1 API)
     pub fcm_service_account_path: String,
     pub fcm_project_id: String,
+
+    // Reject uploads whose replay doesn't match the submitted image
+    #[serde(default)]
+    pub replay_verification_enabled: bool,
+
+    // Local community (by slug) that surfaces remote posts announced to us; unset disables it
+    #[serde(default)]
+    pub federated_inbox_community_slug: Option<String>,
+
+    // Number of activities per ActivityPub outbox page
+    #[serde(default = "default_activitypub_outbox_page_size")]
+    pub activitypub_outbox_page_size: i64,
+
+    // Also store a WebP copy of uploaded drawings, encoded at the given quality (0-100)
+    #[serde(default)]
+    pub webp_enabled: bool,
+    #[serde(default = "default_webp_quality")]
+    pub webp_quality: f32,
+
+    // Email domains rejected when a user registers an address; subdomains are included.
+    // A non-empty allowlist rejects every domain not on it.
+    #[serde(default)]
+    pub blocked_email_domains: Vec<String>,
+    #[serde(default)]
+    pub allowed_email_domains: Vec<String>,
+
+    // Move replays of drawings older than the given age to a cheaper storage class.
+    // Archived replays stay directly readable, so nothing needs restoring on view.
+    #[serde(default)]
+    pub replay_archive_enabled: bool,
+    #[serde(default = "default_replay_archive_after_days")]
+    pub replay_archive_after_days: i64,
+    #[serde(default = "default_replay_archive_storage_class")]
+    pub replay_archive_storage_class: String,
+
+    // Archive communities without a published post for the given number of days, after
+    // warning the owner the given number of days ahead. Owners can restore them.
+    #[serde(default)]
+    pub community_auto_archive_enabled: bool,
+    #[serde(default = "default_community_auto_archive_after_days")]
+    pub community_auto_archive_after_days: i64,
+    #[serde(default = "default_community_auto_archive_notice_days")]
+    pub community_auto_archive_notice_days: i64,
+
+    // Community invitations left unanswered for the given number of days can no longer be
+    // accepted and are cleaned up
+    #[serde(default = "default_community_invitation_ttl_days")]
+    pub community_invitation_ttl_days: i64,
+
+    // Hex-encoded 32-byte key used to encrypt TOTP secrets at rest; unset disables 2FA setup
+    #[serde(default)]
+    pub totp_encryption_key: String,
+
+    // Hex-encoded key for the HMAC stored in place of each password reset token; unset
+    // falls back to an unkeyed SHA-256
+    #[serde(default)]
+    pub password_reset_token_key: String,
+
+    // Outgoing ActivityPub deliveries run in parallel across domains but are capped per
+    // domain; a failing domain backs off exponentially without holding up the others
+    #[serde(default = "default_federation_delivery_concurrency")]
+    pub federation_delivery_concurrency: usize,
+    #[serde(default = "default_federation_delivery_per_domain_concurrency")]
+    pub federation_delivery_per_domain_concurrency: usize,
+    #[serde(default = "default_federation_delivery_max_retries")]
+    pub federation_delivery_max_retries: u32,
+    #[serde(default = "default_federation_delivery_backoff_seconds")]
+    pub federation_delivery_backoff_seconds: u64,
+
+    // Sliding-window limits on login and password reset attempts, counted separately per
+    // client IP and per targeted account; going over either is answered with 429
+    #[serde(default = "default_login_rate_limit_window_seconds")]
+    pub login_rate_limit_window_seconds: u64,
+    #[serde(default = "default_login_rate_limit_per_ip")]
+    pub login_rate_limit_per_ip: u32,
+    #[serde(default = "default_login_rate_limit_per_account")]
+    pub login_rate_limit_per_account: u32,
+    #[serde(default = "default_password_reset_rate_limit_window_seconds")]
+    pub password_reset_rate_limit_window_seconds: u64,
+    #[serde(default = "default_password_reset_rate_limit_per_ip")]
+    pub password_reset_rate_limit_per_ip: u32,
+    #[serde(default = "default_password_reset_rate_limit_per_email")]
+    pub password_reset_rate_limit_per_email: u32,
+
+    // Collaborative sessions with no live connection for longer than the grace period (e.g.
+    // after a restart) are reaped: "keep" frees their participant slots but leaves the drawing
+    // open to rejoin, "discard" ends them and drops their history
+    #[serde(default = "default_collaborate_orphan_policy")]
+    pub collaborate_orphan_policy: String,
+    #[serde(default = "default_collaborate_orphan_grace_seconds")]
+    pub collaborate_orphan_grace_seconds: u64,
+    // Most live connections a collaborative session accepts, whatever its own participant
+    // limit; 0 disables the cap
+    #[serde(default = "default_max_collaborators")]
+    pub max_collaborators: usize,
+
+    // Hold back federation of posts by accounts younger than the given age unless their email
+    // is verified; 0 disables the check. Once an account qualifies, its held-back posts from
+    // the last backfill days can be federated after all.
+    #[serde(default)]
+    pub federation_min_account_age_hours: u64,
+    #[serde(default)]
+    pub federation_backfill_enabled: bool,
+    #[serde(default = "default_federation_backfill_days")]
+    pub federation_backfill_days: i64,
+
+    // Longest side, in pixels, of the downscaled variants generated for each new drawing and
+    // served in post grids; empty disables thumbnail generation
+    #[serde(default = "default_thumbnail_sizes")]
+    pub thumbnail_sizes: Vec<u32>,
+
+    // Machine translation of post content through "deepl" or "libretranslate"; empty disables
+    // it. Translations are stored, so each post is sent once per language. Signed-in users get
+    // a daily number of new translations.
+    #[serde(default)]
+    pub translation_backend: String,
+    #[serde(default)]
+    pub translation_api_url: String,
+    #[serde(default)]
+    pub translation_api_key: String,
+    #[serde(default = "default_translation_daily_limit_per_user")]
+    pub translation_daily_limit_per_user: u32,
+
+    // Content warning sent as the summary of notes for posts marked sensitive, so remote
+    // servers hide them behind it; empty uses the post's title
+    #[serde(default)]
+    pub sensitive_content_warning: String,
+
+    // Lifetimes of the tokens issued to native clients through /oauth/token; a refresh
+    // token is replaced on every use
+    #[serde(default = "default_oauth_access_token_ttl_seconds")]
+    pub oauth_access_token_ttl_seconds: i64,
+    #[serde(default = "default_oauth_refresh_token_ttl_days")]
+    pub oauth_refresh_token_ttl_days: i64,
+
+    // Sign outgoing fetches of remote actors and objects as the instance actor (/ap/actor), as
+    // servers in secure mode (authorized fetch) require. With the fallback on, a fetch that
+    // fails signed is retried unsigned for servers that reject the signature.
+    #[serde(default = "default_signed_fetch_enabled")]
+    pub signed_fetch_enabled: bool,
+    #[serde(default = "default_signed_fetch_fallback")]
+    pub signed_fetch_fallback: bool,
+
+    // Lets anyone who can see a post read its edit history; otherwise only the author can
+    #[serde(default)]
+    pub post_history_public: bool,
+
+    // Bearer token Prometheus must send to scrape /metrics; empty disables the endpoint
+    #[serde(default)]
+    pub metrics_token: String,
+}
+
+fn default_activitypub_outbox_page_size() -> i64 {
+    20
+}
+
+fn default_webp_quality() -> f32 {
+    85.0
+}
+
+fn default_replay_archive_after_days() -> i64 {
+    180
+}
+
+fn default_replay_archive_storage_class() -> String {
+    "STANDARD_IA".to_string()
+}
+
+fn default_community_auto_archive_after_days() -> i64 {
+    365
+}
+
+fn default_community_auto_archive_notice_days() -> i64 {
+    14
+}
+
+fn default_community_invitation_ttl_days() -> i64 {
+    30
+}
+
+fn default_federation_delivery_concurrency() -> usize {
+    32
+}
+
+fn default_federation_delivery_per_domain_concurrency() -> usize {
+    1
+}
+
+fn default_federation_delivery_max_retries() -> u32 {
+    5
+}
+
+fn default_federation_delivery_backoff_seconds() -> u64 {
+    60
+}
+
+fn default_login_rate_limit_window_seconds() -> u64 {
+    900
+}
+
+fn default_login_rate_limit_per_ip() -> u32 {
+    30
+}
+
+fn default_login_rate_limit_per_account() -> u32 {
+    10
+}
+
+fn default_password_reset_rate_limit_window_seconds() -> u64 {
+    3600
+}
+
+fn default_password_reset_rate_limit_per_ip() -> u32 {
+    10
+}
+
+fn default_password_reset_rate_limit_per_email() -> u32 {
+    3
+}
+
+fn default_collaborate_orphan_policy() -> String {
+    "keep".to_string()
+}
+
+fn default_collaborate_orphan_grace_seconds() -> u64 {
+    120
+}
+
+fn default_max_collaborators() -> usize {
+    16
+}
+
+fn default_federation_backfill_days() -> i64 {
+    7
+}
+
+fn default_thumbnail_sizes() -> Vec<u32> {
+    vec![256, 512]
+}
+
+fn default_translation_daily_limit_per_user() -> u32 {
+    10
+}
+
+fn default_oauth_access_token_ttl_seconds() -> i64 {
+    3600
+}
+
+fn default_oauth_refresh_token_ttl_days() -> i64 {
+    30
+}
+
+fn default_signed_fetch_enabled() -> bool {
+    true
+}
+
+fn default_signed_fetch_fallback() -> bool {
+    true
 }
 
 impl AppConfig {
@@ -64,4 +323,129 @@ impl AppConfig {
     pub fn use_activitypub_queue(&self) -> bool {
         self.env == "production"
     }
+
+    /// Whether posts by an account created at `created_at` may be federated yet.
+    /// A verified email address lifts the minimum account age.
+    pub fn is_account_federation_ready(
+        &self,
+        created_at: DateTime<Utc>,
+        email_verified: bool,
+    ) -> bool {
+        email_verified
+            || self.federation_min_account_age_hours == 0
+            || created_at <= self.federation_ready_cutoff()
+    }
+
+    /// Accounts created at or before this time have reached the minimum age for federation
+    pub fn federation_ready_cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - TimeDelta::hours(self.federation_min_account_age_hours as i64)
+    }
+
+    /// When a community invitation sent now stops being valid
+    pub fn community_invitation_expires_at(&self) -> DateTime<Utc> {
+        Utc::now() + TimeDelta::days(self.community_invitation_ttl_days)
+    }
+
+    /// When an OAuth access token issued now stops being accepted
+    pub fn oauth_access_token_expires_at(&self) -> DateTime<Utc> {
+        Utc::now() + TimeDelta::seconds(self.oauth_access_token_ttl_seconds)
+    }
+
+    /// When an OAuth refresh token issued now can no longer be exchanged
+    pub fn oauth_refresh_token_expires_at(&self) -> DateTime<Utc> {
+        Utc::now() + TimeDelta::days(self.oauth_refresh_token_ttl_days)
+    }
+
+    /// Checks an email domain against the configured allowlist and blocklist.
+    pub fn is_email_domain_allowed(&self, domain: &str) -> bool {
+        email_domain_allowed(
+            domain,
+            &self.allowed_email_domains,
+            &self.blocked_email_domains,
+        )
+    }
+}
+
+/// An empty allowlist allows every domain. Entries match case-insensitively
+/// and cover their subdomains.
+fn email_domain_allowed(domain: &str, allowed: &[String], blocked: &[String]) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    let matches = |entry: &String| {
+        let entry = entry.trim().trim_start_matches('@').to_ascii_lowercase();
+        domain == entry || domain.ends_with(&format!(".{}", entry))
+    };
+
+    if !allowed.is_empty() && !allowed.iter().any(matches) {
+        return false;
+    }
+    !blocked.iter().any(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domains(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    fn config_with_min_account_age(hours: u64) -> AppConfig {
+        let mut config = AppConfig::new_from_file_and_env("config/sample.toml").unwrap();
+        config.federation_min_account_age_hours = hours;
+        config
+    }
+
+    #[test]
+    fn too_new_accounts_are_not_federated() {
+        let config = config_with_min_account_age(24);
+        let new_account = Utc::now() - TimeDelta::hours(1);
+        let older_account = Utc::now() - TimeDelta::hours(48);
+
+        assert!(!config.is_account_federation_ready(new_account, false));
+        assert!(config.is_account_federation_ready(older_account, false));
+        // A verified email lifts the minimum age
+        assert!(config.is_account_federation_ready(new_account, true));
+    }
+
+    #[test]
+    fn zero_minimum_age_federates_every_account() {
+        let config = config_with_min_account_age(0);
+
+        assert!(config.is_account_federation_ready(Utc::now(), false));
+    }
+
+    #[test]
+    fn blocked_domain_is_rejected() {
+        let blocked = domains(&["mailinator.com", "@Spam.Example"]);
+
+        assert!(!email_domain_allowed("mailinator.com", &[], &blocked));
+        assert!(!email_domain_allowed("MAILINATOR.COM.", &[], &blocked));
+        assert!(!email_domain_allowed("eu.mailinator.com", &[], &blocked));
+        assert!(!email_domain_allowed("spam.example", &[], &blocked));
+    }
+
+    #[test]
+    fn other_domains_are_allowed() {
+        let blocked = domains(&["mailinator.com"]);
+
+        assert!(email_domain_allowed("gmail.com", &[], &blocked));
+        // Only whole labels match
+        assert!(email_domain_allowed("notmailinator.com", &[], &blocked));
+    }
+
+    #[test]
+    fn allowlist_limits_domains() {
+        let allowed = domains(&["example.ac.kr"]);
+        let blocked = domains(&["guest.example.ac.kr"]);
+
+        assert!(email_domain_allowed("example.ac.kr", &allowed, &blocked));
+        assert!(email_domain_allowed("cs.example.ac.kr", &allowed, &blocked));
+        assert!(!email_domain_allowed("gmail.com", &allowed, &blocked));
+        // The blocklist still applies inside the allowlist
+        assert!(!email_domain_allowed(
+            "guest.example.ac.kr",
+            &allowed,
+            &blocked
+        ));
+    }
 }
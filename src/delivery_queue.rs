@@ -0,0 +1,325 @@
+//! Background delivery of outgoing ActivityPub activities.
+//!
+//! Deliveries are sharded by destination domain. Each domain gets its own worker
+//! that starts deliveries in the order they were queued, with at most
+//! `per_domain_concurrency` of them in flight, so a slow or failing instance only
+//! holds up its own queue and is never hit with a burst of parallel requests.
+//! Different domains deliver in parallel, bounded overall by `concurrency`.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{mpsc, Semaphore};
+use tokio::time::Instant;
+use tracing::{debug, warn, Instrument, Span};
+
+use crate::AppConfig;
+
+// Domain workers with nothing queued for this long shut down
+const IDLE_WORKER_TIMEOUT: Duration = Duration::from_secs(300);
+const MAX_BACKOFF: Duration = Duration::from_secs(3600);
+
+type DeliveryFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+
+/// A single delivery that can be attempted again after a failure
+type Delivery = Box<dyn Fn(DeliveryAttempt) -> DeliveryFuture + Send + Sync>;
+
+/// Passed to each try of a delivery, so it can tell when a failure is final
+#[derive(Clone, Copy, Debug)]
+pub struct DeliveryAttempt {
+    /// Starts at 1
+    pub number: u32,
+    pub is_last: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct DeliveryQueueConfig {
+    /// Deliveries in flight across all domains
+    pub concurrency: usize,
+    /// Deliveries in flight to one domain; 1 keeps them strictly ordered
+    pub per_domain_concurrency: usize,
+    /// Attempts after the first before a delivery is dropped
+    pub max_retries: u32,
+    /// Delay after the first failure, doubled for every further consecutive failure
+    pub base_backoff: Duration,
+}
+
+impl DeliveryQueueConfig {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        Self {
+            concurrency: config.federation_delivery_concurrency.max(1),
+            per_domain_concurrency: config.federation_delivery_per_domain_concurrency.max(1),
+            max_retries: config.federation_delivery_max_retries,
+            base_backoff: Duration::from_secs(config.federation_delivery_backoff_seconds),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DeliveryQueue {
+    config: DeliveryQueueConfig,
+    global: Arc<Semaphore>,
+    workers: Arc<Mutex<HashMap<String, mpsc::UnboundedSender<Delivery>>>>,
+}
+
+/// Failure state shared by all in-flight deliveries to one domain
+#[derive(Default)]
+struct DomainBackoff {
+    consecutive_failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl DomainBackoff {
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.retry_at = None;
+    }
+
+    fn record_failure(&mut self, base_backoff: Duration) {
+        self.consecutive_failures += 1;
+        let delay = backoff_delay(base_backoff, self.consecutive_failures);
+        self.retry_at = Some(Instant::now() + delay);
+    }
+}
+
+fn backoff_delay(base_backoff: Duration, failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    base_backoff.saturating_mul(1 << exponent).min(MAX_BACKOFF)
+}
+
+impl DeliveryQueue {
+    pub fn new(config: DeliveryQueueConfig) -> Self {
+        Self {
+            global: Arc::new(Semaphore::new(config.concurrency)),
+            config,
+            workers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Queues a delivery to `domain`, starting a worker for the domain if none is running.
+    /// `deliver` is called again for every retry, inside the span that was current when
+    /// the delivery was queued, so its logs keep the originating request's correlation ID.
+    pub fn enqueue<F, Fut>(&self, domain: &str, deliver: F)
+    where
+        F: Fn(DeliveryAttempt) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        let span = Span::current();
+        let delivery: Delivery =
+            Box::new(move |attempt| Box::pin(deliver(attempt).instrument(span.clone())));
+        let mut workers = self.workers.lock().unwrap();
+        let delivery = match workers.get(domain) {
+            Some(sender) => match sender.send(delivery) {
+                Ok(()) => return,
+                // The worker is gone; hand the delivery to a new one
+                Err(mpsc::error::SendError(delivery)) => delivery,
+            },
+            None => delivery,
+        };
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        sender
+            .send(delivery)
+            .expect("receiver is held until the worker starts");
+        workers.insert(domain.to_string(), sender);
+        tokio::spawn(self.clone().run_domain_worker(domain.to_string(), receiver));
+    }
+
+    async fn run_domain_worker(
+        self,
+        domain: String,
+        mut receiver: mpsc::UnboundedReceiver<Delivery>,
+    ) {
+        debug!("Starting delivery worker for {}", domain);
+        let domain_slots = Arc::new(Semaphore::new(self.config.per_domain_concurrency));
+        let backoff = Arc::new(Mutex::new(DomainBackoff::default()));
+
+        loop {
+            let delivery = match tokio::time::timeout(IDLE_WORKER_TIMEOUT, receiver.recv()).await {
+                Ok(Some(delivery)) => delivery,
+                Ok(None) => break,
+                Err(_) => {
+                    // Only retire while holding the map lock, so nothing can be
+                    // queued to this worker after it decides to stop
+                    let mut workers = self.workers.lock().unwrap();
+                    if receiver.is_empty() {
+                        workers.remove(&domain);
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            // Taking the domain slot before spawning keeps deliveries starting in queue order
+            let domain_slot = domain_slots
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("domain semaphore is never closed");
+            let queue = self.clone();
+            let domain = domain.clone();
+            let backoff = backoff.clone();
+            tokio::spawn(async move {
+                queue.deliver(&domain, delivery, &backoff).await;
+                drop(domain_slot);
+            });
+        }
+        debug!("Stopped delivery worker for {}", domain);
+    }
+
+    async fn deliver(&self, domain: &str, delivery: Delivery, backoff: &Mutex<DomainBackoff>) {
+        let mut attempt = 0;
+        loop {
+            let retry_at = backoff.lock().unwrap().retry_at;
+            if let Some(retry_at) = retry_at {
+                tokio::time::sleep_until(retry_at).await;
+            }
+
+            let result = {
+                let _permit = self
+                    .global
+                    .acquire()
+                    .await
+                    .expect("global semaphore is never closed");
+                delivery(DeliveryAttempt {
+                    number: attempt + 1,
+                    is_last: attempt >= self.config.max_retries,
+                })
+                .await
+            };
+
+            match result {
+                Ok(()) => {
+                    backoff.lock().unwrap().record_success();
+                    return;
+                }
+                Err(e) => {
+                    backoff
+                        .lock()
+                        .unwrap()
+                        .record_failure(self.config.base_backoff);
+                    if attempt >= self.config.max_retries {
+                        warn!(
+                            "Giving up delivery to {} after {} attempts: {:?}",
+                            domain,
+                            attempt + 1,
+                            e
+                        );
+                        return;
+                    }
+                    attempt += 1;
+                    warn!(
+                        "Delivery to {} failed (attempt {}), retrying: {:?}",
+                        domain, attempt, e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deliveries in flight right now and the most seen at once
+    #[derive(Default)]
+    struct InFlight {
+        current: usize,
+        peak: usize,
+    }
+
+    impl InFlight {
+        fn start(&mut self) {
+            self.current += 1;
+            self.peak = self.peak.max(self.current);
+        }
+
+        fn finish(&mut self) {
+            self.current -= 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct Observed {
+        overall: InFlight,
+        per_domain: HashMap<String, InFlight>,
+        order: HashMap<String, Vec<usize>>,
+    }
+
+    fn test_queue() -> DeliveryQueue {
+        DeliveryQueue::new(DeliveryQueueConfig {
+            concurrency: 8,
+            per_domain_concurrency: 1,
+            max_retries: 0,
+            base_backoff: Duration::from_millis(10),
+        })
+    }
+
+    #[tokio::test]
+    async fn domains_deliver_in_parallel_but_each_domain_in_order() {
+        let queue = test_queue();
+        let observed = Arc::new(Mutex::new(Observed::default()));
+        let (done_sender, mut done_receiver) = mpsc::unbounded_channel();
+
+        for index in 0..3 {
+            for domain in ["a.example", "b.example"] {
+                let observed = observed.clone();
+                let done_sender = done_sender.clone();
+                queue.enqueue(domain, move |_attempt| {
+                    let observed = observed.clone();
+                    let done_sender = done_sender.clone();
+                    async move {
+                        {
+                            let mut observed = observed.lock().unwrap();
+                            observed.overall.start();
+                            observed
+                                .per_domain
+                                .entry(domain.to_string())
+                                .or_default()
+                                .start();
+                            observed
+                                .order
+                                .entry(domain.to_string())
+                                .or_default()
+                                .push(index);
+                        }
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        {
+                            let mut observed = observed.lock().unwrap();
+                            observed.overall.finish();
+                            observed.per_domain.get_mut(domain).unwrap().finish();
+                        }
+                        done_sender.send(()).unwrap();
+                        Ok(())
+                    }
+                });
+            }
+        }
+
+        for _ in 0..6 {
+            tokio::time::timeout(Duration::from_secs(5), done_receiver.recv())
+                .await
+                .expect("deliveries should finish")
+                .unwrap();
+        }
+
+        let observed = observed.lock().unwrap();
+        assert_eq!(observed.overall.peak, 2);
+        for domain in ["a.example", "b.example"] {
+            assert_eq!(observed.per_domain[domain].peak, 1);
+            assert_eq!(observed.order[domain], vec![0, 1, 2]);
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let base = Duration::from_secs(30);
+        assert_eq!(backoff_delay(base, 1), Duration::from_secs(30));
+        assert_eq!(backoff_delay(base, 3), Duration::from_secs(120));
+        assert_eq!(backoff_delay(base, 100), MAX_BACKOFF);
+    }
+}
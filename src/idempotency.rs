@@ -0,0 +1,243 @@
+use std::future::Future;
+
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+
+use crate::redis::RedisPool;
+
+const IDEMPOTENCY_PREFIX: &str = "oeee:idempotency:";
+const IDEMPOTENCY_TTL: u64 = 600; // 10 minutes
+const MAX_KEY_LENGTH: usize = 128;
+
+type StoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Where claimed idempotency keys are remembered
+#[async_trait::async_trait]
+pub trait KeyStore: Send + Sync {
+    /// Atomically claim `key` within `scope`, recording `result` as the outcome.
+    ///
+    /// Returns `None` when the key was unused and the caller should go ahead,
+    /// or the previously recorded result when the key has already been claimed.
+    async fn claim(
+        &self,
+        scope: &str,
+        key: &str,
+        result: &str,
+    ) -> Result<Option<String>, StoreError>;
+
+    /// Forget a claimed key so the request can be retried after a failure.
+    async fn release(&self, scope: &str, key: &str) -> Result<(), StoreError>;
+}
+
+/// Remembers client-supplied idempotency keys so a retried request can be
+/// answered with the original result instead of being performed twice.
+pub struct IdempotencyStore {
+    pool: RedisPool,
+}
+
+impl IdempotencyStore {
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    /// Keys are client-controlled, so only accept short, URL-safe tokens.
+    pub fn is_valid_key(key: &str) -> bool {
+        !key.is_empty()
+            && key.len() <= MAX_KEY_LENGTH
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyStore for IdempotencyStore {
+    async fn claim(
+        &self,
+        scope: &str,
+        key: &str,
+        result: &str,
+    ) -> Result<Option<String>, StoreError> {
+        let mut conn = self.pool.get().await?;
+        let redis_key = format!("{}{}:{}", IDEMPOTENCY_PREFIX, scope, key);
+
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(IDEMPOTENCY_TTL));
+        let claimed: Option<String> = conn.set_options(&redis_key, result, options).await?;
+        if claimed.is_some() {
+            return Ok(None);
+        }
+
+        let previous: Option<String> = conn.get(&redis_key).await?;
+        Ok(Some(previous.unwrap_or_else(|| result.to_string())))
+    }
+
+    async fn release(&self, scope: &str, key: &str) -> Result<(), StoreError> {
+        let mut conn = self.pool.get().await?;
+        let redis_key = format!("{}{}:{}", IDEMPOTENCY_PREFIX, scope, key);
+        conn.del::<_, ()>(&redis_key).await?;
+        Ok(())
+    }
+}
+
+pub enum Performed<T> {
+    /// This request did the work
+    Now(T),
+    /// An earlier request with the same key did, and recorded this result
+    Earlier(String),
+}
+
+/// Run `perform` unless a request with the same key already did.
+///
+/// Without a key, or when the store can't be reached, the work always goes
+/// ahead. A failed attempt gives its key back so the client can retry.
+pub async fn perform_once<S, T, E, F, Fut>(
+    store: &S,
+    scope: &str,
+    key: Option<&str>,
+    result: &str,
+    perform: F,
+) -> Result<Performed<T>, E>
+where
+    S: KeyStore,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let Some(key) = key else {
+        return perform().await.map(Performed::Now);
+    };
+
+    match store.claim(scope, key, result).await {
+        Ok(Some(previous)) => return Ok(Performed::Earlier(previous)),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to check idempotency key: {}", e),
+    }
+
+    let performed = perform().await;
+    if performed.is_err() {
+        if let Err(e) = store.release(scope, key).await {
+            tracing::warn!("Failed to release idempotency key: {}", e);
+        }
+    }
+    performed.map(Performed::Now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        keys: Mutex<HashMap<String, String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl KeyStore for MemoryStore {
+        async fn claim(
+            &self,
+            scope: &str,
+            key: &str,
+            result: &str,
+        ) -> Result<Option<String>, StoreError> {
+            let mut keys = self.keys.lock().unwrap();
+            let scoped_key = format!("{}:{}", scope, key);
+            if let Some(previous) = keys.get(&scoped_key) {
+                return Ok(Some(previous.clone()));
+            }
+            keys.insert(scoped_key, result.to_string());
+            Ok(None)
+        }
+
+        async fn release(&self, scope: &str, key: &str) -> Result<(), StoreError> {
+            self.keys
+                .lock()
+                .unwrap()
+                .remove(&format!("{}:{}", scope, key));
+            Ok(())
+        }
+    }
+
+    /// Mirrors `post_publish`: publish inside `perform_once`, then federate
+    /// only when this request was the one that published
+    async fn submit(
+        store: &MemoryStore,
+        key: Option<&str>,
+        published: &AtomicUsize,
+        delivered: &AtomicUsize,
+        fail: bool,
+    ) -> Result<String, &'static str> {
+        let performed = perform_once(store, "publish:user", key, "/@user", || async {
+            if fail {
+                return Err("commit failed");
+            }
+            published.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .await?;
+        match performed {
+            Performed::Now(()) => {
+                delivered.fetch_add(1, Ordering::SeqCst);
+                Ok("/@user".to_string())
+            }
+            Performed::Earlier(redirect_url) => Ok(redirect_url),
+        }
+    }
+
+    #[test]
+    fn validates_keys() {
+        assert!(IdempotencyStore::is_valid_key(
+            "0b6a2c1e-7d3f-4e4a-9d8b-1f2e3d4c5b6a"
+        ));
+        assert!(!IdempotencyStore::is_valid_key(""));
+        assert!(!IdempotencyStore::is_valid_key("key:with:colons"));
+        assert!(!IdempotencyStore::is_valid_key(&"a".repeat(129)));
+    }
+
+    #[tokio::test]
+    async fn same_key_publishes_and_delivers_once() {
+        let store = MemoryStore::default();
+        let (published, delivered) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        for _ in 0..3 {
+            let redirect_url = submit(&store, Some("key"), &published, &delivered, false).await;
+            assert_eq!(redirect_url.as_deref(), Ok("/@user"));
+        }
+
+        assert_eq!(published.load(Ordering::SeqCst), 1);
+        assert_eq!(delivered.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_attempt_can_be_retried_with_same_key() {
+        let store = MemoryStore::default();
+        let (published, delivered) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        assert!(submit(&store, Some("key"), &published, &delivered, true)
+            .await
+            .is_err());
+        submit(&store, Some("key"), &published, &delivered, false)
+            .await
+            .unwrap();
+
+        assert_eq!(published.load(Ordering::SeqCst), 1);
+        assert_eq!(delivered.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn requests_without_a_key_always_run() {
+        let store = MemoryStore::default();
+        let (published, delivered) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        for _ in 0..2 {
+            submit(&store, None, &published, &delivered, false)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(published.load(Ordering::SeqCst), 2);
+        assert_eq!(delivered.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,102 @@
+use std::io::Cursor;
+
+use anyhow::{anyhow, Result};
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+
+/// Re-encode a PNG or JPEG image as lossy WebP.
+///
+/// `quality` ranges from 0 (smallest) to 100 (best). Alpha is preserved so
+/// transparent drawings stay transparent.
+pub fn encode_webp(bytes: &[u8], quality: f32) -> Result<Vec<u8>> {
+    let image = image::load_from_memory(bytes)?;
+
+    // The WebP encoder only accepts 8-bit RGB(A) buffers
+    let image = DynamicImage::ImageRgba8(image.to_rgba8());
+    let encoder =
+        webp::Encoder::from_image(&image).map_err(|e| anyhow!("Unsupported image: {}", e))?;
+
+    Ok(encoder.encode(quality.clamp(0.0, 100.0)).to_vec())
+}
+
+/// A downscaled copy of an image, already encoded.
+pub struct Thumbnail {
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Downscale an image so its longer side is at most `max_size`, keeping the
+/// aspect ratio. Encodes as WebP at `webp_quality` if given, PNG otherwise.
+///
+/// Returns `None` when the image already fits, since the original serves
+/// just as well there.
+pub fn generate_thumbnail(
+    bytes: &[u8],
+    max_size: u32,
+    webp_quality: Option<f32>,
+) -> Result<Option<Thumbnail>> {
+    let image = image::load_from_memory(bytes)?;
+    if image.width() <= max_size && image.height() <= max_size {
+        return Ok(None);
+    }
+
+    let thumbnail = DynamicImage::ImageRgba8(image.thumbnail(max_size, max_size).to_rgba8());
+    let (width, height) = (thumbnail.width(), thumbnail.height());
+
+    let bytes = match webp_quality {
+        Some(quality) => {
+            let encoder = webp::Encoder::from_image(&thumbnail)
+                .map_err(|e| anyhow!("Unsupported image: {}", e))?;
+            encoder.encode(quality.clamp(0.0, 100.0)).to_vec()
+        }
+        None => {
+            let mut png = Vec::new();
+            thumbnail.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+            png
+        }
+    };
+
+    Ok(Some(Thumbnail {
+        bytes,
+        width,
+        height,
+    }))
+}
+
+/// Tile images into a `width` by `height` PNG on a white background: four in a
+/// grid, two side by side, or one alone, each cropped to fill its cell.
+///
+/// Inputs that fail to decode are skipped; returns `None` if none decode.
+pub fn compose_collage<B: AsRef<[u8]>>(
+    images: &[B],
+    width: u32,
+    height: u32,
+) -> Result<Option<Vec<u8>>> {
+    let images: Vec<DynamicImage> = images
+        .iter()
+        .filter_map(|bytes| image::load_from_memory(bytes.as_ref()).ok())
+        .collect();
+    let (columns, rows) = match images.len() {
+        0 => return Ok(None),
+        1 => (1, 1),
+        2 | 3 => (2, 1),
+        _ => (2, 2),
+    };
+    let (cell_width, cell_height) = (width / columns, height / rows);
+
+    let mut canvas = RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+    for (index, image) in (0..).zip(images.iter().take((columns * rows) as usize)) {
+        let tile = image.resize_to_fill(cell_width, cell_height, FilterType::Triangle);
+        imageops::overlay(
+            &mut canvas,
+            &tile.to_rgba8(),
+            i64::from(index % columns * cell_width),
+            i64::from(index / columns * cell_height),
+        );
+    }
+
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(canvas).write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(Some(png))
+}
@@ -1,11 +1,21 @@
+pub mod announcement_cache;
 pub mod app_error;
+pub mod common_context_cache;
+pub mod community_listing_cache;
 pub mod config;
 pub mod db;
+pub mod delivery_queue;
+pub mod idempotency;
+pub mod image_optimization;
 pub mod locale;
 pub mod markdown_utils;
+pub mod metrics;
 pub mod models;
 pub mod push;
+pub mod rate_limit;
 pub mod redis;
+pub mod replay_verification;
+pub mod translation;
 pub mod web;
 
 pub use crate::config::AppConfig;
@@ -1,8 +1,28 @@
 use std::collections::HashMap;
 
+use fluent::bundle::FluentBundle;
 use fluent::FluentResource;
+use fluent_langneg::{
+    convert_vec_str_to_langids_lossy, negotiate_languages, parse_accepted_languages,
+    NegotiationStrategy,
+};
+use intl_memoizer::concurrent::IntlLangMemoizer;
 use lazy_static::lazy_static;
 
+use crate::models::user::Language;
+
+/// Every language with a locale file. Negotiation and user preferences both offer exactly these,
+/// so the list is derived from `Language` rather than kept by hand.
+pub const AVAILABLE_LANGUAGES: [&str; Language::ALL.len()] = {
+    let mut codes = [""; Language::ALL.len()];
+    let mut i = 0;
+    while i < codes.len() {
+        codes[i] = Language::ALL[i].code();
+        i += 1;
+    }
+    codes
+};
+
 lazy_static! {
     pub static ref LOCALES: HashMap<String, FluentResource> = {
         let mut locales = HashMap::new();
@@ -26,6 +46,113 @@ lazy_static! {
             FluentResource::try_new(include_str!("../locales/zh.ftl").to_string())
                 .expect("Chinese locale file must be valid"),
         );
+        locales.insert(
+            "es".to_string(),
+            FluentResource::try_new(include_str!("../locales/es.ftl").to_string())
+                .expect("Spanish locale file must be valid"),
+        );
         locales
     };
 }
+
+/// The languages of an Accept-Language header that have a locale, best first.
+/// Only the language subtag is matched, so `zh-CN` and `zh-TW` both give `zh`.
+pub fn negotiate_accept_language(header: &str) -> Vec<Language> {
+    let requested = parse_accepted_languages(header);
+    let available = convert_vec_str_to_langids_lossy(AVAILABLE_LANGUAGES);
+    negotiate_languages(&requested, &available, None, NegotiationStrategy::Filtering)
+        .into_iter()
+        .filter_map(|langid| Language::from_code(langid.language.as_str()))
+        .collect()
+}
+
+/// Builds a bundle that resolves each message from the first of `languages` defining it,
+/// with English as the last resort. Unknown and repeated languages are skipped.
+pub fn build_bundle(languages: &[&str]) -> FluentBundle<&'static FluentResource, IntlLangMemoizer> {
+    chain_bundle(&LOCALES, languages)
+}
+
+fn chain_bundle<'a>(
+    locales: &'a HashMap<String, FluentResource>,
+    languages: &[&str],
+) -> FluentBundle<&'a FluentResource, IntlLangMemoizer> {
+    let mut chain: Vec<&str> = Vec::new();
+    for &language in languages.iter().chain(&["en"]) {
+        if locales.contains_key(language) && !chain.contains(&language) {
+            chain.push(language);
+        }
+    }
+
+    let lang_ids = chain
+        .iter()
+        .map(|language| language.parse().expect("Locale keys should parse"))
+        .collect();
+    let mut bundle = FluentBundle::new_concurrent(lang_ids);
+    for language in chain {
+        // Messages already added from an earlier language win; the overlap errors
+        // from later languages are expected
+        let _ = bundle.add_resource(&locales[language]);
+    }
+
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(bundle: &FluentBundle<&FluentResource, IntlLangMemoizer>, id: &str) -> String {
+        let pattern = bundle
+            .get_message(id)
+            .and_then(|message| message.value())
+            .expect("message should resolve");
+        let mut errors = Vec::new();
+        bundle
+            .format_pattern(pattern, None, &mut errors)
+            .into_owned()
+    }
+
+    fn test_locales() -> HashMap<String, FluentResource> {
+        [
+            ("ja", "greeting = こんにちは\n"),
+            ("ko", "greeting = 안녕하세요\nonly-in-korean = 한국어만\n"),
+            ("en", "greeting = Hello\nonly-in-english = English only\n"),
+        ]
+        .into_iter()
+        .map(|(language, source)| {
+            let resource = FluentResource::try_new(source.to_string()).expect("valid FTL");
+            (language.to_string(), resource)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn fallback_chain_resolves_message_missing_from_preferred_language() {
+        let locales = test_locales();
+        let bundle = chain_bundle(&locales, &["ja", "ko", "en"]);
+
+        assert_eq!(message(&bundle, "greeting"), "こんにちは");
+        assert_eq!(message(&bundle, "only-in-korean"), "한국어만");
+        assert_eq!(message(&bundle, "only-in-english"), "English only");
+    }
+
+    #[test]
+    fn english_is_always_the_last_resort() {
+        let locales = test_locales();
+        let bundle = chain_bundle(&locales, &["ja", "fr"]);
+
+        assert_eq!(message(&bundle, "only-in-english"), "English only");
+        assert!(bundle.get_message("only-in-korean").is_none());
+    }
+
+    #[test]
+    fn every_locale_file_parses() {
+        for language in AVAILABLE_LANGUAGES {
+            assert!(
+                LOCALES.contains_key(language),
+                "missing locale {}",
+                language
+            );
+        }
+    }
+}
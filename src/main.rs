@@ -1,10 +1,21 @@
 use fluent::bundle::FluentBundle;
-use fluent::{FluentArgs, FluentValue};
+use fluent::{FluentArgs, FluentResource, FluentValue};
+use intl_memoizer::concurrent::IntlLangMemoizer;
 use minijinja::{path_loader, Environment, State};
-use oeee_cafe::locale::LOCALES;
+use oeee_cafe::common_context_cache::invalidate_on_notification_events;
+use oeee_cafe::delivery_queue::{DeliveryQueue, DeliveryQueueConfig};
+use oeee_cafe::locale::build_bundle;
+use oeee_cafe::models::reaction::AVAILABLE_EMOJIS;
 use oeee_cafe::push::PushService;
 use oeee_cafe::web::app::App;
+use oeee_cafe::web::handlers::account_deletion::finalize_account_deletions;
 use oeee_cafe::web::handlers::collaborate::redis_state::RedisStateManager;
+use oeee_cafe::web::handlers::community_archive::archive_inactive_communities;
+use oeee_cafe::web::handlers::federation_backfill::federate_deferred_posts;
+use oeee_cafe::web::handlers::invitation_cleanup::cleanup_expired_invitations;
+use oeee_cafe::web::handlers::replay_archive::archive_old_replays;
+use oeee_cafe::web::handlers::scheduled_posts::publish_scheduled_posts;
+use oeee_cafe::web::handlers::trending_hashtags::refresh_trending_hashtags;
 use oeee_cafe::web::state::AppState;
 use oeee_cafe::AppConfig;
 use std::collections::HashMap;
@@ -15,6 +26,20 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::Level;
 
+/// Identifies the running build for cache busting: the executable's mtime,
+/// which changes on every deploy, or the startup time if that can't be read
+fn asset_version() -> String {
+    let built_at = std::env::current_exe()
+        .and_then(|path| path.metadata())
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or_else(|_| SystemTime::now());
+    built_at
+        .duration_since(UNIX_EPOCH)
+        .expect("System time is before UNIX_EPOCH")
+        .as_secs()
+        .to_string()
+}
+
 fn main() {
     // Initialize rustls crypto provider for push notifications
     let _ = rustls::crypto::ring::default_provider().install_default();
@@ -44,8 +69,12 @@ fn main() {
                     exit(1);
                 });
 
-            // initialize tracing
+            // initialize tracing; JSON lines carry the fields of the enclosing
+            // spans, so every line logged for a request has its correlation_id
             let subscriber = tracing_subscriber::fmt()
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
                 .with_max_level(Level::DEBUG)
                 .finish();
             let _ = tracing::subscriber::set_global_default(subscriber);
@@ -56,37 +85,49 @@ fn main() {
             let mut env = Environment::new();
             minijinja_contrib::add_to_environment(&mut env);
 
-            fn cachebuster(value: String) -> String {
-                let timestamp = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .expect("System time is before UNIX_EPOCH")
-                    .as_secs();
-                format!("{}?{}", value, timestamp)
-            }
-            env.add_filter("cachebuster", cachebuster);
+            // Asset URLs stay the same for the lifetime of a deploy so browsers and
+            // the CDN can cache them, and change whenever a new binary ships
+            let asset_version = asset_version();
+            let cachebuster_version = asset_version.clone();
+            env.add_filter("cachebuster", move |value: String| -> String {
+                format!("{}?{}", value, cachebuster_version)
+            });
 
             fn markdown_to_html(value: String) -> String {
                 oeee_cafe::markdown_utils::process_markdown_content(&value)
             }
             env.add_filter("markdown", markdown_to_html);
 
-            fn ftl_get_message(state: &State, message_id: String) -> String {
+            /// Bundle for the template's `ftl_lang`, falling back through the current
+            /// user's configured languages
+            fn template_bundle(
+                state: &State,
+            ) -> FluentBundle<&'static FluentResource, IntlLangMemoizer> {
                 // Get the current language from template context
                 let lang = match state.lookup("ftl_lang") {
                     Some(lang_val) => lang_val.as_str().unwrap_or("ko").to_string(),
                     None => "ko".to_string(),
                 };
 
-                // Get the appropriate Fluent resource
-                let ftl = LOCALES
-                    .get(&lang)
-                    .unwrap_or_else(|| LOCALES.get("ko").expect("Korean locale must exist"));
+                let fallbacks: Vec<String> = state
+                    .lookup("current_user")
+                    .and_then(|user| user.get_attr("language_fallbacks").ok())
+                    .and_then(|fallbacks| fallbacks.try_iter().ok())
+                    .map(|fallbacks| {
+                        fallbacks
+                            .filter_map(|code| code.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
 
-                // Create bundle
-                let mut bundle = FluentBundle::new_concurrent(vec![lang
-                    .parse()
-                    .expect("Language string should be valid")]);
-                bundle.add_resource(ftl).expect("Failed to add a resource.");
+                let languages: Vec<&str> = std::iter::once(lang.as_str())
+                    .chain(fallbacks.iter().map(String::as_str))
+                    .collect();
+                build_bundle(&languages)
+            }
+
+            fn ftl_get_message(state: &State, message_id: String) -> String {
+                let bundle = template_bundle(state);
 
                 // Get and format the message
                 match bundle.get_message(&message_id) {
@@ -106,22 +147,7 @@ fn main() {
                 message_id: String,
                 params: minijinja::Value,
             ) -> Result<String, minijinja::Error> {
-                // Get the current language from template context
-                let lang = match state.lookup("ftl_lang") {
-                    Some(lang_val) => lang_val.as_str().unwrap_or("ko").to_string(),
-                    None => "ko".to_string(),
-                };
-
-                // Get the appropriate Fluent resource
-                let ftl = LOCALES
-                    .get(&lang)
-                    .unwrap_or_else(|| LOCALES.get("ko").expect("Korean locale must exist"));
-
-                // Create bundle
-                let mut bundle = FluentBundle::new_concurrent(vec![lang
-                    .parse()
-                    .expect("Language string should be valid")]);
-                bundle.add_resource(ftl).expect("Failed to add a resource.");
+                let bundle = template_bundle(state);
 
                 // Convert minijinja values to FluentArgs by deserializing to HashMap
                 let mut args = FluentArgs::new();
@@ -168,6 +194,8 @@ fn main() {
 
             // Add global variables
             env.add_global("r2_public_endpoint_url", cfg.r2_public_endpoint_url.clone());
+            env.add_global("available_emojis", AVAILABLE_EMOJIS.to_vec());
+            env.add_global("asset_version", asset_version.clone());
 
             env.set_loader(path_loader(&template_path));
 
@@ -198,8 +226,28 @@ fn main() {
                 redis_pool,
                 redis_state,
                 push_service: Arc::new(push_service),
+                notification_events: tokio::sync::broadcast::channel(1024).0,
+                delivery_queue: DeliveryQueue::new(DeliveryQueueConfig::from_app_config(&cfg)),
+                asset_version,
             };
 
+            tokio::spawn(invalidate_on_notification_events(
+                state.redis_pool.clone(),
+                state.notification_events.subscribe(),
+            ));
+            tokio::spawn(publish_scheduled_posts(state.clone()));
+            if state.config.replay_archive_enabled {
+                tokio::spawn(archive_old_replays(state.clone()));
+            }
+            if state.config.community_auto_archive_enabled {
+                tokio::spawn(archive_inactive_communities(state.clone()));
+            }
+            // Runs even with the age check off, so posts held back earlier aren't stranded
+            tokio::spawn(federate_deferred_posts(state.clone()));
+            tokio::spawn(finalize_account_deletions(state.clone()));
+            tokio::spawn(refresh_trending_hashtags(state.clone()));
+            tokio::spawn(cleanup_expired_invitations(state.clone()));
+
             App::new(state)
                 .await
                 .expect("Failed to create app")
@@ -14,6 +14,36 @@ pub fn process_markdown_content(value: &str) -> String {
     to_html(&processed_md)
 }
 
+/// Plain-text summary of markdown content, cut to at most `max_chars` characters
+/// with an ellipsis. Used where markup can't be shown, like link previews.
+pub fn markdown_summary(value: &str, max_chars: usize) -> String {
+    let text = match to_mdast(value, &ParseOptions::default()) {
+        Ok(ast) => plain_text(&ast),
+        Err(_) => value.to_string(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text,
+    }
+}
+
+fn plain_text(node: &mdast::Node) -> String {
+    match node {
+        // Inline content runs together; blocks are separated by spaces
+        mdast::Node::Paragraph(_) | mdast::Node::Heading(_) => node.to_string(),
+        _ => match node.children() {
+            Some(children) => children
+                .iter()
+                .map(plain_text)
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => node.to_string(),
+        },
+    }
+}
+
 fn convert_headings_to_paragraphs(node: mdast::Node) -> mdast::Node {
     match node {
         mdast::Node::Heading(heading) => mdast::Node::Paragraph(mdast::Paragraph {
@@ -0,0 +1,115 @@
+use lazy_static::lazy_static;
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+
+lazy_static! {
+    /// Every metric exposed on /metrics
+    pub static ref REGISTRY: Registry = Registry::new();
+
+    pub static ref HTTP_REQUESTS_TOTAL: IntCounterVec = register(
+        IntCounterVec::new(
+            Opts::new("oeee_http_requests_total", "HTTP requests handled"),
+            &["method", "route", "status"],
+        )
+        .expect("HTTP request counter should be valid"),
+    );
+    pub static ref HTTP_REQUEST_DURATION_SECONDS: HistogramVec = register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "oeee_http_request_duration_seconds",
+                "Time taken to produce HTTP responses",
+            ),
+            &["method", "route"],
+        )
+        .expect("HTTP request histogram should be valid"),
+    );
+
+    // Read when /metrics is scraped
+    pub static ref COLLABORATE_WEBSOCKET_SESSIONS: IntGauge = register(
+        IntGauge::new(
+            "oeee_collaborate_websocket_sessions",
+            "Live collaborative drawing connections across all servers",
+        )
+        .expect("WebSocket session gauge should be valid"),
+    );
+    pub static ref DB_POOL_CONNECTIONS: IntGauge = register(
+        IntGauge::new(
+            "oeee_db_pool_connections",
+            "Database connections currently open by this server",
+        )
+        .expect("DB pool gauge should be valid"),
+    );
+    pub static ref DB_POOL_IDLE_CONNECTIONS: IntGauge = register(
+        IntGauge::new(
+            "oeee_db_pool_idle_connections",
+            "Open database connections not in use",
+        )
+        .expect("DB pool idle gauge should be valid"),
+    );
+    pub static ref DB_POOL_MAX_CONNECTIONS: IntGauge = register(
+        IntGauge::new(
+            "oeee_db_pool_max_connections",
+            "Most database connections the pool may open",
+        )
+        .expect("DB pool max gauge should be valid"),
+    );
+
+    pub static ref PUSH_NOTIFICATIONS_TOTAL: IntCounterVec = register(
+        IntCounterVec::new(
+            Opts::new(
+                "oeee_push_notifications_total",
+                "Push notifications sent to devices, by outcome",
+            ),
+            &["platform", "outcome"],
+        )
+        .expect("Push notification counter should be valid"),
+    );
+
+    pub static ref ACTIVITYPUB_INBOX_ACTIVITIES_TOTAL: IntCounterVec = register(
+        IntCounterVec::new(
+            Opts::new(
+                "oeee_activitypub_inbox_activities_total",
+                "ActivityPub activities delivered to our inboxes, by outcome",
+            ),
+            &["inbox", "outcome"],
+        )
+        .expect("ActivityPub inbox counter should be valid"),
+    );
+    pub static ref ACTIVITYPUB_OUTBOX_ACTIVITIES_TOTAL: IntCounterVec = register(
+        IntCounterVec::new(
+            Opts::new(
+                "oeee_activitypub_outbox_activities_total",
+                "ActivityPub activities sent by local actors",
+            ),
+            &["type"],
+        )
+        .expect("ActivityPub outbox counter should be valid"),
+    );
+}
+
+fn register<T: Collector + Clone + 'static>(collector: T) -> T {
+    REGISTRY
+        .register(Box::new(collector.clone()))
+        .expect("Each metric should be registered once");
+    collector
+}
+
+/// Count an activity received at one of our inboxes
+pub fn record_inbox_activity<T, E>(inbox: &str, result: &Result<T, E>) {
+    let outcome = match result {
+        Ok(_) => "accepted",
+        Err(_) => "rejected",
+    };
+    ACTIVITYPUB_INBOX_ACTIVITIES_TOTAL
+        .with_label_values(&[inbox, outcome])
+        .inc();
+}
+
+/// Everything in the registry in the Prometheus text format
+pub fn render() -> Result<String, prometheus::Error> {
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&REGISTRY.gather(), &mut buffer)?;
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, Postgres, Transaction, Type};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[sqlx(type_name = "activity_delivery_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityDeliveryStatus {
+    Pending,
+    Retrying,
+    Delivered,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ActivityDelivery {
+    pub id: Uuid,
+    pub activity_iri: String,
+    pub object_iri: Option<String>,
+    pub inbox_url: String,
+    pub status: ActivityDeliveryStatus,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of one attempt to post an activity to an inbox
+pub enum DeliveryOutcome {
+    /// The inbox answered; only 2xx counts as delivered
+    Response { status_code: u16 },
+    /// No response at all, e.g. a connection error or timeout
+    Error { message: String },
+}
+
+impl DeliveryOutcome {
+    pub fn is_delivered(&self) -> bool {
+        matches!(self, DeliveryOutcome::Response { status_code } if (200..300).contains(status_code))
+    }
+}
+
+pub async fn create_pending_delivery(
+    tx: &mut Transaction<'_, Postgres>,
+    activity_iri: &str,
+    object_iri: Option<&str>,
+    inbox_url: &str,
+) -> Result<Uuid> {
+    let delivery = query!(
+        r#"
+        INSERT INTO activity_deliveries (activity_iri, object_iri, inbox_url)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (activity_iri, inbox_url) DO UPDATE
+        SET updated_at = NOW()
+        RETURNING id
+        "#,
+        activity_iri,
+        object_iri,
+        inbox_url
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(delivery.id)
+}
+
+/// Records an attempt. A failed attempt is marked `retrying` unless it was the last one.
+pub async fn record_delivery_attempt(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    outcome: &DeliveryOutcome,
+    is_last_attempt: bool,
+) -> Result<()> {
+    let status = if outcome.is_delivered() {
+        ActivityDeliveryStatus::Delivered
+    } else if is_last_attempt {
+        ActivityDeliveryStatus::Failed
+    } else {
+        ActivityDeliveryStatus::Retrying
+    };
+    let (status_code, error) = match outcome {
+        DeliveryOutcome::Response { status_code } => (Some(*status_code as i32), None),
+        DeliveryOutcome::Error { message } => (None, Some(message.as_str())),
+    };
+
+    query!(
+        r#"
+        UPDATE activity_deliveries
+        SET
+            status = $2,
+            last_status_code = $3,
+            last_error = $4,
+            attempts = attempts + 1,
+            updated_at = NOW(),
+            delivered_at = CASE WHEN $2 = 'delivered'::activity_delivery_status
+                THEN NOW() ELSE delivered_at END
+        WHERE id = $1
+        "#,
+        id,
+        status as ActivityDeliveryStatus,
+        status_code,
+        error
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn find_deliveries_by_object_iri(
+    tx: &mut Transaction<'_, Postgres>,
+    object_iri: &str,
+) -> Result<Vec<ActivityDelivery>> {
+    let deliveries = query_as!(
+        ActivityDelivery,
+        r#"
+        SELECT
+            id,
+            activity_iri,
+            object_iri,
+            inbox_url,
+            status AS "status: ActivityDeliveryStatus",
+            last_status_code,
+            last_error,
+            attempts,
+            created_at,
+            updated_at,
+            delivered_at
+        FROM activity_deliveries
+        WHERE object_iri = $1
+        ORDER BY created_at DESC, inbox_url
+        "#,
+        object_iri
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(deliveries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn accepted_is_delivered_and_server_error_is_retried(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let activity_iri = "https://oeee.test/activities/1";
+        let object_iri = "https://oeee.test/posts/1";
+        let accepted = create_pending_delivery(
+            &mut tx,
+            activity_iri,
+            Some(object_iri),
+            "https://a.test/inbox",
+        )
+        .await
+        .unwrap();
+        let erroring = create_pending_delivery(
+            &mut tx,
+            activity_iri,
+            Some(object_iri),
+            "https://b.test/inbox",
+        )
+        .await
+        .unwrap();
+
+        let outcome = DeliveryOutcome::Response { status_code: 202 };
+        record_delivery_attempt(&mut tx, accepted, &outcome, false)
+            .await
+            .unwrap();
+        let outcome = DeliveryOutcome::Response { status_code: 500 };
+        record_delivery_attempt(&mut tx, erroring, &outcome, false)
+            .await
+            .unwrap();
+
+        let deliveries = find_deliveries_by_object_iri(&mut tx, object_iri)
+            .await
+            .unwrap();
+        let delivery = |id| deliveries.iter().find(|d| d.id == id).unwrap();
+        assert_eq!(delivery(accepted).status, ActivityDeliveryStatus::Delivered);
+        assert_eq!(delivery(accepted).last_status_code, Some(202));
+        assert!(delivery(accepted).delivered_at.is_some());
+        assert_eq!(delivery(erroring).status, ActivityDeliveryStatus::Retrying);
+        assert_eq!(delivery(erroring).last_status_code, Some(500));
+        assert_eq!(delivery(erroring).attempts, 1);
+        assert!(delivery(erroring).delivered_at.is_none());
+    }
+}
@@ -1,17 +1,27 @@
-use activitypub_federation::activity_queue::queue_activity;
 use activitypub_federation::activity_sending::SendActivityTask;
 use activitypub_federation::config::Data;
 use activitypub_federation::protocol::context::WithContext;
 use activitypub_federation::traits::ActivityHandler;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use data_encoding::BASE64;
+use openssl::hash::MessageDigest;
+use openssl::pkey::{PKey, Private};
+use openssl::sha::sha256;
+use openssl::sign::Signer;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::{query_as, Postgres, Transaction, Type};
-use url::Url;
+use std::sync::Arc;
+use std::time::Duration;
+use url::{Position, Url};
 use uuid::Uuid;
 
 use crate::app_error::AppError;
+use crate::metrics::ACTIVITYPUB_OUTBOX_ACTIVITIES_TOTAL;
+use crate::models::activity_delivery::{
+    create_pending_delivery, record_delivery_attempt, DeliveryOutcome,
+};
 use crate::models::community::Community;
 use crate::models::instance::{find_or_create_local_instance, upsert_instance};
 use crate::models::nodeinfo;
@@ -96,6 +106,26 @@ impl Actor {
         Ok(actor)
     }
 
+    pub async fn find_by_id(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<Option<Actor>> {
+        let actor = query_as!(
+            Actor,
+            r#"
+            SELECT
+                id, iri, type as "type: _", username, instance_host, handle_host, handle,
+                user_id, community_id, name, bio_html, automatically_approves_followers,
+                inbox_url, shared_inbox_url, followers_url, sensitive,
+                public_key_pem, private_key_pem, url,
+                created_at, updated_at, published_at
+            FROM actors WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(actor)
+    }
+
     pub async fn find_by_iri(
         tx: &mut Transaction<'_, Postgres>,
         iri: String,
@@ -229,6 +259,58 @@ impl Actor {
         Ok(created_actor)
     }
 
+    /// Points this record at the account the actor moved to, so everything
+    /// attached to it (follows, posts, comments) carries over
+    pub async fn update_moved_actor(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        moved_to: &Actor,
+    ) -> Result<Actor> {
+        upsert_instance(tx, &moved_to.instance_host, None, None).await?;
+
+        let actor = query_as!(
+            Actor,
+            r#"
+            UPDATE actors SET
+                iri = $2,
+                inbox_url = $3,
+                shared_inbox_url = $4,
+                followers_url = $5,
+                url = $6,
+                username = $7,
+                instance_host = $8,
+                handle_host = $9,
+                handle = $10,
+                name = $11,
+                public_key_pem = $12,
+                updated_at = now()
+            WHERE id = $1
+            RETURNING
+                id, iri, type as "type: _", username, instance_host, handle_host, handle,
+                user_id, community_id, name, bio_html, automatically_approves_followers,
+                inbox_url, shared_inbox_url, followers_url,
+                sensitive, public_key_pem, private_key_pem, url,
+                created_at, updated_at, published_at
+            "#,
+            id,
+            moved_to.iri,
+            moved_to.inbox_url,
+            moved_to.shared_inbox_url,
+            moved_to.followers_url,
+            moved_to.url,
+            moved_to.username,
+            moved_to.instance_host,
+            moved_to.handle_host,
+            moved_to.handle,
+            moved_to.name,
+            moved_to.public_key_pem
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(actor)
+    }
+
     pub(crate) async fn send<A>(
         &self,
         activity: A,
@@ -257,9 +339,86 @@ impl Actor {
                     .collect(),
             ),
         );
+        let activity_type = serde_json::to_value(&activity)?
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("Unknown")
+            .to_string();
+        ACTIVITYPUB_OUTBOX_ACTIVITIES_TOTAL
+            .with_label_values(&[&activity_type])
+            .inc();
         // Send through queue in some cases and bypass it in others to test both code paths
         if use_queue {
-            queue_activity(&activity, self, recipients, data).await?;
+            let state = data.app_data();
+            let private_key_pem = self
+                .private_key_pem
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("Actor {} has no private key", self.iri))?;
+            let signed = Arc::new(SignedActivity {
+                key_id: format!("{}#main-key", self.iri),
+                private_key: PKey::private_key_from_pem(private_key_pem.as_bytes())
+                    .map_err(anyhow::Error::from)?,
+                body: serde_json::to_vec(&activity)?,
+            });
+            let activity_iri = activity.id().to_string();
+            let object_iri = activity_object_iri(&serde_json::to_value(&activity)?);
+            let client = reqwest::Client::builder()
+                .timeout(DELIVERY_TIMEOUT)
+                .user_agent(format!("oeee-cafe (+https://{})", state.config.domain))
+                .build()
+                .map_err(anyhow::Error::from)?;
+
+            // Local inboxes don't need a round trip over HTTP
+            let mut inboxes: Vec<Url> = recipients
+                .into_iter()
+                .filter(|inbox| inbox_authority(inbox) != state.config.domain)
+                .collect();
+            inboxes.sort();
+            inboxes.dedup();
+
+            let mut tx = state.db_pool.begin().await?;
+            let mut deliveries = Vec::with_capacity(inboxes.len());
+            for inbox in inboxes {
+                let delivery_id = create_pending_delivery(
+                    &mut tx,
+                    &activity_iri,
+                    object_iri.as_deref(),
+                    inbox.as_str(),
+                )
+                .await?;
+                deliveries.push((delivery_id, inbox));
+            }
+            tx.commit().await?;
+
+            for (delivery_id, inbox) in deliveries {
+                let domain = inbox.host_str().unwrap_or_default().to_string();
+                let signed = signed.clone();
+                let client = client.clone();
+                let db_pool = state.db_pool.clone();
+                state.delivery_queue.enqueue(&domain, move |attempt| {
+                    let signed = signed.clone();
+                    let client = client.clone();
+                    let db_pool = db_pool.clone();
+                    let inbox = inbox.clone();
+                    async move {
+                        let outcome = post_signed_activity(&client, &signed, &inbox).await;
+                        let mut tx = db_pool.begin().await?;
+                        record_delivery_attempt(&mut tx, delivery_id, &outcome, attempt.is_last)
+                            .await?;
+                        tx.commit().await?;
+
+                        match outcome {
+                            _ if outcome.is_delivered() => Ok(()),
+                            DeliveryOutcome::Response { status_code } => Err(anyhow::anyhow!(
+                                "{} answered with status {}",
+                                inbox,
+                                status_code
+                            )),
+                            DeliveryOutcome::Error { message } => Err(anyhow::anyhow!(message)),
+                        }
+                    }
+                });
+            }
         } else {
             let sends = SendActivityTask::prepare(&activity, self, recipients, data).await?;
             for send in sends {
@@ -270,6 +429,82 @@ impl Actor {
     }
 }
 
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An activity serialized once and signed separately for every inbox
+struct SignedActivity {
+    key_id: String,
+    private_key: PKey<Private>,
+    body: Vec<u8>,
+}
+
+/// `host[:port]` of a URL, in the same form as the configured domain
+fn inbox_authority(inbox: &Url) -> String {
+    inbox[Position::BeforeHost..Position::AfterPort].to_string()
+}
+
+/// The `object` of a serialized activity, whether it's inlined or just referenced
+fn activity_object_iri(activity: &Value) -> Option<String> {
+    match activity.get("object")? {
+        Value::String(iri) => Some(iri.clone()),
+        object => object.get("id")?.as_str().map(str::to_string),
+    }
+}
+
+/// Posts an activity to an inbox with a draft-cavage HTTP signature, the scheme
+/// Mastodon and most other servers verify, and reports how the inbox answered.
+async fn post_signed_activity(
+    client: &reqwest::Client,
+    signed: &SignedActivity,
+    inbox: &Url,
+) -> DeliveryOutcome {
+    match try_post_signed_activity(client, signed, inbox).await {
+        Ok(status_code) => DeliveryOutcome::Response { status_code },
+        Err(e) => DeliveryOutcome::Error {
+            message: format!("{:#}", e),
+        },
+    }
+}
+
+async fn try_post_signed_activity(
+    client: &reqwest::Client,
+    signed: &SignedActivity,
+    inbox: &Url,
+) -> Result<u16> {
+    let request_target = &inbox[Position::BeforePath..Position::AfterQuery];
+    let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+    let digest = format!("SHA-256={}", BASE64.encode(&sha256(&signed.body)));
+    let signing_string = format!(
+        "(request-target): post {}\nhost: {}\ndate: {}\ndigest: {}",
+        request_target,
+        inbox_authority(inbox),
+        date,
+        digest
+    );
+
+    let mut signer = Signer::new(MessageDigest::sha256(), &signed.private_key)?;
+    signer.update(signing_string.as_bytes())?;
+    let signature = BASE64.encode(&signer.sign_to_vec()?);
+
+    let response = client
+        .post(inbox.clone())
+        .header("Content-Type", "application/activity+json")
+        .header("Date", date)
+        .header("Digest", digest)
+        .header(
+            "Signature",
+            format!(
+                r#"keyId="{}",algorithm="rsa-sha256",headers="(request-target) host date digest",signature="{}""#,
+                signed.key_id, signature
+            ),
+        )
+        .body(signed.body.clone())
+        .send()
+        .await?;
+
+    Ok(response.status().as_u16())
+}
+
 pub async fn create_actor_for_user(
     tx: &mut Transaction<'_, Postgres>,
     user: &User,
@@ -322,7 +557,7 @@ pub async fn create_actor_for_user(
         None::<Uuid>, // community_id is None for user actors
         user.display_name,
         "", // bio_html - empty for now
-        true, // automatically_approves_followers
+        !user.is_locked, // automatically_approves_followers
         inbox_url,
         shared_inbox_url,
         followers_url,
@@ -518,6 +753,74 @@ pub async fn create_actor_for_community(
     Ok(actor)
 }
 
+/// The instance's own Application actor, created on first use. Fetches of remote
+/// objects are signed with its key when no user is behind the request.
+pub async fn find_or_create_instance_actor(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &AppConfig,
+) -> Result<Actor> {
+    use activitypub_federation::http_signatures::generate_actor_keypair;
+
+    let iri = format!("https://{}/ap/actor", config.domain);
+    if let Some(actor) = Actor::find_by_iri(tx, iri.clone()).await? {
+        return Ok(actor);
+    }
+
+    // Ensure local instance exists
+    find_or_create_local_instance(tx, &config.domain, None, None).await?;
+
+    let keypair = generate_actor_keypair()?;
+    let now = Utc::now();
+
+    let handle = format!("@{}@{}", config.domain, config.domain);
+    let shared_inbox_url = format!("https://{}/ap/inbox", config.domain);
+    let followers_url = format!("{}/followers", iri);
+    let url = format!("https://{}/", config.domain);
+
+    // Another request may have created it in the meantime
+    sqlx::query!(
+        r#"
+        INSERT INTO actors (
+            iri, type, username, instance_host, handle_host, handle,
+            user_id, community_id, name, bio_html, automatically_approves_followers,
+            inbox_url, shared_inbox_url, followers_url,
+            sensitive, public_key_pem, private_key_pem, url,
+            created_at, updated_at, published_at
+        ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21
+        )
+        ON CONFLICT DO NOTHING
+        "#,
+        iri,
+        ActorType::Application as _,
+        config.domain,
+        config.domain,
+        config.domain,
+        handle,
+        None::<Uuid>,
+        None::<Uuid>,
+        config.domain,
+        "",
+        false, // automatically_approves_followers
+        shared_inbox_url,
+        shared_inbox_url,
+        followers_url,
+        false, // sensitive
+        keypair.public_key,
+        keypair.private_key,
+        url,
+        now,
+        now,
+        now
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Actor::find_by_iri(tx, iri)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Instance actor missing after insert"))
+}
+
 pub async fn backfill_actors_for_existing_communities(
     tx: &mut Transaction<'_, Postgres>,
     config: &AppConfig,
@@ -0,0 +1,243 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, Postgres, Transaction, Type};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[sqlx(type_name = "announcement_level", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementLevel {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Announcement {
+    pub id: Uuid,
+    /// Shown in languages without their own text
+    pub message: String,
+    pub content_ko: String,
+    pub content_en: String,
+    pub content_ja: String,
+    pub content_zh: String,
+    pub level: AnnouncementLevel,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub dismissible: bool,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Announcement {
+    /// Whether the announcement is scheduled to show at `now`
+    pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+        self.is_active && self.starts_at <= now && self.ends_at.is_none_or(|ends_at| ends_at > now)
+    }
+}
+
+pub struct AnnouncementDraft {
+    pub message: String,
+    pub content_ko: String,
+    pub content_en: String,
+    pub content_ja: String,
+    pub content_zh: String,
+    pub level: AnnouncementLevel,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub dismissible: bool,
+}
+
+pub async fn create_announcement(
+    tx: &mut Transaction<'_, Postgres>,
+    created_by: Uuid,
+    draft: AnnouncementDraft,
+) -> Result<Announcement> {
+    let announcement = query_as!(
+        Announcement,
+        r#"
+        INSERT INTO announcements (
+            message, content_ko, content_en, content_ja, content_zh,
+            level, starts_at, ends_at, dismissible, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING
+            id,
+            message,
+            content_ko,
+            content_en,
+            content_ja,
+            content_zh,
+            level as "level: _",
+            starts_at,
+            ends_at,
+            dismissible,
+            is_active,
+            created_by,
+            created_at
+        "#,
+        draft.message,
+        draft.content_ko,
+        draft.content_en,
+        draft.content_ja,
+        draft.content_zh,
+        draft.level as _,
+        draft.starts_at,
+        draft.ends_at,
+        draft.dismissible,
+        created_by
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(announcement)
+}
+
+/// Get every announcement, newest first
+pub async fn list_announcements(tx: &mut Transaction<'_, Postgres>) -> Result<Vec<Announcement>> {
+    let announcements = query_as!(
+        Announcement,
+        r#"
+        SELECT
+            id,
+            message,
+            content_ko,
+            content_en,
+            content_ja,
+            content_zh,
+            level as "level: _",
+            starts_at,
+            ends_at,
+            dismissible,
+            is_active,
+            created_by,
+            created_at
+        FROM announcements
+        ORDER BY starts_at DESC
+        "#
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(announcements)
+}
+
+/// Get the active announcements that haven't ended yet, including scheduled
+/// ones, latest start first. The set only changes when an admin edits it, so
+/// it can be cached until then.
+pub async fn list_current_announcements(
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<Announcement>> {
+    let announcements = query_as!(
+        Announcement,
+        r#"
+        SELECT
+            id,
+            message,
+            content_ko,
+            content_en,
+            content_ja,
+            content_zh,
+            level as "level: _",
+            starts_at,
+            ends_at,
+            dismissible,
+            is_active,
+            created_by,
+            created_at
+        FROM announcements
+        WHERE is_active
+        AND (ends_at IS NULL OR ends_at > now())
+        ORDER BY starts_at DESC
+        "#
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(announcements)
+}
+
+/// Which of the given announcements a user has dismissed
+pub async fn find_dismissed_announcement_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    announcement_ids: &[Uuid],
+) -> Result<Vec<Uuid>> {
+    let ids = sqlx::query_scalar!(
+        r#"
+        SELECT announcement_id
+        FROM announcement_dismissals
+        WHERE user_id = $1 AND announcement_id = ANY($2)
+        "#,
+        user_id,
+        announcement_ids
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Hide an announcement from a user
+/// Returns false if it doesn't exist, can't be dismissed, or already was
+pub async fn dismiss_announcement(
+    tx: &mut Transaction<'_, Postgres>,
+    announcement_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        INSERT INTO announcement_dismissals (announcement_id, user_id)
+        SELECT id, $2
+        FROM announcements
+        WHERE id = $1 AND dismissible
+        ON CONFLICT (announcement_id, user_id) DO NOTHING
+        "#,
+        announcement_id,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Show or hide an announcement without deleting it
+pub async fn set_announcement_active(
+    tx: &mut Transaction<'_, Postgres>,
+    announcement_id: Uuid,
+    is_active: bool,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        UPDATE announcements
+        SET is_active = $2
+        WHERE id = $1
+        "#,
+        announcement_id,
+        is_active
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_announcement(
+    tx: &mut Transaction<'_, Postgres>,
+    announcement_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        DELETE FROM announcements
+        WHERE id = $1
+        "#,
+        announcement_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{query, query_as, Postgres, Transaction};
+use uuid::Uuid;
+
+/// An Announce sent by a local actor, kept so it can be undone
+#[derive(Clone, Debug, Serialize)]
+pub struct Boost {
+    pub id: Uuid,
+    pub iri: String,
+    pub actor_id: Uuid,
+    pub object_iri: String,
+    pub post_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Record a boost. Returns `None` if the actor had already boosted the object.
+pub async fn create_boost(
+    tx: &mut Transaction<'_, Postgres>,
+    iri: &str,
+    actor_id: Uuid,
+    object_iri: &str,
+    post_id: Option<Uuid>,
+) -> Result<Option<Boost>> {
+    let boost = query_as!(
+        Boost,
+        r#"
+        INSERT INTO boosts (iri, actor_id, object_iri, post_id)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (actor_id, object_iri) DO NOTHING
+        RETURNING id, iri, actor_id, object_iri, post_id, created_at
+        "#,
+        iri,
+        actor_id,
+        object_iri,
+        post_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(boost)
+}
+
+pub async fn find_boost(
+    tx: &mut Transaction<'_, Postgres>,
+    actor_id: Uuid,
+    object_iri: &str,
+) -> Result<Option<Boost>> {
+    let boost = query_as!(
+        Boost,
+        r#"
+        SELECT id, iri, actor_id, object_iri, post_id, created_at
+        FROM boosts
+        WHERE actor_id = $1 AND object_iri = $2
+        "#,
+        actor_id,
+        object_iri
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(boost)
+}
+
+pub async fn delete_boost(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<()> {
+    query!("DELETE FROM boosts WHERE id = $1", id)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+/// Published posts outside private communities can be boosted
+pub async fn is_post_boostable(tx: &mut Transaction<'_, Postgres>, post_id: Uuid) -> Result<bool> {
+    let result = query!(
+        r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM posts
+                LEFT JOIN communities ON posts.community_id = communities.id
+                WHERE posts.id = $1
+                AND posts.published_at IS NOT NULL
+                AND posts.deleted_at IS NULL
+                AND (communities.id IS NULL OR communities.visibility != 'private')
+            ) AS "exists!"
+        "#,
+        post_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.exists)
+}
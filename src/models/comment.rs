@@ -1,25 +1,21 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::Serialize;
 use sqlx::{Postgres, Transaction, Type};
 use uuid::Uuid;
 
-type CommentData = (
-    Uuid,                  // post_id
-    Uuid,                  // actor_id
-    Option<Uuid>,          // parent_comment_id
-    Option<String>,        // content
-    Option<String>,        // content_html
-    Option<String>,        // iri
-    String,                // actor_name
-    String,                // actor_handle
-    String,                // actor_url
-    Option<String>,        // actor_login_name
-    bool,                  // is_local
-    DateTime<Utc>,         // updated_at
-    DateTime<Utc>,         // created_at
-    Option<DateTime<Utc>>, // deleted_at
-);
+use super::comment_reaction::get_comment_reaction_counts_for_post;
+use super::reaction::ReactionCount;
+
+/// How long after posting a comment its author may still edit it
+pub const COMMENT_EDIT_WINDOW_MINUTES: i64 = 5;
+
+/// Whether a comment posted at `created_at` is still within its edit window
+pub fn is_comment_editable(created_at: DateTime<Utc>) -> bool {
+    created_at + TimeDelta::minutes(COMMENT_EDIT_WINDOW_MINUTES) > Utc::now()
+}
 
 #[derive(Clone, Debug, Serialize, Type)]
 #[sqlx(type_name = "comment_deletion_reason", rename_all = "snake_case")]
@@ -38,8 +34,10 @@ pub struct Comment {
     pub content: Option<String>,
     pub content_html: Option<String>,
     pub iri: Option<String>,
+    pub is_sensitive: bool,
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
@@ -77,6 +75,8 @@ pub struct SerializableThreadedComment {
     pub content: Option<String>,
     pub content_html: Option<String>,
     pub iri: Option<String>,
+    /// Collapsed behind a content warning when shown
+    pub is_sensitive: bool,
     pub actor_name: String,
     pub actor_handle: String,
     pub actor_url: String,
@@ -84,7 +84,14 @@ pub struct SerializableThreadedComment {
     pub is_local: bool,
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    /// Content as first posted, for comments that have been edited since
+    pub original_content: Option<String>,
+    /// Whether the author can still edit it
+    pub is_editable: bool,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub reaction_counts: Vec<ReactionCount>,
+    pub child_count: i64,
     pub children: Vec<SerializableThreadedComment>,
 }
 
@@ -164,162 +171,118 @@ pub async fn find_comments_by_post_id(
         .collect())
 }
 
+/// Top-level comments of a post. Replies are loaded on demand with
+/// `find_comment_replies`; each comment carries its `child_count`.
 pub async fn build_comment_thread_tree(
     tx: &mut Transaction<'_, Postgres>,
     post_id: Uuid,
+    viewer_user_id: Option<Uuid>,
 ) -> Result<Vec<SerializableThreadedComment>> {
-    use std::collections::HashMap;
+    find_threaded_comments(tx, post_id, None, viewer_user_id).await
+}
 
-    // Use recursive CTE to fetch all comments with their parent relationships
+/// Immediate replies to a comment, without their own replies
+pub async fn find_comment_replies(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    comment_id: Uuid,
+    viewer_user_id: Option<Uuid>,
+) -> Result<Vec<SerializableThreadedComment>> {
+    find_threaded_comments(tx, post_id, Some(comment_id), viewer_user_id).await
+}
+
+async fn find_threaded_comments(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    parent_comment_id: Option<Uuid>,
+    viewer_user_id: Option<Uuid>,
+) -> Result<Vec<SerializableThreadedComment>> {
     let rows = sqlx::query!(
         r#"
-        WITH RECURSIVE comment_tree AS (
-            -- Base case: all comments for this post
-            SELECT
-                comments.id,
-                comments.post_id,
-                comments.actor_id,
-                comments.parent_comment_id,
-                comments.content,
-                comments.content_html,
-                comments.iri,
-                comments.updated_at,
-                comments.created_at,
-                comments.deleted_at,
-                actors.name AS actor_name,
-                actors.handle AS actor_handle,
-                actors.url AS actor_url,
-                users.login_name AS user_login_name
-            FROM comments
-            LEFT JOIN actors ON comments.actor_id = actors.id
-            LEFT JOIN users ON actors.user_id = users.id
-            WHERE comments.post_id = $1
-        )
         SELECT
-            id,
-            post_id,
-            actor_id,
-            parent_comment_id,
-            content AS "content?",
-            content_html AS "content_html?",
-            iri AS "iri?",
-            updated_at,
-            created_at,
-            deleted_at,
-            actor_name AS "actor_name?",
-            actor_handle AS "actor_handle?",
-            actor_url AS "actor_url?",
-            user_login_name AS "user_login_name?"
-        FROM comment_tree
-        ORDER BY created_at ASC
+            comments.id,
+            comments.post_id,
+            comments.actor_id,
+            comments.parent_comment_id,
+            comments.content,
+            comments.content_html,
+            comments.iri,
+            comments.is_sensitive,
+            comments.updated_at,
+            comments.created_at,
+            comments.edited_at,
+            comments.deleted_at,
+            (
+                SELECT comment_history.content
+                FROM comment_history
+                WHERE comment_history.comment_id = comments.id
+                ORDER BY comment_history.edited_at ASC
+                LIMIT 1
+            ) AS original_content,
+            actors.name AS "actor_name?",
+            actors.handle AS "actor_handle?",
+            actors.url AS "actor_url?",
+            users.login_name AS "user_login_name?",
+            -- Deleted replies only show up as tombstones over their own replies
+            (
+                SELECT COUNT(*)
+                FROM comments AS replies
+                WHERE replies.parent_comment_id = comments.id
+                AND (
+                    replies.deleted_at IS NULL
+                    OR EXISTS (
+                        SELECT 1 FROM comments AS nested
+                        WHERE nested.parent_comment_id = replies.id
+                    )
+                )
+            ) AS "child_count!"
+        FROM comments
+        LEFT JOIN actors ON comments.actor_id = actors.id
+        LEFT JOIN users ON actors.user_id = users.id
+        WHERE comments.post_id = $1
+        AND comments.parent_comment_id IS NOT DISTINCT FROM $2
+        ORDER BY comments.created_at ASC
         "#,
-        post_id
+        post_id,
+        parent_comment_id
     )
     .fetch_all(&mut **tx)
     .await?;
 
-    // Build maps for efficient tree construction
-    let mut comment_data: HashMap<Uuid, CommentData> = HashMap::new();
-
-    let mut children_map: HashMap<Option<Uuid>, Vec<Uuid>> = HashMap::new();
-
-    for row in rows {
-        let comment_id = row.id;
-        // user_login_name can be NULL from LEFT JOIN
-        let user_login_name = row.user_login_name;
-        let is_local = user_login_name.is_some();
+    let mut reaction_counts =
+        get_comment_reaction_counts_for_post(tx, post_id, viewer_user_id).await?;
 
-        comment_data.insert(
-            comment_id,
-            (
-                row.post_id,
-                row.actor_id,
-                row.parent_comment_id,
-                row.content,
-                row.content_html,
-                row.iri,
-                row.actor_name.unwrap_or_default(),
-                row.actor_handle.unwrap_or_default(),
-                row.actor_url.unwrap_or_default(),
-                user_login_name,
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            // user_login_name can be NULL from LEFT JOIN
+            let is_local = row.user_login_name.is_some();
+            SerializableThreadedComment {
+                id: row.id,
+                post_id: row.post_id,
+                actor_id: row.actor_id,
+                parent_comment_id: row.parent_comment_id,
+                content: row.content,
+                content_html: row.content_html,
+                iri: row.iri,
+                is_sensitive: row.is_sensitive,
+                actor_name: row.actor_name.unwrap_or_default(),
+                actor_handle: row.actor_handle.unwrap_or_default(),
+                actor_url: row.actor_url.unwrap_or_default(),
+                actor_login_name: row.user_login_name,
                 is_local,
-                row.updated_at,
-                row.created_at,
-                row.deleted_at,
-            ),
-        );
-
-        children_map
-            .entry(row.parent_comment_id)
-            .or_default()
-            .push(comment_id);
-    }
-
-    // Recursive function to build subtree
-    fn build_subtree(
-        comment_id: Uuid,
-        comment_data: &HashMap<Uuid, CommentData>,
-        children_map: &HashMap<Option<Uuid>, Vec<Uuid>>,
-    ) -> Option<SerializableThreadedComment> {
-        let (
-            post_id,
-            actor_id,
-            parent_comment_id,
-            content,
-            content_html,
-            iri,
-            actor_name,
-            actor_handle,
-            actor_url,
-            actor_login_name,
-            is_local,
-            updated_at,
-            created_at,
-            deleted_at,
-        ) = comment_data.get(&comment_id)?;
-
-        let children = children_map
-            .get(&Some(comment_id))
-            .map(|child_ids| {
-                child_ids
-                    .iter()
-                    .filter_map(|child_id| build_subtree(*child_id, comment_data, children_map))
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        Some(SerializableThreadedComment {
-            id: comment_id,
-            post_id: *post_id,
-            actor_id: *actor_id,
-            parent_comment_id: *parent_comment_id,
-            content: content.clone(),
-            content_html: content_html.clone(),
-            iri: iri.clone(),
-            actor_name: actor_name.clone(),
-            actor_handle: actor_handle.clone(),
-            actor_url: actor_url.clone(),
-            actor_login_name: actor_login_name.clone(),
-            is_local: *is_local,
-            updated_at: *updated_at,
-            created_at: *created_at,
-            deleted_at: *deleted_at,
-            children,
-        })
-    }
-
-    // Build trees for all root comments (comments with no parent)
-    let result: Vec<SerializableThreadedComment> = children_map
-        .get(&None)
-        .map(|root_ids| {
-            root_ids
-                .iter()
-                .filter_map(|comment_id| build_subtree(*comment_id, &comment_data, &children_map))
-                .collect()
+                updated_at: row.updated_at,
+                created_at: row.created_at,
+                edited_at: row.edited_at,
+                original_content: row.original_content,
+                is_editable: is_comment_editable(row.created_at),
+                deleted_at: row.deleted_at,
+                reaction_counts: reaction_counts.remove(&row.id).unwrap_or_default(),
+                child_count: row.child_count,
+                children: Vec::new(),
+            }
         })
-        .unwrap_or_default();
-
-    Ok(result)
+        .collect())
 }
 
 pub async fn build_comment_thread_tree_paginated(
@@ -327,6 +290,7 @@ pub async fn build_comment_thread_tree_paginated(
     post_id: Uuid,
     limit: i64,
     offset: i64,
+    viewer_user_id: Option<Uuid>,
 ) -> Result<(Vec<SerializableThreadedComment>, i64)> {
     // First, get the total count of top-level comments
     let total_count = sqlx::query_scalar!(
@@ -377,8 +341,10 @@ pub async fn build_comment_thread_tree_paginated(
                 c.content,
                 c.content_html,
                 c.iri,
+                c.is_sensitive,
                 c.created_at,
                 c.updated_at,
+                c.edited_at,
                 c.deleted_at,
                 a.name AS actor_name,
                 a.handle AS actor_handle,
@@ -401,8 +367,10 @@ pub async fn build_comment_thread_tree_paginated(
                 c.content,
                 c.content_html,
                 c.iri,
+                c.is_sensitive,
                 c.created_at,
                 c.updated_at,
+                c.edited_at,
                 c.deleted_at,
                 a.name AS actor_name,
                 a.handle AS actor_handle,
@@ -422,9 +390,18 @@ pub async fn build_comment_thread_tree_paginated(
             content AS "content?",
             content_html AS "content_html?",
             iri AS "iri?",
+            is_sensitive,
             created_at,
             updated_at,
+            edited_at,
             deleted_at,
+            (
+                SELECT comment_history.content
+                FROM comment_history
+                WHERE comment_history.comment_id = comment_tree.id
+                ORDER BY comment_history.edited_at ASC
+                LIMIT 1
+            ) AS original_content,
             actor_name AS "actor_name?",
             actor_handle AS "actor_handle?",
             actor_url AS "actor_url?",
@@ -437,11 +414,10 @@ pub async fn build_comment_thread_tree_paginated(
     .fetch_all(&mut **tx)
     .await?;
 
-    // Build comment data map
-    use std::collections::HashMap;
-    let mut comment_data: HashMap<Uuid, SerializableThreadedComment> = HashMap::new();
-    let mut children_map: HashMap<Option<Uuid>, Vec<Uuid>> = HashMap::new();
+    let mut reaction_counts =
+        get_comment_reaction_counts_for_post(tx, post_id, viewer_user_id).await?;
 
+    let mut comments = Vec::with_capacity(rows.len());
     for row in rows {
         // Handle optional fields from LEFT JOIN
         let Some(id) = row.id else { continue };
@@ -456,91 +432,70 @@ pub async fn build_comment_thread_tree_paginated(
             continue;
         };
 
-        // Actor fields can be NULL from LEFT JOIN
-        let actor_name = row.actor_name.unwrap_or_default();
-        let actor_handle = row.actor_handle.unwrap_or_default();
-        let actor_url = row.actor_url.unwrap_or_default();
-
         // user_login_name is already Option<String> from the query
         let user_login_name = row.user_login_name;
         let is_local = user_login_name.is_some();
 
-        comment_data.insert(
+        comments.push(SerializableThreadedComment {
             id,
-            SerializableThreadedComment {
-                id,
-                post_id,
-                actor_id,
-                parent_comment_id: row.parent_comment_id,
-                content: row.content,
-                content_html: row.content_html,
-                iri: row.iri,
-                actor_name,
-                actor_handle,
-                actor_url,
-                actor_login_name: user_login_name,
-                is_local,
-                created_at,
-                updated_at,
-                deleted_at: row.deleted_at,
-                children: Vec::new(),
-            },
-        );
+            post_id,
+            actor_id,
+            parent_comment_id: row.parent_comment_id,
+            content: row.content,
+            content_html: row.content_html,
+            iri: row.iri,
+            is_sensitive: row.is_sensitive.unwrap_or(false),
+            // Actor fields can be NULL from LEFT JOIN
+            actor_name: row.actor_name.unwrap_or_default(),
+            actor_handle: row.actor_handle.unwrap_or_default(),
+            actor_url: row.actor_url.unwrap_or_default(),
+            actor_login_name: user_login_name,
+            is_local,
+            created_at,
+            updated_at,
+            edited_at: row.edited_at,
+            original_content: row.original_content,
+            is_editable: is_comment_editable(created_at),
+            deleted_at: row.deleted_at,
+            reaction_counts: reaction_counts.remove(&id).unwrap_or_default(),
+            child_count: 0,
+            children: Vec::new(),
+        });
+    }
+
+    Ok((nest_comments(comments), total_count))
+}
 
+/// Nest a flat list of comments into a thread, keeping their order within each level.
+///
+/// Follows the same rule as `find_threaded_comments`: a deleted comment only stays as a
+/// tombstone over its own replies, and `child_count` counts the replies that are kept.
+fn nest_comments(comments: Vec<SerializableThreadedComment>) -> Vec<SerializableThreadedComment> {
+    let mut children_map: HashMap<Option<Uuid>, Vec<SerializableThreadedComment>> = HashMap::new();
+    for comment in comments {
         children_map
-            .entry(row.parent_comment_id)
+            .entry(comment.parent_comment_id)
             .or_default()
-            .push(id);
+            .push(comment);
     }
 
-    // Recursive function to build comment tree
-    fn build_subtree(
-        comment_id: Uuid,
-        comment_data: &HashMap<Uuid, SerializableThreadedComment>,
-        children_map: &HashMap<Option<Uuid>, Vec<Uuid>>,
-    ) -> Option<SerializableThreadedComment> {
-        comment_data.get(&comment_id).map(|comment| {
-            let children = children_map
-                .get(&Some(comment_id))
-                .cloned()
-                .unwrap_or_default()
-                .into_iter()
-                .filter_map(|child_id| build_subtree(child_id, comment_data, children_map))
-                .collect();
-
-            SerializableThreadedComment {
-                id: comment.id,
-                post_id: comment.post_id,
-                actor_id: comment.actor_id,
-                parent_comment_id: comment.parent_comment_id,
-                content: comment.content.clone(),
-                content_html: comment.content_html.clone(),
-                iri: comment.iri.clone(),
-                actor_name: comment.actor_name.clone(),
-                actor_handle: comment.actor_handle.clone(),
-                actor_url: comment.actor_url.clone(),
-                actor_login_name: comment.actor_login_name.clone(),
-                is_local: comment.is_local,
-                created_at: comment.created_at,
-                updated_at: comment.updated_at,
-                deleted_at: comment.deleted_at,
-                children,
-            }
-        })
+    fn build_level(
+        parent_id: Option<Uuid>,
+        children_map: &mut HashMap<Option<Uuid>, Vec<SerializableThreadedComment>>,
+    ) -> Vec<SerializableThreadedComment> {
+        let level = children_map.remove(&parent_id).unwrap_or_default();
+        level
+            .into_iter()
+            .filter_map(|mut comment| {
+                let has_replies = children_map.contains_key(&Some(comment.id));
+                comment.children = build_level(Some(comment.id), children_map);
+                comment.child_count = comment.children.len() as i64;
+                (comment.deleted_at.is_none() || has_replies).then_some(comment)
+            })
+            .collect()
     }
 
-    // Build top-level comments with their children
-    let result = children_map
-        .get(&None)
-        .map(|top_level_ids| {
-            top_level_ids
-                .iter()
-                .filter_map(|comment_id| build_subtree(*comment_id, &comment_data, &children_map))
-                .collect()
-        })
-        .unwrap_or_default();
-
-    Ok((result, total_count))
+    build_level(None, &mut children_map)
 }
 
 pub async fn find_comments_to_posts_by_author(
@@ -721,7 +676,7 @@ pub async fn create_comment(
         r#"
         INSERT INTO comments (post_id, actor_id, parent_comment_id, content, content_html)
         VALUES ($1, $2, $3, $4, $5)
-        RETURNING id, post_id, actor_id, parent_comment_id, content, content_html, iri, created_at, updated_at, deleted_at
+        RETURNING id, post_id, actor_id, parent_comment_id, content, content_html, iri, is_sensitive, created_at, updated_at, edited_at, deleted_at
         "#,
         draft.post_id,
         draft.actor_id,
@@ -742,7 +697,7 @@ pub async fn find_comment_by_iri(
     let comment = sqlx::query_as!(
         Comment,
         r#"
-        SELECT id, post_id, actor_id, parent_comment_id, content, content_html, iri, created_at, updated_at, deleted_at
+        SELECT id, post_id, actor_id, parent_comment_id, content, content_html, iri, is_sensitive, created_at, updated_at, edited_at, deleted_at
         FROM comments
         WHERE iri = $1
         AND deleted_at IS NULL
@@ -784,19 +739,21 @@ pub async fn create_comment_from_activitypub(
     content: String,
     content_html: Option<String>,
     iri: String,
+    is_sensitive: bool,
 ) -> Result<Comment> {
     let comment = sqlx::query_as!(
         Comment,
         r#"
-        INSERT INTO comments (post_id, actor_id, parent_comment_id, content, content_html, iri)
-        VALUES ($1, $2, NULL, $3, $4, $5)
-        RETURNING id, post_id, actor_id, parent_comment_id, content, content_html, iri, created_at, updated_at, deleted_at
+        INSERT INTO comments (post_id, actor_id, parent_comment_id, content, content_html, iri, is_sensitive)
+        VALUES ($1, $2, NULL, $3, $4, $5, $6)
+        RETURNING id, post_id, actor_id, parent_comment_id, content, content_html, iri, is_sensitive, created_at, updated_at, edited_at, deleted_at
         "#,
         post_id,
         actor_id,
         content,
         content_html,
-        iri
+        iri,
+        is_sensitive
     )
     .fetch_one(&mut **tx)
     .await?;
@@ -892,3 +849,231 @@ pub async fn delete_comment(
 
     Ok(())
 }
+
+pub async fn find_comment_by_id(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<Option<Comment>> {
+    let comment = sqlx::query_as!(
+        Comment,
+        r#"
+        SELECT id, post_id, actor_id, parent_comment_id, content, content_html, iri, is_sensitive, created_at, updated_at, edited_at, deleted_at
+        FROM comments
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(comment)
+}
+
+/// Replace a comment's content, keeping the content it replaces in
+/// `comment_history`. Returns `None` if the comment is missing or already deleted.
+pub async fn edit_comment(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    content: String,
+) -> Result<Option<Comment>> {
+    sqlx::query!(
+        r#"
+        INSERT INTO comment_history (comment_id, content, edited_at)
+        SELECT id, COALESCE(content, ''), now()
+        FROM comments
+        WHERE id = $1
+        AND deleted_at IS NULL
+        "#,
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let comment = sqlx::query_as!(
+        Comment,
+        r#"
+        UPDATE comments
+        SET
+            content = $2,
+            content_html = NULL,
+            updated_at = now(),
+            edited_at = now()
+        WHERE id = $1
+        AND deleted_at IS NULL
+        RETURNING id, post_id, actor_id, parent_comment_id, content, content_html, iri, is_sensitive, created_at, updated_at, edited_at, deleted_at
+        "#,
+        id,
+        content
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(comment)
+}
+
+/// Soft-delete a comment at its author's request.
+/// Returns false if the comment was already deleted.
+pub async fn soft_delete_comment(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<bool> {
+    let comment = find_comment_by_id(tx, id).await?;
+    match comment {
+        Some(c) if c.deleted_at.is_none() => {
+            delete_comment(tx, id, CommentDeletionReason::UserDeleted).await?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Shared inboxes of remote actors who commented on or reacted to a post.
+/// These are the instances that may hold a copy of a comment in its thread.
+pub async fn get_remote_inboxes_for_post_audience(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+) -> Result<Vec<String>> {
+    let inboxes = sqlx::query!(
+        r#"
+        SELECT DISTINCT actors.shared_inbox_url
+        FROM actors
+        WHERE actors.user_id IS NULL
+        AND actors.community_id IS NULL
+        AND (
+            actors.id IN (SELECT actor_id FROM comments WHERE post_id = $1)
+            OR actors.id IN (SELECT actor_id FROM reactions WHERE post_id = $1)
+        )
+        "#,
+        post_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(inboxes
+        .into_iter()
+        .map(|row| row.shared_inbox_url)
+        .collect())
+}
+
+/// Pull a single comment, together with its replies, out of a thread tree
+pub fn find_comment_in_thread(
+    comments: Vec<SerializableThreadedComment>,
+    id: Uuid,
+) -> Option<SerializableThreadedComment> {
+    for comment in comments {
+        if comment.id == id {
+            return Some(comment);
+        }
+        if let Some(found) = find_comment_in_thread(comment.children, id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(
+        id: u128,
+        parent: Option<u128>,
+        minute: i64,
+        deleted: bool,
+    ) -> SerializableThreadedComment {
+        let created_at = DateTime::UNIX_EPOCH + TimeDelta::minutes(minute);
+        SerializableThreadedComment {
+            id: Uuid::from_u128(id),
+            post_id: Uuid::nil(),
+            actor_id: Uuid::nil(),
+            parent_comment_id: parent.map(Uuid::from_u128),
+            content: Some(format!("comment {}", id)),
+            content_html: None,
+            iri: None,
+            is_sensitive: false,
+            actor_name: "actor".to_string(),
+            actor_handle: "@actor".to_string(),
+            actor_url: "/@actor".to_string(),
+            actor_login_name: Some("actor".to_string()),
+            is_local: true,
+            updated_at: created_at,
+            created_at,
+            edited_at: None,
+            original_content: None,
+            is_editable: false,
+            deleted_at: deleted.then_some(created_at),
+            reaction_counts: Vec::new(),
+            child_count: 0,
+            children: Vec::new(),
+        }
+    }
+
+    /// A thread with nested replies, a tombstone over a reply, and deleted
+    /// leaves that neither view shows, in `created_at` order
+    fn sample_thread() -> Vec<SerializableThreadedComment> {
+        vec![
+            comment(1, None, 0, false),
+            comment(2, None, 1, true),
+            comment(3, Some(1), 2, false),
+            comment(4, Some(1), 3, true),
+            comment(5, Some(2), 4, false),
+            comment(6, Some(3), 5, true),
+            comment(7, Some(6), 6, false),
+            comment(8, None, 7, true),
+            comment(9, Some(1), 8, false),
+        ]
+    }
+
+    /// The comments the HTML view shows under `parent`, as `find_threaded_comments`
+    /// loads them and `comments_macro.jinja` filters them: (id, child_count)
+    fn html_level(flat: &[SerializableThreadedComment], parent: Option<Uuid>) -> Vec<(Uuid, i64)> {
+        let has_replies = |id: Uuid| flat.iter().any(|c| c.parent_comment_id == Some(id));
+        flat.iter()
+            .filter(|c| c.parent_comment_id == parent)
+            .map(|c| {
+                let child_count = flat
+                    .iter()
+                    .filter(|reply| reply.parent_comment_id == Some(c.id))
+                    .filter(|reply| reply.deleted_at.is_none() || has_replies(reply.id))
+                    .count() as i64;
+                (c, child_count)
+            })
+            .filter(|(c, child_count)| c.deleted_at.is_none() || *child_count > 0)
+            .map(|(c, child_count)| (c.id, child_count))
+            .collect()
+    }
+
+    fn assert_same_level(
+        flat: &[SerializableThreadedComment],
+        parent: Option<Uuid>,
+        json: &[SerializableThreadedComment],
+    ) {
+        let html = html_level(flat, parent);
+        let json_level: Vec<(Uuid, i64)> = json.iter().map(|c| (c.id, c.child_count)).collect();
+        assert_eq!(json_level, html, "level under {:?}", parent);
+        for comment in json {
+            assert_eq!(comment.child_count, comment.children.len() as i64);
+            assert_same_level(flat, Some(comment.id), &comment.children);
+        }
+    }
+
+    #[test]
+    fn json_thread_matches_html_thread() {
+        let thread = nest_comments(sample_thread());
+
+        assert_same_level(&sample_thread(), None, &thread);
+        let top_level: Vec<Uuid> = thread.iter().map(|c| c.id).collect();
+        assert_eq!(top_level, vec![Uuid::from_u128(1), Uuid::from_u128(2)]);
+    }
+
+    #[test]
+    fn nesting_keeps_tombstones_only_over_replies() {
+        let thread = nest_comments(sample_thread());
+
+        let first = &thread[0];
+        let replies: Vec<Uuid> = first.children.iter().map(|c| c.id).collect();
+        assert_eq!(replies, vec![Uuid::from_u128(3), Uuid::from_u128(9)]);
+
+        let tombstone = &first.children[0].children[0];
+        assert_eq!(tombstone.id, Uuid::from_u128(6));
+        assert!(tombstone.deleted_at.is_some());
+        assert_eq!(tombstone.children[0].id, Uuid::from_u128(7));
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use super::reaction::ReactionCount;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CommentReaction {
+    pub iri: String,
+    pub comment_id: Uuid,
+    pub actor_id: Uuid,
+    pub emoji: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct CommentReactionDraft {
+    pub comment_id: Uuid,
+    pub actor_id: Uuid,
+    pub emoji: String,
+}
+
+pub async fn create_comment_reaction(
+    tx: &mut Transaction<'_, Postgres>,
+    draft: CommentReactionDraft,
+    domain: &str,
+) -> Result<CommentReaction> {
+    // Generate IRI for local reactions
+    let iri = format!(
+        "https://{}/ap/emojireacts/comments/{}/{}",
+        domain,
+        draft.comment_id,
+        uuid::Uuid::new_v4()
+    );
+
+    let reaction = sqlx::query_as!(
+        CommentReaction,
+        r#"
+        INSERT INTO comment_reactions (iri, comment_id, actor_id, emoji)
+        VALUES ($1, $2, $3, $4)
+        RETURNING iri, comment_id, actor_id, emoji, created_at
+        "#,
+        iri,
+        draft.comment_id,
+        draft.actor_id,
+        draft.emoji
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(reaction)
+}
+
+pub async fn delete_comment_reaction(
+    tx: &mut Transaction<'_, Postgres>,
+    comment_id: Uuid,
+    actor_id: Uuid,
+    emoji: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM comment_reactions
+        WHERE comment_id = $1 AND actor_id = $2 AND emoji = $3
+        RETURNING iri
+        "#,
+        comment_id,
+        actor_id,
+        emoji
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(result.is_some())
+}
+
+pub async fn find_user_comment_reaction(
+    tx: &mut Transaction<'_, Postgres>,
+    comment_id: Uuid,
+    actor_id: Uuid,
+    emoji: &str,
+) -> Result<Option<CommentReaction>> {
+    let reaction = sqlx::query_as!(
+        CommentReaction,
+        r#"
+        SELECT iri, comment_id, actor_id, emoji, created_at
+        FROM comment_reactions
+        WHERE comment_id = $1 AND actor_id = $2 AND emoji = $3
+        "#,
+        comment_id,
+        actor_id,
+        emoji
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(reaction)
+}
+
+/// Reaction counts for a single comment. Unlike post reactions, only emojis
+/// that have been used are returned so comments stay compact.
+pub async fn get_comment_reaction_counts(
+    tx: &mut Transaction<'_, Postgres>,
+    comment_id: Uuid,
+    user_actor_id: Option<Uuid>,
+) -> Result<Vec<ReactionCount>> {
+    let reactions = sqlx::query!(
+        r#"
+        SELECT
+            emoji,
+            COUNT(*) as "count!",
+            COALESCE(BOOL_OR(actor_id = $2), false) as "reacted_by_user!"
+        FROM comment_reactions
+        WHERE comment_id = $1
+        GROUP BY emoji
+        ORDER BY "count!" DESC, emoji
+        "#,
+        comment_id,
+        user_actor_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(reactions
+        .into_iter()
+        .map(|row| ReactionCount {
+            emoji: row.emoji,
+            count: row.count,
+            reacted_by_user: row.reacted_by_user,
+        })
+        .collect())
+}
+
+/// Reaction counts for every comment on a post, keyed by comment ID
+pub async fn get_comment_reaction_counts_for_post(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    viewer_user_id: Option<Uuid>,
+) -> Result<HashMap<Uuid, Vec<ReactionCount>>> {
+    let reactions = sqlx::query!(
+        r#"
+        SELECT
+            comment_reactions.comment_id,
+            comment_reactions.emoji,
+            COUNT(*) as "count!",
+            COALESCE(BOOL_OR(actors.user_id = $2), false) as "reacted_by_user!"
+        FROM comment_reactions
+        JOIN comments ON comment_reactions.comment_id = comments.id
+        JOIN actors ON comment_reactions.actor_id = actors.id
+        WHERE comments.post_id = $1
+        GROUP BY comment_reactions.comment_id, comment_reactions.emoji
+        ORDER BY "count!" DESC, comment_reactions.emoji
+        "#,
+        post_id,
+        viewer_user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut counts: HashMap<Uuid, Vec<ReactionCount>> = HashMap::new();
+    for row in reactions {
+        counts
+            .entry(row.comment_id)
+            .or_default()
+            .push(ReactionCount {
+                emoji: row.emoji,
+                count: row.count,
+                reacted_by_user: row.reacted_by_user,
+            });
+    }
+
+    Ok(counts)
+}
@@ -1,8 +1,10 @@
 use anyhow::{bail, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use sqlx::query;
 use sqlx::query_as;
+use sqlx::query_scalar;
 use sqlx::Postgres;
 use sqlx::Transaction;
 use sqlx::Type;
@@ -51,9 +53,12 @@ pub struct CommunityInvitation {
     pub community_id: Uuid,
     pub inviter_id: Uuid,
     pub invitee_id: Uuid,
+    pub role: CommunityMemberRole,
     pub status: CommunityInvitationStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// A pending invitation can't be accepted after this
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -68,6 +73,7 @@ pub struct Community {
     pub created_at: DateTime<Utc>,
     pub background_color: Option<String>,
     pub foreground_color: Option<String>,
+    pub is_sensitive: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -111,6 +117,7 @@ pub struct CommunityDraft {
     pub slug: String,
     pub description: String,
     pub visibility: CommunityVisibility,
+    pub is_sensitive: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -120,13 +127,80 @@ pub struct CommunityStats {
     pub total_comments: i64,
 }
 
+/// Posting activity of a community on one UTC day
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CommunityDailyActivity {
+    pub day: NaiveDate,
+    pub post_count: i64,
+    pub contributor_count: i64,
+    /// All published posts up to and including this day
+    pub cumulative_post_count: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CommunityTopContributor {
+    pub user_id: Uuid,
+    pub login_name: String,
+    pub display_name: String,
+    pub post_count: i64,
+}
+
+/// How far back a community leaderboard looks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardPeriod {
+    Week,
+    #[default]
+    Month,
+    AllTime,
+}
+
+impl LeaderboardPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardPeriod::Week => "week",
+            LeaderboardPeriod::Month => "month",
+            LeaderboardPeriod::AllTime => "all_time",
+        }
+    }
+
+    /// Start of the period, or `None` when it covers all time
+    pub fn since(&self) -> Option<DateTime<Utc>> {
+        match self {
+            LeaderboardPeriod::Week => Some(Utc::now() - Duration::days(7)),
+            LeaderboardPeriod::Month => Some(Utc::now() - Duration::days(30)),
+            LeaderboardPeriod::AllTime => None,
+        }
+    }
+}
+
+/// One contributor's standing on a community leaderboard
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CommunityLeaderboardEntry {
+    pub rank: i64,
+    pub user_id: Uuid,
+    pub login_name: String,
+    pub display_name: String,
+    pub post_count: i64,
+    pub reactions_received: i64,
+    pub comments_received: i64,
+    pub score: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CommunityAnalytics {
+    pub period_days: i64,
+    pub daily: Vec<CommunityDailyActivity>,
+    pub top_contributors: Vec<CommunityTopContributor>,
+}
+
 pub async fn get_own_communities(
     tx: &mut Transaction<'_, Postgres>,
     owner_id: Uuid,
 ) -> Result<Vec<Community>> {
     let q = query_as!(
         Community,
-        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color FROM communities WHERE owner_id = $1 AND deleted_at IS NULL"#,
+        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color, is_sensitive FROM communities WHERE owner_id = $1 AND deleted_at IS NULL"#,
         owner_id
     );
 
@@ -136,7 +210,7 @@ pub async fn get_own_communities(
 pub async fn get_communities(tx: &mut Transaction<'_, Postgres>) -> Result<Vec<Community>> {
     let q = query_as!(
         Community,
-        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color FROM communities WHERE deleted_at IS NULL"#
+        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color, is_sensitive FROM communities WHERE deleted_at IS NULL"#
     );
     Ok(q.fetch_all(&mut **tx).await?)
 }
@@ -152,7 +226,7 @@ pub async fn get_public_communities(
             FROM communities
             LEFT JOIN posts ON communities.id = posts.community_id AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
             LEFT JOIN users ON communities.owner_id = users.id
-            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL
+            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL AND communities.archived_at IS NULL
             GROUP BY communities.id, users.login_name
             HAVING MAX(posts.published_at) IS NOT NULL
             ORDER BY MAX(posts.published_at) DESC
@@ -166,8 +240,10 @@ pub async fn get_public_communities_paginated(
     tx: &mut Transaction<'_, Postgres>,
     limit: i64,
     offset: i64,
+    category_id: Option<Uuid>,
 ) -> Result<Vec<PublicCommunity>> {
-    // Select communities ordered by latest published post with pagination
+    // Select communities ordered by latest published post with pagination,
+    // optionally only those assigned to a category
     let q = query_as!(
         PublicCommunity,
         r#"
@@ -175,30 +251,46 @@ pub async fn get_public_communities_paginated(
             FROM communities
             LEFT JOIN posts ON communities.id = posts.community_id AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
             LEFT JOIN users ON communities.owner_id = users.id
-            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL
+            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL AND communities.archived_at IS NULL
+            AND ($3::uuid IS NULL OR EXISTS (
+                SELECT 1 FROM community_category_assignments
+                WHERE community_category_assignments.community_id = communities.id
+                AND community_category_assignments.category_id = $3
+            ))
             GROUP BY communities.id, users.login_name
             HAVING MAX(posts.published_at) IS NOT NULL
             ORDER BY MAX(posts.published_at) DESC
             LIMIT $1 OFFSET $2
         "#,
         limit,
-        offset
+        offset,
+        category_id
     );
 
     Ok(q.fetch_all(&mut **tx).await?)
 }
 
-pub async fn count_public_communities(tx: &mut Transaction<'_, Postgres>) -> Result<i64> {
-    // Count public communities with at least one post
+pub async fn count_public_communities(
+    tx: &mut Transaction<'_, Postgres>,
+    category_id: Option<Uuid>,
+) -> Result<i64> {
+    // Count public communities with at least one post, optionally only those
+    // assigned to a category
     let result = query!(
         r#"
             SELECT COUNT(DISTINCT communities.id) AS "count!"
             FROM communities
             LEFT JOIN posts ON communities.id = posts.community_id AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
-            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL
+            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL AND communities.archived_at IS NULL
+            AND ($1::uuid IS NULL OR EXISTS (
+                SELECT 1 FROM community_category_assignments
+                WHERE community_category_assignments.community_id = communities.id
+                AND community_category_assignments.category_id = $1
+            ))
             GROUP BY communities.id
             HAVING MAX(posts.published_at) IS NOT NULL
-        "#
+        "#,
+        category_id
     )
     .fetch_all(&mut **tx)
     .await?;
@@ -206,47 +298,51 @@ pub async fn count_public_communities(tx: &mut Transaction<'_, Postgres>) -> Res
     Ok(result.len() as i64)
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CommunitySearchResult {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub owner_login_name: String,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub visibility: CommunityVisibility,
+    pub updated_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub posts_count: Option<i64>,
+    pub background_color: Option<String>,
+    pub foreground_color: Option<String>,
+    pub rank: f32,
+}
+
+/// Full-text search over public community names, slugs and descriptions, best matches first.
 pub async fn search_public_communities(
     tx: &mut Transaction<'_, Postgres>,
     query: &str,
     limit: i64,
     offset: i64,
-) -> Result<Vec<PublicCommunity>> {
-    // Prepare search pattern
-    let pattern = format!("%{}%", query);
-    let exact_pattern = query.to_string();
-
-    // Search communities by name, slug, or description with ranking
+) -> Result<Vec<CommunitySearchResult>> {
     let q = query_as!(
-        PublicCommunity,
+        CommunitySearchResult,
         r#"
             SELECT communities.id, communities.owner_id, users.login_name AS owner_login_name,
                    communities.name, communities.slug, communities.description,
                    communities.visibility as "visibility: _", communities.updated_at, communities.created_at,
                    communities.background_color, communities.foreground_color,
-                   COALESCE(COUNT(posts.id), 0) AS posts_count
+                   COALESCE(COUNT(posts.id), 0) AS posts_count,
+                   ts_rank(communities.search_vector, websearch_to_tsquery('simple', $1)) AS "rank!"
             FROM communities
             LEFT JOIN posts ON communities.id = posts.community_id AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
             LEFT JOIN users ON communities.owner_id = users.id
-            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL
-              AND (communities.name ILIKE $1
-                   OR communities.slug ILIKE $1
-                   OR communities.description ILIKE $1)
+            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL AND communities.archived_at IS NULL
+              AND communities.search_vector @@ websearch_to_tsquery('simple', $1)
             GROUP BY communities.id, users.login_name
             ORDER BY
-                CASE
-                    WHEN communities.name ILIKE $2 THEN 0  -- Exact match
-                    WHEN communities.slug ILIKE $2 THEN 1  -- Slug match
-                    WHEN communities.name ILIKE $3 THEN 2  -- Starts with match (name)
-                    WHEN communities.slug ILIKE $3 THEN 3  -- Starts with match (slug)
-                    ELSE 4
-                END,
+                ts_rank(communities.search_vector, websearch_to_tsquery('simple', $1)) DESC,
                 communities.name
-            LIMIT $4 OFFSET $5
+            LIMIT $2 OFFSET $3
         "#,
-        pattern,
-        exact_pattern,
-        format!("{}%", query),
+        query,
         limit,
         offset
     );
@@ -258,18 +354,14 @@ pub async fn count_search_public_communities(
     tx: &mut Transaction<'_, Postgres>,
     query: &str,
 ) -> Result<i64> {
-    let pattern = format!("%{}%", query);
-
     let result = query!(
         r#"
             SELECT COUNT(*) AS "count!"
             FROM communities
-            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL
-              AND (communities.name ILIKE $1
-                   OR communities.slug ILIKE $1
-                   OR communities.description ILIKE $1)
+            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL AND communities.archived_at IS NULL
+              AND communities.search_vector @@ websearch_to_tsquery('simple', $1)
         "#,
-        pattern
+        query
     )
     .fetch_one(&mut **tx)
     .await?;
@@ -288,7 +380,7 @@ pub async fn get_active_public_communities_excluding_owner(
             FROM communities
             LEFT JOIN posts ON communities.id = posts.community_id AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
             LEFT JOIN users ON communities.owner_id = users.id
-            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL AND communities.owner_id != $1
+            WHERE communities.visibility = 'public' AND communities.deleted_at IS NULL AND communities.archived_at IS NULL AND communities.owner_id != $1
             GROUP BY communities.id, users.login_name
             HAVING MAX(posts.published_at) IS NOT NULL
             ORDER BY MAX(posts.published_at) DESC
@@ -481,7 +573,7 @@ pub async fn get_participating_communities(
     let q = query_as!(
         Community,
         r#"
-            SELECT communities.id, communities.owner_id, communities.name, communities.slug, communities.description, communities.visibility as "visibility: _", communities.updated_at, communities.created_at, communities.background_color, communities.foreground_color
+            SELECT communities.id, communities.owner_id, communities.name, communities.slug, communities.description, communities.visibility as "visibility: _", communities.updated_at, communities.created_at, communities.background_color, communities.foreground_color, communities.is_sensitive
             FROM communities
             LEFT JOIN posts ON communities.id = posts.community_id
             WHERE communities.deleted_at IS NULL AND communities.id IN (
@@ -504,7 +596,7 @@ pub async fn find_community_by_id(
 ) -> Result<Option<Community>> {
     let q = query_as!(
         Community,
-        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color FROM communities WHERE id = $1 AND deleted_at IS NULL"#,
+        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color, is_sensitive FROM communities WHERE id = $1 AND deleted_at IS NULL"#,
         id
     );
     Ok(q.fetch_optional(&mut **tx).await?)
@@ -516,12 +608,49 @@ pub async fn find_community_by_slug(
 ) -> Result<Option<Community>> {
     let q = query_as!(
         Community,
-        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color FROM communities WHERE slug = $1 AND deleted_at IS NULL"#,
+        r#"SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at, background_color, foreground_color, is_sensitive FROM communities WHERE slug = $1 AND deleted_at IS NULL"#,
         slug
     );
     Ok(q.fetch_optional(&mut **tx).await?)
 }
 
+/// Whether a community with this ID existed but has been soft-deleted
+pub async fn is_community_deleted(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<bool> {
+    let result = query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM communities WHERE id = $1 AND deleted_at IS NOT NULL
+        ) as "exists!"
+        "#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.exists)
+}
+
+/// Whether the slug belonged to a soft-deleted community and hasn't been taken since
+pub async fn is_community_slug_deleted(
+    tx: &mut Transaction<'_, Postgres>,
+    slug: &str,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM communities WHERE slug = $1 AND deleted_at IS NOT NULL
+        ) AND NOT EXISTS(
+            SELECT 1 FROM communities WHERE slug = $1 AND deleted_at IS NULL
+        ) as "deleted!"
+        "#,
+        slug
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.deleted)
+}
+
 /// Check if a slug conflicts with any existing user login_name
 pub async fn slug_conflicts_with_user(
     tx: &mut Transaction<'_, Postgres>,
@@ -548,9 +677,10 @@ pub async fn create_community(
                 name,
                 slug,
                 description,
-                visibility
+                visibility,
+                is_sensitive
             )
-            VALUES ($1, $2, $3, $4, $5)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING id, created_at, updated_at
         "#,
         owner_id,
@@ -558,6 +688,7 @@ pub async fn create_community(
         community_draft.slug,
         community_draft.description,
         community_draft.visibility as _,
+        community_draft.is_sensitive,
     );
     let result = q.fetch_one(&mut **tx).await?;
 
@@ -584,6 +715,7 @@ pub async fn create_community(
         updated_at: result.updated_at,
         background_color: None,
         foreground_color: None,
+        is_sensitive: community_draft.is_sensitive,
     })
 }
 
@@ -609,7 +741,7 @@ pub async fn update_community(
     let q = query!(
         r#"
             UPDATE communities
-            SET name = $2, slug = $3, description = $4, visibility = $5, updated_at = now()
+            SET name = $2, slug = $3, description = $4, visibility = $5, is_sensitive = $6, updated_at = now()
             WHERE id = $1
             RETURNING owner_id, created_at
         "#,
@@ -618,6 +750,7 @@ pub async fn update_community(
         community_draft.slug,
         community_draft.description,
         community_draft.visibility as _,
+        community_draft.is_sensitive,
     );
     let result = q.fetch_one(&mut **tx).await?;
 
@@ -648,6 +781,7 @@ pub async fn update_community(
         updated_at: Utc::now(),
         background_color: None,
         foreground_color: None,
+        is_sensitive: community_draft.is_sensitive,
     })
 }
 
@@ -690,7 +824,7 @@ pub async fn get_communities_for_collaboration(
         Community,
         r#"
         SELECT id, owner_id, name, slug, description, visibility as "visibility: _", updated_at, created_at,
-               background_color, foreground_color
+               background_color, foreground_color, is_sensitive
         FROM communities
         WHERE visibility IN ('public', 'unlisted') OR id IN (
             SELECT community_id FROM community_members WHERE user_id = $1
@@ -731,6 +865,194 @@ pub async fn get_community_stats(
     })
 }
 
+/// Posting activity over the last `period_days` UTC days, today included.
+/// Days without posts are reported with zero counts.
+pub async fn get_community_analytics(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    period_days: i64,
+) -> Result<CommunityAnalytics> {
+    let since = (Utc::now() - Duration::days(period_days - 1))
+        .date_naive()
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+
+    let daily = sqlx::query!(
+        r#"
+        WITH community_posts AS (
+            SELECT
+                date_trunc('day', published_at AT TIME ZONE 'UTC') AS day,
+                author_id
+            FROM posts
+            WHERE community_id = $1
+              AND published_at IS NOT NULL
+              AND deleted_at IS NULL
+        ),
+        days AS (
+            SELECT generate_series(
+                $2::timestamptz AT TIME ZONE 'UTC',
+                date_trunc('day', NOW() AT TIME ZONE 'UTC'),
+                INTERVAL '1 day'
+            ) AS day
+        ),
+        daily AS (
+            SELECT
+                days.day,
+                COUNT(community_posts.author_id) AS post_count,
+                COUNT(DISTINCT community_posts.author_id) AS contributor_count
+            FROM days
+            LEFT JOIN community_posts ON community_posts.day = days.day
+            GROUP BY days.day
+        ),
+        earlier AS (
+            SELECT COUNT(*) AS post_count
+            FROM community_posts
+            WHERE day < $2::timestamptz AT TIME ZONE 'UTC'
+        )
+        SELECT
+            daily.day::date AS "day!",
+            daily.post_count AS "post_count!",
+            daily.contributor_count AS "contributor_count!",
+            (earlier.post_count + SUM(daily.post_count) OVER (ORDER BY daily.day))::bigint
+                AS "cumulative_post_count!"
+        FROM daily
+        CROSS JOIN earlier
+        ORDER BY daily.day
+        "#,
+        community_id,
+        since
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| CommunityDailyActivity {
+        day: row.day,
+        post_count: row.post_count,
+        contributor_count: row.contributor_count,
+        cumulative_post_count: row.cumulative_post_count,
+    })
+    .collect();
+
+    let top_contributors = query_as!(
+        CommunityTopContributor,
+        r#"
+        SELECT
+            users.id AS user_id,
+            users.login_name,
+            users.display_name,
+            COUNT(*) AS "post_count!"
+        FROM posts
+        JOIN users ON posts.author_id = users.id
+        WHERE posts.community_id = $1
+          AND posts.published_at >= $2
+          AND posts.deleted_at IS NULL
+        GROUP BY users.id
+        ORDER BY COUNT(*) DESC, users.login_name ASC
+        LIMIT 10
+        "#,
+        community_id,
+        since
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(CommunityAnalytics {
+        period_days,
+        daily,
+        top_contributors,
+    })
+}
+
+/// Everyone who posted to a community or had their posts there reacted to or
+/// commented on since `since` (ever, if `None`), ranked by
+/// posts × 1 + reactions received × 2 + comments received × 1.5.
+/// Reactions and comments on one's own posts don't count.
+pub async fn get_community_leaderboard(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<CommunityLeaderboardEntry>> {
+    let entries = query_as!(
+        CommunityLeaderboardEntry,
+        r#"
+        WITH community_posts AS (
+            SELECT id, author_id, published_at
+            FROM posts
+            WHERE community_id = $1
+              AND published_at IS NOT NULL
+              AND deleted_at IS NULL
+        ),
+        post_counts AS (
+            SELECT author_id, COUNT(*) AS post_count
+            FROM community_posts
+            WHERE $2::timestamptz IS NULL OR published_at >= $2
+            GROUP BY author_id
+        ),
+        reaction_counts AS (
+            SELECT community_posts.author_id, COUNT(*) AS reactions_received
+            FROM reactions
+            JOIN community_posts ON reactions.post_id = community_posts.id
+            JOIN actors ON reactions.actor_id = actors.id
+            WHERE ($2::timestamptz IS NULL OR reactions.created_at >= $2)
+              AND actors.user_id IS DISTINCT FROM community_posts.author_id
+            GROUP BY community_posts.author_id
+        ),
+        comment_counts AS (
+            SELECT community_posts.author_id, COUNT(*) AS comments_received
+            FROM comments
+            JOIN community_posts ON comments.post_id = community_posts.id
+            JOIN actors ON comments.actor_id = actors.id
+            WHERE comments.deleted_at IS NULL
+              AND ($2::timestamptz IS NULL OR comments.created_at >= $2)
+              AND actors.user_id IS DISTINCT FROM community_posts.author_id
+            GROUP BY community_posts.author_id
+        ),
+        contributors AS (
+            SELECT author_id FROM post_counts
+            UNION SELECT author_id FROM reaction_counts
+            UNION SELECT author_id FROM comment_counts
+        ),
+        scored AS (
+            SELECT
+                users.id AS user_id,
+                users.login_name,
+                users.display_name,
+                COALESCE(post_counts.post_count, 0) AS post_count,
+                COALESCE(reaction_counts.reactions_received, 0) AS reactions_received,
+                COALESCE(comment_counts.comments_received, 0) AS comments_received,
+                (
+                    COALESCE(post_counts.post_count, 0)
+                    + COALESCE(reaction_counts.reactions_received, 0) * 2
+                    + COALESCE(comment_counts.comments_received, 0) * 1.5
+                )::float8 AS score
+            FROM contributors
+            JOIN users ON contributors.author_id = users.id
+            LEFT JOIN post_counts ON contributors.author_id = post_counts.author_id
+            LEFT JOIN reaction_counts ON contributors.author_id = reaction_counts.author_id
+            LEFT JOIN comment_counts ON contributors.author_id = comment_counts.author_id
+            WHERE users.deleted_at IS NULL
+        )
+        SELECT
+            RANK() OVER (ORDER BY score DESC) AS "rank!",
+            user_id AS "user_id!",
+            login_name AS "login_name!",
+            display_name AS "display_name!",
+            post_count AS "post_count!",
+            reactions_received AS "reactions_received!",
+            comments_received AS "comments_received!",
+            score AS "score!"
+        FROM scored
+        ORDER BY score DESC, login_name ASC
+        "#,
+        community_id,
+        since
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(entries)
+}
+
 /// Struct for community member stats
 pub struct CommunityMembersCount {
     pub community_id: Uuid,
@@ -848,6 +1170,7 @@ pub struct CommunityMemberWithDetails {
     pub user_id: Uuid,
     pub login_name: String,
     pub display_name: String,
+    pub avatar_filename: Option<String>,
     pub role: CommunityMemberRole,
     pub joined_at: DateTime<Utc>,
 }
@@ -864,10 +1187,12 @@ pub async fn get_community_members_with_details(
             cm.user_id,
             u.login_name,
             u.display_name,
+            avatar.image_filename as "avatar_filename?",
             cm.role as "role: CommunityMemberRole",
             cm.joined_at
         FROM community_members cm
         JOIN users u ON cm.user_id = u.id
+        LEFT JOIN images avatar ON u.avatar_image_id = avatar.id
         WHERE cm.community_id = $1
         ORDER BY
             CASE cm.role
@@ -889,6 +1214,7 @@ pub async fn get_community_members_with_details(
             user_id: row.user_id,
             login_name: row.login_name,
             display_name: row.display_name,
+            avatar_filename: row.avatar_filename,
             role: row.role,
             joined_at: row.joined_at,
         })
@@ -938,6 +1264,35 @@ pub async fn remove_community_member(
     Ok(())
 }
 
+/// Change a member's role between moderator and member.
+/// The owner's role never changes here; ownership moves through a transfer.
+/// Returns false if the user is not a non-owner member of the community.
+pub async fn set_member_role(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    user_id: Uuid,
+    role: CommunityMemberRole,
+) -> Result<bool> {
+    if role == CommunityMemberRole::Owner {
+        bail!("Ownership can only be changed through an ownership transfer");
+    }
+
+    let result = query!(
+        r#"
+        UPDATE community_members
+        SET role = $3
+        WHERE community_id = $1 AND user_id = $2 AND role != 'owner'
+        "#,
+        community_id,
+        user_id,
+        role as _
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 /// Leave a community (user voluntarily leaves)
 /// Returns an error if the user is the owner (owners cannot leave)
 pub async fn leave_community(
@@ -991,6 +1346,8 @@ pub async fn create_invitation(
     community_id: Uuid,
     inviter_id: Uuid,
     invitee_id: Uuid,
+    role: CommunityMemberRole,
+    expires_at: DateTime<Utc>,
 ) -> Result<CommunityInvitation> {
     // Delete any existing invitations for this user to this community
     // This allows re-inviting users who previously accepted/rejected or left the community
@@ -1005,13 +1362,15 @@ pub async fn create_invitation(
     let invitation = query_as!(
         CommunityInvitation,
         r#"
-        INSERT INTO community_invitations (community_id, inviter_id, invitee_id)
-        VALUES ($1, $2, $3)
-        RETURNING id, community_id, inviter_id, invitee_id, status as "status: _", created_at, updated_at
+        INSERT INTO community_invitations (community_id, inviter_id, invitee_id, role, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, community_id, inviter_id, invitee_id, role as "role: _", status as "status: _", created_at, updated_at, expires_at
         "#,
         community_id,
         inviter_id,
-        invitee_id
+        invitee_id,
+        role as _,
+        expires_at
     )
     .fetch_one(&mut **tx)
     .await?;
@@ -1019,6 +1378,28 @@ pub async fn create_invitation(
     Ok(invitation)
 }
 
+pub async fn has_pending_invitation(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    invitee_id: Uuid,
+) -> Result<bool> {
+    let exists = query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM community_invitations
+            WHERE community_id = $1 AND invitee_id = $2 AND status = 'pending'
+            AND expires_at > now()
+        ) as "exists!"
+        "#,
+        community_id,
+        invitee_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(exists)
+}
+
 /// Get pending invitations for a user
 pub async fn get_pending_invitations_for_user(
     tx: &mut Transaction<'_, Postgres>,
@@ -1027,9 +1408,9 @@ pub async fn get_pending_invitations_for_user(
     let invitations = query_as!(
         CommunityInvitation,
         r#"
-        SELECT id, community_id, inviter_id, invitee_id, status as "status: _", created_at, updated_at
+        SELECT id, community_id, inviter_id, invitee_id, role as "role: _", status as "status: _", created_at, updated_at, expires_at
         FROM community_invitations
-        WHERE invitee_id = $1 AND status = 'pending'
+        WHERE invitee_id = $1 AND status = 'pending' AND expires_at > now()
         ORDER BY created_at DESC
         "#,
         user_id
@@ -1050,7 +1431,9 @@ pub struct InvitationWithDetails {
     pub inviter_id: Uuid,
     pub inviter_login_name: String,
     pub inviter_display_name: String,
+    pub inviter_avatar_filename: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 /// Get pending invitations for a user with all details in a single query
@@ -1068,11 +1451,14 @@ pub async fn get_pending_invitations_with_details_for_user(
             ci.inviter_id,
             u.login_name as inviter_login_name,
             u.display_name as inviter_display_name,
-            ci.created_at
+            avatar.image_filename as "inviter_avatar_filename?",
+            ci.created_at,
+            ci.expires_at
         FROM community_invitations ci
         JOIN communities c ON ci.community_id = c.id
         JOIN users u ON ci.inviter_id = u.id
-        WHERE ci.invitee_id = $1 AND ci.status = 'pending'
+        LEFT JOIN images avatar ON u.avatar_image_id = avatar.id
+        WHERE ci.invitee_id = $1 AND ci.status = 'pending' AND ci.expires_at > now()
         ORDER BY ci.created_at DESC
         "#,
         user_id
@@ -1090,7 +1476,9 @@ pub async fn get_pending_invitations_with_details_for_user(
             inviter_id: row.inviter_id,
             inviter_login_name: row.inviter_login_name,
             inviter_display_name: row.inviter_display_name,
+            inviter_avatar_filename: row.inviter_avatar_filename,
             created_at: row.created_at,
+            expires_at: row.expires_at,
         })
         .collect())
 }
@@ -1103,9 +1491,9 @@ pub async fn get_pending_invitations_for_community(
     let invitations = query_as!(
         CommunityInvitation,
         r#"
-        SELECT id, community_id, inviter_id, invitee_id, status as "status: _", created_at, updated_at
+        SELECT id, community_id, inviter_id, invitee_id, role as "role: _", status as "status: _", created_at, updated_at, expires_at
         FROM community_invitations
-        WHERE community_id = $1 AND status = 'pending'
+        WHERE community_id = $1 AND status = 'pending' AND expires_at > now()
         ORDER BY created_at DESC
         "#,
         community_id
@@ -1123,7 +1511,9 @@ pub struct CommunityInvitationWithInviteeDetails {
     pub invitee_id: Uuid,
     pub invitee_login_name: String,
     pub invitee_display_name: String,
+    pub invitee_avatar_filename: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 /// Get pending invitations for a community with invitee details in a single query
@@ -1138,10 +1528,13 @@ pub async fn get_pending_invitations_with_invitee_details_for_community(
             ci.invitee_id,
             u.login_name as invitee_login_name,
             u.display_name as invitee_display_name,
-            ci.created_at
+            avatar.image_filename as "invitee_avatar_filename?",
+            ci.created_at,
+            ci.expires_at
         FROM community_invitations ci
         JOIN users u ON ci.invitee_id = u.id
-        WHERE ci.community_id = $1 AND ci.status = 'pending'
+        LEFT JOIN images avatar ON u.avatar_image_id = avatar.id
+        WHERE ci.community_id = $1 AND ci.status = 'pending' AND ci.expires_at > now()
         ORDER BY ci.created_at DESC
         "#,
         community_id
@@ -1156,12 +1549,14 @@ pub async fn get_pending_invitations_with_invitee_details_for_community(
             invitee_id: row.invitee_id,
             invitee_login_name: row.invitee_login_name,
             invitee_display_name: row.invitee_display_name,
+            invitee_avatar_filename: row.invitee_avatar_filename,
             created_at: row.created_at,
+            expires_at: row.expires_at,
         })
         .collect())
 }
 
-/// Get invitation by ID
+/// Get invitation by ID. Pending invitations past their expiry count as gone.
 pub async fn get_invitation_by_id(
     tx: &mut Transaction<'_, Postgres>,
     invitation_id: Uuid,
@@ -1169,9 +1564,9 @@ pub async fn get_invitation_by_id(
     let invitation = query_as!(
         CommunityInvitation,
         r#"
-        SELECT id, community_id, inviter_id, invitee_id, status as "status: _", created_at, updated_at
+        SELECT id, community_id, inviter_id, invitee_id, role as "role: _", status as "status: _", created_at, updated_at, expires_at
         FROM community_invitations
-        WHERE id = $1
+        WHERE id = $1 AND (status != 'pending' OR expires_at > now())
         "#,
         invitation_id
     )
@@ -1181,23 +1576,23 @@ pub async fn get_invitation_by_id(
     Ok(invitation)
 }
 
-/// Accept an invitation
+/// Accept an invitation. Returns false if it was no longer pending or has expired.
 pub async fn accept_invitation(
     tx: &mut Transaction<'_, Postgres>,
     invitation_id: Uuid,
-) -> Result<()> {
-    query!(
+) -> Result<bool> {
+    let result = query!(
         r#"
         UPDATE community_invitations
         SET status = 'accepted', updated_at = now()
-        WHERE id = $1 AND status = 'pending'
+        WHERE id = $1 AND status = 'pending' AND expires_at > now()
         "#,
         invitation_id
     )
     .execute(&mut **tx)
     .await?;
 
-    Ok(())
+    Ok(result.rows_affected() > 0)
 }
 
 /// Reject an invitation
@@ -1219,87 +1614,794 @@ pub async fn reject_invitation(
     Ok(())
 }
 
-/// Soft delete a community and all its posts
-pub async fn soft_delete_community(
-    tx: &mut Transaction<'_, Postgres>,
-    slug: &str,
-    user_id: Uuid,
-) -> Result<()> {
-    // First, verify the community exists and user is the owner
-    let community = query!(
-        r#"
-        SELECT id, owner_id, deleted_at
-        FROM communities
-        WHERE slug = $1
-        "#,
-        slug
+/// Delete pending invitations that expired without an answer, returning how many went
+pub async fn delete_expired_invitations(tx: &mut Transaction<'_, Postgres>) -> Result<u64> {
+    let result = query!(
+        "DELETE FROM community_invitations WHERE status = 'pending' AND expires_at <= now()"
     )
-    .fetch_optional(&mut **tx)
+    .execute(&mut **tx)
     .await?;
 
-    let community = community.ok_or_else(|| anyhow::anyhow!("Community not found"))?;
+    Ok(result.rows_affected())
+}
 
-    // Check if already deleted
-    if community.deleted_at.is_some() {
-        return Err(anyhow::anyhow!("Community already deleted"));
-    }
+// ========== Community Join Request Functions ==========
 
-    // Verify ownership
-    if community.owner_id != user_id {
-        return Err(anyhow::anyhow!("Only the owner can delete this community"));
-    }
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[sqlx(type_name = "join_request_status", rename_all = "snake_case")]
+pub enum JoinRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
 
-    // Soft delete the community
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JoinRequest {
+    pub id: Uuid,
+    pub community_id: Uuid,
+    pub requester_id: Uuid,
+    pub status: JoinRequestStatus,
+    pub reviewed_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Whether the community lets non-members ask to join
+pub async fn community_accepts_join_requests(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<bool> {
+    let accepts = query_scalar!(
+        "SELECT accepts_join_requests FROM communities WHERE id = $1",
+        community_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(accepts.unwrap_or(false))
+}
+
+pub async fn set_community_accepts_join_requests(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    accepts_join_requests: bool,
+) -> Result<()> {
     query!(
-        r#"
-        UPDATE communities
-        SET deleted_at = now()
-        WHERE id = $1
-        "#,
-        community.id
+        "UPDATE communities SET accepts_join_requests = $2, updated_at = now() WHERE id = $1",
+        community_id,
+        accepts_join_requests
     )
     .execute(&mut **tx)
     .await?;
 
-    // Soft delete all posts in the community with cascade reason
-    crate::models::post::soft_delete_community_posts(tx, community.id).await?;
-
     Ok(())
 }
 
-pub async fn soft_delete_community_with_activity(
+/// Ask to join a community. Asking again while a request is pending keeps the existing one.
+pub async fn create_join_request(
     tx: &mut Transaction<'_, Postgres>,
-    slug: &str,
-    user_id: Uuid,
-    _config: &crate::config::AppConfig,
-    state: Option<&crate::web::state::AppState>,
-) -> Result<()> {
-    // First, get the community to find its ID
-    let community = query!(
+    community_id: Uuid,
+    requester_id: Uuid,
+) -> Result<JoinRequest> {
+    let join_request = query_as!(
+        JoinRequest,
         r#"
-        SELECT id
-        FROM communities
-        WHERE slug = $1
+        INSERT INTO join_requests (community_id, requester_id)
+        VALUES ($1, $2)
+        ON CONFLICT (community_id, requester_id) WHERE status = 'pending'
+        DO UPDATE SET updated_at = now()
+        RETURNING id, community_id, requester_id, status as "status: _", reviewed_by, created_at, updated_at
         "#,
-        slug
+        community_id,
+        requester_id
     )
-    .fetch_optional(&mut **tx)
+    .fetch_one(&mut **tx)
     .await?;
 
-    let community_id = community
-        .ok_or_else(|| anyhow::anyhow!("Community not found"))?
-        .id;
+    Ok(join_request)
+}
 
-    // Get the community's actor before deletion
-    let actor = super::actor::Actor::find_by_community_id(tx, community_id).await?;
+pub async fn find_join_request_by_id(
+    tx: &mut Transaction<'_, Postgres>,
+    join_request_id: Uuid,
+) -> Result<Option<JoinRequest>> {
+    let join_request = query_as!(
+        JoinRequest,
+        r#"
+        SELECT id, community_id, requester_id, status as "status: _", reviewed_by, created_at, updated_at
+        FROM join_requests
+        WHERE id = $1
+        "#,
+        join_request_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
 
-    // Soft delete the community
-    soft_delete_community(tx, slug, user_id).await?;
+    Ok(join_request)
+}
 
-    // If state is provided and actor exists, send ActivityPub Delete activity
-    if let (Some(state), Some(actor)) = (state, actor) {
-        // Use the actor's IRI as the object URL
-        if let Ok(actor_url) = actor.iri.parse() {
+pub async fn has_pending_join_request(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    requester_id: Uuid,
+) -> Result<bool> {
+    let exists = query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM join_requests
+            WHERE community_id = $1 AND requester_id = $2 AND status = 'pending'
+        ) as "exists!"
+        "#,
+        community_id,
+        requester_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(exists)
+}
+
+/// Approve a pending request and add the requester as a member.
+/// Returns `None` if the request was already reviewed.
+pub async fn approve_join_request(
+    tx: &mut Transaction<'_, Postgres>,
+    join_request_id: Uuid,
+    reviewer_id: Uuid,
+) -> Result<Option<JoinRequest>> {
+    let join_request = query_as!(
+        JoinRequest,
+        r#"
+        UPDATE join_requests
+        SET status = 'approved', reviewed_by = $2, updated_at = now()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id, community_id, requester_id, status as "status: _", reviewed_by, created_at, updated_at
+        "#,
+        join_request_id,
+        reviewer_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if let Some(join_request) = &join_request {
+        // The requester may have joined through an invitation in the meantime
+        if !is_user_member(tx, join_request.requester_id, join_request.community_id).await? {
+            add_community_member(
+                tx,
+                join_request.community_id,
+                join_request.requester_id,
+                CommunityMemberRole::Member,
+                Some(reviewer_id),
+            )
+            .await?;
+        }
+    }
+
+    Ok(join_request)
+}
+
+/// Reject a pending request. Returns `None` if the request was already reviewed.
+pub async fn reject_join_request(
+    tx: &mut Transaction<'_, Postgres>,
+    join_request_id: Uuid,
+    reviewer_id: Uuid,
+) -> Result<Option<JoinRequest>> {
+    let join_request = query_as!(
+        JoinRequest,
+        r#"
+        UPDATE join_requests
+        SET status = 'rejected', reviewed_by = $2, updated_at = now()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id, community_id, requester_id, status as "status: _", reviewed_by, created_at, updated_at
+        "#,
+        join_request_id,
+        reviewer_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(join_request)
+}
+
+/// Struct for join requests with requester details (no N+1 query)
+#[derive(Debug)]
+pub struct JoinRequestWithRequesterDetails {
+    pub id: Uuid,
+    pub requester_id: Uuid,
+    pub requester_login_name: String,
+    pub requester_display_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Get pending join requests for a community with requester details in a single query
+pub async fn get_pending_join_requests_with_requester_details_for_community(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<Vec<JoinRequestWithRequesterDetails>> {
+    let join_requests = query!(
+        r#"
+        SELECT
+            jr.id,
+            jr.requester_id,
+            u.login_name as requester_login_name,
+            u.display_name as requester_display_name,
+            jr.created_at
+        FROM join_requests jr
+        JOIN users u ON jr.requester_id = u.id
+        WHERE jr.community_id = $1 AND jr.status = 'pending'
+        ORDER BY jr.created_at ASC
+        "#,
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(join_requests
+        .into_iter()
+        .map(|row| JoinRequestWithRequesterDetails {
+            id: row.id,
+            requester_id: row.requester_id,
+            requester_login_name: row.requester_login_name,
+            requester_display_name: row.requester_display_name,
+            created_at: row.created_at,
+        })
+        .collect())
+}
+
+// ========== Post Approval Functions ==========
+
+pub async fn community_requires_post_approval(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<bool> {
+    let requires = query_scalar!(
+        "SELECT require_post_approval FROM communities WHERE id = $1",
+        community_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(requires.unwrap_or(false))
+}
+
+pub async fn set_community_requires_post_approval(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    require_post_approval: bool,
+) -> Result<()> {
+    query!(
+        "UPDATE communities SET require_post_approval = $2, updated_at = now() WHERE id = $1",
+        community_id,
+        require_post_approval
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Whether a post by `author_id` has to be reviewed before it appears in the
+/// community. Owners and moderators publish directly.
+pub async fn post_needs_approval(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    author_id: Uuid,
+) -> Result<bool> {
+    let needs_approval = query_scalar!(
+        r#"
+        SELECT (
+            c.require_post_approval
+            AND NOT EXISTS (
+                SELECT 1 FROM community_members m
+                WHERE m.community_id = c.id
+                    AND m.user_id = $2
+                    AND m.role IN ('owner', 'moderator')
+            )
+        ) AS "needs_approval!"
+        FROM communities c
+        WHERE c.id = $1
+        "#,
+        community_id,
+        author_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(needs_approval.unwrap_or(false))
+}
+
+// ========== Community Invite Link Functions ==========
+
+/// Shareable link that lets anyone holding it join a community
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommunityInviteLink {
+    pub id: Uuid,
+    pub community_id: Uuid,
+    pub created_by: Uuid,
+    /// Unlimited when absent
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    /// Never expires when absent
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CommunityInviteLink {
+    /// Whether the link has neither expired nor run out of uses
+    pub fn is_usable(&self) -> bool {
+        let exhausted = matches!(self.max_uses, Some(max_uses) if self.use_count >= max_uses);
+        let expired = matches!(self.expires_at, Some(expires_at) if expires_at <= Utc::now());
+        !exhausted && !expired
+    }
+}
+
+pub async fn create_invite_link(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    created_by: Uuid,
+    max_uses: Option<i32>,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<CommunityInviteLink> {
+    let link = query_as!(
+        CommunityInviteLink,
+        r#"
+        INSERT INTO community_invite_links (community_id, created_by, max_uses, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, community_id, created_by, max_uses, use_count, expires_at, created_at
+        "#,
+        community_id,
+        created_by,
+        max_uses,
+        expires_at
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(link)
+}
+
+pub async fn find_invite_link_by_id(
+    tx: &mut Transaction<'_, Postgres>,
+    link_id: Uuid,
+) -> Result<Option<CommunityInviteLink>> {
+    let link = query_as!(
+        CommunityInviteLink,
+        r#"
+        SELECT id, community_id, created_by, max_uses, use_count, expires_at, created_at
+        FROM community_invite_links
+        WHERE id = $1
+        "#,
+        link_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(link)
+}
+
+/// Invite links of a community, newest first
+pub async fn get_invite_links_for_community(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<Vec<CommunityInviteLink>> {
+    let links = query_as!(
+        CommunityInviteLink,
+        r#"
+        SELECT id, community_id, created_by, max_uses, use_count, expires_at, created_at
+        FROM community_invite_links
+        WHERE community_id = $1
+        ORDER BY created_at DESC
+        "#,
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(links)
+}
+
+/// Count one use of the link. Returns false if the link has expired or run
+/// out of uses in the meantime.
+pub async fn use_invite_link(tx: &mut Transaction<'_, Postgres>, link_id: Uuid) -> Result<bool> {
+    let result = query!(
+        r#"
+        UPDATE community_invite_links
+        SET use_count = use_count + 1
+        WHERE id = $1
+          AND (max_uses IS NULL OR use_count < max_uses)
+          AND (expires_at IS NULL OR expires_at > now())
+        "#,
+        link_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns false if the community has no such link
+pub async fn delete_invite_link(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    link_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        DELETE FROM community_invite_links
+        WHERE id = $1 AND community_id = $2
+        "#,
+        link_id,
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// ========== Default Co-moderator Functions ==========
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DefaultCoModerator {
+    pub id: Uuid,
+    pub login_name: String,
+    pub display_name: String,
+}
+
+/// Users who are made moderators of every community the given user creates
+pub async fn find_default_co_moderators(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<Vec<DefaultCoModerator>> {
+    let co_moderators = query_as!(
+        DefaultCoModerator,
+        r#"
+        SELECT users.id, users.login_name, users.display_name
+        FROM user_default_co_moderators
+        JOIN users ON user_default_co_moderators.moderator_id = users.id
+        WHERE user_default_co_moderators.user_id = $1
+          AND users.deleted_at IS NULL
+        ORDER BY users.login_name
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(co_moderators)
+}
+
+/// Replace the user's default co-moderator list
+pub async fn set_default_co_moderators(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    moderator_ids: &[Uuid],
+) -> Result<()> {
+    query!(
+        "DELETE FROM user_default_co_moderators WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    query!(
+        r#"
+        INSERT INTO user_default_co_moderators (user_id, moderator_id)
+        SELECT $1, moderator_id FROM UNNEST($2::uuid[]) AS moderator_id
+        WHERE moderator_id <> $1
+        ON CONFLICT DO NOTHING
+        "#,
+        user_id,
+        moderator_ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Bring the owner's default co-moderators into a newly created community.
+/// Private communities get them as moderators right away; other communities
+/// receive a moderator invitation that takes effect when accepted.
+pub async fn apply_default_co_moderators(
+    tx: &mut Transaction<'_, Postgres>,
+    community: &Community,
+    invitation_expires_at: DateTime<Utc>,
+) -> Result<Vec<DefaultCoModerator>> {
+    let co_moderators = find_default_co_moderators(tx, community.owner_id).await?;
+
+    for co_moderator in &co_moderators {
+        if community.visibility == CommunityVisibility::Private {
+            add_community_member(
+                tx,
+                community.id,
+                co_moderator.id,
+                CommunityMemberRole::Moderator,
+                Some(community.owner_id),
+            )
+            .await?;
+        } else {
+            create_invitation(
+                tx,
+                community.id,
+                community.owner_id,
+                co_moderator.id,
+                CommunityMemberRole::Moderator,
+                invitation_expires_at,
+            )
+            .await?;
+        }
+    }
+
+    Ok(co_moderators)
+}
+
+// ========== Community Ownership Transfer Functions ==========
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[sqlx(
+    type_name = "community_ownership_transfer_status",
+    rename_all = "snake_case"
+)]
+pub enum CommunityOwnershipTransferStatus {
+    Pending,
+    Confirmed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommunityOwnershipTransfer {
+    pub id: Uuid,
+    pub community_id: Uuid,
+    pub current_owner_id: Uuid,
+    pub proposed_owner_id: Uuid,
+    pub token: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub status: CommunityOwnershipTransferStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Propose handing a community over to another user, replacing any earlier proposal
+pub async fn create_ownership_transfer(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    current_owner_id: Uuid,
+    proposed_owner_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<CommunityOwnershipTransfer> {
+    query!(
+        r#"
+        UPDATE community_ownership_transfers
+        SET status = 'cancelled', updated_at = now()
+        WHERE community_id = $1 AND status = 'pending'
+        "#,
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let transfer = query_as!(
+        CommunityOwnershipTransfer,
+        r#"
+        INSERT INTO community_ownership_transfers
+            (community_id, current_owner_id, proposed_owner_id, token, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, community_id, current_owner_id, proposed_owner_id, token, expires_at,
+                  status as "status: _", created_at, updated_at
+        "#,
+        community_id,
+        current_owner_id,
+        proposed_owner_id,
+        Uuid::new_v4(),
+        expires_at
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(transfer)
+}
+
+/// Find the community's pending, unexpired transfer
+pub async fn find_pending_ownership_transfer(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<Option<CommunityOwnershipTransfer>> {
+    let transfer = query_as!(
+        CommunityOwnershipTransfer,
+        r#"
+        SELECT id, community_id, current_owner_id, proposed_owner_id, token, expires_at,
+               status as "status: _", created_at, updated_at
+        FROM community_ownership_transfers
+        WHERE community_id = $1 AND status = 'pending' AND expires_at > now()
+        "#,
+        community_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(transfer)
+}
+
+pub async fn has_pending_ownership_transfer(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<bool> {
+    Ok(find_pending_ownership_transfer(tx, community_id)
+        .await?
+        .is_some())
+}
+
+pub async fn cancel_ownership_transfer(
+    tx: &mut Transaction<'_, Postgres>,
+    transfer_id: Uuid,
+) -> Result<()> {
+    query!(
+        r#"
+        UPDATE community_ownership_transfers
+        SET status = 'cancelled', updated_at = now()
+        WHERE id = $1 AND status = 'pending'
+        "#,
+        transfer_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Complete a transfer: the proposed user becomes owner and the previous owner stays on as a moderator
+pub async fn confirm_ownership_transfer(
+    tx: &mut Transaction<'_, Postgres>,
+    transfer: &CommunityOwnershipTransfer,
+) -> Result<()> {
+    let updated = query!(
+        r#"
+        UPDATE community_ownership_transfers
+        SET status = 'confirmed', updated_at = now()
+        WHERE id = $1 AND status = 'pending' AND expires_at > now()
+        "#,
+        transfer.id
+    )
+    .execute(&mut **tx)
+    .await?;
+    if updated.rows_affected() == 0 {
+        bail!("Ownership transfer is no longer pending");
+    }
+
+    // Ownership may have changed hands while the transfer was pending, in
+    // which case the person who proposed it can no longer give it away
+    let updated = query!(
+        r#"
+        UPDATE communities
+        SET owner_id = $2, updated_at = now()
+        WHERE id = $1 AND owner_id = $3
+        "#,
+        transfer.community_id,
+        transfer.proposed_owner_id,
+        transfer.current_owner_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    if updated.rows_affected() == 0 {
+        bail!("Community is no longer owned by the user who proposed the transfer");
+    }
+
+    if is_user_member(tx, transfer.proposed_owner_id, transfer.community_id).await? {
+        update_member_role(
+            tx,
+            transfer.community_id,
+            transfer.proposed_owner_id,
+            CommunityMemberRole::Owner,
+        )
+        .await?;
+    } else {
+        add_community_member(
+            tx,
+            transfer.community_id,
+            transfer.proposed_owner_id,
+            CommunityMemberRole::Owner,
+            Some(transfer.current_owner_id),
+        )
+        .await?;
+    }
+    update_member_role(
+        tx,
+        transfer.community_id,
+        transfer.current_owner_id,
+        CommunityMemberRole::Moderator,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Soft delete a community and all its posts
+pub async fn soft_delete_community(
+    tx: &mut Transaction<'_, Postgres>,
+    slug: &str,
+    user_id: Uuid,
+) -> Result<()> {
+    // First, verify the community exists and user is the owner
+    let community = query!(
+        r#"
+        SELECT id, owner_id, deleted_at
+        FROM communities
+        WHERE slug = $1
+        "#,
+        slug
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let community = community.ok_or_else(|| anyhow::anyhow!("Community not found"))?;
+
+    // Check if already deleted
+    if community.deleted_at.is_some() {
+        return Err(anyhow::anyhow!("Community already deleted"));
+    }
+
+    // Verify ownership
+    if community.owner_id != user_id {
+        return Err(anyhow::anyhow!("Only the owner can delete this community"));
+    }
+
+    if has_pending_ownership_transfer(tx, community.id).await? {
+        return Err(anyhow::anyhow!(
+            "Cannot delete a community while an ownership transfer is pending"
+        ));
+    }
+
+    // Soft delete the community
+    query!(
+        r#"
+        UPDATE communities
+        SET deleted_at = now()
+        WHERE id = $1
+        "#,
+        community.id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Soft delete all posts in the community with cascade reason
+    crate::models::post::soft_delete_community_posts(tx, community.id).await?;
+
+    Ok(())
+}
+
+pub async fn soft_delete_community_with_activity(
+    tx: &mut Transaction<'_, Postgres>,
+    slug: &str,
+    user_id: Uuid,
+    _config: &crate::config::AppConfig,
+    state: Option<&crate::web::state::AppState>,
+) -> Result<()> {
+    // First, get the community to find its ID
+    let community = query!(
+        r#"
+        SELECT id
+        FROM communities
+        WHERE slug = $1
+        "#,
+        slug
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let community_id = community
+        .ok_or_else(|| anyhow::anyhow!("Community not found"))?
+        .id;
+
+    // Get the community's actor before deletion
+    let actor = super::actor::Actor::find_by_community_id(tx, community_id).await?;
+
+    // Soft delete the community
+    soft_delete_community(tx, slug, user_id).await?;
+
+    // If state is provided and actor exists, send ActivityPub Delete activity
+    if let (Some(state), Some(actor)) = (state, actor) {
+        // Use the actor's IRI as the object URL
+        if let Ok(actor_url) = actor.iri.parse() {
             // Send Delete activity - don't fail if this fails
             if let Err(e) =
                 crate::web::handlers::activitypub::send_delete_activity(&actor, actor_url, state)
@@ -1316,3 +2418,669 @@ pub async fn soft_delete_community_with_activity(
 
     Ok(())
 }
+
+// ========== Pinned Post Functions ==========
+
+/// Maximum number of posts a community can pin at once
+pub const MAX_PINNED_POSTS: i32 = 5;
+
+/// Pin a post to a community
+/// Uses the requested slot if given, otherwise the lowest free slot
+pub async fn pin_post(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    post_id: Uuid,
+    position: Option<i32>,
+) -> Result<()> {
+    // The post must be a published post of this community
+    let post = query!(
+        r#"
+        SELECT id
+        FROM posts
+        WHERE id = $1
+          AND community_id = $2
+          AND published_at IS NOT NULL
+          AND deleted_at IS NULL
+        "#,
+        post_id,
+        community_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if post.is_none() {
+        bail!("Post not found in this community");
+    }
+
+    let taken = query!(
+        "SELECT post_id, position FROM community_pinned_posts WHERE community_id = $1",
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    if taken.iter().any(|row| row.post_id == post_id) {
+        bail!("Post is already pinned");
+    }
+
+    if taken.len() as i32 >= MAX_PINNED_POSTS {
+        bail!("A community can pin at most {} posts", MAX_PINNED_POSTS);
+    }
+
+    let position = match position {
+        Some(position) => {
+            if !(1..=MAX_PINNED_POSTS).contains(&position) {
+                bail!("Pin position must be between 1 and {}", MAX_PINNED_POSTS);
+            }
+            if taken.iter().any(|row| row.position == position) {
+                bail!("Pin position {} is already taken", position);
+            }
+            position
+        }
+        None => (1..=MAX_PINNED_POSTS)
+            .find(|slot| !taken.iter().any(|row| row.position == *slot))
+            .ok_or_else(|| anyhow::anyhow!("No free pin slot"))?,
+    };
+
+    query!(
+        r#"
+        INSERT INTO community_pinned_posts (community_id, post_id, position)
+        VALUES ($1, $2, $3)
+        "#,
+        community_id,
+        post_id,
+        position
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Unpin a post from a community
+pub async fn unpin_post(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    post_id: Uuid,
+) -> Result<()> {
+    query!(
+        "DELETE FROM community_pinned_posts WHERE community_id = $1 AND post_id = $2",
+        community_id,
+        post_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Get pinned posts of a community ordered by slot
+pub async fn get_pinned_posts_for_community(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    viewer_user_id: Option<Uuid>,
+    viewer_show_sensitive: bool,
+) -> Result<Vec<SerializablePost>> {
+    let rows = query!(
+        r#"
+        SELECT
+            posts.id,
+            posts.title,
+            posts.author_id,
+            users.login_name,
+            images.paint_duration,
+            images.stroke_count,
+            images.image_filename,
+            images.width,
+            images.height,
+            images.replay_filename,
+            posts.viewer_count,
+            posts.is_sensitive,
+            posts.published_at,
+            posts.created_at,
+            posts.updated_at
+        FROM community_pinned_posts
+        JOIN posts ON community_pinned_posts.post_id = posts.id
+        LEFT JOIN images ON posts.image_id = images.id
+        LEFT JOIN users ON posts.author_id = users.id
+        WHERE community_pinned_posts.community_id = $1
+          AND posts.published_at IS NOT NULL
+          AND posts.deleted_at IS NULL
+          AND (posts.is_sensitive = false OR $2 = true OR posts.author_id = $3)
+        ORDER BY community_pinned_posts.position ASC
+        "#,
+        community_id,
+        viewer_show_sensitive,
+        viewer_user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SerializablePost {
+            id: row.id,
+            title: row.title,
+            author_id: row.author_id,
+            user_login_name: Some(row.login_name),
+            paint_duration: row.paint_duration.microseconds.to_string(),
+            stroke_count: row.stroke_count,
+            image_filename: row.image_filename,
+            image_width: row.width,
+            image_height: row.height,
+            replay_filename: row.replay_filename,
+            is_sensitive: row.is_sensitive,
+            viewer_count: row.viewer_count,
+            published_at: row.published_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
+/// Daily time window during which members may publish to a community
+#[derive(Clone, Debug, Serialize)]
+pub struct CommunityPostingWindow {
+    pub community_id: Uuid,
+    pub timezone: String,
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+}
+
+impl CommunityPostingWindow {
+    fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(chrono_tz::Asia::Seoul)
+    }
+
+    pub fn is_open_at(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.tz()).time();
+        if self.opens_at < self.closes_at {
+            self.opens_at <= local && local < self.closes_at
+        } else {
+            // Window wraps past midnight
+            local >= self.opens_at || local < self.closes_at
+        }
+    }
+
+    /// The next time the window opens after `now`, in the community's timezone
+    pub fn next_opening(&self, now: DateTime<Utc>) -> DateTime<Tz> {
+        let tz = self.tz();
+        let local_now = now.with_timezone(&tz);
+        let mut date = local_now.date_naive();
+        if local_now.time() >= self.opens_at {
+            date += Duration::days(1);
+        }
+        let opening = date.and_time(self.opens_at);
+        // A DST gap can swallow the opening time; the window then opens an hour later
+        tz.from_local_datetime(&opening)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(opening + Duration::hours(1)))
+                    .earliest()
+            })
+            .unwrap_or(local_now)
+    }
+}
+
+pub async fn find_community_posting_window(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<Option<CommunityPostingWindow>> {
+    let window = query_as!(
+        CommunityPostingWindow,
+        r#"
+        SELECT community_id, timezone, opens_at, closes_at
+        FROM community_posting_windows
+        WHERE community_id = $1
+        "#,
+        community_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(window)
+}
+
+/// The community's posting window if it is closed to this author at `now`.
+/// Owners and moderators may post at any time.
+pub async fn find_closed_posting_window(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    author_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Option<CommunityPostingWindow>> {
+    let Some(window) = find_community_posting_window(tx, community_id).await? else {
+        return Ok(None);
+    };
+    if window.is_open_at(now) {
+        return Ok(None);
+    }
+
+    let role = get_user_role_in_community(tx, author_id, community_id).await?;
+    let is_exempt = matches!(
+        role,
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)
+    ) || find_community_by_id(tx, community_id)
+        .await?
+        .is_some_and(|community| community.owner_id == author_id);
+
+    Ok((!is_exempt).then_some(window))
+}
+
+pub async fn set_community_posting_window(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    timezone: &str,
+    opens_at: NaiveTime,
+    closes_at: NaiveTime,
+) -> Result<()> {
+    if timezone.parse::<Tz>().is_err() {
+        bail!("Unknown timezone: {}", timezone);
+    }
+    if opens_at == closes_at {
+        bail!("Posting window must not be empty");
+    }
+
+    query!(
+        r#"
+        INSERT INTO community_posting_windows (community_id, timezone, opens_at, closes_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (community_id) DO UPDATE
+        SET timezone = EXCLUDED.timezone,
+            opens_at = EXCLUDED.opens_at,
+            closes_at = EXCLUDED.closes_at,
+            updated_at = now()
+        "#,
+        community_id,
+        timezone,
+        opens_at,
+        closes_at
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_community_posting_window(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<()> {
+    query!(
+        "DELETE FROM community_posting_windows WHERE community_id = $1",
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+// ========== Archival ==========
+
+/// A community considered for archival
+#[derive(Clone, Debug)]
+pub struct InactiveCommunity {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub slug: String,
+}
+
+/// Communities with no post published since `inactive_since` (counting from
+/// creation when there are none) whose owner hasn't been warned yet
+pub async fn find_communities_to_warn_about_archival(
+    tx: &mut Transaction<'_, Postgres>,
+    inactive_since: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<InactiveCommunity>> {
+    let communities = query_as!(
+        InactiveCommunity,
+        r#"
+        SELECT communities.id, communities.owner_id, communities.name, communities.slug
+        FROM communities
+        LEFT JOIN posts ON posts.community_id = communities.id
+            AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
+        WHERE communities.deleted_at IS NULL
+          AND communities.archived_at IS NULL
+          AND communities.archive_notice_sent_at IS NULL
+          AND communities.created_at < $1
+        GROUP BY communities.id
+        HAVING COALESCE(MAX(posts.published_at), communities.created_at) < $1
+        ORDER BY communities.created_at
+        LIMIT $2
+        "#,
+        inactive_since,
+        limit
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(communities)
+}
+
+/// Communities warned before `warned_before` that have stayed inactive since
+/// `inactive_since`
+pub async fn find_communities_to_archive(
+    tx: &mut Transaction<'_, Postgres>,
+    inactive_since: DateTime<Utc>,
+    warned_before: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<InactiveCommunity>> {
+    let communities = query_as!(
+        InactiveCommunity,
+        r#"
+        SELECT communities.id, communities.owner_id, communities.name, communities.slug
+        FROM communities
+        LEFT JOIN posts ON posts.community_id = communities.id
+            AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
+        WHERE communities.deleted_at IS NULL
+          AND communities.archived_at IS NULL
+          AND communities.archive_notice_sent_at < $2
+        GROUP BY communities.id
+        HAVING COALESCE(MAX(posts.published_at), communities.created_at) < $1
+        ORDER BY communities.archive_notice_sent_at
+        LIMIT $3
+        "#,
+        inactive_since,
+        warned_before,
+        limit
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(communities)
+}
+
+/// Withdraw warnings from communities that had a post published since; they
+/// start the inactivity period over
+pub async fn clear_archive_notices_for_active_communities(
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<u64> {
+    let result = query!(
+        r#"
+        UPDATE communities
+        SET archive_notice_sent_at = NULL
+        WHERE archive_notice_sent_at IS NOT NULL
+          AND archived_at IS NULL
+          AND EXISTS (
+              SELECT 1 FROM posts
+              WHERE posts.community_id = communities.id
+                AND posts.deleted_at IS NULL
+                AND posts.published_at > communities.archive_notice_sent_at
+          )
+        "#
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn mark_community_archive_notice_sent(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<()> {
+    query!(
+        "UPDATE communities SET archive_notice_sent_at = now() WHERE id = $1",
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn archive_community(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<()> {
+    query!(
+        "UPDATE communities SET archived_at = now() WHERE id = $1 AND archived_at IS NULL",
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Restore an archived community. The inactivity period starts over from now.
+pub async fn unarchive_community(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        UPDATE communities
+        SET archived_at = NULL, archive_notice_sent_at = NULL, updated_at = now()
+        WHERE id = $1 AND archived_at IS NOT NULL
+        "#,
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn find_community_archived_at(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<Option<DateTime<Utc>>> {
+    let archived_at = query_scalar!(
+        "SELECT archived_at FROM communities WHERE id = $1",
+        community_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(archived_at.flatten())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(opens_at: &str, closes_at: &str) -> CommunityPostingWindow {
+        CommunityPostingWindow {
+            community_id: Uuid::new_v4(),
+            timezone: "Asia/Seoul".to_string(),
+            opens_at: opens_at.parse().unwrap(),
+            closes_at: closes_at.parse().unwrap(),
+        }
+    }
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn posting_is_allowed_only_inside_the_window() {
+        let window = window("09:00:00", "18:00:00");
+
+        assert!(window.is_open_at(at("2026-10-01T10:00:00+09:00")));
+        assert!(!window.is_open_at(at("2026-10-01T08:59:00+09:00")));
+        assert!(!window.is_open_at(at("2026-10-01T18:00:00+09:00")));
+        // Judged in the community's timezone, not the server's
+        assert!(!window.is_open_at(at("2026-10-01T10:00:00+00:00")));
+    }
+
+    #[test]
+    fn window_may_wrap_past_midnight() {
+        let window = window("22:00:00", "02:00:00");
+
+        assert!(window.is_open_at(at("2026-10-01T23:00:00+09:00")));
+        assert!(window.is_open_at(at("2026-10-02T01:00:00+09:00")));
+        assert!(!window.is_open_at(at("2026-10-02T12:00:00+09:00")));
+    }
+
+    #[test]
+    fn closed_window_reopens_at_the_next_opening() {
+        let window = window("09:00:00", "18:00:00");
+
+        assert_eq!(
+            window.next_opening(at("2026-10-01T20:00:00+09:00")),
+            at("2026-10-02T09:00:00+09:00")
+        );
+        assert_eq!(
+            window.next_opening(at("2026-10-01T07:00:00+09:00")),
+            at("2026-10-01T09:00:00+09:00")
+        );
+    }
+
+    #[sqlx::test]
+    async fn new_communities_bring_in_default_co_moderators(pool: sqlx::PgPool) {
+        use crate::models::test_fixtures;
+
+        let mut tx = pool.begin().await.unwrap();
+        let owner = test_fixtures::user(&mut tx, "owner").await;
+        let co_moderator = test_fixtures::user(&mut tx, "comod").await;
+        // Listing yourself is ignored
+        set_default_co_moderators(&mut tx, owner.id, &[co_moderator.id, owner.id])
+            .await
+            .unwrap();
+
+        let public =
+            test_fixtures::community(&mut tx, owner.id, "public", CommunityVisibility::Public)
+                .await;
+        let expires_at = Utc::now() + Duration::days(7);
+        let applied = apply_default_co_moderators(&mut tx, &public, expires_at)
+            .await
+            .unwrap();
+        assert_eq!(
+            applied.iter().map(|user| user.id).collect::<Vec<_>>(),
+            vec![co_moderator.id]
+        );
+        let invitations = get_pending_invitations_for_community(&mut tx, public.id)
+            .await
+            .unwrap();
+        assert_eq!(invitations.len(), 1);
+        assert_eq!(invitations[0].invitee_id, co_moderator.id);
+        assert_eq!(invitations[0].role, CommunityMemberRole::Moderator);
+        assert_eq!(
+            get_user_role_in_community(&mut tx, co_moderator.id, public.id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(accept_invitation(&mut tx, invitations[0].id).await.unwrap());
+        add_community_member(
+            &mut tx,
+            public.id,
+            co_moderator.id,
+            invitations[0].role,
+            Some(owner.id),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_user_role_in_community(&mut tx, co_moderator.id, public.id)
+                .await
+                .unwrap(),
+            Some(CommunityMemberRole::Moderator)
+        );
+
+        let private =
+            test_fixtures::community(&mut tx, owner.id, "private", CommunityVisibility::Private)
+                .await;
+        apply_default_co_moderators(&mut tx, &private, expires_at)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_user_role_in_community(&mut tx, co_moderator.id, private.id)
+                .await
+                .unwrap(),
+            Some(CommunityMemberRole::Moderator)
+        );
+        assert!(get_pending_invitations_for_community(&mut tx, private.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[sqlx::test]
+    async fn long_inactive_community_is_archived_and_recent_one_is_not(pool: sqlx::PgPool) {
+        use crate::models::post::publish_post;
+        use crate::models::test_fixtures;
+
+        let mut tx = pool.begin().await.unwrap();
+        let owner = test_fixtures::user(&mut tx, "owner").await;
+        let mut community_ids = Vec::new();
+        for (slug, last_post_days_ago) in [("dormant", 400), ("lively", 1)] {
+            let community =
+                test_fixtures::community(&mut tx, owner.id, slug, CommunityVisibility::Public)
+                    .await;
+            let post = test_fixtures::draft_post(&mut tx, owner.id, Some(community.id)).await;
+            publish_post(
+                &mut tx,
+                post.id,
+                "title".into(),
+                "content".into(),
+                false,
+                true,
+            )
+            .await
+            .unwrap();
+            sqlx::query!(
+                "UPDATE communities SET created_at = now() - interval '500 days' WHERE id = $1",
+                community.id
+            )
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+            sqlx::query!(
+                "UPDATE posts SET published_at = now() - make_interval(days => $2) WHERE id = $1",
+                post.id,
+                last_post_days_ago
+            )
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+            community_ids.push(community.id);
+        }
+        let (dormant, lively) = (community_ids[0], community_ids[1]);
+
+        let inactive_since = Utc::now() - Duration::days(365);
+        let notice_window = Duration::days(14);
+        let to_warn =
+            find_communities_to_warn_about_archival(&mut tx, inactive_since + notice_window, 10)
+                .await
+                .unwrap();
+        assert_eq!(
+            to_warn.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![dormant]
+        );
+
+        mark_community_archive_notice_sent(&mut tx, dormant)
+            .await
+            .unwrap();
+        sqlx::query!(
+            "UPDATE communities SET archive_notice_sent_at = now() - interval '15 days' WHERE id = $1",
+            dormant
+        )
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        let to_archive =
+            find_communities_to_archive(&mut tx, inactive_since, Utc::now() - notice_window, 10)
+                .await
+                .unwrap();
+        assert_eq!(
+            to_archive.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![dormant]
+        );
+
+        archive_community(&mut tx, dormant).await.unwrap();
+        assert!(find_community_archived_at(&mut tx, dormant)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(find_community_archived_at(&mut tx, lively)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
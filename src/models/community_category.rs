@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommunityCategory {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct CommunityCategoryDraft {
+    pub slug: String,
+    pub name: String,
+    pub position: i32,
+}
+
+pub async fn create_community_category(
+    tx: &mut Transaction<'_, Postgres>,
+    draft: CommunityCategoryDraft,
+) -> Result<CommunityCategory> {
+    let category = query_as!(
+        CommunityCategory,
+        r#"
+        INSERT INTO community_categories (slug, name, position)
+        VALUES ($1, $2, $3)
+        RETURNING id, slug, name, position, created_at
+        "#,
+        draft.slug,
+        draft.name,
+        draft.position
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(category)
+}
+
+/// Get every category in display order
+pub async fn list_community_categories(
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<CommunityCategory>> {
+    let categories = query_as!(
+        CommunityCategory,
+        r#"
+        SELECT id, slug, name, position, created_at
+        FROM community_categories
+        ORDER BY position, name
+        "#
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(categories)
+}
+
+pub async fn find_community_category_by_slug(
+    tx: &mut Transaction<'_, Postgres>,
+    slug: &str,
+) -> Result<Option<CommunityCategory>> {
+    let category = query_as!(
+        CommunityCategory,
+        r#"
+        SELECT id, slug, name, position, created_at
+        FROM community_categories
+        WHERE slug = $1
+        "#,
+        slug
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(category)
+}
+
+pub async fn delete_community_category(
+    tx: &mut Transaction<'_, Postgres>,
+    category_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        DELETE FROM community_categories
+        WHERE id = $1
+        "#,
+        category_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Get the IDs of the communities assigned to a category
+pub async fn find_community_ids_in_category(
+    tx: &mut Transaction<'_, Postgres>,
+    category_id: Uuid,
+) -> Result<Vec<Uuid>> {
+    let rows = query!(
+        r#"
+        SELECT community_id
+        FROM community_category_assignments
+        WHERE category_id = $1
+        "#,
+        category_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.community_id).collect())
+}
+
+/// Batch fetch the categories of many communities, keyed by community ID.
+/// Communities without categories are absent from the map.
+pub async fn find_categories_by_community_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    community_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<CommunityCategory>>> {
+    if community_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = query!(
+        r#"
+        SELECT
+            community_category_assignments.community_id,
+            community_categories.id,
+            community_categories.slug,
+            community_categories.name,
+            community_categories.position,
+            community_categories.created_at
+        FROM community_category_assignments
+        JOIN community_categories
+            ON community_categories.id = community_category_assignments.category_id
+        WHERE community_category_assignments.community_id = ANY($1)
+        ORDER BY community_categories.position, community_categories.name
+        "#,
+        community_ids
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut categories: HashMap<Uuid, Vec<CommunityCategory>> = HashMap::new();
+    for row in rows {
+        categories
+            .entry(row.community_id)
+            .or_default()
+            .push(CommunityCategory {
+                id: row.id,
+                slug: row.slug,
+                name: row.name,
+                position: row.position,
+                created_at: row.created_at,
+            });
+    }
+
+    Ok(categories)
+}
+
+/// Replace a community's categories with the given ones.
+/// Unknown category IDs are ignored.
+pub async fn set_community_categories(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    category_ids: &[Uuid],
+) -> Result<()> {
+    query!(
+        r#"
+        DELETE FROM community_category_assignments
+        WHERE community_id = $1
+        "#,
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    query!(
+        r#"
+        INSERT INTO community_category_assignments (community_id, category_id)
+        SELECT $1, id
+        FROM community_categories
+        WHERE id = ANY($2)
+        "#,
+        community_id,
+        category_ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::community::{
+        count_public_communities, get_public_communities_paginated, CommunityVisibility,
+    };
+    use crate::models::post::publish_post;
+    use crate::models::test_fixtures;
+
+    async fn category(tx: &mut Transaction<'_, Postgres>, slug: &str) -> Uuid {
+        let draft = CommunityCategoryDraft {
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            position: 0,
+        };
+        create_community_category(tx, draft).await.unwrap().id
+    }
+
+    /// Only communities with a published post are listed
+    async fn listed_community(
+        tx: &mut Transaction<'_, Postgres>,
+        owner_id: Uuid,
+        slug: &str,
+    ) -> Uuid {
+        let community =
+            test_fixtures::community(tx, owner_id, slug, CommunityVisibility::Public).await;
+        let post = test_fixtures::draft_post(tx, owner_id, Some(community.id)).await;
+        publish_post(tx, post.id, "title".into(), "content".into(), false, true)
+            .await
+            .unwrap();
+        community.id
+    }
+
+    async fn listed_ids(
+        tx: &mut Transaction<'_, Postgres>,
+        category_id: Option<Uuid>,
+    ) -> Vec<Uuid> {
+        let mut ids: Vec<Uuid> = get_public_communities_paginated(tx, 10, 0, category_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|community| community.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[sqlx::test]
+    async fn category_filter_lists_only_assigned_communities(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let owner = test_fixtures::user(&mut tx, "owner").await;
+        let games = category(&mut tx, "games").await;
+        let music = category(&mut tx, "music").await;
+        let arcade = listed_community(&mut tx, owner.id, "arcade").await;
+        let rhythm = listed_community(&mut tx, owner.id, "rhythm").await;
+        let uncategorized = listed_community(&mut tx, owner.id, "misc").await;
+        set_community_categories(&mut tx, arcade, &[games])
+            .await
+            .unwrap();
+        set_community_categories(&mut tx, rhythm, &[games, music])
+            .await
+            .unwrap();
+
+        let mut in_games = vec![arcade, rhythm];
+        in_games.sort();
+        assert_eq!(listed_ids(&mut tx, Some(games)).await, in_games);
+        assert_eq!(
+            count_public_communities(&mut tx, Some(games))
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(listed_ids(&mut tx, Some(music)).await, vec![rhythm]);
+        assert!(listed_ids(&mut tx, None).await.contains(&uncategorized));
+        assert_eq!(count_public_communities(&mut tx, None).await.unwrap(), 3);
+    }
+}
@@ -0,0 +1,342 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::models::community::CommunityMemberRole;
+
+#[derive(Serialize)]
+pub struct ExportedPost {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub community_id: Option<Uuid>,
+    pub parent_post_id: Option<Uuid>,
+    pub is_sensitive: bool,
+    pub image_filename: String,
+    pub replay_filename: Option<String>,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct ExportedComment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub content: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct ExportedReaction {
+    pub iri: String,
+    pub post_id: Uuid,
+    pub emoji: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Everything a user has authored, as included in their data export
+pub struct UserExport {
+    pub posts: Vec<ExportedPost>,
+    pub comments: Vec<ExportedComment>,
+    pub reactions: Vec<ExportedReaction>,
+}
+
+pub async fn collect_user_export(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<UserExport> {
+    let posts = sqlx::query_as!(
+        ExportedPost,
+        r#"
+        SELECT
+            posts.id,
+            posts.title,
+            posts.content,
+            posts.community_id,
+            posts.parent_post_id,
+            posts.is_sensitive,
+            images.image_filename,
+            images.replay_filename,
+            images.width AS image_width,
+            images.height AS image_height,
+            posts.published_at,
+            posts.created_at,
+            posts.updated_at
+        FROM posts
+        JOIN images ON posts.image_id = images.id
+        WHERE posts.author_id = $1
+        AND posts.deleted_at IS NULL
+        ORDER BY posts.created_at ASC
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let comments = sqlx::query_as!(
+        ExportedComment,
+        r#"
+        SELECT
+            comments.id,
+            comments.post_id,
+            comments.parent_comment_id,
+            comments.content,
+            comments.created_at,
+            comments.updated_at
+        FROM comments
+        JOIN actors ON comments.actor_id = actors.id
+        WHERE actors.user_id = $1
+        AND comments.deleted_at IS NULL
+        ORDER BY comments.created_at ASC
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let reactions = sqlx::query_as!(
+        ExportedReaction,
+        r#"
+        SELECT
+            reactions.iri,
+            reactions.post_id,
+            reactions.emoji,
+            reactions.created_at
+        FROM reactions
+        JOIN actors ON reactions.actor_id = actors.id
+        WHERE actors.user_id = $1
+        ORDER BY reactions.created_at ASC
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(UserExport {
+        posts,
+        comments,
+        reactions,
+    })
+}
+
+/// An entry of the `posts.json` manifest; the drawings themselves are stored in
+/// the archive as `images/{image_filename}`
+#[derive(Serialize)]
+pub struct ExportedCommunityPost {
+    pub id: Uuid,
+    pub author_login_name: String,
+    pub author_display_name: String,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub parent_post_id: Option<Uuid>,
+    pub is_sensitive: bool,
+    pub hashtags: Vec<String>,
+    pub image_filename: String,
+    /// The rest of the post's gallery after the cover, in order
+    pub gallery_image_filenames: Vec<String>,
+    pub replay_filename: Option<String>,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct ExportedCommunityComment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub author_handle: String,
+    pub content: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Members are exported by login name only; no emails or other account details
+#[derive(Serialize)]
+pub struct ExportedCommunityMember {
+    pub login_name: String,
+    pub role: CommunityMemberRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// A community's published content, as included in its owner's export
+pub struct CommunityExport {
+    pub posts: Vec<ExportedCommunityPost>,
+    pub comments: Vec<ExportedCommunityComment>,
+    pub members: Vec<ExportedCommunityMember>,
+}
+
+pub async fn collect_community_export(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<CommunityExport> {
+    let posts = sqlx::query_as!(
+        ExportedCommunityPost,
+        r#"
+        SELECT
+            posts.id,
+            users.login_name AS author_login_name,
+            users.display_name AS author_display_name,
+            posts.title,
+            posts.content,
+            posts.parent_post_id,
+            posts.is_sensitive,
+            COALESCE(
+                (
+                    SELECT array_agg(hashtags.display_name ORDER BY post_hashtags.created_at)
+                    FROM post_hashtags
+                    JOIN hashtags ON post_hashtags.hashtag_id = hashtags.id
+                    WHERE post_hashtags.post_id = posts.id
+                ),
+                '{}'
+            ) AS "hashtags!",
+            images.image_filename,
+            COALESCE(
+                (
+                    SELECT array_agg(gallery.image_filename ORDER BY post_images.position)
+                    FROM post_images
+                    JOIN images AS gallery ON post_images.image_id = gallery.id
+                    WHERE post_images.post_id = posts.id
+                    AND post_images.image_id <> posts.image_id
+                ),
+                '{}'
+            ) AS "gallery_image_filenames!",
+            images.replay_filename,
+            images.width AS image_width,
+            images.height AS image_height,
+            posts.published_at,
+            posts.created_at,
+            posts.updated_at
+        FROM posts
+        JOIN images ON posts.image_id = images.id
+        JOIN users ON posts.author_id = users.id
+        WHERE posts.community_id = $1
+        AND posts.published_at IS NOT NULL
+        AND posts.deleted_at IS NULL
+        ORDER BY posts.published_at ASC
+        "#,
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let comments = sqlx::query_as!(
+        ExportedCommunityComment,
+        r#"
+        SELECT
+            comments.id,
+            comments.post_id,
+            comments.parent_comment_id,
+            actors.handle AS author_handle,
+            comments.content,
+            comments.created_at
+        FROM comments
+        JOIN posts ON comments.post_id = posts.id
+        JOIN actors ON comments.actor_id = actors.id
+        WHERE posts.community_id = $1
+        AND posts.published_at IS NOT NULL
+        AND posts.deleted_at IS NULL
+        AND comments.deleted_at IS NULL
+        ORDER BY comments.created_at ASC
+        "#,
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let members = sqlx::query_as!(
+        ExportedCommunityMember,
+        r#"
+        SELECT
+            users.login_name,
+            community_members.role AS "role: _",
+            community_members.joined_at
+        FROM community_members
+        JOIN users ON community_members.user_id = users.id
+        WHERE community_members.community_id = $1
+        ORDER BY community_members.joined_at ASC
+        "#,
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(CommunityExport {
+        posts,
+        comments,
+        members,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::community::{add_community_member, CommunityVisibility};
+    use crate::models::post::publish_post;
+    use crate::models::test_fixtures;
+
+    #[sqlx::test]
+    async fn community_export_has_posts_and_no_member_emails(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let owner = test_fixtures::user(&mut tx, "owner").await;
+        let member = test_fixtures::user(&mut tx, "member").await;
+        sqlx::query!(
+            "UPDATE users SET email = 'member@example.com' WHERE id = $1",
+            member.id
+        )
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        let community =
+            test_fixtures::community(&mut tx, owner.id, "club", CommunityVisibility::Private).await;
+        add_community_member(
+            &mut tx,
+            community.id,
+            member.id,
+            CommunityMemberRole::Member,
+            Some(owner.id),
+        )
+        .await
+        .unwrap();
+        let published = test_fixtures::draft_post(&mut tx, member.id, Some(community.id)).await;
+        publish_post(
+            &mut tx,
+            published.id,
+            "Sunset".to_string(),
+            String::new(),
+            false,
+            true,
+        )
+        .await
+        .unwrap();
+        test_fixtures::draft_post(&mut tx, member.id, Some(community.id)).await;
+
+        let export = collect_community_export(&mut tx, community.id)
+            .await
+            .unwrap();
+
+        // Drafts stay out of the export
+        assert_eq!(export.posts.len(), 1);
+        assert_eq!(export.posts[0].id, published.id);
+        assert_eq!(export.posts[0].author_login_name, "member");
+        assert_eq!(export.posts[0].image_filename, published.image_filename);
+        let mut login_names: Vec<&str> = export
+            .members
+            .iter()
+            .map(|member| member.login_name.as_str())
+            .collect();
+        login_names.sort();
+        assert_eq!(login_names, vec!["member", "owner"]);
+        let manifest = serde_json::to_string(&export.members).unwrap();
+        assert!(!manifest.contains("member@example.com"));
+        assert!(!manifest.contains("email"));
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{query, query_as, Postgres, Transaction};
+use uuid::Uuid;
+
+/// A remote post that was announced to us and surfaced in a local community
+#[derive(Clone, Debug, Serialize)]
+pub struct FederatedPost {
+    pub iri: String,
+    pub announce_iri: String,
+    pub community_id: Uuid,
+    pub actor_id: Uuid,
+    pub url: Option<String>,
+    pub content_html: String,
+    pub image_url: Option<String>,
+    pub image_width: Option<i32>,
+    pub image_height: Option<i32>,
+    pub is_sensitive: bool,
+    pub published_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Federated post joined with the remote author for rendering
+#[derive(Clone, Debug, Serialize)]
+pub struct SerializableFederatedPost {
+    pub iri: String,
+    pub url: Option<String>,
+    pub content_html: String,
+    pub image_url: Option<String>,
+    pub image_width: Option<i32>,
+    pub image_height: Option<i32>,
+    pub is_sensitive: bool,
+    pub published_at: DateTime<Utc>,
+    pub actor_name: String,
+    pub actor_handle: String,
+    pub actor_url: String,
+}
+
+pub struct FederatedPostDraft {
+    pub iri: String,
+    pub announce_iri: String,
+    pub community_id: Uuid,
+    pub actor_id: Uuid,
+    pub url: Option<String>,
+    pub content_html: String,
+    pub image_url: Option<String>,
+    pub image_width: Option<i32>,
+    pub image_height: Option<i32>,
+    pub is_sensitive: bool,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Store an announced remote post, ignoring posts that were already surfaced
+pub async fn create_federated_post(
+    tx: &mut Transaction<'_, Postgres>,
+    draft: FederatedPostDraft,
+) -> Result<Option<FederatedPost>> {
+    let post = query_as!(
+        FederatedPost,
+        r#"
+        INSERT INTO federated_posts (
+            iri, announce_iri, community_id, actor_id, url, content_html,
+            image_url, image_width, image_height, is_sensitive, published_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, COALESCE($11::timestamptz, now()))
+        ON CONFLICT (iri) DO NOTHING
+        RETURNING iri, announce_iri, community_id, actor_id, url, content_html,
+            image_url, image_width, image_height, is_sensitive, published_at, created_at
+        "#,
+        draft.iri,
+        draft.announce_iri,
+        draft.community_id,
+        draft.actor_id,
+        draft.url,
+        draft.content_html,
+        draft.image_url,
+        draft.image_width,
+        draft.image_height,
+        draft.is_sensitive,
+        draft.published_at
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(post)
+}
+
+/// Get federated posts surfaced in a community, newest first
+pub async fn find_federated_posts_by_community_id(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    limit: i64,
+    viewer_show_sensitive: bool,
+) -> Result<Vec<SerializableFederatedPost>> {
+    let posts = query_as!(
+        SerializableFederatedPost,
+        r#"
+        SELECT
+            federated_posts.iri,
+            federated_posts.url,
+            federated_posts.content_html,
+            federated_posts.image_url,
+            federated_posts.image_width,
+            federated_posts.image_height,
+            federated_posts.is_sensitive,
+            federated_posts.published_at,
+            actors.name AS actor_name,
+            actors.handle AS actor_handle,
+            actors.url AS actor_url
+        FROM federated_posts
+        JOIN actors ON federated_posts.actor_id = actors.id
+        WHERE federated_posts.community_id = $1
+          AND federated_posts.deleted_at IS NULL
+          AND (federated_posts.is_sensitive = false OR $3 = true)
+        ORDER BY federated_posts.published_at DESC
+        LIMIT $2
+        "#,
+        community_id,
+        limit,
+        viewer_show_sensitive
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(posts)
+}
+
+pub async fn find_federated_post_by_iri(
+    tx: &mut Transaction<'_, Postgres>,
+    iri: &str,
+) -> Result<Option<FederatedPost>> {
+    let post = query_as!(
+        FederatedPost,
+        r#"
+        SELECT iri, announce_iri, community_id, actor_id, url, content_html,
+            image_url, image_width, image_height, is_sensitive, published_at, created_at
+        FROM federated_posts
+        WHERE iri = $1
+        "#,
+        iri
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(post)
+}
+
+/// Take down a surfaced post when the Announce that brought it in is undone
+pub async fn delete_federated_post_by_announce_iri(
+    tx: &mut Transaction<'_, Postgres>,
+    announce_iri: &str,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        UPDATE federated_posts
+        SET deleted_at = now()
+        WHERE announce_iri = $1 AND deleted_at IS NULL
+        "#,
+        announce_iri
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Soft-delete a federated post on behalf of its author. Returns whether a
+/// post was deleted.
+pub async fn delete_federated_post_by_iri(
+    tx: &mut Transaction<'_, Postgres>,
+    iri: &str,
+    actor_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        UPDATE federated_posts
+        SET deleted_at = now()
+        WHERE iri = $1 AND actor_id = $2 AND deleted_at IS NULL
+        "#,
+        iri,
+        actor_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
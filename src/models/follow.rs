@@ -112,6 +112,23 @@ pub async fn count_followings_by_user_id(
     Ok(result.count)
 }
 
+/// Counts accepted followers only; pending follow requests live elsewhere
+pub async fn count_followers_by_actor_id(
+    tx: &mut Transaction<'_, Postgres>,
+    following_actor_id: Uuid,
+) -> Result<i64> {
+    let result = query!(
+        r#"SELECT COUNT(*) as "count!"
+        FROM follows
+        WHERE following_actor_id = $1"#,
+        following_actor_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.count)
+}
+
 pub async fn find_followings_by_user_id(
     tx: &mut Transaction<'_, Postgres>,
     user_id: Uuid,
@@ -209,6 +226,51 @@ pub async fn create_follow_by_actor_ids(
     }
 }
 
+pub async fn is_following_by_actor_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    follower_actor_id: Uuid,
+    following_actor_id: Uuid,
+) -> Result<bool> {
+    let exists = query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM follows WHERE follower_actor_id = $1 AND following_actor_id = $2
+        ) AS "exists!"
+        "#,
+        follower_actor_id,
+        following_actor_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(exists.exists)
+}
+
+/// Whether a local user, or the given local actor, follows an actor
+pub async fn is_followed_locally(
+    tx: &mut Transaction<'_, Postgres>,
+    following_actor_id: Uuid,
+    local_follower_actor_id: Uuid,
+) -> Result<bool> {
+    let exists = query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM follows f
+            JOIN actors a ON f.follower_actor_id = a.id
+            WHERE f.following_actor_id = $1
+              AND (a.user_id IS NOT NULL OR a.id = $2)
+        ) AS "exists!"
+        "#,
+        following_actor_id,
+        local_follower_actor_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(exists.exists)
+}
+
 pub async fn unfollow_by_actor_ids(
     tx: &mut Transaction<'_, Postgres>,
     follower_actor_id: Uuid,
@@ -225,6 +287,59 @@ pub async fn unfollow_by_actor_ids(
     Ok(())
 }
 
+/// Hands an actor's follows over to the account it moved to. Follows the new
+/// account already has are kept as they are and the old ones dropped.
+pub async fn move_follows_to_actor(
+    tx: &mut Transaction<'_, Postgres>,
+    old_follower_actor_id: Uuid,
+    new_follower_actor_id: Uuid,
+) -> Result<()> {
+    query!(
+        r#"
+        UPDATE follows SET follower_actor_id = $2
+        WHERE follower_actor_id = $1
+          AND NOT EXISTS (
+            SELECT 1 FROM follows existing
+            WHERE existing.follower_actor_id = $2
+              AND existing.following_actor_id = follows.following_actor_id
+          )
+        "#,
+        old_follower_actor_id,
+        new_follower_actor_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    query!(
+        "DELETE FROM follows WHERE follower_actor_id = $1",
+        old_follower_actor_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// IDs of the local users an actor follows
+pub async fn find_followed_user_ids_by_actor_id(
+    tx: &mut Transaction<'_, Postgres>,
+    follower_actor_id: Uuid,
+) -> Result<Vec<Uuid>> {
+    let rows = query!(
+        r#"
+        SELECT a.user_id AS "user_id!"
+        FROM follows f
+        JOIN actors a ON f.following_actor_id = a.id
+        WHERE f.follower_actor_id = $1 AND a.user_id IS NOT NULL
+        "#,
+        follower_actor_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.user_id).collect())
+}
+
 use crate::models::actor::Actor;
 
 pub async fn find_followers_by_actor_id(
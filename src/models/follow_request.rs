@@ -0,0 +1,138 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::models::follow::{create_follow_by_actor_ids, Follow};
+
+/// A Follow waiting for a locked account to approve or reject it
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FollowRequest {
+    pub id: Uuid,
+    pub follower_actor_id: Uuid,
+    pub following_actor_id: Uuid,
+    pub activity_iri: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A pending request together with the requesting actor, for listing
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FollowRequestInfo {
+    pub id: Uuid,
+    pub follower_name: String,
+    pub follower_handle: String,
+    pub follower_url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Records a pending request. A repeated Follow from the same actor only
+/// replaces the activity IRI, so the request keeps its place in the list.
+pub async fn create_follow_request(
+    tx: &mut Transaction<'_, Postgres>,
+    follower_actor_id: Uuid,
+    following_actor_id: Uuid,
+    activity_iri: &str,
+) -> Result<FollowRequest> {
+    let follow_request = query_as!(
+        FollowRequest,
+        r#"
+        INSERT INTO follow_requests (follower_actor_id, following_actor_id, activity_iri)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (follower_actor_id, following_actor_id) DO UPDATE
+        SET activity_iri = EXCLUDED.activity_iri
+        RETURNING id, follower_actor_id, following_actor_id, activity_iri, created_at
+        "#,
+        follower_actor_id,
+        following_actor_id,
+        activity_iri
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(follow_request)
+}
+
+pub async fn find_follow_request_by_id(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<Option<FollowRequest>> {
+    let follow_request = query_as!(
+        FollowRequest,
+        r#"
+        SELECT id, follower_actor_id, following_actor_id, activity_iri, created_at
+        FROM follow_requests
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(follow_request)
+}
+
+pub async fn find_follow_requests_by_following_actor_id(
+    tx: &mut Transaction<'_, Postgres>,
+    following_actor_id: Uuid,
+) -> Result<Vec<FollowRequestInfo>> {
+    let follow_requests = query_as!(
+        FollowRequestInfo,
+        r#"
+        SELECT
+            fr.id,
+            a.name AS follower_name,
+            a.handle AS follower_handle,
+            a.url AS follower_url,
+            fr.created_at
+        FROM follow_requests fr
+        JOIN actors a ON fr.follower_actor_id = a.id
+        WHERE fr.following_actor_id = $1
+        ORDER BY fr.created_at ASC
+        "#,
+        following_actor_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(follow_requests)
+}
+
+/// Turns the request into a follow and removes it from the pending list
+pub async fn accept_follow_request(
+    tx: &mut Transaction<'_, Postgres>,
+    follow_request: &FollowRequest,
+) -> Result<Follow> {
+    delete_follow_request(tx, follow_request.id).await?;
+    create_follow_by_actor_ids(
+        tx,
+        follow_request.follower_actor_id,
+        follow_request.following_actor_id,
+    )
+    .await
+}
+
+pub async fn delete_follow_request(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<()> {
+    query!("DELETE FROM follow_requests WHERE id = $1", id)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+/// Drops a pending request, e.g. when the requester sends Undo(Follow)
+pub async fn delete_follow_request_by_actor_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    follower_actor_id: Uuid,
+    following_actor_id: Uuid,
+) -> Result<()> {
+    query!(
+        "DELETE FROM follow_requests WHERE follower_actor_id = $1 AND following_actor_id = $2",
+        follower_actor_id,
+        following_actor_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
@@ -2,15 +2,17 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 
 use serde::Serialize;
-use sqlx::{query, Postgres, Transaction};
+use sqlx::{query, query_as, Postgres, Transaction};
 use uuid::Uuid;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct GuestbookEntry {
     pub id: Uuid,
-    pub author_id: Uuid,
+    pub author_actor_id: Uuid,
     pub recipient_id: Uuid,
     pub content: String,
+    pub content_html: Option<String>,
+    pub iri: Option<String>,
     pub reply: Option<String>,
     pub created_at: DateTime<Utc>,
     pub replied_at: Option<DateTime<Utc>>,
@@ -19,22 +21,32 @@ pub struct GuestbookEntry {
 #[derive(Clone, Debug, Serialize)]
 pub struct SerializableGuestbookEntry {
     pub id: Uuid,
-    pub author_id: Uuid,
-    pub author_login_name: String,
+    pub author_actor_id: Uuid,
+    /// Set when the author is a local user
+    pub author_user_id: Option<Uuid>,
+    pub author_login_name: Option<String>,
     pub author_display_name: String,
+    pub author_handle: String,
+    pub author_url: String,
+    pub is_local: bool,
     pub recipient_login_name: String,
     pub recipient_display_name: String,
     pub recipient_id: Uuid,
     pub content: String,
+    pub content_html: Option<String>,
+    pub iri: Option<String>,
     pub reply: Option<String>,
     pub created_at: DateTime<Utc>,
     pub replied_at: Option<DateTime<Utc>>,
 }
 
 pub struct GuestbookEntryDraft {
-    pub author_id: Uuid,
+    pub author_actor_id: Uuid,
     pub recipient_id: Uuid,
     pub content: String,
+    /// Sanitized HTML of entries received over ActivityPub
+    pub content_html: Option<String>,
+    pub iri: Option<String>,
 }
 
 pub async fn add_guestbook_entry_reply(
@@ -64,43 +76,39 @@ pub async fn find_guestbook_entry_by_id(
     tx: &mut Transaction<'_, Postgres>,
     entry_id: Uuid,
 ) -> Result<Option<SerializableGuestbookEntry>> {
-    let entry = query!(
-        "
-                SELECT
+    let entry = query_as!(
+        SerializableGuestbookEntry,
+        r#"
+            SELECT
                 guestbook_entries.id,
-                author.login_name AS author_login_name,
-                author.display_name AS author_display_name,
+                guestbook_entries.author_actor_id,
+                author.user_id AS author_user_id,
+                author_user.login_name AS "author_login_name?",
+                author.name AS author_display_name,
+                author.handle AS author_handle,
+                author.url AS author_url,
+                (author.user_id IS NOT NULL) AS "is_local!",
                 recipient.login_name AS recipient_login_name,
                 recipient.display_name AS recipient_display_name,
-                guestbook_entries.author_id,
                 guestbook_entries.recipient_id,
                 guestbook_entries.content,
+                guestbook_entries.content_html,
+                guestbook_entries.iri,
                 guestbook_entries.reply,
                 guestbook_entries.created_at,
                 guestbook_entries.replied_at
             FROM guestbook_entries
-            JOIN users AS author ON author.id = author_id
-            JOIN users AS recipient ON recipient.id = recipient_id
+            JOIN actors AS author ON author.id = guestbook_entries.author_actor_id
+            LEFT JOIN users AS author_user ON author_user.id = author.user_id
+            JOIN users AS recipient ON recipient.id = guestbook_entries.recipient_id
             WHERE guestbook_entries.id = $1
-        ",
+        "#,
         entry_id
     )
     .fetch_optional(&mut **tx)
     .await?;
 
-    Ok(entry.map(|entry| SerializableGuestbookEntry {
-        id: entry.id,
-        author_id: entry.author_id,
-        author_login_name: entry.author_login_name,
-        author_display_name: entry.author_display_name,
-        recipient_id: entry.recipient_id,
-        recipient_login_name: entry.recipient_login_name,
-        recipient_display_name: entry.recipient_display_name,
-        content: entry.content,
-        reply: entry.reply,
-        created_at: entry.created_at,
-        replied_at: entry.replied_at,
-    }))
+    Ok(entry)
 }
 
 pub async fn delete_guestbook_entry(
@@ -126,99 +134,60 @@ pub async fn create_guestbook_entry(
 ) -> Result<SerializableGuestbookEntry> {
     let entry = query!(
         "
-            INSERT INTO guestbook_entries (author_id, recipient_id, content)
-            VALUES ($1, $2, $3)
-            RETURNING id, created_at
+            INSERT INTO guestbook_entries (author_actor_id, recipient_id, content, content_html, iri)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
         ",
-        draft.author_id,
+        draft.author_actor_id,
         draft.recipient_id,
-        draft.content
-    )
-    .fetch_one(&mut **tx)
-    .await?;
-
-    let entry = query!(
-        "
-            SELECT
-                guestbook_entries.id,
-                author.login_name AS author_login_name,
-                author.display_name AS author_display_name,
-                recipient.login_name AS recipient_login_name,
-                recipient.display_name AS recipient_display_name,
-                guestbook_entries.author_id,
-                guestbook_entries.recipient_id,
-                guestbook_entries.content,
-                guestbook_entries.reply,
-                guestbook_entries.created_at,
-                guestbook_entries.replied_at
-            FROM guestbook_entries
-            JOIN users AS author ON author.id = author_id
-            JOIN users AS recipient ON recipient.id = recipient_id
-            WHERE guestbook_entries.id = $1
-        ",
-        entry.id
+        draft.content,
+        draft.content_html,
+        draft.iri
     )
     .fetch_one(&mut **tx)
     .await?;
 
-    Ok(SerializableGuestbookEntry {
-        id: entry.id,
-        author_id: entry.author_id,
-        author_login_name: entry.author_login_name,
-        author_display_name: entry.author_display_name,
-        recipient_login_name: entry.recipient_login_name,
-        recipient_display_name: entry.recipient_display_name,
-        recipient_id: entry.recipient_id,
-        content: entry.content,
-        reply: entry.reply,
-        created_at: entry.created_at,
-        replied_at: entry.replied_at,
-    })
+    find_guestbook_entry_by_id(tx, entry.id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Guestbook entry {} not found after insert", entry.id))
 }
 
-pub async fn find_guestbook_entries_by_recipient_id(
+pub async fn find_guestbook_entries_for_user(
     tx: &mut Transaction<'_, Postgres>,
     recipient_id: Uuid,
 ) -> Result<Vec<SerializableGuestbookEntry>> {
-    let entries = query!(
-        "
+    let entries = query_as!(
+        SerializableGuestbookEntry,
+        r#"
             SELECT
                 guestbook_entries.id,
-                guestbook_entries.author_id,
-                author.login_name AS author_login_name,
-                author.display_name AS author_display_name,
+                guestbook_entries.author_actor_id,
+                author.user_id AS author_user_id,
+                author_user.login_name AS "author_login_name?",
+                author.name AS author_display_name,
+                author.handle AS author_handle,
+                author.url AS author_url,
+                (author.user_id IS NOT NULL) AS "is_local!",
                 recipient.login_name AS recipient_login_name,
                 recipient.display_name AS recipient_display_name,
                 guestbook_entries.recipient_id,
                 guestbook_entries.content,
+                guestbook_entries.content_html,
+                guestbook_entries.iri,
                 guestbook_entries.reply,
                 guestbook_entries.created_at,
                 guestbook_entries.replied_at
             FROM guestbook_entries
-            JOIN users AS author ON author.id = author_id
-            JOIN users AS recipient ON recipient.id = recipient_id
-            WHERE recipient_id = $1
-            ORDER BY created_at DESC
-        ",
+            JOIN actors AS author ON author.id = guestbook_entries.author_actor_id
+            LEFT JOIN users AS author_user ON author_user.id = author.user_id
+            JOIN users AS recipient ON recipient.id = guestbook_entries.recipient_id
+            WHERE guestbook_entries.recipient_id = $1
+            ORDER BY guestbook_entries.created_at DESC
+        "#,
         recipient_id
     )
     .fetch_all(&mut **tx)
     .await?;
 
-    Ok(entries
-        .into_iter()
-        .map(|entry| SerializableGuestbookEntry {
-            id: entry.id,
-            author_id: entry.author_id,
-            author_login_name: entry.author_login_name,
-            author_display_name: entry.author_display_name,
-            recipient_login_name: entry.recipient_login_name,
-            recipient_display_name: entry.recipient_display_name,
-            recipient_id: entry.recipient_id,
-            content: entry.content,
-            reply: entry.reply,
-            created_at: entry.created_at,
-            replied_at: entry.replied_at,
-        })
-        .collect())
+    Ok(entries)
 }
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
@@ -207,6 +207,89 @@ pub async fn get_hashtags_for_post(
     Ok(hashtags)
 }
 
+/// Get the hashtags a community links to every post published into it
+pub async fn get_community_default_hashtags(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<Vec<Hashtag>> {
+    let hashtags = sqlx::query_as!(
+        Hashtag,
+        r#"
+        SELECT h.id, h.name, h.display_name, h.post_count, h.created_at, h.updated_at
+        FROM hashtags h
+        JOIN community_default_hashtags cdh ON h.id = cdh.hashtag_id
+        WHERE cdh.community_id = $1
+        ORDER BY cdh.created_at ASC
+        "#,
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(hashtags)
+}
+
+/// Replace a community's default hashtags
+pub async fn set_community_default_hashtags(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+    hashtag_names: &[(String, String)], // (normalized_name, display_name) tuples
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM community_default_hashtags WHERE community_id = $1
+        "#,
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    for (name, display_name) in hashtag_names {
+        let hashtag = find_or_create_hashtag(tx, name, display_name).await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO community_default_hashtags (community_id, hashtag_id)
+            VALUES ($1, $2)
+            ON CONFLICT (community_id, hashtag_id) DO NOTHING
+            "#,
+            community_id,
+            hashtag.id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Append the default hashtags of the post's community to the ones its author entered,
+/// skipping any already there so `link_post_to_hashtags` counts each once
+pub async fn add_community_default_hashtags(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    hashtag_names: &mut Vec<(String, String)>,
+) -> Result<()> {
+    let defaults = sqlx::query!(
+        r#"
+        SELECT h.name, h.display_name
+        FROM posts p
+        JOIN community_default_hashtags cdh ON cdh.community_id = p.community_id
+        JOIN hashtags h ON h.id = cdh.hashtag_id
+        WHERE p.id = $1
+        ORDER BY cdh.created_at ASC
+        "#,
+        post_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    for default in defaults {
+        if !hashtag_names.iter().any(|(name, _)| *name == default.name) {
+            hashtag_names.push((default.name, default.display_name));
+        }
+    }
+    Ok(())
+}
+
 /// Find posts by hashtag name (for hashtag view page)
 /// Only returns posts from public communities
 pub async fn find_posts_by_hashtag(
@@ -335,6 +418,61 @@ pub async fn get_trending_hashtags(
     Ok(hashtags)
 }
 
+/// A hashtag ranked by how many drawings used it over the last 24 hours
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrendingHashtag {
+    pub name: String,
+    pub display_name: String,
+    pub post_count: i64,
+    /// Posts in the last hour minus posts in the hour before it
+    pub delta: i64,
+}
+
+/// Hashtags with the most posts published to public communities in the last 24 hours
+pub async fn find_trending_hashtags_in_last_day(
+    tx: &mut Transaction<'_, Postgres>,
+    limit: i64,
+) -> Result<Vec<TrendingHashtag>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            h.name,
+            h.display_name,
+            COUNT(*) AS "post_count!",
+            COUNT(*) FILTER (
+                WHERE posts.published_at > NOW() - INTERVAL '1 hour'
+            ) AS "last_hour_count!",
+            COUNT(*) FILTER (
+                WHERE posts.published_at <= NOW() - INTERVAL '1 hour'
+                AND posts.published_at > NOW() - INTERVAL '2 hours'
+            ) AS "previous_hour_count!"
+        FROM post_hashtags ph
+        JOIN hashtags h ON ph.hashtag_id = h.id
+        JOIN posts ON ph.post_id = posts.id
+        JOIN communities c ON posts.community_id = c.id
+        WHERE posts.published_at > NOW() - INTERVAL '24 hours'
+        AND posts.deleted_at IS NULL
+        AND c.visibility = 'public'
+        GROUP BY h.id, h.name, h.display_name
+        ORDER BY "post_count!" DESC, "last_hour_count!" DESC, h.name ASC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| TrendingHashtag {
+            name: row.name,
+            display_name: row.display_name,
+            post_count: row.post_count,
+            delta: row.last_hour_count - row.previous_hour_count,
+        })
+        .collect())
+}
+
 /// Get hashtag by name
 pub async fn find_hashtag_by_name(
     tx: &mut Transaction<'_, Postgres>,
@@ -353,3 +491,227 @@ pub async fn find_hashtag_by_name(
     .await?;
     Ok(hashtag)
 }
+
+/// Follow a hashtag; following one that is already followed is a no-op
+pub async fn follow_hashtag(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    hashtag_id: Uuid,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO hashtag_follows (user_id, hashtag_id)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, hashtag_id) DO NOTHING
+        "#,
+        user_id,
+        hashtag_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn unfollow_hashtag(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    hashtag_id: Uuid,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM hashtag_follows
+        WHERE user_id = $1 AND hashtag_id = $2
+        "#,
+        user_id,
+        hashtag_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn is_following_hashtag(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    hashtag_id: Uuid,
+) -> Result<bool> {
+    let result = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM hashtag_follows
+            WHERE user_id = $1 AND hashtag_id = $2
+        ) AS "exists!"
+        "#,
+        user_id,
+        hashtag_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(result)
+}
+
+/// Hashtags the user follows, alphabetically
+pub async fn find_followed_hashtags(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<Vec<Hashtag>> {
+    let hashtags = sqlx::query_as!(
+        Hashtag,
+        r#"
+        SELECT h.id, h.name, h.display_name, h.post_count, h.created_at, h.updated_at
+        FROM hashtags h
+        JOIN hashtag_follows hf ON hf.hashtag_id = h.id
+        WHERE hf.user_id = $1
+        ORDER BY h.name ASC
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(hashtags)
+}
+
+/// Published posts tagged with any hashtag the user follows, newest first
+/// Includes personal posts, public communities, and private communities the user belongs to
+pub async fn find_posts_for_followed_hashtags(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    limit: i64,
+    offset: i64,
+    viewer_show_sensitive: bool,
+) -> Result<Vec<crate::models::post::SerializablePost>> {
+    let posts = sqlx::query!(
+        r#"
+        SELECT
+            posts.id,
+            posts.title,
+            posts.author_id,
+            users.login_name,
+            images.paint_duration,
+            images.stroke_count,
+            images.image_filename,
+            images.width,
+            images.height,
+            images.replay_filename,
+            posts.viewer_count,
+            posts.is_sensitive,
+            posts.published_at,
+            posts.created_at,
+            posts.updated_at
+        FROM posts
+        LEFT JOIN communities c ON posts.community_id = c.id
+        LEFT JOIN images ON posts.image_id = images.id
+        LEFT JOIN users ON posts.author_id = users.id
+        WHERE EXISTS (
+            SELECT 1 FROM post_hashtags ph
+            JOIN hashtag_follows hf ON hf.hashtag_id = ph.hashtag_id
+            WHERE ph.post_id = posts.id AND hf.user_id = $1
+        )
+        AND posts.published_at IS NOT NULL
+        AND posts.deleted_at IS NULL
+        AND (
+            posts.community_id IS NULL
+            OR (
+                c.deleted_at IS NULL
+                AND (
+                    c.visibility = 'public'
+                    OR (
+                        c.visibility = 'private'
+                        AND EXISTS (
+                            SELECT 1 FROM community_members
+                            WHERE community_members.community_id = c.id
+                            AND community_members.user_id = $1
+                        )
+                    )
+                )
+            )
+        )
+        AND (posts.is_sensitive = false OR $4 = true OR posts.author_id = $1)
+        ORDER BY posts.published_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        user_id,
+        limit,
+        offset,
+        viewer_show_sensitive
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(posts
+        .into_iter()
+        .map(|row| crate::models::post::SerializablePost {
+            id: row.id,
+            title: row.title,
+            author_id: row.author_id,
+            user_login_name: Some(row.login_name),
+            paint_duration: row.paint_duration.microseconds.to_string(),
+            stroke_count: row.stroke_count,
+            image_filename: row.image_filename,
+            image_width: row.width,
+            image_height: row.height,
+            replay_filename: row.replay_filename,
+            is_sensitive: row.is_sensitive,
+            viewer_count: row.viewer_count,
+            published_at: row.published_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::community::CommunityVisibility;
+    use crate::models::test_fixtures;
+
+    /// Hashtags linked to the post once the publish path has added the community's defaults
+    async fn publish_with_hashtags(
+        tx: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        input: &str,
+    ) -> Vec<String> {
+        let mut hashtag_names = parse_hashtag_input(input);
+        add_community_default_hashtags(tx, post_id, &mut hashtag_names)
+            .await
+            .unwrap();
+        link_post_to_hashtags(tx, post_id, &hashtag_names)
+            .await
+            .unwrap();
+
+        let mut linked: Vec<String> = get_hashtags_for_post(tx, post_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|hashtag| hashtag.name)
+            .collect();
+        linked.sort();
+        linked
+    }
+
+    #[sqlx::test]
+    async fn publishing_into_a_community_applies_its_default_hashtags(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let owner = test_fixtures::user(&mut tx, "owner").await;
+        let gallery =
+            test_fixtures::community(&mut tx, owner.id, "gallery", CommunityVisibility::Public)
+                .await;
+        set_community_default_hashtags(&mut tx, gallery.id, &parse_hashtag_input("gallery daily"))
+            .await
+            .unwrap();
+
+        let post = test_fixtures::draft_post(&mut tx, owner.id, Some(gallery.id)).await;
+        // A default the author also typed is only linked once
+        assert_eq!(
+            publish_with_hashtags(&mut tx, post.id, "daily cat").await,
+            vec!["cat", "daily", "gallery"]
+        );
+
+        let personal = test_fixtures::draft_post(&mut tx, owner.id, None).await;
+        assert_eq!(
+            publish_with_hashtags(&mut tx, personal.id, "cat").await,
+            vec!["cat"]
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use sqlx::{postgres::types::PgInterval, query_as, Postgres, Transaction};
+use sqlx::{postgres::types::PgInterval, query, query_as, Postgres, Transaction};
 use uuid::Uuid;
 
 pub struct Image {
@@ -11,9 +13,116 @@ pub struct Image {
     pub height: i32,
     pub image_filename: String,
     pub replay_filename: Option<String>,
+    pub replay_archived_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
+const REPLAY_PREFIX: &str = "replay";
+const ARCHIVED_REPLAY_PREFIX: &str = "replay-cold";
+
+/// Object key of a replay, which depends on whether it has been moved to cold storage
+pub fn replay_object_key(replay_filename: &str, archived: bool) -> String {
+    let prefix = if archived {
+        ARCHIVED_REPLAY_PREFIX
+    } else {
+        REPLAY_PREFIX
+    };
+    format!(
+        "{}/{}/{}",
+        prefix,
+        replay_filename.get(..2).unwrap_or_default(),
+        replay_filename
+    )
+}
+
+/// Public URL of an image stored under the two-character prefix scheme
+pub fn image_url(r2_public_endpoint_url: &str, image_filename: &str) -> String {
+    format!(
+        "{}/image/{}/{}",
+        r2_public_endpoint_url,
+        image_filename.get(..2).unwrap_or_default(),
+        image_filename
+    )
+}
+
+/// Public URL of a thumbnail, sharded like the originals but under `thumb/`
+pub fn thumbnail_url(r2_public_endpoint_url: &str, thumbnail_filename: &str) -> String {
+    format!(
+        "{}/thumb/{}/{}",
+        r2_public_endpoint_url,
+        thumbnail_filename.get(..2).unwrap_or_default(),
+        thumbnail_filename
+    )
+}
+
+/// Thumbnail URL for an image if one was generated, otherwise the original's URL.
+/// Drawings from before thumbnails existed, or too small to need one, fall back.
+pub fn thumbnail_or_image_url(
+    r2_public_endpoint_url: &str,
+    thumbnails: &HashMap<String, ImageThumbnail>,
+    image_filename: &str,
+) -> String {
+    match thumbnails.get(image_filename) {
+        Some(thumbnail) => thumbnail_url(r2_public_endpoint_url, &thumbnail.thumbnail_filename),
+        None => image_url(r2_public_endpoint_url, image_filename),
+    }
+}
+
+pub fn image_media_type(image_filename: &str) -> &'static str {
+    match image_filename.rsplit('.').next() {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        _ => "image/png",
+    }
+}
+
+/// Records an uploaded image, which has no drawing metadata or replay. Uploading
+/// the same file again reuses its row, since filenames are content hashes.
+pub async fn create_uploaded_image(
+    tx: &mut Transaction<'_, Postgres>,
+    width: i32,
+    height: i32,
+    image_filename: &str,
+) -> Result<Uuid> {
+    let image = query!(
+        r#"
+        INSERT INTO images (
+            paint_duration,
+            stroke_count,
+            width,
+            height,
+            image_filename,
+            replay_filename,
+            tool
+        ) VALUES ('0 seconds'::interval, 0, $1, $2, $3, NULL, 'upload')
+        ON CONFLICT (image_filename) DO UPDATE SET deleted_at = NULL
+        RETURNING id
+        "#,
+        width,
+        height,
+        image_filename
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(image.id)
+}
+
+/// Whether an image is already stored under `image_filename`
+pub async fn image_filename_taken(
+    tx: &mut Transaction<'_, Postgres>,
+    image_filename: &str,
+) -> Result<bool> {
+    let taken = query!(
+        r#"SELECT EXISTS(SELECT 1 FROM images WHERE image_filename = $1) AS "taken!""#,
+        image_filename
+    )
+    .fetch_one(&mut **tx)
+    .await?
+    .taken;
+    Ok(taken)
+}
+
 pub async fn find_image_by_id(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<Image> {
     let image = query_as!(
         Image,
@@ -26,6 +135,7 @@ pub async fn find_image_by_id(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> R
             height,
             image_filename,
             replay_filename,
+            replay_archived_at,
             created_at
         FROM images
         WHERE id = $1
@@ -37,3 +147,223 @@ pub async fn find_image_by_id(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> R
 
     Ok(image)
 }
+
+pub struct ReplayToArchive {
+    pub image_id: Uuid,
+    pub replay_filename: String,
+}
+
+/// Replays still in hot storage whose drawings were created before `older_than`
+pub async fn find_replays_to_archive(
+    tx: &mut Transaction<'_, Postgres>,
+    older_than: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<ReplayToArchive>> {
+    let replays = query_as!(
+        ReplayToArchive,
+        r#"
+        SELECT id AS image_id, replay_filename AS "replay_filename!"
+        FROM images
+        WHERE replay_filename IS NOT NULL
+        AND replay_archived_at IS NULL
+        AND created_at < $1
+        ORDER BY created_at ASC
+        LIMIT $2
+        "#,
+        older_than,
+        limit
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(replays)
+}
+
+pub async fn mark_replay_archived(
+    tx: &mut Transaction<'_, Postgres>,
+    image_id: Uuid,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE images SET replay_archived_at = now() WHERE id = $1",
+        image_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// A downscaled variant of an image whose longer side is at most `max_size`
+#[derive(Clone, Debug)]
+pub struct ImageThumbnail {
+    pub image_filename: String,
+    pub max_size: i32,
+    pub width: i32,
+    pub height: i32,
+    pub thumbnail_filename: String,
+}
+
+pub async fn create_image_thumbnail(
+    tx: &mut Transaction<'_, Postgres>,
+    thumbnail: &ImageThumbnail,
+) -> Result<()> {
+    // Identical drawings share a filename, and so their thumbnails
+    query!(
+        r#"
+        INSERT INTO image_thumbnails (image_filename, max_size, width, height, thumbnail_filename)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (image_filename, max_size) DO NOTHING
+        "#,
+        thumbnail.image_filename,
+        thumbnail.max_size,
+        thumbnail.width,
+        thumbnail.height,
+        thumbnail.thumbnail_filename
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Forget the thumbnails of images that are going away, returning the R2 keys
+/// of the thumbnail objects so they can be removed along with the originals
+pub async fn delete_image_thumbnails(
+    tx: &mut Transaction<'_, Postgres>,
+    image_filenames: &[String],
+) -> Result<Vec<String>> {
+    if image_filenames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let thumbnails = query!(
+        r#"
+        DELETE FROM image_thumbnails
+        WHERE image_filename = ANY($1)
+        RETURNING thumbnail_filename
+        "#,
+        image_filenames
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(thumbnails
+        .into_iter()
+        .map(|thumbnail| {
+            format!(
+                "thumb/{}/{}",
+                thumbnail.thumbnail_filename.get(..2).unwrap_or_default(),
+                thumbnail.thumbnail_filename
+            )
+        })
+        .collect())
+}
+
+/// One thumbnail of each image, keyed by the image filename.
+///
+/// Without `min_width` this is the largest variant: grids display drawings at up to the
+/// smaller sizes, so it stays sharp on dense screens. With it, this is the smallest variant
+/// at least that wide, so clients that know their cell size download no more than they show.
+/// Images with no variant that wide are left out and fall back to the original.
+pub async fn find_thumbnails_by_image_filenames(
+    tx: &mut Transaction<'_, Postgres>,
+    image_filenames: &[String],
+    min_width: Option<i32>,
+) -> Result<HashMap<String, ImageThumbnail>> {
+    if image_filenames.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let thumbnails = query_as!(
+        ImageThumbnail,
+        r#"
+        SELECT DISTINCT ON (image_filename)
+            image_filename,
+            max_size,
+            width,
+            height,
+            thumbnail_filename
+        FROM image_thumbnails
+        WHERE image_filename = ANY($1)
+        AND ($2::integer IS NULL OR width >= $2)
+        ORDER BY image_filename, CASE WHEN $2::integer IS NULL THEN -max_size ELSE max_size END
+        "#,
+        image_filenames,
+        min_width
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(thumbnails
+        .into_iter()
+        .map(|thumbnail| (thumbnail.image_filename.clone(), thumbnail))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_fixtures;
+
+    /// Give a post's drawing a replay, created `days_ago`
+    async fn replay(tx: &mut Transaction<'_, Postgres>, author_id: Uuid, days_ago: i32) -> Uuid {
+        let post = test_fixtures::draft_post(tx, author_id, None).await;
+        query!(
+            r#"
+            UPDATE images
+            SET replay_filename = replace(image_filename, '.png', '.pch'),
+                created_at = now() - make_interval(days => $2)
+            WHERE image_filename = $1
+            RETURNING id
+            "#,
+            post.image_filename,
+            days_ago
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn archived_replays_live_under_their_own_prefix() {
+        assert_eq!(replay_object_key("abcd.pch", false), "replay/ab/abcd.pch");
+        assert_eq!(
+            replay_object_key("abcd.pch", true),
+            "replay-cold/ab/abcd.pch"
+        );
+    }
+
+    #[sqlx::test]
+    async fn aged_replay_is_archived_and_resolves_to_cold_storage(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let author = test_fixtures::user(&mut tx, "author").await;
+        let aged = replay(&mut tx, author.id, 400).await;
+        let recent = replay(&mut tx, author.id, 1).await;
+        let older_than = Utc::now() - chrono::TimeDelta::days(365);
+
+        let due = find_replays_to_archive(&mut tx, older_than, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            due.iter().map(|replay| replay.image_id).collect::<Vec<_>>(),
+            vec![aged]
+        );
+
+        mark_replay_archived(&mut tx, aged).await.unwrap();
+
+        let image = find_image_by_id(&mut tx, aged).await.unwrap();
+        let replay_filename = image.replay_filename.unwrap();
+        assert!(image.replay_archived_at.is_some());
+        assert_eq!(
+            replay_object_key(&replay_filename, image.replay_archived_at.is_some()),
+            format!("replay-cold/{}/{}", &replay_filename[..2], replay_filename)
+        );
+        let recent = find_image_by_id(&mut tx, recent).await.unwrap();
+        assert!(recent.replay_archived_at.is_none());
+        assert!(find_replays_to_archive(&mut tx, older_than, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,10 +1,18 @@
+pub mod activity_delivery;
 pub mod actor;
+pub mod announcement;
 pub mod banner;
+pub mod boost;
 pub mod comment;
+pub mod comment_reaction;
 pub mod community;
+pub mod community_category;
 pub mod device;
 pub mod email_verification_challenge;
+pub mod export;
+pub mod federated_post;
 pub mod follow;
+pub mod follow_request;
 pub mod guestbook_entry;
 pub mod hashtag;
 pub mod image;
@@ -12,7 +20,13 @@ pub mod instance;
 pub mod link;
 pub mod nodeinfo;
 pub mod notification;
+pub mod oauth;
 pub mod password_reset_challenge;
 pub mod post;
+pub mod post_translation;
 pub mod reaction;
+pub mod report;
+#[cfg(test)]
+pub mod test_fixtures;
+pub mod totp;
 pub mod user;
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use sqlx::{query, Postgres, Transaction};
 use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -130,3 +131,60 @@ pub async fn fetch_nodeinfo(host: &str) -> Result<Option<NodeInfo>> {
 
     Ok(Some(nodeinfo))
 }
+
+/// Local usage statistics reported in our own NodeInfo document
+#[derive(Clone, Debug)]
+pub struct LocalUsage {
+    pub total_users: i64,
+    pub active_month_users: i64,
+    pub active_halfyear_users: i64,
+    pub local_posts: i64,
+}
+
+pub async fn get_local_usage(tx: &mut Transaction<'_, Postgres>) -> Result<LocalUsage> {
+    let users = query!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM users
+        WHERE deleted_at IS NULL
+        "#
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    // There is no login tracking, so "active" means having published a post recently
+    let active = query!(
+        r#"
+        SELECT
+            COUNT(DISTINCT author_id) FILTER (
+                WHERE published_at > now() - INTERVAL '30 days'
+            ) AS "active_month!",
+            COUNT(DISTINCT author_id) FILTER (
+                WHERE published_at > now() - INTERVAL '180 days'
+            ) AS "active_halfyear!"
+        FROM posts
+        WHERE published_at IS NOT NULL
+          AND deleted_at IS NULL
+        "#
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let posts = query!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM posts
+        WHERE published_at IS NOT NULL
+          AND deleted_at IS NULL
+        "#
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(LocalUsage {
+        total_users: users.count,
+        active_month_users: active.active_month,
+        active_halfyear_users: active.active_halfyear,
+        local_posts: posts.count,
+    })
+}
@@ -5,6 +5,7 @@ use fluent::{FluentArgs, FluentResource};
 use intl_memoizer::concurrent::IntlLangMemoizer;
 use serde::{Deserialize, Serialize};
 use sqlx::{Postgres, Transaction, Type};
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use crate::locale::LOCALES;
@@ -27,6 +28,31 @@ pub enum NotificationType {
     CommentReply,
     #[sqlx(rename = "community_post")]
     CommunityPost,
+    #[sqlx(rename = "follower_moved")]
+    FollowerMoved,
+}
+
+impl NotificationType {
+    pub const ALL: [NotificationType; 10] = [
+        NotificationType::Comment,
+        NotificationType::Reaction,
+        NotificationType::Follow,
+        NotificationType::GuestbookEntry,
+        NotificationType::GuestbookReply,
+        NotificationType::Mention,
+        NotificationType::PostReply,
+        NotificationType::CommentReply,
+        NotificationType::CommunityPost,
+        NotificationType::FollowerMoved,
+    ];
+}
+
+/// Which channels a user wants a notification type delivered over
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NotificationPreference {
+    pub notification_type: NotificationType,
+    pub push_enabled: bool,
+    pub email_enabled: bool,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -70,6 +96,53 @@ pub struct NotificationWithActor {
     pub guestbook_content: Option<String>,
 }
 
+/// Notifications of one kind on one post, collapsed into their most recent entry
+#[derive(Clone, Debug, Serialize)]
+pub struct GroupedNotification {
+    pub notification: NotificationWithActor,
+    pub others_count: i64,
+    pub unread_count: i64,
+}
+
+/// Keyset position in a notification list, encoded as `<rfc3339>|<notification id>`
+#[derive(Clone, Debug)]
+pub struct NotificationCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl NotificationCursor {
+    pub fn encode(&self) -> String {
+        format!("{}|{}", self.created_at.to_rfc3339(), self.id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (created_at, id) = cursor.split_once('|')?;
+        Some(Self {
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .ok()?
+                .with_timezone(&Utc),
+            id: Uuid::parse_str(id).ok()?,
+        })
+    }
+}
+
+/// Splits off the extra row fetched past `limit` and turns the last kept row into the next cursor
+fn paginate<T>(
+    mut rows: Vec<T>,
+    limit: i64,
+    position: impl Fn(&T) -> NotificationCursor,
+) -> (Vec<T>, Option<NotificationCursor>) {
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit.max(0) as usize);
+    let next_cursor = if has_more {
+        rows.last().map(position)
+    } else {
+        None
+    };
+    (rows, next_cursor)
+}
+
 pub struct CreateNotificationParams {
     pub recipient_id: Uuid,
     pub actor_id: Uuid,
@@ -80,9 +153,21 @@ pub struct CreateNotificationParams {
     pub guestbook_entry_id: Option<Uuid>,
 }
 
+/// Published to connected clients whenever a notification is created
+#[derive(Clone, Debug, Serialize)]
+pub struct NotificationEvent {
+    pub recipient_id: Uuid,
+    pub notification_id: Uuid,
+    pub notification_type: NotificationType,
+    pub unread_count: i64,
+}
+
+pub type NotificationEvents = broadcast::Sender<NotificationEvent>;
+
 /// Create a new notification
 pub async fn create_notification(
     tx: &mut Transaction<'_, Postgres>,
+    events: &NotificationEvents,
     params: CreateNotificationParams,
 ) -> Result<Notification> {
     // Don't create notification if actor is notifying themselves
@@ -118,6 +203,15 @@ pub async fn create_notification(
     .fetch_one(&mut **tx)
     .await?;
 
+    // Sending only fails when nobody is listening, which is fine
+    let unread_count = get_unread_count(tx, notification.recipient_id).await?;
+    let _ = events.send(NotificationEvent {
+        recipient_id: notification.recipient_id,
+        notification_id: notification.id,
+        notification_type: notification.notification_type.clone(),
+        unread_count,
+    });
+
     Ok(notification)
 }
 
@@ -140,13 +234,21 @@ async fn get_user_id_from_actor(
         .ok_or_else(|| anyhow::anyhow!("Actor has no associated user"))
 }
 
-/// List notifications for a user with pagination
+/// List notifications for a user, newest first, optionally only those of one type.
+/// Returns the page and the cursor for the next page, if any.
 pub async fn list_notifications(
     tx: &mut Transaction<'_, Postgres>,
     recipient_id: Uuid,
     limit: i64,
-    offset: i64,
-) -> Result<Vec<NotificationWithActor>> {
+    cursor: Option<NotificationCursor>,
+    type_filter: Option<NotificationType>,
+) -> Result<(Vec<NotificationWithActor>, Option<NotificationCursor>)> {
+    let (cursor_created_at, cursor_id) = match cursor {
+        Some(cursor) => (Some(cursor.created_at), Some(cursor.id)),
+        None => (None, None),
+    };
+
+    // Fetch one extra row to know whether another page exists
     let notifications = sqlx::query!(
         r#"
         SELECT
@@ -182,17 +284,21 @@ pub async fn list_notifications(
         LEFT JOIN reactions r ON n.reaction_iri = r.iri
         LEFT JOIN guestbook_entries g ON n.guestbook_entry_id = g.id
         WHERE n.recipient_id = $1
-        ORDER BY n.created_at DESC
-        LIMIT $2 OFFSET $3
+          AND ($2::notification_type IS NULL OR n.notification_type = $2)
+          AND ($3::timestamptz IS NULL OR (n.created_at, n.id) < ($3, $4))
+        ORDER BY n.created_at DESC, n.id DESC
+        LIMIT $5
         "#,
         recipient_id,
-        limit,
-        offset
+        type_filter as Option<NotificationType>,
+        cursor_created_at,
+        cursor_id,
+        limit + 1
     )
     .fetch_all(&mut **tx)
     .await?;
 
-    Ok(notifications
+    let notifications = notifications
         .into_iter()
         .map(|row| NotificationWithActor {
             id: row.id,
@@ -218,7 +324,156 @@ pub async fn list_notifications(
             comment_content_html: row.comment_content_html,
             guestbook_content: row.guestbook_content,
         })
-        .collect())
+        .collect();
+
+    Ok(paginate(notifications, limit, |n| NotificationCursor {
+        created_at: n.created_at,
+        id: n.id,
+    }))
+}
+
+/// List notifications for a user with those sharing `(notification_type, post_id)` collapsed
+/// into one entry for the most recent of them. Notifications without a post are never grouped.
+/// Pages are keyed on each group's most recent notification.
+pub async fn get_grouped_notifications(
+    tx: &mut Transaction<'_, Postgres>,
+    recipient_id: Uuid,
+    limit: i64,
+    cursor: Option<NotificationCursor>,
+    type_filter: Option<NotificationType>,
+) -> Result<(Vec<GroupedNotification>, Option<NotificationCursor>)> {
+    let (cursor_created_at, cursor_id) = match cursor {
+        Some(cursor) => (Some(cursor.created_at), Some(cursor.id)),
+        None => (None, None),
+    };
+
+    let groups = sqlx::query!(
+        r#"
+        WITH grouped AS (
+            SELECT
+                id,
+                COUNT(*) OVER notification_group AS group_size,
+                COUNT(*) FILTER (WHERE read_at IS NULL) OVER notification_group AS group_unread_count,
+                ROW_NUMBER() OVER (
+                    PARTITION BY notification_type, COALESCE(post_id, id)
+                    ORDER BY created_at DESC
+                ) AS group_position
+            FROM notifications
+            WHERE recipient_id = $1
+              AND ($2::notification_type IS NULL OR notification_type = $2)
+            WINDOW notification_group AS (PARTITION BY notification_type, COALESCE(post_id, id))
+        )
+        SELECT
+            n.id,
+            n.recipient_id,
+            n.actor_id,
+            a.name AS actor_name,
+            a.handle AS actor_handle,
+            actor_users.login_name AS "actor_login_name?",
+            n.notification_type as "notification_type: NotificationType",
+            n.post_id,
+            n.comment_id,
+            n.reaction_iri,
+            r.emoji AS "reaction_emoji?",
+            n.guestbook_entry_id,
+            n.read_at,
+            n.created_at,
+            p.title AS post_title,
+            post_authors.login_name AS "post_author_login_name?",
+            images.image_filename AS "post_image_filename?",
+            images.width AS "post_image_width?",
+            images.height AS "post_image_height?",
+            c.content AS "comment_content?",
+            c.content_html AS "comment_content_html?",
+            g.content AS "guestbook_content?",
+            grouped.group_size AS "group_size!",
+            grouped.group_unread_count AS "group_unread_count!"
+        FROM grouped
+        JOIN notifications n ON grouped.id = n.id
+        LEFT JOIN actors a ON n.actor_id = a.id
+        LEFT JOIN users actor_users ON a.user_id = actor_users.id
+        LEFT JOIN posts p ON n.post_id = p.id
+        LEFT JOIN users post_authors ON p.author_id = post_authors.id
+        LEFT JOIN images ON p.image_id = images.id
+        LEFT JOIN comments c ON n.comment_id = c.id
+        LEFT JOIN reactions r ON n.reaction_iri = r.iri
+        LEFT JOIN guestbook_entries g ON n.guestbook_entry_id = g.id
+        WHERE grouped.group_position = 1
+          AND ($3::timestamptz IS NULL OR (n.created_at, n.id) < ($3, $4))
+        ORDER BY n.created_at DESC, n.id DESC
+        LIMIT $5
+        "#,
+        recipient_id,
+        type_filter as Option<NotificationType>,
+        cursor_created_at,
+        cursor_id,
+        limit + 1
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let groups = groups
+        .into_iter()
+        .map(|row| GroupedNotification {
+            notification: NotificationWithActor {
+                id: row.id,
+                recipient_id: row.recipient_id,
+                actor_id: row.actor_id,
+                actor_name: row.actor_name,
+                actor_handle: row.actor_handle,
+                actor_login_name: row.actor_login_name,
+                notification_type: row.notification_type,
+                post_id: row.post_id,
+                comment_id: row.comment_id,
+                reaction_iri: row.reaction_iri,
+                reaction_emoji: row.reaction_emoji,
+                guestbook_entry_id: row.guestbook_entry_id,
+                read_at: row.read_at,
+                created_at: row.created_at,
+                post_title: row.post_title,
+                post_author_login_name: row.post_author_login_name,
+                post_image_filename: row.post_image_filename,
+                post_image_width: row.post_image_width,
+                post_image_height: row.post_image_height,
+                comment_content: row.comment_content,
+                comment_content_html: row.comment_content_html,
+                guestbook_content: row.guestbook_content,
+            },
+            others_count: row.group_size - 1,
+            unread_count: row.group_unread_count,
+        })
+        .collect();
+
+    Ok(paginate(groups, limit, |group| NotificationCursor {
+        created_at: group.notification.created_at,
+        id: group.notification.id,
+    }))
+}
+
+/// Number of notifications in the same group as the given one, itself included
+pub async fn get_notification_group_size(
+    tx: &mut Transaction<'_, Postgres>,
+    notification_id: Uuid,
+    recipient_id: Uuid,
+) -> Result<i64> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM notifications
+        WHERE recipient_id = $2
+          AND (notification_type, COALESCE(post_id, id)) = (
+              SELECT notification_type, COALESCE(post_id, id)
+              FROM notifications
+              WHERE id = $1 AND recipient_id = $2
+          )
+        "#,
+        notification_id,
+        recipient_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.count)
 }
 
 /// Get a single notification by ID
@@ -316,17 +571,25 @@ pub async fn mark_notification_as_read(
     Ok(result.rows_affected() > 0)
 }
 
-/// Mark all notifications as read for a user
-pub async fn mark_all_notifications_as_read(
+/// Mark every notification in the same group as the given one as read
+pub async fn mark_notification_group_as_read(
     tx: &mut Transaction<'_, Postgres>,
+    notification_id: Uuid,
     recipient_id: Uuid,
 ) -> Result<u64> {
     let result = sqlx::query!(
         r#"
         UPDATE notifications
         SET read_at = CURRENT_TIMESTAMP
-        WHERE recipient_id = $1 AND read_at IS NULL
+        WHERE recipient_id = $2
+          AND read_at IS NULL
+          AND (notification_type, COALESCE(post_id, id)) = (
+              SELECT notification_type, COALESCE(post_id, id)
+              FROM notifications
+              WHERE id = $1 AND recipient_id = $2
+          )
         "#,
+        notification_id,
         recipient_id
     )
     .execute(&mut **tx)
@@ -335,6 +598,67 @@ pub async fn mark_all_notifications_as_read(
     Ok(result.rows_affected())
 }
 
+/// Mark all notifications as read for a user.
+/// With `up_to`, only notifications created at or before it are marked, so ones that
+/// arrived after the client loaded its first page stay unread.
+pub async fn mark_all_notifications_as_read(
+    tx: &mut Transaction<'_, Postgres>,
+    recipient_id: Uuid,
+    up_to: Option<DateTime<Utc>>,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE notifications
+        SET read_at = CURRENT_TIMESTAMP
+        WHERE recipient_id = $1
+          AND read_at IS NULL
+          AND ($2::timestamptz IS NULL OR created_at <= $2)
+        "#,
+        recipient_id,
+        up_to
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Mark all notifications originating from one community as read
+/// A notification belongs to a community through its post, or through the post of its comment
+pub async fn mark_read_by_community(
+    tx: &mut Transaction<'_, Postgres>,
+    recipient_id: Uuid,
+    community_id: Uuid,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE notifications
+        SET read_at = CURRENT_TIMESTAMP
+        WHERE recipient_id = $1
+          AND read_at IS NULL
+          AND (
+              EXISTS (
+                  SELECT 1 FROM posts
+                  WHERE posts.id = notifications.post_id
+                    AND posts.community_id = $2
+              )
+              OR EXISTS (
+                  SELECT 1 FROM comments
+                  JOIN posts ON comments.post_id = posts.id
+                  WHERE comments.id = notifications.comment_id
+                    AND posts.community_id = $2
+              )
+          )
+        "#,
+        recipient_id,
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 /// Get unread notification count for a user
 pub async fn get_unread_count(
     tx: &mut Transaction<'_, Postgres>,
@@ -374,6 +698,111 @@ pub async fn delete_notification(
     Ok(result.rows_affected() > 0)
 }
 
+/// Get a user's preference for every notification type.
+/// Types the user never changed come back with every channel enabled.
+pub async fn get_notification_preferences(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<Vec<NotificationPreference>> {
+    let stored = sqlx::query_as!(
+        NotificationPreference,
+        r#"
+        SELECT
+            notification_type as "notification_type: NotificationType",
+            push_enabled,
+            email_enabled
+        FROM notification_preferences
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(NotificationType::ALL
+        .into_iter()
+        .map(|notification_type| {
+            stored
+                .iter()
+                .find(|p| p.notification_type == notification_type)
+                .cloned()
+                .unwrap_or(NotificationPreference {
+                    notification_type,
+                    push_enabled: true,
+                    email_enabled: true,
+                })
+        })
+        .collect())
+}
+
+/// Store a user's preference for one notification type
+pub async fn set_notification_preference(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    preference: &NotificationPreference,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO notification_preferences (
+            user_id, notification_type, push_enabled, email_enabled
+        )
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id, notification_type)
+        DO UPDATE SET
+            push_enabled = EXCLUDED.push_enabled,
+            email_enabled = EXCLUDED.email_enabled,
+            updated_at = NOW()
+        "#,
+        user_id,
+        preference.notification_type.clone() as NotificationType,
+        preference.push_enabled,
+        preference.email_enabled
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Forget a user's preferences, turning every channel back on
+pub async fn delete_notification_preferences(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM notification_preferences
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Whether a user wants push notifications for the given type
+pub async fn is_push_enabled(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    notification_type: &NotificationType,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        SELECT push_enabled
+        FROM notification_preferences
+        WHERE user_id = $1 AND notification_type = $2
+        "#,
+        user_id,
+        notification_type.clone() as NotificationType
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(result.map(|row| row.push_enabled).unwrap_or(true))
+}
+
 /// Send push notification for a newly created notification
 /// This should be called after create_notification() succeeds and the transaction is committed
 pub async fn send_push_for_notification(
@@ -382,9 +811,26 @@ pub async fn send_push_for_notification(
     notification: &NotificationWithActor,
     badge_count: Option<u32>,
 ) {
-    // Get recipient's preferred language
+    // Get recipient's preferred language, unless they turned this kind of push off
     let preferred_language = match pool.acquire().await {
         Ok(mut conn) => {
+            match is_push_enabled(
+                &mut conn,
+                notification.recipient_id,
+                &notification.notification_type,
+            )
+            .await
+            {
+                Ok(false) => return,
+                Ok(true) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to get notification preferences for user {}: {:?}",
+                        notification.recipient_id,
+                        e
+                    );
+                }
+            }
             match get_user_language_preference(&mut conn, notification.recipient_id).await {
                 Ok(lang) => lang,
                 Err(e) => {
@@ -445,6 +891,7 @@ pub async fn send_push_for_notification(
     if let Err(e) = push_service
         .send_notification_to_user(
             notification.recipient_id,
+            Some(&notification.notification_type),
             &title,
             &body,
             badge,
@@ -483,13 +930,7 @@ pub async fn get_user_language_preference(
 fn get_fluent_bundle(
     language: Option<Language>,
 ) -> FluentBundle<&'static FluentResource, IntlLangMemoizer> {
-    let lang_code = match language {
-        Some(Language::Ko) => "ko",
-        Some(Language::Ja) => "ja",
-        Some(Language::En) => "en",
-        Some(Language::Zh) => "zh",
-        None => "en", // Default to English
-    };
+    let lang_code = language.as_ref().map_or("en", Language::code); // Default to English
 
     let ftl = LOCALES
         .get(lang_code)
@@ -627,6 +1068,16 @@ fn format_notification_message(
             };
             (title, body)
         }
+        NotificationType::FollowerMoved => {
+            let title =
+                get_localized_message(&bundle, "push-notification-follower-moved-title", None);
+            let body = get_localized_message(
+                &bundle,
+                "push-notification-follower-moved-body",
+                Some(&args),
+            );
+            (title, body)
+        }
     }
 }
 
@@ -672,3 +1123,130 @@ pub fn format_community_invitation_message(
         }
     }
 }
+
+/// Format the push notification telling a user their join request was reviewed
+/// Returns a tuple of (title, body) localized for the user's language preference
+pub fn format_community_join_request_message(
+    approved: bool,
+    language: Option<Language>,
+    community_slug: &str,
+) -> (String, String) {
+    let bundle = get_fluent_bundle(language);
+    let mut args = FluentArgs::new();
+    args.set("community", community_slug.to_string());
+
+    let (title_key, body_key) = if approved {
+        (
+            "push-notification-join-request-approved-title",
+            "push-notification-join-request-approved-body",
+        )
+    } else {
+        (
+            "push-notification-join-request-rejected-title",
+            "push-notification-join-request-rejected-body",
+        )
+    };
+    let title = get_localized_message(&bundle, title_key, None);
+    let body = get_localized_message(&bundle, body_key, Some(&args));
+    (title, body)
+}
+
+/// Format the push notification telling an author their post was reviewed
+/// Returns a tuple of (title, body) localized for the user's language preference
+pub fn format_post_approval_message(
+    approved: bool,
+    language: Option<Language>,
+    community_slug: &str,
+) -> (String, String) {
+    let bundle = get_fluent_bundle(language);
+    let mut args = FluentArgs::new();
+    args.set("community", community_slug.to_string());
+
+    let (title_key, body_key) = if approved {
+        (
+            "push-notification-post-approved-title",
+            "push-notification-post-approved-body",
+        )
+    } else {
+        (
+            "push-notification-post-rejected-title",
+            "push-notification-post-rejected-body",
+        )
+    };
+    let title = get_localized_message(&bundle, title_key, None);
+    let body = get_localized_message(&bundle, body_key, Some(&args));
+    (title, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::actor::Actor;
+    use crate::models::community::CommunityVisibility;
+    use crate::models::test_fixtures;
+
+    async fn notify(
+        tx: &mut Transaction<'_, Postgres>,
+        recipient_id: Uuid,
+        actor_id: Uuid,
+        post_id: Uuid,
+    ) -> Uuid {
+        let (events, _) = broadcast::channel(1);
+        create_notification(
+            tx,
+            &events,
+            CreateNotificationParams {
+                recipient_id,
+                actor_id,
+                notification_type: NotificationType::CommunityPost,
+                post_id: Some(post_id),
+                comment_id: None,
+                reaction_iri: None,
+                guestbook_entry_id: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[sqlx::test]
+    async fn marks_only_the_target_communitys_notifications(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let recipient = test_fixtures::user(&mut tx, "recipient").await;
+        let poster = test_fixtures::user(&mut tx, "poster").await;
+        let poster_actor = Actor::find_by_user_id(&mut tx, poster.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let target =
+            test_fixtures::community(&mut tx, poster.id, "target", CommunityVisibility::Public)
+                .await;
+        let other =
+            test_fixtures::community(&mut tx, poster.id, "other", CommunityVisibility::Public)
+                .await;
+        let target_post = test_fixtures::draft_post(&mut tx, poster.id, Some(target.id)).await;
+        let other_post = test_fixtures::draft_post(&mut tx, poster.id, Some(other.id)).await;
+        let personal_post = test_fixtures::draft_post(&mut tx, poster.id, None).await;
+
+        let in_target = notify(&mut tx, recipient.id, poster_actor.id, target_post.id).await;
+        let in_other = notify(&mut tx, recipient.id, poster_actor.id, other_post.id).await;
+        let personal = notify(&mut tx, recipient.id, poster_actor.id, personal_post.id).await;
+
+        let marked = mark_read_by_community(&mut tx, recipient.id, target.id)
+            .await
+            .unwrap();
+
+        assert_eq!(marked, 1);
+        let unread: Vec<Uuid> = sqlx::query_scalar!(
+            "SELECT id FROM notifications WHERE recipient_id = $1 AND read_at IS NULL",
+            recipient.id
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap();
+        assert!(!unread.contains(&in_target));
+        assert!(unread.contains(&in_other));
+        assert!(unread.contains(&personal));
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use data_encoding::BASE64URL_NOPAD;
+use openssl::sha::sha256;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sqlx::{query_as, Postgres, Transaction};
+use uuid::Uuid;
+
+const TOKEN_LENGTH: usize = 32;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct OAuthClient {
+    pub id: Uuid,
+    pub client_id: String,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OAuthClient {
+    /// Redirect URIs are compared exactly, as RFC 6749 asks of native clients
+    pub fn allows_redirect_uri(&self, redirect_uri: &str) -> bool {
+        self.redirect_uris.iter().any(|uri| uri == redirect_uri)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OAuthCode {
+    pub id: Uuid,
+    pub client_id: Uuid,
+    pub user_id: Uuid,
+    pub redirect_uri: String,
+    pub code_challenge: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+pub struct OAuthToken {
+    pub id: Uuid,
+    pub client_id: Uuid,
+    pub user_id: Uuid,
+    pub access_expires_at: DateTime<Utc>,
+    pub refresh_expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Generate an authorization code, access token or refresh token. Only its
+/// hash is ever stored.
+pub fn generate_oauth_secret() -> String {
+    let mut token = [0u8; TOKEN_LENGTH];
+    OsRng.fill_bytes(&mut token);
+    hex::encode(token)
+}
+
+/// Secrets are random and long enough that an unkeyed SHA-256 is sufficient
+pub fn hash_oauth_secret(secret: &str) -> Vec<u8> {
+    sha256(secret.as_bytes()).to_vec()
+}
+
+/// PKCE with the S256 method (RFC 7636): the challenge must be the unpadded
+/// base64url SHA-256 of the verifier
+pub fn verify_pkce(code_verifier: &str, code_challenge: &str) -> bool {
+    let valid_verifier = (43..=128).contains(&code_verifier.len())
+        && code_verifier
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-._~".contains(&b));
+    valid_verifier && BASE64URL_NOPAD.encode(&sha256(code_verifier.as_bytes())) == code_challenge
+}
+
+pub async fn create_oauth_client(
+    tx: &mut Transaction<'_, Postgres>,
+    name: &str,
+    redirect_uris: &[String],
+) -> Result<OAuthClient> {
+    let client = query_as!(
+        OAuthClient,
+        r#"
+        INSERT INTO oauth_clients (client_id, name, redirect_uris)
+        VALUES ($1, $2, $3)
+        RETURNING id, client_id, name, redirect_uris, created_at
+        "#,
+        Uuid::new_v4().to_string(),
+        name,
+        redirect_uris
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(client)
+}
+
+pub async fn find_oauth_client_by_client_id(
+    tx: &mut Transaction<'_, Postgres>,
+    client_id: &str,
+) -> Result<Option<OAuthClient>> {
+    let client = query_as!(
+        OAuthClient,
+        r#"
+        SELECT id, client_id, name, redirect_uris, created_at
+        FROM oauth_clients
+        WHERE client_id = $1
+        "#,
+        client_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(client)
+}
+
+pub async fn create_oauth_code(
+    tx: &mut Transaction<'_, Postgres>,
+    code_hash: &[u8],
+    client_id: Uuid,
+    user_id: Uuid,
+    redirect_uri: &str,
+    code_challenge: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO oauth_codes (code_hash, client_id, user_id, redirect_uri, code_challenge, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        code_hash,
+        client_id,
+        user_id,
+        redirect_uri,
+        code_challenge,
+        expires_at
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Look up an authorization code and delete it in the same statement, so each
+/// code can be exchanged at most once. Expired codes are never returned.
+pub async fn consume_oauth_code(
+    tx: &mut Transaction<'_, Postgres>,
+    code_hash: &[u8],
+) -> Result<Option<OAuthCode>> {
+    let code = query_as!(
+        OAuthCode,
+        r#"
+        DELETE FROM oauth_codes
+        WHERE code_hash = $1
+        RETURNING id, client_id, user_id, redirect_uri, code_challenge, expires_at, created_at
+        "#,
+        code_hash
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(code.filter(|code| code.expires_at > Utc::now()))
+}
+
+pub async fn create_oauth_token(
+    tx: &mut Transaction<'_, Postgres>,
+    client_id: Uuid,
+    user_id: Uuid,
+    access_token_hash: &[u8],
+    refresh_token_hash: &[u8],
+    access_expires_at: DateTime<Utc>,
+    refresh_expires_at: DateTime<Utc>,
+) -> Result<OAuthToken> {
+    let token = query_as!(
+        OAuthToken,
+        r#"
+        INSERT INTO oauth_tokens (
+            client_id, user_id, access_token_hash, refresh_token_hash,
+            access_expires_at, refresh_expires_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, client_id, user_id, access_expires_at, refresh_expires_at, revoked_at, created_at
+        "#,
+        client_id,
+        user_id,
+        access_token_hash,
+        refresh_token_hash,
+        access_expires_at,
+        refresh_expires_at
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(token)
+}
+
+/// The user an unexpired, unrevoked access token was issued to
+pub async fn find_user_id_by_access_token(
+    tx: &mut Transaction<'_, Postgres>,
+    access_token_hash: &[u8],
+) -> Result<Option<Uuid>> {
+    let user_id = sqlx::query_scalar!(
+        r#"
+        SELECT user_id
+        FROM oauth_tokens
+        WHERE access_token_hash = $1
+          AND access_expires_at > NOW()
+          AND revoked_at IS NULL
+        "#,
+        access_token_hash
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(user_id)
+}
+
+/// Revoke the token a refresh token belongs to and return it, if the refresh
+/// token is still valid for the client. Refreshing replaces the token, so a
+/// refresh token works only once.
+pub async fn revoke_oauth_token_for_refresh(
+    tx: &mut Transaction<'_, Postgres>,
+    client_id: Uuid,
+    refresh_token_hash: &[u8],
+) -> Result<Option<OAuthToken>> {
+    let token = query_as!(
+        OAuthToken,
+        r#"
+        UPDATE oauth_tokens
+        SET revoked_at = NOW()
+        WHERE refresh_token_hash = $1
+          AND client_id = $2
+          AND refresh_expires_at > NOW()
+          AND revoked_at IS NULL
+        RETURNING id, client_id, user_id, access_expires_at, refresh_expires_at, revoked_at, created_at
+        "#,
+        refresh_token_hash,
+        client_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(token)
+}
+
+/// Revoke the token that either an access token or a refresh token of the
+/// client belongs to. Returns whether anything was revoked.
+pub async fn revoke_oauth_token(
+    tx: &mut Transaction<'_, Postgres>,
+    client_id: Uuid,
+    token_hash: &[u8],
+) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE oauth_tokens
+        SET revoked_at = NOW()
+        WHERE (access_token_hash = $1 OR refresh_token_hash = $1)
+          AND client_id = $2
+          AND revoked_at IS NULL
+        "#,
+        token_hash,
+        client_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Revoke every token issued to a user, e.g. when their password changes
+pub async fn revoke_oauth_tokens_for_user(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE oauth_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
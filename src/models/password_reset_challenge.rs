@@ -1,36 +1,67 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sha::sha256;
+use openssl::sign::Signer;
+use rand::rngs::OsRng;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use sqlx::{query_as, Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::AppConfig;
+
+const TOKEN_LENGTH: usize = 32;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct PasswordResetChallenge {
     pub id: Uuid,
     pub user_id: Uuid,
     pub email: String,
-    pub token: Uuid,
+    #[serde(skip_serializing)]
+    pub token_hash: Vec<u8>,
     pub expires_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Generate the secret sent in a reset link. Only its hash is ever stored.
+pub fn generate_password_reset_token() -> String {
+    let mut token = [0u8; TOKEN_LENGTH];
+    OsRng.fill_bytes(&mut token);
+    hex::encode(token)
+}
+
+/// HMAC-SHA256 of a reset token under `password_reset_token_key`, or its
+/// plain SHA-256 when no key is configured
+pub fn hash_password_reset_token(config: &AppConfig, token: &str) -> Result<Vec<u8>> {
+    let key = hex::decode(config.password_reset_token_key.trim())?;
+    if key.is_empty() {
+        return Ok(sha256(token.as_bytes()).to_vec());
+    }
+    let key = PKey::hmac(&key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(token.as_bytes())?;
+    Ok(signer.sign_to_vec()?)
+}
+
 pub async fn create_password_reset_challenge(
     tx: &mut Transaction<'_, Postgres>,
     user_id: Uuid,
     email: &str,
-    token: Uuid,
+    token_hash: &[u8],
     expires_at: DateTime<Utc>,
 ) -> Result<PasswordResetChallenge> {
     let challenge = query_as!(
         PasswordResetChallenge,
         r#"
-        INSERT INTO password_reset_challenges (user_id, email, token, expires_at)
+        INSERT INTO password_reset_challenges (user_id, email, token_hash, expires_at)
         VALUES ($1, $2, $3, $4)
-        RETURNING *
+        RETURNING id, user_id, email, token_hash, expires_at, created_at
         "#,
         user_id,
         email,
-        token,
+        token_hash,
         expires_at
     )
     .fetch_one(&mut **tx)
@@ -39,19 +70,18 @@ pub async fn create_password_reset_challenge(
     Ok(challenge)
 }
 
-pub async fn find_password_reset_challenge_by_token(
+pub async fn find_password_reset_challenge_by_token_hash(
     tx: &mut Transaction<'_, Postgres>,
-    token: Uuid,
+    token_hash: &[u8],
 ) -> Result<Option<PasswordResetChallenge>> {
     let challenge = query_as!(
         PasswordResetChallenge,
         r#"
-        SELECT * FROM password_reset_challenges
-        WHERE token = $1 AND expires_at > NOW()
-        ORDER BY created_at DESC
-        LIMIT 1
+        SELECT id, user_id, email, token_hash, expires_at, created_at
+        FROM password_reset_challenges
+        WHERE token_hash = $1 AND expires_at > NOW()
         "#,
-        token
+        token_hash
     )
     .fetch_optional(&mut **tx)
     .await?;
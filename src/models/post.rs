@@ -4,11 +4,13 @@ use chrono_tz::Asia::Seoul;
 use humantime::format_duration;
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
-use sqlx::{postgres::types::PgInterval, query, Postgres, Transaction};
+use sqlx::{postgres::types::PgInterval, query, query_as, Postgres, Transaction};
 use std::collections::HashMap;
 use uuid::Uuid;
 
 use super::community::CommunityVisibility;
+use super::image::{delete_image_thumbnails, replay_object_key};
+use super::user::FeedAlgorithm;
 
 type PostData = (
     Option<String>,        // title
@@ -24,12 +26,18 @@ type PostData = (
     i64,                   // paint_duration_ms
 );
 
+/// How many earlier versions of a post are kept; older ones are dropped as
+/// the post is edited further
+pub const POST_REVISION_LIMIT: i64 = 20;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Type, Serialize, Deserialize)]
 #[sqlx(type_name = "post_deletion_reason", rename_all = "snake_case")]
 pub enum PostDeletionReason {
     UserDeleted,
     Cascade,
     Moderation,
+    /// Hidden while the author's account awaits deletion
+    AccountDeletion,
 }
 
 #[derive(Clone, Debug)]
@@ -113,6 +121,24 @@ pub struct SerializableDraftPost {
     pub image_width: i32,
     pub image_height: i32,
     pub updated_at: DateTime<Utc>,
+    /// Last canvas snapshot saved by the drawing editor
+    pub autosaved_at: Option<DateTime<Utc>>,
+}
+
+/// The image a draft was drawn on, as needed to replace it with a newer
+/// snapshot of the canvas
+pub struct DraftImage {
+    pub image_id: Uuid,
+    pub image_filename: String,
+    pub webp_filename: Option<String>,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// A snapshot of the canvas, uploaded under its own content hash
+pub struct AutosavedImage {
+    pub image_filename: String,
+    pub webp_filename: Option<String>,
 }
 
 /// Structured post detail response for JSON API
@@ -127,12 +153,15 @@ pub struct PostDetailForJson {
     pub paint_duration: String,
     pub viewer_count: i32,
     pub image_filename: String,
+    pub webp_filename: Option<String>,
     pub image_width: i32,
     pub image_height: i32,
     pub image_tool: String,
     pub is_sensitive: bool,
     pub allow_relay: bool,
     pub published_at_utc: Option<String>,
+    /// Waiting for review by the community's owner or a moderator
+    pub pending_approval: bool,
     pub community_id: Option<Uuid>,
     pub community_name: Option<String>,
     pub community_slug: Option<String>,
@@ -141,6 +170,16 @@ pub struct PostDetailForJson {
     pub parent_post_id: Option<Uuid>,
 }
 
+/// A version of a post's title and content replaced by an edit
+#[derive(Clone, Debug, Serialize)]
+pub struct PostRevision {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub edited_at: DateTime<Utc>,
+}
+
 // Minimal structs for post thumbnails (grid/list views)
 #[derive(Serialize)]
 pub struct PostThumbnail {
@@ -192,6 +231,8 @@ pub enum Tool {
     #[serde(rename = "neo-cucumber")]
     #[sqlx(rename = "neo-cucumber")]
     NeoCucumber,
+    // Uploaded rather than drawn, e.g. avatars
+    Upload,
 }
 
 pub struct PostDraft {
@@ -202,6 +243,7 @@ pub struct PostDraft {
     pub width: i32,
     pub height: i32,
     pub image_filename: String,
+    pub webp_filename: Option<String>,
     pub replay_filename: Option<String>,
     pub tool: Tool,
     pub parent_post_id: Option<Uuid>,
@@ -335,6 +377,75 @@ pub async fn find_published_public_posts_by_author_id(
         .collect())
 }
 
+/// An author's most recent posts for their public feed, leaving out posts in
+/// communities that aren't public and sensitive posts the viewer hasn't opted into
+pub async fn find_feed_posts_by_author_id(
+    tx: &mut Transaction<'_, Postgres>,
+    author_id: Uuid,
+    limit: i64,
+    viewer_user_id: Option<Uuid>,
+    viewer_show_sensitive: bool,
+) -> Result<Vec<SerializablePost>> {
+    let q = query!(
+        r#"
+            SELECT
+                posts.id,
+                posts.title,
+                posts.author_id,
+                users.login_name,
+                images.paint_duration,
+                images.stroke_count,
+                images.image_filename,
+                images.width,
+                images.height,
+                images.replay_filename,
+                posts.viewer_count,
+                posts.is_sensitive,
+                posts.published_at,
+                posts.created_at,
+                posts.updated_at
+            FROM posts
+            LEFT JOIN images ON posts.image_id = images.id
+            LEFT JOIN users ON posts.author_id = users.id
+            LEFT JOIN communities ON posts.community_id = communities.id
+            WHERE posts.author_id = $1
+            AND (communities.visibility = 'public' OR posts.community_id IS NULL)
+            AND posts.published_at IS NOT NULL
+            AND posts.deleted_at IS NULL
+            AND (posts.is_sensitive = false OR $3 = true OR posts.author_id = $4)
+            -- Posts in sensitive communities are hidden along with sensitive posts
+            AND (communities.is_sensitive IS NOT TRUE OR $3 = true OR posts.author_id = $4)
+            ORDER BY posts.published_at DESC
+            LIMIT $2
+        "#,
+        author_id,
+        limit,
+        viewer_show_sensitive,
+        viewer_user_id
+    );
+    let result = q.fetch_all(&mut **tx).await?;
+    Ok(result
+        .into_iter()
+        .map(|row| SerializablePost {
+            id: row.id,
+            title: row.title,
+            author_id: row.author_id,
+            user_login_name: Some(row.login_name),
+            paint_duration: row.paint_duration.microseconds.to_string(),
+            stroke_count: row.stroke_count,
+            image_filename: row.image_filename,
+            image_width: row.width,
+            image_height: row.height,
+            replay_filename: row.replay_filename,
+            is_sensitive: row.is_sensitive,
+            viewer_count: row.viewer_count,
+            published_at: row.published_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
 pub async fn find_published_posts_by_author_id(
     tx: &mut Transaction<'_, Postgres>,
     author_id: Uuid,
@@ -388,6 +499,220 @@ pub async fn find_published_posts_by_author_id(
         .collect())
 }
 
+/// Count an author's published posts that may be federated
+/// Posts in private communities are never exposed over ActivityPub
+pub async fn count_federatable_posts_by_author_id(
+    tx: &mut Transaction<'_, Postgres>,
+    author_id: Uuid,
+) -> Result<i64> {
+    let result = query!(
+        r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts
+            LEFT JOIN communities ON posts.community_id = communities.id
+            WHERE posts.author_id = $1
+            AND posts.published_at IS NOT NULL
+            AND posts.deleted_at IS NULL
+            AND (communities.id IS NULL OR communities.visibility != 'private')
+        "#,
+        author_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.count)
+}
+
+/// Get IDs of an author's federatable posts, newest first
+pub async fn find_federatable_post_ids_by_author_id(
+    tx: &mut Transaction<'_, Postgres>,
+    author_id: Uuid,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Uuid>> {
+    let result = query!(
+        r#"
+            SELECT posts.id
+            FROM posts
+            LEFT JOIN communities ON posts.community_id = communities.id
+            WHERE posts.author_id = $1
+            AND posts.published_at IS NOT NULL
+            AND posts.deleted_at IS NULL
+            AND (communities.id IS NULL OR communities.visibility != 'private')
+            ORDER BY posts.published_at DESC
+            LIMIT $2 OFFSET $3
+        "#,
+        author_id,
+        limit,
+        offset
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(result.into_iter().map(|row| row.id).collect())
+}
+
+/// Maximum number of posts a user can pin to their profile
+pub const MAX_PINNED_POSTS: i64 = 5;
+
+/// Whether a post can be pinned to its author's profile.
+/// Only published, non-reply posts outside private communities can be pinned.
+pub async fn is_post_pinnable(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    post_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM posts
+                LEFT JOIN communities ON posts.community_id = communities.id
+                WHERE posts.id = $1
+                AND posts.author_id = $2
+                AND posts.published_at IS NOT NULL
+                AND posts.deleted_at IS NULL
+                AND posts.parent_post_id IS NULL
+                AND (communities.id IS NULL OR communities.visibility != 'private')
+            ) AS "exists!"
+        "#,
+        post_id,
+        user_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.exists)
+}
+
+/// Pin a post to its author's profile
+pub async fn pin_post(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    post_id: Uuid,
+) -> Result<()> {
+    if !is_post_pinnable(tx, user_id, post_id).await? {
+        anyhow::bail!("Post cannot be pinned");
+    }
+
+    let pinned_count = query!(
+        r#"SELECT COUNT(*) AS "count!" FROM pinned_posts WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_one(&mut **tx)
+    .await?
+    .count;
+    if pinned_count >= MAX_PINNED_POSTS {
+        anyhow::bail!("Maximum number of pinned posts reached");
+    }
+
+    query!(
+        r#"
+            INSERT INTO pinned_posts (user_id, post_id)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id, post_id) DO NOTHING
+        "#,
+        user_id,
+        post_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn unpin_post(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    post_id: Uuid,
+) -> Result<()> {
+    query!(
+        "DELETE FROM pinned_posts WHERE user_id = $1 AND post_id = $2",
+        user_id,
+        post_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn is_post_pinned(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    post_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"SELECT EXISTS(SELECT 1 FROM pinned_posts WHERE user_id = $1 AND post_id = $2) AS "exists!""#,
+        user_id,
+        post_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.exists)
+}
+
+/// Get the posts a user has pinned to their profile, most recently pinned first.
+/// Posts that stopped being pinnable (deleted or moved to a private community) are skipped.
+pub async fn find_pinned_posts_by_user(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<Vec<SerializableProfilePost>> {
+    let result = query!(
+        r#"
+            SELECT
+                posts.id,
+                posts.author_id,
+                posts.title,
+                posts.viewer_count,
+                images.paint_duration,
+                images.stroke_count,
+                images.image_filename,
+                images.width,
+                images.height,
+                images.replay_filename,
+                posts.published_at,
+                posts.created_at,
+                posts.updated_at,
+                communities.visibility as "visibility?: CommunityVisibility"
+            FROM pinned_posts
+            JOIN posts ON pinned_posts.post_id = posts.id
+            LEFT JOIN images ON posts.image_id = images.id
+            LEFT JOIN communities ON posts.community_id = communities.id
+            WHERE pinned_posts.user_id = $1
+            AND posts.author_id = $1
+            AND posts.published_at IS NOT NULL
+            AND posts.deleted_at IS NULL
+            AND (communities.id IS NULL OR communities.visibility != 'private')
+            ORDER BY pinned_posts.pinned_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(result
+        .into_iter()
+        .map(|row| SerializableProfilePost {
+            id: row.id,
+            title: row.title,
+            viewer_count: row.viewer_count,
+            author_id: row.author_id,
+            paint_duration: row.paint_duration.microseconds.to_string(),
+            stroke_count: row.stroke_count,
+            image_filename: row.image_filename,
+            image_width: row.width,
+            image_height: row.height,
+            replay_filename: row.replay_filename,
+            published_at: row.published_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            community_visibility: row.visibility,
+        })
+        .collect())
+}
+
 pub async fn find_draft_posts_by_author_id(
     tx: &mut Transaction<'_, Postgres>,
     author_id: Uuid,
@@ -400,6 +725,7 @@ pub async fn find_draft_posts_by_author_id(
                 posts.content,
                 posts.community_id,
                 posts.updated_at,
+                posts.autosaved_at,
                 images.image_filename,
                 images.width,
                 images.height,
@@ -429,10 +755,92 @@ pub async fn find_draft_posts_by_author_id(
             image_width: row.width,
             image_height: row.height,
             updated_at: row.updated_at,
+            autosaved_at: row.autosaved_at,
         })
         .collect())
 }
 
+/// Lock a draft's image for replacing with an autosaved snapshot. Returns
+/// `None` unless the post is the author's own draft that hasn't been
+/// published, scheduled or submitted for approval. The post row stays locked
+/// until the transaction ends, so it can't be published in the meantime.
+pub async fn lock_draft_image_for_autosave(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    author_id: Uuid,
+) -> Result<Option<DraftImage>> {
+    let image = query_as!(
+        DraftImage,
+        "
+            SELECT
+                images.id AS image_id,
+                images.image_filename,
+                images.webp_filename,
+                images.width,
+                images.height
+            FROM posts
+            JOIN images ON posts.image_id = images.id
+            WHERE posts.id = $1
+            AND posts.author_id = $2
+            AND posts.published_at IS NULL
+            AND posts.scheduled_for IS NULL
+            AND posts.pending_approval_at IS NULL
+            AND posts.deleted_at IS NULL
+            FOR UPDATE OF posts
+        ",
+        post_id,
+        author_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+    Ok(image)
+}
+
+/// Point a draft's image at a newer snapshot of the canvas. The thumbnails of
+/// the old drawing are forgotten; the snapshot's own are added by the caller.
+/// Returns when the draft was saved and the R2 keys of the replaced PNG, WebP
+/// and thumbnails, which nothing refers to anymore.
+pub async fn mark_draft_autosaved(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    image: &DraftImage,
+    snapshot: &AutosavedImage,
+) -> Result<(DateTime<Utc>, Vec<String>)> {
+    let mut stale_keys = Vec::new();
+    if snapshot.image_filename != image.image_filename {
+        query!(
+            "UPDATE images SET image_filename = $2, webp_filename = $3 WHERE id = $1",
+            image.image_id,
+            snapshot.image_filename,
+            snapshot.webp_filename
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let prefix = image.image_filename.get(..2).unwrap_or_default();
+        stale_keys.push(format!("image/{}/{}", prefix, image.image_filename));
+        if let Some(ref webp_filename) = image.webp_filename {
+            stale_keys.push(format!("image/{}/{}", prefix, webp_filename));
+        }
+        stale_keys.extend(
+            delete_image_thumbnails(tx, std::slice::from_ref(&image.image_filename)).await?,
+        );
+    }
+    let autosaved_at = query!(
+        r#"
+            UPDATE posts
+            SET updated_at = now(), autosaved_at = now()
+            WHERE id = $1
+            RETURNING autosaved_at AS "autosaved_at!"
+        "#,
+        post_id
+    )
+    .fetch_one(&mut **tx)
+    .await?
+    .autosaved_at;
+    Ok((autosaved_at, stale_keys))
+}
+
 pub async fn find_published_posts_by_community_id(
     tx: &mut Transaction<'_, Postgres>,
     community_id: Uuid,
@@ -595,10 +1003,10 @@ pub async fn find_recent_posts_by_communities(
         .collect())
 }
 
-pub async fn create_post(
+async fn insert_post_draft_image(
     tx: &mut Transaction<'_, Postgres>,
-    post_draft: PostDraft,
-) -> Result<SerializablePost> {
+    post_draft: &PostDraft,
+) -> Result<Uuid> {
     let image = query!(
         r#"
             INSERT INTO images (
@@ -607,9 +1015,10 @@ pub async fn create_post(
                 width,
                 height,
                 image_filename,
+                webp_filename,
                 replay_filename,
                 tool
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING id
         "#,
         post_draft.paint_duration,
@@ -617,12 +1026,22 @@ pub async fn create_post(
         post_draft.width,
         post_draft.height,
         post_draft.image_filename,
+        post_draft.webp_filename,
         post_draft.replay_filename,
         post_draft.tool as _
     )
     .fetch_one(&mut **tx)
     .await?;
 
+    Ok(image.id)
+}
+
+pub async fn create_post(
+    tx: &mut Transaction<'_, Postgres>,
+    post_draft: PostDraft,
+) -> Result<SerializablePost> {
+    let image_id = insert_post_draft_image(tx, &post_draft).await?;
+
     let post = query!(
         "
             INSERT INTO posts (
@@ -636,31 +1055,160 @@ pub async fn create_post(
             RETURNING id, created_at, updated_at
         ",
         post_draft.author_id,
-        image.id,
+        image_id,
         post_draft.community_id,
         false,
         post_draft.parent_post_id
     )
-    .fetch_one(&mut **tx)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    // The cover image takes the first slot of the gallery
+    query!(
+        "
+            INSERT INTO post_images (post_id, image_id, position)
+            VALUES ($1, $2, 0)
+        ",
+        post.id,
+        image_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(SerializablePost {
+        id: post.id,
+        title: None,
+        author_id: post_draft.author_id,
+        user_login_name: None,
+        paint_duration: post_draft.paint_duration.microseconds.to_string(),
+        stroke_count: post_draft.stroke_count,
+        image_filename: post_draft.image_filename,
+        image_width: post_draft.width,
+        image_height: post_draft.height,
+        replay_filename: post_draft.replay_filename,
+        is_sensitive: false,
+        viewer_count: 0,
+        published_at: None,
+        created_at: post.created_at,
+        updated_at: post.updated_at,
+    })
+}
+
+/// Upper bound on the number of images a single post can hold
+pub const MAX_POST_IMAGES: i64 = 10;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PostImage {
+    pub image_id: Uuid,
+    pub position: i32,
+    pub image_filename: String,
+    pub webp_filename: Option<String>,
+    pub width: i32,
+    pub height: i32,
+    pub replay_filename: Option<String>,
+    pub replay_archived_at: Option<DateTime<Utc>>,
+}
+
+/// Get the images of a post in gallery order
+pub async fn find_post_images(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+) -> Result<Vec<PostImage>> {
+    let images = query_as!(
+        PostImage,
+        r#"
+        SELECT
+            images.id AS image_id,
+            post_images.position,
+            images.image_filename,
+            images.webp_filename,
+            images.width,
+            images.height,
+            images.replay_filename,
+            images.replay_archived_at
+        FROM post_images
+        JOIN images ON post_images.image_id = images.id
+        WHERE post_images.post_id = $1
+        ORDER BY post_images.position
+        "#,
+        post_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(images)
+}
+
+/// Append a drawing to the end of a post's gallery
+/// Returns None if the post already holds `MAX_POST_IMAGES` images
+pub async fn add_post_image(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    post_draft: PostDraft,
+) -> Result<Option<i32>> {
+    // Lock the post so concurrent uploads can't claim the same slot
+    query!("SELECT id FROM posts WHERE id = $1 FOR UPDATE", post_id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+    let slots = query!(
+        r#"
+        SELECT COUNT(*) AS "count!", MAX(position) AS last_position
+        FROM post_images
+        WHERE post_id = $1
+        "#,
+        post_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if slots.count >= MAX_POST_IMAGES {
+        return Ok(None);
+    }
+
+    let image_id = insert_post_draft_image(tx, &post_draft).await?;
+    let position = slots.last_position.map_or(0, |last| last + 1);
+
+    query!(
+        "
+            INSERT INTO post_images (post_id, image_id, position)
+            VALUES ($1, $2, $3)
+        ",
+        post_id,
+        image_id,
+        position
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(Some(position))
+}
+
+/// Count the images of each post, for badges on thumbnail grids
+pub async fn count_post_images_by_post_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    post_ids: &[Uuid],
+) -> Result<HashMap<Uuid, i64>> {
+    if post_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let counts = query!(
+        r#"
+        SELECT post_id, COUNT(*) AS "count!"
+        FROM post_images
+        WHERE post_id = ANY($1)
+        GROUP BY post_id
+        "#,
+        post_ids
+    )
+    .fetch_all(&mut **tx)
     .await?;
 
-    Ok(SerializablePost {
-        id: post.id,
-        title: None,
-        author_id: post_draft.author_id,
-        user_login_name: None,
-        paint_duration: post_draft.paint_duration.microseconds.to_string(),
-        stroke_count: post_draft.stroke_count,
-        image_filename: post_draft.image_filename,
-        image_width: post_draft.width,
-        image_height: post_draft.height,
-        replay_filename: post_draft.replay_filename,
-        is_sensitive: false,
-        viewer_count: 0,
-        published_at: None,
-        created_at: post.created_at,
-        updated_at: post.updated_at,
-    })
+    Ok(counts
+        .into_iter()
+        .map(|row| (row.post_id, row.count))
+        .collect())
 }
 
 pub async fn increment_post_viewer_count(
@@ -679,6 +1227,22 @@ pub async fn increment_post_viewer_count(
     Ok(())
 }
 
+/// Whether the post existed but has been soft-deleted, as opposed to never having existed
+pub async fn is_post_deleted(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<bool> {
+    let result = query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM posts WHERE id = $1 AND deleted_at IS NOT NULL
+        ) as "exists!"
+        "#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.exists)
+}
+
 pub async fn find_post_by_id(
     tx: &mut Transaction<'_, Postgres>,
     id: Uuid,
@@ -697,22 +1261,33 @@ pub async fn find_post_by_id(
                 images.width,
                 images.height,
                 images.image_filename,
+                images.webp_filename,
                 images.replay_filename,
+                images.replay_archived_at,
                 posts.viewer_count,
                 posts.published_at,
+                posts.scheduled_for,
+                posts.pending_approval_at,
                 posts.created_at,
                 posts.updated_at,
+                posts.edited_at,
                 posts.allow_relay,
                 posts.parent_post_id,
                 users.display_name AS display_name,
                 users.login_name AS login_name,
                 communities.id AS "community_id?",
                 communities.name AS "community_name?",
-                communities.slug AS "community_slug?"
+                communities.slug AS "community_slug?",
+                gallery.image_ids AS "gallery_image_ids?: Vec<Uuid>"
             FROM posts
             LEFT JOIN images ON posts.image_id = images.id
             LEFT JOIN communities ON posts.community_id = communities.id
             LEFT JOIN users ON posts.author_id = users.id
+            LEFT JOIN LATERAL (
+                SELECT array_agg(post_images.image_id ORDER BY post_images.position) AS image_ids
+                FROM post_images
+                WHERE post_images.post_id = posts.id
+            ) gallery ON TRUE
             WHERE posts.id = $1
             AND posts.deleted_at IS NULL
         "#,
@@ -744,6 +1319,21 @@ pub async fn find_post_by_id(
         );
 
         map.insert("image_id".to_string(), Some(row.image_id.to_string()));
+        let gallery_image_ids = row.gallery_image_ids.unwrap_or_default();
+        map.insert(
+            "image_count".to_string(),
+            Some(gallery_image_ids.len().max(1).to_string()),
+        );
+        map.insert(
+            "image_ids".to_string(),
+            Some(
+                gallery_image_ids
+                    .iter()
+                    .map(Uuid::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        );
         map.insert(
             "image_tool".to_string(),
             Some(row.image_tool.unwrap_or_default()),
@@ -751,6 +1341,13 @@ pub async fn find_post_by_id(
         map.insert("image_width".to_string(), Some(row.width.to_string()));
         map.insert("image_height".to_string(), Some(row.height.to_string()));
         map.insert("image_filename".to_string(), Some(row.image_filename));
+        map.insert("webp_filename".to_string(), row.webp_filename);
+        map.insert(
+            "replay_key".to_string(),
+            row.replay_filename
+                .as_deref()
+                .map(|filename| replay_object_key(filename, row.replay_archived_at.is_some())),
+        );
         map.insert("replay_filename".to_string(), row.replay_filename);
         map.insert(
             "viewer_count".to_string(),
@@ -783,6 +1380,25 @@ pub async fn find_post_by_id(
             }
         }
 
+        map.insert(
+            "scheduled_for".to_string(),
+            row.scheduled_for.map(|scheduled_for| {
+                scheduled_for
+                    .with_timezone(&Seoul)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            }),
+        );
+        map.insert(
+            "pending_approval_at".to_string(),
+            row.pending_approval_at.map(|pending_approval_at| {
+                pending_approval_at
+                    .with_timezone(&Seoul)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            }),
+        );
+
         let updated_at_seoul = row.updated_at.with_timezone(&Seoul);
         let updated_at_human_readable = updated_at_seoul.format("%Y-%m-%d %H:%M").to_string();
         map.insert("updated_at".to_string(), Some(updated_at_human_readable));
@@ -792,6 +1408,15 @@ pub async fn find_post_by_id(
             "updated_at_utc".to_string(),
             Some(row.updated_at.to_rfc3339()),
         );
+        map.insert(
+            "edited_at".to_string(),
+            row.edited_at.map(|edited_at| {
+                edited_at
+                    .with_timezone(&Seoul)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            }),
+        );
         map.insert(
             "community_id".to_string(),
             row.community_id.map(|id| id.to_string()),
@@ -826,9 +1451,11 @@ pub async fn find_post_detail_for_json(
                 images.width,
                 images.height,
                 images.image_filename,
+                images.webp_filename,
                 images.tool::text AS image_tool,
                 posts.viewer_count,
                 posts.published_at,
+                posts.pending_approval_at IS NOT NULL AS \"pending_approval!\",
                 users.display_name AS display_name,
                 users.login_name AS login_name,
                 communities.name AS \"community_name?\",
@@ -863,12 +1490,14 @@ pub async fn find_post_detail_for_json(
             paint_duration: paint_duration_human_readable.to_string(),
             viewer_count: row.viewer_count,
             image_filename: row.image_filename,
+            webp_filename: row.webp_filename,
             image_width: row.width,
             image_height: row.height,
             image_tool: row.image_tool.unwrap_or_else(|| "neo".to_string()),
             is_sensitive: row.is_sensitive,
             allow_relay: row.allow_relay,
             published_at_utc: row.published_at.map(|dt| dt.to_rfc3339()),
+            pending_approval: row.pending_approval,
             community_id: row.community_id,
             community_name: row.community_name,
             community_slug: row.community_slug,
@@ -1156,22 +1785,357 @@ pub async fn publish_post(
             UPDATE posts
             SET
                 published_at = now(),
+                scheduled_for = NULL,
+                pending_approval_at = NULL,
+                title = $1,
+                content = $2,
+                is_sensitive = $3,
+                allow_relay = $4
+            WHERE id = $5
+        ",
+        title,
+        content,
+        is_sensitive,
+        allow_relay,
+        id
+    );
+    q.execute(&mut **tx).await?;
+    Ok(())
+}
+
+pub async fn schedule_post(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    title: String,
+    content: String,
+    is_sensitive: bool,
+    allow_relay: bool,
+    scheduled_for: DateTime<Utc>,
+) -> Result<()> {
+    let q = query!(
+        "
+            UPDATE posts
+            SET
+                scheduled_for = $1,
+                schedule_failed_at = NULL,
+                pending_approval_at = NULL,
+                approved_at = NULL,
+                title = $2,
+                content = $3,
+                is_sensitive = $4,
+                allow_relay = $5
+            WHERE id = $6
+                AND published_at IS NULL
+        ",
+        scheduled_for,
+        title,
+        content,
+        is_sensitive,
+        allow_relay,
+        id
+    );
+    q.execute(&mut **tx).await?;
+    Ok(())
+}
+
+pub struct ScheduledPost {
+    pub id: Uuid,
+    pub author_id: Uuid,
+    pub community_id: Option<Uuid>,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub is_sensitive: bool,
+    pub allow_relay: bool,
+    /// Already approved by a moderator and only waiting for the posting window
+    pub approved: bool,
+}
+
+/// Lock the scheduled post that has been due the longest, skipping any that
+/// another publisher is already working on
+pub async fn lock_next_due_scheduled_post(
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Option<ScheduledPost>> {
+    let post = sqlx::query_as!(
+        ScheduledPost,
+        r#"
+            SELECT
+                id, author_id, community_id, title, content, is_sensitive, allow_relay,
+                approved_at IS NOT NULL AS "approved!"
+            FROM posts
+            WHERE scheduled_for <= NOW()
+                AND published_at IS NULL
+                AND deleted_at IS NULL
+            ORDER BY scheduled_for
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+    Ok(post)
+}
+
+/// Hold an unpublished post until its community's posting window opens. The
+/// scheduled post publisher picks it up then.
+pub async fn defer_post_until(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    publish_at: DateTime<Utc>,
+    approved: bool,
+) -> Result<()> {
+    query!(
+        "
+            UPDATE posts
+            SET
+                scheduled_for = $1,
+                pending_approval_at = NULL,
+                approved_at = CASE WHEN $2 THEN now() ELSE approved_at END
+            WHERE id = $3
+                AND published_at IS NULL
+        ",
+        publish_at,
+        approved,
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Unschedule a post that couldn't be published so it isn't retried on every
+/// tick. It stays a draft its author can schedule again.
+pub async fn mark_scheduled_post_failed(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<()> {
+    query!(
+        "
+            UPDATE posts
+            SET scheduled_for = NULL, schedule_failed_at = now()
+            WHERE id = $1
+                AND published_at IS NULL
+        ",
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Holds a post for review by its community's owner and moderators. It stays
+/// unpublished, and so unfederated, until one of them approves it.
+pub async fn submit_post_for_approval(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    title: String,
+    content: String,
+    is_sensitive: bool,
+    allow_relay: bool,
+) -> Result<()> {
+    let q = query!(
+        "
+            UPDATE posts
+            SET
+                pending_approval_at = now(),
+                scheduled_for = NULL,
                 title = $1,
                 content = $2,
                 is_sensitive = $3,
                 allow_relay = $4
             WHERE id = $5
+                AND published_at IS NULL
         ",
         title,
         content,
         is_sensitive,
         allow_relay,
         id
-    );
-    q.execute(&mut **tx).await?;
+    );
+    q.execute(&mut **tx).await?;
+    Ok(())
+}
+
+pub struct PendingApprovalPost {
+    pub id: Uuid,
+    pub author_id: Uuid,
+    pub community_id: Uuid,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub is_sensitive: bool,
+    pub allow_relay: bool,
+}
+
+/// A post waiting for review. The row stays locked until the transaction ends, so
+/// two moderators can't review it at the same time.
+pub async fn find_pending_approval_post(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<Option<PendingApprovalPost>> {
+    let post = sqlx::query_as!(
+        PendingApprovalPost,
+        r#"
+            SELECT
+                id,
+                author_id,
+                community_id AS "community_id!",
+                title,
+                content,
+                is_sensitive,
+                allow_relay
+            FROM posts
+            WHERE id = $1
+                AND pending_approval_at IS NOT NULL
+                AND published_at IS NULL
+                AND deleted_at IS NULL
+                AND community_id IS NOT NULL
+            FOR UPDATE
+        "#,
+        id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+    Ok(post)
+}
+
+/// Sends a rejected post back to its author's drafts
+pub async fn return_pending_post_to_drafts(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<()> {
+    query!(
+        "
+            UPDATE posts
+            SET pending_approval_at = NULL
+            WHERE id = $1
+                AND published_at IS NULL
+        ",
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct SerializablePendingApprovalPost {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub author_login_name: String,
+    pub author_display_name: String,
+    pub image_filename: String,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub pending_approval_at: DateTime<Utc>,
+}
+
+/// The review queue of a community, oldest submission first
+pub async fn find_pending_approval_posts_by_community_id(
+    tx: &mut Transaction<'_, Postgres>,
+    community_id: Uuid,
+) -> Result<Vec<SerializablePendingApprovalPost>> {
+    let result = query!(
+        r#"
+            SELECT
+                posts.id,
+                posts.title,
+                posts.content,
+                posts.pending_approval_at AS "pending_approval_at!",
+                users.login_name,
+                users.display_name,
+                images.image_filename,
+                images.width,
+                images.height
+            FROM posts
+            JOIN users ON posts.author_id = users.id
+            JOIN images ON posts.image_id = images.id
+            WHERE posts.community_id = $1
+                AND posts.pending_approval_at IS NOT NULL
+                AND posts.published_at IS NULL
+                AND posts.deleted_at IS NULL
+            ORDER BY posts.pending_approval_at
+        "#,
+        community_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(result
+        .into_iter()
+        .map(|row| SerializablePendingApprovalPost {
+            id: row.id,
+            title: row.title,
+            content: row.content,
+            author_login_name: row.login_name,
+            author_display_name: row.display_name,
+            image_filename: row.image_filename,
+            image_width: row.width,
+            image_height: row.height,
+            pending_approval_at: row.pending_approval_at,
+        })
+        .collect())
+}
+
+/// Marks a published post as held back from federation until its author qualifies
+pub async fn defer_post_federation(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<()> {
+    query!(
+        "UPDATE posts SET federation_deferred_at = NOW() WHERE id = $1",
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub struct DeferredFederationPost {
+    pub id: Uuid,
+    pub author_id: Uuid,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Held-back posts whose authors have since verified their email or have accounts created
+/// at or before `ready_cutoff`. The rows stay locked until the transaction ends.
+pub async fn find_deferred_posts_ready_for_federation(
+    tx: &mut Transaction<'_, Postgres>,
+    ready_cutoff: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<DeferredFederationPost>> {
+    let posts = sqlx::query_as!(
+        DeferredFederationPost,
+        "
+            SELECT posts.id, posts.author_id, posts.published_at
+            FROM posts
+            JOIN users ON posts.author_id = users.id
+            WHERE posts.federation_deferred_at IS NOT NULL
+                AND (users.email_verified_at IS NOT NULL OR users.created_at <= $1)
+            ORDER BY posts.federation_deferred_at
+            LIMIT $2
+            FOR UPDATE OF posts SKIP LOCKED
+        ",
+        ready_cutoff,
+        limit
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(posts)
+}
+
+pub async fn clear_post_federation_deferral(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<()> {
+    query!(
+        "UPDATE posts SET federation_deferred_at = NULL WHERE id = $1",
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
     Ok(())
 }
 
+/// Edit a post. When the title or content changes, the version it replaces
+/// is kept in `post_revisions`, which holds at most `POST_REVISION_LIMIT`
+/// versions per post.
 pub async fn edit_post(
     tx: &mut Transaction<'_, Postgres>,
     id: Uuid,
@@ -1180,6 +2144,23 @@ pub async fn edit_post(
     is_sensitive: bool,
     allow_relay: bool,
 ) -> Result<()> {
+    let revised = query!(
+        "
+            INSERT INTO post_revisions (post_id, title, content, edited_at)
+            SELECT id, title, content, now()
+            FROM posts
+            WHERE id = $1
+            AND (title IS DISTINCT FROM $2 OR content IS DISTINCT FROM $3)
+        ",
+        id,
+        title,
+        content
+    )
+    .execute(&mut **tx)
+    .await?
+    .rows_affected()
+        > 0;
+
     let q = query!(
         "
             UPDATE posts
@@ -1187,19 +2168,62 @@ pub async fn edit_post(
                 title = $1,
                 content = $2,
                 is_sensitive = $3,
-                allow_relay = $4
+                allow_relay = $4,
+                edited_at = CASE WHEN $6 THEN now() ELSE edited_at END
             WHERE id = $5
         ",
         title,
         content,
         is_sensitive,
         allow_relay,
-        id
+        id,
+        revised
     );
     q.execute(&mut **tx).await?;
+
+    if revised {
+        query!(
+            "
+                DELETE FROM post_revisions
+                WHERE post_id = $1
+                AND id NOT IN (
+                    SELECT id
+                    FROM post_revisions
+                    WHERE post_id = $1
+                    ORDER BY edited_at DESC
+                    LIMIT $2
+                )
+            ",
+            id,
+            POST_REVISION_LIMIT
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
     Ok(())
 }
 
+/// Earlier versions of a post, newest first. Each one was replaced at its
+/// `edited_at`.
+pub async fn find_post_revisions(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+) -> Result<Vec<PostRevision>> {
+    let revisions = query_as!(
+        PostRevision,
+        "
+            SELECT id, post_id, title, content, edited_at
+            FROM post_revisions
+            WHERE post_id = $1
+            ORDER BY edited_at DESC
+        ",
+        post_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(revisions)
+}
+
 pub async fn edit_post_community(
     tx: &mut Transaction<'_, Postgres>,
     id: Uuid,
@@ -1436,6 +2460,259 @@ pub async fn find_public_community_posts(
         .collect())
 }
 
+/// Home feed posts in the order the viewer asked for.
+///
+/// Anonymous viewers always get the chronological feed.
+pub async fn find_home_feed_posts(
+    tx: &mut Transaction<'_, Postgres>,
+    algorithm: FeedAlgorithm,
+    limit: i64,
+    offset: i64,
+    viewer_user_id: Option<Uuid>,
+    viewer_show_sensitive: bool,
+) -> Result<Vec<SerializablePostForHome>> {
+    match (algorithm, viewer_user_id) {
+        (FeedAlgorithm::Engagement, _) => {
+            find_ranked_public_community_posts(
+                tx,
+                limit,
+                offset,
+                viewer_user_id,
+                viewer_show_sensitive,
+            )
+            .await
+        }
+        (FeedAlgorithm::FollowingFirst, Some(viewer_user_id)) => {
+            find_following_first_public_community_posts(
+                tx,
+                limit,
+                offset,
+                viewer_user_id,
+                viewer_show_sensitive,
+            )
+            .await
+        }
+        _ => {
+            find_public_community_posts(tx, limit, offset, viewer_user_id, viewer_show_sensitive)
+                .await
+        }
+    }
+}
+
+/// Public community posts ordered by engagement.
+///
+/// The score is `reactions * 2 + comments + views / 10`, divided by
+/// `(age in hours + 2) ^ 1.5` so that older posts gradually sink.
+pub async fn find_ranked_public_community_posts(
+    tx: &mut Transaction<'_, Postgres>,
+    limit: i64,
+    offset: i64,
+    viewer_user_id: Option<Uuid>,
+    viewer_show_sensitive: bool,
+) -> Result<Vec<SerializablePostForHome>> {
+    let q = query!(
+        "
+            SELECT
+                posts.id,
+                posts.title,
+                posts.author_id,
+                users.login_name,
+                images.paint_duration,
+                images.stroke_count,
+                images.image_filename,
+                images.width,
+                images.height,
+                images.replay_filename,
+                posts.viewer_count,
+                posts.is_sensitive,
+                posts.published_at,
+                posts.created_at,
+                posts.updated_at
+            FROM posts
+            LEFT JOIN images ON posts.image_id = images.id
+            LEFT JOIN communities ON posts.community_id = communities.id
+            LEFT JOIN users ON posts.author_id = users.id
+            LEFT JOIN (
+                SELECT post_id, COUNT(*) AS reaction_count
+                FROM reactions
+                GROUP BY post_id
+            ) reaction_counts ON reaction_counts.post_id = posts.id
+            LEFT JOIN (
+                SELECT post_id, COUNT(*) AS comment_count
+                FROM comments
+                WHERE deleted_at IS NULL
+                GROUP BY post_id
+            ) comment_counts ON comment_counts.post_id = posts.id
+            WHERE communities.visibility = 'public'
+            AND posts.published_at IS NOT NULL
+            AND posts.deleted_at IS NULL
+            AND (posts.is_sensitive = false OR $3 = true OR posts.author_id = $4)
+            ORDER BY
+                (
+                    COALESCE(reaction_counts.reaction_count, 0) * 2
+                    + COALESCE(comment_counts.comment_count, 0)
+                    + posts.viewer_count / 10.0
+                ) / POWER(EXTRACT(EPOCH FROM now() - posts.published_at) / 3600 + 2, 1.5) DESC,
+                posts.published_at DESC
+            LIMIT $1
+            OFFSET $2
+        ",
+        limit,
+        offset,
+        viewer_show_sensitive,
+        viewer_user_id
+    );
+    let result = q.fetch_all(&mut **tx).await?;
+    Ok(result
+        .into_iter()
+        .map(|row| SerializablePostForHome {
+            id: row.id,
+            title: row.title,
+            author_id: row.author_id,
+            user_login_name: row.login_name,
+            paint_duration: row.paint_duration.microseconds.to_string(),
+            stroke_count: row.stroke_count,
+            image_filename: row.image_filename,
+            image_width: row.width,
+            image_height: row.height,
+            replay_filename: row.replay_filename,
+            is_sensitive: row.is_sensitive,
+            viewer_count: row.viewer_count,
+            published_at: row.published_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
+/// Public community posts with posts by users the viewer follows first,
+/// followed by every other post, each group newest first
+pub async fn find_following_first_public_community_posts(
+    tx: &mut Transaction<'_, Postgres>,
+    limit: i64,
+    offset: i64,
+    viewer_user_id: Uuid,
+    viewer_show_sensitive: bool,
+) -> Result<Vec<SerializablePostForHome>> {
+    let q = query!(
+        r#"
+            SELECT
+                feed.id AS "id!",
+                feed.title,
+                feed.author_id AS "author_id!",
+                feed.login_name AS "login_name!",
+                feed.paint_duration AS "paint_duration!",
+                feed.stroke_count AS "stroke_count!",
+                feed.image_filename AS "image_filename!",
+                feed.width AS "width!",
+                feed.height AS "height!",
+                feed.replay_filename,
+                feed.viewer_count AS "viewer_count!",
+                feed.is_sensitive AS "is_sensitive!",
+                feed.published_at,
+                feed.created_at AS "created_at!",
+                feed.updated_at AS "updated_at!"
+            FROM (
+                SELECT
+                    0 AS tier,
+                    posts.id,
+                    posts.title,
+                    posts.author_id,
+                    users.login_name,
+                    images.paint_duration,
+                    images.stroke_count,
+                    images.image_filename,
+                    images.width,
+                    images.height,
+                    images.replay_filename,
+                    posts.viewer_count,
+                    posts.is_sensitive,
+                    posts.published_at,
+                    posts.created_at,
+                    posts.updated_at
+                FROM posts
+                LEFT JOIN images ON posts.image_id = images.id
+                LEFT JOIN communities ON posts.community_id = communities.id
+                LEFT JOIN users ON posts.author_id = users.id
+                WHERE communities.visibility = 'public'
+                AND posts.published_at IS NOT NULL
+                AND posts.deleted_at IS NULL
+                AND (posts.is_sensitive = false OR $3 = true OR posts.author_id = $4)
+                AND EXISTS (
+                    SELECT 1
+                    FROM actors author_actor
+                    JOIN follows ON author_actor.id = follows.following_actor_id
+                    JOIN actors follower_actor ON follows.follower_actor_id = follower_actor.id
+                    WHERE author_actor.user_id = posts.author_id
+                    AND follower_actor.user_id = $4
+                )
+                UNION ALL
+                SELECT
+                    1 AS tier,
+                    posts.id,
+                    posts.title,
+                    posts.author_id,
+                    users.login_name,
+                    images.paint_duration,
+                    images.stroke_count,
+                    images.image_filename,
+                    images.width,
+                    images.height,
+                    images.replay_filename,
+                    posts.viewer_count,
+                    posts.is_sensitive,
+                    posts.published_at,
+                    posts.created_at,
+                    posts.updated_at
+                FROM posts
+                LEFT JOIN images ON posts.image_id = images.id
+                LEFT JOIN communities ON posts.community_id = communities.id
+                LEFT JOIN users ON posts.author_id = users.id
+                WHERE communities.visibility = 'public'
+                AND posts.published_at IS NOT NULL
+                AND posts.deleted_at IS NULL
+                AND (posts.is_sensitive = false OR $3 = true OR posts.author_id = $4)
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM actors author_actor
+                    JOIN follows ON author_actor.id = follows.following_actor_id
+                    JOIN actors follower_actor ON follows.follower_actor_id = follower_actor.id
+                    WHERE author_actor.user_id = posts.author_id
+                    AND follower_actor.user_id = $4
+                )
+            ) feed
+            ORDER BY feed.tier, feed.published_at DESC
+            LIMIT $1
+            OFFSET $2
+        "#,
+        limit,
+        offset,
+        viewer_show_sensitive,
+        viewer_user_id
+    );
+    let result = q.fetch_all(&mut **tx).await?;
+    Ok(result
+        .into_iter()
+        .map(|row| SerializablePostForHome {
+            id: row.id,
+            title: row.title,
+            author_id: row.author_id,
+            user_login_name: row.login_name,
+            paint_duration: row.paint_duration.microseconds.to_string(),
+            stroke_count: row.stroke_count,
+            image_filename: row.image_filename,
+            image_width: row.width,
+            image_height: row.height,
+            replay_filename: row.replay_filename,
+            is_sensitive: row.is_sensitive,
+            viewer_count: row.viewer_count,
+            published_at: row.published_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+        .collect())
+}
+
 pub async fn find_public_community_posts_excluding_from_community_owner(
     tx: &mut Transaction<'_, Postgres>,
     community_owner_id: Uuid,
@@ -1613,6 +2890,17 @@ pub async fn delete_post(
     .execute(&mut **tx)
     .await?;
 
+    // Earlier versions of the title and content go with the current ones
+    query!(
+        "
+        DELETE FROM post_revisions
+        WHERE post_id = $1
+        ",
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+
     Ok(())
 }
 
@@ -1652,6 +2940,19 @@ pub async fn soft_delete_community_posts(
     .execute(&mut **tx)
     .await?;
 
+    // The content is cleared above, so its earlier versions must not outlive it
+    query!(
+        "
+        DELETE FROM post_revisions
+        WHERE post_id IN (
+            SELECT id FROM posts WHERE community_id = $1
+        )
+        ",
+        community_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
     Ok(())
 }
 
@@ -1702,3 +3003,174 @@ pub async fn delete_post_with_activity(
 
     Ok(())
 }
+
+#[derive(Serialize)]
+pub struct PostSearchResult {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub author_id: Uuid,
+    pub user_login_name: String,
+    pub image_filename: String,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub is_sensitive: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub rank: f32,
+}
+
+/// Full-text search over published post titles and content, best matches first.
+/// Posts in private communities are only returned to members.
+pub async fn search_published_posts(
+    tx: &mut Transaction<'_, Postgres>,
+    query: &str,
+    limit: i64,
+    offset: i64,
+    viewer_user_id: Option<Uuid>,
+    show_sensitive: bool,
+) -> Result<Vec<PostSearchResult>> {
+    let posts = sqlx::query_as!(
+        PostSearchResult,
+        r#"
+        SELECT
+            posts.id,
+            posts.title,
+            posts.content,
+            posts.author_id,
+            users.login_name AS user_login_name,
+            images.image_filename,
+            images.width AS image_width,
+            images.height AS image_height,
+            posts.is_sensitive,
+            posts.published_at,
+            ts_rank(posts.search_vector, websearch_to_tsquery('simple', $1)) AS "rank!"
+        FROM posts
+        JOIN users ON posts.author_id = users.id
+        JOIN images ON posts.image_id = images.id
+        LEFT JOIN communities ON posts.community_id = communities.id
+        WHERE posts.search_vector @@ websearch_to_tsquery('simple', $1)
+          AND posts.published_at IS NOT NULL
+          AND posts.deleted_at IS NULL
+          AND (
+              posts.community_id IS NULL
+              OR (
+                  communities.deleted_at IS NULL
+                  AND (
+                      communities.visibility = 'public'
+                      OR (
+                          communities.visibility = 'private'
+                          AND EXISTS (
+                              SELECT 1 FROM community_members
+                              WHERE community_members.community_id = communities.id
+                              AND community_members.user_id = $4
+                          )
+                      )
+                  )
+              )
+          )
+          AND (posts.is_sensitive = false OR $5 = true OR posts.author_id = $4)
+        ORDER BY
+            ts_rank(posts.search_vector, websearch_to_tsquery('simple', $1)) DESC,
+            posts.published_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        query,
+        limit,
+        offset,
+        viewer_user_id,
+        show_sensitive
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(posts)
+}
+
+pub async fn count_search_published_posts(
+    tx: &mut Transaction<'_, Postgres>,
+    query: &str,
+    viewer_user_id: Option<Uuid>,
+    show_sensitive: bool,
+) -> Result<i64> {
+    let count = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM posts
+        LEFT JOIN communities ON posts.community_id = communities.id
+        WHERE posts.search_vector @@ websearch_to_tsquery('simple', $1)
+          AND posts.published_at IS NOT NULL
+          AND posts.deleted_at IS NULL
+          AND (
+              posts.community_id IS NULL
+              OR (
+                  communities.deleted_at IS NULL
+                  AND (
+                      communities.visibility = 'public'
+                      OR (
+                          communities.visibility = 'private'
+                          AND EXISTS (
+                              SELECT 1 FROM community_members
+                              WHERE community_members.community_id = communities.id
+                              AND community_members.user_id = $2
+                          )
+                      )
+                  )
+              )
+          )
+          AND (posts.is_sensitive = false OR $3 = true OR posts.author_id = $2)
+        "#,
+        query,
+        viewer_user_id,
+        show_sensitive
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_fixtures;
+
+    #[sqlx::test]
+    async fn held_back_posts_are_federated_once_the_account_is_old_enough(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let newcomer = test_fixtures::user(&mut tx, "newcomer").await;
+        let regular = test_fixtures::user(&mut tx, "regular").await;
+        sqlx::query!(
+            "UPDATE users SET created_at = NOW() - INTERVAL '2 days' WHERE id = $1",
+            regular.id
+        )
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+        let mut held_back = Vec::new();
+        for author in [&newcomer, &regular] {
+            let post = test_fixtures::draft_post(&mut tx, author.id, None).await;
+            publish_post(
+                &mut tx,
+                post.id,
+                "title".into(),
+                "content".into(),
+                false,
+                true,
+            )
+            .await
+            .unwrap();
+            defer_post_federation(&mut tx, post.id).await.unwrap();
+            held_back.push(post.id);
+        }
+
+        let ready_cutoff = Utc::now() - chrono::TimeDelta::hours(24);
+        let ready = find_deferred_posts_ready_for_federation(&mut tx, ready_cutoff, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            ready.iter().map(|post| post.id).collect::<Vec<_>>(),
+            vec![held_back[1]]
+        );
+    }
+}
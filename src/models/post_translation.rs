@@ -0,0 +1,56 @@
+use anyhow::Result;
+use sqlx::{query, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::models::user::Language;
+
+/// The stored translation of a post, if one was made from its current content
+pub async fn find_post_translation(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    target_lang: Language,
+    source_content: &str,
+) -> Result<Option<String>> {
+    let translation = query!(
+        r#"
+        SELECT translated_content
+        FROM post_translations
+        WHERE post_id = $1 AND target_lang = $2 AND source_content = $3
+        "#,
+        post_id,
+        target_lang as _,
+        source_content
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(translation.map(|row| row.translated_content))
+}
+
+/// Store a translation, replacing one made from older content
+pub async fn save_post_translation(
+    tx: &mut Transaction<'_, Postgres>,
+    post_id: Uuid,
+    target_lang: Language,
+    source_content: &str,
+    translated_content: &str,
+) -> Result<()> {
+    query!(
+        r#"
+        INSERT INTO post_translations (post_id, target_lang, source_content, translated_content)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (post_id, target_lang) DO UPDATE
+        SET source_content = EXCLUDED.source_content,
+            translated_content = EXCLUDED.translated_content,
+            created_at = now()
+        "#,
+        post_id,
+        target_lang as _,
+        source_content,
+        translated_content
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
@@ -7,6 +7,9 @@ use uuid::Uuid;
 // Available emoji reactions
 pub const AVAILABLE_EMOJIS: &[&str] = &["❤️", "🎉", "😂", "😲", "🤔", "😢", "👀"];
 
+// Emoji that ActivityPub Like activities are mapped to
+pub const LIKE_EMOJI: &str = "❤️";
+
 #[derive(Clone, Debug, Serialize)]
 pub struct Reaction {
     pub iri: String,
@@ -35,7 +38,16 @@ pub struct SerializableReaction {
     pub actor_login_name: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Debug)]
+pub struct ApLike {
+    pub iri: String,
+    pub reaction_iri: String,
+    pub post_id: Uuid,
+    pub actor_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Serialize)]
 pub struct ReactionCount {
     pub emoji: String,
     pub count: i64,
@@ -322,3 +334,57 @@ pub async fn find_user_reaction(
 
     Ok(reaction)
 }
+
+pub fn generate_like_iri(domain: &str, post_id: Uuid) -> String {
+    format!(
+        "https://{}/ap/likes/{}/{}",
+        domain,
+        post_id,
+        uuid::Uuid::new_v4()
+    )
+}
+
+pub async fn create_ap_like(
+    tx: &mut Transaction<'_, Postgres>,
+    iri: String,
+    reaction_iri: String,
+    post_id: Uuid,
+    actor_id: Uuid,
+) -> Result<Option<ApLike>> {
+    let like = sqlx::query_as!(
+        ApLike,
+        r#"
+        INSERT INTO ap_likes (iri, reaction_iri, post_id, actor_id)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING
+        RETURNING iri, reaction_iri, post_id, actor_id, created_at
+        "#,
+        iri,
+        reaction_iri,
+        post_id,
+        actor_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(like)
+}
+
+pub async fn find_ap_like_by_reaction_iri(
+    tx: &mut Transaction<'_, Postgres>,
+    reaction_iri: &str,
+) -> Result<Option<ApLike>> {
+    let like = sqlx::query_as!(
+        ApLike,
+        r#"
+        SELECT iri, reaction_iri, post_id, actor_id, created_at
+        FROM ap_likes
+        WHERE reaction_iri = $1
+        "#,
+        reaction_iri
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(like)
+}
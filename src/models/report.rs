@@ -0,0 +1,271 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, Postgres, Transaction, Type};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[sqlx(type_name = "post_report_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PostReportReason {
+    Spam,
+    Harassment,
+    SexualContent,
+    Violence,
+    Copyright,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[sqlx(type_name = "post_report_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PostReportStatus {
+    Pending,
+    Resolved,
+    Dismissed,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PostReport {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub post_id: Uuid,
+    pub reason: PostReportReason,
+    pub detail: String,
+    pub status: PostReportStatus,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Pending report with the details admins need to triage it
+#[derive(Clone, Debug, Serialize)]
+pub struct PendingPostReport {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub reason: PostReportReason,
+    pub detail: String,
+    pub created_at: DateTime<Utc>,
+    pub reporter_login_name: String,
+    pub post_title: Option<String>,
+    pub post_author_login_name: String,
+    pub post_image_filename: Option<String>,
+}
+
+/// Create a report for a post
+/// Returns None if the reporter already has a pending report for this post
+pub async fn create_report(
+    tx: &mut Transaction<'_, Postgres>,
+    reporter_id: Uuid,
+    post_id: Uuid,
+    reason: PostReportReason,
+    detail: String,
+) -> Result<Option<PostReport>> {
+    let report = query_as!(
+        PostReport,
+        r#"
+        INSERT INTO post_reports (reporter_id, post_id, reason, detail)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (reporter_id, post_id) WHERE status = 'pending' DO NOTHING
+        RETURNING
+            id,
+            reporter_id,
+            post_id,
+            reason as "reason: _",
+            detail,
+            status as "status: _",
+            resolved_by,
+            resolved_at,
+            created_at,
+            updated_at
+        "#,
+        reporter_id,
+        post_id,
+        reason as _,
+        detail
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(report)
+}
+
+/// Get all pending reports, oldest first
+pub async fn get_pending_reports(
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<PendingPostReport>> {
+    let reports = query_as!(
+        PendingPostReport,
+        r#"
+        SELECT
+            post_reports.id,
+            post_reports.post_id,
+            post_reports.reason as "reason: _",
+            post_reports.detail,
+            post_reports.created_at,
+            reporters.login_name as reporter_login_name,
+            posts.title as post_title,
+            authors.login_name as post_author_login_name,
+            images.image_filename as "post_image_filename?"
+        FROM post_reports
+        JOIN users reporters ON post_reports.reporter_id = reporters.id
+        JOIN posts ON post_reports.post_id = posts.id
+        JOIN users authors ON posts.author_id = authors.id
+        LEFT JOIN images ON posts.image_id = images.id
+        WHERE post_reports.status = 'pending'
+        ORDER BY post_reports.created_at ASC
+        "#
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(reports)
+}
+
+/// Close a pending report as resolved or dismissed
+/// Returns false if the report doesn't exist or was already closed
+pub async fn resolve_report(
+    tx: &mut Transaction<'_, Postgres>,
+    report_id: Uuid,
+    resolved_by: Uuid,
+    status: PostReportStatus,
+) -> Result<bool> {
+    if status == PostReportStatus::Pending {
+        anyhow::bail!("A report can't be resolved back to pending");
+    }
+
+    let result = query!(
+        r#"
+        UPDATE post_reports
+        SET status = $3, resolved_by = $2, resolved_at = now(), updated_at = now()
+        WHERE id = $1 AND status = 'pending'
+        "#,
+        report_id,
+        resolved_by,
+        status as _
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Report about one or more ActivityPub objects
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    pub id: Uuid,
+    pub iri: Option<String>,
+    pub reporter_actor_id: Uuid,
+    pub object_iris: Vec<String>,
+    pub comment: String,
+    pub status: PostReportStatus,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Open report with the details admins need to triage it
+#[derive(Clone, Debug, Serialize)]
+pub struct OpenReport {
+    pub id: Uuid,
+    pub object_iris: Vec<String>,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub reporter_handle: String,
+    pub reporter_url: String,
+    /// Received from another instance rather than filed here
+    pub is_remote: bool,
+}
+
+/// Record a report about ActivityPub objects
+/// Returns None if a report with the same Flag IRI was already recorded
+pub async fn create_object_report(
+    tx: &mut Transaction<'_, Postgres>,
+    iri: Option<&str>,
+    reporter_actor_id: Uuid,
+    object_iris: &[String],
+    comment: &str,
+) -> Result<Option<Report>> {
+    let report = query_as!(
+        Report,
+        r#"
+        INSERT INTO reports (iri, reporter_actor_id, object_iris, comment)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (iri) DO NOTHING
+        RETURNING
+            id,
+            iri,
+            reporter_actor_id,
+            object_iris,
+            comment,
+            status as "status: _",
+            resolved_by,
+            resolved_at,
+            created_at
+        "#,
+        iri,
+        reporter_actor_id,
+        object_iris,
+        comment
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(report)
+}
+
+/// Get all open reports about ActivityPub objects, oldest first
+pub async fn get_open_object_reports(
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<OpenReport>> {
+    let reports = query_as!(
+        OpenReport,
+        r#"
+        SELECT
+            reports.id,
+            reports.object_iris,
+            reports.comment,
+            reports.created_at,
+            actors.handle as reporter_handle,
+            actors.url as reporter_url,
+            (actors.user_id IS NULL) as "is_remote!"
+        FROM reports
+        JOIN actors ON reports.reporter_actor_id = actors.id
+        WHERE reports.status = 'pending'
+        ORDER BY reports.created_at ASC
+        "#
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(reports)
+}
+
+/// Close an open report about ActivityPub objects as resolved or dismissed
+/// Returns false if the report doesn't exist or was already closed
+pub async fn resolve_object_report(
+    tx: &mut Transaction<'_, Postgres>,
+    report_id: Uuid,
+    resolved_by: Uuid,
+    status: PostReportStatus,
+) -> Result<bool> {
+    if status == PostReportStatus::Pending {
+        anyhow::bail!("A report can't be resolved back to pending");
+    }
+
+    let result = query!(
+        r#"
+        UPDATE reports
+        SET status = $3, resolved_by = $2, resolved_at = now()
+        WHERE id = $1 AND status = 'pending'
+        "#,
+        report_id,
+        resolved_by,
+        status as _
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,68 @@
+//! Rows for tests that run against a database with `#[sqlx::test]`
+
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use super::community::{create_community, Community, CommunityDraft, CommunityVisibility};
+use super::post::{create_post, PostDraft, SerializablePost, Tool};
+use super::user::{create_user, User, UserDraft};
+use crate::AppConfig;
+
+pub fn config() -> AppConfig {
+    AppConfig::new_from_file_and_env("config/sample.toml").expect("sample config should load")
+}
+
+/// A user with an actor, like one who just signed up
+pub async fn user(tx: &mut Transaction<'_, Postgres>, login_name: &str) -> User {
+    let draft = UserDraft {
+        login_name: login_name.to_string(),
+        // Never signed in with, so not worth hashing
+        password_hash: String::new(),
+        display_name: login_name.to_string(),
+    };
+    create_user(tx, draft, &config())
+        .await
+        .expect("user should be created")
+}
+
+pub async fn community(
+    tx: &mut Transaction<'_, Postgres>,
+    owner_id: Uuid,
+    slug: &str,
+    visibility: CommunityVisibility,
+) -> Community {
+    let draft = CommunityDraft {
+        name: slug.to_string(),
+        slug: slug.to_string(),
+        description: String::new(),
+        visibility,
+        is_sensitive: false,
+    };
+    create_community(tx, owner_id, draft)
+        .await
+        .expect("community should be created")
+}
+
+/// An unpublished drawing, with an image of its own
+pub async fn draft_post(
+    tx: &mut Transaction<'_, Postgres>,
+    author_id: Uuid,
+    community_id: Option<Uuid>,
+) -> SerializablePost {
+    let draft = PostDraft {
+        author_id,
+        community_id,
+        paint_duration: Default::default(),
+        stroke_count: 1,
+        width: 300,
+        height: 300,
+        image_filename: format!("{}.png", Uuid::new_v4().simple()),
+        webp_filename: None,
+        replay_filename: None,
+        tool: Tool::Neo,
+        parent_post_id: None,
+    };
+    create_post(tx, draft)
+        .await
+        .expect("post should be created")
+}
@@ -0,0 +1,268 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rand::seq::SliceRandom;
+use sqlx::{query, query_as, Postgres, Transaction};
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+use crate::models::user::User;
+use crate::AppConfig;
+
+pub const BACKUP_CODE_COUNT: usize = 8;
+const BACKUP_CODE_LENGTH: usize = 10;
+// Lowercase letters and digits without look-alikes (0/o, 1/l/i)
+const BACKUP_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Clone, Debug)]
+pub struct TotpSecret {
+    pub user_id: Uuid,
+    /// Nonce followed by the AES-256-GCM encrypted secret
+    pub secret: Vec<u8>,
+    pub enabled_at: Option<DateTime<Utc>>,
+    /// SHA-256 hashes of the remaining unused backup codes
+    pub backup_codes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TotpSecret {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+fn cipher(config: &AppConfig) -> Result<Aes256Gcm> {
+    let key = hex::decode(config.totp_encryption_key.trim())?;
+    if key.len() != 32 {
+        return Err(anyhow::anyhow!(
+            "totp_encryption_key must be 32 bytes, hex-encoded"
+        ));
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
+
+pub fn encrypt_secret(config: &AppConfig, secret: &[u8]) -> Result<Vec<u8>> {
+    let cipher = cipher(config)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, secret)
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt TOTP secret"))?;
+
+    let mut encrypted = nonce.to_vec();
+    encrypted.extend_from_slice(&ciphertext);
+    Ok(encrypted)
+}
+
+pub fn decrypt_secret(config: &AppConfig, encrypted: &[u8]) -> Result<Vec<u8>> {
+    if encrypted.len() <= NONCE_LENGTH {
+        return Err(anyhow::anyhow!("Encrypted TOTP secret is truncated"));
+    }
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+    cipher(config)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt TOTP secret"))
+}
+
+pub fn generate_secret() -> Result<Vec<u8>> {
+    Secret::generate_secret()
+        .to_bytes()
+        .map_err(|e| anyhow::anyhow!("Failed to generate TOTP secret: {:?}", e))
+}
+
+/// Builds an RFC 6238 TOTP (SHA-1, 6 digits, 30 second step) that authenticator apps understand
+pub fn build_totp(config: &AppConfig, secret: Vec<u8>, login_name: &str) -> Result<TOTP> {
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret,
+        Some(config.domain.clone()),
+        login_name.to_string(),
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid TOTP parameters: {}", e))
+}
+
+pub fn generate_backup_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..BACKUP_CODE_COUNT)
+        .map(|_| {
+            let code: String = (0..BACKUP_CODE_LENGTH)
+                .map(|_| *BACKUP_CODE_ALPHABET.choose(&mut rng).unwrap() as char)
+                .collect();
+            format!("{}-{}", &code[..5], &code[5..])
+        })
+        .collect()
+}
+
+pub fn hash_backup_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    sha256::digest(normalized)
+}
+
+pub async fn find_totp_secret(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<Option<TotpSecret>> {
+    let secret = query_as!(
+        TotpSecret,
+        r#"
+        SELECT
+            user_id,
+            secret,
+            enabled_at,
+            ARRAY(SELECT jsonb_array_elements_text(backup_codes)) as "backup_codes!",
+            created_at,
+            updated_at
+        FROM totp_secrets
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(secret)
+}
+
+pub async fn is_totp_enabled(tx: &mut Transaction<'_, Postgres>, user_id: Uuid) -> Result<bool> {
+    let result = query!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM totp_secrets
+            WHERE user_id = $1 AND enabled_at IS NOT NULL
+        ) as "exists!"
+        "#,
+        user_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.exists)
+}
+
+/// Stores a not-yet-verified secret, replacing any earlier unfinished setup.
+/// An already enabled secret is left untouched.
+pub async fn save_pending_totp_secret(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    encrypted_secret: &[u8],
+) -> Result<()> {
+    query!(
+        r#"
+        INSERT INTO totp_secrets (user_id, secret)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE
+        SET secret = EXCLUDED.secret,
+            backup_codes = '[]'::jsonb,
+            updated_at = NOW()
+        WHERE totp_secrets.enabled_at IS NULL
+        "#,
+        user_id,
+        encrypted_secret
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn enable_totp(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    backup_code_hashes: &[String],
+) -> Result<()> {
+    query!(
+        r#"
+        UPDATE totp_secrets
+        SET enabled_at = NOW(),
+            backup_codes = to_jsonb($2::text[]),
+            updated_at = NOW()
+        WHERE user_id = $1 AND enabled_at IS NULL
+        "#,
+        user_id,
+        backup_code_hashes
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_totp_secret(tx: &mut Transaction<'_, Postgres>, user_id: Uuid) -> Result<()> {
+    query!(
+        r#"
+        DELETE FROM totp_secrets
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Removes the backup code if it is still unused. Returns whether it was accepted.
+pub async fn consume_backup_code(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    code: &str,
+) -> Result<bool> {
+    let code_hash = hash_backup_code(code);
+    let result = query!(
+        r#"
+        UPDATE totp_secrets
+        SET backup_codes = backup_codes - $2::text,
+            updated_at = NOW()
+        WHERE user_id = $1
+            AND enabled_at IS NOT NULL
+            AND backup_codes ? $2::text
+        RETURNING user_id
+        "#,
+        user_id,
+        code_hash
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(result.is_some())
+}
+
+pub fn verify_totp_code(
+    config: &AppConfig,
+    totp_secret: &TotpSecret,
+    login_name: &str,
+    code: &str,
+) -> Result<bool> {
+    let secret = decrypt_secret(config, &totp_secret.secret)?;
+    let totp = build_totp(config, secret, login_name)?;
+    Ok(totp.check_current(code.trim())?)
+}
+
+/// Checks a second-factor code during login, accepting either the current
+/// authenticator code or one of the user's unused backup codes.
+pub async fn verify_login_code(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &AppConfig,
+    user: &User,
+    code: &str,
+) -> Result<bool> {
+    let totp_secret = match find_totp_secret(tx, user.id).await? {
+        Some(secret) if secret.is_enabled() => secret,
+        _ => return Ok(false),
+    };
+
+    if verify_totp_code(config, &totp_secret, &user.login_name, code)? {
+        return Ok(true);
+    }
+
+    consume_backup_code(tx, user.id, code).await
+}
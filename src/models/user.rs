@@ -10,7 +10,10 @@ use serde::{Deserialize, Serialize};
 use sqlx::types::Uuid;
 use sqlx::{query, query_as, PgPool, Postgres, Transaction, Type};
 
-use crate::models::actor::create_actor_for_user;
+use crate::models::actor::{create_actor_for_user, Actor};
+use crate::models::follow::get_follower_shared_inboxes_for_actor;
+use crate::models::image::{delete_image_thumbnails, replay_object_key};
+use crate::models::oauth::revoke_oauth_tokens_for_user;
 use crate::AppConfig;
 
 pub struct UserDraft {
@@ -40,7 +43,7 @@ impl UserDraft {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Debug, Type, PartialEq)]
 #[sqlx(type_name = "preferred_language", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum Language {
@@ -48,6 +51,61 @@ pub enum Language {
     Ja,
     En,
     Zh,
+    Es,
+}
+
+impl Language {
+    /// Every variant, in the order languages are offered for negotiation
+    pub const ALL: [Language; 5] = [
+        Language::Ko,
+        Language::Ja,
+        Language::En,
+        Language::Zh,
+        Language::Es,
+    ];
+
+    /// Key of the language in `LOCALES`
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Language::Ko => "ko",
+            Language::Ja => "ja",
+            Language::En => "en",
+            Language::Zh => "zh",
+            Language::Es => "es",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "ko" => Some(Language::Ko),
+            "ja" => Some(Language::Ja),
+            "en" => Some(Language::En),
+            "zh" => Some(Language::Zh),
+            "es" => Some(Language::Es),
+            _ => None,
+        }
+    }
+}
+
+// Needed to bind and read `language_fallbacks`, a `preferred_language[]` column
+impl sqlx::postgres::PgHasArrayType for Language {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("_preferred_language")
+    }
+}
+
+/// How posts on the home feed are ordered for a user
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Type, PartialEq, Eq)]
+#[sqlx(type_name = "feed_algorithm", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FeedAlgorithm {
+    /// Newest posts first
+    #[default]
+    Chronological,
+    /// Posts scored by reactions, comments and views, decaying with age
+    Engagement,
+    /// Posts by followed users first, then everything else chronologically
+    FollowingFirst,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -63,8 +121,14 @@ pub struct User {
     pub created_at: DateTime<Utc>,
     pub banner_id: Option<Uuid>,
     pub preferred_language: Option<Language>,
+    /// Languages to try, in order, for messages missing from the preferred one.
+    /// English is always the last resort.
+    pub language_fallbacks: Vec<Language>,
     pub deleted_at: Option<DateTime<Utc>>,
     pub show_sensitive_content: bool,
+    pub is_admin: bool,
+    /// Whether new followers have to be approved before they can follow
+    pub is_locked: bool,
 }
 
 impl User {
@@ -98,8 +162,11 @@ pub async fn update_user_preferred_language(
                 updated_at,
                 banner_id,
                 preferred_language AS "preferred_language: _",
+                language_fallbacks AS "language_fallbacks: _",
                 deleted_at,
-                show_sensitive_content
+                show_sensitive_content,
+                is_admin,
+                is_locked
         "#,
         preferred_language as _,
         id,
@@ -117,11 +184,112 @@ pub async fn update_user_preferred_language(
         updated_at: result.updated_at,
         banner_id: result.banner_id,
         preferred_language: result.preferred_language,
+        language_fallbacks: result.language_fallbacks,
         deleted_at: result.deleted_at,
         show_sensitive_content: result.show_sensitive_content,
+        is_admin: result.is_admin,
+        is_locked: result.is_locked,
     })
 }
 
+pub async fn update_user_language_fallbacks(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    language_fallbacks: &[Language],
+) -> Result<()> {
+    query!(
+        r#"
+            UPDATE users
+            SET language_fallbacks = $1, updated_at = now()
+            WHERE id = $2
+        "#,
+        language_fallbacks as &[Language],
+        id,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn update_user_avatar(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    avatar_image_id: Option<Uuid>,
+) -> Result<()> {
+    query!(
+        r#"
+        UPDATE users
+        SET avatar_image_id = $1, updated_at = now()
+        WHERE id = $2
+        "#,
+        avatar_image_id,
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Filename of the user's avatar image, if one has been uploaded
+pub async fn find_user_avatar_filename(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<Option<String>> {
+    let result = query!(
+        r#"
+        SELECT images.image_filename
+        FROM users
+        JOIN images ON users.avatar_image_id = images.id
+        WHERE users.id = $1
+        "#,
+        id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(result.map(|row| row.image_filename))
+}
+
+pub async fn find_user_feed_algorithm(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<FeedAlgorithm> {
+    let result = query!(
+        r#"
+        SELECT feed_algorithm AS "feed_algorithm: FeedAlgorithm"
+        FROM users
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(result.feed_algorithm)
+}
+
+pub async fn update_user_feed_algorithm(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    feed_algorithm: FeedAlgorithm,
+) -> Result<()> {
+    query!(
+        r#"
+        UPDATE users
+        SET feed_algorithm = $1, updated_at = now()
+        WHERE id = $2
+        "#,
+        feed_algorithm as FeedAlgorithm,
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn update_user_show_sensitive_content(
     tx: &mut Transaction<'_, Postgres>,
     id: Uuid,
@@ -144,8 +312,11 @@ pub async fn update_user_show_sensitive_content(
                 updated_at,
                 banner_id,
                 preferred_language AS "preferred_language: _",
+                language_fallbacks AS "language_fallbacks: _",
                 deleted_at,
-                show_sensitive_content
+                show_sensitive_content,
+                is_admin,
+                is_locked
         "#,
         show_sensitive_content,
         id,
@@ -163,11 +334,47 @@ pub async fn update_user_show_sensitive_content(
         updated_at: result.updated_at,
         banner_id: result.banner_id,
         preferred_language: result.preferred_language,
+        language_fallbacks: result.language_fallbacks,
         deleted_at: result.deleted_at,
         show_sensitive_content: result.show_sensitive_content,
+        is_admin: result.is_admin,
+        is_locked: result.is_locked,
     })
 }
 
+/// Also updates the user's actor, so remote servers see `manuallyApprovesFollowers`
+pub async fn update_user_is_locked(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    is_locked: bool,
+) -> Result<()> {
+    query!(
+        r#"
+            UPDATE users
+            SET is_locked = $1, updated_at = now()
+            WHERE id = $2
+        "#,
+        is_locked,
+        id,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    query!(
+        r#"
+            UPDATE actors
+            SET automatically_approves_followers = $1, updated_at = now()
+            WHERE user_id = $2
+        "#,
+        !is_locked,
+        id,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn update_user_email_verified_at(
     tx: &mut Transaction<'_, Postgres>,
     id: Uuid,
@@ -191,8 +398,11 @@ pub async fn update_user_email_verified_at(
                 updated_at,
                 banner_id,
                 preferred_language AS "preferred_language: _",
+                language_fallbacks AS "language_fallbacks: _",
                 deleted_at,
-                show_sensitive_content
+                show_sensitive_content,
+                is_admin,
+                is_locked
         "#,
         email,
         email_verified_at,
@@ -211,8 +421,11 @@ pub async fn update_user_email_verified_at(
         updated_at: result.updated_at,
         banner_id: result.banner_id,
         preferred_language: result.preferred_language,
+        language_fallbacks: result.language_fallbacks,
         deleted_at: result.deleted_at,
         show_sensitive_content: result.show_sensitive_content,
+        is_admin: result.is_admin,
+        is_locked: result.is_locked,
     })
 }
 
@@ -245,14 +458,20 @@ pub async fn update_password(
                 updated_at,
                 banner_id,
                 preferred_language AS "preferred_language: _",
+                language_fallbacks AS "language_fallbacks: _",
                 deleted_at,
-                show_sensitive_content
+                show_sensitive_content,
+                is_admin,
+                is_locked
         "#,
         password_hash,
         id,
     );
     let result = q.fetch_one(&mut **tx).await?;
 
+    // Like sessions, tokens issued to native clients end with the old password
+    revoke_oauth_tokens_for_user(tx, id).await?;
+
     Ok(User {
         id: result.id,
         login_name: result.login_name,
@@ -264,8 +483,11 @@ pub async fn update_password(
         updated_at: result.updated_at,
         banner_id: result.banner_id,
         preferred_language: result.preferred_language,
+        language_fallbacks: result.language_fallbacks,
         deleted_at: result.deleted_at,
         show_sensitive_content: result.show_sensitive_content,
+        is_admin: result.is_admin,
+        is_locked: result.is_locked,
     })
 }
 
@@ -292,8 +514,11 @@ pub async fn update_user(
                 updated_at,
                 banner_id,
                 preferred_language AS "preferred_language: _",
+                language_fallbacks AS "language_fallbacks: _",
                 deleted_at,
-                show_sensitive_content
+                show_sensitive_content,
+                is_admin,
+                is_locked
         "#,
         login_name,
         display_name,
@@ -312,8 +537,11 @@ pub async fn update_user(
         updated_at: result.updated_at,
         banner_id: result.banner_id,
         preferred_language: result.preferred_language,
+        language_fallbacks: result.language_fallbacks,
         deleted_at: result.deleted_at,
         show_sensitive_content: result.show_sensitive_content,
+        is_admin: result.is_admin,
+        is_locked: result.is_locked,
     })
 }
 
@@ -393,8 +621,11 @@ pub async fn create_user(
         updated_at: result.updated_at,
         banner_id: None,
         preferred_language: None,
+        language_fallbacks: Vec::new(),
         deleted_at: None,
         show_sensitive_content: false,
+        is_admin: false,
+        is_locked: false,
     };
 
     // Create actor for the user
@@ -418,8 +649,11 @@ pub async fn find_user_by_id(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Re
             updated_at,
             banner_id,
             preferred_language AS "preferred_language: _",
+            language_fallbacks AS "language_fallbacks: _",
             deleted_at,
-            show_sensitive_content
+            show_sensitive_content,
+            is_admin,
+            is_locked
         FROM users
         WHERE id = $1"#,
         id
@@ -445,8 +679,11 @@ pub async fn find_user_by_login_name(
             updated_at,
             banner_id,
             preferred_language AS "preferred_language: _",
+            language_fallbacks AS "language_fallbacks: _",
             deleted_at,
-            show_sensitive_content
+            show_sensitive_content,
+            is_admin,
+            is_locked
         FROM users
         WHERE login_name = $1"#,
         login_name
@@ -472,8 +709,11 @@ pub async fn find_user_by_email(
             updated_at,
             banner_id,
             preferred_language AS "preferred_language: _",
+            language_fallbacks AS "language_fallbacks: _",
             deleted_at,
-            show_sensitive_content
+            show_sensitive_content,
+            is_admin,
+            is_locked
         FROM users
         WHERE email = $1"#,
         email
@@ -481,6 +721,142 @@ pub async fn find_user_by_email(
     Ok(q.fetch_optional(&mut **tx).await?)
 }
 
+/// A single entry in a user's activity timeline
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActivityItem {
+    Post {
+        post_id: Uuid,
+        created_at: DateTime<Utc>,
+    },
+    Comment {
+        comment_id: Uuid,
+        post_id: Uuid,
+        created_at: DateTime<Utc>,
+    },
+    Reaction {
+        reaction_iri: String,
+        post_id: Uuid,
+        emoji: String,
+        created_at: DateTime<Utc>,
+    },
+}
+
+/// Keyset position in a user's activity timeline, encoded as `<rfc3339>|<item id>`
+#[derive(Clone, Debug)]
+pub struct ActivityCursor {
+    pub created_at: DateTime<Utc>,
+    pub item_id: String,
+}
+
+impl ActivityCursor {
+    pub fn encode(&self) -> String {
+        format!("{}|{}", self.created_at.to_rfc3339(), self.item_id)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let (created_at, item_id) = cursor.split_once('|')?;
+        Some(Self {
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .ok()?
+                .with_timezone(&Utc),
+            item_id: item_id.to_string(),
+        })
+    }
+}
+
+/// Get a user's published posts, comments and reactions, newest first.
+/// Returns the page of items and the cursor for the next page, if any.
+pub async fn get_user_activity(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    limit: i64,
+    cursor: Option<ActivityCursor>,
+) -> Result<(Vec<ActivityItem>, Option<ActivityCursor>)> {
+    let (cursor_created_at, cursor_item_id) = match cursor {
+        Some(cursor) => (Some(cursor.created_at), cursor.item_id),
+        None => (None, String::new()),
+    };
+
+    // Fetch one extra row to know whether another page exists
+    let rows = query!(
+        r#"
+        SELECT
+            kind AS "kind!",
+            item_id AS "item_id!",
+            post_id AS "post_id!",
+            emoji,
+            created_at AS "created_at!"
+        FROM (
+            SELECT 'post' AS kind, posts.id::text AS item_id, posts.id AS post_id,
+                   NULL::text AS emoji, posts.published_at AS created_at
+            FROM posts
+            WHERE posts.author_id = $1
+              AND posts.published_at IS NOT NULL
+              AND posts.deleted_at IS NULL
+            UNION ALL
+            SELECT 'comment', comments.id::text, comments.post_id,
+                   NULL::text, comments.created_at
+            FROM comments
+            JOIN actors ON comments.actor_id = actors.id
+            WHERE actors.user_id = $1
+              AND comments.deleted_at IS NULL
+            UNION ALL
+            SELECT 'reaction', reactions.iri, reactions.post_id,
+                   reactions.emoji, reactions.created_at
+            FROM reactions
+            JOIN actors ON reactions.actor_id = actors.id
+            WHERE actors.user_id = $1
+        ) AS activity
+        WHERE $2::timestamptz IS NULL OR (created_at, item_id) < ($2, $3)
+        ORDER BY created_at DESC, item_id DESC
+        LIMIT $4
+        "#,
+        user_id,
+        cursor_created_at,
+        cursor_item_id,
+        limit + 1,
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let has_more = rows.len() as i64 > limit;
+    let rows = &rows[..rows.len().min(limit.max(0) as usize)];
+
+    let next_cursor = if has_more {
+        rows.last().map(|row| ActivityCursor {
+            created_at: row.created_at,
+            item_id: row.item_id.clone(),
+        })
+    } else {
+        None
+    };
+
+    let items = rows
+        .iter()
+        .filter_map(|row| match row.kind.as_str() {
+            "post" => Some(ActivityItem::Post {
+                post_id: row.post_id,
+                created_at: row.created_at,
+            }),
+            "comment" => Some(ActivityItem::Comment {
+                comment_id: Uuid::parse_str(&row.item_id).ok()?,
+                post_id: row.post_id,
+                created_at: row.created_at,
+            }),
+            "reaction" => Some(ActivityItem::Reaction {
+                reaction_iri: row.item_id.clone(),
+                post_id: row.post_id,
+                emoji: row.emoji.clone().unwrap_or_default(),
+                created_at: row.created_at,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    Ok((items, next_cursor))
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct UserWithPublicPostAndBanner {
     pub login_name: String,
@@ -512,11 +888,27 @@ pub async fn find_users_with_public_posts_and_banner(
     Ok(q.fetch_all(&mut **tx).await?)
 }
 
-pub async fn delete_user(
+/// How long a deleted account can still be restored before its data is removed
+pub const ACCOUNT_DELETION_GRACE_DAYS: i32 = 30;
+
+/// What is left to announce once an account is scheduled for deletion
+pub struct ScheduledAccountDeletion {
+    pub actor: Option<Actor>,
+    /// Shared inboxes of the account's followers
+    pub follower_inboxes: Vec<String>,
+    /// When the account's data will be removed for good
+    pub finalizes_at: DateTime<Utc>,
+}
+
+/// Schedule the account for deletion after the grace period. Setting `deleted_at`
+/// signs the account out everywhere, since sessions only load users that aren't
+/// deleted, and its published posts are hidden until the deletion is either
+/// finalized or cancelled.
+pub async fn schedule_user_deletion(
     tx: &mut Transaction<'_, Postgres>,
     id: Uuid,
     password: &str,
-) -> Result<()> {
+) -> Result<ScheduledAccountDeletion> {
     // First, find the user
     let user = find_user_by_id(tx, id).await?;
     let user = user.ok_or_else(|| anyhow::anyhow!("User not found"))?;
@@ -548,12 +940,13 @@ pub async fn delete_user(
         ));
     }
 
-    // Note: Sessions are not deleted directly here because tower-sessions stores
-    // session data in binary format without a direct user_id reference.
-    // The user won't be able to log in again anyway because authentication
-    // checks filter out deleted users.
+    let actor = Actor::find_by_user_id(tx, id).await?;
+    let follower_inboxes = match actor {
+        Some(ref actor) => get_follower_shared_inboxes_for_actor(tx, actor.id).await?,
+        None => Vec::new(),
+    };
 
-    // Delete devices (will cascade automatically)
+    // Delete devices, which revokes their push tokens
     query!(
         r#"
         DELETE FROM devices
@@ -564,6 +957,132 @@ pub async fn delete_user(
     .execute(&mut **tx)
     .await?;
 
+    // Hide published posts; the reason tells them apart from posts deleted on purpose
+    query!(
+        r#"
+        UPDATE posts
+        SET
+            deleted_at = NOW(),
+            deletion_reason = 'account_deletion'
+        WHERE author_id = $1 AND deleted_at IS NULL AND published_at IS NOT NULL
+        "#,
+        id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let finalizes_at = query!(
+        r#"
+        UPDATE users
+        SET
+            deleted_at = NOW() + make_interval(days => $2),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING deleted_at as "deleted_at!"
+        "#,
+        id,
+        ACCOUNT_DELETION_GRACE_DAYS
+    )
+    .fetch_one(&mut **tx)
+    .await?
+    .deleted_at;
+
+    Ok(ScheduledAccountDeletion {
+        actor,
+        follower_inboxes,
+        finalizes_at,
+    })
+}
+
+/// The account awaiting deletion that `login_name` and `password` sign in to, if any
+pub async fn find_user_awaiting_deletion(
+    tx: &mut Transaction<'_, Postgres>,
+    login_name: &str,
+    password: &str,
+) -> Result<Option<User>> {
+    let user = match find_user_by_login_name(tx, login_name).await? {
+        Some(user) => user,
+        None => return Ok(None),
+    };
+    if !matches!(user.deleted_at, Some(deleted_at) if deleted_at > Utc::now())
+        || user.verify_password(password).is_err()
+    {
+        return Ok(None);
+    }
+    Ok(Some(user))
+}
+
+/// Restore an account scheduled for deletion, as long as the grace period hasn't
+/// run out. Returns whether the account was restored.
+pub async fn cancel_user_deletion(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<bool> {
+    let result = query!(
+        r#"
+        UPDATE users
+        SET deleted_at = NULL, updated_at = NOW()
+        WHERE id = $1 AND deleted_at > NOW() AND deletion_finalized_at IS NULL
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    query!(
+        r#"
+        UPDATE posts
+        SET deleted_at = NULL, deletion_reason = NULL
+        WHERE author_id = $1 AND deletion_reason = 'account_deletion'
+        "#,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(true)
+}
+
+/// Accounts whose grace period has run out but whose data is still there
+pub async fn find_users_due_for_deletion(
+    tx: &mut Transaction<'_, Postgres>,
+    limit: i64,
+) -> Result<Vec<Uuid>> {
+    let ids = query!(
+        r#"
+        SELECT id
+        FROM users
+        WHERE deleted_at <= NOW() AND deletion_finalized_at IS NULL
+        ORDER BY deleted_at
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| row.id)
+    .collect();
+
+    Ok(ids)
+}
+
+/// What is left to clean up outside the database once an account is deleted
+pub struct DeletedAccount {
+    /// R2 keys of the account's images, their WebP copies, thumbnails and replays
+    pub object_keys: Vec<String>,
+}
+
+/// Remove the data of an account whose grace period has run out
+pub async fn finalize_user_deletion(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<DeletedAccount> {
+    let actor = Actor::find_by_user_id(tx, id).await?;
+
     // Delete notifications (will cascade automatically)
     query!(
         r#"
@@ -576,7 +1095,7 @@ pub async fn delete_user(
     .await?;
 
     // Delete follow relationships
-    if let Some(actor) = super::actor::Actor::find_by_user_id(tx, id).await? {
+    if let Some(ref actor) = actor {
         query!(
             r#"
             DELETE FROM follows
@@ -588,15 +1107,118 @@ pub async fn delete_user(
         .await?;
     }
 
-    // Soft delete and anonymize user
+    // Tombstone posts, including drafts and the ones hidden during the grace
+    // period, the same way deleting a single post does
+    let deleted_posts = query!(
+        r#"
+        UPDATE posts
+        SET
+            deleted_at = NOW(),
+            deletion_reason = 'user_deleted',
+            title = NULL,
+            content = NULL
+        WHERE author_id = $1
+            AND (deleted_at IS NULL OR deletion_reason = 'account_deletion')
+        RETURNING id, image_id
+        "#,
+        id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    let post_ids: Vec<Uuid> = deleted_posts.iter().map(|post| post.id).collect();
+    let image_ids: Vec<Uuid> = deleted_posts.iter().map(|post| post.image_id).collect();
+
+    query!(
+        r#"
+        DELETE FROM notifications
+        WHERE post_id = ANY($1)
+        "#,
+        &post_ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    query!(
+        r#"
+        DELETE FROM post_revisions
+        WHERE post_id = ANY($1)
+        "#,
+        &post_ids
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Covers, the rest of each gallery, and the avatar
+    let deleted_images = query!(
+        r#"
+        UPDATE images
+        SET deleted_at = NOW()
+        WHERE id = ANY($1)
+            OR id IN (SELECT image_id FROM post_images WHERE post_id = ANY($3))
+            OR id = (SELECT avatar_image_id FROM users WHERE id = $2)
+        RETURNING image_filename, webp_filename, replay_filename, replay_archived_at
+        "#,
+        &image_ids,
+        id,
+        &post_ids
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let image_filenames: Vec<String> = deleted_images
+        .iter()
+        .map(|image| image.image_filename.clone())
+        .collect();
+    let mut object_keys = delete_image_thumbnails(tx, &image_filenames).await?;
+    for image in deleted_images {
+        object_keys.push(format!(
+            "image/{}/{}",
+            image.image_filename.get(..2).unwrap_or_default(),
+            image.image_filename
+        ));
+        if let Some(webp_filename) = image.webp_filename {
+            object_keys.push(format!(
+                "image/{}/{}",
+                webp_filename.get(..2).unwrap_or_default(),
+                webp_filename
+            ));
+        }
+        if let Some(replay_filename) = image.replay_filename {
+            object_keys.push(replay_object_key(
+                &replay_filename,
+                image.replay_archived_at.is_some(),
+            ));
+        }
+    }
+
+    // Tombstone comments
+    if let Some(ref actor) = actor {
+        query!(
+            r#"
+            UPDATE comments
+            SET
+                deleted_at = NOW(),
+                deletion_reason = 'user_deleted',
+                content = NULL,
+                content_html = NULL
+            WHERE actor_id = $1 AND deleted_at IS NULL
+            "#,
+            actor.id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    // Anonymize user
     query!(
         r#"
         UPDATE users
         SET
-            deleted_at = NOW(),
+            deletion_finalized_at = NOW(),
             email = NULL,
             display_name = '[deleted]',
             password_hash = '',
+            avatar_image_id = NULL,
             updated_at = NOW()
         WHERE id = $1
         "#,
@@ -605,37 +1227,7 @@ pub async fn delete_user(
     .execute(&mut **tx)
     .await?;
 
-    Ok(())
-}
-
-pub async fn delete_user_with_activity(
-    tx: &mut Transaction<'_, Postgres>,
-    id: Uuid,
-    password: &str,
-    _config: &AppConfig,
-    state: Option<&crate::web::state::AppState>,
-) -> Result<()> {
-    // Get the user's actor before deletion
-    let actor = super::actor::Actor::find_by_user_id(tx, id).await?;
-
-    // Delete the user
-    delete_user(tx, id, password).await?;
-
-    // If state is provided and actor exists, send ActivityPub Delete activity
-    if let (Some(state), Some(actor)) = (state, actor) {
-        // Use the actor's IRI as the object URL
-        if let Ok(actor_url) = actor.iri.parse() {
-            // Send Delete activity - don't fail if this fails
-            if let Err(e) =
-                crate::web::handlers::activitypub::send_delete_activity(&actor, actor_url, state)
-                    .await
-            {
-                tracing::warn!("Failed to send Delete activity for user {}: {:?}", id, e);
-            }
-        }
-    }
-
-    Ok(())
+    Ok(DeletedAccount { object_keys })
 }
 
 impl AuthUser for User {
@@ -645,8 +1237,10 @@ impl AuthUser for User {
         self.id
     }
 
+    // Sessions are only valid for the password they were opened with, so
+    // changing or resetting it signs the account out everywhere
     fn session_auth_hash(&self) -> &[u8] {
-        self.id.as_bytes()
+        self.password_hash.as_bytes()
     }
 }
 
@@ -686,8 +1280,11 @@ impl AuthnBackend for Backend {
                 updated_at,
                 banner_id,
                 preferred_language AS "preferred_language: _",
+                language_fallbacks AS "language_fallbacks: _",
                 deleted_at,
-                show_sensitive_content
+                show_sensitive_content,
+                is_admin,
+                is_locked
             FROM users
             WHERE login_name = $1"#,
             creds.login_name
@@ -713,8 +1310,11 @@ impl AuthnBackend for Backend {
                 updated_at,
                 banner_id,
                 preferred_language AS "preferred_language: _",
+                language_fallbacks AS "language_fallbacks: _",
                 deleted_at,
-                show_sensitive_content
+                show_sensitive_content,
+                is_admin,
+                is_locked
             FROM users
             WHERE id = $1"#,
             user_id
@@ -1,7 +1,9 @@
 pub mod apns;
 pub mod fcm;
 
+use crate::metrics::PUSH_NOTIFICATIONS_TOTAL;
 use crate::models::device::{delete_invalid_device, get_user_devices_by_platform, PlatformType};
+use crate::models::notification::{is_push_enabled, NotificationType};
 use crate::AppConfig;
 use anyhow::Result;
 use apns::ApnsClient;
@@ -80,9 +82,12 @@ impl PushService {
         })
     }
 
+    /// Push to all of a user's devices. Pushes tied to a notification type
+    /// are dropped if the user turned that type off.
     pub async fn send_notification_to_user(
         &self,
         user_id: uuid::Uuid,
+        notification_type: Option<&NotificationType>,
         title: &str,
         body: &str,
         badge: Option<u32>,
@@ -91,15 +96,22 @@ impl PushService {
         // Get user's tokens from database
         let mut tx = self.db_pool.begin().await?;
 
+        if let Some(notification_type) = notification_type {
+            if !is_push_enabled(&mut tx, user_id, notification_type).await? {
+                return Ok(());
+            }
+        }
+
         // Send to iOS devices
         if self.apns_client.is_some() {
             let ios_devices =
                 get_user_devices_by_platform(&mut tx, user_id, PlatformType::Ios).await?;
             for token in ios_devices {
-                match self
+                let result = self
                     .send_to_apns(&token.device_token, title, body, badge, data.clone())
-                    .await
-                {
+                    .await;
+                record_push_result("apns", &result);
+                match result {
                     Ok(_) => {}
                     Err(PushError::InvalidToken) => {
                         tracing::info!("Removing invalid APNs token: {}", token.device_token);
@@ -126,10 +138,11 @@ impl PushService {
             let android_devices =
                 get_user_devices_by_platform(&mut tx, user_id, PlatformType::Android).await?;
             for token in android_devices {
-                match self
+                let result = self
                     .send_to_fcm(&token.device_token, title, body, badge, data.clone())
-                    .await
-                {
+                    .await;
+                record_push_result("fcm", &result);
+                match result {
                     Ok(_) => {}
                     Err(PushError::InvalidToken) => {
                         tracing::info!("Removing invalid FCM token: {}", token.device_token);
@@ -187,3 +200,14 @@ impl PushService {
         Ok(())
     }
 }
+
+fn record_push_result(platform: &str, result: &Result<(), PushError>) {
+    let outcome = match result {
+        Ok(_) => "success",
+        Err(PushError::InvalidToken) => "invalid_token",
+        Err(PushError::Other(_)) => "failure",
+    };
+    PUSH_NOTIFICATIONS_TOTAL
+        .with_label_values(&[platform, outcome])
+        .inc();
+}
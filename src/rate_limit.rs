@@ -0,0 +1,77 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::redis::RedisPool;
+
+const RATE_LIMIT_PREFIX: &str = "oeee:rate_limit:";
+
+/// At most `max_attempts` within any `window`
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+    pub max_attempts: u32,
+    pub window: Duration,
+}
+
+impl RateLimit {
+    pub fn new(max_attempts: u32, window_seconds: u64) -> Self {
+        Self {
+            max_attempts,
+            window: Duration::from_secs(window_seconds),
+        }
+    }
+}
+
+/// Sliding-window attempt counters, one Redis sorted set of timestamps per key.
+pub struct RateLimiter {
+    pool: RedisPool,
+}
+
+impl RateLimiter {
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record an attempt for `key` within `scope` and check it against `limit`.
+    ///
+    /// Returns `false` once the limit is exceeded. Rejected attempts are recorded
+    /// too, so a client that keeps retrying stays blocked until it backs off.
+    pub async fn hit(
+        &self,
+        scope: &str,
+        key: &str,
+        limit: RateLimit,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let redis_key = format!("{}{}:{}", RATE_LIMIT_PREFIX, scope, key);
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        let window_ms = limit.window.as_millis() as u64;
+
+        let (attempts,): (u64,) = redis::pipe()
+            .atomic()
+            .zrembyscore(&redis_key, 0, now_ms.saturating_sub(window_ms))
+            .ignore()
+            .zadd(&redis_key, Uuid::new_v4().to_string(), now_ms)
+            .ignore()
+            .zcard(&redis_key)
+            .pexpire(&redis_key, window_ms as i64)
+            .ignore()
+            .query_async(&mut *conn)
+            .await?;
+
+        Ok(attempts <= u64::from(limit.max_attempts))
+    }
+
+    /// Forget all attempts for `key`, e.g. after a successful login.
+    pub async fn reset(
+        &self,
+        scope: &str,
+        key: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let redis_key = format!("{}{}:{}", RATE_LIMIT_PREFIX, scope, key);
+        conn.del::<_, ()>(&redis_key).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,453 @@
+use std::fmt;
+use std::io::Cursor;
+
+use data_url::DataUrl;
+use image::{ImageFormat, ImageReader, Rgba, RgbaImage};
+
+/// Replay file formats produced by the supported drawing tools
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayFormat {
+    /// PaintBBS NEO / neo-cucumber animation
+    Pch,
+    /// Tegaki replay
+    Tgkr,
+}
+
+impl ReplayFormat {
+    pub fn from_tool(tool: &str) -> Option<Self> {
+        match tool {
+            "neo" | "cucumber" | "neo-cucumber-offline" => Some(ReplayFormat::Pch),
+            "tegaki" => Some(ReplayFormat::Tgkr),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReplayFormat::Pch => "pch",
+            ReplayFormat::Tgkr => "tgkr",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReplayVerificationError {
+    InvalidHeader,
+    DimensionMismatch {
+        replay: (i32, i32),
+        image: (i32, i32),
+    },
+    /// The recorded actions couldn't be decompressed or parsed
+    InvalidActions,
+    /// The replay doesn't end with the final state of its layers
+    MissingFinalFrame,
+    InvalidImage,
+    /// The final frame differs from the image in this many pixels
+    FinalFrameMismatch {
+        differing_pixels: u64,
+    },
+}
+
+impl fmt::Display for ReplayVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayVerificationError::InvalidHeader => write!(f, "replay header is invalid"),
+            ReplayVerificationError::DimensionMismatch { replay, image } => write!(
+                f,
+                "replay canvas is {}x{} but image is {}x{}",
+                replay.0, replay.1, image.0, image.1
+            ),
+            ReplayVerificationError::InvalidActions => {
+                write!(f, "replay actions could not be read")
+            }
+            ReplayVerificationError::MissingFinalFrame => {
+                write!(f, "replay does not end with its final frame")
+            }
+            ReplayVerificationError::InvalidImage => write!(f, "image could not be decoded"),
+            ReplayVerificationError::FinalFrameMismatch { differing_pixels } => write!(
+                f,
+                "replay's final frame differs from the image in {} pixels",
+                differing_pixels
+            ),
+        }
+    }
+}
+
+// PCH header: "NEO " magic, u16 LE width, u16 LE height, 4 reserved bytes,
+// followed by the JSON list of actions compressed with lz-string
+const PCH_MAGIC: &[u8] = b"NEO ";
+const PCH_HEADER_LEN: usize = 12;
+// Upper bound on the decompressed actions, in UTF-16 code units
+const PCH_MAX_ACTIONS_LEN: usize = 16 * 1024 * 1024;
+
+// A pixel differs when any channel is further apart than this, which leaves
+// room for rounding in the browser's compositing
+const PIXEL_TOLERANCE: u8 = 16;
+// Share of differing pixels, in thousandths, still accepted as the same drawing
+const MAX_DIFFERING_PIXELS_PER_MILLE: u64 = 10;
+
+/// Check that an uploaded replay produced the uploaded image.
+///
+/// Only PCH replays are checked for now. Their header has to record the
+/// image's canvas size, and the layers saved by their final "restore" action,
+/// composited, have to match the image. Other formats are accepted as-is.
+pub fn verify_replay(
+    format: ReplayFormat,
+    replay: &[u8],
+    image_png: &[u8],
+    image_width: i32,
+    image_height: i32,
+) -> Result<(), ReplayVerificationError> {
+    match format {
+        ReplayFormat::Pch => verify_pch(replay, image_png, image_width, image_height),
+        ReplayFormat::Tgkr => Ok(()),
+    }
+}
+
+fn verify_pch(
+    replay: &[u8],
+    image_png: &[u8],
+    image_width: i32,
+    image_height: i32,
+) -> Result<(), ReplayVerificationError> {
+    if replay.len() < PCH_HEADER_LEN || &replay[0..4] != PCH_MAGIC {
+        return Err(ReplayVerificationError::InvalidHeader);
+    }
+
+    let width = u16::from_le_bytes([replay[4], replay[5]]) as i32;
+    let height = u16::from_le_bytes([replay[6], replay[7]]) as i32;
+
+    if width != image_width || height != image_height {
+        return Err(ReplayVerificationError::DimensionMismatch {
+            replay: (width, height),
+            image: (image_width, image_height),
+        });
+    }
+
+    let actions = decompress_from_uint8_array(&replay[PCH_HEADER_LEN..])
+        .ok_or(ReplayVerificationError::InvalidActions)?;
+    let actions: Vec<serde_json::Value> =
+        serde_json::from_str(&actions).map_err(|_| ReplayVerificationError::InvalidActions)?;
+    let final_frame = render_final_frame(&actions, width as u32, height as u32)?;
+    let image = decode_png(image_png, width as u32, height as u32)
+        .ok_or(ReplayVerificationError::InvalidImage)?;
+
+    let differing_pixels = count_differing_pixels(&final_frame, &image);
+    let total_pixels = width as u64 * height as u64;
+    if differing_pixels * 1000 > total_pixels * MAX_DIFFERING_PIXELS_PER_MILLE {
+        return Err(ReplayVerificationError::FinalFrameMismatch { differing_pixels });
+    }
+
+    Ok(())
+}
+
+/// Composite the background and foreground layers saved by the replay's
+/// closing "restore" action, which NEO and neo-cucumber both record so a
+/// replay can skip straight to the end
+fn render_final_frame(
+    actions: &[serde_json::Value],
+    width: u32,
+    height: u32,
+) -> Result<RgbaImage, ReplayVerificationError> {
+    let layer = |index: usize| -> Result<RgbaImage, ReplayVerificationError> {
+        let layer_url = actions
+            .last()
+            .and_then(|action| action.as_array())
+            .filter(|action| action.first().and_then(|kind| kind.as_str()) == Some("restore"))
+            .and_then(|action| action.get(index))
+            .and_then(|url| url.as_str())
+            .ok_or(ReplayVerificationError::MissingFinalFrame)?;
+        let (png, _fragment) = DataUrl::process(layer_url)
+            .ok()
+            .and_then(|url| url.decode_to_vec().ok())
+            .ok_or(ReplayVerificationError::InvalidActions)?;
+        decode_png(&png, width, height).ok_or(ReplayVerificationError::InvalidActions)
+    };
+
+    let mut frame = layer(1)?;
+    image::imageops::overlay(&mut frame, &layer(2)?, 0, 0);
+    Ok(frame)
+}
+
+/// Decode a PNG of exactly the given size. The size is read from the header
+/// before anything is decompressed.
+fn decode_png(png: &[u8], width: u32, height: u32) -> Option<RgbaImage> {
+    let reader = ImageReader::with_format(Cursor::new(png), ImageFormat::Png);
+    if reader.into_dimensions().ok()? != (width, height) {
+        return None;
+    }
+    let image = ImageReader::with_format(Cursor::new(png), ImageFormat::Png)
+        .decode()
+        .ok()?;
+    Some(image.to_rgba8())
+}
+
+/// Count the pixels that differ once both images are flattened onto white,
+/// as transparent areas of the canvas are shown
+fn count_differing_pixels(a: &RgbaImage, b: &RgbaImage) -> u64 {
+    a.pixels()
+        .zip(b.pixels())
+        .filter(|(a, b)| {
+            let (a, b) = (flatten_on_white(a), flatten_on_white(b));
+            a.iter()
+                .zip(b.iter())
+                .any(|(a, b)| a.abs_diff(*b) > PIXEL_TOLERANCE)
+        })
+        .count() as u64
+}
+
+fn flatten_on_white(pixel: &Rgba<u8>) -> [u8; 3] {
+    let alpha = pixel[3] as u32;
+    let channel = |value: u8| ((value as u32 * alpha + 255 * (255 - alpha)) / 255) as u8;
+    [channel(pixel[0]), channel(pixel[1]), channel(pixel[2])]
+}
+
+/// Reads the bit stream lz-string packs into 16-bit values, most significant
+/// bit first
+struct LzBits<'a> {
+    values: &'a [u16],
+    value: u16,
+    position: u16,
+    index: usize,
+}
+
+impl LzBits<'_> {
+    fn read(&mut self, count: u32) -> u32 {
+        let mut bits = 0;
+        for power in 0..count {
+            if self.value & self.position != 0 {
+                bits |= 1 << power;
+            }
+            self.position >>= 1;
+            if self.position == 0 {
+                self.position = 0x8000;
+                self.value = self.values.get(self.index).copied().unwrap_or(0);
+                self.index += 1;
+            }
+        }
+        bits
+    }
+}
+
+/// Port of lz-string's `decompressFromUint8Array`, which neo-cucumber uses to
+/// pack PCH actions. Returns `None` for corrupt or oversized input.
+fn decompress_from_uint8_array(compressed: &[u8]) -> Option<String> {
+    let values: Vec<u16> = compressed
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect();
+    let mut bits = LzBits {
+        values: &values,
+        value: *values.first()?,
+        position: 0x8000,
+        index: 1,
+    };
+
+    // Codes 0 to 2 are markers, so entries start at 3
+    let mut dictionary: Vec<Vec<u16>> = vec![Vec::new(); 3];
+    let mut enlarge_in: u32 = 4;
+    let mut num_bits: u32 = 3;
+
+    let first = match bits.read(2) {
+        0 => bits.read(8) as u16,
+        1 => bits.read(16) as u16,
+        _ => return Some(String::new()),
+    };
+    dictionary.push(vec![first]);
+    let mut w = vec![first];
+    let mut result = vec![first];
+
+    loop {
+        if bits.index > values.len() {
+            return None;
+        }
+        let code = match bits.read(num_bits) {
+            marker @ (0 | 1) => {
+                let width = if marker == 0 { 8 } else { 16 };
+                dictionary.push(vec![bits.read(width) as u16]);
+                enlarge_in -= 1;
+                dictionary.len() - 1
+            }
+            2 => return String::from_utf16(&result).ok(),
+            code => code as usize,
+        };
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+
+        let entry = if code < dictionary.len() {
+            dictionary[code].clone()
+        } else if code == dictionary.len() {
+            let mut entry = w.clone();
+            entry.push(w[0]);
+            entry
+        } else {
+            return None;
+        };
+        result.extend_from_slice(&entry);
+        if result.len() > PCH_MAX_ACTIONS_LEN {
+            return None;
+        }
+
+        let mut next = w;
+        next.push(entry[0]);
+        dictionary.push(next);
+        enlarge_in -= 1;
+        w = entry;
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::BASE64;
+
+    /// Writes lz-string's bit stream, the inverse of `LzBits`
+    struct LzWriter {
+        values: Vec<u16>,
+        value: u16,
+        position: u16,
+    }
+
+    impl LzWriter {
+        fn write(&mut self, bits: u32, count: u32) {
+            for power in 0..count {
+                if bits & (1 << power) != 0 {
+                    self.value |= self.position;
+                }
+                self.position >>= 1;
+                if self.position == 0 {
+                    self.values.push(self.value);
+                    self.value = 0;
+                    self.position = 0x8000;
+                }
+            }
+        }
+    }
+
+    /// Encode text as a valid lz-string stream made only of literals, which is
+    /// enough to exercise the decoder without porting the compressor
+    fn compress_literals(text: &str) -> Vec<u8> {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let mut writer = LzWriter {
+            values: Vec::new(),
+            value: 0,
+            position: 0x8000,
+        };
+        let (mut enlarge_in, mut num_bits) = (4u32, 3u32);
+        let enlarge = |enlarge_in: &mut u32, num_bits: &mut u32| {
+            *enlarge_in -= 1;
+            if *enlarge_in == 0 {
+                *enlarge_in = 1 << *num_bits;
+                *num_bits += 1;
+            }
+        };
+
+        writer.write(1, 2);
+        writer.write(units[0] as u32, 16);
+        for &unit in &units[1..] {
+            writer.write(1, num_bits);
+            writer.write(unit as u32, 16);
+            // One entry for the literal and one for the sequence it ends
+            enlarge(&mut enlarge_in, &mut num_bits);
+            enlarge(&mut enlarge_in, &mut num_bits);
+        }
+        writer.write(2, num_bits);
+        writer.write(0, 16);
+
+        writer
+            .values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    fn png(image: &RgbaImage) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .expect("PNG should encode");
+        bytes
+    }
+
+    fn data_url(image: &RgbaImage) -> String {
+        format!("data:image/png;base64,{}", BASE64.encode(&png(image)))
+    }
+
+    fn pch(width: u16, height: u16, background: &RgbaImage, foreground: &RgbaImage) -> Vec<u8> {
+        let actions = serde_json::json!([
+            ["freeHand", 0, 0, 1, 1],
+            ["restore", data_url(background), data_url(foreground)],
+        ]);
+        let mut replay = PCH_MAGIC.to_vec();
+        replay.extend_from_slice(&width.to_le_bytes());
+        replay.extend_from_slice(&height.to_le_bytes());
+        replay.extend_from_slice(&[0; 4]);
+        replay.extend(compress_literals(&actions.to_string()));
+        replay
+    }
+
+    fn layers() -> (RgbaImage, RgbaImage) {
+        let background = RgbaImage::from_pixel(16, 8, Rgba([255, 255, 255, 255]));
+        let mut foreground = RgbaImage::new(16, 8);
+        for x in 0..8 {
+            foreground.put_pixel(x, 4, Rgba([0, 0, 0, 255]));
+        }
+        (background, foreground)
+    }
+
+    #[test]
+    fn decompresses_literal_stream() {
+        let text = "[[\"restore\",\"한글\"]]";
+        assert_eq!(
+            decompress_from_uint8_array(&compress_literals(text)).as_deref(),
+            Some(text)
+        );
+    }
+
+    #[test]
+    fn matching_replay_passes() {
+        let (background, foreground) = layers();
+        let mut image = background.clone();
+        image::imageops::overlay(&mut image, &foreground, 0, 0);
+
+        let replay = pch(16, 8, &background, &foreground);
+        assert_eq!(
+            verify_replay(ReplayFormat::Pch, &replay, &png(&image), 16, 8),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn mismatched_replay_fails() {
+        let (background, foreground) = layers();
+        // Same canvas size, different drawing
+        let image = RgbaImage::from_pixel(16, 8, Rgba([255, 0, 0, 255]));
+
+        let replay = pch(16, 8, &background, &foreground);
+        assert!(matches!(
+            verify_replay(ReplayFormat::Pch, &replay, &png(&image), 16, 8),
+            Err(ReplayVerificationError::FinalFrameMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn replay_without_final_frame_fails() {
+        let (background, _) = layers();
+        let mut replay = PCH_MAGIC.to_vec();
+        replay.extend_from_slice(&16u16.to_le_bytes());
+        replay.extend_from_slice(&8u16.to_le_bytes());
+        replay.extend_from_slice(&[0; 4]);
+        replay.extend(compress_literals("[[\"freeHand\",0,0,1,1]]"));
+
+        assert_eq!(
+            verify_replay(ReplayFormat::Pch, &replay, &png(&background), 16, 8),
+            Err(ReplayVerificationError::MissingFinalFrame)
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::models::user::Language;
+use crate::AppConfig;
+
+#[derive(Deserialize)]
+struct DeeplResponse {
+    translations: Vec<DeeplTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeeplTranslation {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct LibreTranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+}
+
+/// Whether a translation backend is configured
+pub fn translation_enabled(config: &AppConfig) -> bool {
+    !config.translation_backend.is_empty()
+}
+
+/// Machine-translate `text` into `target` with the configured backend.
+/// The source language is left for the backend to detect.
+pub async fn translate(config: &AppConfig, text: &str, target: &Language) -> Result<String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let api_url = config.translation_api_url.trim_end_matches('/');
+
+    match config.translation_backend.as_str() {
+        "deepl" => {
+            // DeepL wants a regional variant when translating into English
+            let target_lang = match target {
+                Language::En => "EN-US".to_string(),
+                other => other.code().to_uppercase(),
+            };
+            let response: DeeplResponse = client
+                .post(format!("{}/v2/translate", api_url))
+                .header(
+                    "Authorization",
+                    format!("DeepL-Auth-Key {}", config.translation_api_key),
+                )
+                .json(&json!({
+                    "text": [text],
+                    "target_lang": target_lang,
+                }))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            response
+                .translations
+                .into_iter()
+                .next()
+                .map(|translation| translation.text)
+                .ok_or_else(|| anyhow!("DeepL returned no translation"))
+        }
+        "libretranslate" => {
+            let response: LibreTranslateResponse = client
+                .post(format!("{}/translate", api_url))
+                .json(&json!({
+                    "q": text,
+                    "source": "auto",
+                    "target": target.code(),
+                    "format": "text",
+                    "api_key": config.translation_api_key,
+                }))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            Ok(response.translated_text)
+        }
+        "" => Err(anyhow!("No translation backend is configured")),
+        other => Err(anyhow!("Unknown translation backend: {}", other)),
+    }
+}
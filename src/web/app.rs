@@ -1,42 +1,78 @@
 use super::state::AppState;
 use crate::models::user::Backend;
+use crate::web::correlation::correlation_id_middleware;
 use crate::web::handlers::about::about;
 use crate::web::handlers::account::{
-    account, delete_account, delete_account_htmx, edit_account, edit_password, get_account_json,
-    request_email_verification_code, request_email_verification_json, save_language,
-    save_show_sensitive_content, verify_email_code_json, verify_email_verification_code,
+    account, cancel_account_deletion_page, delete_account, delete_account_page,
+    do_cancel_account_deletion, do_delete_account, edit_account, edit_password, get_account_json,
+    get_notification_preferences_json, notification_preferences, request_email_verification_code,
+    request_email_verification_json, save_account_settings, save_default_co_moderators,
+    save_is_locked, save_language, save_notification_preferences,
+    save_notification_preferences_json, save_show_sensitive_content, verify_email_code_json,
+    verify_email_verification_code,
 };
 use crate::web::handlers::activitypub::{
-    activitypub_get_community, activitypub_get_post, activitypub_get_user,
+    activitypub_get_community, activitypub_get_instance_actor, activitypub_get_post,
+    activitypub_get_user, activitypub_get_user_featured, activitypub_get_user_outbox,
     activitypub_post_community_inbox, activitypub_post_shared_inbox,
     activitypub_post_user_followers, activitypub_post_user_inbox, activitypub_webfinger,
+    community_inbox_handler, federation_config,
+};
+use crate::web::handlers::announcement::{
+    admin_announcements, do_create_announcement, do_delete_announcement, do_dismiss_announcement,
+    do_set_announcement_active,
+};
+use crate::web::handlers::auth::totp::{
+    do_disable_totp, do_totp_challenge, do_totp_setup, totp_challenge, totp_setup,
 };
 use crate::web::handlers::auth::{
     api_login, api_logout, api_me, api_signup, do_login, do_logout, do_signup, login, signup,
 };
+use crate::web::handlers::boost::{boost_post, hx_boost_button, unboost_post};
 use crate::web::handlers::collaborate::{
     collaborate_lobby, create_collaborative_session, get_active_sessions_json, get_auth_info,
-    get_collaboration_meta, save_collaborative_session, serve_collaborative_app,
-    websocket_collaborate_handler,
+    get_collaboration_meta, get_session_health, save_collaborative_session,
+    serve_collaborative_app, websocket_collaborate_handler,
 };
 use crate::web::handlers::collaborate_cleanup::cleanup_collaborative_sessions;
 use crate::web::handlers::community::{
     communities, community, community_comments, community_detail_json, community_iframe,
-    create_community_form, create_community_json, delete_community_json, do_accept_invitation,
-    do_create_community, do_leave_community, do_reject_invitation, get_communities_list_json,
-    get_community_invitations_json, get_community_members_json, get_members,
-    get_public_communities_json, get_user_invitations_json, hx_delete_community,
-    hx_do_edit_community, hx_edit_community, invite_user, invite_user_json,
-    leave_community_json, members_page, redirect_community_to_unified, remove_member,
-    remove_member_json, retract_invitation, retract_invitation_json,
-    search_public_communities_json, update_community_json,
+    community_og_image, create_community_form, create_community_json, delete_community_json,
+    do_accept_invitation, do_approve_join_request, do_approve_pending_post,
+    do_cancel_ownership_transfer, do_confirm_ownership_transfer, do_create_community,
+    do_create_invite_link, do_join_via_invite_link, do_leave_community, do_pin_post,
+    do_reject_invitation, do_reject_join_request, do_reject_pending_post, do_request_to_join,
+    do_revoke_invite_link, do_set_member_role, do_transfer_ownership, do_unarchive_community,
+    do_unpin_post, get_communities_list_json, get_community_analytics_json,
+    get_community_invitations_json, get_community_leaderboard_json, get_community_members_json,
+    get_members, get_public_communities_json, get_user_invitations_json, hx_community_leaderboard,
+    hx_delete_community, hx_do_edit_accepts_join_requests, hx_do_edit_community,
+    hx_do_edit_community_categories, hx_do_edit_default_hashtags, hx_do_edit_posting_window,
+    hx_do_edit_require_post_approval, hx_edit_community, invite_link_page, invite_user,
+    invite_user_json, invite_users_json, leave_community_json, members_page,
+    ownership_transfer_page, redirect_community_to_unified, remove_member, remove_member_json,
+    retract_invitation, retract_invitation_json, search_public_communities_json,
+    set_member_role_json, update_community_json,
+};
+use crate::web::handlers::community_category::{
+    admin_community_categories, do_create_community_category, do_delete_community_category,
 };
 use crate::web::handlers::draw::{
-    banner_draw_finish, draw_finish, start_banner_draw, start_banner_draw_mobile, start_draw,
-    start_draw_get, start_draw_mobile,
+    banner_draw_finish, draw_autosave, draw_finish, start_banner_draw, start_banner_draw_mobile,
+    start_draw, start_draw_get, start_draw_mobile,
+};
+use crate::web::handlers::export::{
+    community_export_status, export_status, request_community_export, request_export,
+};
+use crate::web::handlers::feed::{community_feed, profile_feed};
+use crate::web::handlers::follow_requests::{
+    do_accept_follow_request, do_reject_follow_request, follow_requests,
 };
 use crate::web::handlers::handler_404;
-use crate::web::handlers::hashtag::{hashtag_autocomplete, hashtag_discovery, hashtag_view};
+use crate::web::handlers::hashtag::{
+    followed_hashtags_feed, hashtag_autocomplete, hashtag_discovery, hashtag_view,
+    hx_follow_hashtag, hx_unfollow_hashtag,
+};
 use crate::web::handlers::home::{
     add_reaction_api, create_comment_api, delete_comment_api, delete_post_api, edit_post_api,
     get_active_communities_json, get_latest_comments_json, get_post_comments_api,
@@ -44,41 +80,60 @@ use crate::web::handlers::home::{
     load_more_public_posts_json, my_timeline, remove_reaction_api,
 };
 use crate::web::handlers::notifications::{
-    api_delete_notification, api_list_notifications, api_mark_notification_read,
-    delete_notification_handler, get_unread_notification_count, list_notifications,
-    mark_all_notifications_read, mark_notification_read,
+    api_delete_notification, api_list_grouped_notifications, api_list_notifications,
+    api_mark_notification_group_read, api_mark_notification_read, delete_notification_handler,
+    get_unread_notification_count, hx_mark_all_notifications_read, hx_notifications_page,
+    list_notifications, mark_all_notifications_read, mark_community_notifications_read,
+    mark_notification_group_read, mark_notification_read, notification_stream,
+};
+use crate::web::handlers::oauth::{
+    do_oauth_authorize, oauth_authorize, oauth_bearer_middleware, oauth_revoke, oauth_token,
+    oauth_token_refresh,
 };
 use crate::web::handlers::password_reset::{
     password_reset_request, password_reset_request_page, password_reset_verify,
     password_reset_verify_page,
 };
 use crate::web::handlers::post::{
-    add_reaction, do_create_comment, do_post_edit_community, draft_posts, draft_posts_api,
-    get_movable_communities_api, hx_delete_post, hx_do_edit_post, hx_edit_post,
-    move_post_community_api, post_edit_community, post_publish, post_publish_form,
-    post_reactions_detail, post_relay_view, post_relay_view_by_login_name, post_replay_view,
-    post_replay_view_by_login_name, post_replay_view_mobile, post_view_by_login_name,
-    redirect_post_to_login_name, remove_reaction,
+    add_comment_reaction, add_reaction, do_create_comment, do_delete_comment, do_edit_comment,
+    do_post_edit_community, do_schedule_post, draft_posts, draft_posts_api,
+    get_movable_communities_api, get_post_deliveries_api, hx_comment, hx_comment_replies,
+    hx_delete_post, hx_do_edit_post, hx_edit_comment, hx_edit_post, hx_pin_post,
+    hx_profile_pin_button, hx_unpin_post, move_post_community_api, post_edit_community,
+    post_history, post_publish, post_publish_form, post_reactions_detail, post_relay_view,
+    post_relay_view_by_login_name, post_replay_view, post_replay_view_by_login_name,
+    post_replay_view_mobile, post_translation, post_view_by_login_name,
+    redirect_post_to_login_name, remove_comment_reaction, remove_reaction,
 };
 use crate::web::handlers::policy::policy;
 use crate::web::handlers::privacy::privacy;
-use crate::web::handlers::report::{report_post_api, report_profile_api};
+use crate::web::handlers::report::{
+    admin_reports, admin_verify_federated_object, do_report_comment, do_report_object,
+    do_report_post, do_resolve_object_report, do_resolve_report, report_post_api,
+    report_profile_api,
+};
 use crate::web::handlers::profile::{
     activate_banner_api, banner_management, delete_banner_api, do_activate_banner, do_add_link,
     do_delete_banner, do_delete_guestbook_entry, do_delete_link, do_follow_profile,
     do_move_link_down, do_move_link_up, do_reply_guestbook_entry, do_unfollow_profile,
-    do_write_guestbook_entry, follow_profile_api, guestbook, list_banners_json,
-    profile_banners_iframe, profile_followings_json, profile_iframe, profile_json,
-    profile_or_community, profile_settings, unfollow_profile_api,
+    do_upload_avatar, do_write_guestbook_entry, follow_profile_api, guestbook, list_banners_json,
+    my_activity_json, profile_banners_iframe, profile_followings_json, profile_iframe,
+    profile_json, profile_or_community, profile_settings, unfollow_profile_api,
 };
 use crate::web::handlers::devices::{
     delete_device_handler, list_devices_handler, register_device_handler,
 };
-use crate::web::handlers::search::search_json;
-use crate::web::handlers::well_known::{android_assetlinks, apple_app_site_association};
-use activitypub_federation::config::{FederationConfig, FederationMiddleware};
+use crate::web::handlers::search::{search_json, search_posts, search_posts_json};
+use crate::web::handlers::trending_hashtags::{trending_hashtags_json, trending_hashtags_page};
+use crate::web::handlers::nodeinfo::nodeinfo_2_1;
+use crate::web::handlers::well_known::{
+    android_assetlinks, apple_app_site_association, nodeinfo_well_known,
+};
+use crate::web::metrics::{metrics, track_http_metrics};
+use activitypub_federation::config::FederationMiddleware;
 use anyhow::Result;
 use axum::extract::DefaultBodyLimit;
+use axum::middleware;
 use axum::routing::{delete, get, post, put};
 use axum::Router;
 use axum_login::{login_required, AuthManagerLayerBuilder};
@@ -141,21 +196,36 @@ impl App {
             )
             .nest_service("/static", ServeDir::new("static"));
 
+        // Outside the session and auth layers, so scrapes don't touch sessions
+        let metrics_router = Router::new()
+            .route("/metrics", get(metrics))
+            .with_state(self.state.clone());
+
         let protected_router = Router::new()
             .route("/home", get(my_timeline))
             .route("/notifications", get(list_notifications))
+            .route("/notifications/page", get(hx_notifications_page))
             .route(
                 "/notifications/unread-count",
                 get(get_unread_notification_count),
             )
+            .route("/notifications/stream", get(notification_stream))
             .route(
                 "/notifications/mark-all-read",
-                post(mark_all_notifications_read),
+                post(hx_mark_all_notifications_read),
+            )
+            .route(
+                "/notifications/communities/:slug/mark-read",
+                post(mark_community_notifications_read),
             )
             .route(
                 "/notifications/:notification_id/mark-read",
                 post(mark_notification_read),
             )
+            .route(
+                "/notifications/:notification_id/mark-group-read",
+                post(mark_notification_group_read),
+            )
             .route(
                 "/notifications/:notification_id",
                 delete(delete_notification_handler),
@@ -168,6 +238,28 @@ impl App {
                 "/account/show-sensitive-content",
                 post(save_show_sensitive_content),
             )
+            .route(
+                "/account/default-co-moderators",
+                post(save_default_co_moderators),
+            )
+            .route("/account/locked", post(save_is_locked))
+            .route("/account/settings", put(save_account_settings))
+            .route("/account/notification-preferences", get(notification_preferences))
+            .route("/account/notification-preferences", put(save_notification_preferences))
+            .route("/oauth/authorize", get(oauth_authorize))
+            .route("/oauth/authorize", post(do_oauth_authorize))
+            .route("/account/follow-requests", get(follow_requests))
+            .route(
+                "/account/follow-requests/:id/accept",
+                post(do_accept_follow_request),
+            )
+            .route(
+                "/account/follow-requests/:id/reject",
+                post(do_reject_follow_request),
+            )
+            .route("/account/2fa/setup", get(totp_setup))
+            .route("/account/2fa/setup", post(do_totp_setup))
+            .route("/account/2fa/disable", post(do_disable_totp))
             .route(
                 "/account/request-verify-email",
                 post(request_email_verification_code),
@@ -176,8 +268,17 @@ impl App {
                 "/account/verify-email",
                 post(verify_email_verification_code),
             )
-            .route("/account/delete", delete(delete_account_htmx))
+            .route("/account/delete", get(delete_account_page))
+            .route("/account/delete", post(do_delete_account))
+            .route("/account/export", post(request_export))
+            .route("/account/export/status", get(export_status))
             .route("/comments", post(do_create_comment))
+            .route("/comments/:id", get(hx_comment))
+            .route("/comments/:id", put(do_edit_comment))
+            .route("/comments/:id", delete(do_delete_comment))
+            .route("/comments/:id/edit", get(hx_edit_comment))
+            .route("/comments/:id/reactions", post(add_comment_reaction))
+            .route("/comments/:id/reactions", delete(remove_comment_reaction))
             .route("/posts/:post_id/reactions/add", post(add_reaction))
             .route("/posts/:post_id/reactions/remove", post(remove_reaction))
             .route("/communities/new", get(create_community_form))
@@ -186,11 +287,71 @@ impl App {
                 "/communities/@:slug/members/:user_id",
                 delete(remove_member),
             )
+            .route(
+                "/communities/@:slug/members/:user_id/role",
+                put(do_set_member_role),
+            )
             .route("/communities/@:slug/leave", post(do_leave_community))
+            .route("/hashtags/following", get(followed_hashtags_feed))
+            .route("/hashtags/:hashtag_name/follow", post(hx_follow_hashtag))
+            .route("/hashtags/:hashtag_name/follow", delete(hx_unfollow_hashtag))
+            .route("/communities/@:slug/export", post(request_community_export))
+            .route(
+                "/communities/@:slug/export/status",
+                get(community_export_status),
+            )
+            .route("/communities/@:slug/transfer", post(do_transfer_ownership))
+            .route(
+                "/communities/@:slug/transfer/:token",
+                get(ownership_transfer_page),
+            )
+            .route(
+                "/communities/@:slug/transfer/:token/confirm",
+                post(do_confirm_ownership_transfer),
+            )
+            .route(
+                "/communities/@:slug/transfer/:token/cancel",
+                post(do_cancel_ownership_transfer),
+            )
+            .route("/communities/@:slug/pin", post(do_pin_post))
+            .route("/communities/@:slug/pin/:post_id", delete(do_unpin_post))
+            .route(
+                "/communities/@:slug/unarchive",
+                post(do_unarchive_community),
+            )
             .route(
                 "/communities/@:slug/invitations/:invitation_id",
                 delete(retract_invitation),
             )
+            .route(
+                "/communities/@:slug/invite-links",
+                post(do_create_invite_link),
+            )
+            .route(
+                "/communities/@:slug/invite-links/:id",
+                delete(do_revoke_invite_link),
+            )
+            .route("/invite/:link_id/join", post(do_join_via_invite_link))
+            .route(
+                "/communities/@:slug/join-requests",
+                post(do_request_to_join),
+            )
+            .route(
+                "/communities/@:slug/join-requests/:join_request_id/approve",
+                post(do_approve_join_request),
+            )
+            .route(
+                "/communities/@:slug/join-requests/:join_request_id/reject",
+                post(do_reject_join_request),
+            )
+            .route(
+                "/communities/@:slug/pending-posts/:post_id/approve",
+                post(do_approve_pending_post),
+            )
+            .route(
+                "/communities/@:slug/pending-posts/:post_id/reject",
+                post(do_reject_pending_post),
+            )
             .route("/communities/:id/members", get(get_members))
             .route("/communities/:id/invite", post(invite_user))
             .route("/communities/:id/members/:user_id", delete(remove_member))
@@ -204,12 +365,22 @@ impl App {
                 "/draw/finish",
                 post(draw_finish).layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
             )
+            .route(
+                "/posts/:id/autosave",
+                post(draw_autosave).layer(DefaultBodyLimit::max(10 * 1024 * 1024)),
+            )
             .route("/posts/drafts", get(draft_posts))
             .route("/posts/publish", post(post_publish))
             .route("/posts/:id/edit", get(hx_edit_post))
             .route("/posts/:id/relay", get(post_relay_view))
             .route("/posts/:id", put(hx_do_edit_post))
             .route("/posts/:id", delete(hx_delete_post))
+            .route("/posts/:id/pin", get(hx_profile_pin_button))
+            .route("/posts/:id/pin", post(hx_pin_post))
+            .route("/posts/:id/pin", delete(hx_unpin_post))
+            .route("/posts/:id/boost", get(hx_boost_button))
+            .route("/boosts", post(boost_post))
+            .route("/boosts/undo", post(unboost_post))
             .route("/@:login_name/:id/edit/community", get(post_edit_community))
             .route(
                 "/@:login_name/:id/edit/community",
@@ -219,6 +390,11 @@ impl App {
             .route("/banners/draw/mobile", get(start_banner_draw_mobile))
             .route("/banners/draw/finish", post(banner_draw_finish))
             .route("/posts/:id/publish", get(post_publish_form))
+            .route("/posts/:id/schedule", post(do_schedule_post))
+            .route("/posts/:id/report", post(do_report_post))
+            .route("/comments/:id/report", post(do_report_comment))
+            .route("/reports", post(do_report_object))
+            .route("/posts/:id/translation", get(post_translation))
             .route("/posts/:id/replay", get(post_replay_view))
             .route("/posts/:id/replay/mobile", get(post_replay_view_mobile))
             .route("/@:login_name/follow", post(do_follow_profile))
@@ -232,20 +408,53 @@ impl App {
                 "/@:login_name/guestbook/:entry_id/reply",
                 post(do_reply_guestbook_entry),
             )
+            .route("/admin/reports", get(admin_reports))
+            .route("/admin/reports/:id/resolve", post(do_resolve_report))
+            .route(
+                "/admin/reports/objects/:id/resolve",
+                post(do_resolve_object_report),
+            )
+            .route(
+                "/admin/federation/verify",
+                get(admin_verify_federated_object),
+            )
+            .route("/admin/announcements", get(admin_announcements))
+            .route("/admin/announcements", post(do_create_announcement))
+            .route("/admin/announcements/:id", delete(do_delete_announcement))
+            .route(
+                "/admin/announcements/:id/delete",
+                post(do_delete_announcement),
+            )
+            .route(
+                "/admin/announcements/:id/active",
+                post(do_set_announcement_active),
+            )
+            .route("/announcements/:id/dismiss", post(do_dismiss_announcement))
+            .route(
+                "/admin/community-categories",
+                get(admin_community_categories),
+            )
+            .route(
+                "/admin/community-categories",
+                post(do_create_community_category),
+            )
+            .route(
+                "/admin/community-categories/:id/delete",
+                post(do_delete_community_category),
+            )
             .route("/api/v1/devices", post(register_device_handler))
             .route("/api/v1/devices", get(list_devices_handler))
             .route_layer(login_required!(Backend, login_url = "/login"));
 
-        let state = self.state.clone();
-        let domain = state.config.domain.clone();
-        let activitypub_data = FederationConfig::builder()
-            .domain(domain)
-            .app_data(state)
-            .build()
-            .await?;
+        // Actors and objects fetched while handling incoming activities are signed as the
+        // instance actor
+        let activitypub_data = federation_config(&self.state)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to build federation config: {}", e))?;
 
         let activitypub_router = Router::new()
             .route("/.well-known/webfinger", get(activitypub_webfinger))
+            .route("/ap/actor", get(activitypub_get_instance_actor))
             .route("/ap/users/:login_name", get(activitypub_get_user))
             .route("/ap/posts/:post_id", get(activitypub_get_post))
             .route(
@@ -260,10 +469,19 @@ impl App {
                 "/ap/users/:login_name/followers",
                 get(activitypub_post_user_followers),
             )
+            .route(
+                "/ap/users/:login_name/outbox",
+                get(activitypub_get_user_outbox),
+            )
+            .route(
+                "/ap/users/:login_name/featured",
+                get(activitypub_get_user_featured),
+            )
             .route(
                 "/ap/communities/:community_id/inbox",
                 post(activitypub_post_community_inbox),
             )
+            .route("/communities/@:slug/inbox", post(community_inbox_handler))
             .route("/ap/inbox", post(activitypub_post_shared_inbox))
             .layer(FederationMiddleware::new(activitypub_data));
 
@@ -277,6 +495,8 @@ impl App {
                 "/.well-known/assetlinks.json",
                 get(android_assetlinks),
             )
+            .route("/.well-known/nodeinfo", get(nodeinfo_well_known))
+            .route("/nodeinfo/2.1", get(nodeinfo_2_1))
             .route("/api/home/posts", get(load_more_public_posts))
             .route("/api/v1/posts/public", get(load_more_public_posts_json))
             .route("/api/v1/posts/drafts", get(draft_posts_api))
@@ -284,6 +504,10 @@ impl App {
             .route("/api/v1/posts/:post_id", delete(delete_post_api))
             .route("/api/v1/posts/:post_id", put(edit_post_api))
             .route("/api/v1/posts/:post_id/report", post(report_post_api))
+            .route(
+                "/api/v1/posts/:post_id/deliveries",
+                get(get_post_deliveries_api),
+            )
             .route(
                 "/api/v1/posts/:post_id/comments",
                 get(get_post_comments_api),
@@ -311,10 +535,13 @@ impl App {
                 put(move_post_community_api),
             )
             .route("/api/v1/search", get(search_json))
+            .route("/api/v1/hashtags/trending", get(trending_hashtags_json))
+            .route("/api/v1/search/posts", get(search_posts_json))
             .route(
                 "/api/v1/devices/:device_token",
                 delete(delete_device_handler),
             )
+            .route("/api/v1/users/me/activity", get(my_activity_json))
             .route("/api/v1/profiles/:login_name", get(profile_json))
             .route(
                 "/api/v1/profiles/:login_name/followings",
@@ -364,10 +591,22 @@ impl App {
                 "/api/v1/communities/:slug/members/:user_id",
                 delete(remove_member_json),
             )
+            .route(
+                "/api/v1/communities/:slug/members/:user_id/role",
+                put(set_member_role_json),
+            )
             .route("/api/v1/communities/:slug/leave", post(leave_community_json))
+            .route(
+                "/api/v1/communities/:slug/analytics",
+                get(get_community_analytics_json),
+            )
+            .route(
+                "/api/v1/communities/:slug/leaderboard",
+                get(get_community_leaderboard_json),
+            )
             .route(
                 "/api/v1/communities/:slug/invitations",
-                get(get_community_invitations_json),
+                get(get_community_invitations_json).post(invite_users_json),
             )
             .route(
                 "/api/v1/communities/:slug/invitations/:invitation_id",
@@ -394,7 +633,19 @@ impl App {
                 post(request_email_verification_json),
             )
             .route("/api/v1/account/verify-email", post(verify_email_code_json))
+            .route(
+                "/api/v1/account/notification-preferences",
+                get(get_notification_preferences_json),
+            )
+            .route(
+                "/api/v1/account/notification-preferences",
+                put(save_notification_preferences_json),
+            )
             .route("/api/v1/notifications", get(api_list_notifications))
+            .route(
+                "/api/v1/notifications/grouped",
+                get(api_list_grouped_notifications),
+            )
             .route(
                 "/api/v1/notifications/unread-count",
                 get(get_unread_notification_count),
@@ -403,10 +654,18 @@ impl App {
                 "/api/v1/notifications/mark-all-read",
                 post(mark_all_notifications_read),
             )
+            .route(
+                "/api/v1/notifications/communities/:slug/mark-read",
+                post(mark_community_notifications_read),
+            )
             .route(
                 "/api/v1/notifications/:notification_id/mark-read",
                 post(api_mark_notification_read),
             )
+            .route(
+                "/api/v1/notifications/:notification_id/mark-group-read",
+                post(api_mark_notification_group_read),
+            )
             .route(
                 "/api/v1/notifications/:notification_id",
                 delete(api_delete_notification),
@@ -414,17 +673,47 @@ impl App {
             .route("/communities", get(communities))
             .route("/communities", post(do_create_community))
             .route("/communities/@:slug", get(redirect_community_to_unified))
+            .route("/communities/@:slug/og-image.png", get(community_og_image))
+            .route("/invite/:link_id", get(invite_link_page))
             .route("/communities/:id", get(community))
             .route("/communities/:id", put(hx_do_edit_community))
             .route("/communities/:id/delete", delete(hx_delete_community))
             .route("/communities/:id/edit", get(hx_edit_community))
+            .route(
+                "/communities/:id/posting-window",
+                put(hx_do_edit_posting_window),
+            )
+            .route(
+                "/communities/:id/categories",
+                put(hx_do_edit_community_categories),
+            )
+            .route(
+                "/communities/:id/default-hashtags",
+                put(hx_do_edit_default_hashtags),
+            )
+            .route(
+                "/communities/:id/accepts-join-requests",
+                put(hx_do_edit_accepts_join_requests),
+            )
+            .route(
+                "/communities/:id/require-post-approval",
+                put(hx_do_edit_require_post_approval),
+            )
             .route("/communities/:id/comments", get(community_comments))
             .route("/communities/:id/embed", get(community_iframe))
+            .route("/communities/@:slug/feed.atom", get(community_feed))
+            .route(
+                "/communities/@:slug/leaderboard",
+                get(hx_community_leaderboard),
+            )
             .route("/hashtags", get(hashtag_discovery))
+            .route("/hashtags/trending", get(trending_hashtags_page))
+            .route("/search", get(search_posts))
             .route("/hashtags/:hashtag_name", get(hashtag_view))
             .route("/api/hashtags/autocomplete", get(hashtag_autocomplete))
             .route("/@:slug", get(profile_or_community))
             .route("/@:login_name/embed", get(profile_iframe))
+            .route("/@:login_name/feed.atom", get(profile_feed))
             .route("/@:login_name/banners/embed", get(profile_banners_iframe))
             .route("/@:login_name/settings/links", post(do_add_link))
             .route("/@:login_name/settings/links/:id", delete(do_delete_link))
@@ -433,6 +722,10 @@ impl App {
                 "/@:login_name/settings/links/:id/down",
                 post(do_move_link_down),
             )
+            .route(
+                "/@:login_name/settings/avatar",
+                post(do_upload_avatar).layer(DefaultBodyLimit::max(2 * 1024 * 1024)),
+            )
             .route("/@:login_name/settings", get(profile_settings))
             .route("/@:login_name/settings/banners", get(banner_management))
             .route("/banners/:banner_id/activate", post(do_activate_banner))
@@ -452,16 +745,23 @@ impl App {
                 get(post_relay_view_by_login_name),
             )
             .route("/posts/:id", get(redirect_post_to_login_name))
+            .route("/posts/:id/history", get(post_history))
+            .route(
+                "/posts/:post_id/comments/:comment_id/replies",
+                get(hx_comment_replies),
+            )
             .route(
                 "/collaborate",
                 get(collaborate_lobby).post(create_collaborative_session),
             )
             .route("/collaborate/", get(serve_collaborative_app))
+            .route("/collaborate/replay", get(serve_collaborative_app))
             .route(
                 "/collaborate/:uuid",
                 get(serve_collaborative_app).post(save_collaborative_session),
             )
             .route("/collaborate/:uuid/ws", get(websocket_collaborate_handler))
+            .route("/collaborate/:uuid/health", get(get_session_health))
             .route("/api/auth", get(get_auth_info))
             .route("/collaboration/:uuid/meta", get(get_collaboration_meta))
             .route("/about", get(about))
@@ -471,17 +771,32 @@ impl App {
             .route("/signup", post(do_signup))
             .route("/login", get(login))
             .route("/login", post(do_login))
+            .route("/auth/totp", get(totp_challenge))
+            .route("/auth/totp", post(do_totp_challenge))
+            .route("/account/delete/cancel", get(cancel_account_deletion_page))
+            .route("/account/delete/cancel", post(do_cancel_account_deletion))
             .route("/password-reset", get(password_reset_request_page))
             .route("/password-reset", post(password_reset_request))
             .route("/password-reset/verify", get(password_reset_verify_page))
             .route("/password-reset/verify", post(password_reset_verify))
+            .route("/oauth/token", post(oauth_token))
+            .route("/oauth/token/refresh", post(oauth_token_refresh))
+            .route("/oauth/revoke", post(oauth_revoke))
             .fallback(handler_404)
             .merge(protected_router)
             .layer(MessagesManagerLayer)
+            // Inside the auth layer, so a bearer token can stand in for the session
+            .layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                oauth_bearer_middleware,
+            ))
             .layer(auth_layer)
             .with_state(self.state.clone())
             .merge(static_router)
-            .merge(activitypub_router);
+            .merge(activitypub_router)
+            .merge(metrics_router)
+            .layer(middleware::from_fn(track_http_metrics))
+            .layer(middleware::from_fn(correlation_id_middleware));
 
         // run our app with hyper, listening globally
         let addr = SocketAddr::from(([0, 0, 0, 0], self.state.config.port));
@@ -491,12 +806,15 @@ impl App {
         tracing::info!("listening on {}", addr);
 
         // Ensure we use a shutdown signal to abort the background tasks.
-        axum::serve(listener, app.into_make_service())
-            .with_graceful_shutdown(shutdown_signal(
-                deletion_task.abort_handle(),
-                cleanup_task.abort_handle(),
-            ))
-            .await?;
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown_signal(
+            deletion_task.abort_handle(),
+            cleanup_task.abort_handle(),
+        ))
+        .await?;
 
         deletion_task.await??;
         cleanup_task.await?;
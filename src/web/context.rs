@@ -1,43 +1,196 @@
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::announcement_cache::AnnouncementCache;
 use crate::app_error::AppError;
+use crate::common_context_cache::{load_or_count, CachedCommonCounts, CommonContextCache};
+use crate::models::announcement::{
+    find_dismissed_announcement_ids, list_current_announcements, Announcement,
+};
 use crate::models::community::get_pending_invitations_for_user;
 use crate::models::notification::get_unread_count;
 use crate::models::post::get_draft_post_count;
+use crate::redis::RedisPool;
 
 /// Common context data needed by most template renders
 pub struct CommonContext {
     pub draft_post_count: i64,
     pub unread_notification_count: i64,
+    pub announcement: Option<Announcement>,
 }
 
 impl CommonContext {
     /// Build common context for a user, fetching draft post count and unread notifications
     /// Returns zero values for anonymous users (when user_id is None)
+    /// Counts come from the Redis cache when present and are recounted otherwise
     pub async fn build(
         tx: &mut Transaction<'_, Postgres>,
+        redis_pool: &RedisPool,
         user_id: Option<Uuid>,
     ) -> Result<Self, AppError> {
+        // A broken announcement shouldn't take every page down with it
+        let announcement = Self::active_announcement(tx, redis_pool, user_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load active announcement: {:?}", e);
+                None
+            });
+
         match user_id {
             Some(user_id) => {
-                let draft_post_count = get_draft_post_count(tx, user_id).await.unwrap_or_default();
-                let unread_notification_count = get_unread_count(tx, user_id).await.unwrap_or(0);
-                let pending_invitations = get_pending_invitations_for_user(tx, user_id)
-                    .await
-                    .unwrap_or_default();
-                let pending_invitations_count = pending_invitations.len() as i64;
+                let cache = CommonContextCache::new(redis_pool.clone());
+                let counts = load_or_count(&cache, user_id, move || Self::count(tx, user_id)).await;
 
                 Ok(CommonContext {
-                    draft_post_count,
-                    unread_notification_count: unread_notification_count
-                        + pending_invitations_count,
+                    draft_post_count: counts.draft_post_count,
+                    unread_notification_count: counts.unread_notification_count,
+                    announcement,
                 })
             }
             None => Ok(CommonContext {
                 draft_post_count: 0,
                 unread_notification_count: 0,
+                announcement,
             }),
         }
     }
+
+    /// The most recently started announcement that is running now and, for a
+    /// signed-in user, hasn't been dismissed by them
+    async fn active_announcement(
+        tx: &mut Transaction<'_, Postgres>,
+        redis_pool: &RedisPool,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<Announcement>, AppError> {
+        let cache = AnnouncementCache::new(redis_pool.clone());
+        let announcements = match cache.load().await {
+            Ok(Some(announcements)) => announcements,
+            cached => {
+                if let Err(e) = cached {
+                    tracing::warn!("Failed to load announcement cache: {:?}", e);
+                }
+                let announcements = list_current_announcements(tx).await?;
+                if let Err(e) = cache.store(&announcements).await {
+                    tracing::warn!("Failed to store announcement cache: {:?}", e);
+                }
+                announcements
+            }
+        };
+
+        Self::visible_announcement(tx, announcements, user_id, chrono::Utc::now()).await
+    }
+
+    /// The first of `announcements` (latest start first) that is running at `now`
+    /// and isn't dismissed by the user
+    async fn visible_announcement(
+        tx: &mut Transaction<'_, Postgres>,
+        announcements: Vec<Announcement>,
+        user_id: Option<Uuid>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<Announcement>, AppError> {
+        let running: Vec<Announcement> = announcements
+            .into_iter()
+            .filter(|announcement| announcement.is_running(now))
+            .collect();
+
+        let dismissed = match user_id {
+            Some(user_id) if running.iter().any(|announcement| announcement.dismissible) => {
+                let ids: Vec<Uuid> = running.iter().map(|announcement| announcement.id).collect();
+                find_dismissed_announcement_ids(tx, user_id, &ids).await?
+            }
+            _ => Vec::new(),
+        };
+
+        Ok(running.into_iter().find(|announcement| {
+            !(announcement.dismissible && dismissed.contains(&announcement.id))
+        }))
+    }
+
+    async fn count(tx: &mut Transaction<'_, Postgres>, user_id: Uuid) -> CachedCommonCounts {
+        let draft_post_count = get_draft_post_count(tx, user_id).await.unwrap_or_default();
+        let unread_notification_count = get_unread_count(tx, user_id).await.unwrap_or(0);
+        let pending_invitations = get_pending_invitations_for_user(tx, user_id)
+            .await
+            .unwrap_or_default();
+        let pending_invitations_count = pending_invitations.len() as i64;
+
+        CachedCommonCounts {
+            draft_post_count,
+            unread_notification_count: unread_notification_count + pending_invitations_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::announcement::{
+        create_announcement, dismiss_announcement, AnnouncementDraft, AnnouncementLevel,
+    };
+    use crate::models::test_fixtures;
+
+    fn draft(message: &str, starts_at: chrono::DateTime<chrono::Utc>) -> AnnouncementDraft {
+        AnnouncementDraft {
+            message: message.to_string(),
+            content_ko: String::new(),
+            content_en: String::new(),
+            content_ja: String::new(),
+            content_zh: String::new(),
+            level: AnnouncementLevel::Info,
+            starts_at,
+            ends_at: None,
+            dismissible: true,
+        }
+    }
+
+    async fn visible(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: Option<Uuid>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<Uuid> {
+        let announcements = list_current_announcements(tx).await.unwrap();
+        CommonContext::visible_announcement(tx, announcements, user_id, now)
+            .await
+            .unwrap()
+            .map(|announcement| announcement.id)
+    }
+
+    #[sqlx::test]
+    async fn active_announcement_shows_until_dismissed(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let admin = test_fixtures::user(&mut tx, "admin").await;
+        let viewer = test_fixtures::user(&mut tx, "viewer").await;
+        let now = chrono::Utc::now();
+        let maintenance = create_announcement(
+            &mut tx,
+            admin.id,
+            draft("maintenance", now - chrono::Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+        create_announcement(
+            &mut tx,
+            admin.id,
+            draft("upcoming", now + chrono::Duration::hours(1)),
+        )
+        .await
+        .unwrap();
+
+        // One that hasn't started yet isn't shown
+        assert_eq!(
+            visible(&mut tx, Some(viewer.id), now).await,
+            Some(maintenance.id)
+        );
+
+        assert!(dismiss_announcement(&mut tx, maintenance.id, viewer.id)
+            .await
+            .unwrap());
+        assert_eq!(visible(&mut tx, Some(viewer.id), now).await, None);
+        // Others still see it
+        assert_eq!(
+            visible(&mut tx, Some(admin.id), now).await,
+            Some(maintenance.id)
+        );
+        assert_eq!(visible(&mut tx, None, now).await, Some(maintenance.id));
+    }
 }
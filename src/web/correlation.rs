@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use sentry::{Hub, SentryFutureExt};
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// Identifies every log line, Sentry event and outgoing delivery caused by a
+/// single request; stored as a request extension by the middleware below
+#[derive(Debug, Clone, Copy)]
+pub struct CorrelationId(pub Uuid);
+
+/// Picks up the correlation ID a client or proxy sent along, or generates a
+/// new one, and runs the rest of the request inside a span carrying it
+pub async fn correlation_id_middleware(mut request: Request, next: Next) -> Response {
+    let correlation_id = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok())
+        .unwrap_or_else(Uuid::new_v4);
+    request
+        .extensions_mut()
+        .insert(CorrelationId(correlation_id));
+
+    let span = tracing::info_span!(
+        "request",
+        correlation_id = %correlation_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+
+    // Each request gets its own hub so the tag doesn't leak into other requests
+    let hub = Arc::new(Hub::new_from_top(Hub::current()));
+    hub.configure_scope(|scope| scope.set_tag("correlation_id", correlation_id));
+
+    let mut response = next.run(request).instrument(span).bind_hub(hub).await;
+    if let Ok(value) = HeaderValue::from_str(&correlation_id.to_string()) {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+    }
+    response
+}
@@ -16,8 +16,12 @@ pub async fn about(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let users_with_public_posts_and_banner = find_users_with_public_posts_and_banner(&mut tx)
         .await
@@ -28,6 +32,7 @@ pub async fn about(
         current_user => auth_session.user,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         users_with_public_posts_and_banner,
         ftl_lang,
     })?;
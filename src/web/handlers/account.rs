@@ -1,32 +1,49 @@
 use crate::app_error::{error_codes, AppError};
+use crate::models::community::{find_default_co_moderators, set_default_co_moderators};
 use crate::web::responses::ErrorResponse;
 use crate::models::email_verification_challenge::{
     create_email_verification_challenge, find_email_verification_challenge_by_id,
 };
+use crate::models::notification::{
+    get_notification_preferences, set_notification_preference, NotificationPreference,
+};
+use crate::models::totp::{is_totp_enabled, verify_login_code};
 use crate::models::user::{
-    delete_user_with_activity, find_user_by_id, update_password, update_user_email_verified_at,
-    update_user_preferred_language, update_user_show_sensitive_content, update_user_with_activity,
-    AuthSession, Language,
+    cancel_user_deletion, find_user_awaiting_deletion, find_user_by_id, find_user_by_login_name,
+    find_user_feed_algorithm, schedule_user_deletion, update_password,
+    update_user_email_verified_at, update_user_feed_algorithm, update_user_is_locked,
+    update_user_language_fallbacks, update_user_preferred_language,
+    update_user_show_sensitive_content, update_user_with_activity, AuthSession, FeedAlgorithm,
+    Language, ScheduledAccountDeletion, User, ACCOUNT_DELETION_GRACE_DAYS,
 };
 use crate::web::context::CommonContext;
-use crate::web::handlers::{get_bundle, safe_get_message, ExtractAcceptLanguage, ExtractFtlLang};
+use crate::web::handlers::activitypub::send_delete_activity_to_inboxes;
+use crate::web::handlers::auth::{
+    check_login_rate_limit, check_totp_rate_limit, reset_login_rate_limit,
+};
+use crate::web::handlers::{
+    get_user_bundle, safe_format_message, safe_get_message, safe_parse_email,
+    ExtractAcceptLanguage, ExtractClientIp, ExtractFtlLang,
+};
 use crate::web::state::AppState;
 use axum::response::{IntoResponse, Redirect};
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    extract::State,
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
     response::Html,
     Form, Json,
 };
 use axum_messages::Messages;
-use chrono::{TimeDelta, Utc};
-use fluent::FluentResource;
+use chrono::{DateTime, TimeDelta, Utc};
+use fluent::{FluentArgs, FluentResource};
 use intl_memoizer::concurrent::IntlLangMemoizer;
 use lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
 use lettre::{Message, SmtpTransport, Transport};
 use minijinja::context;
 use rand::{thread_rng, Rng};
 use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::Instrument;
 use uuid::Uuid;
 
 #[derive(Deserialize)]
@@ -44,21 +61,49 @@ pub async fn account(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    let default_co_moderators = match auth_session.user.as_ref() {
+        Some(user) => find_default_co_moderators(&mut tx, user.id).await?,
+        None => Vec::new(),
+    };
+    let totp_enabled = match auth_session.user.as_ref() {
+        Some(user) => is_totp_enabled(&mut tx, user.id).await?,
+        None => false,
+    };
+    let notification_preferences = match auth_session.user.as_ref() {
+        Some(user) => get_notification_preferences(&mut tx, user.id).await?,
+        None => Vec::new(),
+    };
+    let feed_algorithm = match auth_session.user.as_ref() {
+        Some(user) => find_user_feed_algorithm(&mut tx, user.id).await?,
+        None => FeedAlgorithm::default(),
+    };
 
     let languages = vec![
         ("ko", "한국어"),
         ("ja", "日本語"),
         ("en", "English"),
         ("zh", "中文"),
+        ("es", "Español"),
     ];
     let template: minijinja::Template<'_, '_> = state.env.get_template("account.jinja")?;
     let rendered = template.render(context! {
         current_user => auth_session.user,
         languages,
+        default_co_moderators,
+        totp_enabled,
+        totp_available => !state.config.totp_encryption_key.is_empty(),
+        notification_preferences,
+        feed_algorithm,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         messages => messages.into_iter().collect::<Vec<_>>(),
         ftl_lang
     })?;
@@ -69,6 +114,8 @@ pub async fn account(
 #[derive(Deserialize)]
 pub struct LanguageEditForm {
     pub language: Option<String>,
+    /// Comma-separated language codes, e.g. "ko, en"
+    pub language_fallbacks: Option<String>,
 }
 
 pub async fn save_language(
@@ -78,19 +125,21 @@ pub async fn save_language(
 ) -> Result<impl IntoResponse, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
-    let language = match form.language.as_deref() {
-        Some("ko") => Some(Language::Ko),
-        Some("ja") => Some(Language::Ja),
-        Some("en") => Some(Language::En),
-        Some("zh") => Some(Language::Zh),
-        _ => None,
-    };
-    let _ = update_user_preferred_language(
-        &mut tx,
-        auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id,
-        language,
-    )
-    .await;
+    let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
+    let language = form.language.as_deref().and_then(Language::from_code);
+
+    let mut language_fallbacks: Vec<Language> = Vec::new();
+    for code in form.language_fallbacks.as_deref().unwrap_or("").split(',') {
+        let Some(fallback) = Language::from_code(code.trim()) else {
+            continue;
+        };
+        if language.as_ref() != Some(&fallback) && !language_fallbacks.contains(&fallback) {
+            language_fallbacks.push(fallback);
+        }
+    }
+
+    let _ = update_user_preferred_language(&mut tx, user_id, language).await;
+    let _ = update_user_language_fallbacks(&mut tx, user_id, &language_fallbacks).await;
     let _ = tx.commit().await;
 
     Ok(Redirect::to("/account").into_response())
@@ -120,6 +169,199 @@ pub async fn save_show_sensitive_content(
     Ok(Redirect::to("/account").into_response())
 }
 
+#[derive(Deserialize)]
+pub struct IsLockedForm {
+    pub is_locked: Option<String>,
+}
+
+pub async fn save_is_locked(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Form(form): Form<IsLockedForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let is_locked = form.is_locked.as_deref() == Some("on");
+    update_user_is_locked(
+        &mut tx,
+        auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id,
+        is_locked,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(Redirect::to("/account").into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AccountSettingsForm {
+    pub feed_algorithm: FeedAlgorithm,
+}
+
+pub async fn save_account_settings(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Form(form): Form<AccountSettingsForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    update_user_feed_algorithm(&mut tx, user.id, form.feed_algorithm).await?;
+    tx.commit().await?;
+
+    let template = state.env.get_template("account_settings.jinja")?;
+    let rendered = template.render(context! {
+        feed_algorithm => form.feed_algorithm,
+        saved => true,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct DefaultCoModeratorsForm {
+    pub login_names: String,
+}
+
+pub async fn save_default_co_moderators(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    messages: Messages,
+    Form(form): Form<DefaultCoModeratorsForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let mut moderator_ids = Vec::new();
+    for login_name in form
+        .login_names
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|name| name.trim_start_matches('@'))
+        .filter(|name| !name.is_empty())
+    {
+        let Some(moderator) = find_user_by_login_name(&mut tx, login_name).await? else {
+            let mut args = FluentArgs::new();
+            args.set("login_name", login_name);
+            messages.error(safe_format_message(
+                &bundle,
+                "account-default-co-moderators-user-not-found",
+                Some(&args),
+            ));
+            return Ok(Redirect::to("/account").into_response());
+        };
+        if moderator.id == user.id {
+            messages.error(safe_get_message(
+                &bundle,
+                "account-default-co-moderators-self",
+            ));
+            return Ok(Redirect::to("/account").into_response());
+        }
+        moderator_ids.push(moderator.id);
+    }
+
+    set_default_co_moderators(&mut tx, user.id, &moderator_ids).await?;
+    tx.commit().await?;
+
+    Ok(Redirect::to("/account").into_response())
+}
+
+pub async fn notification_preferences(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let notification_preferences = get_notification_preferences(&mut tx, user.id).await?;
+    tx.commit().await?;
+
+    let template = state.env.get_template("notification_preferences.jinja")?;
+    let rendered = template.render(context! {
+        notification_preferences,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+/// Checked boxes arrive as `push_<NotificationType>=on`; unchecked ones are absent
+pub async fn save_notification_preferences(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    // The form only covers push, so email settings are carried over as they are
+    for mut preference in get_notification_preferences(&mut tx, user.id).await? {
+        let field = format!("push_{:?}", preference.notification_type);
+        preference.push_enabled = form.get(&field).map(String::as_str) == Some("on");
+        set_notification_preference(&mut tx, user.id, &preference).await?;
+    }
+    let notification_preferences = get_notification_preferences(&mut tx, user.id).await?;
+    tx.commit().await?;
+
+    let template = state.env.get_template("notification_preferences.jinja")?;
+    let rendered = template.render(context! {
+        notification_preferences,
+        saved => true,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn get_notification_preferences_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match auth_session.user.as_ref() {
+        Some(user) => user,
+        None => return Ok((StatusCode::UNAUTHORIZED).into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let notification_preferences = get_notification_preferences(&mut tx, user.id).await?;
+    tx.commit().await?;
+
+    Ok((StatusCode::OK, Json(notification_preferences)).into_response())
+}
+
+/// Types left out of the request body keep their current settings
+pub async fn save_notification_preferences_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Json(preferences): Json<Vec<NotificationPreference>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match auth_session.user.as_ref() {
+        Some(user) => user,
+        None => return Ok((StatusCode::UNAUTHORIZED).into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    for preference in &preferences {
+        set_notification_preference(&mut tx, user.id, preference).await?;
+    }
+    let notification_preferences = get_notification_preferences(&mut tx, user.id).await?;
+    tx.commit().await?;
+
+    Ok((StatusCode::OK, Json(notification_preferences)).into_response())
+}
+
 #[derive(Deserialize)]
 pub struct EditPasswordForm {
     current_password: String,
@@ -128,24 +370,18 @@ pub struct EditPasswordForm {
 }
 
 pub async fn edit_password(
-    auth_session: AuthSession,
+    mut auth_session: AuthSession,
     ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
     messages: Messages,
     State(state): State<AppState>,
     Form(form): Form<EditPasswordForm>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user_preferred_language = auth_session
-        .user
-        .clone()
-        .map(|u| u.preferred_language)
-        .unwrap_or_else(|| None);
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
 
-    let current_user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
 
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
-    let user_id = current_user.id;
     let user = find_user_by_id(&mut tx, user_id)
         .await?
         .ok_or_else(|| AppError::NotFound("User".to_string()))?;
@@ -171,8 +407,13 @@ pub async fn edit_password(
         ));
         return Ok(Redirect::to("/account").into_response());
     }
-    let _ = update_password(&mut tx, user_id, form.new_password).await;
-    let _ = tx.commit().await;
+    let updated_user = update_password(&mut tx, user_id, form.new_password).await?;
+    tx.commit().await?;
+
+    // Other sessions end with the old password; keep this one signed in
+    if auth_session.login(&updated_user).await.is_err() {
+        return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+    }
 
     messages.success(safe_get_message(&bundle, "account-change-password-success"));
     Ok(Redirect::to("/account").into_response())
@@ -192,12 +433,7 @@ pub async fn verify_email_verification_code(
     let now = Utc::now();
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("email_verify.jinja")?;
-    let user_preferred_language = auth_session
-        .user
-        .clone()
-        .map(|u| u.preferred_language)
-        .unwrap_or_else(|| None);
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
 
     if challenge.token != form.token {
         let ftl_lang = bundle
@@ -269,12 +505,7 @@ pub async fn request_email_verification_code(
     State(state): State<AppState>,
     Form(form): Form<RequestEmailVerificationCodeForm>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user_preferred_language = auth_session
-        .user
-        .clone()
-        .map(|u| u.preferred_language)
-        .unwrap_or_else(|| None);
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
     let edit_email_template = state.env.get_template("email_edit.jinja")?;
 
     let current_user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
@@ -297,6 +528,21 @@ pub async fn request_email_verification_code(
         .into_response());
     }
 
+    let email = safe_parse_email(&form.email)?;
+    if !state.config.is_email_domain_allowed(email.domain()) {
+        let ftl_lang = bundle
+            .locales
+            .first()
+            .map(|l| l.to_string())
+            .unwrap_or_else(|| "en".to_string());
+        return Ok(Html(edit_email_template.render(context! {
+            current_user => auth_session.user,
+            message => safe_get_message(&bundle, "account-change-email-error-domain-blocked"),
+            ftl_lang,
+        })?)
+        .into_response());
+    }
+
     // Use shared helper function to create challenge and send email
     let email_verification_challenge = create_and_send_verification_email(
         &state,
@@ -336,12 +582,7 @@ pub async fn edit_account(
     State(state): State<AppState>,
     Form(form): Form<EditUserForm>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user_preferred_language = auth_session
-        .user
-        .clone()
-        .map(|u| u.preferred_language)
-        .unwrap_or_else(|| None);
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
 
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -361,6 +602,126 @@ pub async fn edit_account(
     Ok(Redirect::to("/account").into_response())
 }
 
+/// Tells followers' servers the actor is gone and emails the user how to change
+/// their mind. Runs after the deletion is scheduled; failures are only logged.
+async fn announce_account_deletion(
+    state: AppState,
+    user: User,
+    scheduled: ScheduledAccountDeletion,
+) {
+    if let Some(actor) = scheduled.actor {
+        match actor.iri.parse() {
+            Ok(actor_url) => {
+                if let Err(e) = send_delete_activity_to_inboxes(
+                    &actor,
+                    actor_url,
+                    scheduled.follower_inboxes,
+                    &state,
+                )
+                .await
+                {
+                    tracing::warn!(
+                        "Failed to send Delete activity for user {}: {:?}",
+                        user.id,
+                        e
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("Invalid actor IRI {}: {:?}", actor.iri, e),
+        }
+    }
+
+    if let Err(e) = send_account_deletion_email(&state, &user, scheduled.finalizes_at) {
+        tracing::warn!(
+            "Failed to email deletion confirmation to user {}: {:?}",
+            user.id,
+            e
+        );
+    }
+}
+
+fn send_account_deletion_email(
+    state: &AppState,
+    user: &User,
+    finalizes_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let email = match (&user.email, user.email_verified_at) {
+        (Some(email), Some(_)) => email.clone(),
+        _ => return Ok(()),
+    };
+
+    // The request's language is gone with the session, so use the user's saved languages
+    let bundle = get_user_bundle(&HeaderValue::from_static(""), Some(user));
+    let mut args = FluentArgs::new();
+    args.set("date", finalizes_at.format("%Y-%m-%d").to_string());
+    let email_message = Message::builder()
+        .from(safe_get_message(&bundle, "email-from-address").parse()?)
+        .to(email.parse()?)
+        .subject(safe_get_message(&bundle, "account-delete-email-subject"))
+        .body(format!(
+            "{}\n\nhttps://{}/account/delete/cancel",
+            safe_format_message(&bundle, "account-delete-email-body", Some(&args)),
+            state.config.domain
+        ))?;
+
+    let mailer = SmtpTransport::relay(&state.config.smtp_host)?
+        .credentials(SmtpCredentials::new(
+            state.config.smtp_user.clone(),
+            state.config.smtp_password.clone(),
+        ))
+        .build();
+    mailer.send(&email_message)?;
+
+    Ok(())
+}
+
+/// Schedules the user's deletion and announces it. The error explains why the
+/// deletion was refused.
+async fn start_account_deletion(
+    state: &AppState,
+    user: &User,
+    password: &str,
+) -> anyhow::Result<()> {
+    let mut tx = state.db_pool.begin().await?;
+    let scheduled = schedule_user_deletion(&mut tx, user.id, password).await?;
+    tx.commit().await?;
+
+    tokio::spawn(
+        announce_account_deletion(state.clone(), user.clone(), scheduled).in_current_span(),
+    );
+
+    Ok(())
+}
+
+pub async fn delete_account_page(
+    messages: Messages,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let Some(user) = auth_session.user.as_ref() else {
+        return Ok(Redirect::to("/login?next=/account/delete").into_response());
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("account_delete.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        grace_days => ACCOUNT_DELETION_GRACE_DAYS,
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
 #[derive(Deserialize)]
 pub struct DeleteAccountRequest {
     password: String,
@@ -385,39 +746,21 @@ pub async fn delete_account(
         }
     };
 
-    let db = &state.db_pool;
-    let mut tx = db.begin().await?;
-
-    // Attempt to delete the user
-    match delete_user_with_activity(
-        &mut tx,
-        user.id,
-        &payload.password,
-        &state.config,
-        Some(&state),
-    )
-    .await
-    {
-        Ok(_) => {
-            tx.commit().await?;
-
+    match start_account_deletion(&state, &user, &payload.password).await {
+        Ok(()) => {
             // Log the user out
             auth_session.logout().await?;
 
             Ok(StatusCode::NO_CONTENT.into_response())
         }
-        Err(e) => {
-            tx.rollback().await?;
-
-            Ok((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(
-                    error_codes::VALIDATION_ERROR,
-                    e.to_string(),
-                )),
-            )
-                .into_response())
-        }
+        Err(e) => Ok((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                error_codes::VALIDATION_ERROR,
+                e.to_string(),
+            )),
+        )
+            .into_response()),
     }
 }
 
@@ -426,56 +769,110 @@ pub struct DeleteAccountForm {
     password: String,
 }
 
-pub async fn delete_account_htmx(
+pub async fn do_delete_account(
     mut auth_session: AuthSession,
+    messages: Messages,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
     State(state): State<AppState>,
-    Query(form): Query<DeleteAccountForm>,
+    Form(form): Form<DeleteAccountForm>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user = match auth_session.user.as_ref() {
-        Some(user) => user.clone(),
-        None => {
-            return Ok((StatusCode::OK, [("HX-Redirect", "/login")]).into_response());
-        }
-    };
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    let bundle = get_user_bundle(&accept_language, Some(&user));
 
-    let db = &state.db_pool;
-    let mut tx = db.begin().await?;
+    if let Err(e) = start_account_deletion(&state, &user, &form.password).await {
+        messages.error(e.to_string());
+        return Ok(Redirect::to("/account/delete").into_response());
+    }
 
-    // Attempt to delete the user
-    match delete_user_with_activity(
-        &mut tx,
-        user.id,
-        &form.password,
-        &state.config,
-        Some(&state),
-    )
-    .await
-    {
-        Ok(_) => {
-            tx.commit().await?;
+    // Log the user out
+    auth_session.logout().await?;
 
-            // Log the user out
-            auth_session.logout().await?;
+    let mut args = FluentArgs::new();
+    args.set("days", ACCOUNT_DELETION_GRACE_DAYS);
+    messages.success(safe_format_message(
+        &bundle,
+        "account-delete-scheduled",
+        Some(&args),
+    ));
+    Ok(Redirect::to("/").into_response())
+}
+
+pub async fn cancel_account_deletion_page(
+    messages: Messages,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+) -> Result<Html<String>, AppError> {
+    let template = state.env.get_template("account_delete_cancel.jinja")?;
+    let rendered = template.render(context! {
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered))
+}
+
+#[derive(Deserialize)]
+pub struct CancelAccountDeletionForm {
+    login_name: String,
+    password: String,
+    /// Required when the account has two-factor authentication
+    totp_code: Option<String>,
+}
+
+/// Restores an account during its grace period. The account is signed out while it
+/// awaits deletion, so this asks for the same credentials as logging in, and is
+/// throttled like it.
+pub async fn do_cancel_account_deletion(
+    messages: Messages,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractClientIp(client_ip): ExtractClientIp,
+    State(state): State<AppState>,
+    Form(form): Form<CancelAccountDeletionForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let bundle = get_user_bundle(&accept_language, None);
+
+    if let Some(retry_after) = check_login_rate_limit(&state, client_ip, &form.login_name).await {
+        return Ok((
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            safe_get_message(&bundle, "message-too-many-attempts"),
+        )
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let Some(user) = find_user_awaiting_deletion(&mut tx, &form.login_name, &form.password).await?
+    else {
+        messages.error(safe_get_message(&bundle, "account-delete-cancel-failed"));
+        return Ok(Redirect::to("/account/delete/cancel").into_response());
+    };
 
-            // Redirect to homepage with HX-Redirect header
-            Ok((StatusCode::OK, [("HX-Redirect", "/")]).into_response())
+    if is_totp_enabled(&mut tx, user.id).await? {
+        if check_totp_rate_limit(&state, &user.login_name)
+            .await
+            .is_some()
+        {
+            messages.error(safe_get_message(&bundle, "message-too-many-attempts"));
+            return Ok(Redirect::to("/account/delete/cancel").into_response());
         }
-        Err(e) => {
-            // Don't commit the transaction on error
-            let _ = tx.rollback().await;
-
-            // Return error message as HTML for HTMX to display
-            // Basic HTML escaping for safety
-            let error_msg = e
-                .to_string()
-                .replace('&', "&amp;")
-                .replace('<', "&lt;")
-                .replace('>', "&gt;")
-                .replace('"', "&quot;");
-            let error_html = format!(r#"<p class="error" style="color: red;">{}</p>"#, error_msg);
-            Ok((StatusCode::OK, Html(error_html)).into_response())
+        let code = form.totp_code.as_deref().unwrap_or_default();
+        if !verify_login_code(&mut tx, &state.config, &user, code).await? {
+            messages.error(safe_get_message(&bundle, "totp-error-invalid-code"));
+            return Ok(Redirect::to("/account/delete/cancel").into_response());
         }
     }
+
+    if !cancel_user_deletion(&mut tx, user.id).await? {
+        messages.error(safe_get_message(&bundle, "account-delete-cancel-failed"));
+        return Ok(Redirect::to("/account/delete/cancel").into_response());
+    }
+    tx.commit().await?;
+    reset_login_rate_limit(&state, &user.login_name).await;
+
+    tracing::info!("User {} cancelled their account deletion", user.id);
+    messages.success(safe_get_message(&bundle, "account-delete-cancelled"));
+    Ok(Redirect::to("/login").into_response())
 }
 
 // JSON API endpoints for mobile apps
@@ -593,8 +990,20 @@ pub async fn request_email_verification_json(
             .into_response());
     }
 
-    let user_preferred_language = user.preferred_language;
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    if let Ok(address) = payload.email.parse::<lettre::Address>() {
+        if !state.config.is_email_domain_allowed(address.domain()) {
+            return Ok((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(
+                    error_codes::EMAIL_DOMAIN_NOT_ALLOWED,
+                    "Email domain is not allowed",
+                )),
+            )
+                .into_response());
+        }
+    }
+
+    let bundle = get_user_bundle(&accept_language, Some(&user));
 
     // Create challenge and send email using shared helper
     match create_and_send_verification_email(&state, user.id, &payload.email, &bundle).await {
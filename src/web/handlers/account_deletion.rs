@@ -0,0 +1,112 @@
+use crate::models::user::{finalize_user_deletion, find_users_due_for_deletion};
+use crate::web::state::AppState;
+use aws_sdk_s3::config::{Credentials as AwsCredentials, Region, SharedCredentialsProvider};
+use aws_sdk_s3::types::{Delete, ObjectIdentifier};
+use aws_sdk_s3::Client;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const FINALIZE_INTERVAL_SECONDS: u64 = 3600;
+const FINALIZE_BATCH_SIZE: i64 = 100;
+// DeleteObjects accepts at most this many keys per request
+const OBJECT_DELETE_BATCH_SIZE: usize = 1000;
+
+/// Periodically remove the data of accounts whose deletion grace period has run out
+pub async fn finalize_account_deletions(state: AppState) {
+    info!(
+        "Starting account deletion finalizer (interval: {}s)",
+        FINALIZE_INTERVAL_SECONDS
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(FINALIZE_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = finalize_due_accounts(&state).await {
+            error!("Failed to finalize account deletions: {:?}", e);
+        }
+    }
+}
+
+async fn finalize_due_accounts(state: &AppState) -> anyhow::Result<()> {
+    let mut tx = state.db_pool.begin().await?;
+    let user_ids = find_users_due_for_deletion(&mut tx, FINALIZE_BATCH_SIZE).await?;
+    tx.commit().await?;
+
+    for user_id in user_ids {
+        // One account per transaction, so a failure only holds back that account
+        let mut tx = state.db_pool.begin().await?;
+        let deleted = match finalize_user_deletion(&mut tx, user_id).await {
+            Ok(deleted) => deleted,
+            Err(e) => {
+                error!("Failed to finalize deletion of user {}: {:?}", user_id, e);
+                continue;
+            }
+        };
+        tx.commit().await?;
+
+        purge_account_objects(state, user_id, &deleted.object_keys).await;
+        info!("Finalized deletion of user {}", user_id);
+    }
+
+    Ok(())
+}
+
+/// Removes a deleted account's objects from R2. Runs after the deletion is committed,
+/// so a failure here can't leave the database half-updated; failures are only logged.
+async fn purge_account_objects(state: &AppState, user_id: Uuid, object_keys: &[String]) {
+    if object_keys.is_empty() {
+        return;
+    }
+
+    let credentials = AwsCredentials::new(
+        state.config.aws_access_key_id.clone(),
+        state.config.aws_secret_access_key.clone(),
+        None,
+        None,
+        "",
+    );
+    let credentials_provider = SharedCredentialsProvider::new(credentials);
+    let config = aws_sdk_s3::Config::builder()
+        .endpoint_url(state.config.r2_endpoint_url.clone())
+        .region(Region::new(state.config.aws_region.clone()))
+        .credentials_provider(credentials_provider)
+        .behavior_version_latest()
+        .build();
+    let client = Client::from_conf(config);
+
+    for keys in object_keys.chunks(OBJECT_DELETE_BATCH_SIZE) {
+        let objects = match keys
+            .iter()
+            .map(|key| ObjectIdentifier::builder().key(key).build())
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(objects) => objects,
+            Err(e) => {
+                warn!("Failed to build object identifiers: {:?}", e);
+                continue;
+            }
+        };
+        let delete = match Delete::builder().set_objects(Some(objects)).build() {
+            Ok(delete) => delete,
+            Err(e) => {
+                warn!("Failed to build object deletion: {:?}", e);
+                continue;
+            }
+        };
+        if let Err(e) = client
+            .delete_objects()
+            .bucket(state.config.aws_s3_bucket.clone())
+            .delete(delete)
+            .send()
+            .await
+        {
+            warn!(
+                "Failed to delete objects of deleted user {}: {:?}",
+                user_id, e
+            );
+        }
+    }
+}
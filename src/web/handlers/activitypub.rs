@@ -1,8 +1,10 @@
 use activitypub_federation::axum::inbox::{receive_activity, ActivityData};
 use activitypub_federation::axum::json::FederationJson;
 use activitypub_federation::config::Data;
+use activitypub_federation::error::Error as FederationError;
 use activitypub_federation::fetch::object_id::ObjectId;
 use activitypub_federation::fetch::webfinger::{build_webfinger_response, extract_webfinger_name};
+use activitypub_federation::fetch::{fetch_object_http, FetchObjectResponse};
 use activitypub_federation::kinds::actor::PersonType;
 use activitypub_federation::protocol::context::WithContext;
 use activitypub_federation::protocol::public_key::PublicKey;
@@ -10,9 +12,11 @@ use activitypub_federation::protocol::verification::verify_domains_match;
 use activitypub_federation::traits::{
     ActivityHandler, Actor as ActivityPubFederationActor, Object,
 };
+use activitypub_federation::FEDERATION_CONTENT_TYPE;
 
 use activitystreams_kinds::activity::{
-    AcceptType, AnnounceType, CreateType, DeleteType, FollowType, UndoType, UpdateType,
+    AcceptType, AnnounceType, CreateType, DeleteType, FlagType, FollowType, MoveType, RejectType,
+    UndoType, UpdateType,
 };
 use activitystreams_kinds::actor::GroupType;
 use activitystreams_kinds::object::NoteType;
@@ -20,6 +24,7 @@ use axum::extract::{Path, Query};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::Json;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use url::Url;
@@ -27,6 +32,7 @@ use uuid::Uuid;
 
 use crate::app_error::AppError;
 use crate::markdown_utils::process_markdown_content;
+use crate::metrics::record_inbox_activity;
 
 fn extract_note_content(note: &Note) -> (String, Option<String>) {
     // Try to get HTML content from contents field or content field
@@ -87,20 +93,35 @@ fn extract_note_content(note: &Note) -> (String, Option<String>) {
 
     (markdown_content, html_content)
 }
-use crate::models::actor::{create_actor_for_user, Actor, ActorType};
+use crate::models::actor::{
+    create_actor_for_user, find_or_create_instance_actor, Actor, ActorType,
+};
 use crate::models::comment::{
     create_comment_from_activitypub, delete_comment_by_iri, find_comment_by_iri,
+    get_remote_inboxes_for_post_audience, Comment,
 };
 use crate::models::community::{find_community_by_id, find_community_by_slug, CommunityVisibility};
+use crate::models::federated_post::{
+    create_federated_post, delete_federated_post_by_announce_iri, delete_federated_post_by_iri,
+    find_federated_post_by_iri, FederatedPostDraft,
+};
 use crate::models::follow;
-use crate::models::image::find_image_by_id;
+use crate::models::follow_request;
+use crate::models::guestbook_entry::{create_guestbook_entry, GuestbookEntryDraft};
+use crate::models::image::{find_image_by_id, image_media_type, image_url};
 use crate::models::notification::{
     create_notification, get_notification_by_id, get_unread_count, send_push_for_notification,
     CreateNotificationParams, NotificationType,
 };
-use crate::models::post::find_post_by_id;
-use crate::models::user::{find_user_by_id, find_user_by_login_name};
+use crate::models::post::{
+    count_federatable_posts_by_author_id, find_federatable_post_ids_by_author_id,
+    find_pinned_posts_by_user, find_post_by_id, is_post_deleted,
+};
+use crate::models::report::create_object_report;
+use crate::models::user::{find_user_avatar_filename, find_user_by_id, find_user_by_login_name};
+use crate::web::handlers::post::spawn_push_notifications;
 use crate::web::state::AppState;
+use tracing::Instrument;
 
 // Custom deserializers for flexible ActivityPub field formats
 fn string_or_vec_deser<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -199,6 +220,21 @@ pub struct Person {
     followers: Url,
     manually_approves_followers: bool,
     url: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    featured: Option<Url>,
+    // Kept as raw JSON since remote servers vary in how they shape it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    icon: Option<serde_json::Value>,
+    // Earlier accounts of the same person, which a Move must be backed by
+    #[serde(
+        default,
+        deserialize_with = "string_or_vec_deser",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    also_known_as: Vec<String>,
+    // Set once the account has itself moved elsewhere
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    moved_to: Option<Url>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -242,7 +278,23 @@ impl Object for Actor {
         Ok(actor)
     }
 
-    async fn into_json(self, _data: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
+    async fn into_json(self, data: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
+        let icon = match self.user_id {
+            Some(user_id) => {
+                let mut tx = data.app_data().db_pool.begin().await?;
+                let avatar_filename = find_user_avatar_filename(&mut tx, user_id).await?;
+                tx.commit().await?;
+                avatar_filename.map(|filename| {
+                    serde_json::json!({
+                        "type": "Image",
+                        "mediaType": image_media_type(&filename),
+                        "url": image_url(&data.app_data().config.r2_public_endpoint_url, &filename),
+                    })
+                })
+            }
+            None => None,
+        };
+
         let public_key = PublicKey {
             id: format!("{}#main-key", self.iri)
                 .parse()
@@ -278,6 +330,11 @@ impl Object for Actor {
             | ActorType::Service
             | ActorType::Application
             | ActorType::Organization => Ok(ActorObject::Person(Person {
+                // Only local users have a featured (pinned posts) collection
+                featured: match self.user_id {
+                    Some(_) => Some(format!("{}/featured", self.iri).parse()?),
+                    None => None,
+                },
                 id: ObjectId::parse(&self.iri)?,
                 r#type: PersonType::Person,
                 inbox: self.inbox_url.parse()?,
@@ -289,6 +346,9 @@ impl Object for Actor {
                 outbox: format!("{}/outbox", self.iri).parse()?,
                 preferred_username: self.username,
                 url: self.url.parse()?,
+                icon,
+                also_known_as: Vec::new(),
+                moved_to: None,
             })),
         }
     }
@@ -482,20 +542,16 @@ pub async fn activitypub_get_user(
     .await?
     {
         let json_actor = actor.into_json(&data).await?;
-        let context = [
+        let context = serde_json::json!([
             "https://www.w3.org/ns/activitystreams",
             "https://w3id.org/security/v1",
-        ];
+            {
+                "toot": "http://joinmastodon.org/ns#",
+                "featured": { "@id": "toot:featured", "@type": "@id" }
+            }
+        ]);
 
-        let activity = WithContext::new(
-            json_actor,
-            serde_json::Value::Array(
-                context
-                    .into_iter()
-                    .map(|s| serde_json::Value::String(s.to_string()))
-                    .collect(),
-            ),
-        );
+        let activity = WithContext::new(json_actor, context);
         Ok(FederationJson(activity).into_response())
     } else {
         Ok((StatusCode::NOT_FOUND, "Actor not found").into_response())
@@ -538,6 +594,32 @@ pub async fn activitypub_get_community(
     }
 }
 
+/// The instance actor, whose key signs fetches of remote objects
+pub async fn activitypub_get_instance_actor(
+    data: Data<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut tx = data.app_data().db_pool.begin().await?;
+    let actor = find_or_create_instance_actor(&mut tx, &data.app_data().config).await?;
+    tx.commit().await?;
+
+    let json_actor = actor.into_json(&data).await?;
+    let context = [
+        "https://www.w3.org/ns/activitystreams",
+        "https://w3id.org/security/v1",
+    ];
+
+    let activity = WithContext::new(
+        json_actor,
+        serde_json::Value::Array(
+            context
+                .into_iter()
+                .map(|s| serde_json::Value::String(s.to_string()))
+                .collect(),
+        ),
+    );
+    Ok(FederationJson(activity).into_response())
+}
+
 pub async fn activitypub_get_post(
     _header_map: HeaderMap,
     Path(post_id): Path<String>,
@@ -549,6 +631,15 @@ pub async fn activitypub_get_post(
     let post_uuid = Uuid::parse_str(&post_id)?;
 
     if let Some(post) = find_post_by_id(&mut tx, post_uuid).await? {
+        // Posts waiting for review aren't federated until they are approved
+        if post
+            .get("pending_approval_at")
+            .and_then(|v| v.as_ref())
+            .is_some()
+        {
+            return Ok((StatusCode::NOT_FOUND, "Post not found").into_response());
+        }
+
         // Check community visibility - only expose posts from public and unlisted communities via ActivityPub
         // Private community posts should not be accessible
         // Personal posts (no community) are always accessible
@@ -597,6 +688,7 @@ pub async fn activitypub_get_post(
             &author_actor,
             &data.app_data().config.domain,
             &data.app_data().config.r2_public_endpoint_url,
+            &data.app_data().config.sensitive_content_warning,
         )
         .await?;
 
@@ -618,6 +710,9 @@ pub async fn activitypub_get_post(
             ),
         ))
         .into_response())
+    } else if is_post_deleted(&mut tx, post_uuid).await? {
+        // Remote servers treat 410 as the object having been deleted
+        Ok((StatusCode::GONE, "Post deleted").into_response())
     } else {
         Ok((StatusCode::NOT_FOUND, "Post not found").into_response())
     }
@@ -645,6 +740,7 @@ pub async fn activitypub_post_user_inbox(
     .await;
 
     tracing::info!("=== DEBUG: receive_activity function completed ===");
+    record_inbox_activity("user", &result);
 
     if let Err(ref e) = result {
         tracing::error!("Activity processing failed: {:?}", e);
@@ -671,14 +767,43 @@ pub async fn activitypub_post_community_inbox(
     activity_data: ActivityData,
 ) -> impl IntoResponse {
     tracing::warn!("🔔 COMMUNITY INBOX: Request received at /ap/communities/*/inbox");
-    receive_activity::<WithContext<GroupAcceptedActivities>, Actor, AppState>(activity_data, &data)
-        .await
+    let result = receive_activity::<WithContext<GroupAcceptedActivities>, Actor, AppState>(
+        activity_data,
+        &data,
+    )
+    .await;
+    record_inbox_activity("community", &result);
+    result
+}
+
+/// Group inbox addressed by slug: POST /communities/@:slug/inbox
+pub async fn community_inbox_handler(
+    Path(slug): Path<String>,
+    data: Data<AppState>,
+    activity_data: ActivityData,
+) -> Result<impl IntoResponse, AppError> {
+    let mut tx = data.db_pool.begin().await?;
+    let community = find_community_by_slug(&mut tx, slug).await?;
+    tx.commit().await?;
+
+    if community.is_none() {
+        return Ok((StatusCode::NOT_FOUND, "Community not found").into_response());
+    }
+
+    let result = receive_activity::<WithContext<GroupAcceptedActivities>, Actor, AppState>(
+        activity_data,
+        &data,
+    )
+    .await;
+    record_inbox_activity("community", &result);
+    result?;
+    Ok(StatusCode::OK.into_response())
 }
 
 pub async fn activitypub_post_user_followers(
     Path(login_name): Path<String>,
     data: Data<AppState>,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, AppError> {
     tracing::warn!(
         "🔔 USER FOLLOWERS: Request received at /ap/users/{}/followers",
         login_name
@@ -687,15 +812,166 @@ pub async fn activitypub_post_user_followers(
     let domain = &data.app_data().config.domain;
     let followers_url = format!("https://{}/ap/users/{}/followers", domain, login_name);
 
-    // Return empty OrderedCollection following ActivityPub spec
+    // Only accepted follows count; pending follow requests are left out
+    let db = &data.app_data().db_pool;
+    let mut tx = db.begin().await?;
+    let total_items = match Uuid::parse_str(&login_name) {
+        Ok(user_id) => match Actor::find_by_user_id(&mut tx, user_id).await? {
+            Some(actor) => follow::count_followers_by_actor_id(&mut tx, actor.id).await?,
+            None => 0,
+        },
+        Err(_) => 0,
+    };
+    tx.commit().await?;
+
+    // Return the follower count without listing followers, following ActivityPub spec
     let collection = serde_json::json!({
         "type": "OrderedCollection",
         "id": followers_url,
         "@context": "https://www.w3.org/ns/activitystreams",
-        "totalItems": 0,
+        "totalItems": total_items,
+    });
+
+    Ok(Json(collection))
+}
+
+#[derive(Deserialize)]
+pub struct OutboxQuery {
+    page: Option<i64>,
+}
+
+pub async fn activitypub_get_user_outbox(
+    Path(login_name): Path<String>,
+    Query(query): Query<OutboxQuery>,
+    data: Data<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &data.app_data().db_pool;
+    let mut tx = db.begin().await?;
+
+    let user_id = match Uuid::parse_str(&login_name) {
+        Ok(user_id) => user_id,
+        Err(_) => return Ok((StatusCode::NOT_FOUND, "Actor not found").into_response()),
+    };
+    let actor = match Actor::find_by_user_id(&mut tx, user_id).await? {
+        Some(actor) => actor,
+        None => return Ok((StatusCode::NOT_FOUND, "Actor not found").into_response()),
+    };
+
+    let outbox_url = format!("{}/outbox", actor.iri);
+    let page_size = data.app_data().config.activitypub_outbox_page_size.max(1);
+    let total_items = count_federatable_posts_by_author_id(&mut tx, user_id).await?;
+    let last_page = ((total_items + page_size - 1) / page_size).max(1);
+
+    // Without a page, return the collection itself pointing at its first and last pages
+    let page = match query.page {
+        Some(page) if page >= 1 => page,
+        Some(_) => return Ok((StatusCode::BAD_REQUEST, "Invalid page").into_response()),
+        None => {
+            let collection = serde_json::json!({
+                "type": "OrderedCollection",
+                "id": outbox_url,
+                "@context": "https://www.w3.org/ns/activitystreams",
+                "totalItems": total_items,
+                "first": format!("{}?page=1", outbox_url),
+                "last": format!("{}?page={}", outbox_url, last_page),
+            });
+            return Ok(Json(collection).into_response());
+        }
+    };
+    // Pages past the end don't exist, which also keeps the offset from overflowing
+    if page > last_page {
+        return Ok((StatusCode::NOT_FOUND, "Page not found").into_response());
+    }
+
+    let post_ids =
+        find_federatable_post_ids_by_author_id(&mut tx, user_id, page_size, (page - 1) * page_size)
+            .await?;
+
+    let actor_object_id = ObjectId::<Actor>::parse(&actor.iri)?;
+    let mut ordered_items = Vec::with_capacity(post_ids.len());
+    for post_id in post_ids {
+        let note = create_note_from_post(
+            &mut tx,
+            post_id,
+            &actor,
+            &data.app_data().config.domain,
+            &data.app_data().config.r2_public_endpoint_url,
+            &data.app_data().config.sensitive_content_warning,
+        )
+        .await?;
+
+        // Outbox activities need stable IDs, so derive them from the note
+        let activity_id: Url = format!("{}/activity", note.id).parse()?;
+        let to = note.to.clone();
+        let cc = note.cc.clone();
+        let published = note.published.clone().unwrap_or_default();
+        let create = Create::new(actor_object_id.clone(), note, activity_id, to, cc, published);
+        ordered_items.push(serde_json::to_value(create)?);
+    }
+
+    tx.commit().await?;
+
+    let mut collection_page = serde_json::json!({
+        "type": "OrderedCollectionPage",
+        "id": format!("{}?page={}", outbox_url, page),
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "partOf": outbox_url,
+        "totalItems": total_items,
+        "orderedItems": ordered_items,
+    });
+    if page > 1 {
+        collection_page["prev"] = Value::String(format!("{}?page={}", outbox_url, page - 1));
+    }
+    if page < last_page {
+        collection_page["next"] = Value::String(format!("{}?page={}", outbox_url, page + 1));
+    }
+
+    Ok(Json(collection_page).into_response())
+}
+
+pub async fn activitypub_get_user_featured(
+    Path(login_name): Path<String>,
+    data: Data<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &data.app_data().db_pool;
+    let mut tx = db.begin().await?;
+
+    let user_id = match Uuid::parse_str(&login_name) {
+        Ok(user_id) => user_id,
+        Err(_) => return Ok((StatusCode::NOT_FOUND, "Actor not found").into_response()),
+    };
+    let actor = match Actor::find_by_user_id(&mut tx, user_id).await? {
+        Some(actor) => actor,
+        None => return Ok((StatusCode::NOT_FOUND, "Actor not found").into_response()),
+    };
+
+    let pinned_posts = find_pinned_posts_by_user(&mut tx, user_id).await?;
+
+    let mut ordered_items = Vec::with_capacity(pinned_posts.len());
+    for post in pinned_posts {
+        let note = create_note_from_post(
+            &mut tx,
+            post.id,
+            &actor,
+            &data.app_data().config.domain,
+            &data.app_data().config.r2_public_endpoint_url,
+            &data.app_data().config.sensitive_content_warning,
+        )
+        .await?;
+        ordered_items.push(serde_json::to_value(note)?);
+    }
+
+    tx.commit().await?;
+
+    let collection = serde_json::json!({
+        "type": "OrderedCollection",
+        "id": format!("{}/featured", actor.iri),
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "totalItems": ordered_items.len(),
+        "orderedItems": ordered_items,
     });
 
-    Json(collection)
+    Ok(Json(collection).into_response())
 }
 
 pub async fn activitypub_post_shared_inbox(
@@ -715,6 +991,7 @@ pub async fn activitypub_post_shared_inbox(
     .await;
 
     tracing::info!("=== DEBUG: shared inbox receive_activity function completed ===");
+    record_inbox_activity("shared", &result);
 
     if let Err(ref e) = result {
         tracing::error!("Shared inbox activity processing failed: {:?}", e);
@@ -749,6 +1026,9 @@ pub enum PersonAcceptedActivities {
     Delete(Delete),
     Like(Like),
     EmojiReact(EmojiReact),
+    Announce(Announce),
+    Move(Move),
+    Flag(Flag),
     Unknown(UnknownActivity),
 }
 
@@ -826,6 +1106,7 @@ pub enum GroupAcceptedActivities {
     Undo(Undo),
     Update(Box<Update>),
     Delete(Box<Delete>),
+    Create(Box<Create>),
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -897,6 +1178,27 @@ impl ActivityHandler for Follow {
         };
         tracing::info!("persisted_follower: {:?}", persisted_follower);
 
+        // Locked accounts approve followers themselves; the Accept is sent from
+        // the follow requests page instead
+        let is_locked = match following_actor.user_id {
+            Some(user_id) => find_user_by_id(&mut tx, user_id)
+                .await?
+                .is_some_and(|user| user.is_locked),
+            None => false,
+        };
+        if is_locked {
+            let follow_request = follow_request::create_follow_request(
+                &mut tx,
+                persisted_follower.id,
+                following_actor.id,
+                self.id.as_str(),
+            )
+            .await?;
+            tracing::info!("follow_request: {:?}", follow_request);
+            tx.commit().await?;
+            return Ok(());
+        }
+
         // Create the follow relationship
         let follow_relation =
             follow::create_follow_by_actor_ids(&mut tx, persisted_follower.id, following_actor.id)
@@ -967,12 +1269,56 @@ impl ActivityHandler for Accept {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Reject {
+    actor: ObjectId<Actor>,
+    object: Follow,
+    r#type: RejectType,
+    id: Url,
+}
+
+impl Reject {
+    pub fn new(actor: ObjectId<Actor>, object: Follow, id: Url) -> Reject {
+        Reject {
+            actor,
+            object,
+            r#type: Default::default(),
+            id,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for Reject {
+    type DataType = AppState;
+    type Error = AppError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn receive(self, _data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        // Only sent by locked accounts declining a follow request
+        Ok(())
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum UndoObject {
     Follow(Box<Follow>),
     Like(Box<Like>),
     EmojiReact(Box<EmojiReact>),
+    Announce(Box<Announce>),
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -993,6 +1339,14 @@ impl Undo {
             id,
         }
     }
+    pub fn new_announce(actor: ObjectId<Actor>, object: Announce, id: Url) -> Undo {
+        Undo {
+            actor,
+            object: UndoObject::Announce(Box::new(object)),
+            r#type: Default::default(),
+            id,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -1034,9 +1388,15 @@ impl ActivityHandler for Undo {
                 let follower_actor =
                     follower_actor.ok_or_else(|| anyhow::anyhow!("Follower actor not found"))?;
 
-                // Remove the follow relationship
+                // Remove the follow relationship, or the request if it was never approved
                 follow::unfollow_by_actor_ids(&mut tx, follower_actor.id, following_actor.id)
                     .await?;
+                follow_request::delete_follow_request_by_actor_ids(
+                    &mut tx,
+                    follower_actor.id,
+                    following_actor.id,
+                )
+                .await?;
                 tracing::info!(
                     "Removed follow relationship: {} -> {}",
                     follower_actor.iri,
@@ -1094,6 +1454,25 @@ impl ActivityHandler for Undo {
                     tracing::warn!("Failed to delete reaction with IRI: {}", react.id);
                 }
             }
+            UndoObject::Announce(announce) => {
+                tracing::info!("Undo type: Announce");
+                tracing::info!("Announce IRI: {}", announce.id);
+
+                // Only whoever sent the Announce can take it back
+                if announce.actor.inner() != self.actor.inner() {
+                    tracing::warn!(
+                        "Ignoring Undo of Announce {} sent by another actor",
+                        announce.id
+                    );
+                } else if delete_federated_post_by_announce_iri(&mut tx, announce.id.as_str())
+                    .await?
+                {
+                    tracing::info!("Removed post surfaced by Announce {}", announce.id);
+                    tx.commit().await?;
+                } else {
+                    tracing::info!("No surfaced post for Announce {}", announce.id);
+                }
+            }
         }
 
         tracing::info!("================================");
@@ -1155,6 +1534,12 @@ pub struct Note {
     tag: Vec<Tag>,
     #[serde(skip_serializing_if = "Option::is_none")]
     source: Option<serde_json::Value>,
+    /// Mastodon's extension for media that should be hidden until clicked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sensitive: Option<bool>,
+    /// Content warning shown in place of the content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
     #[serde(flatten)]
     extra: std::collections::HashMap<String, serde_json::Value>,
 }
@@ -1177,6 +1562,8 @@ pub struct NoteParams {
     pub updated: Option<String>,
     pub url: Url,
     pub attachment: Vec<Attachment>,
+    pub sensitive: bool,
+    pub summary: Option<String>,
 }
 
 impl Note {
@@ -1196,12 +1583,27 @@ impl Note {
             reply_target: None,
             tag: Vec::new(),
             source: None,
+            sensitive: params.sensitive.then_some(true),
+            summary: params.summary,
             extra: std::collections::HashMap::new(),
         }
     }
+
+    /// Whether the origin marked the note sensitive
+    fn is_sensitive(&self) -> bool {
+        self.sensitive.unwrap_or(false)
+    }
+
+    /// Whether the note is addressed to the public rather than only followers or mentions
+    fn is_public(&self) -> bool {
+        self.to
+            .iter()
+            .chain(self.cc.iter())
+            .any(|audience| audience == "https://www.w3.org/ns/activitystreams#Public")
+    }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Create {
     actor: ObjectId<Actor>,
@@ -1279,9 +1681,32 @@ impl ActivityHandler for Create {
         tracing::info!("reply_target: {:?}", self.object.reply_target);
         tracing::info!("================================");
 
+        // Guestbook entries carry a `Guestbook` tag whose href is the IRI of the
+        // local user whose guestbook they are written in
+        if let Some(guestbook_owner_iri) = self
+            .object
+            .tag
+            .iter()
+            .find(|tag| tag.r#type == "Guestbook")
+            .and_then(|tag| tag.href.as_ref())
+        {
+            return receive_guestbook_entry(&self, guestbook_owner_iri, data).await;
+        }
+
         let db = &data.app_data().db_pool;
         let mut tx = db.begin().await?;
 
+        // A top-level note addressed to one of our communities is a post submitted
+        // to that group; replies stay comments even when they copy the community
+        if self.object.in_reply_to.is_none() && self.object.reply_target.is_none() {
+            if let Some(community_actor) =
+                find_addressed_community_actor(&mut tx, &self, data).await?
+            {
+                tx.commit().await?;
+                return receive_community_note(&self, community_actor, data).await;
+            }
+        }
+
         // Check if this is a reply to a local post
         // Support both in_reply_to and reply_target (different ActivityPub implementations use different names)
         let reply_target_url = self
@@ -1322,45 +1747,10 @@ impl ActivityHandler for Create {
                             .and_then(|id_str| Uuid::parse_str(id_str).ok());
 
                         // Get the actor who sent this comment, fetching from remote if needed
-                        let actor = Actor::read_from_id(self.actor.inner().clone(), data).await?;
-
-                        let actor = if let Some(actor) = actor {
-                            actor
-                        } else {
-                            // Actor not found locally, fetch from remote and persist
-                            tracing::info!(
-                                "Actor not found locally, fetching from remote: {}",
-                                self.actor.inner()
-                            );
-
-                            match self.actor.dereference(data).await {
-                                Ok(remote_actor) => {
-                                    tracing::info!(
-                                        "Successfully fetched remote actor: {}",
-                                        self.actor.inner()
-                                    );
-
-                                    // Persist the remote actor
-                                    let persisted_actor =
-                                        Actor::create_or_update_actor(&mut tx, &remote_actor)
-                                            .await?;
-                                    tracing::info!(
-                                        "Persisted new actor: {} ({})",
-                                        persisted_actor.handle,
-                                        persisted_actor.iri
-                                    );
-                                    persisted_actor
-                                }
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "Failed to fetch remote actor {}: {:?}",
-                                        self.actor.inner(),
-                                        e
-                                    );
-                                    tx.rollback().await?;
-                                    return Ok(());
-                                }
-                            }
+                        let Some(actor) = find_or_fetch_actor(&mut tx, &self.actor, data).await?
+                        else {
+                            tx.rollback().await?;
+                            return Ok(());
                         };
 
                         // Create the comment from the ActivityPub note
@@ -1373,6 +1763,7 @@ impl ActivityHandler for Create {
                             markdown_content,
                             html_content,
                             self.object.id.to_string(),
+                            self.object.is_sensitive(),
                         )
                         .await;
 
@@ -1390,6 +1781,7 @@ impl ActivityHandler for Create {
                                 if let Some(post_author_id) = post_author_user_id {
                                     match create_notification(
                                         &mut tx,
+                                        &data.app_data().notification_events,
                                         CreateNotificationParams {
                                             recipient_id: post_author_id,
                                             actor_id: actor.id,
@@ -1420,7 +1812,7 @@ impl ActivityHandler for Create {
                                 if !notification_info.is_empty() {
                                     let push_service = data.push_service.clone();
                                     let db_pool = data.db_pool.clone();
-                                    tokio::spawn(async move {
+                                    let send_notifications = async move {
                                         for (notification_id, recipient_id) in notification_info {
                                             let mut tx = match db_pool.begin().await {
                                                 Ok(tx) => tx,
@@ -1456,7 +1848,8 @@ impl ActivityHandler for Create {
                                             }
                                             let _ = tx.commit().await;
                                         }
-                                    });
+                                    };
+                                    tokio::spawn(send_notifications.in_current_span());
                                 }
                             }
                             Err(e) => {
@@ -1478,46 +1871,310 @@ impl ActivityHandler for Create {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct Announce {
-    actor: ObjectId<Actor>,
-    object: Url,
-    r#type: AnnounceType,
-    id: Url,
-    to: Vec<String>,
-    cc: Vec<String>,
-    published: String,
-}
+/// Looks up the sender of an activity, fetching and persisting it if it isn't known yet.
+/// Returns `None` when the remote actor can't be fetched.
+async fn find_or_fetch_actor(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    actor_id: &ObjectId<Actor>,
+    data: &Data<AppState>,
+) -> Result<Option<Actor>, AppError> {
+    if let Some(actor) = Actor::read_from_id(actor_id.inner().clone(), data).await? {
+        return Ok(Some(actor));
+    }
 
-impl Announce {
-    pub fn new(
-        actor: ObjectId<Actor>,
-        object: Url,
-        id: Url,
-        to: Vec<String>,
-        cc: Vec<String>,
-        published: String,
-    ) -> Announce {
-        Announce {
-            actor,
-            object,
-            r#type: Default::default(),
-            id,
-            to,
-            cc,
-            published,
+    // Actor not found locally, fetch from remote and persist
+    tracing::info!(
+        "Actor not found locally, fetching from remote: {}",
+        actor_id.inner()
+    );
+
+    match dereference_actor(actor_id, data).await {
+        Ok(remote_actor) => {
+            tracing::info!("Successfully fetched remote actor: {}", actor_id.inner());
+
+            // Persist the remote actor
+            let persisted_actor = Actor::create_or_update_actor(tx, &remote_actor).await?;
+            tracing::info!(
+                "Persisted new actor: {} ({})",
+                persisted_actor.handle,
+                persisted_actor.iri
+            );
+            Ok(Some(persisted_actor))
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch remote actor {}: {:?}", actor_id.inner(), e);
+            Ok(None)
         }
     }
 }
 
-#[async_trait::async_trait]
-impl ActivityHandler for Announce {
-    type DataType = AppState;
-    type Error = AppError;
-
-    fn id(&self) -> &Url {
-        &self.id
+/// The local community actor a Create is addressed to, if any. Lemmy names the
+/// group in `audience`; Mastodon-style servers put it in `to` or `cc`.
+async fn find_addressed_community_actor(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    create: &Create,
+    data: &Data<AppState>,
+) -> Result<Option<Actor>, AppError> {
+    let audience = create
+        .object
+        .extra
+        .get("audience")
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+    let local_addresses = create
+        .to
+        .iter()
+        .chain(create.cc.iter())
+        .chain(create.object.to.iter())
+        .chain(create.object.cc.iter())
+        .chain(audience.iter())
+        .filter_map(|address| address.parse::<Url>().ok())
+        .filter(|address| address.domain() == Some(data.config.domain.as_str()));
+
+    for address in local_addresses {
+        if let Some(actor) = Actor::find_by_iri(tx, address.to_string()).await? {
+            if actor.community_id.is_some() {
+                return Ok(Some(actor));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Mirror a note submitted to a community's group inbox and announce it to the
+/// community's followers, the way Lemmy and Mastodon groups redistribute posts
+async fn receive_community_note(
+    create: &Create,
+    community_actor: Actor,
+    data: &Data<AppState>,
+) -> Result<(), AppError> {
+    let mut tx = data.db_pool.begin().await?;
+
+    let Some(community) = (match community_actor.community_id {
+        Some(community_id) => find_community_by_id(&mut tx, community_id).await?,
+        None => None,
+    }) else {
+        return Ok(());
+    };
+    // Private and unlisted communities don't take posts from outside the instance
+    if community.visibility != CommunityVisibility::Public {
+        tracing::info!(
+            "Community @{} is not public, ignoring note {}",
+            community.slug,
+            create.object.id
+        );
+        return Ok(());
+    }
+
+    let Some(author) = find_or_fetch_actor(&mut tx, &create.actor, data).await? else {
+        tx.rollback().await?;
+        return Ok(());
+    };
+    if !follow::is_following_by_actor_ids(&mut tx, author.id, community_actor.id).await? {
+        tracing::info!(
+            "{} does not follow community @{}, ignoring note {}",
+            author.iri,
+            community.slug,
+            create.object.id
+        );
+        return Ok(());
+    }
+
+    // Only accept the sender's own notes, hosted on the sender's server
+    let note = &create.object;
+    let attributed_to_sender = note
+        .attributed_to
+        .as_ref()
+        .is_none_or(|attributed_to| attributed_to.inner() == create.actor.inner());
+    if !attributed_to_sender || verify_domains_match(&note.id, create.actor.inner()).is_err() {
+        tracing::warn!(
+            "Note {} submitted to community @{} is not authored by sender {}",
+            note.id,
+            community.slug,
+            create.actor.inner()
+        );
+        return Ok(());
+    }
+    if !note.is_public() {
+        tracing::info!(
+            "Note {} submitted to community @{} is not public, ignoring",
+            note.id,
+            community.slug
+        );
+        return Ok(());
+    }
+
+    let announce_id = generate_object_id(&data.config.domain)?;
+    let mirrored = create_federated_post(
+        &mut tx,
+        federated_post_draft(note, &announce_id, community.id, author.id),
+    )
+    .await?;
+    if mirrored.is_none() {
+        tracing::debug!("Note {} was already mirrored", note.id);
+        return Ok(());
+    }
+    let followers = follow::find_followers_by_actor_id(&mut tx, community_actor.id).await?;
+
+    tx.commit().await?;
+
+    tracing::info!(
+        "Mirrored note {} from {} into community @{}",
+        note.id,
+        author.iri,
+        community.slug
+    );
+
+    let follower_inboxes: Vec<Url> = followers
+        .iter()
+        .filter_map(|follower| follower.inbox_url.parse().ok())
+        .collect();
+    if follower_inboxes.is_empty() {
+        return Ok(());
+    }
+
+    let announce = Announce::new(
+        ObjectId::<Actor>::parse(&community_actor.iri)?,
+        note.id.clone(),
+        announce_id,
+        vec!["https://www.w3.org/ns/activitystreams#Public".to_string()],
+        vec![format!("{}/followers", community_actor.iri)],
+        chrono::Utc::now().to_rfc3339(),
+    );
+    community_actor
+        .send(
+            announce,
+            follower_inboxes,
+            data.config.use_activitypub_queue(),
+            data,
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn receive_guestbook_entry(
+    create: &Create,
+    owner_iri: &Url,
+    data: &Data<AppState>,
+) -> Result<(), AppError> {
+    let mut tx = data.db_pool.begin().await?;
+
+    let Some(owner_id) = Actor::find_by_iri(&mut tx, owner_iri.to_string())
+        .await?
+        .and_then(|owner| owner.user_id)
+    else {
+        tracing::debug!("Guestbook owner {} is not a local user", owner_iri);
+        return Ok(());
+    };
+
+    let Some(author) = find_or_fetch_actor(&mut tx, &create.actor, data).await? else {
+        tx.rollback().await?;
+        return Ok(());
+    };
+    // Same rule as the web form: nobody writes in their own guestbook
+    if author.user_id == Some(owner_id) {
+        return Ok(());
+    }
+
+    let (content, content_html) = extract_note_content(&create.object);
+    let entry = match create_guestbook_entry(
+        &mut tx,
+        GuestbookEntryDraft {
+            author_actor_id: author.id,
+            recipient_id: owner_id,
+            content,
+            content_html,
+            iri: Some(create.object.id.to_string()),
+        },
+    )
+    .await
+    {
+        Ok(entry) => entry,
+        Err(e) => {
+            tracing::error!(
+                "Failed to create guestbook entry from ActivityPub note {}: {:?}",
+                create.object.id,
+                e
+            );
+            return Ok(());
+        }
+    };
+    tracing::info!(
+        "Created guestbook entry from ActivityPub for user {}",
+        owner_id
+    );
+
+    let mut notification_info: Vec<(Uuid, Uuid)> = Vec::new();
+    match create_notification(
+        &mut tx,
+        &data.notification_events,
+        CreateNotificationParams {
+            recipient_id: owner_id,
+            actor_id: author.id,
+            notification_type: NotificationType::GuestbookEntry,
+            post_id: None,
+            comment_id: None,
+            reaction_iri: None,
+            guestbook_entry_id: Some(entry.id),
+        },
+    )
+    .await
+    {
+        Ok(notification) => notification_info.push((notification.id, owner_id)),
+        Err(e) => tracing::warn!("Failed to create guestbook entry notification: {:?}", e),
+    }
+
+    tx.commit().await?;
+
+    spawn_push_notifications(data.app_data(), notification_info);
+
+    Ok(())
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Announce {
+    actor: ObjectId<Actor>,
+    object: Url,
+    r#type: AnnounceType,
+    id: Url,
+    #[serde(default, deserialize_with = "string_or_vec_deser")]
+    to: Vec<String>,
+    #[serde(default, deserialize_with = "string_or_vec_deser")]
+    cc: Vec<String>,
+    #[serde(default)]
+    published: String,
+}
+
+impl Announce {
+    pub fn new(
+        actor: ObjectId<Actor>,
+        object: Url,
+        id: Url,
+        to: Vec<String>,
+        cc: Vec<String>,
+        published: String,
+    ) -> Announce {
+        Announce {
+            actor,
+            object,
+            r#type: Default::default(),
+            id,
+            to,
+            cc,
+            published,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for Announce {
+    type DataType = AppState;
+    type Error = AppError;
+
+    fn id(&self) -> &Url {
+        &self.id
     }
 
     fn actor(&self) -> &Url {
@@ -1528,15 +2185,468 @@ impl ActivityHandler for Announce {
         Ok(())
     }
 
-    async fn receive(self, _data: &Data<Self::DataType>) -> Result<(), Self::Error> {
-        // Announce activities are typically sent outbound, not received
-        // If we wanted to handle incoming Announce activities (e.g., boosts from other servers),
-        // we would implement the logic here
+    async fn receive(self, data: &Data<Self::DataType>) -> Result<(), Self::Error> {
         tracing::info!("Received Announce activity: {:?}", self);
+
+        // Surfacing announced remote posts is opt-in
+        let community_slug = match &data.app_data().config.federated_inbox_community_slug {
+            Some(slug) => slug.clone(),
+            None => return Ok(()),
+        };
+
+        // Our own posts are announced by us, never surfaced back into a community
+        if self.object.domain() == Some(data.app_data().config.domain.as_str()) {
+            return Ok(());
+        }
+
+        let db = &data.app_data().db_pool;
+        let mut tx = db.begin().await?;
+
+        let community = match find_community_by_slug(&mut tx, community_slug.clone()).await? {
+            Some(community) => community,
+            None => {
+                tracing::warn!(
+                    "Federated inbox community @{} does not exist, ignoring Announce",
+                    community_slug
+                );
+                return Ok(());
+            }
+        };
+
+        // Only take boosts from actors someone here chose to follow, so an
+        // arbitrary server can't fill the community
+        let announcer = Actor::find_by_iri(&mut tx, self.actor.inner().to_string()).await?;
+        let community_actor = Actor::find_by_community_id(&mut tx, community.id).await?;
+        let followed = match (&announcer, &community_actor) {
+            (Some(announcer), Some(community_actor)) => {
+                follow::is_followed_locally(&mut tx, announcer.id, community_actor.id).await?
+            }
+            _ => false,
+        };
+        if !followed {
+            tracing::info!(
+                "{} is not followed locally, ignoring Announce of {}",
+                self.actor.inner(),
+                self.object
+            );
+            return Ok(());
+        }
+
+        let note = match fetch_remote_object::<Note>(&self.object, data).await {
+            Ok(response) => response.object,
+            Err(e) => {
+                tracing::warn!("Failed to fetch announced object {}: {:?}", self.object, e);
+                return Ok(());
+            }
+        };
+
+        // Only public notes may be surfaced; followers-only or direct notes stay private
+        if !note.is_public() {
+            tracing::info!("Announced object {} is not public, skipping", self.object);
+            return Ok(());
+        }
+
+        let author_id = match &note.attributed_to {
+            Some(author_id) => author_id.clone(),
+            None => {
+                tracing::info!("Announced object {} has no author, skipping", self.object);
+                return Ok(());
+            }
+        };
+        // The note has to live on its author's server, or anyone could put
+        // words in a remote user's mouth
+        if verify_domains_match(&note.id, author_id.inner()).is_err() {
+            tracing::warn!(
+                "Announced object {} is not hosted by its author {}, skipping",
+                note.id,
+                author_id.inner()
+            );
+            return Ok(());
+        }
+        let author = dereference_actor(&author_id, data).await?;
+        let author = Actor::create_or_update_actor(&mut tx, &author).await?;
+
+        create_federated_post(
+            &mut tx,
+            federated_post_draft(&note, &self.id, community.id, author.id),
+        )
+        .await?;
+
+        tx.commit().await?;
+
         Ok(())
     }
 }
 
+/// A report about objects, sent to and received from the moderators of other
+/// instances. Mastodon lists the reported account followed by the reported posts.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Flag {
+    actor: ObjectId<Actor>,
+    #[serde(deserialize_with = "string_or_vec_deser")]
+    object: Vec<String>,
+    r#type: FlagType,
+    id: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+}
+
+impl Flag {
+    pub fn new(actor: ObjectId<Actor>, object: Vec<String>, id: Url, content: String) -> Flag {
+        Flag {
+            actor,
+            object,
+            r#type: Default::default(),
+            id,
+            content: (!content.is_empty()).then_some(content),
+        }
+    }
+}
+
+/// Longest report comment kept from a received Flag, in characters
+const MAX_FLAG_COMMENT_LENGTH: usize = 1000;
+
+/// The objects of a received Flag that live on this instance, normalized by
+/// parsing so that only well-formed IRIs are stored
+fn local_flag_objects(objects: &[String], domain: &str) -> Vec<String> {
+    let mut iris: Vec<String> = objects
+        .iter()
+        .filter_map(|iri| Url::parse(iri).ok())
+        .filter(|url| matches!(url.scheme(), "http" | "https") && url.domain() == Some(domain))
+        .map(|url| url.to_string())
+        .collect();
+    iris.dedup();
+    iris
+}
+
+/// The comment of a received Flag, cut to MAX_FLAG_COMMENT_LENGTH characters
+fn flag_comment(content: Option<&str>) -> String {
+    content
+        .unwrap_or_default()
+        .chars()
+        .take(MAX_FLAG_COMMENT_LENGTH)
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for Flag {
+    type DataType = AppState;
+    type Error = AppError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(&self.id, self.actor.inner())?;
+        Ok(())
+    }
+
+    async fn receive(self, data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        tracing::info!("Received Flag activity: {:?}", self);
+
+        // Only reports about our own content are ours to review
+        let local_iris = local_flag_objects(&self.object, data.domain());
+        if local_iris.is_empty() {
+            tracing::info!("Flag {} reports no local objects, ignoring", self.id);
+            sentry::capture_message(
+                &format!(
+                    "Ignored Flag {} from {} with no local objects",
+                    self.id,
+                    self.actor.inner()
+                ),
+                sentry::Level::Info,
+            );
+            return Ok(());
+        }
+
+        let mut tx = data.app_data().db_pool.begin().await?;
+        let Some(reporter) = find_or_fetch_actor(&mut tx, &self.actor, data).await? else {
+            tracing::warn!("Could not resolve the sender of Flag {}", self.id);
+            sentry::capture_message(
+                &format!(
+                    "Ignored Flag {} from unresolvable actor {}",
+                    self.id,
+                    self.actor.inner()
+                ),
+                sentry::Level::Info,
+            );
+            return Ok(());
+        };
+        let report = create_object_report(
+            &mut tx,
+            Some(self.id.as_str()),
+            reporter.id,
+            &local_iris,
+            &flag_comment(self.content.as_deref()),
+        )
+        .await?;
+        tx.commit().await?;
+
+        // Redelivered Flags were already logged when first received
+        if let Some(report) = report {
+            sentry::capture_message(
+                &format!(
+                    "Received Flag {} from {} about {}, filed as report {}",
+                    self.id,
+                    reporter.handle,
+                    local_iris.join(", "),
+                    report.id
+                ),
+                sentry::Level::Info,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The author of a remote note, provided the note is public. Followers-only
+/// and direct notes must never be boosted.
+pub async fn find_public_note_author(
+    object: &Url,
+    data: &Data<AppState>,
+) -> Result<Option<Actor>, AppError> {
+    let note = match fetch_remote_object::<Note>(object, data).await {
+        Ok(response) => response.object,
+        Err(e) => {
+            tracing::warn!("Failed to fetch note {}: {:?}", object, e);
+            return Ok(None);
+        }
+    };
+    if !note.is_public() {
+        return Ok(None);
+    }
+
+    match &note.attributed_to {
+        Some(author_id) => Ok(Some(dereference_actor(author_id, data).await?)),
+        None => Ok(None),
+    }
+}
+
+/// Describe a remote note for storage as a federated post in a community
+fn federated_post_draft(
+    note: &Note,
+    announce_iri: &Url,
+    community_id: Uuid,
+    actor_id: Uuid,
+) -> FederatedPostDraft {
+    let (_, html_content) = extract_note_content(note);
+    let image = note
+        .attachment
+        .iter()
+        .find(|attachment| attachment.media_type.starts_with("image/"));
+    let is_sensitive = note.is_sensitive();
+    let published_at = note
+        .published
+        .as_deref()
+        .and_then(|published| chrono::DateTime::parse_from_rfc3339(published).ok())
+        .map(|published| published.with_timezone(&chrono::Utc));
+
+    FederatedPostDraft {
+        iri: note.id.to_string(),
+        announce_iri: announce_iri.to_string(),
+        community_id,
+        actor_id,
+        url: note.url.as_ref().map(|url| url.to_string()),
+        content_html: html_content.unwrap_or_default(),
+        image_url: image.map(|attachment| attachment.url.clone()),
+        image_width: image.and_then(|attachment| attachment.width),
+        image_height: image.and_then(|attachment| attachment.height),
+        is_sensitive,
+        published_at,
+    }
+}
+
+/// Result of re-fetching a stored federated post or comment from its origin,
+/// for moderators checking whether reported content is genuine
+#[derive(Serialize, Debug)]
+pub struct FederatedObjectVerification {
+    pub iri: String,
+    /// "post" or "comment"
+    pub kind: &'static str,
+    /// Whether the origin still serves the object, unknown when it couldn't be asked
+    pub exists: Option<bool>,
+    pub fetch_error: Option<String>,
+    /// Whether the remote content still matches what we stored
+    pub content_matches: Option<bool>,
+    pub image_matches: Option<bool>,
+    /// The actor we attributed the object to when it arrived
+    pub stored_actor_iri: String,
+    /// The actor the origin now attributes the object to
+    pub remote_actor_iri: Option<String>,
+    pub remote_actor_handle: Option<String>,
+    pub actor_matches: Option<bool>,
+    /// Whether the object is hosted on its author's domain; a mismatch means
+    /// some other server claims to speak for the author
+    pub same_origin: Option<bool>,
+    /// Key the author signs activities with, and whether it changed since we
+    /// last stored the actor
+    pub signing_key_id: Option<String>,
+    pub signing_key_changed: Option<bool>,
+}
+
+/// The copy of a federated object we stored, to compare with the origin
+pub struct StoredFederatedObject {
+    pub kind: &'static str,
+    pub iri: String,
+    pub content_html: String,
+    pub image_url: Option<String>,
+    pub actor: Actor,
+}
+
+/// What the origin answered when asked for a stored object again
+enum RemoteLookup {
+    Found(Box<Note>),
+    /// The origin says the object is gone, with a 404 or 410
+    Gone,
+    /// Anything else, which says nothing about whether the object still exists
+    Unavailable(String),
+}
+
+async fn lookup_remote_note(url: &Url, data: &Data<AppState>) -> RemoteLookup {
+    let error = match fetch_remote_object::<Note>(url, data).await {
+        Ok(response) => return RemoteLookup::Found(Box::new(response.object)),
+        Err(e) => e,
+    };
+    if let AppError::Anyhow(ref e) = error {
+        if let Some(FederationError::ObjectDeleted(_)) = e.downcast_ref::<FederationError>() {
+            return RemoteLookup::Gone;
+        }
+    }
+
+    // Only a 410 is reported as such by the fetch, so ask for the status of
+    // anything else before calling the object gone
+    let status = match reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+    {
+        Ok(client) => client
+            .get(url.as_str())
+            .header("Accept", FEDERATION_CONTENT_TYPE)
+            .send()
+            .await
+            .map(|response| response.status())
+            .ok(),
+        Err(_) => None,
+    };
+    match status {
+        Some(reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE) => RemoteLookup::Gone,
+        _ => RemoteLookup::Unavailable(error.to_string()),
+    }
+}
+
+/// Compare a re-fetched note with our copy and record who the origin says wrote it
+fn compare_remote_note(
+    stored: &StoredFederatedObject,
+    note: &Note,
+    verification: &mut FederatedObjectVerification,
+) {
+    let (_, html_content) = extract_note_content(note);
+    verification.content_matches =
+        Some(html_content.unwrap_or_default().trim() == stored.content_html.trim());
+    if stored.kind == "post" {
+        let remote_image_url = note
+            .attachment
+            .iter()
+            .find(|attachment| attachment.media_type.starts_with("image/"))
+            .map(|attachment| attachment.url.clone());
+        verification.image_matches = Some(remote_image_url == stored.image_url);
+    }
+
+    if let Some(author_id) = &note.attributed_to {
+        verification.remote_actor_iri = Some(author_id.inner().to_string());
+        verification.actor_matches = Some(author_id.inner().as_str() == stored.actor.iri);
+        verification.same_origin = Some(verify_domains_match(&note.id, author_id.inner()).is_ok());
+    }
+}
+
+/// Record the key the author publishes now, and whether it differs from the one we stored
+fn compare_remote_author(
+    stored: &StoredFederatedObject,
+    author: &Actor,
+    public_key: &PublicKey,
+    verification: &mut FederatedObjectVerification,
+) {
+    verification.remote_actor_handle = Some(author.handle.clone());
+    verification.signing_key_id = Some(public_key.id.clone());
+    if author.iri == stored.actor.iri {
+        verification.signing_key_changed =
+            Some(public_key.public_key_pem != stored.actor.public_key_pem);
+    }
+}
+
+/// Re-fetch a stored federated object and compare it with what we stored.
+/// The author is re-resolved as well, so a rotated key shows up here.
+pub async fn verify_federated_object(
+    stored: StoredFederatedObject,
+    data: &Data<AppState>,
+) -> Result<FederatedObjectVerification, AppError> {
+    let mut verification = FederatedObjectVerification {
+        iri: stored.iri.clone(),
+        kind: stored.kind,
+        exists: None,
+        fetch_error: None,
+        content_matches: None,
+        image_matches: None,
+        stored_actor_iri: stored.actor.iri.clone(),
+        remote_actor_iri: None,
+        remote_actor_handle: None,
+        actor_matches: None,
+        same_origin: None,
+        signing_key_id: None,
+        signing_key_changed: None,
+    };
+
+    let object_url: Url = stored
+        .iri
+        .parse()
+        .map_err(|e| AppError::InvalidFormData(format!("Invalid IRI: {}", e)))?;
+    let note = match lookup_remote_note(&object_url, data).await {
+        RemoteLookup::Found(note) => note,
+        RemoteLookup::Gone => {
+            verification.exists = Some(false);
+            return Ok(verification);
+        }
+        RemoteLookup::Unavailable(error) => {
+            verification.fetch_error = Some(error);
+            return Ok(verification);
+        }
+    };
+    verification.exists = Some(true);
+    compare_remote_note(&stored, &note, &mut verification);
+
+    let Some(author_id) = &note.attributed_to else {
+        return Ok(verification);
+    };
+    let author = match fetch_remote_object::<ActorObject>(author_id.inner(), data).await {
+        Ok(response) => response.object,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to resolve author {} of {}: {:?}",
+                author_id,
+                stored.iri,
+                e
+            );
+            return Ok(verification);
+        }
+    };
+    Actor::verify(&author, author_id.inner(), data).await?;
+    let public_key = match &author {
+        ActorObject::Person(person) => person.public_key.clone(),
+        ActorObject::Group(group) => group.public_key.clone(),
+    };
+    let author = Actor::from_json(author, data).await?;
+    compare_remote_author(&stored, &author, &public_key, &mut verification);
+
+    Ok(verification)
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Update {
@@ -1621,6 +2731,151 @@ impl ActivityHandler for Update {
     }
 }
 
+/// Sent when a remote account migrates to another one. `object` is the old
+/// account and `target` the new one.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Move {
+    actor: ObjectId<Actor>,
+    object: ObjectId<Actor>,
+    target: ObjectId<Actor>,
+    #[serde(rename = "type")]
+    r#type: MoveType,
+    id: Url,
+}
+
+impl Move {
+    /// Logs why a move was refused and turns the reason into the error returned
+    fn reject(&self, reason: &str) -> AppError {
+        tracing::warn!(
+            "Rejected Move {} from {} to {}: {}",
+            self.id,
+            self.object,
+            self.target,
+            reason
+        );
+        anyhow::anyhow!("Rejected Move: {}", reason).into()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for Move {
+    type DataType = AppState;
+    type Error = AppError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        // Only an account can move itself
+        if self.actor.inner() != self.object.inner() {
+            return Err(self.reject("actor does not match object"));
+        }
+        if self.target.inner() == self.object.inner() {
+            return Err(self.reject("target is the moving account itself"));
+        }
+        // Local accounts can't claim remote ones, so a move here can't be verified
+        if self.target.inner().domain() == Some(data.domain()) {
+            return Err(self.reject("target is a local account"));
+        }
+
+        // The new account has to claim the old one, or anyone could steal followers
+        let target = fetch_remote_object::<ActorObject>(self.target.inner(), data)
+            .await
+            .map_err(|e| self.reject(&format!("could not fetch target: {}", e)))?
+            .object;
+        let person = match &target {
+            ActorObject::Person(person) => person,
+            ActorObject::Group(_) => return Err(self.reject("target is not a person")),
+        };
+        if person.id.inner() != self.target.inner() {
+            return Err(self.reject("fetched target has a different id"));
+        }
+        if !person
+            .also_known_as
+            .iter()
+            .any(|iri| iri == self.object.inner().as_str())
+        {
+            return Err(self.reject("target does not list the old account in alsoKnownAs"));
+        }
+        // Following a chain of moves could loop back to where it started
+        if let Some(moved_to) = &person.moved_to {
+            return Err(self.reject(&format!("target has itself moved to {}", moved_to)));
+        }
+
+        Ok(())
+    }
+
+    async fn receive(self, data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        tracing::info!("Received Move activity: {} -> {}", self.object, self.target);
+
+        let db = &data.app_data().db_pool;
+        let mut tx = db.begin().await?;
+
+        let Some(old_actor) = Actor::find_by_iri(&mut tx, self.object.to_string()).await? else {
+            tracing::info!("Moved actor {} is not known here, ignoring", self.object);
+            return Ok(());
+        };
+        let followed_user_ids =
+            follow::find_followed_user_ids_by_actor_id(&mut tx, old_actor.id).await?;
+
+        let new_actor = match Actor::find_by_iri(&mut tx, self.target.to_string()).await? {
+            // Both accounts are known here, so hand the follows over to the new one
+            Some(new_actor) => {
+                follow::move_follows_to_actor(&mut tx, old_actor.id, new_actor.id).await?;
+                new_actor
+            }
+            // Otherwise the old record becomes the new account, follows and all
+            None => {
+                let fetched = self.target.dereference(data).await?;
+                Actor::update_moved_actor(&mut tx, old_actor.id, &fetched).await?
+            }
+        };
+        tracing::info!(
+            "Accepted Move {}: actor {} ({}) is now {} ({}), {} local follows carried over",
+            self.id,
+            old_actor.handle,
+            self.object,
+            new_actor.handle,
+            new_actor.iri,
+            followed_user_ids.len()
+        );
+
+        let mut notification_info: Vec<(Uuid, Uuid)> = Vec::new();
+        for user_id in followed_user_ids {
+            match create_notification(
+                &mut tx,
+                &data.notification_events,
+                CreateNotificationParams {
+                    recipient_id: user_id,
+                    actor_id: new_actor.id,
+                    notification_type: NotificationType::FollowerMoved,
+                    post_id: None,
+                    comment_id: None,
+                    reaction_iri: None,
+                    guestbook_entry_id: None,
+                },
+            )
+            .await
+            {
+                Ok(notification) => notification_info.push((notification.id, user_id)),
+                Err(e) => tracing::warn!("Failed to create follower moved notification: {:?}", e),
+            }
+        }
+
+        tx.commit().await?;
+
+        spawn_push_notifications(data.app_data(), notification_info);
+
+        Ok(())
+    }
+}
+
 pub fn generate_object_id(domain: &str) -> Result<Url, AppError> {
     Ok(Url::parse(&format!(
         "https://{}/objects/{}",
@@ -1629,31 +2884,148 @@ pub fn generate_object_id(domain: &str) -> Result<Url, AppError> {
     ))?)
 }
 
+/// Federation config for work done outside the federation middleware. Fetches of
+/// remote objects are signed as the instance actor unless signed fetch is disabled.
+pub async fn federation_config(
+    state: &AppState,
+) -> Result<activitypub_federation::config::FederationConfig<AppState>, AppError> {
+    Ok(federation_config_builder(state).await?.build().await?)
+}
+
+async fn federation_config_builder(
+    state: &AppState,
+) -> Result<activitypub_federation::config::FederationConfigBuilder<AppState>, AppError> {
+    let mut builder = activitypub_federation::config::FederationConfig::builder();
+    builder.domain(&state.config.domain).app_data(state.clone());
+    if state.config.signed_fetch_enabled {
+        let mut tx = state.db_pool.begin().await?;
+        let instance_actor = find_or_create_instance_actor(&mut tx, &state.config).await?;
+        tx.commit().await?;
+        builder.signed_fetch_actor(&instance_actor);
+    }
+    Ok(builder)
+}
+
+/// Whether a signed fetch that failed should be tried again without a signature
+fn should_retry_unsigned(data: &Data<AppState>) -> bool {
+    let config = &data.app_data().config;
+    config.signed_fetch_enabled && config.signed_fetch_fallback
+}
+
+async fn unsigned_federation_data(state: &AppState) -> Result<Data<AppState>, AppError> {
+    let federation_config = activitypub_federation::config::FederationConfig::builder()
+        .domain(&state.config.domain)
+        .app_data(state.clone())
+        .build()
+        .await?;
+    Ok(federation_config.to_request_data())
+}
+
+/// Fetch a remote object over HTTP. Some servers reject signed requests, so a
+/// failed signed fetch is retried unsigned when the fallback is enabled.
+pub async fn fetch_remote_object<Kind: DeserializeOwned>(
+    url: &Url,
+    data: &Data<AppState>,
+) -> Result<FetchObjectResponse<Kind>, AppError> {
+    match fetch_object_http::<AppState, Kind>(url, data).await {
+        Ok(response) => Ok(response),
+        Err(e) if should_retry_unsigned(data) => {
+            tracing::info!("Signed fetch of {} failed, retrying unsigned: {:?}", url, e);
+            let unsigned_data = unsigned_federation_data(data.app_data()).await?;
+            Ok(fetch_object_http::<AppState, Kind>(url, &unsigned_data).await?)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Dereference a remote actor with the same unsigned fallback as [`fetch_remote_object`]
+pub async fn dereference_actor(
+    actor_id: &ObjectId<Actor>,
+    data: &Data<AppState>,
+) -> Result<Actor, AppError> {
+    match actor_id.dereference(data).await {
+        Err(e) if should_retry_unsigned(data) => {
+            tracing::info!(
+                "Signed fetch of actor {} failed, retrying unsigned: {:?}",
+                actor_id.inner(),
+                e
+            );
+            let unsigned_data = unsigned_federation_data(data.app_data()).await?;
+            actor_id.dereference(&unsigned_data).await
+        }
+        result => result,
+    }
+}
+
+/// Content warning for the note of a sensitive post: the configured default,
+/// or the post's title when none is set
+fn note_content_warning(is_sensitive: bool, title: &str, default: &str) -> Option<String> {
+    if !is_sensitive {
+        return None;
+    }
+    [default.trim(), title.trim()]
+        .into_iter()
+        .find(|warning| !warning.is_empty())
+        .map(str::to_string)
+}
+
 pub async fn create_note_from_post(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     post_id: Uuid,
     author_actor: &Actor,
     domain: &str,
     r2_public_endpoint_url: &str,
+    content_warning: &str,
 ) -> Result<Note, AppError> {
     // Get post details
     let post = find_post_by_id(tx, post_id).await?;
     let post = post.ok_or_else(|| anyhow::anyhow!("Post not found"))?;
 
-    // Get title and content
-    let title = post.get("title").and_then(|t| t.as_ref()).map_or("", |v| v);
-    let content = post
-        .get("content")
-        .and_then(|c| c.as_ref())
-        .map_or("", |v| v);
+    let attachments = post_attachments(tx, &post, r2_public_endpoint_url).await;
+    note_from_post(
+        post_id,
+        &post,
+        attachments,
+        author_actor,
+        domain,
+        content_warning,
+        None,
+    )
+}
 
-    // Format content with title if available and process as markdown
-    let formatted_content = if title.is_empty() {
-        process_markdown_content(content)
-    } else {
-        let combined_content = format!("{}\n\n{}", title, content);
-        process_markdown_content(&combined_content)
-    };
+pub async fn create_updated_note_from_post(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post_id: Uuid,
+    author_actor: &Actor,
+    domain: &str,
+    r2_public_endpoint_url: &str,
+    content_warning: &str,
+) -> Result<Note, AppError> {
+    // Get post details
+    let post = find_post_by_id(tx, post_id).await?;
+    let post = post.ok_or_else(|| anyhow::anyhow!("Post not found"))?;
+
+    let attachments = post_attachments(tx, &post, r2_public_endpoint_url).await;
+    // Use current time for ActivityPub update timestamp
+    let updated = chrono::Utc::now().to_rfc3339();
+    note_from_post(
+        post_id,
+        &post,
+        attachments,
+        author_actor,
+        domain,
+        content_warning,
+        Some(updated),
+    )
+}
+
+/// The post's drawing as a Note attachment, if it has one
+async fn post_attachments(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post: &std::collections::HashMap<String, Option<String>>,
+    r2_public_endpoint_url: &str,
+) -> Vec<Attachment> {
+    let title = post.get("title").and_then(|t| t.as_ref()).map_or("", |v| v);
 
     // Get attachments if image exists
     let mut attachments = Vec::new();
@@ -1680,48 +3052,19 @@ pub async fn create_note_from_post(
             }
         }
     }
-
-    // Create URLs and IDs
-    let post_url: Url =
-        format!("https://{}/@{}/{}", domain, author_actor.username, post_id).parse()?;
-
-    let note_id: Url = format!("https://{}/ap/posts/{}", domain, post_id).parse()?;
-
-    // Set up audience - public post
-    let to = vec!["https://www.w3.org/ns/activitystreams#Public".to_string()];
-    let cc = vec![format!("{}/followers", author_actor.iri)];
-
-    // Get published date
-    let published = post
-        .get("published_at_utc")
-        .ok_or_else(|| anyhow::anyhow!("Missing published_at_utc"))?;
-
-    let note = Note::from_params(NoteParams {
-        id: note_id,
-        attributed_to: ObjectId::<Actor>::parse(&author_actor.iri)?,
-        content: formatted_content,
-        to,
-        cc,
-        published: published.clone().unwrap_or_default(),
-        updated: None,
-        url: post_url,
-        attachment: attachments,
-    });
-
-    Ok(note)
+    attachments
 }
 
-pub async fn create_updated_note_from_post(
-    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+/// Describe a post row as a Note. `updated` is set when federating an edit.
+fn note_from_post(
     post_id: Uuid,
+    post: &std::collections::HashMap<String, Option<String>>,
+    attachments: Vec<Attachment>,
     author_actor: &Actor,
     domain: &str,
-    r2_public_endpoint_url: &str,
+    content_warning: &str,
+    updated: Option<String>,
 ) -> Result<Note, AppError> {
-    // Get post details
-    let post = find_post_by_id(tx, post_id).await?;
-    let post = post.ok_or_else(|| anyhow::anyhow!("Post not found"))?;
-
     // Get title and content
     let title = post.get("title").and_then(|t| t.as_ref()).map_or("", |v| v);
     let content = post
@@ -1737,31 +3080,7 @@ pub async fn create_updated_note_from_post(
         process_markdown_content(&combined_content)
     };
 
-    // Get attachments if image exists
-    let mut attachments = Vec::new();
-    if let Some(Some(image_id_str)) = post.get("image_id") {
-        if let Ok(image_id) = Uuid::parse_str(image_id_str) {
-            if let Ok(image) = find_image_by_id(tx, image_id).await {
-                let image_url = format!(
-                    "{}/image/{}{}/{}",
-                    r2_public_endpoint_url,
-                    image.image_filename.chars().next().unwrap_or('0'),
-                    image.image_filename.chars().nth(1).unwrap_or('0'),
-                    image.image_filename
-                );
-
-                let attachment = Attachment {
-                    r#type: "Image".to_string(),
-                    url: image_url,
-                    media_type: "image/png".to_string(),
-                    name: Some(title.to_string()),
-                    width: Some(image.width),
-                    height: Some(image.height),
-                };
-                attachments.push(attachment);
-            }
-        }
-    }
+    let is_sensitive = post.get("is_sensitive").and_then(|value| value.as_deref()) == Some("true");
 
     // Create URLs and IDs
     let post_url: Url =
@@ -1778,23 +3097,19 @@ pub async fn create_updated_note_from_post(
         .get("published_at_utc")
         .ok_or_else(|| anyhow::anyhow!("Missing published_at_utc"))?;
 
-    // Use current time for ActivityPub update timestamp
-    let updated = chrono::Utc::now().to_rfc3339();
-
-    // Create the Note object with updated timestamp
-    let note = Note::from_params(NoteParams {
+    Ok(Note::from_params(NoteParams {
         id: note_id,
         attributed_to: ObjectId::<Actor>::parse(&author_actor.iri)?,
         content: formatted_content,
         to,
         cc,
         published: published.clone().unwrap_or_default(),
-        updated: Some(updated),
+        updated,
         url: post_url,
         attachment: attachments,
-    });
-
-    Ok(note)
+        sensitive: is_sensitive,
+        summary: note_content_warning(is_sensitive, title, content_warning),
+    }))
 }
 
 pub async fn send_update_activity(
@@ -1869,7 +3184,6 @@ pub async fn send_delete_activity(
     app_state: &crate::web::state::AppState,
 ) -> Result<(), AppError> {
     use crate::models::follow::get_follower_shared_inboxes_for_actor;
-    use activitypub_federation::config::FederationConfig;
 
     let db = &app_state.db_pool;
     let mut tx = db.begin().await?;
@@ -1878,12 +3192,164 @@ pub async fn send_delete_activity(
     let follower_inboxes = get_follower_shared_inboxes_for_actor(&mut tx, actor.id).await?;
     tx.commit().await?;
 
-    if follower_inboxes.is_empty() {
+    send_delete_activity_to_inboxes(actor, object_url, follower_inboxes, app_state).await
+}
+
+/// Sends a Delete for a comment to the instances that took part in its
+/// post's thread, i.e. remote actors who commented on or reacted to the post.
+pub async fn send_delete_activity_for_comment(
+    actor: &Actor,
+    comment: &Comment,
+    app_state: &crate::web::state::AppState,
+) -> Result<(), AppError> {
+    let db = &app_state.db_pool;
+    let mut tx = db.begin().await?;
+
+    // Posts are always authored locally, so the audience is only the thread
+    let inboxes = get_remote_inboxes_for_post_audience(&mut tx, comment.post_id).await?;
+    tx.commit().await?;
+
+    let object_url = match &comment.iri {
+        Some(iri) => iri.parse::<Url>()?,
+        None => format!(
+            "https://{}/comments/{}",
+            app_state.config.domain, comment.id
+        )
+        .parse::<Url>()?,
+    };
+
+    send_delete_activity_to_inboxes(actor, object_url, inboxes, app_state).await
+}
+
+/// Sends an Update with the edited content of a comment to the instances
+/// taking part in its post's thread. Only comments federated as objects, i.e.
+/// those with an IRI, have anything to update.
+pub async fn send_update_activity_for_comment(
+    actor: &Actor,
+    comment: &Comment,
+    app_state: &crate::web::state::AppState,
+) -> Result<(), AppError> {
+    use activitypub_federation::config::FederationConfig;
+
+    let Some(iri) = &comment.iri else {
+        return Ok(());
+    };
+
+    let db = &app_state.db_pool;
+    let mut tx = db.begin().await?;
+    let inboxes = get_remote_inboxes_for_post_audience(&mut tx, comment.post_id).await?;
+    tx.commit().await?;
+
+    if inboxes.is_empty() {
+        return Ok(());
+    }
+    let inbox_urls = inboxes
+        .into_iter()
+        .map(|inbox| inbox.parse::<Url>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let to = vec!["https://www.w3.org/ns/activitystreams#Public".to_string()];
+    let cc = vec![format!("{}/followers", actor.iri)];
+    let mut note = Note::from_params(NoteParams {
+        id: iri.parse()?,
+        attributed_to: ObjectId::parse(&actor.iri)?,
+        content: process_markdown_content(comment.content.as_deref().unwrap_or_default()),
+        to: to.clone(),
+        cc: cc.clone(),
+        published: comment.created_at.to_rfc3339(),
+        updated: comment.edited_at.map(|edited_at| edited_at.to_rfc3339()),
+        url: iri.parse()?,
+        attachment: Vec::new(),
+        sensitive: comment.is_sensitive,
+        summary: None,
+    });
+    note.in_reply_to = Some(
+        format!(
+            "https://{}/ap/posts/{}",
+            app_state.config.domain, comment.post_id
+        )
+        .parse()?,
+    );
+
+    let federation_config = FederationConfig::builder()
+        .domain(app_state.config.domain.clone())
+        .app_data(app_state.clone())
+        .build()
+        .await?;
+    let federation_data = federation_config.to_request_data();
+
+    let update = UpdateNote::new(
+        ObjectId::parse(&actor.iri)?,
+        note,
+        generate_object_id(&app_state.config.domain)?,
+        to,
+        cc,
+        chrono::Utc::now().to_rfc3339(),
+    );
+    actor
+        .send(
+            update,
+            inbox_urls,
+            app_state.config.use_activitypub_queue(),
+            &federation_data,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Sends a Flag about a remote object to the moderators of its author's
+/// instance. It comes from the instance actor so the reporter stays anonymous.
+pub async fn send_flag_activity(
+    flag_iri: Url,
+    author: &Actor,
+    object_iri: &str,
+    comment: &str,
+    app_state: &crate::web::state::AppState,
+) -> Result<(), AppError> {
+    let mut tx = app_state.db_pool.begin().await?;
+    let instance_actor = find_or_create_instance_actor(&mut tx, &app_state.config).await?;
+    tx.commit().await?;
+
+    let federation_data = federation_config(app_state).await?.to_request_data();
+    let flag = Flag::new(
+        ObjectId::parse(&instance_actor.iri)?,
+        vec![author.iri.clone(), object_iri.to_string()],
+        flag_iri,
+        comment.to_string(),
+    );
+
+    let inbox = author.shared_inbox_or_inbox();
+    sentry::capture_message(
+        &format!("Sending Flag {} about {} to {}", flag.id, object_iri, inbox),
+        sentry::Level::Info,
+    );
+    instance_actor
+        .send(
+            flag,
+            vec![inbox],
+            app_state.config.use_activitypub_queue(),
+            &federation_data,
+        )
+        .await
+}
+
+/// Sends a Delete of `object_url` to the given inboxes. Used when the follows
+/// are removed along with the object, so the inboxes have to be collected first.
+pub async fn send_delete_activity_to_inboxes(
+    actor: &Actor,
+    object_url: Url,
+    inboxes: Vec<String>,
+    app_state: &crate::web::state::AppState,
+) -> Result<(), AppError> {
+    use activitypub_federation::config::FederationConfig;
+
+    if inboxes.is_empty() {
         return Ok(());
     }
 
     // Convert inboxes to Urls
-    let inbox_urls: Result<Vec<Url>, _> = follower_inboxes
+    let inbox_urls: Result<Vec<Url>, _> = inboxes
         .into_iter()
         .map(|inbox| inbox.parse::<Url>())
         .collect();
@@ -2075,6 +3541,34 @@ impl ActivityHandler for Delete {
                 }
                 tx.commit().await?;
             }
+        } else if find_federated_post_by_iri(&mut tx, &object_url)
+            .await?
+            .is_some()
+        {
+            // A note mirrored into a community; only its author may take it down
+            let deleting_actor = match &actor_url {
+                Some(actor_url) => Actor::read_from_id(actor_url.clone(), data).await?,
+                None => None,
+            };
+            match deleting_actor {
+                Some(deleting_actor) => {
+                    if delete_federated_post_by_iri(&mut tx, &object_url, deleting_actor.id).await?
+                    {
+                        tracing::info!("Deleted federated post with IRI: {}", object_url);
+                        tx.commit().await?;
+                    } else {
+                        tracing::warn!(
+                            "Actor {} attempted to delete federated post {} it does not own",
+                            deleting_actor.id,
+                            object_url
+                        );
+                    }
+                }
+                None => tracing::warn!(
+                    "Could not verify the author of Delete activity for federated post {}",
+                    object_url
+                ),
+            }
         } else {
             // Check if this is a comment deletion by IRI
             // Try to find a comment with this IRI
@@ -2141,6 +3635,19 @@ pub struct Like {
     pub cc: Vec<String>,
 }
 
+impl Like {
+    pub fn new(actor: ObjectId<Actor>, object: Url, id: Url, to: Vec<String>) -> Like {
+        Like {
+            actor,
+            object,
+            r#type: "Like".to_string(),
+            id,
+            to,
+            cc: vec![],
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl ActivityHandler for Like {
     type DataType = AppState;
@@ -2202,13 +3709,15 @@ impl ActivityHandler for Like {
                         .and_then(|id_str| Uuid::parse_str(id_str).ok());
 
                     // Create reaction using Like's IRI (for idempotency)
-                    use crate::models::reaction::create_reaction_from_activitypub;
+                    use crate::models::reaction::{
+                        create_ap_like, create_reaction_from_activitypub, LIKE_EMOJI,
+                    };
                     match create_reaction_from_activitypub(
                         &mut tx,
                         self.id.to_string(),
                         post_id,
                         persisted_actor.id,
-                        "❤️".to_string(),
+                        LIKE_EMOJI.to_string(),
                     )
                     .await
                     {
@@ -2218,6 +3727,16 @@ impl ActivityHandler for Like {
                                 post_id
                             );
 
+                            // Record the Like so it can be told apart from EmojiReact
+                            create_ap_like(
+                                &mut tx,
+                                self.id.to_string(),
+                                reaction.iri.clone(),
+                                post_id,
+                                persisted_actor.id,
+                            )
+                            .await?;
+
                             // Collect notification info to send push after commit
                             let mut notification_info: Vec<(Uuid, Uuid)> = Vec::new();
 
@@ -2225,6 +3744,7 @@ impl ActivityHandler for Like {
                             if let Some(post_author_id) = post_author_user_id {
                                 match create_notification(
                                     &mut tx,
+                                    &data.app_data().notification_events,
                                     CreateNotificationParams {
                                         recipient_id: post_author_id,
                                         actor_id: persisted_actor.id,
@@ -2254,7 +3774,7 @@ impl ActivityHandler for Like {
                             if !notification_info.is_empty() {
                                 let push_service = data.push_service.clone();
                                 let db_pool = data.db_pool.clone();
-                                tokio::spawn(async move {
+                                let send_notifications = async move {
                                     for (notification_id, recipient_id) in notification_info {
                                         let mut tx = match db_pool.begin().await {
                                             Ok(tx) => tx,
@@ -2288,7 +3808,8 @@ impl ActivityHandler for Like {
                                         }
                                         let _ = tx.commit().await;
                                     }
-                                });
+                                };
+                                tokio::spawn(send_notifications.in_current_span());
                             }
                         }
                         Err(e) => {
@@ -2461,6 +3982,7 @@ impl ActivityHandler for EmojiReact {
                             if let Some(post_author_id) = post_author_user_id {
                                 match create_notification(
                                     &mut tx,
+                                    &data.app_data().notification_events,
                                     CreateNotificationParams {
                                         recipient_id: post_author_id,
                                         actor_id: persisted_actor.id,
@@ -2491,7 +4013,7 @@ impl ActivityHandler for EmojiReact {
                             if !notification_info.is_empty() {
                                 let push_service = data.push_service.clone();
                                 let db_pool = data.db_pool.clone();
-                                tokio::spawn(async move {
+                                let send_notifications = async move {
                                     for (notification_id, recipient_id) in notification_info {
                                         let mut tx = match db_pool.begin().await {
                                             Ok(tx) => tx,
@@ -2525,7 +4047,8 @@ impl ActivityHandler for EmojiReact {
                                         }
                                         let _ = tx.commit().await;
                                     }
-                                });
+                                };
+                                tokio::spawn(send_notifications.in_current_span());
                             }
                         }
                         Err(e) => {
@@ -2610,3 +4133,408 @@ impl ActivityHandler for UpdateNote {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTHOR_IRI: &str = "https://remote.example/users/alice";
+
+    fn actor(iri: &str, public_key_pem: &str) -> Actor {
+        let now = chrono::Utc::now();
+        Actor {
+            id: Uuid::new_v4(),
+            iri: iri.to_string(),
+            url: iri.to_string(),
+            r#type: crate::models::actor::ActorType::Person,
+            username: "alice".to_string(),
+            instance_host: "remote.example".to_string(),
+            handle_host: "remote.example".to_string(),
+            handle: "@alice@remote.example".to_string(),
+            user_id: None,
+            community_id: None,
+            name: "Alice".to_string(),
+            bio_html: String::new(),
+            automatically_approves_followers: true,
+            inbox_url: format!("{}/inbox", iri),
+            shared_inbox_url: "https://remote.example/inbox".to_string(),
+            followers_url: format!("{}/followers", iri),
+            sensitive: false,
+            public_key_pem: public_key_pem.to_string(),
+            private_key_pem: None,
+            created_at: now,
+            updated_at: now,
+            published_at: now,
+        }
+    }
+
+    fn stored_post() -> StoredFederatedObject {
+        StoredFederatedObject {
+            kind: "post",
+            iri: "https://remote.example/notes/1".to_string(),
+            content_html: "<p>hello</p>".to_string(),
+            image_url: Some("https://remote.example/media/1.png".to_string()),
+            actor: actor(AUTHOR_IRI, "stored key"),
+        }
+    }
+
+    fn verification(stored: &StoredFederatedObject) -> FederatedObjectVerification {
+        FederatedObjectVerification {
+            iri: stored.iri.clone(),
+            kind: stored.kind,
+            exists: Some(true),
+            fetch_error: None,
+            content_matches: None,
+            image_matches: None,
+            stored_actor_iri: stored.actor.iri.clone(),
+            remote_actor_iri: None,
+            remote_actor_handle: None,
+            actor_matches: None,
+            same_origin: None,
+            signing_key_id: None,
+            signing_key_changed: None,
+        }
+    }
+
+    /// A remote note as its origin would serve it
+    fn remote_note(id: &str, author: &str, content: &str, image_url: &str) -> Note {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": "Note",
+            "attributedTo": author,
+            "content": content,
+            "to": ["https://www.w3.org/ns/activitystreams#Public"],
+            "attachment": [{
+                "type": "Document",
+                "url": image_url,
+                "mediaType": "image/png",
+            }],
+        }))
+        .expect("remote note should parse")
+    }
+
+    fn public_key(id: &str, public_key_pem: &str) -> PublicKey {
+        PublicKey {
+            id: id.to_string(),
+            owner: AUTHOR_IRI.parse().unwrap(),
+            public_key_pem: public_key_pem.to_string(),
+        }
+    }
+
+    #[test]
+    fn matching_remote_object_is_reported_as_matching() {
+        let stored = stored_post();
+        let note = remote_note(
+            "https://remote.example/notes/1",
+            AUTHOR_IRI,
+            "<p>hello</p>",
+            "https://remote.example/media/1.png",
+        );
+        let mut result = verification(&stored);
+
+        compare_remote_note(&stored, &note, &mut result);
+        compare_remote_author(
+            &stored,
+            &actor(AUTHOR_IRI, "stored key"),
+            &public_key("https://remote.example/users/alice#key-2024", "stored key"),
+            &mut result,
+        );
+
+        assert_eq!(result.content_matches, Some(true));
+        assert_eq!(result.image_matches, Some(true));
+        assert_eq!(result.actor_matches, Some(true));
+        assert_eq!(result.same_origin, Some(true));
+        assert_eq!(result.signing_key_changed, Some(false));
+        // The key id the actor publishes, not one guessed from its IRI
+        assert_eq!(
+            result.signing_key_id.as_deref(),
+            Some("https://remote.example/users/alice#key-2024")
+        );
+    }
+
+    #[test]
+    fn diverging_remote_object_is_reported_as_diverging() {
+        let stored = stored_post();
+        let impostor = "https://other.example/users/alice";
+        let note = remote_note(
+            "https://remote.example/notes/1",
+            impostor,
+            "<p>edited</p>",
+            "https://remote.example/media/2.png",
+        );
+        let mut result = verification(&stored);
+
+        compare_remote_note(&stored, &note, &mut result);
+
+        assert_eq!(result.content_matches, Some(false));
+        assert_eq!(result.image_matches, Some(false));
+        assert_eq!(result.actor_matches, Some(false));
+        assert_eq!(result.same_origin, Some(false));
+        assert_eq!(result.remote_actor_iri.as_deref(), Some(impostor));
+    }
+
+    #[test]
+    fn rotated_key_is_reported_as_changed() {
+        let stored = stored_post();
+        let mut result = verification(&stored);
+
+        compare_remote_author(
+            &stored,
+            &actor(AUTHOR_IRI, "rotated key"),
+            &public_key("https://remote.example/users/alice#main-key", "rotated key"),
+            &mut result,
+        );
+
+        assert_eq!(result.signing_key_changed, Some(true));
+    }
+
+    fn post_row(
+        title: &str,
+        is_sensitive: bool,
+    ) -> std::collections::HashMap<String, Option<String>> {
+        [
+            ("title", Some(title.to_string())),
+            ("content", Some("drawing notes".to_string())),
+            ("is_sensitive", Some(is_sensitive.to_string())),
+            (
+                "published_at_utc",
+                Some("2026-10-01T12:00:00+00:00".to_string()),
+            ),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    }
+
+    /// Build the outgoing note for a post, deliver it wrapped in a Create,
+    /// and parse it back the way the inbox does
+    fn round_trip(
+        post: &std::collections::HashMap<String, Option<String>>,
+        content_warning: &str,
+    ) -> Note {
+        let author = actor(AUTHOR_IRI, "stored key");
+        let note = note_from_post(
+            Uuid::new_v4(),
+            post,
+            Vec::new(),
+            &author,
+            "oeee.example",
+            content_warning,
+            None,
+        )
+        .expect("note should build");
+        let delivered = serde_json::json!({
+            "id": "https://oeee.example/ap/activities/1",
+            "type": "Create",
+            "actor": AUTHOR_IRI,
+            "to": ["https://www.w3.org/ns/activitystreams#Public"],
+            "object": note,
+        });
+        match serde_json::from_value(delivered).expect("activity should parse") {
+            PersonAcceptedActivities::Create(create) => create.object,
+            other => panic!("expected a Create, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn sensitive_flag_survives_federation() {
+        let received = round_trip(&post_row("night sketch", true), "");
+
+        assert!(received.is_sensitive());
+        // Without a configured warning the title stands in for one
+        assert_eq!(received.summary.as_deref(), Some("night sketch"));
+        let draft = federated_post_draft(
+            &received,
+            &"https://remote.example/activities/1".parse().unwrap(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        assert!(draft.is_sensitive);
+
+        let received = round_trip(&post_row("night sketch", true), "gore");
+        assert_eq!(received.summary.as_deref(), Some("gore"));
+    }
+
+    #[test]
+    fn unflagged_post_federates_without_warning() {
+        let received = round_trip(&post_row("morning sketch", false), "gore");
+
+        assert!(!received.is_sensitive());
+        assert_eq!(received.summary, None);
+        let draft = federated_post_draft(
+            &received,
+            &"https://remote.example/activities/1".parse().unwrap(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        assert!(!draft.is_sensitive);
+    }
+
+    #[test]
+    fn flag_keeps_only_well_formed_local_objects() {
+        let objects = vec![
+            "https://oeee.example/ap/posts/1".to_string(),
+            "https://remote.example/users/alice".to_string(),
+            "https://oeee.example/ap/posts/\"><script>".to_string(),
+            "javascript://oeee.example/%0Aalert(1)".to_string(),
+            "not an iri".to_string(),
+        ];
+
+        assert_eq!(
+            local_flag_objects(&objects, "oeee.example"),
+            vec![
+                "https://oeee.example/ap/posts/1".to_string(),
+                "https://oeee.example/ap/posts/%22%3E%3Cscript%3E".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn flag_comment_is_capped() {
+        let long = "가".repeat(MAX_FLAG_COMMENT_LENGTH + 10);
+
+        assert_eq!(
+            flag_comment(Some(&long)).chars().count(),
+            MAX_FLAG_COMMENT_LENGTH
+        );
+        assert_eq!(flag_comment(Some("spam")), "spam");
+        assert_eq!(flag_comment(None), "");
+    }
+
+    #[sqlx::test]
+    async fn announced_post_lands_in_the_configured_community(pool: sqlx::PgPool) {
+        use crate::models::community::CommunityVisibility;
+        use crate::models::federated_post::find_federated_posts_by_community_id;
+        use crate::models::test_fixtures;
+
+        let mut tx = pool.begin().await.unwrap();
+        let owner = test_fixtures::user(&mut tx, "owner").await;
+        let inbox =
+            test_fixtures::community(&mut tx, owner.id, "inbox", CommunityVisibility::Public).await;
+        let other =
+            test_fixtures::community(&mut tx, owner.id, "other", CommunityVisibility::Public).await;
+        let author = Actor::find_by_user_id(&mut tx, owner.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let note = remote_note(
+            "https://remote.example/notes/1",
+            AUTHOR_IRI,
+            "<p>hello</p>",
+            "https://remote.example/media/1.png",
+        );
+        let announce_iri = "https://remote.example/activities/1".parse().unwrap();
+
+        create_federated_post(
+            &mut tx,
+            federated_post_draft(&note, &announce_iri, inbox.id, author.id),
+        )
+        .await
+        .unwrap();
+
+        let surfaced = find_federated_posts_by_community_id(&mut tx, inbox.id, 10, false)
+            .await
+            .unwrap();
+        assert_eq!(surfaced.len(), 1);
+        assert_eq!(surfaced[0].iri, "https://remote.example/notes/1");
+        assert_eq!(
+            surfaced[0].image_url.as_deref(),
+            Some("https://remote.example/media/1.png")
+        );
+        assert!(
+            find_federated_posts_by_community_id(&mut tx, other.id, 10, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    async fn state(pool: sqlx::PgPool, config: crate::AppConfig) -> AppState {
+        use crate::delivery_queue::{DeliveryQueue, DeliveryQueueConfig};
+        use crate::push::PushService;
+        use crate::web::handlers::collaborate::redis_state::RedisStateManager;
+
+        // Nothing here talks to Redis, so the pool never has to connect
+        let redis_manager =
+            bb8_redis::RedisConnectionManager::new(config.redis_url.clone()).unwrap();
+        let redis_pool = bb8_redis::bb8::Pool::builder().build_unchecked(redis_manager);
+        AppState {
+            env: minijinja::Environment::new(),
+            db_pool: pool.clone(),
+            redis_state: RedisStateManager::new(redis_pool.clone()),
+            redis_pool,
+            push_service: std::sync::Arc::new(PushService::new(&config, pool).await.unwrap()),
+            notification_events: tokio::sync::broadcast::channel(1).0,
+            delivery_queue: DeliveryQueue::new(DeliveryQueueConfig::from_app_config(&config)),
+            asset_version: String::new(),
+            config,
+        }
+    }
+
+    /// A server in secure mode: it only serves the note to fetches signed by our instance actor
+    async fn secure_mode_server(instance_actor_iri: String) -> Url {
+        let router = axum::Router::new().route(
+            "/notes/1",
+            axum::routing::get(move |headers: HeaderMap| async move {
+                let signed_by_instance_actor = headers
+                    .get("signature")
+                    .and_then(|signature| signature.to_str().ok())
+                    .is_some_and(|signature| {
+                        signature.contains(&format!("keyId=\"{}#main-key\"", instance_actor_iri))
+                    });
+                if !signed_by_instance_actor {
+                    return StatusCode::UNAUTHORIZED.into_response();
+                }
+                let host = headers["host"].to_str().unwrap();
+                (
+                    [("content-type", "application/activity+json")],
+                    serde_json::json!({
+                        "id": format!("http://{}/notes/1", host),
+                        "type": "Note",
+                        "content": "<p>members only</p>",
+                    })
+                    .to_string(),
+                )
+                    .into_response()
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://localhost:{}/notes/1", address.port())
+            .parse()
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn signed_fetch_reaches_a_secure_mode_server(pool: sqlx::PgPool) {
+        let mut config = crate::models::test_fixtures::config();
+        let instance_actor_iri = format!("https://{}/ap/actor", config.domain);
+        let note_url = secure_mode_server(instance_actor_iri).await;
+        // Debug mode lets the library fetch from the plain-HTTP stub on localhost
+        let data = |state: AppState| async move {
+            federation_config_builder(&state)
+                .await
+                .unwrap()
+                .debug(true)
+                .build()
+                .await
+                .unwrap()
+                .to_request_data()
+        };
+
+        let signed = data(state(pool.clone(), config.clone()).await).await;
+        let note = fetch_remote_object::<Note>(&note_url, &signed)
+            .await
+            .expect("signed fetch should be answered")
+            .object;
+        assert_eq!(note.content.as_deref(), Some("<p>members only</p>"));
+
+        config.signed_fetch_enabled = false;
+        config.signed_fetch_fallback = false;
+        let unsigned = data(state(pool, config).await).await;
+        assert!(fetch_remote_object::<Note>(&note_url, &unsigned)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::announcement_cache::AnnouncementCache;
+use crate::app_error::AppError;
+use crate::models::announcement::{
+    create_announcement, delete_announcement, dismiss_announcement, list_announcements,
+    set_announcement_active, AnnouncementDraft, AnnouncementLevel,
+};
+use crate::models::user::AuthSession;
+use crate::web::context::CommonContext;
+use crate::web::handlers::{render_403, ExtractFtlLang};
+use crate::web::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Redirect};
+use axum::Form;
+use chrono::{DateTime, Utc};
+use minijinja::context;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// datetime-local inputs carry no offset; times are entered in KST like everything we display
+fn parse_kst_datetime(value: &str, field: &str) -> Result<DateTime<Utc>, AppError> {
+    use chrono::TimeZone;
+    let naive = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+        .map_err(|e| AppError::InvalidFormData(format!("Invalid {}: {}", field, e)))?;
+    Ok(chrono_tz::Asia::Seoul
+        .from_local_datetime(&naive)
+        .single()
+        .ok_or_else(|| AppError::InvalidFormData(format!("Ambiguous {}", field)))?
+        .with_timezone(&Utc))
+}
+
+/// Admin page: GET /admin/announcements
+pub async fn admin_announcements(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let announcements = list_announcements(&mut tx).await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("admin_announcements.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        announcements,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct CreateAnnouncementForm {
+    pub message: String,
+    /// Per-language text; empty falls back to the message
+    #[serde(default)]
+    pub content_ko: String,
+    #[serde(default)]
+    pub content_en: String,
+    #[serde(default)]
+    pub content_ja: String,
+    #[serde(default)]
+    pub content_zh: String,
+    pub level: AnnouncementLevel,
+    /// Empty means right away
+    pub starts_at: String,
+    /// Empty means until deleted
+    pub ends_at: String,
+    pub dismissible: Option<String>,
+}
+
+/// Admin endpoint: POST /admin/announcements
+pub async fn do_create_announcement(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Form(form): Form<CreateAnnouncementForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let message = form.message.trim().to_string();
+    if message.is_empty() {
+        return Err(AppError::InvalidFormData(
+            "Announcement message is empty".to_string(),
+        ));
+    }
+    let starts_at = match form.starts_at.trim() {
+        "" => Utc::now(),
+        value => parse_kst_datetime(value, "starts_at")?,
+    };
+    let ends_at = match form.ends_at.trim() {
+        "" => None,
+        value => Some(parse_kst_datetime(value, "ends_at")?),
+    };
+    if ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+        return Err(AppError::InvalidFormData(
+            "ends_at must be after starts_at".to_string(),
+        ));
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    create_announcement(
+        &mut tx,
+        user.id,
+        AnnouncementDraft {
+            message,
+            content_ko: form.content_ko.trim().to_string(),
+            content_en: form.content_en.trim().to_string(),
+            content_ja: form.content_ja.trim().to_string(),
+            content_zh: form.content_zh.trim().to_string(),
+            level: form.level,
+            starts_at,
+            ends_at,
+            dismissible: form.dismissible.as_deref() == Some("on"),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    AnnouncementCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+
+    Ok(Redirect::to("/admin/announcements").into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ToggleAnnouncementForm {
+    pub is_active: bool,
+}
+
+/// Admin endpoint: POST /admin/announcements/:id/active
+pub async fn do_set_announcement_active(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(announcement_id): Path<Uuid>,
+    Form(form): Form<ToggleAnnouncementForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if !set_announcement_active(&mut tx, announcement_id, form.is_active).await? {
+        return Err(AppError::NotFound("Announcement".to_string()));
+    }
+
+    tx.commit().await?;
+    AnnouncementCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+
+    Ok(Redirect::to("/admin/announcements").into_response())
+}
+
+/// Admin endpoint: DELETE /admin/announcements/:id, or POST
+/// /admin/announcements/:id/delete from the admin page's forms
+pub async fn do_delete_announcement(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(announcement_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if !delete_announcement(&mut tx, announcement_id).await? {
+        return Err(AppError::NotFound("Announcement".to_string()));
+    }
+
+    tx.commit().await?;
+    AnnouncementCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+
+    Ok(Redirect::to("/admin/announcements").into_response())
+}
+
+/// HTMX: hide an announcement for the current user, removing the bar
+pub async fn do_dismiss_announcement(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(announcement_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    dismiss_announcement(&mut tx, announcement_id, user.id).await?;
+    tx.commit().await?;
+
+    Ok((StatusCode::OK, Html(String::new())).into_response())
+}
@@ -1,22 +1,30 @@
+pub mod totp;
+
 use crate::app_error::{error_codes, AppError};
 use crate::models::device::delete_device_by_token;
 use crate::models::user::{
     create_user, update_user_preferred_language, AuthSession, Credentials, Language, UserDraft,
 };
+use crate::rate_limit::{RateLimit, RateLimiter};
 use crate::web::handlers::{
-    detect_preferred_language, get_bundle, safe_format_message, safe_get_message, ExtractFtlLang,
+    detect_preferred_language, get_user_bundle, safe_format_message, safe_get_message,
+    ExtractClientIp, ExtractFtlLang,
 };
 use crate::web::responses::ErrorResponse;
 use crate::web::state::AppState;
 use axum::extract::Query;
+use axum::http::header::RETRY_AFTER;
 use axum::response::{IntoResponse, Redirect};
 use axum::{extract::State, http::StatusCode, response::Html, response::Json, Form};
 use axum_messages::Messages;
 use fluent::{FluentArgs, FluentValue};
 use minijinja::context;
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use tower_sessions::Session;
 
 use super::ExtractAcceptLanguage;
+use totp::{check_login_code, is_totp_required, start_pending_totp_login};
 
 // This allows us to extract the "next" field from the query string. We use this
 // to redirect after log in.
@@ -58,12 +66,7 @@ pub async fn do_signup(
     State(state): State<AppState>,
     Form(form): Form<CreateUserForm>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user_preferred_language = auth_session
-        .user
-        .clone()
-        .map(|u| u.preferred_language)
-        .unwrap_or_else(|| None);
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
 
     if form.password != form.password_confirm {
         messages.error(safe_get_message(
@@ -107,6 +110,82 @@ pub async fn do_signup(
     }
 }
 
+const LOGIN_RATE_LIMIT_IP_SCOPE: &str = "login:ip";
+const LOGIN_RATE_LIMIT_ACCOUNT_SCOPE: &str = "login:account";
+
+/// Counts a login attempt against both the client IP and the targeted account.
+/// Returns the window to wait out when either is over its limit. Throttling fails
+/// open, so logins keep working while Redis is unavailable.
+pub(crate) async fn check_login_rate_limit(
+    state: &AppState,
+    client_ip: Option<IpAddr>,
+    login_name: &str,
+) -> Option<u64> {
+    let config = &state.config;
+    let window_seconds = config.login_rate_limit_window_seconds;
+    let limiter = RateLimiter::new(state.redis_pool.clone());
+    let mut allowed = true;
+
+    if let Some(ip) = client_ip {
+        let limit = RateLimit::new(config.login_rate_limit_per_ip, window_seconds);
+        match limiter
+            .hit(LOGIN_RATE_LIMIT_IP_SCOPE, &ip.to_string(), limit)
+            .await
+        {
+            Ok(ip_allowed) => allowed &= ip_allowed,
+            Err(e) => tracing::warn!("Failed to check login rate limit: {:?}", e),
+        }
+    }
+
+    let limit = RateLimit::new(config.login_rate_limit_per_account, window_seconds);
+    match limiter
+        .hit(
+            LOGIN_RATE_LIMIT_ACCOUNT_SCOPE,
+            &login_name.to_lowercase(),
+            limit,
+        )
+        .await
+    {
+        Ok(account_allowed) => allowed &= account_allowed,
+        Err(e) => tracing::warn!("Failed to check login rate limit: {:?}", e),
+    }
+
+    (!allowed).then_some(window_seconds)
+}
+
+const TOTP_RATE_LIMIT_SCOPE: &str = "login:totp";
+/// Second-factor attempts allowed per account within the login window
+const MAX_TOTP_ATTEMPTS: u32 = 5;
+
+/// Counts a two-factor code attempt against the account, on the web challenge
+/// and the API alike. Six digits don't take long to guess, so this allows far
+/// fewer attempts than the password limit. Fails open like the login limit.
+pub(crate) async fn check_totp_rate_limit(state: &AppState, login_name: &str) -> Option<u64> {
+    let window_seconds = state.config.login_rate_limit_window_seconds;
+    let limiter = RateLimiter::new(state.redis_pool.clone());
+    let limit = RateLimit::new(MAX_TOTP_ATTEMPTS, window_seconds);
+    match limiter
+        .hit(TOTP_RATE_LIMIT_SCOPE, &login_name.to_lowercase(), limit)
+        .await
+    {
+        Ok(allowed) => (!allowed).then_some(window_seconds),
+        Err(e) => {
+            tracing::warn!("Failed to check TOTP rate limit: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Clears the per-account counters once the user has fully logged in.
+pub(crate) async fn reset_login_rate_limit(state: &AppState, login_name: &str) {
+    let limiter = RateLimiter::new(state.redis_pool.clone());
+    for scope in [LOGIN_RATE_LIMIT_ACCOUNT_SCOPE, TOTP_RATE_LIMIT_SCOPE] {
+        if let Err(e) = limiter.reset(scope, &login_name.to_lowercase()).await {
+            tracing::warn!("Failed to reset login rate limit: {:?}", e);
+        }
+    }
+}
+
 pub async fn login(
     messages: Messages,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
@@ -128,17 +207,23 @@ pub async fn login(
 
 pub async fn do_login(
     mut auth_session: AuthSession,
+    session: Session,
     ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractClientIp(client_ip): ExtractClientIp,
     messages: Messages,
     State(state): State<AppState>,
     Form(creds): Form<Credentials>,
 ) -> impl IntoResponse {
-    let user_preferred_language = auth_session
-        .user
-        .clone()
-        .map(|u| u.preferred_language)
-        .unwrap_or_else(|| None);
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
+
+    if let Some(retry_after) = check_login_rate_limit(&state, client_ip, &creds.login_name).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            safe_get_message(&bundle, "message-too-many-attempts"),
+        )
+            .into_response();
+    }
 
     let user = match auth_session.authenticate(creds.clone()).await {
         Ok(Some(user)) => user,
@@ -155,6 +240,21 @@ pub async fn do_login(
         Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     };
 
+    // Users with two-factor authentication finish logging in on the TOTP challenge page
+    match is_totp_required(&state.db_pool, user.id).await {
+        Ok(true) => {
+            if start_pending_totp_login(&session, user.id, creds.next)
+                .await
+                .is_err()
+            {
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+            return Redirect::to("/auth/totp").into_response();
+        }
+        Ok(false) => {}
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+
     // Auto-set language preference from browser if not already set
     if user.preferred_language.is_none() {
         if let Some(lang) = detect_preferred_language(&accept_language) {
@@ -173,6 +273,7 @@ pub async fn do_login(
     if auth_session.login(&user).await.is_err() {
         return StatusCode::INTERNAL_SERVER_ERROR.into_response();
     }
+    reset_login_rate_limit(&state, &user.login_name).await;
 
     let mut args = FluentArgs::new();
     args.set("name", FluentValue::from(user.display_name.clone()));
@@ -205,6 +306,8 @@ pub struct LoginRequest {
     pub login_name: String,
     pub password: String,
     pub preferred_language: Option<String>,
+    /// Authenticator or backup code, required when two-factor authentication is enabled
+    pub totp_code: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -252,9 +355,22 @@ pub struct SignupResponse {
 pub async fn api_login(
     mut auth_session: AuthSession,
     ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractClientIp(client_ip): ExtractClientIp,
     State(state): State<AppState>,
     Json(req): Json<LoginRequest>,
 ) -> impl IntoResponse {
+    if let Some(retry_after) = check_login_rate_limit(&state, client_ip, &req.login_name).await {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            Json(ErrorResponse::new(
+                error_codes::RATE_LIMITED,
+                "Too many login attempts",
+            )),
+        )
+            .into_response();
+    }
+
     // Create credentials from JSON request
     let creds = Credentials {
         login_name: req.login_name,
@@ -287,18 +403,75 @@ pub async fn api_login(
         }
     };
 
+    match is_totp_required(&state.db_pool, user.id).await {
+        Ok(true) => {
+            let Some(totp_code) = req.totp_code.as_deref() else {
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    Json(ErrorResponse::new(
+                        error_codes::TOTP_REQUIRED,
+                        "Two-factor authentication code required",
+                    )),
+                )
+                    .into_response();
+            };
+            if let Some(retry_after) = check_totp_rate_limit(&state, &user.login_name).await {
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(RETRY_AFTER, retry_after.to_string())],
+                    Json(ErrorResponse::new(
+                        error_codes::RATE_LIMITED,
+                        "Too many two-factor authentication attempts",
+                    )),
+                )
+                    .into_response();
+            }
+
+            match check_login_code(&state.db_pool, &state.config, &user, totp_code).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    return (
+                        StatusCode::UNAUTHORIZED,
+                        Json(ErrorResponse::new(
+                            error_codes::INVALID_TOTP_CODE,
+                            "Invalid two-factor authentication code",
+                        )),
+                    )
+                        .into_response();
+                }
+                Err(_) => {
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse::new(
+                            error_codes::INTERNAL_ERROR,
+                            "Authentication error",
+                        )),
+                    )
+                        .into_response();
+                }
+            }
+        }
+        Ok(false) => {}
+        Err(_) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(
+                    error_codes::INTERNAL_ERROR,
+                    "Authentication error",
+                )),
+            )
+                .into_response();
+        }
+    }
+
     // Auto-set language preference if not already set
     if user.preferred_language.is_none() {
         // Determine language: explicit request field takes priority over Accept-Language
-        let lang_to_set = req.preferred_language.as_deref().and_then(|lang_str| {
-            match lang_str {
-                "ko" => Some(Language::Ko),
-                "ja" => Some(Language::Ja),
-                "en" => Some(Language::En),
-                "zh" => Some(Language::Zh),
-                _ => None,
-            }
-        }).or_else(|| detect_preferred_language(&accept_language));
+        let lang_to_set = req
+            .preferred_language
+            .as_deref()
+            .and_then(Language::from_code)
+            .or_else(|| detect_preferred_language(&accept_language));
 
         if let Some(lang) = lang_to_set {
             let db = &state.db_pool;
@@ -324,6 +497,7 @@ pub async fn api_login(
         )
             .into_response();
     }
+    reset_login_rate_limit(&state, &user.login_name).await;
 
     // Return success with user info
     (
@@ -502,15 +676,11 @@ pub async fn api_signup(
     };
 
     // Determine language: explicit request field takes priority over Accept-Language
-    let lang_to_set = req.preferred_language.as_deref().and_then(|lang_str| {
-        match lang_str {
-            "ko" => Some(Language::Ko),
-            "ja" => Some(Language::Ja),
-            "en" => Some(Language::En),
-            "zh" => Some(Language::Zh),
-            _ => None,
-        }
-    }).or_else(|| detect_preferred_language(&accept_language));
+    let lang_to_set = req
+        .preferred_language
+        .as_deref()
+        .and_then(Language::from_code)
+        .or_else(|| detect_preferred_language(&accept_language));
 
     if let Some(lang) = lang_to_set {
         if update_user_preferred_language(&mut tx, user.id, Some(lang))
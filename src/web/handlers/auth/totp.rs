@@ -0,0 +1,332 @@
+use crate::app_error::AppError;
+use crate::models::totp::{
+    build_totp, decrypt_secret, delete_totp_secret, enable_totp, encrypt_secret, find_totp_secret,
+    generate_backup_codes, generate_secret, hash_backup_code, is_totp_enabled,
+    save_pending_totp_secret, verify_login_code, verify_totp_code,
+};
+use crate::models::user::{find_user_by_id, AuthSession, User};
+use crate::web::context::CommonContext;
+use crate::web::handlers::auth::{
+    check_login_rate_limit, check_totp_rate_limit, reset_login_rate_limit,
+};
+use crate::web::handlers::{
+    get_user_bundle, safe_format_message, safe_get_message, ExtractAcceptLanguage, ExtractClientIp,
+    ExtractFtlLang,
+};
+use crate::web::state::AppState;
+use crate::AppConfig;
+use anyhow::Result;
+use axum::http::header::RETRY_AFTER;
+use axum::response::{IntoResponse, Redirect};
+use axum::{extract::State, http::StatusCode, response::Html, Form};
+use axum_messages::Messages;
+use chrono::{DateTime, TimeDelta, Utc};
+use fluent::{FluentArgs, FluentValue};
+use minijinja::context;
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tower_sessions::Session;
+use uuid::Uuid;
+
+const PENDING_TOTP_LOGIN_KEY: &str = "pending_totp_login";
+const PENDING_TOTP_LOGIN_TTL_MINUTES: i64 = 5;
+
+/// A login that passed the password check and is waiting for the second factor
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingTotpLogin {
+    user_id: Uuid,
+    next: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+pub async fn is_totp_required(db: &PgPool, user_id: Uuid) -> Result<bool> {
+    let mut tx = db.begin().await?;
+    let enabled = is_totp_enabled(&mut tx, user_id).await?;
+    tx.commit().await?;
+    Ok(enabled)
+}
+
+pub async fn check_login_code(
+    db: &PgPool,
+    config: &AppConfig,
+    user: &User,
+    code: &str,
+) -> Result<bool> {
+    let mut tx = db.begin().await?;
+    let verified = verify_login_code(&mut tx, config, user, code).await?;
+    tx.commit().await?;
+    Ok(verified)
+}
+
+pub async fn start_pending_totp_login(
+    session: &Session,
+    user_id: Uuid,
+    next: Option<String>,
+) -> Result<()> {
+    session
+        .insert(
+            PENDING_TOTP_LOGIN_KEY,
+            PendingTotpLogin {
+                user_id,
+                next,
+                created_at: Utc::now(),
+            },
+        )
+        .await?;
+    Ok(())
+}
+
+async fn find_pending_totp_login(session: &Session) -> Result<Option<PendingTotpLogin>> {
+    let pending = session
+        .get::<PendingTotpLogin>(PENDING_TOTP_LOGIN_KEY)
+        .await?
+        .filter(|pending| {
+            pending.created_at + TimeDelta::minutes(PENDING_TOTP_LOGIN_TTL_MINUTES) > Utc::now()
+        });
+    Ok(pending)
+}
+
+pub async fn totp_challenge(
+    messages: Messages,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    session: Session,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    if find_pending_totp_login(&session).await?.is_none() {
+        return Ok(Redirect::to("/login").into_response());
+    }
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("totp_challenge.jinja")?;
+    let rendered: String = template.render(context! {
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct TotpCodeForm {
+    code: String,
+}
+
+pub async fn do_totp_challenge(
+    mut auth_session: AuthSession,
+    session: Session,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractClientIp(client_ip): ExtractClientIp,
+    messages: Messages,
+    State(state): State<AppState>,
+    Form(form): Form<TotpCodeForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let Some(pending) = find_pending_totp_login(&session).await? else {
+        session
+            .remove::<PendingTotpLogin>(PENDING_TOTP_LOGIN_KEY)
+            .await?;
+        return Ok(Redirect::to("/login").into_response());
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let Some(user) = find_user_by_id(&mut tx, pending.user_id)
+        .await?
+        .filter(|user| user.deleted_at.is_none())
+    else {
+        session
+            .remove::<PendingTotpLogin>(PENDING_TOTP_LOGIN_KEY)
+            .await?;
+        return Ok(Redirect::to("/login").into_response());
+    };
+    let bundle = get_user_bundle(&accept_language, Some(&user));
+
+    // Codes count as login attempts, so they can't be guessed from many sessions
+    if let Some(retry_after) = check_login_rate_limit(&state, client_ip, &user.login_name).await {
+        return Ok((
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            safe_get_message(&bundle, "message-too-many-attempts"),
+        )
+            .into_response());
+    }
+    // After a handful of wrong codes the login has to start over from the password
+    if check_totp_rate_limit(&state, &user.login_name)
+        .await
+        .is_some()
+    {
+        session
+            .remove::<PendingTotpLogin>(PENDING_TOTP_LOGIN_KEY)
+            .await?;
+        messages.error(safe_get_message(&bundle, "message-too-many-attempts"));
+        return Ok(Redirect::to("/login").into_response());
+    }
+
+    if !verify_login_code(&mut tx, &state.config, &user, &form.code).await? {
+        messages.error(safe_get_message(&bundle, "totp-error-invalid-code"));
+        return Ok(Redirect::to("/auth/totp").into_response());
+    }
+    tx.commit().await?;
+
+    session
+        .remove::<PendingTotpLogin>(PENDING_TOTP_LOGIN_KEY)
+        .await?;
+    if auth_session.login(&user).await.is_err() {
+        return Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+    }
+    reset_login_rate_limit(&state, &user.login_name).await;
+
+    let mut args = FluentArgs::new();
+    args.set("name", FluentValue::from(user.display_name.clone()));
+    messages.success(safe_format_message(&bundle, "welcome", Some(&args)));
+
+    if let Some(ref next) = pending.next {
+        Ok(Redirect::to(next).into_response())
+    } else {
+        Ok(Redirect::to("/").into_response())
+    }
+}
+
+pub async fn totp_setup(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    messages: Messages,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    if state.config.totp_encryption_key.is_empty() {
+        messages.error(safe_get_message(&bundle, "totp-error-unavailable"));
+        return Ok(Redirect::to("/account").into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+
+    // Keep showing the same secret until setup is finished, so reloading the
+    // page doesn't invalidate an authenticator entry that was already scanned
+    let secret = match find_totp_secret(&mut tx, user.id).await? {
+        Some(existing) if existing.is_enabled() => {
+            return Ok(Redirect::to("/account").into_response());
+        }
+        Some(existing) => decrypt_secret(&state.config, &existing.secret)?,
+        None => {
+            let secret = generate_secret()?;
+            let encrypted = encrypt_secret(&state.config, &secret)?;
+            save_pending_totp_secret(&mut tx, user.id, &encrypted).await?;
+            secret
+        }
+    };
+    tx.commit().await?;
+
+    let totp = build_totp(&state.config, secret, &user.login_name)?;
+    let qr_code_svg = QrCode::new(totp.get_url().as_bytes())?
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .build();
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("totp_setup.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        qr_code_svg,
+        secret_base32 => totp.get_secret_base32(),
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn do_totp_setup(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    messages: Messages,
+    State(state): State<AppState>,
+    Form(form): Form<TotpCodeForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let Some(totp_secret) = find_totp_secret(&mut tx, user.id)
+        .await?
+        .filter(|secret| !secret.is_enabled())
+    else {
+        return Ok(Redirect::to("/account").into_response());
+    };
+
+    if !verify_totp_code(&state.config, &totp_secret, &user.login_name, &form.code)? {
+        messages.error(safe_get_message(&bundle, "totp-error-invalid-code"));
+        return Ok(Redirect::to("/account/2fa/setup").into_response());
+    }
+
+    // Backup codes are only stored hashed, so this is the one time they're shown
+    let backup_codes = generate_backup_codes();
+    let backup_code_hashes: Vec<String> = backup_codes
+        .iter()
+        .map(|code| hash_backup_code(code))
+        .collect();
+    enable_totp(&mut tx, user.id, &backup_code_hashes).await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+    tx.commit().await?;
+
+    let messages = messages.success(safe_get_message(&bundle, "totp-enabled"));
+
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("totp_backup_codes.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        backup_codes,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct DisableTotpForm {
+    password: String,
+}
+
+pub async fn do_disable_totp(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    messages: Messages,
+    State(state): State<AppState>,
+    Form(form): Form<DisableTotpForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let bundle = get_user_bundle(&accept_language, Some(current_user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let user = find_user_by_id(&mut tx, current_user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User".to_string()))?;
+
+    if user.verify_password(&form.password).is_err() {
+        messages.error(safe_get_message(
+            &bundle,
+            "account-change-password-error-incorrect-current",
+        ));
+        return Ok(Redirect::to("/account").into_response());
+    }
+
+    delete_totp_secret(&mut tx, user.id).await?;
+    tx.commit().await?;
+
+    messages.success(safe_get_message(&bundle, "totp-disabled"));
+    Ok(Redirect::to("/account").into_response())
+}
@@ -0,0 +1,301 @@
+use crate::app_error::AppError;
+use crate::models::actor::Actor;
+use crate::models::boost::{create_boost, delete_boost, find_boost, is_post_boostable};
+use crate::models::follow;
+use crate::models::user::AuthSession;
+use crate::web::handlers::activitypub::{
+    federation_config, find_public_note_author, generate_object_id, Announce, Undo,
+};
+use crate::web::handlers::ExtractFtlLang;
+use crate::web::state::AppState;
+use activitypub_federation::config::Data;
+use activitypub_federation::fetch::object_id::ObjectId;
+use activitypub_federation::traits::Actor as ActivityPubFederationActor;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse};
+use axum::Form;
+use minijinja::context;
+use serde::Deserialize;
+use sqlx::{Postgres, Transaction};
+use url::Url;
+use uuid::Uuid;
+
+const PUBLIC_AUDIENCE: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+#[derive(Deserialize)]
+pub struct BoostForm {
+    /// ActivityPub IRI of the post, local or remote
+    pub object_iri: String,
+}
+
+/// A post that has been checked to be boostable
+struct BoostTarget {
+    object: Url,
+    /// Set for local posts
+    post_id: Option<Uuid>,
+    /// Told about the boost alongside the booster's followers
+    remote_author: Option<Actor>,
+}
+
+async fn federation_data(state: &AppState) -> Result<Data<AppState>, AppError> {
+    Ok(federation_config(state).await?.to_request_data())
+}
+
+/// Local posts are boosted by their ActivityPub IRI, /ap/posts/:id
+fn local_post_id(object: &Url) -> Option<Uuid> {
+    let mut segments = object.path_segments()?;
+    match (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) {
+        (Some("ap"), Some("posts"), Some(id), None) => Uuid::parse_str(id).ok(),
+        _ => None,
+    }
+}
+
+/// Check that `object_iri` names a post that may be boosted. Posts in private
+/// communities and remote posts that aren't public never are.
+async fn resolve_boost_target(
+    state: &AppState,
+    data: &Data<AppState>,
+    object_iri: &str,
+) -> Result<Option<BoostTarget>, AppError> {
+    let Ok(object) = Url::parse(object_iri) else {
+        return Ok(None);
+    };
+
+    if object.domain() == Some(state.config.domain.as_str()) {
+        let Some(post_id) = local_post_id(&object) else {
+            return Ok(None);
+        };
+        let mut tx = state.db_pool.begin().await?;
+        let boostable = is_post_boostable(&mut tx, post_id).await?;
+        tx.commit().await?;
+
+        return Ok(boostable.then_some(BoostTarget {
+            object,
+            post_id: Some(post_id),
+            remote_author: None,
+        }));
+    }
+
+    let Some(author) = find_public_note_author(&object, data).await? else {
+        return Ok(None);
+    };
+    Ok(Some(BoostTarget {
+        object,
+        post_id: None,
+        remote_author: Some(author),
+    }))
+}
+
+/// Inboxes of the booster's remote followers, plus the remote author's.
+/// Local followers read boosts from this server directly.
+async fn boost_recipients(
+    tx: &mut Transaction<'_, Postgres>,
+    actor: &Actor,
+    remote_author: Option<&Actor>,
+) -> Result<Vec<Url>, AppError> {
+    let followers = follow::find_followers_by_actor_id(tx, actor.id).await?;
+    let mut inboxes: Vec<Url> = followers
+        .iter()
+        .filter(|follower| follower.user_id.is_none() && follower.community_id.is_none())
+        .chain(remote_author)
+        .map(|recipient| recipient.shared_inbox_or_inbox())
+        .collect();
+    inboxes.sort();
+    inboxes.dedup();
+    Ok(inboxes)
+}
+
+fn boost_announce(
+    actor: &Actor,
+    boost_iri: &str,
+    object: Url,
+    remote_author: Option<&Actor>,
+    published: String,
+) -> Result<Announce, AppError> {
+    let mut cc = vec![actor.followers_url.clone()];
+    cc.extend(remote_author.map(|author| author.iri.clone()));
+
+    Ok(Announce::new(
+        ObjectId::parse(&actor.iri)?,
+        object,
+        boost_iri.parse()?,
+        vec![PUBLIC_AUDIENCE.to_string()],
+        cc,
+        published,
+    ))
+}
+
+fn render_boost_button(
+    state: &AppState,
+    ftl_lang: String,
+    object_iri: &str,
+    is_boosted: bool,
+) -> Result<Html<String>, AppError> {
+    let template: minijinja::Template<'_, '_> = state.env.get_template("boost_button.jinja")?;
+    let rendered = template.render(context! {
+        object_iri,
+        is_boosted,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered))
+}
+
+/// HTMX: show the boost toggle for a local post that can be boosted
+pub async fn hx_boost_button(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if !is_post_boostable(&mut tx, id).await? {
+        return Ok(Html(String::new()).into_response());
+    }
+    let actor = Actor::find_by_user_id(&mut tx, user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+    let object_iri = format!("https://{}/ap/posts/{}", state.config.domain, id);
+    let is_boosted = find_boost(&mut tx, actor.id, &object_iri).await?.is_some();
+
+    tx.commit().await?;
+
+    Ok(render_boost_button(&state, ftl_lang, &object_iri, is_boosted)?.into_response())
+}
+
+/// HTMX: boost a post, local or remote, to the current user's followers
+pub async fn boost_post(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Form(form): Form<BoostForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let data = federation_data(&state).await?;
+    let Some(target) = resolve_boost_target(&state, &data, &form.object_iri).await? else {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let actor = Actor::find_by_user_id(&mut tx, user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+
+    let boost_iri = generate_object_id(&state.config.domain)?;
+    let boost = create_boost(
+        &mut tx,
+        boost_iri.as_str(),
+        actor.id,
+        target.object.as_str(),
+        target.post_id,
+    )
+    .await?;
+    let Some(boost) = boost else {
+        // Boosted already, so there is nothing new to announce
+        tx.commit().await?;
+        return Ok(
+            render_boost_button(&state, ftl_lang, target.object.as_str(), true)?.into_response(),
+        );
+    };
+    let recipients = boost_recipients(&mut tx, &actor, target.remote_author.as_ref()).await?;
+    tx.commit().await?;
+
+    let object_iri = target.object.to_string();
+    let announce = boost_announce(
+        &actor,
+        &boost.iri,
+        target.object,
+        target.remote_author.as_ref(),
+        boost.created_at.to_rfc3339(),
+    )?;
+    // The boost is already recorded, so a failed delivery is only logged
+    if !recipients.is_empty() {
+        if let Err(e) = actor
+            .send(
+                announce,
+                recipients,
+                state.config.use_activitypub_queue(),
+                &data,
+            )
+            .await
+        {
+            tracing::warn!("Failed to send Announce of {}: {:?}", object_iri, e);
+        }
+    }
+
+    Ok(render_boost_button(&state, ftl_lang, &object_iri, true)?.into_response())
+}
+
+/// HTMX: take back a boost and tell everyone who received it
+pub async fn unboost_post(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Form(form): Form<BoostForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let actor = Actor::find_by_user_id(&mut tx, user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+    let Some(boost) = find_boost(&mut tx, actor.id, &form.object_iri).await? else {
+        tx.commit().await?;
+        return Ok(render_boost_button(&state, ftl_lang, &form.object_iri, false)?.into_response());
+    };
+    delete_boost(&mut tx, boost.id).await?;
+    tx.commit().await?;
+
+    let data = federation_data(&state).await?;
+    let object: Url = boost.object_iri.parse()?;
+    // The remote author was told about the boost, so tell them it's gone too
+    let remote_author = match boost.post_id {
+        Some(_) => None,
+        None => find_public_note_author(&object, &data).await?,
+    };
+
+    let mut tx = db.begin().await?;
+    let recipients = boost_recipients(&mut tx, &actor, remote_author.as_ref()).await?;
+    tx.commit().await?;
+
+    let announce = boost_announce(
+        &actor,
+        &boost.iri,
+        object,
+        remote_author.as_ref(),
+        boost.created_at.to_rfc3339(),
+    )?;
+    let undo = Undo::new_announce(
+        ObjectId::parse(&actor.iri)?,
+        announce,
+        generate_object_id(&state.config.domain)?,
+    );
+    if !recipients.is_empty() {
+        if let Err(e) = actor
+            .send(
+                undo,
+                recipients,
+                state.config.use_activitypub_queue(),
+                &data,
+            )
+            .await
+        {
+            tracing::warn!("Failed to send Undo of boost {}: {:?}", boost.iri, e);
+        }
+    }
+
+    Ok(render_boost_button(&state, ftl_lang, &boost.object_iri, false)?.into_response())
+}
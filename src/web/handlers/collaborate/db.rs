@@ -1,3 +1,5 @@
+use super::recording::{assemble_replay, SessionRecorder, COLLABORATIVE_REPLAY_EXTENSION};
+use crate::models::image::replay_object_key;
 use crate::web::state::AppState;
 use anyhow::Result;
 use aws_sdk_s3;
@@ -13,6 +15,7 @@ pub struct SessionInfo {
     pub height: i32,
     pub title: Option<String>,
     pub max_participants: i32,
+    pub record_replay: bool,
 }
 
 pub async fn get_session_info(
@@ -21,7 +24,8 @@ pub async fn get_session_info(
 ) -> Result<Option<SessionInfo>, sqlx::Error> {
     let session = sqlx::query!(
         r#"
-        SELECT owner_id, width, height, title, max_participants FROM collaborative_sessions
+        SELECT owner_id, width, height, title, max_participants, record_replay
+        FROM collaborative_sessions
         WHERE id = $1 AND ended_at IS NULL
         "#,
         room_uuid
@@ -35,6 +39,7 @@ pub async fn get_session_info(
         height: s.height,
         title: s.title,
         max_participants: s.max_participants,
+        record_replay: s.record_replay,
     }))
 }
 
@@ -239,8 +244,11 @@ pub async fn get_active_participants(
             updated_at: chrono::Utc::now(),
             banner_id: None,
             preferred_language: None,
+            language_fallbacks: Vec::new(),
             deleted_at: None,
             show_sensitive_content: false,
+            is_admin: false,
+            is_locked: false,
         })
         .collect())
 }
@@ -283,13 +291,18 @@ pub async fn save_session_to_post(
     png_data: Vec<u8>,
     state: AppState,
 ) -> Result<(Uuid, String), Box<dyn std::error::Error + Send + Sync>> {
+    // Flush the tail of the recording before locking the session row, which
+    // the chunk insert's foreign key check would otherwise wait on
+    let recorder = SessionRecorder::new(state.redis_pool.clone());
+    recorder.flush(&db, session_id).await?;
+
     let mut tx = db.begin().await?;
 
     // Lock the session row and check if it's already saved atomically
     let session = sqlx::query!(
         r#"
         SELECT cs.owner_id, cs.title, cs.width, cs.height, cs.community_id, 
-               cs.created_at, cs.ended_at, cs.saved_post_id,
+               cs.created_at, cs.ended_at, cs.saved_post_id, cs.record_replay,
                u.login_name as owner_login_name 
         FROM collaborative_sessions cs
         JOIN users u ON cs.owner_id = u.id
@@ -364,6 +377,29 @@ pub async fn save_session_to_post(
         .send()
         .await?;
 
+    let replay_filename = if session.record_replay {
+        match assemble_replay(&mut tx, session_id, session.width, session.height).await? {
+            Some(replay_data) => {
+                let replay_filename = format!(
+                    "{}.{}",
+                    sha256::digest(&replay_data),
+                    COLLABORATIVE_REPLAY_EXTENSION
+                );
+                s3_client
+                    .put_object()
+                    .bucket(&state.config.aws_s3_bucket)
+                    .key(replay_object_key(&replay_filename, false))
+                    .body(aws_sdk_s3::primitives::ByteStream::from(replay_data))
+                    .send()
+                    .await?;
+                Some(replay_filename)
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+
     let participant_names: Vec<String> =
         participants.iter().map(|p| p.login_name.clone()).collect();
 
@@ -399,13 +435,14 @@ pub async fn save_session_to_post(
     sqlx::query!(
         r#"
         INSERT INTO images (id, width, height, paint_duration, stroke_count, image_filename, replay_filename, tool)
-        VALUES ($1, $2, $3, $4, 0, $5, NULL, 'neo-cucumber'::tool)
+        VALUES ($1, $2, $3, $4, 0, $5, $6, 'neo-cucumber'::tool)
         "#,
         image_id,
         session.width,
         session.height,
         paint_duration,
         format!("{}.png", image_sha256),
+        replay_filename,
     )
     .execute(&mut *tx)
     .await?;
@@ -414,7 +451,7 @@ pub async fn save_session_to_post(
     sqlx::query!(
         r#"
         INSERT INTO posts (id, author_id, community_id, image_id, is_sensitive, published_at)
-        VALUES ($1, $2, $3, $4, false, NOW())
+        VALUES ($1, $2, $3, $4, COALESCE((SELECT is_sensitive FROM communities WHERE id = $3), false), NOW())
         "#,
         post_id,
         owner_id,
@@ -432,8 +469,24 @@ pub async fn save_session_to_post(
     .execute(&mut *tx)
     .await?;
 
+    // The replay file now holds the recording
+    sqlx::query!(
+        "DELETE FROM collaborative_session_recording_chunks WHERE session_id = $1",
+        session_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
     tx.commit().await?;
 
+    if let Err(e) = recorder.cleanup(session_id).await {
+        tracing::warn!(
+            "Failed to clean up recording buffer for session {}: {}",
+            session_id,
+            e
+        );
+    }
+
     tracing::info!(
         "Successfully saved collaborative drawing from session {} as post {}",
         session_id,
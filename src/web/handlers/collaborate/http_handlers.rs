@@ -78,6 +78,12 @@ pub async fn get_collaboration_meta(
     .fetch_one(db)
     .await?;
 
+    let spectator_count = state
+        .redis_state
+        .get_spectator_count(session_uuid)
+        .await
+        .unwrap_or(0);
+
     Ok(Json(CollaborationMeta {
         title: session
             .title
@@ -89,6 +95,77 @@ pub async fn get_collaboration_meta(
         owner_login_name: session.owner_login_name,
         max_users: session.max_participants,
         current_user_count: user_count,
+        spectator_count,
+    }))
+}
+
+/// Participant count and connection staleness for a live session
+pub async fn get_session_health(
+    Path(session_uuid): Path<Uuid>,
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+) -> Result<Json<SessionHealth>, AppError> {
+    let _user = auth_session
+        .user
+        .ok_or_else(|| anyhow::anyhow!("Authentication required"))?;
+
+    let db = &state.db_pool;
+
+    let is_live = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM collaborative_sessions
+            WHERE id = $1 AND ended_at IS NULL
+        ) as "exists!"
+        "#,
+        session_uuid
+    )
+    .fetch_one(db)
+    .await?;
+    if !is_live {
+        return Err(AppError::NotFound("Session".to_string()));
+    }
+
+    let participant_count = db::get_active_user_count(db, session_uuid).await?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("System time is before UNIX_EPOCH")
+        .as_secs();
+
+    let connection_ids = state
+        .redis_state
+        .get_room_connections(session_uuid)
+        .await
+        .unwrap_or_default();
+    let mut pong_ages = Vec::with_capacity(connection_ids.len());
+    for connection_id in &connection_ids {
+        if let Ok(Some(info)) = state.redis_state.get_connection_info(connection_id).await {
+            pong_ages.push(now.saturating_sub(info.last_pong));
+        }
+    }
+
+    let spectator_count = state
+        .redis_state
+        .get_spectator_count(session_uuid)
+        .await
+        .unwrap_or(0);
+    let last_activity = state
+        .redis_state
+        .get_room_activity(session_uuid)
+        .await
+        .unwrap_or(None);
+
+    Ok(Json(SessionHealth {
+        participant_count,
+        connection_count: pong_ages.len(),
+        spectator_count,
+        stale_connection_count: pong_ages
+            .iter()
+            .filter(|&&age| age > super::websocket::PING_INTERVAL_SECONDS)
+            .count(),
+        max_pong_age_seconds: pong_ages.iter().copied().max(),
+        last_activity_age_seconds: last_activity.map(|at| now.saturating_sub(at)),
     }))
 }
 
@@ -105,7 +182,7 @@ pub async fn collaborate_lobby(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx = CommonContext::build(&mut tx, Some(user.id)).await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
 
     let active_sessions = sqlx::query_as!(
         SessionWithCounts,
@@ -145,6 +222,7 @@ pub async fn collaborate_lobby(
         ],
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -173,8 +251,8 @@ pub async fn create_collaborative_session(
     sqlx::query!(
         r#"
         INSERT INTO collaborative_sessions
-        (id, owner_id, title, width, height, is_public, community_id, max_participants)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        (id, owner_id, title, width, height, is_public, community_id, max_participants, record_replay)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         "#,
         session_id,
         user.id,
@@ -183,7 +261,8 @@ pub async fn create_collaborative_session(
         request.height,
         request.is_public,
         community_id,
-        request.max_participants
+        request.max_participants,
+        request.record_replay
     )
     .execute(&mut *tx)
     .await?;
@@ -20,6 +20,15 @@ pub struct EndSessionContext<'a> {
     pub connection_id: &'a str,
 }
 
+pub struct KickContext<'a> {
+    pub user_id: Uuid,
+    pub user_login_name: &'a str,
+    pub room_uuid: Uuid,
+    pub is_owner: bool,
+    pub db: &'a Pool<Postgres>,
+    pub state: &'a AppState,
+}
+
 // Safe timestamp helper to avoid panics from system clock issues
 fn get_current_timestamp_ms() -> u64 {
     std::time::SystemTime::now()
@@ -29,6 +38,7 @@ fn get_current_timestamp_ms() -> u64 {
 }
 
 const MAX_USER_MESSAGES: usize = 100;
+const PRESENCE_MESSAGE_LEN: usize = 26;
 
 // Message type constants matching neo-cucumber protocol
 #[repr(u8)]
@@ -42,6 +52,11 @@ pub enum MessageType {
     EndSession = 0x07,
     SessionExpired = 0x08,
     Leave = 0x09,
+    SpectatorCount = 0x0A,
+    Kick = 0x0B,
+    Presence = 0x0C,
+    Sequenced = 0x0D,
+    Resync = 0x0E,
 }
 
 // Message structures
@@ -78,6 +93,11 @@ pub struct LeaveMessage {
     pub username: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct SpectatorCountMessage {
+    pub count: u32,
+}
+
 // Message serialization functions
 impl JoinMessage {
     pub fn serialize(&self) -> Vec<u8> {
@@ -180,6 +200,40 @@ impl LeaveMessage {
     }
 }
 
+impl SpectatorCountMessage {
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(1 + 4);
+        buffer.push(MessageType::SpectatorCount as u8);
+        buffer.extend_from_slice(&self.count.to_le_bytes());
+
+        buffer
+    }
+}
+
+/// An op stamped with its sequence number in the session. Format:
+/// [0x0D][sequence:8][op:variable]
+pub fn encode_sequenced(sequence: u64, op: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(1 + 8 + op.len());
+    buffer.push(MessageType::Sequenced as u8);
+    buffer.extend_from_slice(&sequence.to_le_bytes());
+    buffer.extend_from_slice(op);
+
+    buffer
+}
+
+/// Sent to a connecting client before it is caught up. Format:
+/// [0x0E][fullReload:1][sequence:8]. With fullReload set the client discards its
+/// canvases and the full history follows; otherwise only the ops it missed follow.
+/// Either way the client is at `sequence` afterwards.
+pub fn encode_resync(full_reload: bool, sequence: u64) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(1 + 1 + 8);
+    buffer.push(MessageType::Resync as u8);
+    buffer.push(u8::from(full_reload));
+    buffer.extend_from_slice(&sequence.to_le_bytes());
+
+    buffer
+}
+
 // Message parsing utilities
 pub fn parse_message_type(data: &[u8]) -> Option<MessageType> {
     if data.is_empty() {
@@ -195,6 +249,11 @@ pub fn parse_message_type(data: &[u8]) -> Option<MessageType> {
         0x07 => Some(MessageType::EndSession),
         0x08 => Some(MessageType::SessionExpired),
         0x09 => Some(MessageType::Leave),
+        0x0A => Some(MessageType::SpectatorCount),
+        0x0B => Some(MessageType::Kick),
+        0x0C => Some(MessageType::Presence),
+        0x0D => Some(MessageType::Sequenced),
+        0x0E => Some(MessageType::Resync),
         _ => None,
     }
 }
@@ -417,6 +476,22 @@ pub fn handle_chat_message(data: &[u8], user_id: Uuid, user_login_name: &str) ->
     Some(Message::Binary(chat_message.serialize()))
 }
 
+/// Cursor position of a participant. Format:
+/// [0x0C][UUID:16][x:2][y:2][flags:1][tool:1][r:1][g:1][b:1], where flag bit 0 is
+/// set while the pointer is over the canvas
+pub fn handle_presence_message(data: &[u8], user_id: Uuid) -> Option<Message> {
+    if data.len() < PRESENCE_MESSAGE_LEN {
+        return None;
+    }
+
+    let presence_user = bytes_to_uuid(&data[1..17]).ok()?;
+    if presence_user != user_id {
+        return None;
+    }
+
+    Some(Message::Binary(data[..PRESENCE_MESSAGE_LEN].to_vec()))
+}
+
 pub async fn handle_end_session_message(data: &[u8], ctx: EndSessionContext<'_>) {
     if ctx.is_owner && data.len() >= 19 {
         if let Ok(sender_uuid) = bytes_to_uuid(&data[1..17]) {
@@ -494,6 +569,133 @@ pub async fn handle_end_session_message(data: &[u8], ctx: EndSessionContext<'_>)
     }
 }
 
+/// Remove a participant on the owner's behalf. Format: [0x0B][sender UUID:16][target UUID:16]
+pub async fn handle_kick_message(data: &[u8], ctx: KickContext<'_>) {
+    if !ctx.is_owner {
+        warn!(
+            "Non-owner {} attempted to kick a participant from session {}",
+            ctx.user_login_name, ctx.room_uuid
+        );
+        return;
+    }
+    if data.len() < 33 {
+        return;
+    }
+
+    let (Ok(sender_uuid), Ok(target_uuid)) =
+        (bytes_to_uuid(&data[1..17]), bytes_to_uuid(&data[17..33]))
+    else {
+        return;
+    };
+    if sender_uuid != ctx.user_id || target_uuid == ctx.user_id {
+        return;
+    }
+
+    // Mark first, so a reconnect racing the kick is already turned away
+    if let Err(e) = ctx
+        .state
+        .redis_state
+        .mark_kicked(ctx.room_uuid, target_uuid)
+        .await
+    {
+        error!(
+            "Failed to mark user {} as kicked from room {}: {}",
+            target_uuid, ctx.room_uuid, e
+        );
+        return;
+    }
+
+    let connections = match ctx
+        .state
+        .redis_state
+        .get_room_connections(ctx.room_uuid)
+        .await
+    {
+        Ok(connections) => connections,
+        Err(e) => {
+            error!(
+                "Failed to get Redis connections for kick in room {}: {}",
+                ctx.room_uuid, e
+            );
+            Vec::new()
+        }
+    };
+    let mut target_connections = Vec::new();
+    for connection_id in connections {
+        if let Ok(Some(info)) = ctx
+            .state
+            .redis_state
+            .get_connection_info(&connection_id)
+            .await
+        {
+            if info.user_id == target_uuid {
+                target_connections.push(info);
+            }
+        }
+    }
+
+    info!(
+        "Owner {} kicked user {} ({} connections) from session {}",
+        ctx.user_login_name,
+        target_uuid,
+        target_connections.len(),
+        ctx.room_uuid
+    );
+
+    // Tell the target's connections, wherever they are served, to close
+    let room_message = super::redis_state::RoomMessage {
+        from_connection: "system".to_string(),
+        user_id: target_uuid,
+        user_login_name: "system".to_string(),
+        message_type: "kick".to_string(),
+        payload: Vec::new(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("System time is before UNIX_EPOCH")
+            .as_secs(),
+    };
+    if let Err(e) = ctx
+        .state
+        .redis_state
+        .publish_message(ctx.room_uuid, &room_message)
+        .await
+    {
+        error!(
+            "Failed to publish kick for user {} in room {}: {}",
+            target_uuid, ctx.room_uuid, e
+        );
+    }
+
+    // The kicked connections skip their own cleanup, so remove them here; this
+    // also covers sockets left behind on an instance that went away
+    if let Some(info) = target_connections.first() {
+        send_leave_message(
+            ctx.room_uuid,
+            &info.connection_id,
+            target_uuid,
+            &info.user_login_name,
+            ctx.state,
+        )
+        .await;
+    }
+    for info in &target_connections {
+        if let Err(e) = ctx
+            .state
+            .redis_state
+            .unregister_connection(&info.connection_id)
+            .await
+        {
+            error!(
+                "Failed to unregister kicked connection {} from Redis: {}",
+                info.connection_id, e
+            );
+        }
+    }
+    if let Err(e) = db::mark_participant_inactive(ctx.db, ctx.room_uuid, target_uuid).await {
+        error!("Failed to mark kicked participant inactive: {}", e);
+    }
+}
+
 pub fn should_store_message(msg: &Message) -> bool {
     if let Message::Binary(data) = msg {
         if data.is_empty() {
@@ -505,9 +707,11 @@ pub fn should_store_message(msg: &Message) -> bool {
         // - Chat messages (ephemeral conversation)
         // - JOIN messages (current participants sent via JOIN_RESPONSE)
         // - LEAVE messages (current participants tracked in Redis presence)
+        // - SPECTATOR_COUNT messages (current count served by the meta endpoint)
         msg_type != MessageType::Chat as u8
             && msg_type != MessageType::Join as u8
             && msg_type != MessageType::Leave as u8
+            && msg_type != MessageType::SpectatorCount as u8
     } else {
         true
     }
@@ -591,6 +795,23 @@ async fn send_snapshot_request(
     }
 }
 
+/// Number an op that is about to be broadcast, so clients that drop and
+/// reconnect can ask for the ops they missed. Falls back to the unstamped op if
+/// Redis can't hand out a sequence number.
+pub async fn sequence_op(msg: &Message, room_uuid: Uuid, state: &AppState) -> Message {
+    let Message::Binary(data) = msg else {
+        return msg.clone();
+    };
+
+    match state.redis_state.append_op(room_uuid, data).await {
+        Ok(sequence) => Message::Binary(encode_sequenced(sequence, data)),
+        Err(e) => {
+            error!("Failed to sequence op for room {}: {}", room_uuid, e);
+            msg.clone()
+        }
+    }
+}
+
 pub async fn broadcast_message(
     msg: &Message,
     room_uuid: Uuid,
@@ -705,3 +926,44 @@ pub async fn send_leave_message(
         }
     }
 }
+
+pub async fn send_spectator_count_message(
+    room_uuid: Uuid,
+    spectator_count: usize,
+    state: &AppState,
+) {
+    let spectator_count_message = SpectatorCountMessage {
+        count: spectator_count as u32,
+    };
+
+    let room_message = super::redis_state::RoomMessage {
+        from_connection: "system".to_string(),
+        user_id: Uuid::nil(),
+        user_login_name: "system".to_string(),
+        message_type: "spectator_count_changed".to_string(),
+        payload: spectator_count_message.serialize(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("System time is before UNIX_EPOCH")
+            .as_secs(),
+    };
+
+    match state
+        .redis_state
+        .publish_message(room_uuid, &room_message)
+        .await
+    {
+        Ok(subscriber_count) => {
+            debug!(
+                "Sent spectator count {} to {} subscribers in room {}",
+                spectator_count, subscriber_count, room_uuid
+            );
+        }
+        Err(e) => {
+            error!(
+                "Failed to publish spectator count for room {}: {}",
+                room_uuid, e
+            );
+        }
+    }
+}
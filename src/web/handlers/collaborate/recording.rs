@@ -0,0 +1,184 @@
+use axum::extract::ws::Message;
+use redis::AsyncCommands;
+use sqlx::{Pool, Postgres};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::messages::{is_client_message, MessageType};
+use crate::redis::RedisPool;
+
+const RECORDING_BUFFER_PREFIX: &str = "oeee:recording:";
+const RECORDING_CHUNK_COUNTER_PREFIX: &str = "oeee:recording_chunks:";
+const RECORDING_TTL: u64 = 604800; // 7 days, refreshed as ops arrive
+const RECORDING_CHUNK_OPS: usize = 2000;
+
+/// Extension of replay files assembled from a recording
+pub const COLLABORATIVE_REPLAY_EXTENSION: &str = "ncr";
+const COLLABORATIVE_REPLAY_MAGIC: &[u8; 4] = b"NCR1";
+
+/// Records the op stream of sessions created with `record_replay`.
+///
+/// Ops are buffered in Redis and flushed to Postgres in chunks, so a long
+/// session never has to be held in memory or written one op per row. Unlike
+/// the message history, the recording is never pruned by snapshots.
+pub struct SessionRecorder {
+    pool: RedisPool,
+}
+
+impl SessionRecorder {
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    /// Whether a message belongs in the recording: drawing ops, plus joins so
+    /// playback can stack layers in the order participants arrived
+    pub fn should_record(msg: &Message) -> bool {
+        match msg {
+            Message::Binary(data) => data.first().is_some_and(|&msg_type| {
+                is_client_message(msg_type) || msg_type == MessageType::Join as u8
+            }),
+            _ => false,
+        }
+    }
+
+    pub async fn record(
+        &self,
+        db: &Pool<Postgres>,
+        room_uuid: Uuid,
+        msg: &Message,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Message::Binary(data) = msg else {
+            return Ok(());
+        };
+
+        let mut conn = self.pool.get().await?;
+        let key = format!("{}{}", RECORDING_BUFFER_PREFIX, room_uuid);
+        let counter_key = format!("{}{}", RECORDING_CHUNK_COUNTER_PREFIX, room_uuid);
+
+        let (buffered,): (usize,) = redis::pipe()
+            .rpush(&key, data)
+            .expire(&key, RECORDING_TTL as i64)
+            .ignore()
+            .expire(&counter_key, RECORDING_TTL as i64)
+            .ignore()
+            .query_async(&mut *conn)
+            .await?;
+        drop(conn);
+
+        if buffered >= RECORDING_CHUNK_OPS {
+            self.flush(db, room_uuid).await?;
+        }
+        Ok(())
+    }
+
+    /// Move everything buffered in Redis into a new chunk in Postgres
+    pub async fn flush(
+        &self,
+        db: &Pool<Postgres>,
+        room_uuid: Uuid,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let key = format!("{}{}", RECORDING_BUFFER_PREFIX, room_uuid);
+        let counter_key = format!("{}{}", RECORDING_CHUNK_COUNTER_PREFIX, room_uuid);
+
+        let buffered: usize = conn.llen(&key).await?;
+        if buffered == 0 {
+            return Ok(());
+        }
+
+        // Taking the ops and numbering the chunk in one transaction keeps chunks
+        // in op order even when two connections flush at once
+        let (ops, chunk_index): (Vec<Vec<u8>>, i64) = redis::pipe()
+            .atomic()
+            .lrange(&key, 0, -1)
+            .del(&key)
+            .ignore()
+            .incr(&counter_key, 1)
+            .expire(&counter_key, RECORDING_TTL as i64)
+            .ignore()
+            .query_async(&mut *conn)
+            .await?;
+        drop(conn);
+
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        let mut chunk = Vec::with_capacity(ops.iter().map(|op| op.len() + 4).sum());
+        for op in &ops {
+            chunk.extend_from_slice(&(op.len() as u32).to_le_bytes());
+            chunk.extend_from_slice(op);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO collaborative_session_recording_chunks
+            (session_id, chunk_index, ops, op_count)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            room_uuid,
+            chunk_index,
+            chunk,
+            ops.len() as i32
+        )
+        .execute(db)
+        .await?;
+
+        debug!(
+            "Flushed recording chunk {} ({} ops) for room {}",
+            chunk_index,
+            ops.len(),
+            room_uuid
+        );
+        Ok(())
+    }
+
+    pub async fn cleanup(
+        &self,
+        room_uuid: Uuid,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let keys = vec![
+            format!("{}{}", RECORDING_BUFFER_PREFIX, room_uuid),
+            format!("{}{}", RECORDING_CHUNK_COUNTER_PREFIX, room_uuid),
+        ];
+        conn.del::<_, ()>(&keys).await?;
+        Ok(())
+    }
+}
+
+/// Build a replay file from a session's recorded chunks.
+///
+/// Layout: `NCR1`, canvas width and height as u16 LE, then every op as a
+/// u32 LE length followed by the op exactly as it was sent over the socket.
+/// Returns `None` when nothing was recorded.
+pub async fn assemble_replay(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    session_id: Uuid,
+    width: i32,
+    height: i32,
+) -> Result<Option<Vec<u8>>, sqlx::Error> {
+    let chunks = sqlx::query_scalar!(
+        r#"
+        SELECT ops FROM collaborative_session_recording_chunks
+        WHERE session_id = $1
+        ORDER BY chunk_index ASC
+        "#,
+        session_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    if chunks.is_empty() {
+        return Ok(None);
+    }
+
+    let mut replay = Vec::with_capacity(8 + chunks.iter().map(Vec::len).sum::<usize>());
+    replay.extend_from_slice(COLLABORATIVE_REPLAY_MAGIC);
+    replay.extend_from_slice(&(width as u16).to_le_bytes());
+    replay.extend_from_slice(&(height as u16).to_le_bytes());
+    for chunk in &chunks {
+        replay.extend_from_slice(chunk);
+    }
+    Ok(Some(replay))
+}
@@ -12,21 +12,48 @@ const SNAPSHOT_REQ_PREFIX: &str = "oeee:snapshot_req:";
 const CONNECTION_PREFIX: &str = "oeee:connection:";
 const ROOM_PREFIX: &str = "oeee:room:";
 const PUBSUB_PREFIX: &str = "oeee:pubsub:";
+const KICKED_PREFIX: &str = "oeee:kicked:";
+const SEQUENCE_PREFIX: &str = "oeee:seq:";
+const OP_LOG_PREFIX: &str = "oeee:oplog:";
 
 // TTL constants
 const ACTIVITY_TTL: u64 = 3600; // 1 hour
 const SNAPSHOT_REQ_TTL: u64 = 300; // 5 minutes
 const CONNECTION_TTL: u64 = 30; // 30 seconds (with heartbeat)
+const SPECTATOR_TTL: u64 = 86400; // 24 hours, refreshed as spectators join
+const KICKED_TTL: u64 = 600; // 10 minutes before a kicked user may rejoin
+const OP_LOG_TTL: u64 = 3600; // 1 hour, refreshed alongside the message history
+
+// Most recent ops kept for clients resyncing after a dropped connection
+const OP_LOG_LENGTH: usize = 2000;
+
+// Numbers the op and appends it to the log in one step, so the log is always
+// in sequence order. Entries are "<sequence>:<op bytes>".
+const APPEND_OP_SCRIPT: &str = r#"
+local sequence = redis.call('INCR', KEYS[1])
+redis.call('ZADD', KEYS[2], sequence, sequence .. ':' .. ARGV[1])
+redis.call('ZREMRANGEBYRANK', KEYS[2], 0, -(tonumber(ARGV[2]) + 1))
+redis.call('EXPIRE', KEYS[1], ARGV[3])
+redis.call('EXPIRE', KEYS[2], ARGV[3])
+return sequence
+"#;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionInfo {
     pub connection_id: String,
+    /// Correlation ID of the upgrade request, so the connection's Redis state can
+    /// be matched with the server's logs
+    #[serde(default)]
+    pub correlation_id: Option<Uuid>,
     pub user_id: Uuid,
     pub room_id: Uuid,
     pub user_login_name: String,
     pub server_instance: String,
     pub connected_at: u64,
     pub last_heartbeat: u64,
+    /// When the client last answered a ping; starts out as the connection time
+    #[serde(default)]
+    pub last_pong: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +66,64 @@ pub struct RoomMessage {
     pub timestamp: u64,
 }
 
+/// How a (re)connecting client is brought up to date
+#[derive(Debug)]
+pub enum ResyncPlan {
+    /// Replay these `(sequence, op)` pairs, after which the client is at `sequence`
+    Replay {
+        sequence: u64,
+        ops: Vec<(u64, Vec<u8>)>,
+    },
+    /// The client has nothing to resume from, or the ops it missed are no longer
+    /// in the log; it has to reload the full history, which brings it to `sequence`
+    Reload { sequence: u64 },
+}
+
+impl ResyncPlan {
+    /// Decide from the log entries after `resume_from`, plus the oldest entry
+    /// still in the log, whether the client can catch up by replaying them
+    fn from_log(
+        resume_from: u64,
+        sequence: u64,
+        oldest: Option<u64>,
+        ops: Vec<(u64, Vec<u8>)>,
+    ) -> Self {
+        // A client ahead of the room saw a sequence from before the log expired
+        if resume_from > sequence {
+            return ResyncPlan::Reload { sequence };
+        }
+        if resume_from == sequence {
+            return ResyncPlan::Replay {
+                sequence,
+                ops: Vec::new(),
+            };
+        }
+        match oldest {
+            Some(oldest) if oldest <= resume_from.saturating_add(1) => {
+                let sequence = ops.last().map_or(sequence, |(last, _)| sequence.max(*last));
+                ResyncPlan::Replay { sequence, ops }
+            }
+            _ => ResyncPlan::Reload { sequence },
+        }
+    }
+}
+
+/// Whether a room holding `count` connections, including the one just added, stays
+/// within `max_connections`; 0 means no limit
+fn is_within_connection_cap(count: usize, max_connections: usize) -> bool {
+    max_connections == 0 || count <= max_connections
+}
+
+/// Split an op log entry into its sequence number and op
+fn parse_op_log_entry(entry: &[u8]) -> Option<(u64, Vec<u8>)> {
+    let separator = entry.iter().position(|&byte| byte == b':')?;
+    let sequence = std::str::from_utf8(&entry[..separator])
+        .ok()?
+        .parse()
+        .ok()?;
+    Some((sequence, entry[separator + 1..].to_vec()))
+}
+
 #[derive(Clone)]
 pub struct RedisStateManager {
     pool: RedisPool,
@@ -184,6 +269,84 @@ impl RedisStateManager {
         Ok(())
     }
 
+    /// Register a connection unless its room already holds `max_connections` live
+    /// ones; 0 means no limit. Returns whether the connection was admitted and how
+    /// many connections the room holds without it.
+    pub async fn try_register_connection(
+        &self,
+        connection_info: &ConnectionInfo,
+        max_connections: usize,
+    ) -> Result<(bool, usize), Box<dyn std::error::Error + Send + Sync>> {
+        let room_uuid = connection_info.room_id;
+        if max_connections > 0 {
+            self.prune_dropped_connections(room_uuid).await?;
+        }
+
+        self.register_connection(connection_info).await?;
+        if max_connections == 0 {
+            return Ok((true, 0));
+        }
+
+        // Counting after adding ourselves means two sockets racing for the last
+        // slot can both be refused, but never both admitted
+        let mut conn = self.pool.get().await?;
+        let room_key = format!("{}{}:connections", ROOM_PREFIX, room_uuid);
+        let count: usize = conn.scard(&room_key).await?;
+        drop(conn);
+
+        if !is_within_connection_cap(count, max_connections) {
+            self.unregister_connection(&connection_info.connection_id)
+                .await?;
+            debug!(
+                "Refused connection {} to room {} ({} of {} connections in use)",
+                connection_info.connection_id,
+                room_uuid,
+                count - 1,
+                max_connections
+            );
+            return Ok((false, count - 1));
+        }
+
+        Ok((true, count))
+    }
+
+    /// Drop members of the room's connection set whose registration has expired,
+    /// i.e. sockets that went away without running their cleanup
+    async fn prune_dropped_connections(
+        &self,
+        room_uuid: Uuid,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let room_key = format!("{}{}:connections", ROOM_PREFIX, room_uuid);
+
+        let connection_ids = conn.smembers::<_, Vec<String>>(&room_key).await?;
+        if connection_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut pipe = redis::pipe();
+        for connection_id in &connection_ids {
+            pipe.exists(format!("{}{}", CONNECTION_PREFIX, connection_id));
+        }
+        let alive: Vec<bool> = pipe.query_async(&mut *conn).await?;
+
+        let dropped: Vec<&String> = connection_ids
+            .iter()
+            .zip(alive)
+            .filter(|(_, alive)| !alive)
+            .map(|(connection_id, _)| connection_id)
+            .collect();
+        if !dropped.is_empty() {
+            conn.srem::<_, _, ()>(&room_key, &dropped).await?;
+            debug!(
+                "Pruned {} dropped connections from room {}",
+                dropped.len(),
+                room_uuid
+            );
+        }
+        Ok(dropped.len())
+    }
+
     pub async fn heartbeat_connection(
         &self,
         connection_id: &str,
@@ -201,6 +364,11 @@ impl RedisStateManager {
             conn.set::<_, _, ()>(&key, &serialized).await?;
             conn.expire::<_, ()>(&key, CONNECTION_TTL as i64).await?;
 
+            // Keep the room's connection set alive as long as any connection in it is
+            let room_key = format!("{}{}:connections", ROOM_PREFIX, connection_info.room_id);
+            conn.expire::<_, ()>(&room_key, CONNECTION_TTL as i64)
+                .await?;
+
             debug!("Updated heartbeat for connection {}", connection_id);
             Ok(true)
         } else {
@@ -209,6 +377,34 @@ impl RedisStateManager {
         }
     }
 
+    pub async fn record_pong(
+        &self,
+        connection_id: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let key = format!("{}{}", CONNECTION_PREFIX, connection_id);
+
+        if let Some(info_str) = conn.get::<_, Option<String>>(&key).await? {
+            let mut connection_info: ConnectionInfo = serde_json::from_str(&info_str)?;
+            connection_info.last_pong = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+            // Keep the remaining TTL; only heartbeats extend the registration
+            let serialized = serde_json::to_string(&connection_info)?;
+            conn.set_options::<_, _, ()>(
+                &key,
+                &serialized,
+                redis::SetOptions::default().with_expiration(redis::SetExpiry::KEEPTTL),
+            )
+            .await?;
+
+            debug!("Recorded pong for connection {}", connection_id);
+            Ok(true)
+        } else {
+            debug!("Connection {} not found for pong", connection_id);
+            Ok(false)
+        }
+    }
+
     pub async fn get_connection_info(
         &self,
         connection_id: &str,
@@ -267,6 +463,173 @@ impl RedisStateManager {
         Ok(connections)
     }
 
+    /// Count live connections across every server instance. Uses SCAN so a
+    /// large keyspace doesn't block Redis the way KEYS would.
+    pub async fn count_connections(&self) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let pattern = format!("{}*", CONNECTION_PREFIX);
+
+        let mut cursor: u64 = 0;
+        let mut count: u64 = 0;
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(1000)
+                .query_async(&mut *conn)
+                .await?;
+            count += keys.len() as u64;
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        Ok(count)
+    }
+
+    // Kicked Participants
+    pub async fn mark_kicked(
+        &self,
+        room_uuid: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let key = format!("{}{}:{}", KICKED_PREFIX, room_uuid, user_id);
+
+        conn.set_ex::<_, _, ()>(&key, 1u8, KICKED_TTL).await?;
+        debug!("Marked user {} as kicked from room {}", user_id, room_uuid);
+        Ok(())
+    }
+
+    pub async fn is_kicked(
+        &self,
+        room_uuid: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let key = format!("{}{}:{}", KICKED_PREFIX, room_uuid, user_id);
+
+        let kicked: bool = conn.exists(&key).await?;
+        Ok(kicked)
+    }
+
+    // Op Sequencing
+    /// Give a broadcast op the room's next sequence number and keep it in the op log
+    pub async fn append_op(
+        &self,
+        room_uuid: Uuid,
+        op: &[u8],
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let sequence_key = format!("{}{}", SEQUENCE_PREFIX, room_uuid);
+        let log_key = format!("{}{}", OP_LOG_PREFIX, room_uuid);
+
+        let sequence: u64 = redis::Script::new(APPEND_OP_SCRIPT)
+            .key(&sequence_key)
+            .key(&log_key)
+            .arg(op)
+            .arg(OP_LOG_LENGTH)
+            .arg(OP_LOG_TTL)
+            .invoke_async(&mut *conn)
+            .await?;
+        Ok(sequence)
+    }
+
+    /// Work out what a client needs to be sent to catch up with the room.
+    /// `resume_from` is the last sequence number the client saw, if it is reconnecting.
+    pub async fn plan_resync(
+        &self,
+        room_uuid: Uuid,
+        resume_from: Option<u64>,
+    ) -> Result<ResyncPlan, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let sequence_key = format!("{}{}", SEQUENCE_PREFIX, room_uuid);
+        let log_key = format!("{}{}", OP_LOG_PREFIX, room_uuid);
+
+        let Some(resume_from) = resume_from else {
+            let sequence: Option<u64> = conn.get(&sequence_key).await?;
+            return Ok(ResyncPlan::Reload {
+                sequence: sequence.unwrap_or(0),
+            });
+        };
+
+        let (sequence, oldest, entries): (Option<u64>, Vec<Vec<u8>>, Vec<Vec<u8>>) = redis::pipe()
+            .atomic()
+            .get(&sequence_key)
+            .zrange(&log_key, 0, 0)
+            .zrangebyscore(&log_key, format!("({}", resume_from), "+inf")
+            .query_async(&mut *conn)
+            .await?;
+
+        let oldest = oldest
+            .first()
+            .and_then(|entry| parse_op_log_entry(entry))
+            .map(|(sequence, _)| sequence);
+        let ops = entries
+            .iter()
+            .filter_map(|entry| parse_op_log_entry(entry))
+            .collect();
+
+        Ok(ResyncPlan::from_log(
+            resume_from,
+            sequence.unwrap_or(0),
+            oldest,
+            ops,
+        ))
+    }
+
+    // Spectator Tracking
+    pub async fn add_spectator(
+        &self,
+        room_uuid: Uuid,
+        connection_id: &str,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let room_key = format!("{}{}:spectators", ROOM_PREFIX, room_uuid);
+
+        conn.sadd::<_, _, ()>(&room_key, connection_id).await?;
+        conn.expire::<_, ()>(&room_key, SPECTATOR_TTL as i64)
+            .await?;
+        let count: usize = conn.scard(&room_key).await?;
+
+        debug!(
+            "Added spectator {} to room {} ({} watching)",
+            connection_id, room_uuid, count
+        );
+        Ok(count)
+    }
+
+    pub async fn remove_spectator(
+        &self,
+        room_uuid: Uuid,
+        connection_id: &str,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let room_key = format!("{}{}:spectators", ROOM_PREFIX, room_uuid);
+
+        conn.srem::<_, _, ()>(&room_key, connection_id).await?;
+        let count: usize = conn.scard(&room_key).await?;
+
+        debug!(
+            "Removed spectator {} from room {} ({} watching)",
+            connection_id, room_uuid, count
+        );
+        Ok(count)
+    }
+
+    pub async fn get_spectator_count(
+        &self,
+        room_uuid: Uuid,
+    ) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.pool.get().await?;
+        let room_key = format!("{}{}:spectators", ROOM_PREFIX, room_uuid);
+
+        let count: usize = conn.scard(&room_key).await?;
+        Ok(count)
+    }
+
     // Pub/Sub for message broadcasting
     pub async fn publish_message(
         &self,
@@ -339,3 +702,60 @@ impl RedisStateManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connection_past_the_cap_is_refused() {
+        let max_collaborators = 3;
+
+        for count in 1..=max_collaborators {
+            assert!(is_within_connection_cap(count, max_collaborators));
+        }
+        assert!(!is_within_connection_cap(
+            max_collaborators + 1,
+            max_collaborators
+        ));
+        // 0 disables the cap
+        assert!(is_within_connection_cap(100, 0));
+    }
+
+    /// The ops still in the log after `resume_from`, as `plan_resync` reads them back
+    fn ops_after(log: &[&[u8]], resume_from: u64) -> Vec<(u64, Vec<u8>)> {
+        log.iter()
+            .filter_map(|entry| parse_op_log_entry(entry))
+            .filter(|(sequence, _)| *sequence > resume_from)
+            .collect()
+    }
+
+    #[test]
+    fn rejoin_replays_exactly_the_missed_ops() {
+        let log: &[&[u8]] = &[b"3:draw", b"4:fill", b"5:undo", b"6:draw:2"];
+
+        match ResyncPlan::from_log(4, 6, Some(3), ops_after(log, 4)) {
+            ResyncPlan::Replay { sequence, ops } => {
+                assert_eq!(sequence, 6);
+                assert_eq!(ops, vec![(5, b"undo".to_vec()), (6, b"draw:2".to_vec())]);
+            }
+            plan => panic!("expected a replay, got {:?}", plan),
+        }
+    }
+
+    #[test]
+    fn rejoin_past_the_log_reloads_the_history() {
+        let log: &[&[u8]] = &[b"3:draw", b"4:fill"];
+
+        // Ops 2 and earlier have been trimmed from the log
+        assert!(matches!(
+            ResyncPlan::from_log(1, 4, Some(3), ops_after(log, 1)),
+            ResyncPlan::Reload { sequence: 4 }
+        ));
+        // A sequence from before the log expired
+        assert!(matches!(
+            ResyncPlan::from_log(9, 4, Some(3), Vec::new()),
+            ResyncPlan::Reload { sequence: 4 }
+        ));
+    }
+}
@@ -9,6 +9,9 @@ pub struct CreateSessionRequest {
     pub is_public: bool,
     pub max_participants: i32,
     pub community_id: Option<String>,
+    /// Record the session's ops so the saved post gets a replay
+    #[serde(default)]
+    pub record_replay: bool,
 }
 
 #[derive(Serialize)]
@@ -57,4 +60,32 @@ pub struct CollaborationMeta {
     pub max_users: i32,
     #[serde(rename = "currentUserCount")]
     pub current_user_count: i64,
+    #[serde(rename = "spectatorCount")]
+    pub spectator_count: usize,
+}
+
+#[derive(Serialize)]
+pub struct SessionHealth {
+    pub participant_count: i64,
+    pub connection_count: usize,
+    pub spectator_count: usize,
+    /// Connections that have left at least one ping unanswered
+    pub stale_connection_count: usize,
+    /// Time since the least responsive connection last answered a ping
+    pub max_pong_age_seconds: Option<u64>,
+    pub last_activity_age_seconds: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct WebSocketParams {
+    pub mode: Option<String>,
+    /// Last op sequence number a reconnecting client saw
+    pub resume_from: Option<u64>,
+}
+
+impl WebSocketParams {
+    /// Spectators receive the session state but can't draw or chat
+    pub fn is_spectator(&self) -> bool {
+        self.mode.as_deref() == Some("spectator")
+    }
 }
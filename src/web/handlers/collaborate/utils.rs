@@ -1,8 +1,5 @@
+use crate::locale::negotiate_accept_language;
 use crate::models::user::Language;
-use fluent_langneg::{
-    convert_vec_str_to_langids_lossy, negotiate_languages, parse_accepted_languages,
-    NegotiationStrategy,
-};
 use uuid::Uuid;
 
 pub fn bytes_to_uuid(bytes: &[u8]) -> Result<Uuid, &'static str> {
@@ -37,29 +34,13 @@ pub fn get_preferred_locale(
     accept_language: &axum::http::HeaderValue,
 ) -> String {
     match user_preferred_language {
-        Some(lang) => match lang {
-            Language::Ko => "ko".to_string(),
-            Language::Ja => "ja".to_string(),
-            Language::En => "en".to_string(),
-            Language::Zh => "zh".to_string(),
-        },
+        Some(lang) => lang.code().to_string(),
         None => {
             if let Ok(accept_language_str) = accept_language.to_str() {
-                let requested = parse_accepted_languages(accept_language_str);
-                let available = convert_vec_str_to_langids_lossy(["ko", "ja", "en", "zh"]);
-                let default = "en".parse().expect("Failed to parse default langid.");
-
-                let supported = negotiate_languages(
-                    &requested,
-                    &available,
-                    Some(&default),
-                    NegotiationStrategy::Filtering,
-                );
-
-                supported
+                negotiate_accept_language(accept_language_str)
                     .first()
-                    .map(|lang| lang.language.as_str().to_string())
-                    .unwrap_or_else(|| "en".to_string())
+                    .map_or("en", Language::code)
+                    .to_string()
             } else {
                 "en".to_string()
             }
@@ -1,15 +1,91 @@
 use crate::app_error::AppError;
 use crate::models::user::AuthSession;
+use crate::web::correlation::CorrelationId;
 use crate::web::state::AppState;
-use axum::extract::{ws::Message, ws::WebSocket, Path, State, WebSocketUpgrade};
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use axum::extract::{Path, Query, State, WebSocketUpgrade};
 use axum::response::Response;
+use axum::Extension;
 use futures_util::stream::SplitSink;
 use futures_util::{SinkExt, StreamExt};
-use tokio::sync::mpsc;
-use tracing::{debug, error, info, warn};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 use uuid::Uuid;
 
-use super::{db, messages, redis_messages, utils};
+use super::redis_state::ResyncPlan;
+use super::types::WebSocketParams;
+use super::{db, messages, recording, redis_messages, utils};
+
+// Must stay well below the connection TTL in Redis
+const HEARTBEAT_INTERVAL_SECONDS: u64 = 10;
+
+// Clients are pinged this often, and dropped once they leave this many pings
+// unanswered; mobile networks tend to lose idle sockets without closing them
+pub const PING_INTERVAL_SECONDS: u64 = 30;
+pub const MAX_MISSED_PONGS: u64 = 3;
+
+// Application close code telling the client the session had no room for it
+const SESSION_FULL_CLOSE_CODE: u16 = 4001;
+// Application close code telling the client the owner removed it from the session
+const KICKED_CLOSE_CODE: u16 = 4002;
+const KICK_CLOSE_GRACE_SECONDS: u64 = 5;
+
+// Presence updates are relayed at most this often per connection (20 per second)
+const PRESENCE_INTERVAL_MILLIS: u64 = 50;
+
+enum JoinRejection {
+    /// The session is at capacity; the counts are reported to the client
+    Full {
+        current: usize,
+        max: usize,
+    },
+    /// The owner removed this user from the session a moment ago
+    Kicked,
+    Failed,
+}
+
+/// Coalesces a connection's presence updates so only the latest one goes out
+/// once per interval
+struct PresenceThrottle {
+    last_sent: Option<tokio::time::Instant>,
+    pending: Option<Message>,
+}
+
+impl PresenceThrottle {
+    fn new() -> Self {
+        Self {
+            last_sent: None,
+            pending: None,
+        }
+    }
+
+    fn next_send_at(&self) -> tokio::time::Instant {
+        match self.last_sent {
+            Some(last_sent) => {
+                last_sent + std::time::Duration::from_millis(PRESENCE_INTERVAL_MILLIS)
+            }
+            None => tokio::time::Instant::now(),
+        }
+    }
+
+    /// Returns the update if it may go out now, otherwise holds on to it
+    fn offer(&mut self, msg: Message) -> Option<Message> {
+        if tokio::time::Instant::now() >= self.next_send_at() {
+            self.pending = None;
+            self.last_sent = Some(tokio::time::Instant::now());
+            Some(msg)
+        } else {
+            self.pending = Some(msg);
+            None
+        }
+    }
+
+    fn take_pending(&mut self) -> Option<Message> {
+        let msg = self.pending.take()?;
+        self.last_sent = Some(tokio::time::Instant::now());
+        Some(msg)
+    }
+}
 
 struct SessionContext<'a> {
     connection_id: &'a str,
@@ -17,21 +93,40 @@ struct SessionContext<'a> {
     user_id: Uuid,
     room_uuid: Uuid,
     is_owner: bool,
+    is_recording: bool,
     db: &'a sqlx::Pool<sqlx::Postgres>,
     state: &'a AppState,
 }
 
 pub async fn websocket_collaborate_handler(
     Path(room_uuid): Path<Uuid>,
+    Query(params): Query<WebSocketParams>,
     auth_session: AuthSession,
+    Extension(CorrelationId(correlation_id)): Extension<CorrelationId>,
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
 ) -> Result<Response, AppError> {
     let user = auth_session
         .user
         .ok_or_else(|| anyhow::anyhow!("Authentication required"))?;
+    // The socket outlives the upgrade request, so it gets a span of its own
+    // carrying the request's correlation ID
+    let span = info_span!(
+        "collaboration_socket",
+        correlation_id = %correlation_id,
+        room_id = %room_uuid,
+    );
     Ok(ws.on_upgrade(move |socket| {
-        handle_socket(socket, room_uuid, state, user.id, user.login_name)
+        handle_socket(
+            socket,
+            room_uuid,
+            state,
+            user.id,
+            user.login_name,
+            params,
+            correlation_id,
+        )
+        .instrument(span)
     }))
 }
 
@@ -41,7 +136,11 @@ pub async fn handle_socket(
     state: AppState,
     user_id: Uuid,
     user_login_name: String,
+    params: WebSocketParams,
+    correlation_id: Uuid,
 ) {
+    let is_spectator = params.is_spectator();
+    let resume_from = params.resume_from;
     let (mut sender, mut receiver) = socket.split();
 
     let connection_id = Uuid::new_v4().to_string();
@@ -53,28 +152,81 @@ pub async fn handle_socket(
 
     let db = &state.db_pool;
 
-    let is_owner = match setup_connection(
-        db,
+    let setup_result = if is_spectator {
+        // Spectators don't take a participant slot, so they can't own the session
+        setup_spectator(db, room_uuid, &connection_id, &state)
+            .await
+            .map(|()| (false, false))
+            .map_err(|()| JoinRejection::Failed)
+    } else {
+        setup_connection(
+            db,
+            room_uuid,
+            user_id,
+            &user_login_name,
+            &connection_id,
+            correlation_id,
+            &state,
+        )
+        .await
+    };
+    let (is_owner, is_recording) = match setup_result {
+        Ok(status) => status,
+        Err(JoinRejection::Full { current, max }) => {
+            info!(
+                "Closing connection {}: session {} is full ({}/{})",
+                connection_id, room_uuid, current, max
+            );
+            let reason = serde_json::json!({
+                "currentUserCount": current,
+                "maxUsers": max,
+            })
+            .to_string();
+            let _ = sender
+                .send(Message::Close(Some(CloseFrame {
+                    code: SESSION_FULL_CLOSE_CODE,
+                    reason: reason.into(),
+                })))
+                .await;
+            return;
+        }
+        Err(JoinRejection::Kicked) => {
+            info!(
+                "Closing connection {}: user {} was kicked from session {}",
+                connection_id, user_login_name, room_uuid
+            );
+            let _ = sender
+                .send(Message::Close(Some(CloseFrame {
+                    code: KICKED_CLOSE_CODE,
+                    reason: "kicked".into(),
+                })))
+                .await;
+            return;
+        }
+        Err(JoinRejection::Failed) => return,
+    };
+
+    catch_up_new_connection(
+        &state,
         room_uuid,
         user_id,
-        &user_login_name,
+        resume_from,
+        &mut sender,
         &connection_id,
-        &state,
     )
-    .await
-    {
-        Ok(owner_status) => owner_status,
-        Err(_) => return,
-    };
-
-    // Send history to new connection
-    send_history_to_new_connection(&state, room_uuid, &mut sender, &connection_id).await;
+    .await;
 
     info!(
         "User {} joined session {} as {}",
         user_login_name,
         room_uuid,
-        if is_owner { "owner" } else { "participant" }
+        if is_spectator {
+            "spectator"
+        } else if is_owner {
+            "owner"
+        } else {
+            "participant"
+        }
     );
 
     // Create Redis Pub/Sub subscriber for this connection
@@ -84,6 +236,9 @@ pub async fn handle_socket(
     // Create separate Redis subscriber task that will handle incoming Redis messages
     // and send them through a channel to the main WebSocket sending loop
     let (redis_tx, mut redis_rx) = mpsc::unbounded_channel::<Message>();
+    let ping_tx = redis_tx.clone();
+    let (kicked_tx, kicked_rx) = oneshot::channel::<()>();
+    let mut kicked_tx = (!is_spectator).then_some(kicked_tx);
 
     let redis_task = tokio::spawn(async move {
         let redis_url = state_clone.config.redis_url.clone();
@@ -99,6 +254,21 @@ pub async fn handle_socket(
                             let payload: String = msg.get_payload().unwrap_or_default();
                             match serde_json::from_str::<super::redis_state::RoomMessage>(&payload)
                             {
+                                Ok(room_msg) if room_msg.message_type == "kick" => {
+                                    // Kicks only concern the participant they name and are
+                                    // never forwarded to clients
+                                    if room_msg.user_id != user_id {
+                                        continue;
+                                    }
+                                    if let Some(kicked_tx) = kicked_tx.take() {
+                                        let _ = redis_tx.send(Message::Close(Some(CloseFrame {
+                                            code: KICKED_CLOSE_CODE,
+                                            reason: "kicked".into(),
+                                        })));
+                                        let _ = kicked_tx.send(());
+                                        break;
+                                    }
+                                }
                                 Ok(room_msg) => {
                                     // Allow chat messages to echo back to sender for confirmation
                                     // Don't echo other message types back to sender (avoid duplicate drawing commands)
@@ -143,7 +313,7 @@ pub async fn handle_socket(
     });
 
     // Handle outgoing messages (from Redis) in a separate task
-    let outgoing_task = tokio::spawn(async move {
+    let mut outgoing_task = tokio::spawn(async move {
         while let Some(msg) = redis_rx.recv().await {
             if sender.send(msg).await.is_err() {
                 debug!("WebSocket send failed");
@@ -152,29 +322,115 @@ pub async fn handle_socket(
         }
     });
 
-    handle_incoming_messages(
-        &mut receiver,
-        SessionContext {
-            connection_id: &connection_id,
-            user_login_name: &user_login_name,
-            user_id,
-            room_uuid,
-            is_owner,
-            db,
-            state: &state,
-        },
-    )
-    .await;
+    // Keep the connection's Redis registration fresh so the cleanup task can tell
+    // live connections from ones left behind by a restart
+    let heartbeat_task = (!is_spectator).then(|| {
+        let state = state.clone();
+        let connection_id = connection_id.clone();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(HEARTBEAT_INTERVAL_SECONDS));
+            loop {
+                interval.tick().await;
+                if let Err(e) = state.redis_state.heartbeat_connection(&connection_id).await {
+                    error!(
+                        "Failed to update heartbeat for connection {}: {}",
+                        connection_id, e
+                    );
+                }
+            }
+        })
+    });
 
-    cleanup_connection(
-        &connection_id,
-        &user_login_name,
-        user_id,
-        room_uuid,
-        db,
-        &state,
-    )
-    .await;
+    // Ping the client and give up on it once it stops answering; the pongs are
+    // recorded by the incoming message loop
+    let (unresponsive_tx, unresponsive_rx) = oneshot::channel::<()>();
+    let ping_task = (!is_spectator).then(|| {
+        let state = state.clone();
+        let connection_id = connection_id.clone();
+        tokio::spawn(async move {
+            let period = std::time::Duration::from_secs(PING_INTERVAL_SECONDS);
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                match state.redis_state.get_connection_info(&connection_id).await {
+                    Ok(Some(info)) => {
+                        let now = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .expect("System time is before UNIX_EPOCH")
+                            .as_secs();
+                        if now.saturating_sub(info.last_pong)
+                            > PING_INTERVAL_SECONDS * MAX_MISSED_PONGS
+                        {
+                            let _ = unresponsive_tx.send(());
+                            break;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        error!(
+                            "Failed to check pongs for connection {}: {}",
+                            connection_id, e
+                        );
+                    }
+                }
+                if ping_tx.send(Message::Ping(Vec::new())).is_err() {
+                    break;
+                }
+            }
+        })
+    });
+
+    if is_spectator {
+        ignore_spectator_messages(&mut receiver, &connection_id).await;
+        cleanup_spectator(&connection_id, room_uuid, &state).await;
+    } else {
+        let kicked = handle_incoming_messages(
+            &mut receiver,
+            SessionContext {
+                connection_id: &connection_id,
+                user_login_name: &user_login_name,
+                user_id,
+                room_uuid,
+                is_owner,
+                is_recording,
+                db,
+                state: &state,
+            },
+            unresponsive_rx,
+            kicked_rx,
+        )
+        .await;
+
+        // Stop heartbeats first so they can't re-register the connection
+        if let Some(heartbeat_task) = heartbeat_task {
+            heartbeat_task.abort();
+        }
+        if let Some(ping_task) = ping_task {
+            ping_task.abort();
+        }
+
+        if kicked {
+            // The owner's kick already removed this connection; just let the
+            // close frame go out before tearing the socket down
+            let _ = tokio::time::timeout(
+                std::time::Duration::from_secs(KICK_CLOSE_GRACE_SECONDS),
+                &mut outgoing_task,
+            )
+            .await;
+        } else {
+            cleanup_connection(
+                &connection_id,
+                &user_login_name,
+                user_id,
+                room_uuid,
+                db,
+                &state,
+            )
+            .await;
+        }
+    }
 
     redis_task.abort();
     outgoing_task.abort();
@@ -186,20 +442,61 @@ async fn setup_connection(
     user_id: Uuid,
     user_login_name: &str,
     connection_id: &str,
+    correlation_id: Uuid,
     state: &AppState,
-) -> Result<bool, ()> {
+) -> Result<(bool, bool), JoinRejection> {
     let session_info = match db::get_session_info(db, room_uuid).await {
         Ok(Some(info)) => info,
         Ok(None) => {
             error!("Session {} not found", room_uuid);
-            return Err(());
+            return Err(JoinRejection::Failed);
         }
         Err(e) => {
             error!("Failed to get session info: {}", e);
-            return Err(());
+            return Err(JoinRejection::Failed);
         }
     };
 
+    if session_info.owner_id != user_id {
+        match state.redis_state.is_kicked(room_uuid, user_id).await {
+            Ok(true) => {
+                info!(
+                    "User {} rejected from session {} (recently kicked)",
+                    user_login_name, room_uuid
+                );
+                return Err(JoinRejection::Kicked);
+            }
+            Ok(false) => {}
+            Err(e) => error!("Failed to check kicked status in Redis: {}", e),
+        }
+    }
+
+    // Take a connection slot before touching the participant table, so a socket
+    // refused here never shows up as an active participant
+    let max_collaborators = state.config.max_collaborators;
+    if !setup_connection_atomically(
+        state,
+        room_uuid,
+        user_id,
+        connection_id,
+        correlation_id,
+        user_login_name,
+        max_collaborators,
+    )
+    .await
+    {
+        let current = state
+            .redis_state
+            .get_room_connections(room_uuid)
+            .await
+            .map(|connections| connections.len())
+            .unwrap_or(max_collaborators);
+        return Err(JoinRejection::Full {
+            current,
+            max: max_collaborators,
+        });
+    }
+
     // Use atomic capacity check and participant tracking to prevent race conditions
     let join_success = match db::track_participant_with_capacity_check(
         db,
@@ -212,7 +509,13 @@ async fn setup_connection(
         Ok(success) => success,
         Err(e) => {
             error!("Failed to track participant: {}", e);
-            false
+            if let Err(e) = state.redis_state.unregister_connection(connection_id).await {
+                error!(
+                    "Failed to unregister connection {} from Redis: {}",
+                    connection_id, e
+                );
+            }
+            return Err(JoinRejection::Failed);
         }
     };
 
@@ -221,15 +524,59 @@ async fn setup_connection(
             "User {} rejected from session {} (capacity check failed)",
             user_login_name, room_uuid
         );
-        return Err(());
+        if let Err(e) = state.redis_state.unregister_connection(connection_id).await {
+            error!(
+                "Failed to unregister connection {} from Redis: {}",
+                connection_id, e
+            );
+        }
+        let max = session_info.max_participants.max(0) as usize;
+        let current = db::get_active_user_count(db, room_uuid)
+            .await
+            .map(|count| count.max(0) as usize)
+            .unwrap_or(max);
+        return Err(JoinRejection::Full { current, max });
     }
 
     db::update_session_activity(state, room_uuid).await;
 
-    // Atomically handle all connection management
-    setup_connection_atomically(state, room_uuid, user_id, connection_id, user_login_name).await;
+    Ok((session_info.owner_id == user_id, session_info.record_replay))
+}
+
+async fn setup_spectator(
+    db: &sqlx::Pool<sqlx::Postgres>,
+    room_uuid: Uuid,
+    connection_id: &str,
+    state: &AppState,
+) -> Result<(), ()> {
+    match db::get_session_info(db, room_uuid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            error!("Session {} not found", room_uuid);
+            return Err(());
+        }
+        Err(e) => {
+            error!("Failed to get session info: {}", e);
+            return Err(());
+        }
+    }
+
+    // Spectators are kept out of the participant table and the connection
+    // registry, so they never count against capacity or affect room cleanup
+    match state
+        .redis_state
+        .add_spectator(room_uuid, connection_id)
+        .await
+    {
+        Ok(spectator_count) => {
+            messages::send_spectator_count_message(room_uuid, spectator_count, state).await;
+        }
+        Err(e) => {
+            error!("Failed to register spectator in Redis: {}", e);
+        }
+    }
 
-    Ok(session_info.owner_id == user_id)
+    Ok(())
 }
 
 async fn setup_connection_atomically(
@@ -237,8 +584,10 @@ async fn setup_connection_atomically(
     room_uuid: Uuid,
     user_id: Uuid,
     connection_id: &str,
+    correlation_id: Uuid,
     user_login_name: &str,
-) {
+    max_connections: usize,
+) -> bool {
     // With pure Redis Pub/Sub, we don't need local room tracking
     // Each connection is independent with its own Redis subscriber
 
@@ -250,6 +599,7 @@ async fn setup_connection_atomically(
     // Register connection in Redis
     let connection_info = super::redis_state::ConnectionInfo {
         connection_id: connection_id.to_string(),
+        correlation_id: Some(correlation_id),
         user_id,
         room_id: room_uuid,
         user_login_name: user_login_name.to_string(),
@@ -262,14 +612,21 @@ async fn setup_connection_atomically(
             .duration_since(std::time::UNIX_EPOCH)
             .expect("System time is before UNIX_EPOCH")
             .as_secs(),
+        last_pong: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("System time is before UNIX_EPOCH")
+            .as_secs(),
     };
 
-    if let Err(e) = state
+    match state
         .redis_state
-        .register_connection(&connection_info)
+        .try_register_connection(&connection_info, max_connections)
         .await
     {
-        error!("Failed to register connection in Redis: {}", e);
+        Ok((true, _)) => {}
+        Ok((false, _)) => return false,
+        // Let the socket in rather than lock everyone out while Redis is unavailable
+        Err(e) => error!("Failed to register connection in Redis: {}", e),
     }
 
     // Note: Previously added user to Redis room presence, but now using database
@@ -279,6 +636,70 @@ async fn setup_connection_atomically(
         "Completed Redis Pub/Sub setup for connection {} in room {}",
         connection_id, room_uuid
     );
+    true
+}
+
+/// Bring a new connection up to date: a client resuming from a sequence number
+/// still in the op log gets just the ops it missed, anyone else the full history
+async fn catch_up_new_connection(
+    state: &AppState,
+    room_uuid: Uuid,
+    user_id: Uuid,
+    resume_from: Option<u64>,
+    sender: &mut SplitSink<WebSocket, Message>,
+    connection_id: &str,
+) {
+    let plan = match state.redis_state.plan_resync(room_uuid, resume_from).await {
+        Ok(plan) => plan,
+        Err(e) => {
+            error!(
+                "Failed to plan resync for connection {} in room {}: {}",
+                connection_id, room_uuid, e
+            );
+            ResyncPlan::Reload { sequence: 0 }
+        }
+    };
+
+    match plan {
+        ResyncPlan::Replay { sequence, ops } => {
+            info!(
+                "Resuming connection {} in room {} from sequence {}: replaying {} missed ops",
+                connection_id,
+                room_uuid,
+                resume_from.unwrap_or_default(),
+                ops.len()
+            );
+            // The client's own ops are already on its canvas
+            let missed_ops = ops
+                .into_iter()
+                .filter(|(_, op)| {
+                    op.get(1..17)
+                        .and_then(|bytes| utils::bytes_to_uuid(bytes).ok())
+                        != Some(user_id)
+                })
+                .map(|(op_sequence, op)| messages::encode_sequenced(op_sequence, &op));
+            for msg in std::iter::once(messages::encode_resync(false, sequence)).chain(missed_ops) {
+                if sender.send(Message::Binary(msg)).await.is_err() {
+                    warn!("Failed to send missed op to connection {}", connection_id);
+                    break;
+                }
+            }
+        }
+        ResyncPlan::Reload { sequence } => {
+            if let Some(resume_from) = resume_from {
+                info!(
+                    "Connection {} in room {} is too far behind at sequence {}, sending full history",
+                    connection_id, room_uuid, resume_from
+                );
+            }
+            let resync = messages::encode_resync(true, sequence);
+            if sender.send(Message::Binary(resync)).await.is_err() {
+                warn!("Failed to send resync to connection {}", connection_id);
+                return;
+            }
+            send_history_to_new_connection(state, room_uuid, sender, connection_id).await;
+        }
+    }
 }
 
 async fn send_history_to_new_connection(
@@ -318,8 +739,39 @@ async fn send_history_to_new_connection(
 async fn handle_incoming_messages(
     receiver: &mut futures_util::stream::SplitStream<WebSocket>,
     ctx: SessionContext<'_>,
-) {
-    while let Some(msg) = receiver.next().await {
+    mut unresponsive: oneshot::Receiver<()>,
+    mut kicked: oneshot::Receiver<()>,
+) -> bool {
+    let mut presence = PresenceThrottle::new();
+
+    loop {
+        let msg = tokio::select! {
+            msg = receiver.next() => msg,
+            _ = tokio::time::sleep_until(presence.next_send_at()), if presence.pending.is_some() => {
+                if let Some(msg) = presence.take_pending() {
+                    messages::broadcast_message(&msg, ctx.room_uuid, ctx.connection_id, ctx.state)
+                        .await;
+                }
+                continue;
+            }
+            _ = &mut unresponsive => {
+                info!(
+                    "Connection {} missed {} pongs, removing it from room {}",
+                    ctx.connection_id, MAX_MISSED_PONGS, ctx.room_uuid
+                );
+                return false;
+            }
+            Ok(()) = &mut kicked => {
+                info!(
+                    "Connection {} was kicked from room {}",
+                    ctx.connection_id, ctx.room_uuid
+                );
+                return true;
+            }
+        };
+        let Some(msg) = msg else {
+            return false;
+        };
         let mut msg = match msg {
             Ok(msg) => msg,
             Err(e) => {
@@ -327,10 +779,20 @@ async fn handle_incoming_messages(
                     "Websocket error for connection {}: {}",
                     ctx.connection_id, e
                 );
-                break;
+                return false;
             }
         };
 
+        if matches!(msg, Message::Pong(_)) {
+            if let Err(e) = ctx.state.redis_state.record_pong(ctx.connection_id).await {
+                error!(
+                    "Failed to record pong for connection {}: {}",
+                    ctx.connection_id, e
+                );
+            }
+            continue;
+        }
+
         if !matches!(msg, Message::Binary(_)) {
             continue;
         }
@@ -339,6 +801,22 @@ async fn handle_incoming_messages(
             if !data.is_empty() {
                 let msg_type = data[0];
 
+                // Presence is relayed live but never stored or recorded
+                if msg_type == messages::MessageType::Presence as u8 {
+                    if let Some(presence_msg) = messages::handle_presence_message(data, ctx.user_id)
+                        .and_then(|presence_msg| presence.offer(presence_msg))
+                    {
+                        messages::broadcast_message(
+                            &presence_msg,
+                            ctx.room_uuid,
+                            ctx.connection_id,
+                            ctx.state,
+                        )
+                        .await;
+                    }
+                    continue;
+                }
+
                 if msg_type < 0x10 {
                     msg = match process_server_message(msg_type, data, &msg, &ctx).await {
                         Some(processed_msg) => processed_msg,
@@ -357,10 +835,47 @@ async fn handle_incoming_messages(
         )
         .await;
 
+        if ctx.is_recording && recording::SessionRecorder::should_record(&msg) {
+            let recorder = recording::SessionRecorder::new(ctx.state.redis_pool.clone());
+            if let Err(e) = recorder.record(ctx.db, ctx.room_uuid, &msg).await {
+                error!("Failed to record message for room {}: {}", ctx.room_uuid, e);
+            }
+        }
+
+        // Stored ops are numbered so reconnecting clients can catch up on them
+        let msg = if messages::should_store_message(&msg) {
+            messages::sequence_op(&msg, ctx.room_uuid, ctx.state).await
+        } else {
+            msg
+        };
+
         messages::broadcast_message(&msg, ctx.room_uuid, ctx.connection_id, ctx.state).await;
     }
 }
 
+async fn ignore_spectator_messages(
+    receiver: &mut futures_util::stream::SplitStream<WebSocket>,
+    connection_id: &str,
+) {
+    // Keep reading so we notice when the spectator disconnects, but never
+    // store or broadcast anything they send
+    while let Some(msg) = receiver.next().await {
+        match msg {
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {
+                debug!(
+                    "Ignoring message from spectator connection {}",
+                    connection_id
+                );
+            }
+            Err(e) => {
+                error!("Websocket error for connection {}: {}", connection_id, e);
+                break;
+            }
+        }
+    }
+}
+
 async fn process_server_message(
     msg_type: u8,
     data: &[u8],
@@ -395,6 +910,23 @@ async fn process_server_message(
             Some(msg.clone())
         }
         0x03 => messages::handle_chat_message(data, ctx.user_id, ctx.user_login_name),
+        0x0B => {
+            messages::handle_kick_message(
+                data,
+                messages::KickContext {
+                    user_id: ctx.user_id,
+                    user_login_name: ctx.user_login_name,
+                    room_uuid: ctx.room_uuid,
+                    is_owner: ctx.is_owner,
+                    db: ctx.db,
+                    state: ctx.state,
+                },
+            )
+            .await;
+
+            // Kicks are handled here and never broadcast
+            None
+        }
         0x07 => {
             messages::handle_end_session_message(
                 data,
@@ -679,3 +1211,26 @@ async fn cleanup_connection(
         );
     }
 }
+
+async fn cleanup_spectator(connection_id: &str, room_uuid: Uuid, state: &AppState) {
+    info!(
+        "Spectator connection {} leaving room {}",
+        connection_id, room_uuid
+    );
+
+    match state
+        .redis_state
+        .remove_spectator(room_uuid, connection_id)
+        .await
+    {
+        Ok(spectator_count) => {
+            messages::send_spectator_count_message(room_uuid, spectator_count, state).await;
+        }
+        Err(e) => {
+            error!(
+                "Failed to unregister spectator {} from Redis: {}",
+                connection_id, e
+            );
+        }
+    }
+}
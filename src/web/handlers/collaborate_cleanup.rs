@@ -7,6 +7,33 @@ use uuid::Uuid;
 
 const CLEANUP_INTERVAL_MINUTES: u64 = 5;
 const INACTIVE_THRESHOLD_MINUTES: u64 = 30;
+// Connections that haven't sent a heartbeat for this long are considered gone
+const STALE_CONNECTION_SECONDS: u64 = 60;
+
+/// What to do with a session nobody is connected to anymore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OrphanPolicy {
+    /// Free the participant slots but leave the session open to rejoin
+    Keep,
+    /// End the session and drop its drawing history
+    Discard,
+}
+
+impl OrphanPolicy {
+    fn from_config(value: &str) -> Self {
+        match value {
+            "keep" => Self::Keep,
+            "discard" => Self::Discard,
+            other => {
+                warn!(
+                    "Unknown collaborate_orphan_policy {:?}, falling back to \"keep\"",
+                    other
+                );
+                Self::Keep
+            }
+        }
+    }
+}
 
 pub async fn cleanup_collaborative_sessions(state: AppState) {
     let cleanup_interval = Duration::from_secs(CLEANUP_INTERVAL_MINUTES * 60);
@@ -17,6 +44,21 @@ pub async fn cleanup_collaborative_sessions(state: AppState) {
         CLEANUP_INTERVAL_MINUTES, INACTIVE_THRESHOLD_MINUTES
     );
 
+    // Startup reconciliation: connections registered by the previous process stop sending
+    // heartbeats, so once they've had time to go stale, reap the sessions they left behind
+    tokio::time::sleep(Duration::from_secs(STALE_CONNECTION_SECONDS)).await;
+    let mut sessions_synced = 0;
+    if let Err(e) = sync_activity_to_database(&state, &state.db_pool, &mut sessions_synced).await {
+        error!("Failed to sync activity to database: {}", e);
+    }
+    match reap_orphaned_sessions(&state).await {
+        Ok(reaped) => info!(
+            "Startup reconciliation reaped {} orphaned collaborative sessions",
+            reaped
+        ),
+        Err(e) => error!("Failed to reap orphaned sessions at startup: {}", e),
+    }
+
     loop {
         tokio::time::sleep(cleanup_interval).await;
 
@@ -55,10 +97,19 @@ pub async fn cleanup_collaborative_sessions(state: AppState) {
         // Step 5: Enforce history limits on active sessions
         enforce_history_limits_for_active_sessions(&state).await;
 
+        // Step 6: Reap sessions nobody is connected to anymore
+        let orphaned_sessions_reaped = match reap_orphaned_sessions(&state).await {
+            Ok(reaped) => reaped,
+            Err(e) => {
+                error!("Failed to reap orphaned sessions: {}", e);
+                0
+            }
+        };
+
         let elapsed = start_time.elapsed();
         debug!(
-            "Cleanup cycle completed in {:?}: {} sessions synced, {} ended sessions cleaned, {} inactive sessions cleaned",
-            elapsed, sessions_synced, ended_sessions_cleaned, inactive_sessions_cleaned
+            "Cleanup cycle completed in {:?}: {} sessions synced, {} ended sessions cleaned, {} inactive sessions cleaned, {} orphaned sessions reaped",
+            elapsed, sessions_synced, ended_sessions_cleaned, inactive_sessions_cleaned, orphaned_sessions_reaped
         );
     }
 }
@@ -510,8 +561,8 @@ async fn cleanup_stale_redis_connections(state: &AppState) {
                         .duration_since(std::time::UNIX_EPOCH)
                         .expect("System time is before UNIX_EPOCH")
                         .as_secs();
-                    // If connection hasn't sent a heartbeat in the last 60 seconds, consider it stale
-                    if now - conn_info.last_heartbeat > 60 {
+                    // If connection hasn't sent a heartbeat recently, consider it stale
+                    if !is_heartbeat_live(conn_info.last_heartbeat, now) {
                         if let Err(e) = state.redis_state.unregister_connection(&conn_id).await {
                             error!(
                                 "Failed to cleanup stale Redis connection {}: {}",
@@ -558,3 +609,214 @@ async fn cleanup_stale_redis_connections(state: &AppState) {
         );
     }
 }
+
+/// Whether a connection whose last heartbeat was at `last_heartbeat` (Unix seconds) is live
+fn is_heartbeat_live(last_heartbeat: u64, now: u64) -> bool {
+    now.saturating_sub(last_heartbeat) <= STALE_CONNECTION_SECONDS
+}
+
+/// Whether a session has been idle past the grace period and may be reaped, provided it has no
+/// live connection. With "keep", a session without active participants has already been reaped.
+fn is_reap_candidate(
+    policy: OrphanPolicy,
+    has_active_participants: bool,
+    last_activity: chrono::NaiveDateTime,
+    grace_cutoff: chrono::NaiveDateTime,
+) -> bool {
+    if policy == OrphanPolicy::Keep && !has_active_participants {
+        return false;
+    }
+    last_activity <= grace_cutoff
+}
+
+/// Whether any connection registered in the room is still sending heartbeats
+async fn has_live_connections(
+    state: &AppState,
+    room_uuid: Uuid,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+
+    for conn_id in state.redis_state.get_room_connections(room_uuid).await? {
+        if let Some(conn_info) = state.redis_state.get_connection_info(&conn_id).await? {
+            if is_heartbeat_live(conn_info.last_heartbeat, now) {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// Reaps open sessions that have had no live connection for longer than the configured
+/// grace period, according to `collaborate_orphan_policy`. Returns how many were reaped.
+async fn reap_orphaned_sessions(
+    state: &AppState,
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let policy = OrphanPolicy::from_config(&state.config.collaborate_orphan_policy);
+    let grace_cutoff = chrono::Utc::now().naive_utc()
+        - chrono::Duration::seconds(state.config.collaborate_orphan_grace_seconds as i64);
+    let db = &state.db_pool;
+
+    let open_sessions = sqlx::query!(
+        r#"
+        SELECT
+            cs.id,
+            cs.last_activity,
+            EXISTS (
+                SELECT 1 FROM collaborative_sessions_participants csp
+                WHERE csp.session_id = cs.id AND csp.is_active = true
+            ) AS "has_active_participants!"
+        FROM collaborative_sessions cs
+        WHERE cs.ended_at IS NULL
+        "#
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut reaped = 0;
+
+    for session in open_sessions {
+        let session_id = session.id;
+
+        // Redis has the freshest activity; the database copy is only synced periodically
+        let last_activity = match state.redis_state.get_room_activity(session_id).await {
+            Ok(Some(timestamp)) => chrono::DateTime::from_timestamp(timestamp as i64, 0)
+                .map(|activity| activity.naive_utc().max(session.last_activity))
+                .unwrap_or(session.last_activity),
+            Ok(None) => session.last_activity,
+            Err(e) => {
+                error!(
+                    "Failed to get activity from Redis for session {}: {}",
+                    session_id, e
+                );
+                continue;
+            }
+        };
+        if !is_reap_candidate(
+            policy,
+            session.has_active_participants,
+            last_activity,
+            grace_cutoff,
+        ) {
+            continue;
+        }
+
+        match has_live_connections(state, session_id).await {
+            Ok(false) => {}
+            Ok(true) => continue,
+            Err(e) => {
+                error!(
+                    "Failed to check live connections for session {}: {}",
+                    session_id, e
+                );
+                continue;
+            }
+        }
+
+        let mut tx = db.begin().await?;
+
+        if policy == OrphanPolicy::Discard {
+            sqlx::query!(
+                "UPDATE collaborative_sessions SET ended_at = NOW() WHERE id = $1",
+                session_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE collaborative_sessions_participants
+            SET is_active = false, left_at = NOW()
+            WHERE session_id = $1 AND is_active = true
+            "#,
+            session_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        if policy == OrphanPolicy::Discard {
+            let redis_store = redis_messages::RedisMessageStore::new(state.redis_pool.clone());
+            if let Err(e) = redis_store.cleanup_room(session_id).await {
+                error!(
+                    "Failed to cleanup Redis message history for session {}: {}",
+                    session_id, e
+                );
+            }
+        }
+
+        // Drops the dead connection registrations along with the rest of the room state;
+        // the message history is kept separately, so "keep" sessions can still be rejoined
+        if let Err(e) = state.redis_state.cleanup_room_state(session_id).await {
+            error!(
+                "Failed to cleanup Redis room state for session {}: {}",
+                session_id, e
+            );
+        }
+
+        info!(
+            "Reaped orphaned session {} ({:?}, last activity {})",
+            session_id, policy, last_activity
+        );
+        reaped += 1;
+    }
+
+    Ok(reaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_left_behind_by_a_restart_is_reaped() {
+        let restarted_at = chrono::DateTime::from_timestamp(1_800_000_000, 0)
+            .unwrap()
+            .naive_utc();
+        let last_activity = restarted_at - chrono::Duration::minutes(10);
+        let grace_cutoff = restarted_at - chrono::Duration::seconds(120);
+        // The startup pass runs once the previous process's connections have gone stale
+        let last_heartbeat = restarted_at.and_utc().timestamp() as u64;
+        let startup_pass = last_heartbeat + STALE_CONNECTION_SECONDS + 1;
+
+        assert!(is_reap_candidate(
+            OrphanPolicy::Keep,
+            true,
+            last_activity,
+            grace_cutoff
+        ));
+        assert!(!is_heartbeat_live(last_heartbeat, startup_pass));
+    }
+
+    #[test]
+    fn recent_or_already_freed_sessions_are_left_alone() {
+        let now = chrono::DateTime::from_timestamp(1_800_000_000, 0)
+            .unwrap()
+            .naive_utc();
+        let grace_cutoff = now - chrono::Duration::seconds(120);
+
+        assert!(!is_reap_candidate(
+            OrphanPolicy::Keep,
+            true,
+            now - chrono::Duration::seconds(30),
+            grace_cutoff
+        ));
+        assert!(!is_reap_candidate(
+            OrphanPolicy::Keep,
+            false,
+            now - chrono::Duration::hours(1),
+            grace_cutoff
+        ));
+        assert!(is_reap_candidate(
+            OrphanPolicy::Discard,
+            false,
+            now - chrono::Duration::hours(1),
+            grace_cutoff
+        ));
+        assert!(is_heartbeat_live(1_800_000_000, 1_800_000_010));
+    }
+}
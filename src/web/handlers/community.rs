@@ -1,36 +1,78 @@
 use crate::app_error::AppError;
+use crate::common_context_cache::CommonContextCache;
+use crate::community_listing_cache::{CachedCommunityListing, CommunityListingCache};
+use crate::image_optimization::compose_collage;
+use crate::markdown_utils::markdown_summary;
 use crate::models::actor::create_actor_for_community;
 use crate::models::comment::find_latest_comments_in_community;
 use crate::models::community::{
-    accept_invitation, add_community_member, count_public_communities,
-    count_search_public_communities, create_community, create_invitation, find_community_by_id,
-    find_community_by_slug, get_communities_members_count, get_community_members_with_details,
-    get_community_stats, get_invitation_by_id, get_own_communities, get_participating_communities,
-    get_pending_invitations_with_details_for_user,
-    get_pending_invitations_with_invitee_details_for_community, get_public_communities,
-    get_public_communities_paginated, get_user_role_in_community, is_user_member,
-    leave_community, reject_invitation, remove_community_member, search_public_communities,
-    slug_conflicts_with_user, soft_delete_community_with_activity,
-    update_community_with_activity, CommunityDraft, CommunityMemberRole, CommunityVisibility,
+    accept_invitation, add_community_member, apply_default_co_moderators, approve_join_request,
+    cancel_ownership_transfer, clear_community_posting_window, community_accepts_join_requests,
+    community_requires_post_approval, confirm_ownership_transfer, count_public_communities,
+    count_search_public_communities, create_community, create_invitation, create_invite_link,
+    create_join_request, create_ownership_transfer, delete_invite_link, find_closed_posting_window,
+    find_community_archived_at, find_community_by_id, find_community_by_slug,
+    find_community_posting_window, find_invite_link_by_id, find_join_request_by_id,
+    find_pending_ownership_transfer, get_communities_members_count, get_community_analytics,
+    get_community_leaderboard, get_community_members_with_details, get_community_stats,
+    get_invitation_by_id, get_invite_links_for_community, get_own_communities,
+    get_participating_communities, get_pending_invitations_with_details_for_user,
+    get_pending_invitations_with_invitee_details_for_community,
+    get_pending_join_requests_with_requester_details_for_community, get_pinned_posts_for_community,
+    get_public_communities, get_public_communities_paginated, get_user_role_in_community,
+    has_pending_invitation, has_pending_join_request, has_pending_ownership_transfer,
+    is_community_deleted, is_community_slug_deleted, is_user_member, leave_community, pin_post,
+    reject_invitation, reject_join_request, remove_community_member, search_public_communities,
+    set_community_accepts_join_requests, set_community_posting_window,
+    set_community_requires_post_approval, set_member_role, slug_conflicts_with_user,
+    soft_delete_community_with_activity, unarchive_community, unpin_post,
+    update_community_with_activity, use_invite_link, Community, CommunityAnalytics, CommunityDraft,
+    CommunityLeaderboardEntry, CommunityMemberRole, CommunityOwnershipTransfer,
+    CommunityVisibility, LeaderboardPeriod,
+};
+use crate::models::community_category::{
+    find_categories_by_community_ids, find_community_category_by_slug,
+    find_community_ids_in_category, list_community_categories, set_community_categories,
+};
+use crate::models::federated_post::find_federated_posts_by_community_id;
+use crate::models::hashtag::{
+    get_community_default_hashtags, parse_hashtag_input, set_community_default_hashtags,
+};
+use crate::models::image::{find_thumbnails_by_image_filenames, image_url, thumbnail_or_image_url};
+use crate::models::notification::{
+    format_community_invitation_message, format_community_join_request_message,
+    format_post_approval_message, get_user_language_preference,
+};
+use crate::models::post::{
+    count_post_images_by_post_ids, defer_post_until, find_pending_approval_post,
+    find_pending_approval_posts_by_community_id, find_published_posts_by_community_id,
+    find_recent_posts_by_communities, publish_post, return_pending_post_to_drafts,
+    PendingApprovalPost, SerializablePost,
+};
+use crate::models::user::{
+    find_user_avatar_filename, find_user_by_id, find_user_by_login_name, AuthSession, Language,
+    User,
+};
+use crate::redis::RedisPool;
+use crate::web::handlers::home::{LoadMoreQuery, ThumbnailQuery};
+use crate::web::handlers::post::notify_and_federate_published_post;
+use crate::web::handlers::{
+    handler_410, parse_id_with_legacy_support, LinkPreview, ParsedId,
+    LINK_PREVIEW_DESCRIPTION_LENGTH,
 };
-use crate::models::notification::{format_community_invitation_message, get_user_language_preference};
-use crate::models::post::{find_published_posts_by_community_id, find_recent_posts_by_communities};
-use crate::models::user::{find_user_by_login_name, AuthSession};
-use crate::web::handlers::home::LoadMoreQuery;
-use crate::web::handlers::{parse_id_with_legacy_support, ParsedId};
 use crate::web::responses::{
     CommunityComment, CommunityDetailResponse, CommunityInfo, CommunityInvitationResponse,
-    CommunityInvitationsListResponse, CommunityMemberResponse, CommunityMembersListResponse,
-    CommunityPostThumbnail, CommunityStats, CommunityWithPosts, CreateCommunityResponse,
-    ErrorResponse, InvitationCommunityInfo, InvitationUserInfo, MyCommunitiesResponse,
-    PaginationMeta, PublicCommunitiesResponse, UserInvitationResponse,
-    UserInvitationsListResponse,
+    CommunityInvitationsListResponse, CommunityLeaderboardResponse, CommunityMemberResponse,
+    CommunityMembersListResponse, CommunityPostThumbnail, CommunityStats, CommunityWithPosts,
+    CreateCommunityResponse, ErrorResponse, InvitationCommunityInfo, InvitationUserInfo,
+    InviteOutcome, InviteUserResult, InviteUsersResponse, MyCommunitiesResponse, PaginationMeta,
+    PublicCommunitiesResponse, UserInvitationResponse, UserInvitationsListResponse,
 };
 use crate::web::handlers::render_403;
 use crate::web::state::AppState;
 use axum::extract::{Path, Query};
-use axum::http::{uri::Uri, HeaderMap, HeaderValue};
-use axum::response::{IntoResponse, Redirect};
+use axum::http::{header, uri::Uri, HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Redirect, Response};
 use axum::{
     extract::State,
     http::StatusCode,
@@ -38,13 +80,20 @@ use axum::{
     Form,
 };
 use axum_messages::Messages;
+use chrono::{DateTime, NaiveTime, TimeDelta, Utc};
+use fluent::FluentArgs;
+use lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
+use lettre::{Message, SmtpTransport, Transport};
 use minijinja::context;
+use redis::AsyncCommands;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::web::context::CommonContext;
-use crate::web::handlers::{get_bundle, safe_get_message, ExtractAcceptLanguage, ExtractFtlLang};
+use crate::web::handlers::{
+    get_user_bundle, safe_format_message, safe_get_message, ExtractAcceptLanguage, ExtractFtlLang,
+};
 
 pub async fn redirect_community_to_unified(
     Path(slug): Path<String>,
@@ -52,14 +101,78 @@ pub async fn redirect_community_to_unified(
     Redirect::permanent(&format!("/@{}", slug))
 }
 
+/// Set once the viewer has passed the sensitive community interstitial
+#[derive(Deserialize)]
+pub struct SensitiveConsentQuery {
+    #[serde(default)]
+    pub accept_sensitive: bool,
+}
+
+/// How a community's posts are shown to a viewer
+#[derive(Debug, PartialEq, Eq)]
+enum SensitiveGate {
+    Open,
+    /// Shown, but blurred until clicked
+    Blurred,
+    /// Hidden behind the interstitial until the viewer accepts it
+    Interstitial,
+}
+
+/// Viewers who haven't opted into sensitive content must pass the interstitial
+/// of a sensitive community, after which its posts are shown blurred
+fn sensitive_gate(community: &Community, viewer: Option<&User>, accepted: bool) -> SensitiveGate {
+    let viewer_show_sensitive = viewer.is_some_and(|user| user.show_sensitive_content);
+    if !community.is_sensitive || viewer_show_sensitive {
+        SensitiveGate::Open
+    } else if accepted {
+        SensitiveGate::Blurred
+    } else {
+        SensitiveGate::Interstitial
+    }
+}
+
+/// Render the interstitial shown before entering a sensitive community
+pub async fn render_sensitive_interstitial(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    auth_session: &AuthSession,
+    state: &AppState,
+    community: &Community,
+    uri: &Uri,
+    ftl_lang: String,
+) -> Result<Html<String>, AppError> {
+    let common_ctx = CommonContext::build(
+        tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("community_sensitive.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        community,
+        accept_url => format!("{}?accept_sensitive=true", uri.path()),
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered))
+}
+
 pub async fn community(
     auth_session: AuthSession,
     headers: HeaderMap,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
     Path(id): Path<String>,
+    Query(consent): Query<SensitiveConsentQuery>,
     uri: Uri,
 ) -> Result<impl IntoResponse, AppError> {
+    let is_htmx = headers.get("HX-Request") == Some(&HeaderValue::from_static("true"));
+
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
@@ -91,6 +204,22 @@ pub async fn community(
         }
     };
 
+    if community.is_none() {
+        let deleted = match community_id.strip_prefix('@') {
+            Some(slug) => is_community_slug_deleted(&mut tx, slug).await?,
+            None => match Uuid::parse_str(&community_id) {
+                Ok(uuid) => is_community_deleted(&mut tx, uuid).await?,
+                Err(_) => false,
+            },
+        };
+        if deleted {
+            return Ok((
+                StatusCode::GONE,
+                handler_410(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
+            )
+                .into_response());
+        }
+    }
     let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
     let community_uuid = community.id;
 
@@ -126,6 +255,27 @@ pub async fn community(
         (None, false)
     };
 
+    // Fragments are only requested from a page past the interstitial
+    let gate = sensitive_gate(
+        &community,
+        auth_session.user.as_ref(),
+        is_htmx || consent.accept_sensitive,
+    );
+    if gate == SensitiveGate::Interstitial {
+        return Ok(render_sensitive_interstitial(
+            &mut tx,
+            &auth_session,
+            &state,
+            &community,
+            &uri,
+            ftl_lang,
+        )
+        .await?
+        .into_response());
+    }
+    let should_blur = gate == SensitiveGate::Blurred;
+    let viewer_show_sensitive = viewer_show_sensitive || community.is_sensitive;
+
     let posts = find_published_posts_by_community_id(
         &mut tx,
         community_uuid,
@@ -135,33 +285,87 @@ pub async fn community(
         viewer_show_sensitive,
     )
     .await?;
+    let pinned_posts = get_pinned_posts_for_community(
+        &mut tx,
+        community_uuid,
+        viewer_user_id,
+        viewer_show_sensitive,
+    )
+    .await?;
+    let viewer_role = match viewer_user_id {
+        Some(user_id) => get_user_role_in_community(&mut tx, user_id, community_uuid).await?,
+        None => None,
+    };
+    let can_moderate = matches!(
+        viewer_role,
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)
+    );
+    // Logged-in non-members may ask to join communities that take requests
+    let can_request_to_join = viewer_user_id.is_some()
+        && viewer_role.is_none()
+        && community.visibility != CommunityVisibility::Private
+        && community_accepts_join_requests(&mut tx, community_uuid).await?;
+    let join_request_pending = match viewer_user_id {
+        Some(user_id) if can_request_to_join => {
+            has_pending_join_request(&mut tx, community_uuid, user_id).await?
+        }
+        _ => false,
+    };
+    let pending_posts = if can_moderate && !is_htmx {
+        find_pending_approval_posts_by_community_id(&mut tx, community_uuid).await?
+    } else {
+        Vec::new()
+    };
+    let federated_posts =
+        find_federated_posts_by_community_id(&mut tx, community_uuid, 20, viewer_show_sensitive)
+            .await?;
     let comments = find_latest_comments_in_community(&mut tx, community_uuid, 5).await?;
     let stats = get_community_stats(&mut tx, community_uuid).await?;
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let archived_at = find_community_archived_at(&mut tx, community_uuid).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("community.jinja")?;
 
-    if headers.get("HX-Request") == Some(&HeaderValue::from_static("true")) {
+    if is_htmx {
         let rendered = template
             .eval_to_state(context! {
                 current_user => auth_session.user,
                 community => Some(&community),
                 community_id => community.id.to_string(),
                 domain => state.config.domain.clone(),
+                can_moderate,
                 ftl_lang
             })?
             .render_block("community_edit_block")?;
         Ok(Html(rendered).into_response())
     } else {
+        let link_preview = community_link_preview(&state, &community, !posts.is_empty());
         let rendered = template.render(context! {
         current_user => auth_session.user,
         community => Some(community),
         community_id => community_id,
         domain => state.config.domain.clone(),
+        og_title => link_preview.title,
+        og_description => link_preview.description,
+        og_image_url => link_preview.image_url,
+        og_url => link_preview.url,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         comments => comments,
         stats => stats,
+        pinned_posts => pinned_posts,
+        federated_posts => federated_posts,
+        can_moderate => can_moderate,
+        can_request_to_join => can_request_to_join,
+        has_pending_join_request => join_request_pending,
+        pending_posts => pending_posts,
+        should_blur => should_blur,
+        archived_at => archived_at,
         posts => posts.iter().map(|post| {
             HashMap::<String, String>::from_iter(vec![
                 ("id".to_string(), post.id.to_string()),
@@ -183,6 +387,104 @@ pub async fn community(
     }
 }
 
+/// Size of the link preview image, the one Open Graph consumers expect
+const LINK_PREVIEW_IMAGE_WIDTH: u32 = 1200;
+const LINK_PREVIEW_IMAGE_HEIGHT: u32 = 630;
+/// How many recent drawings are tiled into a community's preview image
+const LINK_PREVIEW_COLLAGE_POSTS: i64 = 4;
+
+/// Link preview for a community page. The image is a collage of recent drawings,
+/// left out for private and sensitive communities.
+pub fn community_link_preview(
+    state: &AppState,
+    community: &Community,
+    has_posts: bool,
+) -> LinkPreview {
+    let show_image = has_posts
+        && community.visibility != CommunityVisibility::Private
+        && !community.is_sensitive;
+    LinkPreview {
+        title: Some(community.name.clone()),
+        description: Some(markdown_summary(
+            &community.description,
+            LINK_PREVIEW_DESCRIPTION_LENGTH,
+        ))
+        .filter(|description| !description.is_empty()),
+        image_url: show_image.then(|| {
+            format!(
+                "{}/communities/@{}/og-image.png",
+                state.config.base_url, community.slug
+            )
+        }),
+        url: format!("{}/@{}", state.config.base_url, community.slug),
+    }
+}
+
+/// Link preview image for a community: its most recent drawings tiled together
+pub async fn community_og_image(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let community = match find_community_by_slug(&mut tx, slug).await? {
+        Some(community)
+            if community.visibility != CommunityVisibility::Private && !community.is_sensitive =>
+        {
+            community
+        }
+        _ => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+    let posts = find_published_posts_by_community_id(
+        &mut tx,
+        community.id,
+        LINK_PREVIEW_COLLAGE_POSTS,
+        0,
+        None,
+        false,
+    )
+    .await?;
+    tx.commit().await?;
+
+    let client = reqwest::Client::new();
+    let downloads = posts.iter().map(|post| {
+        let request = client.get(image_url(
+            &state.config.r2_public_endpoint_url,
+            &post.image_filename,
+        ));
+        async move { request.send().await?.error_for_status()?.bytes().await }
+    });
+    let images: Vec<_> = futures_util::future::join_all(downloads)
+        .await
+        .into_iter()
+        .filter_map(|result| match result {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                tracing::warn!("Failed to fetch image for community preview: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let collage = tokio::task::spawn_blocking(move || {
+        compose_collage(&images, LINK_PREVIEW_IMAGE_WIDTH, LINK_PREVIEW_IMAGE_HEIGHT)
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("Collage task panicked: {}", e))??;
+
+    match collage {
+        Some(png) => Ok((
+            [
+                (header::CONTENT_TYPE, "image/png"),
+                (header::CACHE_CONTROL, "public, max-age=3600"),
+            ],
+            png,
+        )
+            .into_response()),
+        None => Ok(StatusCode::NOT_FOUND.into_response()),
+    }
+}
+
 pub async fn community_iframe(
     auth_session: AuthSession,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
@@ -291,10 +593,17 @@ pub async fn community_iframe(
     Ok(Html(rendered).into_response())
 }
 
+#[derive(Deserialize)]
+pub struct CommunitiesQuery {
+    /// Slug of a category to list only its public communities
+    pub category: Option<String>,
+}
+
 pub async fn communities(
     auth_session: AuthSession,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
+    Query(query): Query<CommunitiesQuery>,
     messages: Messages,
 ) -> Result<Html<String>, AppError> {
     let db = &state.db_pool;
@@ -306,7 +615,66 @@ pub async fn communities(
         None => vec![],
     };
 
-    let public_communities_raw = get_public_communities(&mut tx).await?;
+    // The public listing is the same for every viewer, so it is served from
+    // the cache until a community or published post changes it
+    let listing_cache = CommunityListingCache::new(state.redis_pool.clone());
+    let cache_version = match listing_cache.version().await {
+        Ok(version) => Some(version),
+        Err(e) => {
+            tracing::warn!("Failed to read community listing cache version: {:?}", e);
+            None
+        }
+    };
+    let cached_listing = match cache_version {
+        Some(version) => listing_cache.load(version).await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to load cached community listing: {:?}", e);
+            None
+        }),
+        None => None,
+    };
+    let listing = match cached_listing {
+        Some(listing) => listing,
+        None => {
+            let public_communities = get_public_communities(&mut tx).await?;
+            let public_community_ids: Vec<Uuid> = public_communities.iter().map(|c| c.id).collect();
+            let mut members_count: HashMap<Uuid, Option<i64>> =
+                public_community_ids.iter().map(|id| (*id, None)).collect();
+            for stat in get_communities_members_count(&mut tx, &public_community_ids).await? {
+                members_count.insert(stat.community_id, stat.members_count);
+            }
+            let listing = CachedCommunityListing {
+                public_communities,
+                members_count,
+            };
+            if let Some(version) = cache_version {
+                if let Err(e) = listing_cache.store(version, &listing).await {
+                    tracing::warn!("Failed to cache community listing: {:?}", e);
+                }
+            }
+            listing
+        }
+    };
+    // The cached listing covers every category, so narrow it here
+    let categories = list_community_categories(&mut tx).await?;
+    let selected_category = match query.category.as_deref().filter(|slug| !slug.is_empty()) {
+        Some(slug) => Some(
+            categories
+                .iter()
+                .find(|category| category.slug == slug)
+                .cloned()
+                .ok_or_else(|| AppError::NotFound("Category".to_string()))?,
+        ),
+        None => None,
+    };
+    let mut public_communities_raw = listing.public_communities;
+    if let Some(category) = &selected_category {
+        let category_community_ids: HashSet<Uuid> =
+            find_community_ids_in_category(&mut tx, category.id)
+                .await?
+                .into_iter()
+                .collect();
+        public_communities_raw.retain(|c| category_community_ids.contains(&c.id));
+    }
 
     let participating_communities_raw = match auth_session.user.clone() {
         Some(user) => get_participating_communities(&mut tx, user.id).await?,
@@ -337,8 +705,14 @@ pub async fn communities(
     )
     .await?;
 
-    // Fetch members count (unique contributors) and posts count for all communities
-    let members_stats = get_communities_members_count(&mut tx, &all_community_ids).await?;
+    // Fetch members count (unique contributors) and posts count for all communities.
+    // Members counts of public communities came with the listing.
+    let uncached_community_ids: Vec<Uuid> = all_community_ids
+        .iter()
+        .filter(|id| !listing.members_count.contains_key(id))
+        .copied()
+        .collect();
+    let members_stats = get_communities_members_count(&mut tx, &uncached_community_ids).await?;
 
     let community_stats = if !all_community_ids.is_empty() {
         sqlx::query!(
@@ -382,6 +756,9 @@ pub async fn communities(
         Vec::new()
     };
 
+    let categories_by_community =
+        find_categories_by_community_ids(&mut tx, &all_community_ids).await?;
+
     // Group posts by community_id
     use std::collections::HashMap as StdHashMap;
     let mut posts_by_community: StdHashMap<Uuid, Vec<serde_json::Value>> = StdHashMap::new();
@@ -399,7 +776,7 @@ pub async fn communities(
     }
 
     // Create stats lookup maps
-    let mut members_by_community: StdHashMap<Uuid, Option<i64>> = StdHashMap::new();
+    let mut members_by_community: StdHashMap<Uuid, Option<i64>> = listing.members_count;
     for stat in members_stats {
         members_by_community.insert(stat.community_id, stat.members_count);
     }
@@ -475,6 +852,10 @@ pub async fn communities(
                 "posts_count": community.posts_count,
                 "members_count": members_count,
                 "recent_posts": recent_posts,
+                "categories": categories_by_community
+                    .get(&community.id)
+                    .cloned()
+                    .unwrap_or_default(),
             })
         })
         .collect();
@@ -526,8 +907,12 @@ pub async fn communities(
         })
         .collect();
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     tx.commit().await?;
 
@@ -537,10 +922,13 @@ pub async fn communities(
         messages => messages.into_iter().collect::<Vec<_>>(),
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         official_communities,
         public_communities,
         participating_communities,
         own_communities,
+        categories,
+        selected_category,
         r2_public_endpoint_url => state.config.r2_public_endpoint_url.clone(),
         ftl_lang
     })?;
@@ -554,6 +942,7 @@ pub struct CreateCommunityForm {
     slug: String,
     description: String,
     visibility: String,
+    is_sensitive: Option<String>,
 }
 
 pub async fn do_create_community(
@@ -580,12 +969,7 @@ pub async fn do_create_community(
 
     // Check if slug conflicts with any user login_name
     if slug_conflicts_with_user(&mut tx, &form.slug).await? {
-        let user_preferred_language = auth_session
-            .user
-            .clone()
-            .map(|u| u.preferred_language)
-            .unwrap_or_else(|| None);
-        let bundle = get_bundle(&accept_language, user_preferred_language);
+        let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
         let error_message = safe_get_message(&bundle, "community-slug-conflict-error");
         messages.error(error_message);
         return Ok(Redirect::to("/communities/new").into_response());
@@ -599,15 +983,26 @@ pub async fn do_create_community(
             slug: form.slug,
             description: form.description,
             visibility,
+            is_sensitive: form.is_sensitive.as_deref() == Some("on"),
         },
     )
     .await?;
 
+    apply_default_co_moderators(
+        &mut tx,
+        &community,
+        state.config.community_invitation_expires_at(),
+    )
+    .await?;
+
     // Create actor for the community (only for non-member_only communities)
     if visibility != CommunityVisibility::Private {
         match create_actor_for_community(&mut tx, &community, &state.config).await {
             Ok(_) => {
                 let _ = tx.commit().await;
+                CommunityListingCache::new(state.redis_pool.clone())
+                    .invalidate()
+                    .await;
                 Ok(Redirect::to(&format!("/communities/@{}", community.slug)).into_response())
             }
             Err(e) => {
@@ -615,12 +1010,7 @@ pub async fn do_create_community(
                 // Check if it's a unique constraint violation (handle conflict)
                 if let Some(sqlx::Error::Database(db_err)) = e.downcast_ref::<sqlx::Error>() {
                     if db_err.constraint().is_some() {
-                        let user_preferred_language = auth_session
-                            .user
-                            .clone()
-                            .map(|u| u.preferred_language)
-                            .unwrap_or_else(|| None);
-                        let bundle = get_bundle(&accept_language, user_preferred_language);
+                        let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
                         let error_message =
                             safe_get_message(&bundle, "community-slug-conflict-error");
                         messages.error(error_message);
@@ -646,8 +1036,12 @@ pub async fn create_community_form(
 ) -> Result<Html<String>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("create_community.jinja")?;
     let rendered = template.render(context! {
@@ -655,6 +1049,7 @@ pub async fn create_community_form(
         messages => messages.into_iter().collect::<Vec<_>>(),
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -704,16 +1099,55 @@ pub async fn hx_edit_community(
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    let posting_window = match &community {
+        Some(community) => find_community_posting_window(&mut tx, community.id).await?,
+        None => None,
+    };
+    let default_hashtags = match &community {
+        Some(community) => get_community_default_hashtags(&mut tx, community.id).await?,
+        None => Vec::new(),
+    };
+    let accepts_join_requests = match &community {
+        Some(community) => community_accepts_join_requests(&mut tx, community.id).await?,
+        None => false,
+    };
+    let require_post_approval = match &community {
+        Some(community) => community_requires_post_approval(&mut tx, community.id).await?,
+        None => false,
+    };
+    let categories = list_community_categories(&mut tx).await?;
+    let assigned_category_ids: Vec<Uuid> = match &community {
+        Some(community) => find_categories_by_community_ids(&mut tx, &[community.id])
+            .await?
+            .remove(&community.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|category| category.id)
+            .collect(),
+        None => Vec::new(),
+    };
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("community_edit.jinja")?;
     let rendered = template.render(context! {
         current_user => auth_session.user,
         community,
         community_id => id,
+        posting_window,
+        default_hashtags,
+        accepts_join_requests,
+        require_post_approval,
+        categories,
+        assigned_category_ids,
         domain => state.config.domain.clone(),
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -772,6 +1206,7 @@ pub async fn hx_do_edit_community(
         slug: form.slug.clone(),
         description: form.description.clone(),
         visibility,
+        is_sensitive: form.is_sensitive.as_deref() == Some("on"),
     };
 
     match update_community_with_activity(
@@ -786,6 +1221,9 @@ pub async fn hx_do_edit_community(
         Ok(updated_community) => {
             // Success - commit transaction
             let _ = tx.commit().await;
+            CommunityListingCache::new(state.redis_pool.clone())
+                .invalidate()
+                .await;
 
             // Check if slug changed - if so, redirect entire page to new URL
             if form.slug != original_slug {
@@ -798,12 +1236,7 @@ pub async fn hx_do_edit_community(
             } else {
                 // Slug didn't change - return updated content block
                 let template = state.env.get_template("community.jinja")?;
-                let user_preferred_language = auth_session
-                    .user
-                    .clone()
-                    .map(|u| u.preferred_language)
-                    .unwrap_or_else(|| None);
-                let bundle = get_bundle(&accept_language, user_preferred_language);
+                let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
                 let ftl_lang = bundle
                     .locales
                     .first()
@@ -831,12 +1264,7 @@ pub async fn hx_do_edit_community(
             let error_message =
                 if let Some(sqlx::Error::Database(db_err)) = e.downcast_ref::<sqlx::Error>() {
                     if db_err.constraint().is_some() {
-                        let user_preferred_language = auth_session
-                            .user
-                            .clone()
-                            .map(|u| u.preferred_language)
-                            .unwrap_or_else(|| None);
-                        let bundle = get_bundle(&accept_language, user_preferred_language);
+                        let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
                         Some(safe_get_message(&bundle, "community-slug-conflict-error"))
                     } else {
                         None
@@ -850,12 +1278,7 @@ pub async fn hx_do_edit_community(
             let current_community = find_community_by_id(&mut tx, community_uuid).await?;
 
             let template = state.env.get_template("community_edit.jinja")?;
-            let user_preferred_language = auth_session
-                .user
-                .clone()
-                .map(|u| u.preferred_language)
-                .unwrap_or_else(|| None);
-            let bundle = get_bundle(&accept_language, user_preferred_language);
+            let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
             let ftl_lang = bundle
                 .locales
                 .first()
@@ -876,314 +1299,378 @@ pub async fn hx_do_edit_community(
     }
 }
 
-pub async fn community_comments(
+#[derive(Deserialize)]
+pub struct PostingWindowForm {
+    timezone: String,
+    opens_at: String,
+    closes_at: String,
+}
+
+pub async fn hx_do_edit_posting_window(
     auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
     Path(id): Path<String>,
-    uri: Uri,
+    Form(form): Form<PostingWindowForm>,
 ) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let community = if id.starts_with('@') {
-        // Handle @slug format
-        let slug = id
-            .strip_prefix('@')
-            .ok_or_else(|| AppError::InvalidFormData("Invalid slug format".to_string()))?
-            .to_string();
-        find_community_by_slug(&mut tx, slug).await?
-    } else {
-        // Handle UUID format - redirect to @slug
-        let uuid = match parse_id_with_legacy_support(&id, "/communities", &state)? {
-            ParsedId::Uuid(uuid) => uuid,
-            ParsedId::Redirect(redirect) => return Ok(redirect.into_response()),
-            ParsedId::InvalidId(error_response) => return Ok(error_response),
-        };
-        let community = find_community_by_id(&mut tx, uuid).await?;
-        if let Some(community) = &community {
-            // Redirect UUID to @slug format
-            return Ok(
-                Redirect::to(&format!("/communities/@{}/comments", community.slug)).into_response(),
-            );
-        } else {
-            None
-        }
+    let slug = id
+        .strip_prefix('@')
+        .ok_or_else(|| AppError::InvalidFormData("Invalid slug format".to_string()))?
+        .to_string();
+    let community = match find_community_by_slug(&mut tx, slug).await? {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
     };
-
-    if community.is_none() {
-        return Ok(StatusCode::NOT_FOUND.into_response());
+    if community.owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
-    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
-    let community_uuid = community.id;
-
-    // Access control: verify access based on community visibility
-    match community.visibility {
-        CommunityVisibility::Private => {
-            // Private communities require authentication AND membership
-            match &auth_session.user {
-                Some(user) => {
-                    // User is authenticated, check membership
-                    let is_member = is_user_member(&mut tx, user.id, community_uuid).await?;
-                    if !is_member {
-                        // Authenticated but not a member - show 403 forbidden
-                        return Ok(render_403(&auth_session, &state, ftl_lang).await?.into_response());
-                    }
-                }
-                None => {
-                    // Not authenticated - redirect to login with next URL
-                    let next_url = uri.path();
-                    return Ok(Redirect::to(&format!("/login?next={}", next_url)).into_response());
-                }
-            }
-        }
-        CommunityVisibility::Public | CommunityVisibility::Unlisted => {
-            // Public and unlisted communities are accessible to everyone
-            // No authentication required
+    // Leaving both times empty removes the window
+    let error_message = if form.opens_at.is_empty() && form.closes_at.is_empty() {
+        clear_community_posting_window(&mut tx, community.id).await?;
+        None
+    } else {
+        let opens_at = NaiveTime::parse_from_str(&form.opens_at, "%H:%M");
+        let closes_at = NaiveTime::parse_from_str(&form.closes_at, "%H:%M");
+        match (opens_at, closes_at) {
+            (Ok(opens_at), Ok(closes_at)) => set_community_posting_window(
+                &mut tx,
+                community.id,
+                form.timezone.trim(),
+                opens_at,
+                closes_at,
+            )
+            .await
+            .err(),
+            _ => Some(anyhow::anyhow!("Invalid time")),
         }
-    }
+        .map(|e| {
+            tracing::warn!("Rejected posting window for {}: {}", community.slug, e);
+            let bundle = get_user_bundle(&accept_language, Some(user));
+            safe_get_message(&bundle, "community-posting-window-invalid")
+        })
+    };
 
-    // Get more comments for the dedicated comments page (100 instead of 5)
-    let comments = find_latest_comments_in_community(&mut tx, community_uuid, 100).await?;
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let posting_window = find_community_posting_window(&mut tx, community.id).await?;
+    if error_message.is_none() {
+        tx.commit().await?;
+    }
 
-    let template: minijinja::Template<'_, '_> =
-        state.env.get_template("community_comments.jinja")?;
+    let template = state.env.get_template("community_posting_window.jinja")?;
     let rendered = template.render(context! {
-        current_user => auth_session.user,
-        community => community,
-        comments => comments,
-        domain => state.config.domain.clone(),
-        unread_notification_count => common_ctx.unread_notification_count,
-        draft_post_count => common_ctx.draft_post_count,
-        ftl_lang,
+        community_id => id,
+        posting_window,
+        error_message,
+        ftl_lang
     })?;
 
     Ok(Html(rendered).into_response())
 }
 
-// ========== Member Management Endpoints ==========
+#[derive(Deserialize)]
+pub struct DefaultHashtagsForm {
+    hashtags: String,
+}
 
-/// List community members
-pub async fn get_members(
+pub async fn hx_do_edit_default_hashtags(
     auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
-    Path(slug): Path<String>,
+    Path(id): Path<String>,
+    Form(form): Form<DefaultHashtagsForm>,
 ) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let community =
-        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
-            .await?;
-
-    if community.is_none() {
-        return Ok(StatusCode::NOT_FOUND.into_response());
-    }
-
-    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
-
-    // Only members can view member list
-    let user = match auth_session.user {
-        Some(user) => user,
-        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    let slug = id
+        .strip_prefix('@')
+        .ok_or_else(|| AppError::InvalidFormData("Invalid slug format".to_string()))?
+        .to_string();
+    let community = match find_community_by_slug(&mut tx, slug).await? {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
     };
-
-    let is_member = is_user_member(&mut tx, user.id, community.id).await?;
-    if !is_member {
+    if community.owner_id != user.id {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
-    // Fetch members with user details in a single query (no N+1)
-    let members = get_community_members_with_details(&mut tx, community.id).await?;
-
-    let members_with_details: Vec<serde_json::Value> = members
-        .into_iter()
-        .map(|member| {
-            serde_json::json!({
-                "id": member.id,
-                "user_id": member.user_id,
-                "login_name": member.login_name,
-                "display_name": member.display_name,
-                "role": member.role,
-                "joined_at": member.joined_at,
-            })
-        })
-        .collect();
+    let hashtag_names = parse_hashtag_input(&form.hashtags);
+    set_community_default_hashtags(&mut tx, community.id, &hashtag_names).await?;
+    let default_hashtags = get_community_default_hashtags(&mut tx, community.id).await?;
 
     tx.commit().await?;
 
-    Ok(axum::Json(members_with_details).into_response())
+    let template = state.env.get_template("community_default_hashtags.jinja")?;
+    let rendered = template.render(context! {
+        community_id => id,
+        default_hashtags,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
 }
 
-/// Invite a user to a community
 #[derive(Deserialize)]
-pub struct InviteUserForm {
-    login_name: String,
+pub struct JoinRequestsSettingForm {
+    accepts_join_requests: Option<String>,
 }
 
-pub async fn invite_user(
+pub async fn hx_do_edit_accepts_join_requests(
     auth_session: AuthSession,
-    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
-    Path(slug): Path<String>,
-    messages: Messages,
-    Form(form): Form<InviteUserForm>,
+    Path(id): Path<String>,
+    Form(form): Form<JoinRequestsSettingForm>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user_preferred_language = auth_session
-        .user
-        .as_ref()
-        .and_then(|u| u.preferred_language.clone());
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
 
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let community =
-        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
-            .await?;
-
-    if community.is_none() {
-        return Ok(StatusCode::NOT_FOUND.into_response());
+    let slug = id
+        .strip_prefix('@')
+        .ok_or_else(|| AppError::InvalidFormData("Invalid slug format".to_string()))?
+        .to_string();
+    let community = match find_community_by_slug(&mut tx, slug).await? {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+    if community.owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+    // Private communities grow through invitations only
+    if community.visibility == CommunityVisibility::Private {
+        return Ok(StatusCode::BAD_REQUEST.into_response());
     }
 
-    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+    let accepts_join_requests = form.accepts_join_requests.as_deref() == Some("on");
+    set_community_accepts_join_requests(&mut tx, community.id, accepts_join_requests).await?;
 
-    // Must be logged in
-    let inviter = match auth_session.user {
-        Some(user) => user,
-        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
-    };
+    tx.commit().await?;
 
-    // Check if user is owner or moderator
-    let role = get_user_role_in_community(&mut tx, inviter.id, community.id).await?;
-    match role {
-        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
-        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
-    }
+    let template = state
+        .env
+        .get_template("community_join_requests_form.jinja")?;
+    let rendered = template.render(context! {
+        community_id => id,
+        accepts_join_requests,
+        ftl_lang
+    })?;
 
-    // Find the invitee by login_name
-    let invitee = find_user_by_login_name(&mut tx, &form.login_name).await?;
-    if invitee.is_none() {
-        messages.error(safe_get_message(&bundle, "community-invite-user-not-found"));
-        return Ok(
-            Redirect::to(&format!("/communities/@{}/members", community.slug)).into_response(),
-        );
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct PostApprovalSettingForm {
+    require_post_approval: Option<String>,
+}
+
+pub async fn hx_do_edit_require_post_approval(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(form): Form<PostApprovalSettingForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let slug = id
+        .strip_prefix('@')
+        .ok_or_else(|| AppError::InvalidFormData("Invalid slug format".to_string()))?
+        .to_string();
+    let community = match find_community_by_slug(&mut tx, slug).await? {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+    if community.owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
     }
-    let invitee = invitee.ok_or_else(|| AppError::NotFound("User".to_string()))?;
 
-    // Check if user is already a member
-    let already_member = is_user_member(&mut tx, invitee.id, community.id).await?;
-    if already_member {
-        messages.error(safe_get_message(&bundle, "community-invite-already-member"));
-        return Ok(
-            Redirect::to(&format!("/communities/@{}/members", community.slug)).into_response(),
-        );
+    let require_post_approval = form.require_post_approval.as_deref() == Some("on");
+    set_community_requires_post_approval(&mut tx, community.id, require_post_approval).await?;
+
+    tx.commit().await?;
+
+    let template = state
+        .env
+        .get_template("community_post_approval_form.jinja")?;
+    let rendered = template.render(context! {
+        community_id => id,
+        require_post_approval,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+/// The checked boxes of the category form, as repeated `category_id` fields
+pub async fn hx_do_edit_community_categories(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(fields): Form<Vec<(String, String)>>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let category_ids = fields
+        .into_iter()
+        .filter(|(name, _)| name == "category_id")
+        .map(|(_, value)| Uuid::parse_str(&value))
+        .collect::<Result<Vec<Uuid>, _>>()
+        .map_err(|e| AppError::InvalidFormData(format!("Invalid category_id: {}", e)))?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let slug = id
+        .strip_prefix('@')
+        .ok_or_else(|| AppError::InvalidFormData("Invalid slug format".to_string()))?
+        .to_string();
+    let community = match find_community_by_slug(&mut tx, slug).await? {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+    if community.owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
-    // Create invitation
-    match create_invitation(&mut tx, community.id, inviter.id, invitee.id).await {
-        Ok(_invitation) => {
-            // Get invitee's language preference before committing transaction
-            let invitee_language = get_user_language_preference(&mut tx, invitee.id).await.ok().flatten();
+    set_community_categories(&mut tx, community.id, &category_ids).await?;
 
-            tx.commit().await?;
+    let categories = list_community_categories(&mut tx).await?;
+    let assigned_category_ids: Vec<Uuid> =
+        find_categories_by_community_ids(&mut tx, &[community.id])
+            .await?
+            .remove(&community.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
 
-            // Send push notification to invitee with localized message
-            let (title, body) = format_community_invitation_message(
-                "invite",
-                invitee_language,
-                &inviter.display_name,
-                &community.slug,
-            );
+    tx.commit().await?;
 
-            let mut data = serde_json::Map::new();
-            data.insert(
-                "community_id".to_string(),
-                serde_json::json!(community.id.to_string()),
-            );
-            data.insert(
-                "community_slug".to_string(),
-                serde_json::json!(community.slug),
-            );
-            data.insert(
-                "notification_type".to_string(),
-                serde_json::json!("community_invite"),
-            );
+    let template = state.env.get_template("community_categories_form.jinja")?;
+    let rendered = template.render(context! {
+        community_id => id,
+        categories,
+        assigned_category_ids,
+        ftl_lang
+    })?;
 
-            tracing::info!(
-                "Sending community invitation push notification to user {}: title={}, body={}",
-                invitee.id,
-                title,
-                body
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn community_comments(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    uri: Uri,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community = if id.starts_with('@') {
+        // Handle @slug format
+        let slug = id
+            .strip_prefix('@')
+            .ok_or_else(|| AppError::InvalidFormData("Invalid slug format".to_string()))?
+            .to_string();
+        find_community_by_slug(&mut tx, slug).await?
+    } else {
+        // Handle UUID format - redirect to @slug
+        let uuid = match parse_id_with_legacy_support(&id, "/communities", &state)? {
+            ParsedId::Uuid(uuid) => uuid,
+            ParsedId::Redirect(redirect) => return Ok(redirect.into_response()),
+            ParsedId::InvalidId(error_response) => return Ok(error_response),
+        };
+        let community = find_community_by_id(&mut tx, uuid).await?;
+        if let Some(community) = &community {
+            // Redirect UUID to @slug format
+            return Ok(
+                Redirect::to(&format!("/communities/@{}/comments", community.slug)).into_response(),
             );
+        } else {
+            None
+        }
+    };
 
-            // Get unread notification count for badge
-            let mut badge_tx = db.begin().await?;
-            let unread_count =
-                crate::models::notification::get_unread_count(&mut badge_tx, invitee.id)
-                    .await
-                    .ok();
-            let _ = badge_tx.commit().await;
+    if community.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
 
-            // Send push notification (don't fail if this errors)
-            match state
-                .push_service
-                .send_notification_to_user(
-                    invitee.id,
-                    &title,
-                    &body,
-                    unread_count.map(|c| c as u32), // badge count
-                    Some(serde_json::Value::Object(data)),
-                )
-                .await
-            {
-                Ok(_) => {
-                    tracing::info!(
-                        "Successfully sent community invitation push notification to user {}",
-                        invitee.id
-                    );
+    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+    let community_uuid = community.id;
+
+    // Access control: verify access based on community visibility
+    match community.visibility {
+        CommunityVisibility::Private => {
+            // Private communities require authentication AND membership
+            match &auth_session.user {
+                Some(user) => {
+                    // User is authenticated, check membership
+                    let is_member = is_user_member(&mut tx, user.id, community_uuid).await?;
+                    if !is_member {
+                        // Authenticated but not a member - show 403 forbidden
+                        return Ok(render_403(&auth_session, &state, ftl_lang).await?.into_response());
+                    }
                 }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to send community invitation push notification to user {}: {:?}",
-                        invitee.id,
-                        e
-                    );
+                None => {
+                    // Not authenticated - redirect to login with next URL
+                    let next_url = uri.path();
+                    return Ok(Redirect::to(&format!("/login?next={}", next_url)).into_response());
                 }
             }
-
-            messages.success(safe_get_message(&bundle, "community-invite-success"));
-
-            Ok(Redirect::to(&format!("/communities/@{}/members", community.slug)).into_response())
         }
-        Err(e) => {
-            // Check if this is a duplicate key constraint error
-            if let Some(sqlx::Error::Database(ref err)) = e.downcast_ref::<sqlx::Error>() {
-                if err.is_unique_violation() {
-                    messages.error(safe_get_message(
-                        &bundle,
-                        "community-invite-already-invited",
-                    ));
-                    return Ok(
-                        Redirect::to(&format!("/communities/@{}/members", community.slug))
-                            .into_response(),
-                    );
-                }
-            }
-            // For other errors, propagate them
-            Err(e.into())
+        CommunityVisibility::Public | CommunityVisibility::Unlisted => {
+            // Public and unlisted communities are accessible to everyone
+            // No authentication required
         }
     }
+
+    // Get more comments for the dedicated comments page (100 instead of 5)
+    let comments = find_latest_comments_in_community(&mut tx, community_uuid, 100).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("community_comments.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        community => community,
+        comments => comments,
+        domain => state.config.domain.clone(),
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        draft_post_count => common_ctx.draft_post_count,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
 }
 
-/// Remove a member from a community
-pub async fn remove_member(
+// ========== Member Management Endpoints ==========
+
+/// List community members
+pub async fn get_members(
     auth_session: AuthSession,
     State(state): State<AppState>,
-    Path((slug, user_id)): Path<(String, Uuid)>,
+    Path(slug): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -1198,40 +1685,474 @@ pub async fn remove_member(
 
     let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
 
-    // Must be logged in
-    let current_user = match auth_session.user {
+    // Only members can view member list
+    let user = match auth_session.user {
         Some(user) => user,
         None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
     };
 
-    // Check if current user is owner or moderator
-    let current_role = get_user_role_in_community(&mut tx, current_user.id, community.id).await?;
-    match current_role {
-        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
-        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
+    let is_member = is_user_member(&mut tx, user.id, community.id).await?;
+    if !is_member {
+        return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
-    // Cannot remove the owner
-    let target_role = get_user_role_in_community(&mut tx, user_id, community.id).await?;
-    if target_role == Some(CommunityMemberRole::Owner) {
-        return Ok((StatusCode::BAD_REQUEST, "Cannot remove community owner").into_response());
-    }
+    // Fetch members with user details in a single query (no N+1)
+    let members = get_community_members_with_details(&mut tx, community.id).await?;
 
-    // Remove the member
-    remove_community_member(&mut tx, community.id, user_id).await?;
+    let members_with_details: Vec<serde_json::Value> = members
+        .into_iter()
+        .map(|member| {
+            serde_json::json!({
+                "id": member.id,
+                "user_id": member.user_id,
+                "login_name": member.login_name,
+                "display_name": member.display_name,
+                "role": member.role,
+                "joined_at": member.joined_at,
+            })
+        })
+        .collect();
 
     tx.commit().await?;
 
-    // Return empty HTML for HTMX to remove the row
-    Ok(Html(String::new()).into_response())
+    Ok(axum::Json(members_with_details).into_response())
 }
 
-// ========== Invitation Endpoints ==========
+/// Invite users to a community
+#[derive(Deserialize)]
+pub struct InviteUserForm {
+    /// One or more login names, separated by commas or whitespace
+    login_names: String,
+}
 
-/// Accept an invitation
-pub async fn do_accept_invitation(
-    auth_session: AuthSession,
-    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+/// Most users a single request may invite
+const MAX_INVITES_PER_REQUEST: usize = 50;
+
+/// Pending invitations this close to expiring are highlighted on the members page
+const INVITATION_EXPIRING_SOON_DAYS: i64 = 3;
+
+/// Split pasted login names on commas and whitespace, dropping `@` prefixes and repeats
+fn parse_login_names(input: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|name| name.trim_start_matches('@'))
+        .filter(|name| !name.is_empty() && seen.insert(name.to_string()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Someone invited by `invite_users_by_login_name`, to be notified after the commit
+struct InvitedUser {
+    id: Uuid,
+    language: Option<Language>,
+}
+
+/// Invite each named user who is neither a member nor already invited,
+/// reporting what happened to every name
+async fn invite_users_by_login_name(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    community_id: Uuid,
+    inviter_id: Uuid,
+    login_names: Vec<String>,
+    expires_at: DateTime<Utc>,
+) -> Result<(Vec<InviteUserResult>, Vec<InvitedUser>), AppError> {
+    let mut results = Vec::with_capacity(login_names.len());
+    let mut invited = Vec::new();
+
+    for login_name in login_names {
+        let invitee = match find_user_by_login_name(tx, &login_name).await? {
+            Some(invitee) => invitee,
+            None => {
+                results.push(InviteUserResult {
+                    login_name,
+                    result: InviteOutcome::NotFound,
+                });
+                continue;
+            }
+        };
+
+        let result = if is_user_member(tx, invitee.id, community_id).await? {
+            InviteOutcome::AlreadyMember
+        } else if has_pending_invitation(tx, community_id, invitee.id).await? {
+            InviteOutcome::AlreadyInvited
+        } else {
+            create_invitation(
+                tx,
+                community_id,
+                inviter_id,
+                invitee.id,
+                CommunityMemberRole::Member,
+                expires_at,
+            )
+            .await?;
+            invited.push(InvitedUser {
+                id: invitee.id,
+                language: get_user_language_preference(tx, invitee.id)
+                    .await
+                    .ok()
+                    .flatten(),
+            });
+            InviteOutcome::Invited
+        };
+        results.push(InviteUserResult { login_name, result });
+    }
+
+    Ok((results, invited))
+}
+
+/// Clear the cached invitation counts of everyone invited and push them a notification
+async fn notify_invited_users(
+    state: &AppState,
+    inviter: &User,
+    community: &Community,
+    invited: Vec<InvitedUser>,
+) {
+    let context_cache = CommonContextCache::new(state.redis_pool.clone());
+    for invitee in invited {
+        context_cache.invalidate(invitee.id).await;
+        send_community_invitation_notification(
+            state,
+            &inviter.display_name,
+            community,
+            invitee.id,
+            invitee.language,
+        )
+        .await;
+    }
+}
+
+async fn send_community_invitation_notification(
+    state: &AppState,
+    inviter_display_name: &str,
+    community: &Community,
+    invitee_id: Uuid,
+    invitee_language: Option<Language>,
+) {
+    // Send push notification to invitee with localized message
+    let (title, body) = format_community_invitation_message(
+        "invite",
+        invitee_language,
+        inviter_display_name,
+        &community.slug,
+    );
+
+    let mut data = serde_json::Map::new();
+    data.insert(
+        "community_id".to_string(),
+        serde_json::json!(community.id.to_string()),
+    );
+    data.insert(
+        "community_slug".to_string(),
+        serde_json::json!(community.slug),
+    );
+    data.insert(
+        "notification_type".to_string(),
+        serde_json::json!("community_invite"),
+    );
+
+    tracing::info!(
+        "Sending community invitation push notification to user {}: title={}, body={}",
+        invitee_id,
+        title,
+        body
+    );
+
+    // Get unread notification count for badge
+    let unread_count = match state.db_pool.begin().await {
+        Ok(mut badge_tx) => {
+            let count = crate::models::notification::get_unread_count(&mut badge_tx, invitee_id)
+                .await
+                .ok();
+            let _ = badge_tx.commit().await;
+            count
+        }
+        Err(_) => None,
+    };
+
+    // Send push notification (don't fail if this errors)
+    match state
+        .push_service
+        .send_notification_to_user(
+            invitee_id,
+            None,
+            &title,
+            &body,
+            unread_count.map(|c| c as u32), // badge count
+            Some(serde_json::Value::Object(data)),
+        )
+        .await
+    {
+        Ok(_) => {
+            tracing::info!(
+                "Successfully sent community invitation push notification to user {}",
+                invitee_id
+            );
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to send community invitation push notification to user {}: {:?}",
+                invitee_id,
+                e
+            );
+        }
+    }
+}
+
+pub async fn invite_user(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    mut messages: Messages,
+    Form(form): Form<InviteUserForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+
+    if community.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    // Must be logged in
+    let inviter = match auth_session.user {
+        Some(user) => user,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    // Check if user is owner or moderator
+    let role = get_user_role_in_community(&mut tx, inviter.id, community.id).await?;
+    match role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
+    }
+
+    let members_url = format!("/communities/@{}/members", community.slug);
+
+    let login_names = parse_login_names(&form.login_names);
+    if login_names.len() > MAX_INVITES_PER_REQUEST {
+        let mut args = FluentArgs::new();
+        args.set("max", MAX_INVITES_PER_REQUEST);
+        messages.error(safe_format_message(
+            &bundle,
+            "community-invite-too-many",
+            Some(&args),
+        ));
+        return Ok(Redirect::to(&members_url).into_response());
+    }
+
+    let (results, invited) = invite_users_by_login_name(
+        &mut tx,
+        community.id,
+        inviter.id,
+        login_names,
+        state.config.community_invitation_expires_at(),
+    )
+    .await?;
+    tx.commit().await?;
+
+    notify_invited_users(&state, &inviter, &community, invited).await;
+
+    // One message per outcome, listing the names it applies to
+    for (outcome, key) in [
+        (InviteOutcome::Invited, "community-invite-invited-users"),
+        (
+            InviteOutcome::AlreadyMember,
+            "community-invite-users-already-members",
+        ),
+        (
+            InviteOutcome::AlreadyInvited,
+            "community-invite-users-already-invited",
+        ),
+        (InviteOutcome::NotFound, "community-invite-users-not-found"),
+    ] {
+        let names: Vec<String> = results
+            .iter()
+            .filter(|r| r.result == outcome)
+            .map(|r| format!("@{}", r.login_name))
+            .collect();
+        if names.is_empty() {
+            continue;
+        }
+        let mut args = FluentArgs::new();
+        args.set("names", names.join(", "));
+        let message = safe_format_message(&bundle, key, Some(&args));
+        messages = if outcome == InviteOutcome::Invited {
+            messages.success(message)
+        } else {
+            messages.error(message)
+        };
+    }
+
+    Ok(Redirect::to(&members_url).into_response())
+}
+
+/// Remove a member from a community
+pub async fn remove_member(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, user_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+
+    if community.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    // Must be logged in
+    let current_user = match auth_session.user {
+        Some(user) => user,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    // Check if current user is owner or moderator
+    let current_role = get_user_role_in_community(&mut tx, current_user.id, community.id).await?;
+    match current_role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
+    }
+
+    // Cannot remove the owner
+    let target_role = get_user_role_in_community(&mut tx, user_id, community.id).await?;
+    if target_role == Some(CommunityMemberRole::Owner) {
+        return Ok((StatusCode::BAD_REQUEST, "Cannot remove community owner").into_response());
+    }
+
+    // Remove the member
+    remove_community_member(&mut tx, community.id, user_id).await?;
+
+    tx.commit().await?;
+
+    // Return empty HTML for HTMX to remove the row
+    Ok(Html(String::new()).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct SetMemberRoleRequest {
+    pub role: String,
+}
+
+/// Roles the owner can hand out; ownership itself moves through a transfer
+fn parse_assignable_role(role: &str) -> Option<CommunityMemberRole> {
+    match role {
+        "moderator" => Some(CommunityMemberRole::Moderator),
+        "member" => Some(CommunityMemberRole::Member),
+        _ => None,
+    }
+}
+
+/// Promote a member to moderator or demote them back, on behalf of the owner
+async fn change_member_role(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    current_user_id: Uuid,
+    community_id: Uuid,
+    user_id: Uuid,
+    role: &str,
+) -> Result<Result<(), StatusCode>, AppError> {
+    let role = match parse_assignable_role(role) {
+        Some(role) => role,
+        None => return Ok(Err(StatusCode::BAD_REQUEST)),
+    };
+
+    // Only the owner changes roles; moderators can't promote each other
+    let current_role = get_user_role_in_community(tx, current_user_id, community_id).await?;
+    if current_role != Some(CommunityMemberRole::Owner) {
+        return Ok(Err(StatusCode::FORBIDDEN));
+    }
+
+    match get_user_role_in_community(tx, user_id, community_id).await? {
+        None => return Ok(Err(StatusCode::NOT_FOUND)),
+        Some(CommunityMemberRole::Owner) => return Ok(Err(StatusCode::BAD_REQUEST)),
+        Some(_) => {}
+    }
+
+    if !set_member_role(tx, community_id, user_id, role).await? {
+        return Ok(Err(StatusCode::NOT_FOUND));
+    }
+
+    Ok(Ok(()))
+}
+
+/// Change a member's role (HTMX), returning the updated member row
+pub async fn do_set_member_role(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path((slug, user_id)): Path<(String, Uuid)>,
+    Form(form): Form<SetMemberRoleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = match &auth_session.user {
+        Some(user) => user,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    let community = match community {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+
+    if let Err(status) =
+        change_member_role(&mut tx, current_user.id, community.id, user_id, &form.role).await?
+    {
+        return Ok(status.into_response());
+    }
+
+    let member = get_community_members_with_details(&mut tx, community.id)
+        .await?
+        .into_iter()
+        .find(|member| member.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Member".to_string()))?;
+
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("community_member_row.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        community,
+        member => serde_json::json!({
+            "id": member.id,
+            "user_id": member.user_id,
+            "login_name": member.login_name,
+            "display_name": member.display_name,
+            "role": member.role,
+            "joined_at": member.joined_at,
+        }),
+        can_remove => true,
+        can_change_roles => true,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+// ========== Invitation Endpoints ==========
+
+/// Accept an invitation
+pub async fn do_accept_invitation(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
     State(state): State<AppState>,
     Path(invitation_id): Path<Uuid>,
     messages: Messages,
@@ -1241,8 +2162,7 @@ pub async fn do_accept_invitation(
         None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
     };
 
-    let user_preferred_language = user.preferred_language.clone();
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, Some(user));
 
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -1266,15 +2186,17 @@ pub async fn do_accept_invitation(
     // Store inviter_id before consuming invitation
     let inviter_id = invitation.inviter_id;
 
-    // Accept the invitation
-    accept_invitation(&mut tx, invitation_id).await?;
+    // Accept the invitation; it may have expired since it was loaded
+    if !accept_invitation(&mut tx, invitation_id).await? {
+        return Ok(StatusCode::GONE.into_response());
+    }
 
-    // Add user as a member
+    // Add user with the role offered by the invitation
     add_community_member(
         &mut tx,
         invitation.community_id,
         user.id,
-        CommunityMemberRole::Member,
+        invitation.role,
         Some(inviter_id),
     )
     .await?;
@@ -1283,6 +2205,9 @@ pub async fn do_accept_invitation(
     let inviter_language = get_user_language_preference(&mut tx, inviter_id).await.ok().flatten();
 
     tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     // Send push notification to inviter with localized message
     let (title, body) = format_community_invitation_message(
@@ -1325,6 +2250,7 @@ pub async fn do_accept_invitation(
         .push_service
         .send_notification_to_user(
             inviter_id,
+            None,
             &title,
             &body,
             unread_count.map(|c| c as u32), // badge count
@@ -1365,8 +2291,7 @@ pub async fn do_reject_invitation(
         None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
     };
 
-    let user_preferred_language = user.preferred_language.clone();
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, Some(user));
 
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -1397,6 +2322,9 @@ pub async fn do_reject_invitation(
     let inviter_language = get_user_language_preference(&mut tx, inviter_id).await.ok().flatten();
 
     tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     // Send push notification to inviter with localized message
     let (title, body) = format_community_invitation_message(
@@ -1439,6 +2367,7 @@ pub async fn do_reject_invitation(
         .push_service
         .send_notification_to_user(
             inviter_id,
+            None,
             &title,
             &body,
             unread_count.map(|c| c as u32), // badge count
@@ -1582,6 +2511,7 @@ pub async fn members_page(
             let invitations =
                 get_pending_invitations_with_invitee_details_for_community(&mut tx, community.id)
                     .await?;
+            let expiring_soon_cutoff = Utc::now() + TimeDelta::days(INVITATION_EXPIRING_SOON_DAYS);
             invitations
                 .into_iter()
                 .map(|invitation| {
@@ -1590,6 +2520,8 @@ pub async fn members_page(
                         "invitee_login_name": invitation.invitee_login_name,
                         "invitee_display_name": invitation.invitee_display_name,
                         "created_at": invitation.created_at,
+                        "expires_at": invitation.expires_at,
+                        "expires_soon": invitation.expires_at <= expiring_soon_cutoff,
                     })
                 })
                 .collect()
@@ -1597,35 +2529,141 @@ pub async fn members_page(
         _ => Vec::new(),
     };
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
-
-    tx.commit().await?;
+    let pending_join_requests = match user_role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {
+            let join_requests = get_pending_join_requests_with_requester_details_for_community(
+                &mut tx,
+                community.id,
+            )
+            .await?;
+            join_requests
+                .into_iter()
+                .map(|join_request| {
+                    serde_json::json!({
+                        "id": join_request.id,
+                        "requester_login_name": join_request.requester_login_name,
+                        "requester_display_name": join_request.requester_display_name,
+                        "created_at": join_request.created_at,
+                    })
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    };
 
-    let template: minijinja::Template<'_, '_> =
-        state.env.get_template("community_members.jinja")?;
-    let rendered = template.render(context! {
-        current_user => auth_session.user,
-        community,
+    let invite_links = match user_role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {
+            let links = get_invite_links_for_community(&mut tx, community.id).await?;
+            links
+                .into_iter()
+                .map(|link| {
+                    serde_json::json!({
+                        "id": link.id,
+                        "max_uses": link.max_uses,
+                        "use_count": link.use_count,
+                        "expires_at": link.expires_at,
+                        "is_usable": link.is_usable(),
+                    })
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    // Owners see their outstanding transfer so they can withdraw it
+    let pending_transfer = match user_role {
+        Some(CommunityMemberRole::Owner) => {
+            match find_pending_ownership_transfer(&mut tx, community.id).await? {
+                Some(transfer) => {
+                    let proposed_owner = find_user_by_id(&mut tx, transfer.proposed_owner_id)
+                        .await?
+                        .map(|user| user.login_name);
+                    Some(serde_json::json!({
+                        "token": transfer.token,
+                        "proposed_owner_login_name": proposed_owner,
+                        "expires_at": transfer.expires_at,
+                    }))
+                }
+                None => None,
+            }
+        }
+        _ => None,
+    };
+
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("community_members.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        community,
         members => members_with_details,
         pending_invitations,
+        pending_join_requests,
+        invite_links,
+        domain => state.config.domain.clone(),
         user_role,
         can_invite => matches!(user_role, Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)),
         can_remove => matches!(user_role, Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)),
+        can_change_roles => matches!(user_role, Some(CommunityMemberRole::Owner)),
+        can_transfer => matches!(user_role, Some(CommunityMemberRole::Owner)),
+        pending_transfer,
         messages => messages.into_iter().collect::<Vec<_>>(),
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang,
     })?;
 
     Ok(Html(rendered).into_response())
 }
 
+/// Community detail with no posts or comments, for viewers who can't see them yet
+fn gated_community_detail(community: Community, should_blur: bool) -> CommunityDetailResponse {
+    CommunityDetailResponse {
+        community: CommunityInfo {
+            id: community.id,
+            name: community.name,
+            slug: community.slug,
+            description: community.description,
+            visibility: community.visibility,
+            owner_id: community.owner_id,
+            background_color: community.background_color,
+            foreground_color: community.foreground_color,
+            is_sensitive: community.is_sensitive,
+        },
+        should_blur,
+        stats: CommunityStats {
+            total_posts: 0,
+            total_contributors: 0,
+            total_comments: 0,
+        },
+        pinned_posts: vec![],
+        posts: vec![],
+        pagination: PaginationMeta {
+            offset: 0,
+            limit: 0,
+            total: None,
+            has_more: false,
+        },
+        comments: vec![],
+    }
+}
+
 pub async fn community_detail_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
     Path(slug): Path<String>,
     Query(query): Query<LoadMoreQuery>,
+    Query(consent): Query<SensitiveConsentQuery>,
+    Query(thumbnail_query): Query<ThumbnailQuery>,
 ) -> Result<Json<CommunityDetailResponse>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -1633,9 +2671,12 @@ pub async fn community_detail_json(
     // Strip @ prefix if present
     let slug = slug.strip_prefix('@').unwrap_or(&slug);
 
-    let community = find_community_by_slug(&mut tx, slug.to_string())
-        .await?
-        .ok_or_else(|| anyhow::anyhow!("Community not found"))?;
+    let Some(community) = find_community_by_slug(&mut tx, slug.to_string()).await? else {
+        if is_community_slug_deleted(&mut tx, slug).await? {
+            return Err(AppError::Gone("Community".to_string()));
+        }
+        return Err(AppError::NotFound("Community".to_string()));
+    };
 
     // Access control: verify access based on community visibility
     match community.visibility {
@@ -1647,60 +2688,12 @@ pub async fn community_detail_json(
                     let is_member = is_user_member(&mut tx, user.id, community.id).await?;
                     if !is_member {
                         // Authenticated but not a member - return 403 Forbidden
-                        return Ok(Json(CommunityDetailResponse {
-                            community: CommunityInfo {
-                                id: community.id,
-                                name: community.name,
-                                slug: community.slug,
-                                description: community.description,
-                                visibility: community.visibility,
-                                owner_id: community.owner_id,
-                                background_color: community.background_color,
-                                foreground_color: community.foreground_color,
-                            },
-                            stats: CommunityStats {
-                                total_posts: 0,
-                                total_contributors: 0,
-                                total_comments: 0,
-                            },
-                            posts: vec![],
-                            pagination: PaginationMeta {
-                                offset: 0,
-                                limit: 0,
-                                total: None,
-                                has_more: false,
-                            },
-                            comments: vec![],
-                        }));
+                        return Ok(Json(gated_community_detail(community, false)));
                     }
                 }
                 None => {
                     // Not authenticated - return basic info only for private communities
-                    return Ok(Json(CommunityDetailResponse {
-                        community: CommunityInfo {
-                            id: community.id,
-                            name: community.name,
-                            slug: community.slug,
-                            description: community.description,
-                            visibility: community.visibility,
-                            owner_id: community.owner_id,
-                            background_color: community.background_color,
-                            foreground_color: community.foreground_color,
-                        },
-                        stats: CommunityStats {
-                            total_posts: 0,
-                            total_contributors: 0,
-                            total_comments: 0,
-                        },
-                        posts: vec![],
-                        pagination: PaginationMeta {
-                            offset: 0,
-                            limit: 0,
-                            total: None,
-                            has_more: false,
-                        },
-                        comments: vec![],
-                    }));
+                    return Ok(Json(gated_community_detail(community, false)));
                 }
             }
         }
@@ -1716,6 +2709,19 @@ pub async fn community_detail_json(
         (None, false)
     };
 
+    // Sensitive communities are gated until the viewer accepts the interstitial,
+    // after which posts are returned for the client to blur
+    let gate = sensitive_gate(
+        &community,
+        auth_session.user.as_ref(),
+        consent.accept_sensitive,
+    );
+    if gate == SensitiveGate::Interstitial {
+        return Ok(Json(gated_community_detail(community, true)));
+    }
+    let should_blur = gate == SensitiveGate::Blurred;
+    let viewer_show_sensitive = viewer_show_sensitive || community.is_sensitive;
+
     let posts = find_published_posts_by_community_id(
         &mut tx,
         community.id,
@@ -1725,28 +2731,58 @@ pub async fn community_detail_json(
         viewer_show_sensitive,
     )
     .await?;
+    let pinned_posts = get_pinned_posts_for_community(
+        &mut tx,
+        community.id,
+        viewer_user_id,
+        viewer_show_sensitive,
+    )
+    .await?;
     let comments = find_latest_comments_in_community(&mut tx, community.id, 5).await?;
     let stats = get_community_stats(&mut tx, community.id).await?;
+    let image_filenames: Vec<String> = posts
+        .iter()
+        .chain(pinned_posts.iter())
+        .map(|post| post.image_filename.clone())
+        .collect();
+    let thumbnails = find_thumbnails_by_image_filenames(
+        &mut tx,
+        &image_filenames,
+        thumbnail_query.thumbnail_width,
+    )
+    .await?;
+    let post_ids: Vec<Uuid> = posts
+        .iter()
+        .chain(pinned_posts.iter())
+        .map(|post| post.id)
+        .collect();
+    let image_counts = count_post_images_by_post_ids(&mut tx, &post_ids).await?;
 
     tx.commit().await?;
 
     // Convert posts to typed structs with minimal fields for thumbnails
-    let posts_typed: Vec<CommunityPostThumbnail> = posts
-        .into_iter()
-        .map(|post| {
-            let image_prefix = &post.image_filename[..2];
-            CommunityPostThumbnail {
-                id: post.id,
-                image_url: format!(
-                    "{}/image/{}/{}",
-                    state.config.r2_public_endpoint_url, image_prefix, post.image_filename
-                ),
-                image_width: post.image_width,
-                image_height: post.image_height,
-                is_sensitive: post.is_sensitive,
-            }
-        })
-        .collect();
+    let to_thumbnail = |post: SerializablePost| {
+        let image_prefix = &post.image_filename[..2];
+        CommunityPostThumbnail {
+            id: post.id,
+            image_url: format!(
+                "{}/image/{}/{}",
+                state.config.r2_public_endpoint_url, image_prefix, post.image_filename
+            ),
+            thumbnail_url: thumbnail_or_image_url(
+                &state.config.r2_public_endpoint_url,
+                &thumbnails,
+                &post.image_filename,
+            ),
+            image_width: post.image_width,
+            image_height: post.image_height,
+            is_sensitive: post.is_sensitive,
+            image_count: image_counts.get(&post.id).copied().unwrap_or(1),
+        }
+    };
+    let pinned_posts_typed: Vec<CommunityPostThumbnail> =
+        pinned_posts.into_iter().map(to_thumbnail).collect();
+    let posts_typed: Vec<CommunityPostThumbnail> = posts.into_iter().map(to_thumbnail).collect();
 
     // Convert comments to typed structs
     let comments_typed: Vec<CommunityComment> = comments
@@ -1792,12 +2828,15 @@ pub async fn community_detail_json(
             owner_id: community.owner_id,
             background_color: community.background_color,
             foreground_color: community.foreground_color,
+            is_sensitive: community.is_sensitive,
         },
+        should_blur,
         stats: CommunityStats {
             total_posts: stats.total_posts,
             total_contributors: stats.total_contributors,
             total_comments: stats.total_comments,
         },
+        pinned_posts: pinned_posts_typed,
         posts: posts_typed,
         pagination: PaginationMeta {
             offset: query.offset + query.limit,
@@ -1813,6 +2852,7 @@ pub async fn community_detail_json(
 pub async fn get_communities_list_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
+    Query(thumbnail_query): Query<ThumbnailQuery>,
 ) -> Result<Json<MyCommunitiesResponse>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -1874,6 +2914,21 @@ pub async fn get_communities_list_json(
         Vec::new()
     };
 
+    let image_filenames: Vec<String> = recent_posts
+        .iter()
+        .map(|post| post.image_filename.clone())
+        .collect();
+    let thumbnails = find_thumbnails_by_image_filenames(
+        &mut tx,
+        &image_filenames,
+        thumbnail_query.thumbnail_width,
+    )
+    .await?;
+    let post_ids: Vec<Uuid> = recent_posts.iter().map(|post| post.id).collect();
+    let image_counts = count_post_images_by_post_ids(&mut tx, &post_ids).await?;
+    let mut categories_by_community =
+        find_categories_by_community_ids(&mut tx, &all_community_ids).await?;
+
     tx.commit().await?;
 
     // Group posts by community_id
@@ -1889,9 +2944,15 @@ pub async fn get_communities_list_json(
                     "{}/image/{}/{}",
                     state.config.r2_public_endpoint_url, image_prefix, post.image_filename
                 ),
+                thumbnail_url: thumbnail_or_image_url(
+                    &state.config.r2_public_endpoint_url,
+                    &thumbnails,
+                    &post.image_filename,
+                ),
                 image_width: post.image_width,
                 image_height: post.image_height,
                 is_sensitive: post.is_sensitive,
+                image_count: image_counts.get(&post.id).copied().unwrap_or(1),
             });
         }
     }
@@ -1936,6 +2997,9 @@ pub async fn get_communities_list_json(
                 posts_count: Some(posts_count),
                 members_count,
                 recent_posts,
+                categories: categories_by_community
+                    .remove(&community.id)
+                    .unwrap_or_default(),
             }
         })
         .collect();
@@ -1943,10 +3007,19 @@ pub async fn get_communities_list_json(
     Ok(Json(MyCommunitiesResponse { communities }))
 }
 
+#[derive(Deserialize)]
+pub struct PublicCommunitiesQuery {
+    pub offset: i64,
+    pub limit: i64,
+    /// Slug of a category to list only its communities
+    pub category: Option<String>,
+}
+
 pub async fn get_public_communities_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
-    Query(query): Query<LoadMoreQuery>,
+    Query(query): Query<PublicCommunitiesQuery>,
+    Query(thumbnail_query): Query<ThumbnailQuery>,
 ) -> Result<Json<PublicCommunitiesResponse>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -1955,11 +3028,22 @@ pub async fn get_public_communities_json(
     let limit = query.limit.clamp(1, 100);
     let offset = query.offset.max(0);
 
+    let category_id = match query.category.as_deref().filter(|slug| !slug.is_empty()) {
+        Some(slug) => Some(
+            find_community_category_by_slug(&mut tx, slug)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Category".to_string()))?
+                .id,
+        ),
+        None => None,
+    };
+
     // Fetch paginated public communities
-    let public_communities_raw = get_public_communities_paginated(&mut tx, limit, offset).await?;
+    let public_communities_raw =
+        get_public_communities_paginated(&mut tx, limit, offset, category_id).await?;
 
     // Get total count
-    let total_count = count_public_communities(&mut tx).await?;
+    let total_count = count_public_communities(&mut tx, category_id).await?;
 
     // Collect all community IDs for batch queries
     let community_ids: Vec<Uuid> = public_communities_raw.iter().map(|c| c.id).collect();
@@ -1991,6 +3075,21 @@ pub async fn get_public_communities_json(
         Vec::new()
     };
 
+    let image_filenames: Vec<String> = recent_posts
+        .iter()
+        .map(|post| post.image_filename.clone())
+        .collect();
+    let thumbnails = find_thumbnails_by_image_filenames(
+        &mut tx,
+        &image_filenames,
+        thumbnail_query.thumbnail_width,
+    )
+    .await?;
+    let post_ids: Vec<Uuid> = recent_posts.iter().map(|post| post.id).collect();
+    let image_counts = count_post_images_by_post_ids(&mut tx, &post_ids).await?;
+    let mut categories_by_community =
+        find_categories_by_community_ids(&mut tx, &community_ids).await?;
+
     tx.commit().await?;
 
     // Group posts by community_id
@@ -2006,9 +3105,15 @@ pub async fn get_public_communities_json(
                     "{}/image/{}/{}",
                     state.config.r2_public_endpoint_url, image_prefix, post.image_filename
                 ),
+                thumbnail_url: thumbnail_or_image_url(
+                    &state.config.r2_public_endpoint_url,
+                    &thumbnails,
+                    &post.image_filename,
+                ),
                 image_width: post.image_width,
                 image_height: post.image_height,
                 is_sensitive: post.is_sensitive,
+                image_count: image_counts.get(&post.id).copied().unwrap_or(1),
             });
         }
     }
@@ -2042,6 +3147,9 @@ pub async fn get_public_communities_json(
                 posts_count: community.posts_count,
                 members_count,
                 recent_posts,
+                categories: categories_by_community
+                    .remove(&community.id)
+                    .unwrap_or_default(),
             }
         })
         .collect();
@@ -2076,6 +3184,7 @@ pub async fn search_public_communities_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
     Query(query): Query<SearchQuery>,
+    Query(thumbnail_query): Query<ThumbnailQuery>,
 ) -> Result<Json<PublicCommunitiesResponse>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -2136,6 +3245,21 @@ pub async fn search_public_communities_json(
         Vec::new()
     };
 
+    let image_filenames: Vec<String> = recent_posts
+        .iter()
+        .map(|post| post.image_filename.clone())
+        .collect();
+    let thumbnails = find_thumbnails_by_image_filenames(
+        &mut tx,
+        &image_filenames,
+        thumbnail_query.thumbnail_width,
+    )
+    .await?;
+    let post_ids: Vec<Uuid> = recent_posts.iter().map(|post| post.id).collect();
+    let image_counts = count_post_images_by_post_ids(&mut tx, &post_ids).await?;
+    let mut categories_by_community =
+        find_categories_by_community_ids(&mut tx, &community_ids).await?;
+
     tx.commit().await?;
 
     // Group posts by community_id
@@ -2151,9 +3275,15 @@ pub async fn search_public_communities_json(
                     "{}/image/{}/{}",
                     state.config.r2_public_endpoint_url, image_prefix, post.image_filename
                 ),
+                thumbnail_url: thumbnail_or_image_url(
+                    &state.config.r2_public_endpoint_url,
+                    &thumbnails,
+                    &post.image_filename,
+                ),
                 image_width: post.image_width,
                 image_height: post.image_height,
                 is_sensitive: post.is_sensitive,
+                image_count: image_counts.get(&post.id).copied().unwrap_or(1),
             });
         }
     }
@@ -2187,6 +3317,9 @@ pub async fn search_public_communities_json(
                 posts_count: community.posts_count,
                 members_count,
                 recent_posts,
+                categories: categories_by_community
+                    .remove(&community.id)
+                    .unwrap_or_default(),
             }
         })
         .collect();
@@ -2266,7 +3399,9 @@ pub async fn get_community_members_json(
                 user_id: m.user_id,
                 username: m.login_name,
                 display_name: m.display_name,
-                avatar_url: None, // TODO: Add avatar support to database query
+                avatar_url: m
+                    .avatar_filename
+                    .map(|f| image_url(&state.config.r2_public_endpoint_url, &f)),
                 role: role_str.to_string(),
                 joined_at: m.joined_at,
                 invited_by_username: None, // TODO: Add to database query if needed
@@ -2330,77 +3465,88 @@ pub async fn invite_user_json(
     }
 
     // Create invitation
-    create_invitation(&mut tx, community.id, user.id, invitee.id).await?;
+    create_invitation(
+        &mut tx,
+        community.id,
+        user.id,
+        invitee.id,
+        CommunityMemberRole::Member,
+        state.config.community_invitation_expires_at(),
+    )
+    .await?;
 
     // Get invitee's language preference before committing transaction
     let invitee_language = get_user_language_preference(&mut tx, invitee.id).await.ok().flatten();
 
     tx.commit().await?;
+    notify_invited_users(
+        &state,
+        user,
+        &community,
+        vec![InvitedUser {
+            id: invitee.id,
+            language: invitee_language,
+        }],
+    )
+    .await;
 
-    // Send push notification to invitee with localized message
-    let (title, body) = format_community_invitation_message(
-        "invite",
-        invitee_language,
-        &user.display_name,
-        &community.slug,
-    );
+    Ok(StatusCode::CREATED)
+}
 
-    let mut data = serde_json::Map::new();
-    data.insert(
-        "community_id".to_string(),
-        serde_json::json!(community.id.to_string()),
-    );
-    data.insert(
-        "community_slug".to_string(),
-        serde_json::json!(community.slug),
-    );
-    data.insert(
-        "notification_type".to_string(),
-        serde_json::json!("community_invite"),
-    );
+/// Invite several users to a community at once (JSON API for mobile)
+#[derive(Deserialize)]
+pub struct InviteUsersRequest {
+    pub login_names: Vec<String>,
+}
 
-    tracing::info!(
-        "Sending community invitation push notification to user {}: title={}, body={}",
-        invitee.id,
-        title,
-        body
-    );
+pub async fn invite_users_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Json(request): Json<InviteUsersRequest>,
+) -> Result<Response, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
 
-    // Get unread notification count for badge
-    let mut badge_tx = db.begin().await?;
-    let unread_count = crate::models::notification::get_unread_count(&mut badge_tx, invitee.id)
-        .await
-        .ok();
-    let _ = badge_tx.commit().await;
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
 
-    // Send push notification (don't fail if this errors)
-    match state
-        .push_service
-        .send_notification_to_user(
-            invitee.id,
-            &title,
-            &body,
-            unread_count.map(|c| c as u32), // badge count
-            Some(serde_json::Value::Object(data)),
-        )
-        .await
-    {
-        Ok(_) => {
-            tracing::info!(
-                "Successfully sent community invitation push notification to user {}",
-                invitee.id
-            );
-        }
-        Err(e) => {
-            tracing::warn!(
-                "Failed to send community invitation push notification to user {}: {:?}",
-                invitee.id,
-                e
-            );
-        }
+    // Find community
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    let community = match community {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+
+    // Check if user has permission to invite (owner or moderator)
+    let role = get_user_role_in_community(&mut tx, user.id, community.id).await?;
+    match role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
     }
 
-    Ok(StatusCode::CREATED)
+    let login_names = parse_login_names(&request.login_names.join(","));
+    if login_names.is_empty() || login_names.len() > MAX_INVITES_PER_REQUEST {
+        return Ok(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    let (results, invited) = invite_users_by_login_name(
+        &mut tx,
+        community.id,
+        user.id,
+        login_names,
+        state.config.community_invitation_expires_at(),
+    )
+    .await?;
+    tx.commit().await?;
+
+    notify_invited_users(&state, user, &community, invited).await;
+
+    Ok(Json(InviteUsersResponse { results }).into_response())
 }
 
 /// Remove a member from a community (JSON API for mobile)
@@ -2454,6 +3600,41 @@ pub async fn remove_member_json(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Change a member's role (JSON API for mobile)
+pub async fn set_member_role_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, user_id)): Path<(String, Uuid)>,
+    Json(request): Json<SetMemberRoleRequest>,
+) -> Result<StatusCode, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    // Find community
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    let community = match community {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND),
+    };
+
+    if let Err(status) =
+        change_member_role(&mut tx, user.id, community.id, user_id, &request.role).await?
+    {
+        return Ok(status);
+    }
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Leave a community (HTMX)
 pub async fn do_leave_community(
     auth_session: AuthSession,
@@ -2467,8 +3648,7 @@ pub async fn do_leave_community(
         None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
     };
 
-    let user_preferred_language = user.preferred_language.clone();
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, Some(user));
 
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -2588,6 +3768,9 @@ pub async fn get_community_invitations_json(
     // Get pending invitations with invitee details
     let invitations =
         get_pending_invitations_with_invitee_details_for_community(&mut tx, community.id).await?;
+    let user_avatar_url = find_user_avatar_filename(&mut tx, user.id)
+        .await?
+        .map(|f| image_url(&state.config.r2_public_endpoint_url, &f));
 
     // Convert to response format
     // Note: Current model only has invitee details, need to fetch inviter separately or enhance the model
@@ -2600,15 +3783,18 @@ pub async fn get_community_invitations_json(
                 id: inv.invitee_id,
                 username: inv.invitee_login_name,
                 display_name: inv.invitee_display_name,
-                avatar_url: None,
+                avatar_url: inv
+                    .invitee_avatar_filename
+                    .map(|f| image_url(&state.config.r2_public_endpoint_url, &f)),
             },
             inviter: InvitationUserInfo {
                 id: user.id, // Using current user as placeholder - TODO: fetch actual inviter
                 username: user.login_name.clone(),
                 display_name: user.display_name.clone(),
-                avatar_url: None,
+                avatar_url: user_avatar_url.clone(),
             },
             created_at: inv.created_at,
+            expires_at: inv.expires_at,
         })
         .collect();
 
@@ -2661,9 +3847,12 @@ pub async fn get_user_invitations_json(
                     id: inv.inviter_id,
                     username: inv.inviter_login_name,
                     display_name: inv.inviter_display_name,
-                    avatar_url: None,
+                    avatar_url: inv
+                        .inviter_avatar_filename
+                        .map(|f| image_url(&state.config.r2_public_endpoint_url, &f)),
                 },
                 created_at: inv.created_at,
+                expires_at: inv.expires_at,
             });
         }
     }
@@ -2740,6 +3929,8 @@ pub struct CreateCommunityRequest {
     pub slug: String,
     pub description: String,
     pub visibility: String,
+    #[serde(default)]
+    pub is_sensitive: bool,
 }
 
 pub async fn create_community_json(
@@ -2792,188 +3983,1541 @@ pub async fn create_community_json(
             .into_response());
     }
 
-    // Parse visibility
-    let visibility = match request.visibility.to_lowercase().as_str() {
-        "public" => CommunityVisibility::Public,
-        "unlisted" => CommunityVisibility::Unlisted,
-        "private" => CommunityVisibility::Private,
-        _ => CommunityVisibility::Public,
-    };
+    // Parse visibility
+    let visibility = match request.visibility.to_lowercase().as_str() {
+        "public" => CommunityVisibility::Public,
+        "unlisted" => CommunityVisibility::Unlisted,
+        "private" => CommunityVisibility::Private,
+        _ => CommunityVisibility::Public,
+    };
+
+    // Create community draft
+    let draft = CommunityDraft {
+        name: request.name,
+        slug: request.slug.clone(),
+        description: request.description,
+        visibility,
+        is_sensitive: request.is_sensitive,
+    };
+
+    // Create community (this already adds the owner as a member)
+    let community = match create_community(&mut tx, user.id, draft).await {
+        Ok(community) => community,
+        Err(e) => {
+            // Check if this is a duplicate slug error
+            if let Some(db_err) = e.downcast_ref::<sqlx::Error>() {
+                if let sqlx::Error::Database(ref db_error) = db_err {
+                    if db_error.constraint() == Some("communities_slug_key") {
+                        return Ok((
+                            StatusCode::CONFLICT,
+                            Json(ErrorResponse::new(
+                                "SLUG_ALREADY_EXISTS",
+                                "A community with this ID already exists",
+                            )),
+                        )
+                            .into_response());
+                    }
+                }
+            }
+            return Err(e.into());
+        }
+    };
+
+    apply_default_co_moderators(
+        &mut tx,
+        &community,
+        state.config.community_invitation_expires_at(),
+    )
+    .await?;
+
+    // Create ActivityPub actor only for non-private communities
+    if visibility != CommunityVisibility::Private {
+        create_actor_for_community(&mut tx, &community, &state.config).await?;
+    }
+
+    tx.commit().await?;
+    CommunityListingCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+
+    Ok(Json(CreateCommunityResponse {
+        community: CommunityInfo {
+            id: community.id,
+            name: community.name,
+            slug: community.slug,
+            description: community.description,
+            visibility: community.visibility,
+            owner_id: community.owner_id,
+            background_color: community.background_color,
+            foreground_color: community.foreground_color,
+            is_sensitive: community.is_sensitive,
+        },
+    })
+    .into_response())
+}
+
+/// Update community (JSON API for mobile)
+#[derive(Deserialize)]
+pub struct UpdateCommunityRequest {
+    pub name: String,
+    pub description: String,
+    pub visibility: String,
+    pub is_sensitive: Option<bool>,
+}
+
+pub async fn update_community_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Json(request): Json<UpdateCommunityRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    // Find community
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    if community.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    // Check if user is the owner
+    if user.id != community.owner_id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    // Parse new visibility
+    let new_visibility = match request.visibility.to_lowercase().as_str() {
+        "public" => CommunityVisibility::Public,
+        "unlisted" => CommunityVisibility::Unlisted,
+        "private" => CommunityVisibility::Private,
+        _ => community.visibility,
+    };
+
+    // Validate visibility change: can't change between private and public/unlisted
+    if (community.visibility == CommunityVisibility::Private
+        && new_visibility != CommunityVisibility::Private)
+        || (community.visibility != CommunityVisibility::Private
+            && new_visibility == CommunityVisibility::Private)
+    {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "INVALID_VISIBILITY_CHANGE",
+                "Cannot change visibility between private and public/unlisted",
+            )),
+        )
+            .into_response());
+    }
+
+    // Create updated draft
+    let draft = CommunityDraft {
+        name: request.name,
+        slug: community.slug.clone(), // Keep the same slug
+        description: request.description,
+        visibility: new_visibility,
+        is_sensitive: request.is_sensitive.unwrap_or(community.is_sensitive),
+    };
+
+    // Update community with ActivityPub notification
+    update_community_with_activity(&mut tx, community.id, draft, &state.config, None).await?;
+
+    tx.commit().await?;
+    CommunityListingCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+
+    Ok(StatusCode::OK.into_response())
+}
+
+/// DELETE handler for web interface (HTMX)
+pub async fn hx_delete_community(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    // Verify user is authenticated
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    // Deleting would strand a pending ownership transfer; it must be cancelled first
+    if let Some(community) =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string()).await?
+    {
+        if has_pending_ownership_transfer(&mut tx, community.id).await? {
+            return Ok(StatusCode::CONFLICT.into_response());
+        }
+    }
+
+    // Attempt to delete the community
+    soft_delete_community_with_activity(&mut tx, &slug, user.id, &state.config, Some(&state))
+        .await?;
+
+    tx.commit().await?;
+    CommunityListingCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+
+    // Redirect to communities list
+    Ok(([("HX-Redirect", "/communities")],).into_response())
+}
+
+/// DELETE handler for API (mobile apps)
+pub async fn delete_community_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+    // Verify user is authenticated
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    // Attempt to delete the community
+    soft_delete_community_with_activity(&mut tx, &slug, user.id, &state.config, Some(&state))
+        .await?;
+
+    tx.commit().await?;
+    CommunityListingCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+
+    Ok(StatusCode::OK)
+}
+
+// ========== Ownership Transfer Endpoints ==========
+
+const OWNERSHIP_TRANSFER_VALIDITY_HOURS: i64 = 72;
+
+#[derive(Deserialize)]
+pub struct TransferOwnershipForm {
+    login_name: String,
+}
+
+/// Propose a new owner and email them a confirmation link (owner only)
+pub async fn do_transfer_ownership(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    messages: Messages,
+    Form(form): Form<TransferOwnershipForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    if community.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    if community.owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let members_url = format!("/communities/@{}/members", community.slug);
+
+    let proposed_owner = find_user_by_login_name(&mut tx, form.login_name.trim()).await?;
+    let proposed_owner = match proposed_owner {
+        Some(proposed_owner) if proposed_owner.id != user.id => proposed_owner,
+        _ => {
+            messages.error(safe_get_message(&bundle, "community-invite-user-not-found"));
+            return Ok(Redirect::to(&members_url).into_response());
+        }
+    };
+
+    // The confirmation link goes out by email, so the new owner needs a verified address
+    let email = match (&proposed_owner.email, proposed_owner.email_verified_at) {
+        (Some(email), Some(_)) => email.clone(),
+        _ => {
+            messages.error(safe_get_message(&bundle, "community-transfer-no-email"));
+            return Ok(Redirect::to(&members_url).into_response());
+        }
+    };
+
+    let expires_at = Utc::now()
+        + TimeDelta::try_hours(OWNERSHIP_TRANSFER_VALIDITY_HOURS)
+            .expect("72 hours is a valid duration");
+    let transfer = create_ownership_transfer(
+        &mut tx,
+        community.id,
+        user.id,
+        proposed_owner.id,
+        expires_at,
+    )
+    .await?;
+    tx.commit().await?;
+
+    // Write the email in the recipient's language
+    let recipient_bundle = get_user_bundle(&accept_language, Some(&proposed_owner));
+    let mut args = FluentArgs::new();
+    args.set("community", community.name.clone());
+    args.set("owner", user.display_name.clone());
+    let email_message = Message::builder()
+        .from(safe_get_message(&recipient_bundle, "email-from-address").parse()?)
+        .to(email.parse()?)
+        .subject(safe_format_message(
+            &recipient_bundle,
+            "community-transfer-email-subject",
+            Some(&args),
+        ))
+        .body(format!(
+            "{}\n\nhttps://{}/communities/@{}/transfer/{}",
+            safe_format_message(
+                &recipient_bundle,
+                "community-transfer-email-body",
+                Some(&args)
+            ),
+            state.config.domain,
+            community.slug,
+            transfer.token
+        ))?;
+
+    let mailer = SmtpTransport::relay(&state.config.smtp_host)?
+        .credentials(SmtpCredentials::new(
+            state.config.smtp_user.clone(),
+            state.config.smtp_password.clone(),
+        ))
+        .build();
+    mailer.send(&email_message)?;
+
+    messages.success(safe_get_message(&bundle, "community-transfer-sent"));
+    Ok(Redirect::to(&members_url).into_response())
+}
+
+/// Confirmation page reached from the emailed link
+pub async fn ownership_transfer_page(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path((slug, token)): Path<(String, Uuid)>,
+    messages: Messages,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let (community, _transfer) =
+        match find_transfer_for_user(&mut tx, &slug, token, user.id).await? {
+            Ok(found) => found,
+            Err(status) => return Ok(status.into_response()),
+        };
+
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+
+    tx.commit().await?;
+
+    let template = state.env.get_template("community_transfer.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        community,
+        token,
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+/// Accept ownership (proposed owner only)
+pub async fn do_confirm_ownership_transfer(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path((slug, token)): Path<(String, Uuid)>,
+    messages: Messages,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let (community, transfer) = match find_transfer_for_user(&mut tx, &slug, token, user.id).await?
+    {
+        Ok(found) => found,
+        Err(status) => return Ok(status.into_response()),
+    };
+    if transfer.proposed_owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    confirm_ownership_transfer(&mut tx, &transfer).await?;
+    tx.commit().await?;
+
+    messages.success(safe_get_message(&bundle, "community-transfer-confirmed"));
+    Ok(Redirect::to(&format!("/communities/@{}/members", community.slug)).into_response())
+}
+
+/// Withdraw or decline a pending transfer (current or proposed owner)
+pub async fn do_cancel_ownership_transfer(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path((slug, token)): Path<(String, Uuid)>,
+    messages: Messages,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let (community, transfer) = match find_transfer_for_user(&mut tx, &slug, token, user.id).await?
+    {
+        Ok(found) => found,
+        Err(status) => return Ok(status.into_response()),
+    };
+
+    cancel_ownership_transfer(&mut tx, transfer.id).await?;
+    tx.commit().await?;
+
+    messages.success(safe_get_message(&bundle, "community-transfer-cancelled"));
+    Ok(Redirect::to(&format!("/communities/@{}", community.slug)).into_response())
+}
+
+/// Look up a pending transfer by token, visible only to the two users it involves
+async fn find_transfer_for_user(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    slug: &str,
+    token: Uuid,
+    user_id: Uuid,
+) -> Result<Result<(Community, CommunityOwnershipTransfer), StatusCode>, AppError> {
+    let slug = slug.strip_prefix('@').unwrap_or(slug).to_string();
+    let community = match find_community_by_slug(tx, slug).await? {
+        Some(community) => community,
+        None => return Ok(Err(StatusCode::NOT_FOUND)),
+    };
+
+    let transfer = match find_pending_ownership_transfer(tx, community.id).await? {
+        Some(transfer) if transfer.token == token => transfer,
+        _ => return Ok(Err(StatusCode::NOT_FOUND)),
+    };
+
+    if transfer.current_owner_id != user_id && transfer.proposed_owner_id != user_id {
+        return Ok(Err(StatusCode::FORBIDDEN));
+    }
+
+    Ok(Ok((community, transfer)))
+}
+
+// ========== Invite Link Endpoints ==========
+
+#[derive(Deserialize)]
+pub struct CreateInviteLinkForm {
+    /// Left empty for a link that can be used any number of times
+    max_uses: Option<String>,
+    /// Left empty for a link that never expires
+    expires_in_days: Option<String>,
+}
+
+/// Longest lifetime an invite link can be given
+const INVITE_LINK_MAX_VALIDITY_DAYS: i64 = 365;
+
+/// Parse a number between 1 and `max` from a form field, treating a blank field as absent
+fn parse_optional_limit(value: Option<&str>, max: i64) -> Result<Option<i64>, ()> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => match value.parse::<i64>() {
+            Ok(number) if (1..=max).contains(&number) => Ok(Some(number)),
+            _ => Err(()),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Generate a shareable invite link (owner or moderator only)
+pub async fn do_create_invite_link(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    messages: Messages,
+    Form(form): Form<CreateInviteLinkForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    if community.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    let role = get_user_role_in_community(&mut tx, user.id, community.id).await?;
+    match role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
+    }
+
+    let members_url = format!("/communities/@{}/members", community.slug);
+
+    let (Ok(max_uses), Ok(expires_in_days)) = (
+        parse_optional_limit(form.max_uses.as_deref(), i32::MAX.into()),
+        parse_optional_limit(
+            form.expires_in_days.as_deref(),
+            INVITE_LINK_MAX_VALIDITY_DAYS,
+        ),
+    ) else {
+        messages.error(safe_get_message(&bundle, "community-invite-link-invalid"));
+        return Ok(Redirect::to(&members_url).into_response());
+    };
+    let max_uses = max_uses.map(|max_uses| max_uses as i32);
+    let expires_at = expires_in_days
+        .and_then(TimeDelta::try_days)
+        .map(|validity| Utc::now() + validity);
+
+    create_invite_link(&mut tx, community.id, user.id, max_uses, expires_at).await?;
+    tx.commit().await?;
+
+    messages.success(safe_get_message(&bundle, "community-invite-link-created"));
+    Ok(Redirect::to(&members_url).into_response())
+}
+
+/// Revoke an invite link (owner or moderator only)
+pub async fn do_revoke_invite_link(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, link_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    if community.is_none() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    let role = get_user_role_in_community(&mut tx, user.id, community.id).await?;
+    match role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
+    }
+
+    if !delete_invite_link(&mut tx, community.id, link_id).await? {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+    tx.commit().await?;
+
+    // Return empty HTML for HTMX to remove the row
+    Ok(Html(String::new()).into_response())
+}
+
+/// Landing page for an invite link, showing what the visitor is about to join
+pub async fn invite_link_page(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(link_id): Path<Uuid>,
+    messages: Messages,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let link = match find_invite_link_by_id(&mut tx, link_id).await? {
+        Some(link) => link,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+    let community = match find_community_by_id(&mut tx, link.community_id).await? {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+
+    let is_member = match &auth_session.user {
+        Some(user) => is_user_member(&mut tx, user.id, community.id).await?,
+        None => false,
+    };
+
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let template = state.env.get_template("community_invite_link.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        community,
+        link_id,
+        is_usable => link.is_usable(),
+        is_member,
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+/// Join the community an invite link belongs to
+pub async fn do_join_via_invite_link(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path(link_id): Path<Uuid>,
+    messages: Messages,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let link = match find_invite_link_by_id(&mut tx, link_id).await? {
+        Some(link) => link,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+    let community = match find_community_by_id(&mut tx, link.community_id).await? {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+
+    let community_url = format!("/communities/@{}", community.slug);
+
+    if is_user_member(&mut tx, user.id, community.id).await? {
+        return Ok(Redirect::to(&community_url).into_response());
+    }
+
+    // Counting the use first keeps concurrent joins from going over the limit
+    if !use_invite_link(&mut tx, link.id).await? {
+        messages.error(safe_get_message(&bundle, "community-invite-link-unusable"));
+        return Ok(Redirect::to(&format!("/invite/{}", link.id)).into_response());
+    }
+
+    add_community_member(
+        &mut tx,
+        community.id,
+        user.id,
+        CommunityMemberRole::Member,
+        Some(link.created_by),
+    )
+    .await?;
+
+    tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
+
+    messages.success(safe_get_message(&bundle, "community-invite-link-joined"));
+    Ok(Redirect::to(&community_url).into_response())
+}
+
+// ========== Join Request Endpoints ==========
+
+/// Ask to join a community that takes join requests
+pub async fn do_request_to_join(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    messages: Messages,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        match find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?
+        {
+            Some(community) => community,
+            None => return Ok(StatusCode::NOT_FOUND.into_response()),
+        };
+
+    if community.visibility == CommunityVisibility::Private
+        || !community_accepts_join_requests(&mut tx, community.id).await?
+    {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let community_url = format!("/communities/@{}", community.slug);
+
+    if is_user_member(&mut tx, user.id, community.id).await? {
+        return Ok(Redirect::to(&community_url).into_response());
+    }
+
+    create_join_request(&mut tx, community.id, user.id).await?;
+
+    tx.commit().await?;
+
+    messages.success(safe_get_message(&bundle, "community-join-request-sent"));
+    Ok(Redirect::to(&community_url).into_response())
+}
+
+/// Approve a pending join request, adding the requester as a member
+pub async fn do_approve_join_request(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, join_request_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    review_join_request(auth_session, state, slug, join_request_id, true).await
+}
+
+/// Reject a pending join request
+pub async fn do_reject_join_request(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, join_request_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    review_join_request(auth_session, state, slug, join_request_id, false).await
+}
+
+/// Shared by approval and rejection. Responds with an empty body so the
+/// request's row on the members page is removed.
+async fn review_join_request(
+    auth_session: AuthSession,
+    state: AppState,
+    slug: String,
+    join_request_id: Uuid,
+    approve: bool,
+) -> Result<Response, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        match find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?
+        {
+            Some(community) => community,
+            None => return Ok(StatusCode::NOT_FOUND.into_response()),
+        };
+
+    // Only owners and moderators review join requests
+    let role = get_user_role_in_community(&mut tx, user.id, community.id).await?;
+    if !matches!(
+        role,
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)
+    ) {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let belongs_to_community = find_join_request_by_id(&mut tx, join_request_id)
+        .await?
+        .is_some_and(|join_request| join_request.community_id == community.id);
+    if !belongs_to_community {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    let reviewed = if approve {
+        approve_join_request(&mut tx, join_request_id, user.id).await?
+    } else {
+        reject_join_request(&mut tx, join_request_id, user.id).await?
+    };
+    // Another moderator got to it first
+    let join_request = match reviewed {
+        Some(join_request) => join_request,
+        None => return Ok(Html(String::new()).into_response()),
+    };
+
+    // Get requester's language preference before committing transaction
+    let requester_language = get_user_language_preference(&mut tx, join_request.requester_id)
+        .await
+        .ok()
+        .flatten();
+
+    tx.commit().await?;
+    if approve {
+        CommonContextCache::new(state.redis_pool.clone())
+            .invalidate(join_request.requester_id)
+            .await;
+    }
+
+    send_join_request_reviewed_notification(
+        &state,
+        join_request.requester_id,
+        requester_language,
+        &community,
+        approve,
+    )
+    .await;
+
+    Ok(Html(String::new()).into_response())
+}
+
+/// Tell the requester how their join request went. Failures are only logged.
+async fn send_join_request_reviewed_notification(
+    state: &AppState,
+    requester_id: Uuid,
+    requester_language: Option<Language>,
+    community: &Community,
+    approved: bool,
+) {
+    let (title, body) =
+        format_community_join_request_message(approved, requester_language, &community.slug);
+
+    let notification_type = if approved {
+        "join_request_approved"
+    } else {
+        "join_request_rejected"
+    };
+
+    let mut data = serde_json::Map::new();
+    data.insert(
+        "community_id".to_string(),
+        serde_json::json!(community.id.to_string()),
+    );
+    data.insert(
+        "community_slug".to_string(),
+        serde_json::json!(community.slug),
+    );
+    data.insert(
+        "notification_type".to_string(),
+        serde_json::json!(notification_type),
+    );
+
+    // Get unread notification count for badge
+    let unread_count = match state.db_pool.begin().await {
+        Ok(mut badge_tx) => {
+            let count = crate::models::notification::get_unread_count(&mut badge_tx, requester_id)
+                .await
+                .ok();
+            let _ = badge_tx.commit().await;
+            count
+        }
+        Err(_) => None,
+    };
+
+    if let Err(e) = state
+        .push_service
+        .send_notification_to_user(
+            requester_id,
+            None,
+            &title,
+            &body,
+            unread_count.map(|c| c as u32), // badge count
+            Some(serde_json::Value::Object(data)),
+        )
+        .await
+    {
+        tracing::warn!(
+            "Failed to send {} push notification to user {}: {:?}",
+            notification_type,
+            requester_id,
+            e
+        );
+    }
+}
+
+// ========== Post Approval Endpoints ==========
+
+/// Approve a post waiting for review, publishing and federating it
+pub async fn do_approve_pending_post(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, post_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    review_pending_post(auth_session, state, slug, post_id, true).await
+}
+
+/// Reject a post waiting for review, sending it back to the author's drafts
+pub async fn do_reject_pending_post(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, post_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    review_pending_post(auth_session, state, slug, post_id, false).await
+}
+
+/// Shared by approval and rejection. Responds with an empty body so the
+/// post's entry in the review queue is removed.
+async fn review_pending_post(
+    auth_session: AuthSession,
+    state: AppState,
+    slug: String,
+    post_id: Uuid,
+    approve: bool,
+) -> Result<Response, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        match find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?
+        {
+            Some(community) => community,
+            None => return Ok(StatusCode::NOT_FOUND.into_response()),
+        };
+
+    // Only owners and moderators review posts
+    let role = get_user_role_in_community(&mut tx, user.id, community.id).await?;
+    if !matches!(
+        role,
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)
+    ) {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let post = match find_pending_approval_post(&mut tx, post_id).await? {
+        Some(post) if post.community_id == community.id => post,
+        Some(_) => return Ok(StatusCode::NOT_FOUND.into_response()),
+        // Another moderator got to it first
+        None => return Ok(Html(String::new()).into_response()),
+    };
+
+    // An approval outside the posting window holds the post until it opens
+    let mut published = false;
+    if approve {
+        let now = Utc::now();
+        match find_closed_posting_window(&mut tx, community.id, post.author_id, now).await? {
+            Some(window) => {
+                let opens_at = window.next_opening(now).with_timezone(&Utc);
+                defer_post_until(&mut tx, post.id, opens_at, true).await?;
+            }
+            None => {
+                publish_post(
+                    &mut tx,
+                    post.id,
+                    post.title.clone().unwrap_or_default(),
+                    post.content.clone().unwrap_or_default(),
+                    post.is_sensitive,
+                    post.allow_relay,
+                )
+                .await?;
+                published = true;
+            }
+        }
+    } else {
+        return_pending_post_to_drafts(&mut tx, post.id).await?;
+    }
+
+    // Get author's language preference before committing transaction
+    let author_language = get_user_language_preference(&mut tx, post.author_id)
+        .await
+        .ok()
+        .flatten();
+
+    tx.commit().await?;
+
+    if published {
+        if let Err(e) = notify_and_federate_published_post(&state, post.id, post.author_id).await {
+            tracing::error!(
+                "Failed to deliver approved post {} to followers: {:?}",
+                post.id,
+                e
+            );
+        }
+    }
+
+    send_post_reviewed_notification(&state, &post, author_language, &community, approve).await;
+
+    Ok(Html(String::new()).into_response())
+}
+
+/// Tell the author how the review of their post went. Failures are only logged.
+async fn send_post_reviewed_notification(
+    state: &AppState,
+    post: &PendingApprovalPost,
+    author_language: Option<Language>,
+    community: &Community,
+    approved: bool,
+) {
+    let (title, body) = format_post_approval_message(approved, author_language, &community.slug);
+
+    let notification_type = if approved {
+        "post_approved"
+    } else {
+        "post_rejected"
+    };
+
+    let mut data = serde_json::Map::new();
+    data.insert(
+        "post_id".to_string(),
+        serde_json::json!(post.id.to_string()),
+    );
+    data.insert(
+        "community_id".to_string(),
+        serde_json::json!(community.id.to_string()),
+    );
+    data.insert(
+        "community_slug".to_string(),
+        serde_json::json!(community.slug),
+    );
+    data.insert(
+        "notification_type".to_string(),
+        serde_json::json!(notification_type),
+    );
+
+    // Get unread notification count for badge
+    let unread_count = match state.db_pool.begin().await {
+        Ok(mut badge_tx) => {
+            let count =
+                crate::models::notification::get_unread_count(&mut badge_tx, post.author_id)
+                    .await
+                    .ok();
+            let _ = badge_tx.commit().await;
+            count
+        }
+        Err(_) => None,
+    };
+
+    if let Err(e) = state
+        .push_service
+        .send_notification_to_user(
+            post.author_id,
+            None,
+            &title,
+            &body,
+            unread_count.map(|c| c as u32), // badge count
+            Some(serde_json::Value::Object(data)),
+        )
+        .await
+    {
+        tracing::warn!(
+            "Failed to send {} push notification to user {}: {:?}",
+            notification_type,
+            post.author_id,
+            e
+        );
+    }
+}
+
+// ========== Pinned Post Endpoints ==========
+
+#[derive(Deserialize)]
+pub struct PinPostForm {
+    pub post_id: Uuid,
+    pub position: Option<i32>,
+}
+
+/// Pin a post to the top of a community (owner or moderator only)
+pub async fn do_pin_post(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Form(form): Form<PinPostForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = match auth_session.user {
+        Some(user) => user,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    let community = match community {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+
+    let role = get_user_role_in_community(&mut tx, current_user.id, community.id).await?;
+    match role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
+    }
+
+    if let Err(e) = pin_post(&mut tx, community.id, form.post_id, form.position).await {
+        return Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response());
+    }
 
-    // Create community draft
-    let draft = CommunityDraft {
-        name: request.name,
-        slug: request.slug.clone(),
-        description: request.description,
-        visibility,
+    tx.commit().await?;
+
+    Ok(Redirect::to(&format!("/@{}", community.slug)).into_response())
+}
+
+/// Unpin a post from a community (owner or moderator only)
+pub async fn do_unpin_post(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path((slug, post_id)): Path<(String, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = match auth_session.user {
+        Some(user) => user,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
     };
 
-    // Create community (this already adds the owner as a member)
-    let community = match create_community(&mut tx, user.id, draft).await {
-        Ok(community) => community,
-        Err(e) => {
-            // Check if this is a duplicate slug error
-            if let Some(db_err) = e.downcast_ref::<sqlx::Error>() {
-                if let sqlx::Error::Database(ref db_error) = db_err {
-                    if db_error.constraint() == Some("communities_slug_key") {
-                        return Ok((
-                            StatusCode::CONFLICT,
-                            Json(ErrorResponse::new(
-                                "SLUG_ALREADY_EXISTS",
-                                "A community with this ID already exists",
-                            )),
-                        )
-                            .into_response());
-                    }
-                }
-            }
-            return Err(e.into());
-        }
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    let community = match community {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
     };
 
-    // Create ActivityPub actor only for non-private communities
-    if visibility != CommunityVisibility::Private {
-        create_actor_for_community(&mut tx, &community, &state.config).await?;
+    let role = get_user_role_in_community(&mut tx, current_user.id, community.id).await?;
+    match role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
     }
 
-    tx.commit().await?;
+    unpin_post(&mut tx, community.id, post_id).await?;
 
-    Ok(Json(CreateCommunityResponse {
-        community: CommunityInfo {
-            id: community.id,
-            name: community.name,
-            slug: community.slug,
-            description: community.description,
-            visibility: community.visibility,
-            owner_id: community.owner_id,
-            background_color: community.background_color,
-            foreground_color: community.foreground_color,
-        },
-    })
-    .into_response())
-}
+    tx.commit().await?;
 
-/// Update community (JSON API for mobile)
-#[derive(Deserialize)]
-pub struct UpdateCommunityRequest {
-    pub name: String,
-    pub description: String,
-    pub visibility: String,
+    // Return empty HTML for HTMX to remove the pinned item
+    Ok(Html(String::new()).into_response())
 }
 
-pub async fn update_community_json(
+/// Restore an archived community to discovery (owner only)
+pub async fn do_unarchive_community(
     auth_session: AuthSession,
     State(state): State<AppState>,
     Path(slug): Path<String>,
-    Json(request): Json<UpdateCommunityRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let user = match &auth_session.user {
-        Some(u) => u,
+    let current_user = match auth_session.user {
+        Some(user) => user,
         None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
     };
 
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    // Find community
     let community =
         find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
             .await?;
-    if community.is_none() {
-        return Ok(StatusCode::NOT_FOUND.into_response());
-    }
-    let community = community.ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+    let community = match community {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
 
-    // Check if user is the owner
-    if user.id != community.owner_id {
+    if community.owner_id != current_user.id {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
-    // Parse new visibility
-    let new_visibility = match request.visibility.to_lowercase().as_str() {
-        "public" => CommunityVisibility::Public,
-        "unlisted" => CommunityVisibility::Unlisted,
-        "private" => CommunityVisibility::Private,
-        _ => community.visibility,
+    let restored = unarchive_community(&mut tx, community.id).await?;
+    tx.commit().await?;
+
+    if restored {
+        CommunityListingCache::new(state.redis_pool.clone())
+            .invalidate()
+            .await;
+    }
+
+    Ok(Redirect::to(&format!("/communities/@{}", community.slug)).into_response())
+}
+
+const ANALYTICS_CACHE_TTL: u64 = 3600; // 1 hour
+const DEFAULT_ANALYTICS_PERIOD_DAYS: i64 = 30;
+const MAX_ANALYTICS_PERIOD_DAYS: i64 = 365;
+
+#[derive(Deserialize)]
+pub struct AnalyticsQuery {
+    /// Number of days followed by `d`, e.g. `30d`
+    pub period: Option<String>,
+}
+
+fn parse_analytics_period(period: Option<&str>) -> Option<i64> {
+    let Some(period) = period else {
+        return Some(DEFAULT_ANALYTICS_PERIOD_DAYS);
     };
+    period
+        .strip_suffix('d')
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| (1..=MAX_ANALYTICS_PERIOD_DAYS).contains(days))
+}
 
-    // Validate visibility change: can't change between private and public/unlisted
-    if (community.visibility == CommunityVisibility::Private
-        && new_visibility != CommunityVisibility::Private)
-        || (community.visibility != CommunityVisibility::Private
-            && new_visibility == CommunityVisibility::Private)
-    {
+async fn load_cached_analytics(
+    pool: &RedisPool,
+    key: &str,
+) -> anyhow::Result<Option<CommunityAnalytics>> {
+    let mut conn = pool.get().await?;
+    let value: Option<String> = conn.get(key).await?;
+    Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+}
+
+async fn save_cached_analytics(
+    pool: &RedisPool,
+    key: &str,
+    analytics: &CommunityAnalytics,
+) -> anyhow::Result<()> {
+    let mut conn = pool.get().await?;
+    conn.set_ex::<_, _, ()>(key, serde_json::to_string(analytics)?, ANALYTICS_CACHE_TTL)
+        .await?;
+    Ok(())
+}
+
+/// Posting activity of a community for its owner and moderators
+pub async fn get_community_analytics_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<AnalyticsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match &auth_session.user {
+        Some(u) => u,
+        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
+    };
+
+    let Some(period_days) = parse_analytics_period(query.period.as_deref()) else {
         return Ok((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse::new(
-                "INVALID_VISIBILITY_CHANGE",
-                "Cannot change visibility between private and public/unlisted",
+                "INVALID_PERIOD",
+                "Period must be between 1d and 365d",
             )),
         )
             .into_response());
-    }
+    };
 
-    // Create updated draft
-    let draft = CommunityDraft {
-        name: request.name,
-        slug: community.slug.clone(), // Keep the same slug
-        description: request.description,
-        visibility: new_visibility,
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?;
+    let community = match community {
+        Some(community) => community,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
     };
 
-    // Update community with ActivityPub notification
-    update_community_with_activity(&mut tx, community.id, draft, &state.config, None).await?;
+    let role = get_user_role_in_community(&mut tx, user.id, community.id).await?;
+    match role {
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator) => {}
+        _ => return Ok(StatusCode::FORBIDDEN.into_response()),
+    }
+
+    // Analytics are expensive to aggregate and don't need to be live; a cache
+    // failure only means computing them again
+    let cache_key = format!("analytics:{}:{}d", community.id, period_days);
+    match load_cached_analytics(&state.redis_pool, &cache_key).await {
+        Ok(Some(analytics)) => return Ok(Json(analytics).into_response()),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to load cached community analytics: {:?}", e),
+    }
 
+    let analytics = get_community_analytics(&mut tx, community.id, period_days).await?;
     tx.commit().await?;
 
-    Ok(StatusCode::OK.into_response())
+    if let Err(e) = save_cached_analytics(&state.redis_pool, &cache_key, &analytics).await {
+        tracing::warn!("Failed to cache community analytics: {:?}", e);
+    }
+
+    Ok(Json(analytics).into_response())
 }
 
-/// DELETE handler for web interface (HTMX)
-pub async fn hx_delete_community(
+const LEADERBOARD_CACHE_TTL: u64 = 3600; // 1 hour
+const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(Deserialize)]
+pub struct LeaderboardQuery {
+    #[serde(default)]
+    pub period: LeaderboardPeriod,
+}
+
+async fn load_cached_leaderboard(
+    pool: &RedisPool,
+    key: &str,
+) -> anyhow::Result<Option<Vec<CommunityLeaderboardEntry>>> {
+    let mut conn = pool.get().await?;
+    let value: Option<String> = conn.get(key).await?;
+    Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+}
+
+async fn save_cached_leaderboard(
+    pool: &RedisPool,
+    key: &str,
+    entries: &[CommunityLeaderboardEntry],
+) -> anyhow::Result<()> {
+    let mut conn = pool.get().await?;
+    conn.set_ex::<_, _, ()>(key, serde_json::to_string(entries)?, LEADERBOARD_CACHE_TTL)
+        .await?;
+    Ok(())
+}
+
+/// The community a leaderboard was requested for, if the viewer may see it.
+/// Private communities only show theirs to members.
+async fn find_leaderboard_community(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    slug: &str,
+    viewer: Option<&User>,
+) -> Result<Option<Community>, AppError> {
+    let slug = slug.strip_prefix('@').unwrap_or(slug);
+    let Some(community) = find_community_by_slug(tx, slug.to_string()).await? else {
+        return Ok(None);
+    };
+
+    if community.visibility == CommunityVisibility::Private {
+        let is_member = match viewer {
+            Some(user) => is_user_member(tx, user.id, community.id).await?,
+            None => false,
+        };
+        if !is_member {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(community))
+}
+
+/// Build the leaderboard shown to a viewer. The whole ranking is cached per
+/// period so every viewer can find their own rank in it.
+async fn build_community_leaderboard(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    state: &AppState,
+    community_id: Uuid,
+    period: LeaderboardPeriod,
+    viewer_id: Option<Uuid>,
+) -> Result<CommunityLeaderboardResponse, AppError> {
+    // Like analytics, a cache failure only means ranking everyone again
+    let cache_key = format!("leaderboard:{}:{}", community_id, period.as_str());
+    let cached = match load_cached_leaderboard(&state.redis_pool, &cache_key).await {
+        Ok(cached) => cached,
+        Err(e) => {
+            tracing::warn!("Failed to load cached community leaderboard: {:?}", e);
+            None
+        }
+    };
+
+    let mut entries = match cached {
+        Some(entries) => entries,
+        None => {
+            let entries = get_community_leaderboard(tx, community_id, period.since()).await?;
+            if let Err(e) = save_cached_leaderboard(&state.redis_pool, &cache_key, &entries).await {
+                tracing::warn!("Failed to cache community leaderboard: {:?}", e);
+            }
+            entries
+        }
+    };
+
+    let viewer_entry = viewer_id.and_then(|viewer_id| {
+        entries
+            .iter()
+            .find(|entry| entry.user_id == viewer_id)
+            .cloned()
+    });
+    entries.truncate(LEADERBOARD_SIZE);
+
+    Ok(CommunityLeaderboardResponse {
+        period,
+        entries,
+        viewer_entry,
+    })
+}
+
+/// Top contributors of a community over the last week, month or all time
+pub async fn get_community_leaderboard_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
     Path(slug): Path<String>,
+    Query(query): Query<LeaderboardQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    // Verify user is authenticated
-    let user = match &auth_session.user {
-        Some(u) => u,
-        None => return Ok(StatusCode::UNAUTHORIZED.into_response()),
-    };
-
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    // Attempt to delete the community
-    soft_delete_community_with_activity(&mut tx, &slug, user.id, &state.config, Some(&state))
-        .await?;
+    let Some(community) =
+        find_leaderboard_community(&mut tx, &slug, auth_session.user.as_ref()).await?
+    else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
 
+    let leaderboard = build_community_leaderboard(
+        &mut tx,
+        &state,
+        community.id,
+        query.period,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
     tx.commit().await?;
 
-    // Redirect to communities list
-    Ok(([("HX-Redirect", "/communities")],).into_response())
+    Ok(Json(leaderboard).into_response())
 }
 
-/// DELETE handler for API (mobile apps)
-pub async fn delete_community_json(
+/// HTMX: leaderboard section of the community page, loaded once it scrolls into view
+pub async fn hx_community_leaderboard(
     auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
     Path(slug): Path<String>,
-) -> Result<StatusCode, AppError> {
-    // Verify user is authenticated
-    let user = match &auth_session.user {
-        Some(u) => u,
-        None => return Ok(StatusCode::UNAUTHORIZED),
-    };
-
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<impl IntoResponse, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    // Attempt to delete the community
-    soft_delete_community_with_activity(&mut tx, &slug, user.id, &state.config, Some(&state))
-        .await?;
+    let Some(community) =
+        find_leaderboard_community(&mut tx, &slug, auth_session.user.as_ref()).await?
+    else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
 
+    let leaderboard = build_community_leaderboard(
+        &mut tx,
+        &state,
+        community.id,
+        query.period,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
     tx.commit().await?;
 
-    Ok(StatusCode::OK)
+    let template = state.env.get_template("community_leaderboard.jinja")?;
+    let rendered = template.render(context! {
+        community_slug => community.slug,
+        period => leaderboard.period.as_str(),
+        entries => leaderboard.entries,
+        viewer_entry => leaderboard.viewer_entry,
+        leaderboard_size => LEADERBOARD_SIZE,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn community(is_sensitive: bool) -> Community {
+        let now = Utc::now();
+        Community {
+            id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Night drawings".to_string(),
+            slug: "night".to_string(),
+            description: String::new(),
+            visibility: CommunityVisibility::Public,
+            updated_at: now,
+            created_at: now,
+            background_color: None,
+            foreground_color: None,
+            is_sensitive,
+        }
+    }
+
+    fn viewer(show_sensitive_content: bool) -> User {
+        let now = Utc::now();
+        User {
+            id: Uuid::new_v4(),
+            login_name: "viewer".to_string(),
+            password_hash: String::new(),
+            display_name: "viewer".to_string(),
+            email: None,
+            email_verified_at: None,
+            created_at: now,
+            updated_at: now,
+            banner_id: None,
+            preferred_language: None,
+            language_fallbacks: Vec::new(),
+            deleted_at: None,
+            show_sensitive_content,
+            is_admin: false,
+            is_locked: false,
+        }
+    }
+
+    #[test]
+    fn anonymous_viewer_of_sensitive_community_is_gated() {
+        let community = community(true);
+
+        assert_eq!(
+            sensitive_gate(&community, None, false),
+            SensitiveGate::Interstitial
+        );
+        let detail = gated_community_detail(community, true);
+        assert!(detail.should_blur);
+        assert!(detail.posts.is_empty());
+        assert!(detail.pinned_posts.is_empty());
+        assert!(detail.comments.is_empty());
+    }
+
+    #[test]
+    fn accepted_interstitial_shows_posts_blurred() {
+        assert_eq!(
+            sensitive_gate(&community(true), None, true),
+            SensitiveGate::Blurred
+        );
+        assert_eq!(
+            sensitive_gate(&community(true), Some(&viewer(false)), true),
+            SensitiveGate::Blurred
+        );
+    }
+
+    #[test]
+    fn opted_in_viewers_and_other_communities_are_open() {
+        assert_eq!(
+            sensitive_gate(&community(true), Some(&viewer(true)), false),
+            SensitiveGate::Open
+        );
+        assert_eq!(
+            sensitive_gate(&community(false), None, false),
+            SensitiveGate::Open
+        );
+    }
 }
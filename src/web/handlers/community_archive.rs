@@ -0,0 +1,156 @@
+use crate::community_listing_cache::CommunityListingCache;
+use crate::models::community::{
+    archive_community, clear_archive_notices_for_active_communities, find_communities_to_archive,
+    find_communities_to_warn_about_archival, mark_community_archive_notice_sent, InactiveCommunity,
+};
+use crate::models::user::find_user_by_id;
+use crate::web::handlers::{get_user_bundle, safe_format_message, safe_get_message};
+use crate::web::state::AppState;
+use axum::http::HeaderValue;
+use chrono::{TimeDelta, Utc};
+use fluent::FluentArgs;
+use lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
+use lettre::{Message, SmtpTransport, Transport};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const ARCHIVE_INTERVAL_SECONDS: u64 = 3600;
+const ARCHIVE_BATCH_SIZE: i64 = 100;
+
+/// Periodically archive communities that have gone without posts, warning their
+/// owners first. Archived communities only drop out of discovery; owners can restore them.
+pub async fn archive_inactive_communities(state: AppState) {
+    info!(
+        "Starting community archiver (interval: {}s, after: {} days, notice: {} days)",
+        ARCHIVE_INTERVAL_SECONDS,
+        state.config.community_auto_archive_after_days,
+        state.config.community_auto_archive_notice_days
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(ARCHIVE_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = archive_due_communities(&state).await {
+            error!("Failed to archive inactive communities: {:?}", e);
+        }
+    }
+}
+
+async fn archive_due_communities(state: &AppState) -> anyhow::Result<()> {
+    let after_days = state.config.community_auto_archive_after_days;
+    let notice_days = state
+        .config
+        .community_auto_archive_notice_days
+        .clamp(0, after_days);
+    let now = Utc::now();
+    let inactive_since = now
+        - TimeDelta::try_days(after_days)
+            .ok_or_else(|| anyhow::anyhow!("Invalid community_auto_archive_after_days"))?;
+    let notice_window = TimeDelta::try_days(notice_days)
+        .ok_or_else(|| anyhow::anyhow!("Invalid community_auto_archive_notice_days"))?;
+
+    let mut tx = state.db_pool.begin().await?;
+    // A post since the warning means the community is active again
+    let cleared = clear_archive_notices_for_active_communities(&mut tx).await?;
+    let to_warn = find_communities_to_warn_about_archival(
+        &mut tx,
+        inactive_since + notice_window,
+        ARCHIVE_BATCH_SIZE,
+    )
+    .await?;
+    let to_archive = find_communities_to_archive(
+        &mut tx,
+        inactive_since,
+        now - notice_window,
+        ARCHIVE_BATCH_SIZE,
+    )
+    .await?;
+    tx.commit().await?;
+
+    if cleared > 0 {
+        info!(
+            "Withdrew archival notices for {} active communities",
+            cleared
+        );
+    }
+
+    for community in to_warn {
+        // Record the notice even if the email can't be delivered, so archival isn't
+        // held back forever by an owner without a verified address
+        if let Err(e) = send_archive_notice(state, &community).await {
+            warn!(
+                "Failed to email archival notice for community {}: {:?}",
+                community.slug, e
+            );
+        }
+        let mut tx = state.db_pool.begin().await?;
+        mark_community_archive_notice_sent(&mut tx, community.id).await?;
+        tx.commit().await?;
+    }
+
+    if to_archive.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = state.db_pool.begin().await?;
+    for community in &to_archive {
+        archive_community(&mut tx, community.id).await?;
+    }
+    tx.commit().await?;
+
+    CommunityListingCache::new(state.redis_pool.clone())
+        .invalidate()
+        .await;
+    info!("Archived {} inactive communities", to_archive.len());
+
+    Ok(())
+}
+
+async fn send_archive_notice(
+    state: &AppState,
+    community: &InactiveCommunity,
+) -> anyhow::Result<()> {
+    let mut tx = state.db_pool.begin().await?;
+    let owner = find_user_by_id(&mut tx, community.owner_id).await?;
+    tx.commit().await?;
+
+    let Some(owner) = owner else {
+        return Ok(());
+    };
+    let email = match (&owner.email, owner.email_verified_at) {
+        (Some(email), Some(_)) => email.clone(),
+        _ => return Ok(()),
+    };
+
+    // No request to negotiate from, so this falls back to the owner's saved languages
+    let bundle = get_user_bundle(&HeaderValue::from_static(""), Some(&owner));
+    let mut args = FluentArgs::new();
+    args.set("community", community.name.clone());
+    args.set("days", state.config.community_auto_archive_notice_days);
+    let email_message = Message::builder()
+        .from(safe_get_message(&bundle, "email-from-address").parse()?)
+        .to(email.parse()?)
+        .subject(safe_format_message(
+            &bundle,
+            "community-archive-email-subject",
+            Some(&args),
+        ))
+        .body(format!(
+            "{}\n\nhttps://{}/communities/@{}",
+            safe_format_message(&bundle, "community-archive-email-body", Some(&args)),
+            state.config.domain,
+            community.slug
+        ))?;
+
+    let mailer = SmtpTransport::relay(&state.config.smtp_host)?
+        .credentials(SmtpCredentials::new(
+            state.config.smtp_user.clone(),
+            state.config.smtp_password.clone(),
+        ))
+        .build();
+    mailer.send(&email_message)?;
+
+    Ok(())
+}
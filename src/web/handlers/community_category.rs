@@ -0,0 +1,149 @@
+use crate::app_error::AppError;
+use crate::models::community_category::{
+    create_community_category, delete_community_category, find_community_category_by_slug,
+    list_community_categories, CommunityCategoryDraft,
+};
+use crate::models::user::AuthSession;
+use crate::web::context::CommonContext;
+use crate::web::handlers::{render_403, ExtractFtlLang};
+use crate::web::state::AppState;
+use axum::extract::{Path, State};
+use axum::response::{Html, IntoResponse, Redirect};
+use axum::Form;
+use minijinja::context;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Admin page: GET /admin/community-categories
+pub async fn admin_community_categories(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let categories = list_community_categories(&mut tx).await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("admin_community_categories.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        categories,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct CreateCommunityCategoryForm {
+    pub slug: String,
+    pub name: String,
+    /// Lower comes first; empty means 0
+    pub position: String,
+}
+
+/// Admin endpoint: POST /admin/community-categories
+pub async fn do_create_community_category(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Form(form): Form<CreateCommunityCategoryForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let slug = form.slug.trim().to_lowercase();
+    if slug.is_empty()
+        || !slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(AppError::InvalidFormData(
+            "Category slug may only contain a-z, 0-9 and -".to_string(),
+        ));
+    }
+    let name = form.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::InvalidFormData(
+            "Category name is empty".to_string(),
+        ));
+    }
+    let position = match form.position.trim() {
+        "" => 0,
+        value => value
+            .parse::<i32>()
+            .map_err(|e| AppError::InvalidFormData(format!("Invalid position: {}", e)))?,
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if find_community_category_by_slug(&mut tx, &slug)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::InvalidFormData(format!(
+            "Category {} already exists",
+            slug
+        )));
+    }
+
+    create_community_category(
+        &mut tx,
+        CommunityCategoryDraft {
+            slug,
+            name,
+            position,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Redirect::to("/admin/community-categories").into_response())
+}
+
+/// Admin endpoint: POST /admin/community-categories/:id/delete
+pub async fn do_delete_community_category(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(category_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if !delete_community_category(&mut tx, category_id).await? {
+        return Err(AppError::NotFound("Category".to_string()));
+    }
+
+    tx.commit().await?;
+
+    Ok(Redirect::to("/admin/community-categories").into_response())
+}
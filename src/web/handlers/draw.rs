@@ -1,9 +1,17 @@
 use crate::app_error::AppError;
+use crate::common_context_cache::CommonContextCache;
+use crate::image_optimization::{encode_webp, generate_thumbnail};
 use crate::models::banner::{create_banner, BannerDraft};
 use crate::models::community::find_community_by_id;
-use crate::models::post::{create_post, find_post_by_id, PostDraft, Tool};
+use crate::models::image::{create_image_thumbnail, image_filename_taken, ImageThumbnail};
+use crate::models::post::{
+    add_post_image, create_post, find_post_by_id, lock_draft_image_for_autosave,
+    mark_draft_autosaved, AutosavedImage, PostDraft, Tool, MAX_POST_IMAGES,
+};
 use crate::models::user::{update_user_preferred_language, AuthSession};
+use crate::replay_verification::{verify_replay, ReplayFormat};
 use crate::web::context::CommonContext;
+use crate::web::handlers::post::post_author_id;
 use crate::web::handlers::{
     detect_preferred_language, safe_decode_hash, safe_parse_uuid, ExtractAcceptLanguage,
     ExtractFtlLang,
@@ -13,11 +21,12 @@ use aws_sdk_s3::config::{Credentials as AwsCredentials, Region, SharedCredential
 use aws_sdk_s3::error::SdkError;
 use aws_sdk_s3::operation::put_object::{PutObjectError, PutObjectOutput};
 use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{Delete, ObjectIdentifier};
 use aws_sdk_s3::Client;
 use axum::response::{IntoResponse, Redirect};
 use axum::Json;
 use axum::{
-    extract::{Multipart, State},
+    extract::{Multipart, Path, State},
     http::StatusCode,
     response::Html,
     Form,
@@ -72,8 +81,12 @@ pub async fn start_draw(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let community_id = input
         .community_id
@@ -112,6 +125,7 @@ pub async fn start_draw(
         parent_post_id => input.parent_post_id,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -221,6 +235,7 @@ pub async fn upload_object(
     bytes: Vec<u8>,
     key: &str,
     checksum_sha256: &str,
+    content_type: &str,
 ) -> Result<PutObjectOutput, SdkError<PutObjectError>> {
     let body = ByteStream::from(bytes);
     client
@@ -228,11 +243,149 @@ pub async fn upload_object(
         .bucket(bucket_name)
         .key(key)
         .checksum_sha256(checksum_sha256)
+        .content_type(content_type)
         .body(body)
         .send()
         .await
 }
 
+/// Re-encode an uploaded PNG as WebP and store it next to the original.
+///
+/// Returns the WebP filename on success. Failures are logged and swallowed so
+/// that a broken encode never blocks publishing the PNG.
+async fn upload_webp(
+    client: &Client,
+    state: &AppState,
+    png: Vec<u8>,
+    image_sha256: &str,
+) -> Option<String> {
+    let quality = state.config.webp_quality;
+    let webp = match tokio::task::spawn_blocking(move || encode_webp(&png, quality)).await {
+        Ok(Ok(webp)) => webp,
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to encode WebP for {}: {}", image_sha256, e);
+            return None;
+        }
+        Err(e) => {
+            tracing::warn!("WebP encoding task panicked for {}: {}", image_sha256, e);
+            return None;
+        }
+    };
+
+    let webp_sha256 = digest(&webp);
+    let checksum = match safe_decode_hash(&webp_sha256) {
+        Ok(hash) => BASE64.encode(&hash),
+        Err(_) => return None,
+    };
+
+    // Keyed by the PNG hash so both variants live under the same prefix
+    let key = format!("image/{}/{}.webp", &image_sha256[0..2], image_sha256);
+    if let Err(e) = upload_object(
+        client,
+        &state.config.aws_s3_bucket,
+        webp,
+        &key,
+        &checksum,
+        "image/webp",
+    )
+    .await
+    {
+        tracing::warn!("Failed to upload WebP for {}: {}", image_sha256, e);
+        return None;
+    }
+
+    Some(format!("{}.webp", image_sha256))
+}
+
+/// Generate the configured thumbnail sizes of an uploaded PNG and store them under `thumb/`.
+///
+/// Sizes the drawing already fits within are skipped, as are any that fail to
+/// encode or upload; those fall back to the original when served.
+async fn upload_thumbnails(
+    client: &Client,
+    state: &AppState,
+    png: Vec<u8>,
+    image_sha256: &str,
+) -> Vec<ImageThumbnail> {
+    let mut thumbnails = Vec::new();
+    let webp_quality = state
+        .config
+        .webp_enabled
+        .then_some(state.config.webp_quality);
+    let (extension, content_type) = if webp_quality.is_some() {
+        ("webp", "image/webp")
+    } else {
+        ("png", "image/png")
+    };
+
+    for &max_size in &state.config.thumbnail_sizes {
+        let png = png.clone();
+        let thumbnail = match tokio::task::spawn_blocking(move || {
+            generate_thumbnail(&png, max_size, webp_quality)
+        })
+        .await
+        {
+            Ok(Ok(Some(thumbnail))) => thumbnail,
+            Ok(Ok(None)) => continue,
+            Ok(Err(e)) => {
+                tracing::warn!(
+                    "Failed to generate {}px thumbnail for {}: {}",
+                    max_size,
+                    image_sha256,
+                    e
+                );
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Thumbnail task panicked for {} at {}px: {}",
+                    image_sha256,
+                    max_size,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let checksum = match safe_decode_hash(&digest(&thumbnail.bytes)) {
+            Ok(hash) => BASE64.encode(&hash),
+            Err(_) => continue,
+        };
+
+        // Keyed by the PNG hash so every variant of a drawing shares its shard
+        let thumbnail_filename = format!("{}_{}.{}", image_sha256, max_size, extension);
+        let key = format!("thumb/{}/{}", &image_sha256[0..2], thumbnail_filename);
+        if let Err(e) = upload_object(
+            client,
+            &state.config.aws_s3_bucket,
+            thumbnail.bytes,
+            &key,
+            &checksum,
+            content_type,
+        )
+        .await
+        {
+            tracing::warn!(
+                "Failed to upload {}px thumbnail for {}: {}",
+                max_size,
+                image_sha256,
+                e
+            );
+            continue;
+        }
+
+        thumbnails.push(ImageThumbnail {
+            image_filename: format!("{}.png", image_sha256),
+            max_size: max_size as i32,
+            width: thumbnail.width as i32,
+            height: thumbnail.height as i32,
+            thumbnail_filename,
+        });
+    }
+
+    thumbnails
+}
+
 #[derive(Serialize)]
 pub struct DrawFinishResponse {
     pub community_id: Option<String>,
@@ -266,11 +419,17 @@ pub async fn draw_finish(
     let mut image_sha256 = String::new();
     let mut replay_sha256 = String::new();
     let mut replay_data = Vec::new();
+    // Kept only when the replay has to be checked against it
+    let mut image_data = Vec::new();
     let mut community_id = None;
     let mut security_timer = 0;
     let mut security_count = 0;
     let mut tool = String::new();
     let mut parent_post_id = None;
+    // Set when the drawing is another image for an existing draft
+    let mut target_post_id = None;
+    let mut webp_filename = None;
+    let mut thumbnails = Vec::new();
 
     while let Some(field) = multipart.next_field().await? {
         let name = field
@@ -293,6 +452,14 @@ pub async fn draw_finish(
             assert_eq!(url.mime_type().type_, "image");
             assert_eq!(url.mime_type().subtype, "png");
 
+            if state.config.webp_enabled {
+                webp_filename = upload_webp(&client, &state, body.clone(), &image_sha256).await;
+            }
+            thumbnails = upload_thumbnails(&client, &state, body.clone(), &image_sha256).await;
+            if state.config.replay_verification_enabled {
+                image_data = body.clone();
+            }
+
             upload_object(
                 &client,
                 &state.config.aws_s3_bucket,
@@ -310,6 +477,7 @@ pub async fn draw_finish(
                     image_sha256
                 ),
                 &BASE64.encode(&safe_decode_hash(&image_sha256)?),
+                "image/png",
             )
             .await?;
         } else if name == "animation" {
@@ -358,6 +526,11 @@ pub async fn draw_finish(
                 AppError::InvalidFormData(format!("Invalid UTF-8 in parent_post_id: {}", e))
             })?;
             parent_post_id = Some(safe_parse_uuid(parent_id_str)?);
+        } else if name == "post_id" && !data.is_empty() {
+            let post_id_str = std::str::from_utf8(data.as_ref()).map_err(|e| {
+                AppError::InvalidFormData(format!("Invalid UTF-8 in post_id: {}", e))
+            })?;
+            target_post_id = Some(safe_parse_uuid(post_id_str)?);
         }
     }
     let start = SystemTime::now();
@@ -366,6 +539,14 @@ pub async fn draw_finish(
         .expect("Time went backwards");
     let duration_ms = since_the_epoch.as_millis() - security_timer;
 
+    if state.config.replay_verification_enabled {
+        if let Some(format) = ReplayFormat::from_tool(&tool) {
+            verify_replay(format, &replay_data, &image_data, width, height).map_err(|e| {
+                AppError::InvalidFormData(format!("Replay does not match image: {}", e))
+            })?;
+        }
+    }
+
     if tool == "neo" || tool == "cucumber" || tool == "neo-cucumber-offline" {
         // Get first 2 characters for directory prefix
         let replay_prefix = replay_sha256.chars().take(2).collect::<String>();
@@ -378,6 +559,7 @@ pub async fn draw_finish(
             replay_data,
             &format!("replay/{}/{}.pch", replay_prefix, replay_sha256),
             &BASE64.encode(&safe_decode_hash(&replay_sha256)?),
+            "application/octet-stream",
         )
         .await?;
     } else if tool == "tegaki" {
@@ -392,6 +574,7 @@ pub async fn draw_finish(
             replay_data,
             &format!("replay/{}/{}.tgkr", replay_prefix, replay_sha256),
             &BASE64.encode(&safe_decode_hash(&replay_sha256)?),
+            "application/octet-stream",
         )
         .await?;
     } else {
@@ -440,13 +623,50 @@ pub async fn draw_finish(
         width,
         height,
         image_filename: format!("{}.png", image_sha256),
+        webp_filename,
         replay_filename: Some(replay_filename),
         tool: tool_enum,
         parent_post_id,
     };
 
-    let post = create_post(&mut tx, post_draft).await?;
+    let post_id = match target_post_id {
+        Some(target_post_id) => {
+            let target_post = find_post_by_id(&mut tx, target_post_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Post".to_string()))?;
+            if post_author_id(&target_post, target_post_id)? != current_user.id {
+                return Ok(StatusCode::FORBIDDEN.into_response());
+            }
+            // Galleries are assembled before publishing
+            if target_post
+                .get("published_at")
+                .and_then(|v| v.as_ref())
+                .is_some()
+            {
+                return Err(AppError::InvalidFormData(
+                    "Images can only be added to drafts".to_string(),
+                ));
+            }
+            if add_post_image(&mut tx, target_post_id, post_draft)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::InvalidFormData(format!(
+                    "A post can have at most {} images",
+                    MAX_POST_IMAGES
+                )));
+            }
+            target_post_id
+        }
+        None => create_post(&mut tx, post_draft).await?.id,
+    };
+    for thumbnail in &thumbnails {
+        create_image_thumbnail(&mut tx, thumbnail).await?;
+    }
     let _ = tx.commit().await;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(current_user.id)
+        .await;
 
     // Construct image URL
     let image_prefix = &image_sha256[0..2];
@@ -457,12 +677,171 @@ pub async fn draw_finish(
 
     Ok(Json(DrawFinishResponse {
         community_id: community_id.map(|id| id.to_string()),
-        post_id: post.id.to_string(),
+        post_id: post_id.to_string(),
         image_url,
     })
     .into_response())
 }
 
+#[derive(Serialize)]
+pub struct DrawAutosaveResponse {
+    pub autosaved_at: String,
+}
+
+/// Remove objects that nothing refers to anymore. Runs after the change is
+/// committed, so failures are only logged.
+async fn delete_stale_objects(client: &Client, state: &AppState, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+
+    let objects = match keys
+        .iter()
+        .map(|key| ObjectIdentifier::builder().key(key).build())
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(objects) => objects,
+        Err(e) => {
+            tracing::warn!("Failed to build object identifiers: {:?}", e);
+            return;
+        }
+    };
+    let delete = match Delete::builder().set_objects(Some(objects)).build() {
+        Ok(delete) => delete,
+        Err(e) => {
+            tracing::warn!("Failed to build object deletion: {:?}", e);
+            return;
+        }
+    };
+    if let Err(e) = client
+        .delete_objects()
+        .bucket(state.config.aws_s3_bucket.clone())
+        .delete(delete)
+        .send()
+        .await
+    {
+        tracing::warn!("Failed to delete stale objects {:?}: {:?}", keys, e);
+    }
+}
+
+/// POST /posts/:id/autosave
+///
+/// Replaces a draft's image with a snapshot of the canvas its author is
+/// still drawing, so a crashed tab doesn't lose the work. The snapshot is
+/// stored under its own hash with its WebP copy and thumbnails, like a
+/// finished drawing, and the replaced objects are removed. An unchanged
+/// canvas only has its save time recorded. The replay is stored when the
+/// drawing is finished.
+pub async fn draw_autosave(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let mut snapshot = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() != Some("image") {
+            continue;
+        }
+        let data = field.bytes().await?;
+        let data_str = std::str::from_utf8(data.as_ref()).map_err(|e| {
+            AppError::InvalidFormData(format!("Invalid UTF-8 in image data: {}", e))
+        })?;
+        let url = DataUrl::process(data_str)
+            .map_err(|e| AppError::InvalidFormData(format!("Invalid data URL: {}", e)))?;
+        if url.mime_type().type_ != "image" || url.mime_type().subtype != "png" {
+            return Err(AppError::InvalidFormData(
+                "Autosaved image must be a PNG".to_string(),
+            ));
+        }
+        let (body, _fragment) = url
+            .decode_to_vec()
+            .map_err(|e| AppError::InvalidFormData(format!("Failed to decode image: {}", e)))?;
+        snapshot = Some(body);
+    }
+    let snapshot =
+        snapshot.ok_or_else(|| AppError::InvalidFormData("Missing image".to_string()))?;
+    let (width, height) =
+        image::ImageReader::with_format(std::io::Cursor::new(&snapshot), image::ImageFormat::Png)
+            .into_dimensions()
+            .map_err(|e| AppError::InvalidFormData(format!("Invalid PNG: {}", e)))?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    // Holds the draft's row lock through the upload, so it can't be published
+    // with a half-replaced image
+    let draft_image = lock_draft_image_for_autosave(&mut tx, post_id, current_user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Draft".to_string()))?;
+    if (width as i32, height as i32) != (draft_image.width, draft_image.height) {
+        return Err(AppError::InvalidFormData(
+            "Snapshot size doesn't match the draft".to_string(),
+        ));
+    }
+
+    let credentials: AwsCredentials = AwsCredentials::new(
+        state.config.aws_access_key_id.clone(),
+        state.config.aws_secret_access_key.clone(),
+        None,
+        None,
+        "",
+    );
+    let credentials_provider = SharedCredentialsProvider::new(credentials);
+    let config = aws_sdk_s3::Config::builder()
+        .endpoint_url(state.config.r2_endpoint_url.clone())
+        .region(Region::new(state.config.aws_region.clone()))
+        .credentials_provider(credentials_provider)
+        .behavior_version_latest()
+        .build();
+    let client = Client::from_conf(config);
+
+    let image_sha256 = digest(&snapshot);
+    let mut saved = AutosavedImage {
+        image_filename: format!("{}.png", image_sha256),
+        webp_filename: draft_image.webp_filename.clone(),
+    };
+    let mut thumbnails = Vec::new();
+    if saved.image_filename != draft_image.image_filename {
+        // Image filenames are unique, so a drawing can't take another's hash
+        if image_filename_taken(&mut tx, &saved.image_filename).await? {
+            return Err(AppError::InvalidFormData(
+                "An identical drawing already exists".to_string(),
+            ));
+        }
+
+        saved.webp_filename = match state.config.webp_enabled {
+            true => upload_webp(&client, &state, snapshot.clone(), &image_sha256).await,
+            false => None,
+        };
+        thumbnails = upload_thumbnails(&client, &state, snapshot.clone(), &image_sha256).await;
+        upload_object(
+            &client,
+            &state.config.aws_s3_bucket,
+            snapshot,
+            &format!("image/{}/{}", &image_sha256[..2], saved.image_filename),
+            &BASE64.encode(&safe_decode_hash(&image_sha256)?),
+            "image/png",
+        )
+        .await?;
+    }
+
+    let (autosaved_at, stale_keys) =
+        mark_draft_autosaved(&mut tx, post_id, &draft_image, &saved).await?;
+    for thumbnail in &thumbnails {
+        create_image_thumbnail(&mut tx, thumbnail).await?;
+    }
+    tx.commit().await?;
+    delete_stale_objects(&client, &state, &stale_keys).await;
+
+    Ok(Json(DrawAutosaveResponse {
+        autosaved_at: autosaved_at.to_rfc3339(),
+    })
+    .into_response())
+}
+
 pub async fn banner_draw_finish(
     auth_session: AuthSession,
     State(state): State<AppState>,
@@ -529,6 +908,7 @@ pub async fn banner_draw_finish(
                     image_sha256
                 ),
                 &BASE64.encode(&safe_decode_hash(&image_sha256)?),
+                "image/png",
             )
             .await?;
         } else if name == "animation" {
@@ -545,6 +925,7 @@ pub async fn banner_draw_finish(
                 data.to_vec(),
                 &format!("replay/{}/{}.pch", replay_prefix, replay_sha256),
                 &BASE64.encode(&safe_decode_hash(&replay_sha256)?),
+                "application/octet-stream",
             )
             .await?;
         } else if name == "security_timer" {
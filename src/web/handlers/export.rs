@@ -0,0 +1,546 @@
+use crate::app_error::AppError;
+use crate::models::community::{find_community_by_slug, Community};
+use crate::models::export::{collect_community_export, collect_user_export, CommunityExport};
+use crate::models::user::{AuthSession, User};
+use crate::redis::RedisPool;
+use crate::web::handlers::draw::upload_object;
+use crate::web::handlers::{
+    get_user_bundle, safe_decode_hash, safe_format_message, safe_get_message, ExtractAcceptLanguage,
+};
+use crate::web::state::AppState;
+use anyhow::{anyhow, Context};
+use aws_sdk_s3::config::{Credentials as AwsCredentials, Region, SharedCredentialsProvider};
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, TimeDelta, Utc};
+use data_encoding::BASE64;
+use fluent::FluentArgs;
+use lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
+use lettre::{Message, SmtpTransport, Transport};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sha256::{digest, try_digest};
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::path::{Path as FilePath, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, Instrument};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+const EXPORT_JOB_PREFIX: &str = "oeee:export:";
+const EXPORT_JOB_TTL: u64 = 86400; // 24 hours, matching the download link
+const EXPORT_LINK_VALIDITY: Duration = Duration::from_secs(86400);
+/// Parts of an archive that may wait for the writer before downloads pause
+const ARCHIVE_PART_BUFFER: usize = 16;
+// A community can be exported again this long after its last completed export
+const COMMUNITY_EXPORT_COOLDOWN: Duration = Duration::from_secs(86400);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportJob {
+    pub status: ExportStatus,
+    pub requested_at: DateTime<Utc>,
+    pub download_url: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// `subject` identifies what is being exported, e.g. a user ID or `community:{id}`
+async fn load_export_job(pool: &RedisPool, subject: &str) -> anyhow::Result<Option<ExportJob>> {
+    let mut conn = pool.get().await?;
+    let key = format!("{}{}", EXPORT_JOB_PREFIX, subject);
+    let value: Option<String> = conn.get(&key).await?;
+    Ok(value.and_then(|v| serde_json::from_str(&v).ok()))
+}
+
+async fn save_export_job(pool: &RedisPool, subject: &str, job: &ExportJob) -> anyhow::Result<()> {
+    let mut conn = pool.get().await?;
+    let key = format!("{}{}", EXPORT_JOB_PREFIX, subject);
+    conn.set_ex::<_, _, ()>(&key, serde_json::to_string(job)?, EXPORT_JOB_TTL)
+        .await?;
+    Ok(())
+}
+
+/// Localized strings for the completion email, resolved while we still have the request
+struct ExportEmail {
+    from: String,
+    subject: String,
+    body: String,
+}
+
+pub async fn request_export(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let bundle = get_user_bundle(&accept_language, Some(&user));
+    let email = ExportEmail {
+        from: safe_get_message(&bundle, "email-from-address"),
+        subject: safe_get_message(&bundle, "account-export-email-subject"),
+        body: safe_get_message(&bundle, "account-export-email-body"),
+    };
+
+    let subject = user.id.to_string();
+    let job = start_export_job(&state, subject, move |state| async move {
+        run_export(&state, &user, email).await
+    })
+    .await?;
+
+    Ok((StatusCode::ACCEPTED, Json(job)).into_response())
+}
+
+pub async fn export_status(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    match load_export_job(&state.redis_pool, &user.id.to_string()).await? {
+        Some(job) => Ok(Json(job).into_response()),
+        None => Ok(StatusCode::NOT_FOUND.into_response()),
+    }
+}
+
+/// Export a community's posts with their drawings, comments and member list (owner only)
+pub async fn request_community_export(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let mut tx = state.db_pool.begin().await?;
+    let community = find_community_by_slug(&mut tx, slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+    tx.commit().await?;
+
+    if community.owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let subject = format!("community:{}", community.id);
+    if let Some(job) = load_export_job(&state.redis_pool, &subject).await? {
+        let cooldown_ends_at = job.requested_at
+            + TimeDelta::from_std(COMMUNITY_EXPORT_COOLDOWN).expect("24 hours is a valid duration");
+        if job.status == ExportStatus::Completed && Utc::now() < cooldown_ends_at {
+            return Ok((StatusCode::TOO_MANY_REQUESTS, Json(job)).into_response());
+        }
+    }
+
+    let bundle = get_user_bundle(&accept_language, Some(&user));
+    let mut args = FluentArgs::new();
+    args.set("community", community.name.clone());
+    let email = ExportEmail {
+        from: safe_get_message(&bundle, "email-from-address"),
+        subject: safe_format_message(&bundle, "community-export-email-subject", Some(&args)),
+        body: safe_get_message(&bundle, "account-export-email-body"),
+    };
+
+    let job = start_export_job(&state, subject, move |state| async move {
+        run_community_export(&state, &user, &community, email).await
+    })
+    .await?;
+
+    Ok((StatusCode::ACCEPTED, Json(job)).into_response())
+}
+
+pub async fn community_export_status(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let mut tx = state.db_pool.begin().await?;
+    let community = find_community_by_slug(&mut tx, slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+    tx.commit().await?;
+
+    if community.owner_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let subject = format!("community:{}", community.id);
+    match load_export_job(&state.redis_pool, &subject).await? {
+        Some(job) => Ok(Json(job).into_response()),
+        None => Ok(StatusCode::NOT_FOUND.into_response()),
+    }
+}
+
+/// Record a pending job and run `export` in the background, one export at a time per subject
+async fn start_export_job<F, Fut>(
+    state: &AppState,
+    subject: String,
+    export: F,
+) -> anyhow::Result<ExportJob>
+where
+    F: FnOnce(AppState) -> Fut + Send + 'static,
+    Fut: Future<Output = anyhow::Result<(String, DateTime<Utc>)>> + Send + 'static,
+{
+    if let Some(job) = load_export_job(&state.redis_pool, &subject).await? {
+        if job.status == ExportStatus::Pending {
+            return Ok(job);
+        }
+    }
+
+    let job = ExportJob {
+        status: ExportStatus::Pending,
+        requested_at: Utc::now(),
+        download_url: None,
+        expires_at: None,
+    };
+    save_export_job(&state.redis_pool, &subject, &job).await?;
+
+    let job_state = state.clone();
+    let pending_job = job.clone();
+    let run_export = async move {
+        let job = match export(job_state.clone()).await {
+            Ok((download_url, expires_at)) => {
+                info!("Completed data export for {}", subject);
+                ExportJob {
+                    status: ExportStatus::Completed,
+                    download_url: Some(download_url),
+                    expires_at: Some(expires_at),
+                    ..pending_job
+                }
+            }
+            Err(e) => {
+                error!("Data export for {} failed: {:?}", subject, e);
+                ExportJob {
+                    status: ExportStatus::Failed,
+                    ..pending_job
+                }
+            }
+        };
+        if let Err(e) = save_export_job(&job_state.redis_pool, &subject, &job).await {
+            error!("Failed to record export status for {}: {:?}", subject, e);
+        }
+    };
+    tokio::spawn(run_export.in_current_span());
+
+    Ok(job)
+}
+
+/// Build the archive, upload it to R2 and email a presigned link to the user
+async fn run_export(
+    state: &AppState,
+    user: &User,
+    email: ExportEmail,
+) -> anyhow::Result<(String, DateTime<Utc>)> {
+    let mut tx = state.db_pool.begin().await?;
+    let export = collect_user_export(&mut tx, user.id).await?;
+    tx.commit().await?;
+
+    let image_urls: Vec<String> = export
+        .posts
+        .iter()
+        .map(|post| image_url(state, &post.image_filename))
+        .collect();
+
+    let archive = build_archive(vec![
+        (
+            "posts.json".to_string(),
+            serde_json::to_vec_pretty(&export.posts)?,
+        ),
+        (
+            "comments.json".to_string(),
+            serde_json::to_vec_pretty(&export.comments)?,
+        ),
+        (
+            "reactions.json".to_string(),
+            serde_json::to_vec_pretty(&export.reactions)?,
+        ),
+        (
+            "images.json".to_string(),
+            serde_json::to_vec_pretty(&image_urls)?,
+        ),
+    ])?;
+
+    let key = format!("export/{}/{}.zip", user.id, Uuid::new_v4());
+    let (download_url, expires_at) = upload_archive(state, archive, &key).await?;
+    send_export_email(state, user, email, &download_url)?;
+
+    Ok((download_url, expires_at))
+}
+
+async fn run_community_export(
+    state: &AppState,
+    owner: &User,
+    community: &Community,
+    email: ExportEmail,
+) -> anyhow::Result<(String, DateTime<Utc>)> {
+    let mut tx = state.db_pool.begin().await?;
+    let export = collect_community_export(&mut tx, community.id).await?;
+    tx.commit().await?;
+
+    // A community's drawings can add up to more than we'd want in memory, so the
+    // archive is written to disk and each drawing is streamed into it. The file
+    // is written on a blocking thread, fed by the downloads below.
+    let archive = TempArchive::new();
+    let (parts, received_parts) = mpsc::channel(ARCHIVE_PART_BUFFER);
+    let path = archive.path.clone();
+    let writer = tokio::task::spawn_blocking(move || write_archive(&path, received_parts));
+    let streamed = stream_community_archive(state, &export, parts).await;
+    // A failed write closes the channel, so its error explains a failed stream
+    writer.await.context("Archive writer panicked")??;
+    streamed?;
+
+    let key = format!("export/community/{}/{}.zip", community.id, Uuid::new_v4());
+    let (download_url, expires_at) = upload_archive_file(state, &archive.path, &key).await?;
+    send_export_email(state, owner, email, &download_url)?;
+
+    Ok((download_url, expires_at))
+}
+
+/// Send the contents of a community's archive to its writer: the exported
+/// rows, then every drawing, downloaded from R2 a chunk at a time
+async fn stream_community_archive(
+    state: &AppState,
+    export: &CommunityExport,
+    parts: mpsc::Sender<ArchivePart>,
+) -> anyhow::Result<()> {
+    let closed = || anyhow!("Archive writer stopped");
+    for (name, contents) in [
+        ("posts.json", serde_json::to_vec_pretty(&export.posts)?),
+        (
+            "comments.json",
+            serde_json::to_vec_pretty(&export.comments)?,
+        ),
+        ("members.json", serde_json::to_vec_pretty(&export.members)?),
+    ] {
+        parts
+            .send(ArchivePart::File(name.to_string()))
+            .await
+            .map_err(|_| closed())?;
+        parts
+            .send(ArchivePart::Data(contents))
+            .await
+            .map_err(|_| closed())?;
+    }
+
+    // The drawings go into the archive itself, so it stays complete even after
+    // the community is gone
+    let client = r2_client(state);
+    let image_filenames: Vec<String> = export
+        .posts
+        .iter()
+        .flat_map(|post| {
+            std::iter::once(post.image_filename.clone())
+                .chain(post.gallery_image_filenames.iter().cloned())
+        })
+        .collect();
+    for image_filename in image_filenames {
+        let key = format!("image/{}/{}", &image_filename[..2], image_filename);
+        let mut object = client
+            .get_object()
+            .bucket(&state.config.aws_s3_bucket)
+            .key(&key)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", key))?;
+        parts
+            .send(ArchivePart::File(format!("images/{}", image_filename)))
+            .await
+            .map_err(|_| closed())?;
+        while let Some(chunk) = object.body.try_next().await? {
+            parts
+                .send(ArchivePart::Data(chunk.to_vec()))
+                .await
+                .map_err(|_| closed())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A piece of an archive, handed to the blocking task writing it to disk
+enum ArchivePart {
+    /// Start the next file in the archive
+    File(String),
+    /// Append to the file started last
+    Data(Vec<u8>),
+}
+
+/// Write the parts received until the sender is dropped into a zip archive at
+/// `path`. Blocks, so it runs on a blocking thread.
+fn write_archive(path: &FilePath, mut parts: mpsc::Receiver<ArchivePart>) -> anyhow::Result<()> {
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(path)?));
+    let options = SimpleFileOptions::default();
+    while let Some(part) = parts.blocking_recv() {
+        match part {
+            ArchivePart::File(name) => zip.start_file(name, options)?,
+            ArchivePart::Data(data) => zip.write_all(&data)?,
+        }
+    }
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+/// An archive being built on disk, removed once it has been uploaded or the export fails
+struct TempArchive {
+    path: PathBuf,
+}
+
+impl TempArchive {
+    fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("oeee-export-{}.zip", Uuid::new_v4()));
+        Self { path }
+    }
+}
+
+impl Drop for TempArchive {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                error!("Failed to remove {}: {:?}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+fn image_url(state: &AppState, image_filename: &str) -> String {
+    format!(
+        "{}/image/{}/{}",
+        state.config.r2_public_endpoint_url,
+        &image_filename[..2],
+        image_filename
+    )
+}
+
+fn build_archive(files: Vec<(String, Vec<u8>)>) -> anyhow::Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for (name, contents) in files {
+        zip.start_file(name, options)?;
+        zip.write_all(&contents)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+fn r2_client(state: &AppState) -> Client {
+    let credentials = AwsCredentials::new(
+        state.config.aws_access_key_id.clone(),
+        state.config.aws_secret_access_key.clone(),
+        None,
+        None,
+        "",
+    );
+    let config = aws_sdk_s3::Config::builder()
+        .endpoint_url(state.config.r2_endpoint_url.clone())
+        .region(Region::new(state.config.aws_region.clone()))
+        .credentials_provider(SharedCredentialsProvider::new(credentials))
+        .behavior_version_latest()
+        .build();
+    Client::from_conf(config)
+}
+
+/// Upload the archive under `key` and return a presigned download link
+async fn upload_archive(
+    state: &AppState,
+    archive: Vec<u8>,
+    key: &str,
+) -> anyhow::Result<(String, DateTime<Utc>)> {
+    let client = r2_client(state);
+
+    let checksum = BASE64
+        .encode(&safe_decode_hash(&digest(&archive)).map_err(|e| anyhow!("Invalid hash: {}", e))?);
+    upload_object(
+        &client,
+        &state.config.aws_s3_bucket,
+        archive,
+        key,
+        &checksum,
+        "application/zip",
+    )
+    .await?;
+
+    presign_download(state, &client, key).await
+}
+
+/// Like `upload_archive`, for an archive on disk, which is streamed to R2 as it's read
+async fn upload_archive_file(
+    state: &AppState,
+    path: &FilePath,
+    key: &str,
+) -> anyhow::Result<(String, DateTime<Utc>)> {
+    let client = r2_client(state);
+
+    let checksum = BASE64
+        .encode(&safe_decode_hash(&try_digest(path)?).map_err(|e| anyhow!("Invalid hash: {}", e))?);
+    client
+        .put_object()
+        .bucket(&state.config.aws_s3_bucket)
+        .key(key)
+        .checksum_sha256(checksum)
+        .content_type("application/zip")
+        .body(ByteStream::from_path(path).await?)
+        .send()
+        .await?;
+
+    presign_download(state, &client, key).await
+}
+
+async fn presign_download(
+    state: &AppState,
+    client: &Client,
+    key: &str,
+) -> anyhow::Result<(String, DateTime<Utc>)> {
+    let presigned = client
+        .get_object()
+        .bucket(&state.config.aws_s3_bucket)
+        .key(key)
+        .presigned(PresigningConfig::expires_in(EXPORT_LINK_VALIDITY)?)
+        .await?;
+    let download_url = presigned.uri().to_string();
+    let expires_at = Utc::now()
+        + TimeDelta::from_std(EXPORT_LINK_VALIDITY).expect("24 hours is a valid duration");
+
+    Ok((download_url, expires_at))
+}
+
+/// Only mail verified addresses; the status endpoint still returns the link
+fn send_export_email(
+    state: &AppState,
+    user: &User,
+    email: ExportEmail,
+    download_url: &str,
+) -> anyhow::Result<()> {
+    if let (Some(address), Some(_)) = (&user.email, user.email_verified_at) {
+        let email_message = Message::builder()
+            .from(email.from.parse()?)
+            .to(address.parse()?)
+            .subject(email.subject)
+            .body(format!("{}\n\n{}", email.body, download_url))?;
+
+        let mailer = SmtpTransport::relay(&state.config.smtp_host)?
+            .credentials(SmtpCredentials::new(
+                state.config.smtp_user.clone(),
+                state.config.smtp_password.clone(),
+            ))
+            .build();
+
+        mailer.send(&email_message)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::models::actor::Actor;
+use crate::models::community::{find_community_by_id, CommunityVisibility};
+use crate::models::post::{
+    clear_post_federation_deferral, find_deferred_posts_ready_for_federation, find_post_by_id,
+    is_post_deleted, DeferredFederationPost,
+};
+use crate::web::handlers::post::federate_published_post;
+use crate::web::state::AppState;
+use chrono::{TimeDelta, Utc};
+use std::time::Duration;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+const BACKFILL_INTERVAL_SECONDS: u64 = 600;
+const BACKFILL_BATCH_SIZE: i64 = 100;
+
+/// Periodically release posts held back from federation once their authors qualify,
+/// federating the recent ones if backfill is enabled.
+pub async fn federate_deferred_posts(state: AppState) {
+    info!(
+        "Starting deferred federation backfill (interval: {}s, min account age: {}h, backfill: {})",
+        BACKFILL_INTERVAL_SECONDS,
+        state.config.federation_min_account_age_hours,
+        if state.config.federation_backfill_enabled {
+            format!("{} days", state.config.federation_backfill_days)
+        } else {
+            "disabled".to_string()
+        }
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(BACKFILL_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = release_ready_posts(&state).await {
+            error!("Failed to release deferred posts: {:?}", e);
+        }
+    }
+}
+
+async fn release_ready_posts(state: &AppState) -> anyhow::Result<()> {
+    let backfill_since = Utc::now()
+        - TimeDelta::try_days(state.config.federation_backfill_days)
+            .ok_or_else(|| anyhow::anyhow!("Invalid federation_backfill_days"))?;
+
+    let mut tx = state.db_pool.begin().await?;
+    let posts = find_deferred_posts_ready_for_federation(
+        &mut tx,
+        state.config.federation_ready_cutoff(),
+        BACKFILL_BATCH_SIZE,
+    )
+    .await?;
+    for post in &posts {
+        clear_post_federation_deferral(&mut tx, post.id).await?;
+    }
+    tx.commit().await?;
+
+    if posts.is_empty() {
+        return Ok(());
+    }
+
+    let mut federated = 0;
+    for post in &posts {
+        let is_recent = post
+            .published_at
+            .is_some_and(|published_at| published_at >= backfill_since);
+        if !state.config.federation_backfill_enabled || !is_recent {
+            continue;
+        }
+
+        match federate_deferred_post(state, post).await {
+            Ok(true) => federated += 1,
+            Ok(false) => {}
+            Err(e) => error!("Failed to backfill federation of post {}: {:?}", post.id, e),
+        }
+    }
+
+    debug!(
+        "Released {} deferred posts, federated {} of them",
+        posts.len(),
+        federated
+    );
+
+    Ok(())
+}
+
+/// Returns whether the post was sent; posts deleted or moved to a private community
+/// since they were held back stay local.
+async fn federate_deferred_post(
+    state: &AppState,
+    post: &DeferredFederationPost,
+) -> anyhow::Result<bool> {
+    let mut tx = state.db_pool.begin().await?;
+    if is_post_deleted(&mut tx, post.id).await? {
+        return Ok(false);
+    }
+    let Some(post_data) = find_post_by_id(&mut tx, post.id).await? else {
+        return Ok(false);
+    };
+    let Some(actor) = Actor::find_by_user_id(&mut tx, post.author_id).await? else {
+        return Ok(false);
+    };
+
+    let community_id = post_data
+        .get("community_id")
+        .and_then(|v| v.as_ref())
+        .and_then(|s| Uuid::parse_str(s).ok());
+    if let Some(cid) = community_id {
+        let is_private = find_community_by_id(&mut tx, cid)
+            .await?
+            .map(|community| community.visibility == CommunityVisibility::Private)
+            .unwrap_or(true);
+        if is_private {
+            return Ok(false);
+        }
+    }
+    tx.commit().await?;
+
+    let title = post_data
+        .get("title")
+        .and_then(|v| v.clone())
+        .unwrap_or_default();
+    let content = post_data
+        .get("content")
+        .and_then(|v| v.clone())
+        .unwrap_or_default();
+    federate_published_post(&actor, post.id, community_id, title, content, state).await;
+
+    Ok(true)
+}
@@ -0,0 +1,309 @@
+use crate::app_error::AppError;
+use crate::models::community::{find_community_by_slug, CommunityVisibility};
+use crate::models::post::{
+    find_feed_posts_by_author_id, find_published_posts_by_community_id, SerializablePost,
+};
+use crate::models::user::{find_user_by_login_name, AuthSession};
+use crate::web::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::header::{CONTENT_TYPE, IF_MODIFIED_SINCE, LAST_MODIFIED};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+
+/// How many of the newest posts a feed carries
+const FEED_ENTRY_LIMIT: i64 = 50;
+
+/// Atom feed of a community's newest posts. Private communities have no feed.
+pub async fn community_feed(
+    auth_session: AuthSession,
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let community = find_community_by_slug(&mut tx, slug)
+        .await?
+        .filter(|community| community.visibility != CommunityVisibility::Private)
+        .ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    let viewer_show_sensitive = auth_session
+        .user
+        .as_ref()
+        .is_some_and(|user| user.show_sensitive_content);
+    // Everything in a sensitive community counts as sensitive
+    let posts = if community.is_sensitive && !viewer_show_sensitive {
+        Vec::new()
+    } else {
+        find_published_posts_by_community_id(
+            &mut tx,
+            community.id,
+            FEED_ENTRY_LIMIT,
+            0,
+            auth_session.user.as_ref().map(|user| user.id),
+            viewer_show_sensitive,
+        )
+        .await?
+    };
+    tx.commit().await?;
+
+    let base_url = &state.config.base_url;
+    let page_url = format!("{}/communities/@{}", base_url, community.slug);
+    let feed = AtomFeed {
+        id: format!("{}/feed.atom", page_url),
+        title: community.name,
+        subtitle: Some(community.description).filter(|d| !d.is_empty()),
+        page_url,
+        updated: feed_updated_at(community.updated_at, &posts),
+        entries: posts
+            .iter()
+            .map(|post| {
+                let author_login_name = post.user_login_name.clone().unwrap_or_default();
+                feed_entry(&state, post, &author_login_name)
+            })
+            .collect(),
+    };
+
+    Ok(feed.into_conditional_response(&headers))
+}
+
+/// Atom feed of a user's newest posts outside private and unlisted communities
+pub async fn profile_feed(
+    auth_session: AuthSession,
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(login_name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let user = find_user_by_login_name(&mut tx, &login_name)
+        .await?
+        .filter(|user| user.deleted_at.is_none())
+        .ok_or_else(|| AppError::NotFound("User".to_string()))?;
+
+    let posts = find_feed_posts_by_author_id(
+        &mut tx,
+        user.id,
+        FEED_ENTRY_LIMIT,
+        auth_session.user.as_ref().map(|user| user.id),
+        auth_session
+            .user
+            .as_ref()
+            .is_some_and(|user| user.show_sensitive_content),
+    )
+    .await?;
+    tx.commit().await?;
+
+    let page_url = format!("{}/@{}", state.config.base_url, user.login_name);
+    let feed = AtomFeed {
+        id: format!("{}/feed.atom", page_url),
+        title: user.display_name.clone(),
+        subtitle: None,
+        page_url,
+        updated: feed_updated_at(user.updated_at, &posts),
+        entries: posts
+            .iter()
+            .map(|post| AtomEntry {
+                author_name: user.display_name.clone(),
+                ..feed_entry(&state, post, &user.login_name)
+            })
+            .collect(),
+    };
+
+    Ok(feed.into_conditional_response(&headers))
+}
+
+struct AtomFeed {
+    id: String,
+    title: String,
+    subtitle: Option<String>,
+    page_url: String,
+    updated: DateTime<Utc>,
+    entries: Vec<AtomEntry>,
+}
+
+struct AtomEntry {
+    url: String,
+    title: String,
+    author_name: String,
+    author_url: String,
+    image_url: String,
+    image_type: &'static str,
+    image_width: i32,
+    image_height: i32,
+    published: DateTime<Utc>,
+    updated: DateTime<Utc>,
+}
+
+fn feed_entry(state: &AppState, post: &SerializablePost, author_login_name: &str) -> AtomEntry {
+    let base_url = &state.config.base_url;
+    AtomEntry {
+        url: format!("{}/@{}/{}", base_url, author_login_name, post.id),
+        title: post
+            .title
+            .clone()
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| "Untitled".to_string()),
+        author_name: author_login_name.to_string(),
+        author_url: format!("{}/@{}", base_url, author_login_name),
+        image_url: format!(
+            "{}/image/{}/{}",
+            state.config.r2_public_endpoint_url,
+            post.image_filename.get(..2).unwrap_or_default(),
+            post.image_filename
+        ),
+        image_type: image_content_type(&post.image_filename),
+        image_width: post.image_width,
+        image_height: post.image_height,
+        published: post.published_at.unwrap_or(post.created_at),
+        updated: post.updated_at,
+    }
+}
+
+fn image_content_type(filename: &str) -> &'static str {
+    match filename.rsplit('.').next() {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        _ => "image/png",
+    }
+}
+
+/// The feed changes whenever its owner or any of its posts does
+fn feed_updated_at(owner_updated_at: DateTime<Utc>, posts: &[SerializablePost]) -> DateTime<Utc> {
+    posts
+        .iter()
+        .flat_map(|post| [Some(post.updated_at), post.published_at])
+        .flatten()
+        .fold(owner_updated_at, DateTime::max)
+}
+
+/// HTTP dates only have second precision, so compare at that granularity
+fn is_not_modified_since(headers: &HeaderMap, last_modified: DateTime<Utc>) -> bool {
+    headers
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+impl AtomFeed {
+    fn into_conditional_response(self, headers: &HeaderMap) -> Response {
+        let last_modified = self.updated.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        if is_not_modified_since(headers, self.updated) {
+            return (StatusCode::NOT_MODIFIED, [(LAST_MODIFIED, last_modified)]).into_response();
+        }
+
+        (
+            [
+                (
+                    CONTENT_TYPE,
+                    "application/atom+xml; charset=utf-8".to_string(),
+                ),
+                (LAST_MODIFIED, last_modified),
+            ],
+            self.render(),
+        )
+            .into_response()
+    }
+
+    fn render(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        xml.push_str(&format!("  <id>{}</id>\n", escape_xml(&self.id)));
+        xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&self.title)));
+        if let Some(subtitle) = &self.subtitle {
+            xml.push_str(&format!(
+                "  <subtitle>{}</subtitle>\n",
+                escape_xml(subtitle)
+            ));
+        }
+        xml.push_str(&format!(
+            "  <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>\n",
+            escape_xml(&self.id)
+        ));
+        xml.push_str(&format!(
+            "  <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+            escape_xml(&self.page_url)
+        ));
+        xml.push_str(&format!(
+            "  <updated>{}</updated>\n",
+            self.updated.to_rfc3339()
+        ));
+        for entry in &self.entries {
+            entry.render_into(&mut xml);
+        }
+        xml.push_str("</feed>\n");
+        xml
+    }
+}
+
+impl AtomEntry {
+    fn render_into(&self, xml: &mut String) {
+        let url = escape_xml(&self.url);
+        let image_url = escape_xml(&self.image_url);
+        // The content is itself HTML, escaped once more to embed it in the XML
+        let content = format!(
+            "<a href=\"{}\"><img src=\"{}\" width=\"{}\" height=\"{}\" alt=\"{}\"></a>",
+            url,
+            image_url,
+            self.image_width,
+            self.image_height,
+            escape_xml(&self.title)
+        );
+
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <id>{}</id>\n", url));
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&self.title)));
+        xml.push_str(&format!(
+            "    <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+            url
+        ));
+        xml.push_str(&format!(
+            "    <link rel=\"enclosure\" type=\"{}\" href=\"{}\"/>\n",
+            self.image_type, image_url
+        ));
+        xml.push_str(&format!(
+            "    <published>{}</published>\n",
+            self.published.to_rfc3339()
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            self.updated.to_rfc3339()
+        ));
+        xml.push_str("    <author>\n");
+        xml.push_str(&format!(
+            "      <name>{}</name>\n",
+            escape_xml(&self.author_name)
+        ));
+        xml.push_str(&format!(
+            "      <uri>{}</uri>\n",
+            escape_xml(&self.author_url)
+        ));
+        xml.push_str("    </author>\n");
+        xml.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape_xml(&content)
+        ));
+        xml.push_str("  </entry>\n");
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
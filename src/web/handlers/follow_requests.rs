@@ -0,0 +1,175 @@
+use crate::app_error::AppError;
+use crate::models::actor::Actor;
+use crate::models::follow_request::{
+    accept_follow_request, delete_follow_request, find_follow_request_by_id,
+    find_follow_requests_by_following_actor_id, FollowRequest,
+};
+use crate::models::user::AuthSession;
+use crate::web::context::CommonContext;
+use crate::web::handlers::activitypub::{generate_object_id, Accept, Follow, Reject};
+use crate::web::handlers::{
+    get_user_bundle, safe_get_message, ExtractAcceptLanguage, ExtractFtlLang,
+};
+use crate::web::state::AppState;
+use activitypub_federation::config::{Data, FederationConfig};
+use activitypub_federation::fetch::object_id::ObjectId;
+use activitypub_federation::traits::Actor as ActivityPubFederationActor;
+use axum::extract::{Path, State};
+use axum::response::{Html, IntoResponse, Redirect};
+use axum_messages::Messages;
+use minijinja::context;
+use sqlx::{Postgres, Transaction};
+use url::Url;
+use uuid::Uuid;
+
+pub async fn follow_requests(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    messages: Messages,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let actor = Actor::find_by_user_id(&mut tx, user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+    let follow_requests = find_follow_requests_by_following_actor_id(&mut tx, actor.id).await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("follow_requests.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        follow_requests,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        messages => messages.into_iter().collect::<Vec<_>>(),
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered))
+}
+
+/// Looks up a request addressed to the user, along with both actors
+async fn find_own_follow_request(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    follow_request_id: Uuid,
+) -> Result<(FollowRequest, Actor, Actor), AppError> {
+    let actor = Actor::find_by_user_id(tx, user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+    let follow_request = find_follow_request_by_id(tx, follow_request_id)
+        .await?
+        .filter(|follow_request| follow_request.following_actor_id == actor.id)
+        .ok_or_else(|| AppError::NotFound("Follow request".to_string()))?;
+    let follower_actor = Actor::find_by_id(tx, follow_request.follower_actor_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+
+    Ok((follow_request, actor, follower_actor))
+}
+
+/// Rebuilds the Follow the requester sent, to be quoted in the Accept or Reject
+fn original_follow(
+    follow_request: &FollowRequest,
+    actor: &Actor,
+    follower_actor: &Actor,
+) -> Result<Follow, AppError> {
+    Ok(Follow::new(
+        ObjectId::parse(&follower_actor.iri)?,
+        ObjectId::parse(&actor.iri)?,
+        Url::parse(&follow_request.activity_iri)?,
+    ))
+}
+
+async fn federation_data(state: &AppState) -> Result<Data<AppState>, AppError> {
+    let federation_config = FederationConfig::builder()
+        .domain(&state.config.domain)
+        .app_data(state.clone())
+        .build()
+        .await?;
+    Ok(federation_config.to_request_data())
+}
+
+pub async fn do_accept_follow_request(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    messages: Messages,
+    State(state): State<AppState>,
+    Path(follow_request_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let (follow_request, actor, follower_actor) =
+        find_own_follow_request(&mut tx, user.id, follow_request_id).await?;
+    accept_follow_request(&mut tx, &follow_request).await?;
+    tx.commit().await?;
+
+    let follow = original_follow(&follow_request, &actor, &follower_actor)?;
+    let accept = Accept::new(
+        ObjectId::parse(&actor.iri)?,
+        follow,
+        generate_object_id(&state.config.domain)?,
+    );
+    // The follow is already recorded, so a failed delivery is only logged
+    if let Err(e) = actor
+        .send(
+            accept,
+            vec![follower_actor.shared_inbox_or_inbox()],
+            state.config.use_activitypub_queue(),
+            &federation_data(&state).await?,
+        )
+        .await
+    {
+        tracing::warn!("Failed to send Accept to {}: {:?}", follower_actor.iri, e);
+    }
+
+    messages.success(safe_get_message(&bundle, "follow-request-accepted"));
+    Ok(Redirect::to("/account/follow-requests").into_response())
+}
+
+pub async fn do_reject_follow_request(
+    auth_session: AuthSession,
+    ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    messages: Messages,
+    State(state): State<AppState>,
+    Path(follow_request_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let bundle = get_user_bundle(&accept_language, Some(user));
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let (follow_request, actor, follower_actor) =
+        find_own_follow_request(&mut tx, user.id, follow_request_id).await?;
+    delete_follow_request(&mut tx, follow_request.id).await?;
+    tx.commit().await?;
+
+    let follow = original_follow(&follow_request, &actor, &follower_actor)?;
+    let reject = Reject::new(
+        ObjectId::parse(&actor.iri)?,
+        follow,
+        generate_object_id(&state.config.domain)?,
+    );
+    if let Err(e) = actor
+        .send(
+            reject,
+            vec![follower_actor.shared_inbox_or_inbox()],
+            state.config.use_activitypub_queue(),
+            &federation_data(&state).await?,
+        )
+        .await
+    {
+        tracing::warn!("Failed to send Reject to {}: {:?}", follower_actor.iri, e);
+    }
+
+    messages.success(safe_get_message(&bundle, "follow-request-rejected"));
+    Ok(Redirect::to("/account/follow-requests").into_response())
+}
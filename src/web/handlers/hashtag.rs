@@ -1,6 +1,8 @@
 use crate::app_error::AppError;
 use crate::models::hashtag::{
-    find_hashtag_by_name, find_posts_by_hashtag, get_trending_hashtags, search_hashtags,
+    find_followed_hashtags, find_hashtag_by_name, find_posts_by_hashtag,
+    find_posts_for_followed_hashtags, follow_hashtag, get_trending_hashtags, is_following_hashtag,
+    search_hashtags, unfollow_hashtag,
 };
 use crate::models::user::AuthSession;
 use crate::web::context::CommonContext;
@@ -53,8 +55,17 @@ pub async fn hashtag_view(
     )
     .await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let is_following = match viewer_user_id {
+        Some(user_id) => is_following_hashtag(&mut tx, user_id, hashtag.id).await?,
+        None => false,
+    };
+
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     tx.commit().await?;
 
@@ -63,8 +74,118 @@ pub async fn hashtag_view(
         current_user => auth_session.user,
         hashtag => hashtag,
         posts => posts,
+        is_following,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        r2_public_endpoint_url => state.config.r2_public_endpoint_url.clone(),
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+/// Follow a hashtag (HTMX), returning the updated follow button
+pub async fn hx_follow_hashtag(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    Path(hashtag_name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    set_hashtag_follow(auth_session, state, ftl_lang, hashtag_name, true).await
+}
+
+/// Unfollow a hashtag (HTMX), returning the updated follow button
+pub async fn hx_unfollow_hashtag(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    Path(hashtag_name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    set_hashtag_follow(auth_session, state, ftl_lang, hashtag_name, false).await
+}
+
+async fn set_hashtag_follow(
+    auth_session: AuthSession,
+    state: AppState,
+    ftl_lang: String,
+    hashtag_name: String,
+    follow: bool,
+) -> Result<axum::response::Response, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let normalized_name = hashtag_name.replace('-', "_").to_lowercase();
+    let hashtag = match find_hashtag_by_name(&mut tx, &normalized_name).await? {
+        Some(hashtag) => hashtag,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+
+    if follow {
+        follow_hashtag(&mut tx, user.id, hashtag.id).await?;
+    } else {
+        unfollow_hashtag(&mut tx, user.id, hashtag.id).await?;
+    }
+
+    tx.commit().await?;
+
+    let template = state.env.get_template("hashtag_follow_button.jinja")?;
+    let rendered = template.render(context! {
+        hashtag => hashtag,
+        is_following => follow,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct FollowedHashtagsQuery {
+    #[serde(default)]
+    offset: i64,
+}
+
+const FOLLOWED_HASHTAGS_PAGE_SIZE: i64 = 50;
+
+/// Feed of posts from the hashtags the user follows
+pub async fn followed_hashtags_feed(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    Query(params): Query<FollowedHashtagsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let offset = params.offset.max(0);
+    let hashtags = find_followed_hashtags(&mut tx, user.id).await?;
+    let posts = find_posts_for_followed_hashtags(
+        &mut tx,
+        user.id,
+        FOLLOWED_HASHTAGS_PAGE_SIZE,
+        offset,
+        user.show_sensitive_content,
+    )
+    .await?;
+    let has_more = posts.len() as i64 == FOLLOWED_HASHTAGS_PAGE_SIZE;
+
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+
+    tx.commit().await?;
+
+    let template = state.env.get_template("hashtag_following.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        hashtags => hashtags,
+        posts => posts,
+        next_offset => if has_more { Some(offset + FOLLOWED_HASHTAGS_PAGE_SIZE) } else { None },
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         r2_public_endpoint_url => state.config.r2_public_endpoint_url.clone(),
         ftl_lang
     })?;
@@ -118,8 +239,12 @@ pub async fn hashtag_discovery(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     // Determine sort order
     let sort_by = params.sort.as_deref().unwrap_or("trending");
@@ -150,6 +275,7 @@ pub async fn hashtag_discovery(
         sort_by => sort_by,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -1,29 +1,34 @@
 use super::ExtractFtlLang;
 use crate::app_error::{error_codes, AppError};
+use crate::common_context_cache::CommonContextCache;
 use crate::models::actor::Actor;
 use crate::models::comment::{
-    build_comment_thread_tree_paginated, create_comment,
+    build_comment_thread_tree_paginated, create_comment, find_comment_by_id,
     find_latest_comments_from_public_communities, CommentDraft,
 };
 use crate::models::community::{
-    get_communities_members_count, get_public_communities, is_user_member, Community,
+    get_communities_members_count, get_public_communities, get_user_role_in_community,
+    is_user_member, Community, CommunityMemberRole,
 };
+use crate::models::community_category::find_categories_by_community_ids;
 use crate::models::hashtag::{get_hashtags_for_post, link_post_to_hashtags, parse_hashtag_input, unlink_post_hashtags};
+use crate::models::image::{find_thumbnails_by_image_filenames, thumbnail_or_image_url};
 use crate::models::notification::{
     create_notification, get_notification_by_id, get_unread_count, send_push_for_notification,
     CreateNotificationParams, NotificationType,
 };
 use crate::models::post::{
-    build_thread_tree, delete_post_with_activity, edit_post, find_following_posts_by_user_id, find_post_by_id,
-    find_post_detail_for_json, find_public_community_posts, find_recent_posts_by_communities,
-    SerializableThreadedPost,
+    build_thread_tree, count_post_images_by_post_ids, delete_post_with_activity, edit_post, find_following_posts_by_user_id, find_post_by_id,
+    find_home_feed_posts, find_post_detail_for_json, find_recent_posts_by_communities,
+    is_post_deleted, SerializableThreadedPost,
 };
 use crate::models::reaction::{
     create_reaction, delete_reaction, find_reactions_by_post_id_and_emoji, find_user_reaction,
     get_reaction_counts, ReactionDraft,
 };
-use crate::models::user::AuthSession;
+use crate::models::user::{find_user_feed_algorithm, AuthSession, FeedAlgorithm};
 use crate::web::context::CommonContext;
+use crate::web::handlers::activitypub::send_delete_activity_for_comment;
 use crate::web::responses::{
     AuthorInfo, ChildPostAuthor, ChildPostImage, ChildPostResponse, CommentListResponse,
     CommentWithPost, CommentsListResponse, CommunityListResponse, CommunityPostThumbnail,
@@ -38,10 +43,22 @@ use axum::response::IntoResponse;
 use axum::{extract::State, response::Html, response::Json};
 use axum_messages::Messages;
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 use uuid::Uuid;
 
 use minijinja::context;
 
+/// The viewer's preferred home feed ordering; anonymous viewers get the default
+async fn viewer_feed_algorithm(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    viewer_user_id: Option<Uuid>,
+) -> Result<FeedAlgorithm, AppError> {
+    match viewer_user_id {
+        Some(user_id) => Ok(find_user_feed_algorithm(tx, user_id).await?),
+        None => Ok(FeedAlgorithm::default()),
+    }
+}
+
 pub async fn home(
     auth_session: AuthSession,
     State(state): State<AppState>,
@@ -51,8 +68,12 @@ pub async fn home(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let (viewer_user_id, viewer_show_sensitive) = if let Some(ref user) = auth_session.user {
         (Some(user.id), user.show_sensitive_content)
@@ -60,8 +81,16 @@ pub async fn home(
         (None, false)
     };
 
-    let non_official_public_community_posts =
-        find_public_community_posts(&mut tx, 18, 0, viewer_user_id, viewer_show_sensitive).await?;
+    let feed_algorithm = viewer_feed_algorithm(&mut tx, viewer_user_id).await?;
+    let non_official_public_community_posts = find_home_feed_posts(
+        &mut tx,
+        feed_algorithm,
+        18,
+        0,
+        viewer_user_id,
+        viewer_show_sensitive,
+    )
+    .await?;
     let active_public_communities_raw = get_public_communities(&mut tx).await?;
 
     // Filter to communities with at least 10 posts
@@ -147,6 +176,7 @@ pub async fn home(
         recent_comments,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -162,8 +192,12 @@ pub async fn my_timeline(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let user = auth_session
         .user
@@ -180,6 +214,7 @@ pub async fn my_timeline(
         posts,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -192,6 +227,13 @@ pub struct LoadMoreQuery {
     pub limit: i64,
 }
 
+/// Lets clients ask for thumbnails sized to how they display them
+#[derive(Deserialize)]
+pub struct ThumbnailQuery {
+    /// Width in physical pixels of a grid cell; omitted means the largest thumbnail
+    pub thumbnail_width: Option<i32>,
+}
+
 #[derive(Deserialize)]
 pub struct CommentsQuery {
     #[serde(default)]
@@ -224,8 +266,10 @@ pub async fn load_more_public_posts(
         (None, false)
     };
 
-    let posts = find_public_community_posts(
+    let feed_algorithm = viewer_feed_algorithm(&mut tx, viewer_user_id).await?;
+    let posts = find_home_feed_posts(
         &mut tx,
+        feed_algorithm,
         query.limit,
         query.offset,
         viewer_user_id,
@@ -261,8 +305,10 @@ pub async fn load_more_public_posts_json(
         (None, false)
     };
 
-    let posts = find_public_community_posts(
+    let feed_algorithm = viewer_feed_algorithm(&mut tx, viewer_user_id).await?;
+    let posts = find_home_feed_posts(
         &mut tx,
+        feed_algorithm,
         query.limit,
         query.offset,
         viewer_user_id,
@@ -305,6 +351,7 @@ pub async fn load_more_public_posts_json(
 pub async fn get_active_communities_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
+    Query(thumbnail_query): Query<ThumbnailQuery>,
 ) -> Result<Json<CommunityListResponse>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -338,6 +385,21 @@ pub async fn get_active_communities_json(
     .await?;
     let community_stats = get_communities_members_count(&mut tx, &community_ids).await?;
 
+    let image_filenames: Vec<String> = recent_posts
+        .iter()
+        .map(|post| post.image_filename.clone())
+        .collect();
+    let thumbnails = find_thumbnails_by_image_filenames(
+        &mut tx,
+        &image_filenames,
+        thumbnail_query.thumbnail_width,
+    )
+    .await?;
+    let post_ids: Vec<Uuid> = recent_posts.iter().map(|post| post.id).collect();
+    let image_counts = count_post_images_by_post_ids(&mut tx, &post_ids).await?;
+    let mut categories_by_community =
+        find_categories_by_community_ids(&mut tx, &community_ids).await?;
+
     tx.commit().await?;
 
     // Group posts by community_id
@@ -353,9 +415,15 @@ pub async fn get_active_communities_json(
                     "{}/image/{}/{}",
                     state.config.r2_public_endpoint_url, image_prefix, post.image_filename
                 ),
+                thumbnail_url: thumbnail_or_image_url(
+                    &state.config.r2_public_endpoint_url,
+                    &thumbnails,
+                    &post.image_filename,
+                ),
                 image_width: post.image_width,
                 image_height: post.image_height,
                 is_sensitive: post.is_sensitive,
+                image_count: image_counts.get(&post.id).copied().unwrap_or(1),
             });
         }
     }
@@ -386,6 +454,9 @@ pub async fn get_active_communities_json(
                 posts_count: community.posts_count,
                 members_count,
                 recent_posts,
+                categories: categories_by_community
+                    .remove(&community.id)
+                    .unwrap_or_default(),
             }
         })
         .collect();
@@ -472,6 +543,18 @@ fn threaded_post_to_response(
     }
 }
 
+/// Deleted posts answer 410 so clients can tell them apart from IDs that never existed
+async fn missing_post_error(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post_id: Uuid,
+) -> Result<AppError, AppError> {
+    if is_post_deleted(tx, post_id).await? {
+        Ok(AppError::Gone("Post".to_string()))
+    } else {
+        Ok(AppError::NotFound("Post".to_string()))
+    }
+}
+
 pub async fn get_post_details_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
@@ -481,15 +564,32 @@ pub async fn get_post_details_json(
     let mut tx = db.begin().await?;
 
     // Get post details with proper types
-    let post_data = find_post_detail_for_json(&mut tx, post_id)
-        .await?
-        .ok_or_else(|| anyhow::anyhow!("Post not found"))?;
+    let Some(post_data) = find_post_detail_for_json(&mut tx, post_id).await? else {
+        return Err(missing_post_error(&mut tx, post_id).await?);
+    };
+
+    // Posts waiting for review are only shown to their author and the community's staff
+    if post_data.pending_approval {
+        let viewer_id = auth_session.user.as_ref().map(|user| user.id);
+        let can_view = match (viewer_id, post_data.community_id) {
+            (Some(viewer_id), _) if viewer_id == post_data.author_id => true,
+            (Some(viewer_id), Some(community_id)) => matches!(
+                get_user_role_in_community(&mut tx, viewer_id, community_id).await?,
+                Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)
+            ),
+            _ => false,
+        };
+        if !can_view {
+            return Err(AppError::NotFound("Post".to_string()));
+        }
+    }
 
     // Get parent post if it exists
     let parent_post = if let Some(parent_id) = post_data.parent_post_id {
         let parent_data = find_post_detail_for_json(&mut tx, parent_id).await?;
         parent_data.map(|p| {
-            let image_prefix = &p.image_filename[..2];
+            let image_filename = p.webp_filename.as_ref().unwrap_or(&p.image_filename);
+            let image_prefix = &image_filename[..2];
             ChildPostResponse {
                 id: p.id,
                 title: p.title,
@@ -503,7 +603,7 @@ pub async fn get_post_details_json(
                 image: ChildPostImage {
                     url: format!(
                         "{}/image/{}/{}",
-                        state.config.r2_public_endpoint_url, image_prefix, p.image_filename
+                        state.config.r2_public_endpoint_url, image_prefix, image_filename
                     ),
                     width: p.image_width,
                     height: p.image_height,
@@ -554,6 +654,7 @@ pub async fn get_post_details_json(
         viewer_count: post_data.viewer_count,
         image: ImageInfo {
             filename: post_data.image_filename,
+            webp_filename: post_data.webp_filename,
             width: post_data.image_width,
             height: post_data.image_height,
             tool: post_data.image_tool,
@@ -644,23 +745,27 @@ pub async fn get_post_comments_api(
     let offset = query.offset.max(0);
 
     // Get post's community_id to check visibility
-    let post_community_id = sqlx::query_scalar!(
+    let post = sqlx::query!(
         r#"
-        SELECT community_id
+        SELECT community_id, deleted_at
         FROM posts
-        WHERE id = $1 AND deleted_at IS NULL
+        WHERE id = $1
         "#,
         post_id
     )
     .fetch_optional(&mut *tx)
     .await?
-    .ok_or_else(|| anyhow::anyhow!("Post not found"))?;
+    .ok_or_else(|| AppError::NotFound("Post".to_string()))?;
+    if post.deleted_at.is_some() {
+        return Err(AppError::Gone("Post".to_string()));
+    }
+    let post_community_id = post.community_id;
 
     // Get the community to check visibility
     let community = sqlx::query_as!(
         Community,
         r#"
-        SELECT id, slug, name, description, owner_id, visibility AS "visibility: _", created_at, updated_at, background_color, foreground_color
+        SELECT id, slug, name, description, owner_id, visibility AS "visibility: _", created_at, updated_at, background_color, foreground_color, is_sensitive
         FROM communities
         WHERE id = $1
         "#,
@@ -672,27 +777,28 @@ pub async fn get_post_comments_api(
     // Check authorization for private communities
     if let Some(ref community) = community {
         if community.visibility == crate::models::community::CommunityVisibility::Private {
-            // For private communities, user must be logged in and be a member
+            // For private communities, user must be logged in and be a member.
+            // Non-members get the same answer as for a missing post.
             if let Some(ref user) = auth_session.user {
                 let is_member = is_user_member(&mut tx, user.id, community.id).await?;
                 if !is_member {
-                    return Err(anyhow::anyhow!(
-                        "You must be a member to view comments in this private community"
-                    )
-                    .into());
+                    return Err(AppError::NotFound("Post".to_string()));
                 }
             } else {
-                return Err(anyhow::anyhow!(
-                    "Authentication required to view comments in private community"
-                )
-                .into());
+                return Err(AppError::Unauthorized);
             }
         }
     }
 
     // Get paginated comments
-    let (comments_data, _total_count) =
-        build_comment_thread_tree_paginated(&mut tx, post_id, limit, offset).await?;
+    let (comments_data, total_count) = build_comment_thread_tree_paginated(
+        &mut tx,
+        post_id,
+        limit,
+        offset,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     tx.commit().await?;
 
@@ -709,11 +815,21 @@ pub async fn get_post_comments_api(
             content_html: comment.content_html,
             actor_name: comment.actor_name,
             actor_handle: comment.actor_handle,
+            actor_url: comment.actor_url,
             actor_login_name: comment.actor_login_name,
             is_local: comment.is_local,
             created_at: comment.created_at,
             updated_at: comment.updated_at,
             deleted_at: comment.deleted_at,
+            reactions: comment
+                .reaction_counts
+                .into_iter()
+                .map(|r| ReactionCount {
+                    emoji: r.emoji,
+                    count: r.count,
+                    reacted_by_user: r.reacted_by_user,
+                })
+                .collect(),
             children: comment
                 .children
                 .into_iter()
@@ -725,15 +841,15 @@ pub async fn get_post_comments_api(
     let comments: Vec<ThreadedCommentResponse> =
         comments_data.into_iter().map(convert_to_response).collect();
 
-    // Determine if there are more comments
-    let has_more = comments.len() as i64 == limit;
+    // Pagination counts top-level threads; replies always come with their thread
+    let has_more = offset + limit < total_count;
 
     Ok(Json(CommentsListResponse {
         comments,
         pagination: PaginationMeta {
             offset: offset + limit,
             limit,
-            total: None,
+            total: Some(total_count),
             has_more,
         },
     }))
@@ -781,7 +897,7 @@ pub async fn create_comment_api(
     let community = sqlx::query_as!(
         Community,
         r#"
-        SELECT id, slug, name, description, owner_id, visibility AS "visibility: _", created_at, updated_at, background_color, foreground_color
+        SELECT id, slug, name, description, owner_id, visibility AS "visibility: _", created_at, updated_at, background_color, foreground_color, is_sensitive
         FROM communities
         WHERE id = $1
         "#,
@@ -848,6 +964,7 @@ pub async fn create_comment_api(
                     if parent_user_id != user.id {
                         if let Ok(notification) = create_notification(
                             &mut tx,
+                            &state.notification_events,
                             CreateNotificationParams {
                                 recipient_id: parent_user_id,
                                 actor_id: actor.id,
@@ -871,6 +988,7 @@ pub async fn create_comment_api(
         if post.author_id != user.id {
             if let Ok(notification) = create_notification(
                 &mut tx,
+                &state.notification_events,
                 CreateNotificationParams {
                     recipient_id: post.author_id,
                     actor_id: actor.id,
@@ -894,7 +1012,7 @@ pub async fn create_comment_api(
     if !notification_info.is_empty() {
         let push_service = state.push_service.clone();
         let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
+        let send_notifications = async move {
             for (notification_id, recipient_id) in notification_info {
                 let mut tx = match db_pool.begin().await {
                     Ok(tx) => tx,
@@ -922,7 +1040,8 @@ pub async fn create_comment_api(
                 }
                 let _ = tx.commit().await;
             }
-        });
+        };
+        tokio::spawn(send_notifications.in_current_span());
     }
 
     // Return the created comment
@@ -935,11 +1054,13 @@ pub async fn create_comment_api(
         content_html: comment.content_html,
         actor_name: String::new(), // Will be populated by client from their cached data
         actor_handle: String::new(),
+        actor_url: String::new(),
         actor_login_name: None,
         is_local: true,
         created_at: comment.created_at,
         updated_at: comment.updated_at,
         deleted_at: None,
+        reactions: Vec::new(),
         children: Vec::new(),
     }))
 }
@@ -966,16 +1087,7 @@ pub async fn delete_comment_api(
         .ok_or_else(|| anyhow::anyhow!("No actor found for user"))?;
 
     // Find the comment
-    let comment = sqlx::query!(
-        r#"
-        SELECT id, actor_id, deleted_at
-        FROM comments
-        WHERE id = $1
-        "#,
-        comment_uuid
-    )
-    .fetch_optional(&mut *tx)
-    .await?;
+    let comment = find_comment_by_id(&mut tx, comment_uuid).await?;
 
     let comment = match comment {
         Some(c) => c,
@@ -1002,6 +1114,15 @@ pub async fn delete_comment_api(
 
     tx.commit().await?;
 
+    // Send Delete activity - don't fail if this fails
+    if let Err(e) = send_delete_activity_for_comment(&actor, &comment, &state).await {
+        tracing::warn!(
+            "Failed to send Delete activity for comment {}: {:?}",
+            comment_uuid,
+            e
+        );
+    }
+
     Ok(StatusCode::NO_CONTENT.into_response())
 }
 
@@ -1075,6 +1196,10 @@ pub async fn delete_post_api(
     delete_post_with_activity(&mut tx, post_uuid, Some(&state)).await?;
 
     tx.commit().await?;
+    // Deleting a draft lowers the author's draft count
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     Ok(StatusCode::NO_CONTENT.into_response())
 }
@@ -1249,6 +1374,7 @@ pub async fn add_reaction_api(
                     // Create notification for post author
                     let notification = create_notification(
                         &mut tx,
+                        &state.notification_events,
                         CreateNotificationParams {
                             recipient_id: author_id,
                             actor_id: actor.id,
@@ -1343,3 +1469,26 @@ pub async fn remove_reaction_api(
     })
     .into_response())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::post::{delete_post, PostDeletionReason};
+    use crate::models::test_fixtures;
+
+    #[sqlx::test]
+    async fn deleted_post_is_gone_and_unknown_post_is_not_found(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let author = test_fixtures::user(&mut tx, "author").await;
+        let post = test_fixtures::draft_post(&mut tx, author.id, None).await;
+        delete_post(&mut tx, post.id, PostDeletionReason::UserDeleted)
+            .await
+            .unwrap();
+
+        let deleted = missing_post_error(&mut tx, post.id).await.unwrap();
+        assert_eq!(deleted.into_response().status(), StatusCode::GONE);
+
+        let unknown = missing_post_error(&mut tx, Uuid::new_v4()).await.unwrap();
+        assert_eq!(unknown.into_response().status(), StatusCode::NOT_FOUND);
+    }
+}
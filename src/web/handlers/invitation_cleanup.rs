@@ -0,0 +1,33 @@
+use crate::models::community::delete_expired_invitations;
+use crate::web::state::AppState;
+use std::time::Duration;
+use tracing::{error, info};
+
+const CLEANUP_INTERVAL_SECONDS: u64 = 3600;
+
+/// Periodically delete community invitations that expired without an answer
+pub async fn cleanup_expired_invitations(state: AppState) {
+    info!(
+        "Starting expired invitation cleanup (interval: {}s, ttl: {} days)",
+        CLEANUP_INTERVAL_SECONDS, state.config.community_invitation_ttl_days
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(CLEANUP_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        match delete_expired(&state).await {
+            Ok(0) => {}
+            Ok(deleted) => info!("Deleted {} expired community invitations", deleted),
+            Err(e) => error!("Failed to delete expired community invitations: {:?}", e),
+        }
+    }
+}
+
+async fn delete_expired(state: &AppState) -> anyhow::Result<u64> {
+    let mut tx = state.db_pool.begin().await?;
+    let deleted = delete_expired_invitations(&mut tx).await?;
+    tx.commit().await?;
+    Ok(deleted)
+}
@@ -1,10 +1,10 @@
 use crate::app_error::AppError;
-use crate::locale::LOCALES;
-use crate::models::user::{AuthSession, Language};
+use crate::locale::{build_bundle, negotiate_accept_language};
+use crate::models::user::{AuthSession, Language, User};
 use crate::web::context::CommonContext;
 use anyhow;
 use anyhow::Result;
-use axum::extract::State;
+use axum::extract::{ConnectInfo, State};
 use axum::http::StatusCode;
 use axum::response::{Html, IntoResponse};
 use axum::{
@@ -16,14 +16,11 @@ use axum::{
     },
 };
 use data_encoding::BASE64URL_NOPAD;
+use std::net::{IpAddr, SocketAddr};
 use uuid::Uuid;
 
 use fluent::bundle::FluentBundle;
 use fluent::FluentResource;
-use fluent_langneg::convert_vec_str_to_langids_lossy;
-use fluent_langneg::negotiate_languages;
-use fluent_langneg::parse_accepted_languages;
-use fluent_langneg::NegotiationStrategy;
 use intl_memoizer::concurrent::IntlLangMemoizer;
 use minijinja::context;
 
@@ -31,25 +28,51 @@ use super::state::AppState;
 
 pub mod about;
 pub mod account;
+pub mod account_deletion;
 pub mod activitypub;
+pub mod announcement;
 pub mod auth;
+pub mod boost;
 pub mod collaborate;
 pub mod collaborate_cleanup;
 pub mod community;
+pub mod community_archive;
+pub mod community_category;
 pub mod devices;
 pub mod draw;
+pub mod export;
+pub mod federation_backfill;
+pub mod feed;
+pub mod follow_requests;
 pub mod hashtag;
 pub mod home;
+pub mod invitation_cleanup;
+pub mod nodeinfo;
 pub mod notifications;
+pub mod oauth;
 pub mod password_reset;
 pub mod post;
 pub mod policy;
 pub mod privacy;
 pub mod report;
 pub mod profile;
+pub mod replay_archive;
+pub mod scheduled_posts;
 pub mod search;
+pub mod trending_hashtags;
 pub mod well_known;
 
+/// Longest description shown in a link preview, in characters
+pub const LINK_PREVIEW_DESCRIPTION_LENGTH: usize = 160;
+
+/// Open Graph and Twitter Card fields describing a page when its link is shared
+pub struct LinkPreview {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub url: String,
+}
+
 pub async fn handler_404(
     auth_session: AuthSession,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
@@ -58,14 +81,46 @@ pub async fn handler_404(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("404.jinja")?;
     let rendered: String = template.render(context! {
         current_user => auth_session.user,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn handler_410(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("410.jinja")?;
+    let rendered: String = template.render(context! {
+        current_user => auth_session.user,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -80,14 +135,19 @@ pub async fn render_403(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("403.jinja")?;
     let rendered: String = template.render(context! {
         current_user => auth_session.user,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -96,25 +156,9 @@ pub async fn render_403(
 
 pub fn detect_preferred_language(accept_language: &HeaderValue) -> Option<Language> {
     let header_str = accept_language.to_str().ok()?;
-    let requested = parse_accepted_languages(header_str);
-    let available = convert_vec_str_to_langids_lossy(["ko", "ja", "en", "zh"]);
-
-    let supported = negotiate_languages(
-        &requested,
-        &available,
-        None, // No default - if no match, return None
-        NegotiationStrategy::Filtering,
-    );
-
-    let lang_code = supported.first().map(|l| l.language.as_str())?;
-
-    match lang_code {
-        "ko" => Some(Language::Ko),
-        "ja" => Some(Language::Ja),
-        "en" => Some(Language::En),
-        "zh" => Some(Language::Zh),
-        _ => None, // No match - return None instead of defaulting
-    }
+
+    // No match - return None instead of defaulting
+    negotiate_accept_language(header_str).into_iter().next()
 }
 
 pub struct ExtractAcceptLanguage(HeaderValue);
@@ -135,6 +179,32 @@ where
     }
 }
 
+/// Client address for throttling. Behind Caddy the peer is always the proxy, so the
+/// address it appended to X-Forwarded-For wins over the connection's peer address.
+pub struct ExtractClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ExtractClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let forwarded = parts
+            .headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok());
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        Ok(ExtractClientIp(forwarded.or(peer)))
+    }
+}
+
 /// Extractor that provides the computed locale string for templates
 pub struct ExtractFtlLang(pub String);
 
@@ -163,14 +233,8 @@ where
                 )
             })?;
 
-        // Get user's preferred language
-        let user_preferred_language = auth_session
-            .user
-            .as_ref()
-            .and_then(|u| u.preferred_language.clone());
-
-        // Get the bundle and extract locale
-        let bundle = get_bundle(&accept_language, user_preferred_language);
+        // Get the bundle for the user's languages and extract the locale
+        let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
         let ftl_lang = bundle
             .locales
             .first()
@@ -186,59 +250,49 @@ fn get_bundle(
     user_preferred_language: Option<Language>,
 ) -> FluentBundle<&FluentResource, IntlLangMemoizer> {
     match user_preferred_language {
-        Some(lang) => {
-            let language = match lang {
-                Language::Ko => "ko",
-                Language::Ja => "ja",
-                Language::En => "en",
-                Language::Zh => "zh",
-            };
-            let ftl = LOCALES
-                .get(language)
-                .or_else(|| LOCALES.get("en"))
-                .expect("English locale must exist");
-
-            let lang_id = language
-                .parse()
-                .expect("Hardcoded language string should parse");
-            let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
-            bundle.add_resource(ftl).expect("Failed to add a resource.");
-
-            bundle
-        }
-        None => {
-            // Fallback to "en" if header is not valid UTF-8
-            let header_str = accept_language.to_str().unwrap_or("en");
-            let requested = parse_accepted_languages(header_str);
-            let available = convert_vec_str_to_langids_lossy(["ko", "ja", "en", "zh"]);
-            let default = "en".parse().expect("Failed to parse a langid.");
-
-            let supported = negotiate_languages(
-                &requested,
-                &available,
-                Some(&default),
-                NegotiationStrategy::Filtering,
-            );
-
-            let lang_code = supported
-                .first()
-                .map(|l| l.language.as_str())
-                .unwrap_or("en");
-
-            let ftl = LOCALES
-                .get(lang_code)
-                .or_else(|| LOCALES.get("en"))
-                .expect("English locale must exist");
-
-            let lang_id = lang_code.parse().expect("Negotiated language should parse");
-            let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
-            bundle.add_resource(ftl).expect("Failed to add a resource.");
+        Some(lang) => build_bundle(&[lang.code()]),
+        None => negotiated_bundle(accept_language, &[]),
+    }
+}
 
-            bundle
+/// Like `get_bundle`, but falls back through the user's configured languages
+/// before English
+fn get_user_bundle(
+    accept_language: &HeaderValue,
+    user: Option<&User>,
+) -> FluentBundle<&'static FluentResource, IntlLangMemoizer> {
+    let Some(user) = user else {
+        return negotiated_bundle(accept_language, &[]);
+    };
+    match &user.preferred_language {
+        Some(lang) => {
+            let languages: Vec<&str> = std::iter::once(lang)
+                .chain(&user.language_fallbacks)
+                .map(Language::code)
+                .collect();
+            build_bundle(&languages)
         }
+        None => negotiated_bundle(accept_language, &user.language_fallbacks),
     }
 }
 
+fn negotiated_bundle(
+    accept_language: &HeaderValue,
+    fallbacks: &[Language],
+) -> FluentBundle<&'static FluentResource, IntlLangMemoizer> {
+    // Fallback to "en" if header is not valid UTF-8
+    let header_str = accept_language.to_str().unwrap_or("en");
+
+    // Every accepted language is tried in order, then the user's own fallbacks;
+    // `build_bundle` ends the chain with English
+    let languages: Vec<&str> = negotiate_accept_language(header_str)
+        .iter()
+        .chain(fallbacks)
+        .map(Language::code)
+        .collect();
+    build_bundle(&languages)
+}
+
 /// Parse ID from URL path, supporting both UUID format and legacy base64 format.
 /// Returns either the parsed UUID, a redirect response for legacy URLs, or an error response.
 pub enum ParsedId {
@@ -406,3 +460,40 @@ pub fn safe_parse_email(s: &str) -> Result<lettre::Address, AppError> {
     s.parse()
         .map_err(|e| AppError::InvalidEmail(format!("{}: {}", s, e)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle_locale(bundle: &FluentBundle<&FluentResource, IntlLangMemoizer>) -> String {
+        bundle.locales[0].to_string()
+    }
+
+    #[test]
+    fn regional_chinese_without_a_preference_gets_the_chinese_bundle() {
+        let accept_language = HeaderValue::from_static("zh-CN,zh;q=0.9,en;q=0.8");
+
+        assert_eq!(
+            detect_preferred_language(&accept_language),
+            Some(Language::Zh)
+        );
+        assert_eq!(bundle_locale(&get_bundle(&accept_language, None)), "zh");
+        assert_eq!(
+            bundle_locale(&get_user_bundle(&accept_language, None)),
+            "zh"
+        );
+    }
+
+    #[test]
+    fn preferred_language_wins_over_accept_language() {
+        let accept_language = HeaderValue::from_static("zh-CN");
+        let bundle = get_bundle(&accept_language, Some(Language::Ja));
+        assert_eq!(bundle_locale(&bundle), "ja");
+    }
+
+    #[test]
+    fn unknown_accept_language_falls_back_to_english() {
+        let accept_language = HeaderValue::from_static("fr-FR");
+        assert_eq!(bundle_locale(&get_bundle(&accept_language, None)), "en");
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::Json;
+use lazy_static::lazy_static;
+use serde_json::json;
+use tokio::sync::RwLock;
+
+use crate::app_error::AppError;
+use crate::models::nodeinfo::{get_local_usage, LocalUsage};
+use crate::web::state::AppState;
+
+/// How long usage statistics are served from memory before hitting the database again
+const USAGE_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+lazy_static! {
+    static ref USAGE_CACHE: RwLock<Option<(Instant, LocalUsage)>> = RwLock::new(None);
+}
+
+async fn cached_local_usage(state: &AppState) -> Result<LocalUsage, AppError> {
+    if let Some((fetched_at, usage)) = USAGE_CACHE.read().await.as_ref() {
+        if fetched_at.elapsed() < USAGE_CACHE_TTL {
+            return Ok(usage.clone());
+        }
+    }
+
+    let mut cache = USAGE_CACHE.write().await;
+
+    // Another request may have refreshed the cache while we waited for the lock
+    if let Some((fetched_at, usage)) = cache.as_ref() {
+        if fetched_at.elapsed() < USAGE_CACHE_TTL {
+            return Ok(usage.clone());
+        }
+    }
+
+    let mut tx = state.db_pool.begin().await?;
+    let usage = get_local_usage(&mut tx).await?;
+    tx.commit().await?;
+
+    *cache = Some((Instant::now(), usage.clone()));
+
+    Ok(usage)
+}
+
+/// Handler for the NodeInfo 2.1 document
+/// More info: https://github.com/jhass/nodeinfo/blob/main/PROTOCOL.md
+pub async fn nodeinfo_2_1(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let usage = cached_local_usage(&state).await?;
+
+    let nodeinfo = json!({
+        "version": "2.1",
+        "software": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+            "repository": "https://github.com/yangnaru/oeee-cafe",
+            "homepage": state.config.base_url,
+        },
+        "protocols": ["activitypub"],
+        "services": {
+            "inbound": [],
+            "outbound": [],
+        },
+        "openRegistrations": true,
+        "usage": {
+            "users": {
+                "total": usage.total_users,
+                "activeMonth": usage.active_month_users,
+                "activeHalfyear": usage.active_halfyear_users,
+            },
+            "localPosts": usage.local_posts,
+        },
+        "metadata": {
+            "nodeName": state.config.domain,
+        },
+    });
+
+    Ok((
+        [(
+            CONTENT_TYPE,
+            "application/json; profile=\"http://nodeinfo.diaspora.software/ns/schema/2.1#\"",
+        )],
+        Json(nodeinfo),
+    ))
+}
@@ -2,20 +2,29 @@ use crate::app_error::{error_codes, AppError};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::{Html, IntoResponse, Json},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Json,
+    },
 };
 use axum_messages::Messages;
+use chrono::{DateTime, Utc};
+use futures_util::stream;
 use minijinja::context;
 use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
 use uuid::Uuid;
 
 use crate::{
+    common_context_cache::CommonContextCache,
     models::{
-        community::get_pending_invitations_with_details_for_user,
+        community::{find_community_by_slug, get_pending_invitations_with_details_for_user},
         notification::{
-            delete_notification, get_notification_by_id, get_unread_count,
+            delete_notification, get_grouped_notifications, get_notification_by_id,
+            get_notification_group_size, get_unread_count,
             list_notifications as fetch_notifications, mark_all_notifications_as_read,
-            mark_notification_as_read,
+            mark_notification_as_read, mark_notification_group_as_read, mark_read_by_community,
+            NotificationCursor, NotificationType, NotificationWithActor,
         },
         user::AuthSession,
     },
@@ -23,18 +32,38 @@ use crate::{
         context::CommonContext,
         handlers::ExtractFtlLang,
         responses::{
-            ErrorResponse, MarkAllReadResponse, MarkNotificationReadResponse, NotificationItem,
+            ErrorResponse, GroupedNotificationItem, GroupedNotificationsListResponse,
+            MarkAllReadResponse, MarkNotificationReadResponse, NotificationItem,
             NotificationsListResponse, UnreadCountResponse,
         },
         state::AppState,
     },
 };
 
+const NOTIFICATIONS_PAGE_SIZE: i64 = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationPageQuery {
+    pub cursor: Option<String>,
+    #[serde(rename = "type")]
+    pub type_filter: Option<NotificationType>,
+}
+
+fn parse_cursor(cursor: Option<&str>) -> Result<Option<NotificationCursor>, AppError> {
+    cursor
+        .map(|cursor| {
+            NotificationCursor::decode(cursor)
+                .ok_or_else(|| AppError::InvalidFormData("Invalid cursor".to_string()))
+        })
+        .transpose()
+}
+
 pub async fn list_notifications(
     auth_session: AuthSession,
     State(state): State<AppState>,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
     messages: Messages,
+    Query(query): Query<NotificationPageQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -45,8 +74,15 @@ pub async fn list_notifications(
         .ok_or(AppError::Unauthorized)?
         .clone();
 
-    // Fetch notifications using the new notification system
-    let notifications = fetch_notifications(&mut tx, user.id, 50, 0).await?;
+    // Fetch the first page, collapsing repeated notifications on the same post
+    let (notification_groups, next_cursor) = get_grouped_notifications(
+        &mut tx,
+        user.id,
+        NOTIFICATIONS_PAGE_SIZE,
+        None,
+        query.type_filter.clone(),
+    )
+    .await?;
 
     // Fetch pending invitations with all details in a single query (no N+1)
     let invitations = get_pending_invitations_with_details_for_user(&mut tx, user.id).await?;
@@ -66,8 +102,12 @@ pub async fn list_notifications(
         .collect();
 
     // Get common context (includes unread_notification_count and draft_post_count)
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     tx.commit().await?;
 
@@ -75,16 +115,56 @@ pub async fn list_notifications(
     let rendered = template.render(context! {
         current_user => auth_session.user,
         messages => messages.into_iter().collect::<Vec<_>>(),
-        notifications => notifications,
+        notification_groups,
+        next_cursor => next_cursor.map(|cursor| cursor.encode()),
+        type_filter => query.type_filter,
+        // "Mark all as read" only covers what existed when the page was loaded
+        loaded_at => Utc::now().to_rfc3339(),
         invitations => invitations_with_details,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
     Ok(Html(rendered).into_response())
 }
 
+/// Next page of notification groups for infinite scroll (HTMX)
+pub async fn hx_notifications_page(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    Query(query): Query<NotificationPageQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let cursor = parse_cursor(query.cursor.as_deref())?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let (notification_groups, next_cursor) = get_grouped_notifications(
+        &mut tx,
+        user.id,
+        NOTIFICATIONS_PAGE_SIZE,
+        cursor,
+        query.type_filter.clone(),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let template = state.env.get_template("notifications_fragment.jinja")?;
+    let rendered = template.render(context! {
+        notification_groups,
+        next_cursor => next_cursor.map(|cursor| cursor.encode()),
+        type_filter => query.type_filter,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
 /// Mark a specific notification as read
 pub async fn mark_notification_read(
     auth_session: AuthSession,
@@ -112,6 +192,9 @@ pub async fn mark_notification_read(
     let notification = get_notification_by_id(&mut tx, notification_id, user.id).await?;
 
     tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     if let Some(notification) = notification {
         // Render the notification using the notification_item template
@@ -127,10 +210,89 @@ pub async fn mark_notification_read(
     }
 }
 
+/// Mark every notification grouped with the given one as read
+pub async fn mark_notification_group_read(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(notification_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let user = auth_session
+        .user
+        .as_ref()
+        .ok_or(AppError::Unauthorized)?
+        .clone();
+
+    mark_notification_group_as_read(&mut tx, notification_id, user.id).await?;
+
+    let notification = get_notification_by_id(&mut tx, notification_id, user.id).await?;
+    let Some(notification) = notification else {
+        tx.rollback().await?;
+        return Ok((StatusCode::NOT_FOUND, Html("".to_string())).into_response());
+    };
+    let group_size = get_notification_group_size(&mut tx, notification_id, user.id).await?;
+
+    tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
+
+    let template = state.env.get_template("notification_item.jinja")?;
+    let rendered = template.render(context! {
+        notification,
+        others_count => group_size - 1,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkAllReadQuery {
+    /// Only mark notifications created up to this time, typically when the list was loaded
+    pub up_to: Option<DateTime<Utc>>,
+}
+
+/// Mark all notifications as read for the current user (HTMX)
+/// Responds with an out-of-band swap that clears the unread badge in the header
+pub async fn hx_mark_all_notifications_read(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Query(query): Query<MarkAllReadQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let user = auth_session
+        .user
+        .as_ref()
+        .ok_or(AppError::Unauthorized)?
+        .clone();
+
+    mark_all_notifications_as_read(&mut tx, user.id, query.up_to).await?;
+    let unread_notification_count = get_unread_count(&mut tx, user.id).await?;
+
+    tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
+
+    let template = state.env.get_template("notification_unread_count.jinja")?;
+    let rendered = template.render(context! {
+        unread_notification_count,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
 /// Mark all notifications as read for the current user
 pub async fn mark_all_notifications_read(
     auth_session: AuthSession,
     State(state): State<AppState>,
+    Query(query): Query<MarkAllReadQuery>,
 ) -> Result<Json<MarkAllReadResponse>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -141,12 +303,51 @@ pub async fn mark_all_notifications_read(
         .ok_or(AppError::Unauthorized)?
         .clone();
 
-    let count = mark_all_notifications_as_read(&mut tx, user.id).await?;
+    let count = mark_all_notifications_as_read(&mut tx, user.id, query.up_to).await?;
+    let unread_count = get_unread_count(&mut tx, user.id).await?;
 
     tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     Ok(Json(MarkAllReadResponse {
         count: count as i64,
+        unread_count,
+    }))
+}
+
+/// Mark all notifications from a single community as read
+pub async fn mark_community_notifications_read(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<MarkAllReadResponse>, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let user = auth_session
+        .user
+        .as_ref()
+        .ok_or(AppError::Unauthorized)?
+        .clone();
+
+    let community =
+        find_community_by_slug(&mut tx, slug.strip_prefix('@').unwrap_or(&slug).to_string())
+            .await?
+            .ok_or_else(|| AppError::NotFound("Community".to_string()))?;
+
+    let count = mark_read_by_community(&mut tx, user.id, community.id).await?;
+    let unread_count = get_unread_count(&mut tx, user.id).await?;
+
+    tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
+
+    Ok(Json(MarkAllReadResponse {
+        count: count as i64,
+        unread_count,
     }))
 }
 
@@ -171,6 +372,34 @@ pub async fn get_unread_notification_count(
     Ok(Json(UnreadCountResponse { count }))
 }
 
+/// Stream `notification_created` events for the current user as server-sent events
+pub async fn notification_stream(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
+    let receiver = state.notification_events.subscribe();
+
+    let events = stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) if event.recipient_id == user_id => {
+                    let sse = Event::default()
+                        .event("notification_created")
+                        .json_data(&event);
+                    return Some((sse, receiver));
+                }
+                Ok(_) => continue,
+                // Skipped events are harmless since each one carries the full unread count
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
 /// Delete a specific notification
 pub async fn delete_notification_handler(
     auth_session: AuthSession,
@@ -189,6 +418,9 @@ pub async fn delete_notification_handler(
     let success = delete_notification(&mut tx, notification_id, user.id).await?;
 
     tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     if success {
         // Return empty response to remove the notification from DOM
@@ -206,14 +438,49 @@ pub async fn delete_notification_handler(
 pub struct NotificationQueryParams {
     #[serde(default = "default_limit")]
     pub limit: i64,
-    #[serde(default)]
-    pub offset: i64,
+    pub cursor: Option<String>,
+    #[serde(rename = "type")]
+    pub type_filter: Option<NotificationType>,
 }
 
 fn default_limit() -> i64 {
     50
 }
 
+fn to_notification_item(n: NotificationWithActor, r2_base_url: &str) -> NotificationItem {
+    // Build full image URL if filename exists
+    let post_image_url = n
+        .post_image_filename
+        .as_ref()
+        .map(|filename| format!("{}/image/{}/{}", r2_base_url, &filename[0..2], filename));
+
+    NotificationItem {
+        id: n.id,
+        recipient_id: n.recipient_id,
+        actor_id: n.actor_id,
+        actor_name: n.actor_name,
+        actor_handle: n.actor_handle,
+        actor_login_name: n.actor_login_name,
+        notification_type: n.notification_type,
+        post_id: n.post_id,
+        comment_id: n.comment_id,
+        reaction_iri: n.reaction_iri,
+        reaction_emoji: n.reaction_emoji,
+        guestbook_entry_id: n.guestbook_entry_id,
+        read_at: n.read_at,
+        created_at: n.created_at,
+        post_title: n.post_title,
+        post_author_login_name: n.post_author_login_name,
+        post_image_filename: n.post_image_filename,
+        post_image_url,
+        post_image_width: n.post_image_width,
+        post_image_height: n.post_image_height,
+        comment_content: n.comment_content,
+        comment_content_html: n.comment_content_html,
+        guestbook_content: n.guestbook_content,
+    }
+}
+
 /// API: List notifications with pagination (JSON response)
 pub async fn api_list_notifications(
     auth_session: AuthSession,
@@ -229,12 +496,12 @@ pub async fn api_list_notifications(
         .ok_or(AppError::Unauthorized)?
         .clone();
 
-    // Fetch notifications with pagination
-    let notifications = fetch_notifications(&mut tx, user.id, params.limit, params.offset).await?;
+    let cursor = parse_cursor(params.cursor.as_deref())?;
+    let limit = params.limit.clamp(1, 100);
 
-    // Get total unread count to determine if there are more
-    let total_count = get_unread_count(&mut tx, user.id).await?;
-    let has_more = (params.offset + params.limit) < total_count;
+    let (notifications, next_cursor) =
+        fetch_notifications(&mut tx, user.id, limit, cursor, params.type_filter).await?;
+    let unread_count = get_unread_count(&mut tx, user.id).await?;
 
     tx.commit().await?;
 
@@ -242,45 +509,83 @@ pub async fn api_list_notifications(
     let r2_base_url = &state.config.r2_public_endpoint_url;
     let notifications_typed: Vec<NotificationItem> = notifications
         .into_iter()
-        .map(|n| {
-            // Build full image URL if filename exists
-            let post_image_url = n
-                .post_image_filename
-                .as_ref()
-                .map(|filename| format!("{}/image/{}/{}", r2_base_url, &filename[0..2], filename));
-
-            NotificationItem {
-                id: n.id,
-                recipient_id: n.recipient_id,
-                actor_id: n.actor_id,
-                actor_name: n.actor_name,
-                actor_handle: n.actor_handle,
-                actor_login_name: n.actor_login_name,
-                notification_type: n.notification_type,
-                post_id: n.post_id,
-                comment_id: n.comment_id,
-                reaction_iri: n.reaction_iri,
-                reaction_emoji: n.reaction_emoji,
-                guestbook_entry_id: n.guestbook_entry_id,
-                read_at: n.read_at,
-                created_at: n.created_at,
-                post_title: n.post_title,
-                post_author_login_name: n.post_author_login_name,
-                post_image_filename: n.post_image_filename,
-                post_image_url,
-                post_image_width: n.post_image_width,
-                post_image_height: n.post_image_height,
-                comment_content: n.comment_content,
-                comment_content_html: n.comment_content_html,
-                guestbook_content: n.guestbook_content,
-            }
-        })
+        .map(|n| to_notification_item(n, r2_base_url))
         .collect();
 
     Ok(Json(NotificationsListResponse {
         notifications: notifications_typed,
-        total: total_count as usize,
-        has_more,
+        total: unread_count as usize,
+        has_more: next_cursor.is_some(),
+        next_cursor: next_cursor.map(|cursor| cursor.encode()),
+    }))
+}
+
+/// API: List notifications grouped by kind and post (JSON response)
+pub async fn api_list_grouped_notifications(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Query(params): Query<NotificationQueryParams>,
+) -> Result<Json<GroupedNotificationsListResponse>, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let user = auth_session
+        .user
+        .as_ref()
+        .ok_or(AppError::Unauthorized)?
+        .clone();
+
+    let cursor = parse_cursor(params.cursor.as_deref())?;
+    let limit = params.limit.clamp(1, 100);
+
+    let (groups, next_cursor) =
+        get_grouped_notifications(&mut tx, user.id, limit, cursor, params.type_filter).await?;
+
+    tx.commit().await?;
+
+    let r2_base_url = &state.config.r2_public_endpoint_url;
+    let groups = groups
+        .into_iter()
+        .map(|group| GroupedNotificationItem {
+            notification: to_notification_item(group.notification, r2_base_url),
+            others_count: group.others_count,
+            unread_count: group.unread_count,
+        })
+        .collect();
+
+    Ok(Json(GroupedNotificationsListResponse {
+        groups,
+        has_more: next_cursor.is_some(),
+        next_cursor: next_cursor.map(|cursor| cursor.encode()),
+    }))
+}
+
+/// API: Mark every notification in a group as read (JSON response)
+pub async fn api_mark_notification_group_read(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(notification_id): Path<Uuid>,
+) -> Result<Json<MarkAllReadResponse>, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let user = auth_session
+        .user
+        .as_ref()
+        .ok_or(AppError::Unauthorized)?
+        .clone();
+
+    let count = mark_notification_group_as_read(&mut tx, notification_id, user.id).await?;
+    let unread_count = get_unread_count(&mut tx, user.id).await?;
+
+    tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
+
+    Ok(Json(MarkAllReadResponse {
+        count: count as i64,
+        unread_count,
     }))
 }
 
@@ -317,40 +622,12 @@ pub async fn api_mark_notification_read(
     let notification = get_notification_by_id(&mut tx, notification_id, user.id).await?;
 
     tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     if let Some(n) = notification {
-        // Build full image URL if filename exists
-        let r2_base_url = &state.config.r2_public_endpoint_url;
-        let post_image_url = n
-            .post_image_filename
-            .as_ref()
-            .map(|filename| format!("{}/image/{}/{}", r2_base_url, &filename[0..2], filename));
-
-        let notification_item = NotificationItem {
-            id: n.id,
-            recipient_id: n.recipient_id,
-            actor_id: n.actor_id,
-            actor_name: n.actor_name,
-            actor_handle: n.actor_handle,
-            actor_login_name: n.actor_login_name,
-            notification_type: n.notification_type,
-            post_id: n.post_id,
-            comment_id: n.comment_id,
-            reaction_iri: n.reaction_iri,
-            reaction_emoji: n.reaction_emoji,
-            guestbook_entry_id: n.guestbook_entry_id,
-            read_at: n.read_at,
-            created_at: n.created_at,
-            post_title: n.post_title,
-            post_author_login_name: n.post_author_login_name,
-            post_image_filename: n.post_image_filename,
-            post_image_url,
-            post_image_width: n.post_image_width,
-            post_image_height: n.post_image_height,
-            comment_content: n.comment_content,
-            comment_content_html: n.comment_content_html,
-            guestbook_content: n.guestbook_content,
-        };
+        let notification_item = to_notification_item(n, &state.config.r2_public_endpoint_url);
 
         Ok(Json(MarkNotificationReadResponse {
             notification: notification_item,
@@ -386,6 +663,9 @@ pub async fn api_delete_notification(
     let success = delete_notification(&mut tx, notification_id, user.id).await?;
 
     tx.commit().await?;
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user.id)
+        .await;
 
     if success {
         Ok(StatusCode::NO_CONTENT.into_response())
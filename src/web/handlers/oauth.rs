@@ -0,0 +1,428 @@
+use crate::app_error::AppError;
+use crate::models::oauth::{
+    consume_oauth_code, create_oauth_code, create_oauth_token, find_oauth_client_by_client_id,
+    find_user_id_by_access_token, generate_oauth_secret, hash_oauth_secret, revoke_oauth_token,
+    revoke_oauth_token_for_refresh, verify_pkce, OAuthClient,
+};
+use crate::models::user::AuthSession;
+use crate::web::context::CommonContext;
+use crate::web::handlers::ExtractFtlLang;
+use crate::web::responses::{OAuthErrorResponse, OAuthTokenResponse};
+use crate::web::state::AppState;
+use axum::extract::{Query, Request, State};
+use axum::http::header::{AUTHORIZATION, CACHE_CONTROL};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::{Form, Json};
+use axum_login::AuthnBackend;
+use chrono::{TimeDelta, Utc};
+use minijinja::context;
+use serde::Deserialize;
+use sqlx::{Postgres, Transaction};
+use url::Url;
+use uuid::Uuid;
+
+const AUTHORIZATION_CODE_TTL_MINUTES: i64 = 10;
+
+#[derive(Deserialize, Debug)]
+pub struct AuthorizeParams {
+    response_type: String,
+    client_id: String,
+    redirect_uri: String,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+    state: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AuthorizeForm {
+    #[serde(flatten)]
+    params: AuthorizeParams,
+    decision: String,
+}
+
+#[derive(Deserialize)]
+pub struct TokenForm {
+    grant_type: String,
+    client_id: String,
+    code: Option<String>,
+    redirect_uri: Option<String>,
+    code_verifier: Option<String>,
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RefreshForm {
+    client_id: String,
+    refresh_token: String,
+}
+
+#[derive(Deserialize)]
+pub struct RevokeForm {
+    client_id: String,
+    token: String,
+}
+
+fn oauth_error(status: StatusCode, error: &'static str, description: &str) -> Response {
+    (
+        status,
+        [(CACHE_CONTROL, "no-store")],
+        Json(OAuthErrorResponse {
+            error,
+            error_description: description.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Send the user back to the client with `params` added to its redirect URI
+fn redirect_to_client(
+    redirect_uri: &str,
+    params: &[(&str, &str)],
+    state: Option<&str>,
+) -> Response {
+    let Ok(mut url) = Url::parse(redirect_uri) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    {
+        let mut query = url.query_pairs_mut();
+        query.extend_pairs(params);
+        if let Some(state) = state {
+            query.append_pair("state", state);
+        }
+    }
+    Redirect::to(url.as_str()).into_response()
+}
+
+/// Check an authorization request. An unknown client or redirect URI is shown
+/// as an error page, since redirecting there could hand the result to anyone;
+/// other problems are reported back to the client.
+async fn check_authorize_request(
+    tx: &mut Transaction<'_, Postgres>,
+    params: &AuthorizeParams,
+) -> Result<Result<OAuthClient, Response>, AppError> {
+    let client = find_oauth_client_by_client_id(tx, &params.client_id).await?;
+    let Some(client) = client.filter(|client| client.allows_redirect_uri(&params.redirect_uri))
+    else {
+        return Ok(Err((
+            StatusCode::BAD_REQUEST,
+            "Unknown client_id or redirect_uri",
+        )
+            .into_response()));
+    };
+
+    // PKCE is mandatory, and only with S256
+    let has_challenge = params
+        .code_challenge
+        .as_deref()
+        .is_some_and(|challenge| !challenge.is_empty());
+    let error = if params.response_type != "code" {
+        Some("unsupported_response_type")
+    } else if !has_challenge || params.code_challenge_method.as_deref() != Some("S256") {
+        Some("invalid_request")
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        return Ok(Err(redirect_to_client(
+            &params.redirect_uri,
+            &[("error", error)],
+            params.state.as_deref(),
+        )));
+    }
+
+    Ok(Ok(client))
+}
+
+/// Consent page asking the signed-in user to let a client act on their behalf
+pub async fn oauth_authorize(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Query(params): Query<AuthorizeParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let client = match check_authorize_request(&mut tx, &params).await? {
+        Ok(client) => client,
+        Err(response) => return Ok(response),
+    };
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("oauth_authorize.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        client_name => client.name,
+        client_id => params.client_id,
+        redirect_uri => params.redirect_uri,
+        code_challenge => params.code_challenge,
+        code_challenge_method => params.code_challenge_method,
+        oauth_state => params.state,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+/// Record the user's decision on the consent page and issue an authorization
+/// code if they approved
+pub async fn do_oauth_authorize(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Form(form): Form<AuthorizeForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+    let params = form.params;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let client = match check_authorize_request(&mut tx, &params).await? {
+        Ok(client) => client,
+        Err(response) => return Ok(response),
+    };
+
+    if form.decision != "approve" {
+        return Ok(redirect_to_client(
+            &params.redirect_uri,
+            &[("error", "access_denied")],
+            params.state.as_deref(),
+        ));
+    }
+
+    let code = generate_oauth_secret();
+    create_oauth_code(
+        &mut tx,
+        &hash_oauth_secret(&code),
+        client.id,
+        user.id,
+        &params.redirect_uri,
+        params.code_challenge.as_deref().unwrap_or_default(),
+        Utc::now() + TimeDelta::minutes(AUTHORIZATION_CODE_TTL_MINUTES),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(redirect_to_client(
+        &params.redirect_uri,
+        &[("code", &code)],
+        params.state.as_deref(),
+    ))
+}
+
+async fn issue_tokens(
+    tx: &mut Transaction<'_, Postgres>,
+    state: &AppState,
+    client_id: Uuid,
+    user_id: Uuid,
+) -> Result<Response, AppError> {
+    let access_token = generate_oauth_secret();
+    let refresh_token = generate_oauth_secret();
+    create_oauth_token(
+        tx,
+        client_id,
+        user_id,
+        &hash_oauth_secret(&access_token),
+        &hash_oauth_secret(&refresh_token),
+        state.config.oauth_access_token_expires_at(),
+        state.config.oauth_refresh_token_expires_at(),
+    )
+    .await?;
+
+    Ok((
+        [(CACHE_CONTROL, "no-store")],
+        Json(OAuthTokenResponse {
+            access_token,
+            token_type: "Bearer",
+            expires_in: state.config.oauth_access_token_ttl_seconds,
+            refresh_token,
+        }),
+    )
+        .into_response())
+}
+
+async fn refresh_token_grant(
+    state: &AppState,
+    client_id: &str,
+    refresh_token: &str,
+) -> Result<Response, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let Some(client) = find_oauth_client_by_client_id(&mut tx, client_id).await? else {
+        return Ok(oauth_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Unknown client",
+        ));
+    };
+
+    let old_token =
+        revoke_oauth_token_for_refresh(&mut tx, client.id, &hash_oauth_secret(refresh_token))
+            .await?;
+    let Some(old_token) = old_token else {
+        return Ok(oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            "Invalid or expired refresh token",
+        ));
+    };
+
+    let response = issue_tokens(&mut tx, state, client.id, old_token.user_id).await?;
+    tx.commit().await?;
+
+    Ok(response)
+}
+
+/// Exchange an authorization code and its PKCE verifier for tokens. Refresh
+/// tokens are accepted here too, as RFC 6749 clients expect.
+pub async fn oauth_token(
+    State(state): State<AppState>,
+    Form(form): Form<TokenForm>,
+) -> Result<impl IntoResponse, AppError> {
+    match form.grant_type.as_str() {
+        "authorization_code" => {}
+        "refresh_token" => {
+            let Some(refresh_token) = form.refresh_token else {
+                return Ok(oauth_error(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_request",
+                    "Missing refresh_token",
+                ));
+            };
+            return refresh_token_grant(&state, &form.client_id, &refresh_token).await;
+        }
+        _ => {
+            return Ok(oauth_error(
+                StatusCode::BAD_REQUEST,
+                "unsupported_grant_type",
+                "Only authorization_code and refresh_token are supported",
+            ));
+        }
+    }
+
+    let (Some(code), Some(redirect_uri), Some(code_verifier)) =
+        (form.code, form.redirect_uri, form.code_verifier)
+    else {
+        return Ok(oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            "Missing code, redirect_uri or code_verifier",
+        ));
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let Some(client) = find_oauth_client_by_client_id(&mut tx, &form.client_id).await? else {
+        return Ok(oauth_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Unknown client",
+        ));
+    };
+
+    // The code is used up even when the checks below fail, so a guessed or
+    // intercepted code can't be retried
+    let oauth_code = consume_oauth_code(&mut tx, &hash_oauth_secret(&code)).await?;
+    tx.commit().await?;
+    let Some(oauth_code) = oauth_code.filter(|oauth_code| {
+        oauth_code.client_id == client.id
+            && oauth_code.redirect_uri == redirect_uri
+            && verify_pkce(&code_verifier, &oauth_code.code_challenge)
+    }) else {
+        return Ok(oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            "Invalid authorization code or code_verifier",
+        ));
+    };
+
+    let mut tx = db.begin().await?;
+    let response = issue_tokens(&mut tx, &state, client.id, oauth_code.user_id).await?;
+    tx.commit().await?;
+
+    Ok(response)
+}
+
+/// Exchange a refresh token for a new access token and refresh token
+pub async fn oauth_token_refresh(
+    State(state): State<AppState>,
+    Form(form): Form<RefreshForm>,
+) -> Result<impl IntoResponse, AppError> {
+    refresh_token_grant(&state, &form.client_id, &form.refresh_token).await
+}
+
+/// Revoke an access token or refresh token (RFC 7009). Unknown tokens are not
+/// an error, so this can't be used to probe for valid ones.
+pub async fn oauth_revoke(
+    State(state): State<AppState>,
+    Form(form): Form<RevokeForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let Some(client) = find_oauth_client_by_client_id(&mut tx, &form.client_id).await? else {
+        return Ok(oauth_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Unknown client",
+        ));
+    };
+    revoke_oauth_token(&mut tx, client.id, &hash_oauth_secret(&form.token)).await?;
+    tx.commit().await?;
+
+    Ok(StatusCode::OK.into_response())
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Sign in requests that carry an OAuth access token but no session, so
+/// handlers see the token's user through `AuthSession` as usual. Nothing is
+/// written to the session. Must run inside the auth layer.
+pub async fn oauth_bearer_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let signed_in = request
+        .extensions()
+        .get::<AuthSession>()
+        .map(|auth_session| auth_session.user.is_some());
+    let token = bearer_token(request.headers()).map(hash_oauth_secret);
+
+    if let (Some(false), Some(token_hash)) = (signed_in, token) {
+        match find_bearer_user(&state, &token_hash).await {
+            Ok(Some(user_id)) => {
+                if let Some(auth_session) = request.extensions_mut().get_mut::<AuthSession>() {
+                    match auth_session.backend.get_user(&user_id).await {
+                        Ok(user) => auth_session.user = user,
+                        Err(e) => tracing::error!("Failed to load OAuth token user: {:?}", e),
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to look up OAuth access token: {:?}", e),
+        }
+    }
+
+    next.run(request).await
+}
+
+async fn find_bearer_user(state: &AppState, token_hash: &[u8]) -> Result<Option<Uuid>, AppError> {
+    let mut tx = state.db_pool.begin().await?;
+    let user_id = find_user_id_by_access_token(&mut tx, token_hash).await?;
+    tx.commit().await?;
+    Ok(user_id)
+}
@@ -1,12 +1,18 @@
 use crate::app_error::AppError;
 use crate::models::password_reset_challenge::{
     create_password_reset_challenge, delete_password_reset_challenges_for_user,
-    find_password_reset_challenge_by_token, PasswordResetChallenge,
+    find_password_reset_challenge_by_token_hash, generate_password_reset_token,
+    hash_password_reset_token, PasswordResetChallenge,
 };
 use crate::models::user::{find_user_by_email, update_password};
-use crate::web::handlers::{get_bundle, safe_get_message, ExtractAcceptLanguage, ExtractFtlLang};
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::web::handlers::{
+    get_bundle, safe_get_message, ExtractAcceptLanguage, ExtractClientIp, ExtractFtlLang,
+};
 use crate::web::state::AppState;
 use axum::extract::State;
+use axum::http::header::RETRY_AFTER;
+use axum::http::StatusCode;
 use axum::response::{Html, IntoResponse, Redirect};
 use axum::Form;
 use axum_messages::Messages;
@@ -17,7 +23,10 @@ use lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
 use lettre::{Message, SmtpTransport, Transport};
 use minijinja::context;
 use serde::Deserialize;
-use uuid::Uuid;
+use std::net::IpAddr;
+
+const PASSWORD_RESET_RATE_LIMIT_IP_SCOPE: &str = "password_reset:ip";
+const PASSWORD_RESET_RATE_LIMIT_EMAIL_SCOPE: &str = "password_reset:email";
 
 // Web form handlers
 
@@ -40,13 +49,64 @@ pub struct PasswordResetRequestForm {
     pub email: String,
 }
 
+/// Counts a reset request against both the client IP and the email address, so
+/// neither one inbox nor one client can be used to send a flood of reset emails.
+/// Returns the window to wait out when either is over its limit; fails open.
+async fn check_password_reset_rate_limit(
+    state: &AppState,
+    client_ip: Option<IpAddr>,
+    email: &str,
+) -> Option<u64> {
+    let config = &state.config;
+    let window_seconds = config.password_reset_rate_limit_window_seconds;
+    let limiter = RateLimiter::new(state.redis_pool.clone());
+    let mut allowed = true;
+
+    if let Some(ip) = client_ip {
+        let limit = RateLimit::new(config.password_reset_rate_limit_per_ip, window_seconds);
+        match limiter
+            .hit(PASSWORD_RESET_RATE_LIMIT_IP_SCOPE, &ip.to_string(), limit)
+            .await
+        {
+            Ok(ip_allowed) => allowed &= ip_allowed,
+            Err(e) => tracing::warn!("Failed to check password reset rate limit: {:?}", e),
+        }
+    }
+
+    let limit = RateLimit::new(config.password_reset_rate_limit_per_email, window_seconds);
+    match limiter
+        .hit(
+            PASSWORD_RESET_RATE_LIMIT_EMAIL_SCOPE,
+            &email.trim().to_lowercase(),
+            limit,
+        )
+        .await
+    {
+        Ok(email_allowed) => allowed &= email_allowed,
+        Err(e) => tracing::warn!("Failed to check password reset rate limit: {:?}", e),
+    }
+
+    (!allowed).then_some(window_seconds)
+}
+
 pub async fn password_reset_request(
     ExtractAcceptLanguage(accept_language): ExtractAcceptLanguage,
+    ExtractClientIp(client_ip): ExtractClientIp,
     State(state): State<AppState>,
     Form(form): Form<PasswordResetRequestForm>,
 ) -> Result<impl IntoResponse, AppError> {
     let bundle = get_bundle(&accept_language, None);
 
+    if let Some(retry_after) = check_password_reset_rate_limit(&state, client_ip, &form.email).await
+    {
+        return Ok((
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            safe_get_message(&bundle, "message-too-many-attempts"),
+        )
+            .into_response());
+    }
+
     // Always show success message to prevent email enumeration
     let template = state.env.get_template("password_reset_sent.jinja")?;
     let ftl_lang = bundle
@@ -92,7 +152,7 @@ pub async fn password_reset_request(
 
 #[derive(Deserialize)]
 pub struct PasswordResetVerifyForm {
-    pub token: Uuid,
+    pub token: String,
     pub new_password: String,
     pub new_password_confirm: String,
 }
@@ -138,11 +198,13 @@ pub async fn password_reset_verify(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    // Find challenge by token
-    let challenge = find_password_reset_challenge_by_token(&mut tx, form.token).await?;
+    // Find challenge by the token's hash; expired ones don't match
+    let token_hash = hash_password_reset_token(&state.config, &form.token)?;
+    let challenge = find_password_reset_challenge_by_token_hash(&mut tx, &token_hash).await?;
 
     if let Some(challenge) = challenge {
-        // Update password
+        // Update password, which also signs the user out everywhere since
+        // sessions are bound to the password hash
         let _ = update_password(&mut tx, challenge.user_id, form.new_password.clone()).await?;
 
         // Delete all password reset challenges for this user
@@ -174,7 +236,7 @@ pub async fn password_reset_verify_page(
 ) -> Result<Html<String>, AppError> {
     let template = state.env.get_template("password_reset_verify.jinja")?;
     let rendered = template.render(context! {
-        token => query.token.map(|t| t.to_string()),
+        token => query.token,
         ftl_lang
     })?;
 
@@ -183,7 +245,7 @@ pub async fn password_reset_verify_page(
 
 #[derive(Deserialize)]
 pub struct TokenQuery {
-    pub token: Option<Uuid>,
+    pub token: Option<String>,
 }
 
 // Helper function to create password reset challenge and send email
@@ -196,14 +258,14 @@ async fn create_and_send_password_reset_email(
     let db = &state.db_pool;
     let mut tx = db.begin().await.map_err(|e| e.to_string())?;
 
-    // Generate UUID token for magic link
-    let token = Uuid::new_v4();
+    // Random token for the magic link; only its hash is stored
+    let token = generate_password_reset_token();
+    let token_hash = hash_password_reset_token(&state.config, &token).map_err(|e| e.to_string())?;
 
-    let expires_at =
-        Utc::now() + TimeDelta::try_seconds(60 * 15).expect("15 minutes is a valid duration"); // 15 minutes
+    let expires_at = Utc::now() + TimeDelta::try_hours(1).expect("1 hour is a valid duration");
 
     let password_reset_challenge =
-        create_password_reset_challenge(&mut tx, user_id, email, token, expires_at)
+        create_password_reset_challenge(&mut tx, user_id, email, &token_hash, expires_at)
             .await
             .map_err(|e| e.to_string())?;
     tx.commit().await.map_err(|e| e.to_string())?;
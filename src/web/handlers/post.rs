@@ -1,36 +1,55 @@
 use crate::app_error::AppError;
+use crate::common_context_cache::CommonContextCache;
+use crate::community_listing_cache::CommunityListingCache;
+use crate::idempotency::{perform_once, IdempotencyStore, Performed};
+use crate::markdown_utils::markdown_summary;
 use crate::models::actor::Actor;
 use crate::models::comment::{
-    build_comment_thread_tree, create_comment, extract_mentions, find_users_by_login_names,
-    CommentDraft,
+    build_comment_thread_tree, create_comment, edit_comment, extract_mentions, find_comment_by_id,
+    find_comment_in_thread, find_comment_replies, find_users_by_login_names, is_comment_editable,
+    soft_delete_comment, Comment, CommentDraft,
+};
+use crate::models::comment_reaction::{
+    create_comment_reaction, delete_comment_reaction, find_user_comment_reaction,
+    get_comment_reaction_counts, CommentReactionDraft,
 };
 use crate::models::community::{
-    find_community_by_id, get_known_communities, get_user_role_in_community, is_user_member,
+    find_closed_posting_window, find_community_by_id, get_known_communities,
+    get_user_role_in_community, is_user_member, post_needs_approval, CommunityMemberRole,
 };
 use crate::models::follow;
 use crate::models::hashtag::{
-    get_hashtags_for_post, link_post_to_hashtags, parse_hashtag_input, unlink_post_hashtags,
+    add_community_default_hashtags, get_hashtags_for_post, link_post_to_hashtags,
+    parse_hashtag_input, unlink_post_hashtags,
+};
+use crate::models::image::{
+    delete_image_thumbnails, find_thumbnails_by_image_filenames, image_url, replay_object_key,
+    thumbnail_or_image_url,
 };
-use crate::models::image::find_image_by_id;
 use crate::models::notification::{
     create_notification, get_notification_by_id, get_unread_count, send_push_for_notification,
-    CreateNotificationParams, NotificationType,
+    CreateNotificationParams, NotificationEvents, NotificationType,
 };
 use crate::models::post::{
-    build_thread_tree, delete_post_with_activity, edit_post, edit_post_community,
-    find_draft_posts_by_author_id, find_post_by_id, increment_post_viewer_count, publish_post,
-    SerializableThreadedPost,
+    build_thread_tree, defer_post_federation, delete_post_with_activity, edit_post,
+    edit_post_community, find_draft_posts_by_author_id, find_post_by_id, find_post_images,
+    increment_post_viewer_count, is_post_deleted, is_post_pinnable, is_post_pinned, pin_post,
+    publish_post, schedule_post, submit_post_for_approval, unpin_post, SerializableThreadedPost,
 };
 use crate::models::reaction::{
-    create_reaction, delete_reaction, find_reactions_by_post_id, get_reaction_counts, ReactionDraft,
+    create_ap_like, create_reaction, delete_reaction, find_ap_like_by_reaction_iri,
+    find_reactions_by_post_id, generate_like_iri, get_reaction_counts, ReactionCount,
+    ReactionDraft, LIKE_EMOJI,
 };
-use crate::models::user::{find_user_by_id, AuthSession, Language};
+use crate::models::user::{find_user_by_id, AuthSession, User};
 use crate::web::context::CommonContext;
 use crate::web::handlers::activitypub::{
-    create_note_from_post, create_updated_note_from_post, generate_object_id, Announce, Create,
-    Note, UpdateNote,
+    create_note_from_post, create_updated_note_from_post, generate_object_id,
+    send_delete_activity_for_comment, send_update_activity_for_comment, Announce, Create, Note,
+    UpdateNote,
 };
-use crate::web::handlers::{get_bundle, handler_404, parse_id_with_legacy_support, safe_get_message, ExtractFtlLang, ParsedId};
+use crate::web::handlers::home::ThumbnailQuery;
+use crate::web::handlers::{get_user_bundle, handler_404, handler_410, parse_id_with_legacy_support, safe_format_message, safe_get_message, ExtractFtlLang, LinkPreview, ParsedId, LINK_PREVIEW_DESCRIPTION_LENGTH};
 use crate::web::state::AppState;
 use activitypub_federation::fetch::object_id::ObjectId;
 use activitypub_federation::traits::Actor as ActivityPubActor;
@@ -38,13 +57,15 @@ use anyhow::Error;
 use aws_sdk_s3::config::{Credentials as AwsCredentials, Region, SharedCredentialsProvider};
 use aws_sdk_s3::types::{Delete, ObjectIdentifier};
 use aws_sdk_s3::Client;
-use axum::extract::Path;
+use axum::extract::{Path, Query};
 use axum::http::{HeaderMap, HeaderValue};
 use axum::response::{IntoResponse, Json, Redirect};
 use axum::{extract::State, http::StatusCode, response::Html, Form};
 use axum_messages::Messages;
+use fluent::FluentArgs;
 use minijinja::context;
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 use urlencoding;
 use uuid::Uuid;
 
@@ -61,10 +82,55 @@ async fn get_community_slug_url(
     }
 }
 
+/// Read a post row's author_id, which every post must have
+pub(crate) fn post_author_id(
+    post: &std::collections::HashMap<String, Option<String>>,
+    post_id: Uuid,
+) -> Result<Uuid, AppError> {
+    post.get("author_id")
+        .and_then(|v| v.as_ref())
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or_else(|| AppError::DataIntegrity(format!("post {} has no valid author_id", post_id)))
+}
+
+/// Read a post row's community_id. Personal posts have none, but a value
+/// that isn't a UUID means the row is corrupt.
+fn post_community_id(
+    post: &std::collections::HashMap<String, Option<String>>,
+    post_id: Uuid,
+) -> Result<Option<Uuid>, AppError> {
+    match post.get("community_id").and_then(|v| v.as_ref()) {
+        Some(s) => Uuid::parse_str(s).map(Some).map_err(|_| {
+            AppError::DataIntegrity(format!("post {} has an invalid community_id", post_id))
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Link preview for a post page. Sensitive drawings are left out of the preview image.
+fn post_link_preview(
+    state: &AppState,
+    post: &std::collections::HashMap<String, Option<String>>,
+    post_id: Uuid,
+) -> LinkPreview {
+    let field = |key: &str| post.get(key).cloned().flatten();
+    let is_sensitive = field("is_sensitive").as_deref() == Some("true");
+    LinkPreview {
+        title: field("title"),
+        description: field("content")
+            .map(|content| markdown_summary(&content, LINK_PREVIEW_DESCRIPTION_LENGTH))
+            .filter(|description| !description.is_empty()),
+        image_url: field("image_filename")
+            .filter(|_| !is_sensitive)
+            .map(|filename| image_url(&state.config.r2_public_endpoint_url, &filename)),
+        url: format!("{}/posts/{}", state.config.base_url, post_id),
+    }
+}
+
 /// Helper function to show a flash error message and redirect
 fn flash_error_and_redirect(
     headers: &HeaderMap,
-    user_preferred_language: Option<Language>,
+    user: Option<&User>,
     messages: Messages,
     message_key: &str,
     redirect_path: &str,
@@ -73,12 +139,41 @@ fn flash_error_and_redirect(
         .get(axum::http::header::ACCEPT_LANGUAGE)
         .cloned()
         .unwrap_or_else(|| axum::http::HeaderValue::from_static(""));
-    let bundle = get_bundle(&accept_language, user_preferred_language);
+    let bundle = get_user_bundle(&accept_language, user);
     let error_message = safe_get_message(&bundle, message_key);
     messages.error(error_message);
     Redirect::to(redirect_path).into_response()
 }
 
+/// Posts waiting for review are only shown to their author and the community's
+/// owner and moderators
+async fn can_view_post_pending_approval(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    post: &std::collections::HashMap<String, Option<String>>,
+    post_id: Uuid,
+    community_id: Uuid,
+    viewer: Option<&User>,
+) -> Result<bool, AppError> {
+    if post
+        .get("pending_approval_at")
+        .and_then(|v| v.as_ref())
+        .is_none()
+    {
+        return Ok(true);
+    }
+    let Some(viewer) = viewer else {
+        return Ok(false);
+    };
+    if post_author_id(post, post_id)? == viewer.id {
+        return Ok(true);
+    }
+    let role = get_user_role_in_community(tx, viewer.id, community_id).await?;
+    Ok(matches!(
+        role,
+        Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)
+    ))
+}
+
 /// Helper function to redirect unauthenticated users to login with next parameter
 fn redirect_to_login(current_path: &str) -> axum::response::Response {
     let login_url = format!("/login?next={}", urlencoding::encode(current_path));
@@ -105,6 +200,7 @@ async fn send_post_to_followers(
         actor,
         &state.config.domain,
         &state.config.r2_public_endpoint_url,
+        &state.config.sensitive_content_warning,
     )
     .await?;
 
@@ -319,10 +415,7 @@ pub async fn post_relay_view(
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
     // Check if post is in a private community and if user has access
-    let community_id = post
-        .get("community_id")
-        .and_then(|v| v.as_ref())
-        .and_then(|s| Uuid::parse_str(s).ok());
+    let community_id = post_community_id(&post, uuid)?;
 
     if let Some(cid) = community_id {
         let community = find_community_by_id(&mut tx, cid).await?;
@@ -339,8 +432,7 @@ pub async fn post_relay_view(
                                 .get(axum::http::header::ACCEPT_LANGUAGE)
                                 .cloned()
                                 .unwrap_or_else(|| axum::http::HeaderValue::from_static(""));
-                            let user_preferred_language = user.preferred_language.clone();
-                            let bundle = get_bundle(&accept_language, user_preferred_language);
+                            let bundle = get_user_bundle(&accept_language, Some(user));
                             let error_message = safe_get_message(&bundle, "private-community-no-access");
                             messages.error(error_message);
                             return Ok(Redirect::to("/").into_response());
@@ -360,15 +452,16 @@ pub async fn post_relay_view(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     // Relay only works for community posts, not personal posts
     let post_data = post.clone();
-    let community_id = post_data
-        .get("community_id")
-        .and_then(|id| id.as_ref())
-        .and_then(|id_str| Uuid::parse_str(id_str).ok());
+    let community_id = post_community_id(&post_data, uuid)?;
 
     let community_id = match community_id {
         Some(id) => id,
@@ -403,6 +496,7 @@ pub async fn post_relay_view(
         is_relay => true,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -432,10 +526,7 @@ pub async fn post_view(
     match post {
         Some(ref post_data) => {
             // Check if post is in a private community and if user has access
-            let community_id = post_data
-                .get("community_id")
-                .and_then(|v| v.as_ref())
-                .and_then(|s| Uuid::parse_str(s).ok());
+            let community_id = post_community_id(post_data, uuid)?;
 
             let community = if let Some(cid) = community_id {
                 find_community_by_id(&mut tx, cid).await?
@@ -451,7 +542,7 @@ pub async fn post_view(
                                 get_user_role_in_community(&mut tx, user.id, community.id).await?;
                             if user_role.is_none() {
                                 // User is not a member of this private community
-                                return Ok(flash_error_and_redirect(&headers, user.preferred_language.clone(), messages, "private-community-no-access", "/"));
+                                return Ok(flash_error_and_redirect(&headers, Some(user), messages, "private-community-no-access", "/"));
                             }
                         }
                         None => {
@@ -460,6 +551,21 @@ pub async fn post_view(
                         }
                     }
                 }
+                if !can_view_post_pending_approval(
+                    &mut tx,
+                    post_data,
+                    uuid,
+                    community.id,
+                    auth_session.user.as_ref(),
+                )
+                .await?
+                {
+                    return Ok((
+                        StatusCode::NOT_FOUND,
+                        handler_404(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
+                    )
+                        .into_response());
+                }
                 post_community = Some(community);
             } else {
                 post_community = None;
@@ -468,6 +574,13 @@ pub async fn post_view(
             increment_post_viewer_count(&mut tx, uuid).await?;
         }
         None => {
+            if is_post_deleted(&mut tx, uuid).await? {
+                return Ok((
+                    StatusCode::GONE,
+                    handler_410(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
+                )
+                    .into_response());
+            }
             return Ok((
                 StatusCode::NOT_FOUND,
                 handler_404(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
@@ -479,7 +592,8 @@ pub async fn post_view(
     // At this point post is guaranteed to be Some (would have returned 404 otherwise)
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    let comments = build_comment_thread_tree(&mut tx, uuid).await?;
+    let comments =
+        build_comment_thread_tree(&mut tx, uuid, auth_session.user.as_ref().map(|u| u.id)).await?;
 
     // Get parent post data if it exists
     let (parent_post_author_login_name, parent_post_data) =
@@ -555,13 +669,14 @@ pub async fn post_view(
             (String::new(), None)
         };
 
-    let community_id = post
-        .get("community_id")
-        .and_then(|id| id.as_ref())
-        .and_then(|id_str| Uuid::parse_str(id_str).ok());
+    let community_id = post_community_id(&post, uuid)?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     // Get collaborative session participants if this post is from a collaborative session
     let collaborative_participants: Vec<CollaborativeParticipant> = sqlx::query!(
@@ -627,6 +742,7 @@ pub async fn post_view(
             .map_err(|e| AppError::from(anyhow::anyhow!("Template render error: {}", e)))?;
         Ok(Html(rendered).into_response())
     } else {
+        let link_preview = post_link_preview(&state, &post, uuid);
         let rendered = template
             .render(context! {
                 current_user => auth_session.user,
@@ -645,8 +761,15 @@ pub async fn post_view(
                 community_id,
                 draft_post_count => common_ctx.draft_post_count,
                 unread_notification_count => common_ctx.unread_notification_count,
+                announcement => common_ctx.announcement,
                 base_url => state.config.base_url.clone(),
                 domain => state.config.domain.clone(),
+                translation_enabled => crate::translation::translation_enabled(&state.config),
+                post_history_public => state.config.post_history_public,
+                og_title => link_preview.title,
+                og_description => link_preview.description,
+                og_image_url => link_preview.image_url,
+                og_url => link_preview.url,
                 comments,
                 collaborative_participants,
                 reaction_counts,
@@ -682,10 +805,7 @@ pub async fn post_replay_view(
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
     // Check if post is in a private community and if user has access
-    let community_id = post
-        .get("community_id")
-        .and_then(|v| v.as_ref())
-        .and_then(|s| Uuid::parse_str(s).ok());
+    let community_id = post_community_id(&post, uuid)?;
 
     if let Some(cid) = community_id {
         let community = find_community_by_id(&mut tx, cid).await?;
@@ -702,8 +822,7 @@ pub async fn post_replay_view(
                                 .get(axum::http::header::ACCEPT_LANGUAGE)
                                 .cloned()
                                 .unwrap_or_else(|| axum::http::HeaderValue::from_static(""));
-                            let user_preferred_language = user.preferred_language.clone();
-                            let bundle = get_bundle(&accept_language, user_preferred_language);
+                            let bundle = get_user_bundle(&accept_language, Some(user));
                             let error_message = safe_get_message(&bundle, "private-community-no-access");
                             messages.error(error_message);
                             return Ok(Redirect::to("/").into_response());
@@ -719,8 +838,12 @@ pub async fn post_replay_view(
     }
     // Personal posts (community_id is None) are always accessible
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let community_id = community_id.map(|id| id.to_string());
 
@@ -733,6 +856,8 @@ pub async fn post_replay_view(
                 "post_replay_view_pch.jinja"
             } else if replay_filename.ends_with(".tgkr") {
                 "post_replay_view_tgkr.jinja"
+            } else if replay_filename.ends_with(".ncr") {
+                "post_replay_view_ncr.jinja"
             } else {
                 "post_replay_view_pch.jinja"
             }
@@ -752,6 +877,7 @@ pub async fn post_replay_view(
             community_id,
             draft_post_count => common_ctx.draft_post_count,
             unread_notification_count => common_ctx.unread_notification_count,
+            announcement => common_ctx.announcement,
             ftl_lang,
         })
         .map_err(|e| AppError::from(anyhow::anyhow!("Template render error: {}", e)))?;
@@ -777,10 +903,7 @@ pub async fn post_replay_view_mobile(
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
     // Check if post is in a private community and if user has access
-    let community_id = post
-        .get("community_id")
-        .and_then(|v| v.as_ref())
-        .and_then(|s| Uuid::parse_str(s).ok());
+    let community_id = post_community_id(&post, uuid)?;
 
     if let Some(cid) = community_id {
         let community = find_community_by_id(&mut tx, cid).await?;
@@ -815,6 +938,8 @@ pub async fn post_replay_view_mobile(
                 "post_replay_view_pch_mobile.jinja"
             } else if replay_filename.ends_with(".tgkr") {
                 "post_replay_view_tgkr_mobile.jinja"
+            } else if replay_filename.ends_with(".ncr") {
+                "post_replay_view_ncr_mobile.jinja"
             } else {
                 "post_replay_view_pch_mobile.jinja"
             }
@@ -834,6 +959,7 @@ pub async fn post_replay_view_mobile(
 
 pub async fn post_publish_form(
     auth_session: AuthSession,
+    messages: Messages,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -853,16 +979,8 @@ pub async fn post_publish_form(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    if *post
-        .get("author_id")
-        .and_then(|v| v.as_ref())
-        .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?
-        != auth_session
-            .user
-            .as_ref()
-            .ok_or(AppError::Unauthorized)?
-            .id
-            .to_string()
+    if post_author_id(&post, post_uuid)?
+        != auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id
     {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
@@ -875,13 +993,14 @@ pub async fn post_publish_form(
         return Ok(Redirect::to(&format!("/posts/{}", id)).into_response());
     }
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
-    let community_id = post
-        .get("community_id")
-        .and_then(|id| id.as_ref())
-        .and_then(|id_str| Uuid::parse_str(id_str).ok());
+    let community_id = post_community_id(&post, post_uuid)?;
 
     let link = if let Some(cid) = community_id {
         Some(get_community_slug_url(&mut tx, cid).await?)
@@ -889,16 +1008,28 @@ pub async fn post_publish_form(
         None
     };
 
+    // Posts in sensitive communities default to sensitive
+    let community_is_sensitive = match community_id {
+        Some(cid) => find_community_by_id(&mut tx, cid)
+            .await?
+            .is_some_and(|community| community.is_sensitive),
+        None => false,
+    };
+
     let template: minijinja::Template<'_, '_> = state.env.get_template("post_form.jinja")?;
     let rendered = template.render(context! {
         current_user => auth_session.user,
         post_id => id,
         link,
+        community_is_sensitive,
+        idempotency_key => Uuid::new_v4().to_string(),
+        messages => messages.into_iter().collect::<Vec<_>>(),
         post => {
             post
         },
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -913,11 +1044,14 @@ pub struct PostPublishForm {
     is_sensitive: Option<String>,
     allow_relay: Option<String>,
     hashtags: Option<String>,
+    idempotency_key: Option<String>,
 }
 
 pub async fn post_publish(
     auth_session: AuthSession,
+    messages: Messages,
     State(state): State<AppState>,
+    headers: HeaderMap,
     Form(form): Form<PostPublishForm>,
 ) -> Result<impl IntoResponse, AppError> {
     let post_id = Uuid::parse_str(&form.post_id)?;
@@ -930,11 +1064,7 @@ pub async fn post_publish(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    let author_id = Uuid::parse_str(
-        post.get("author_id")
-            .and_then(|v| v.as_ref())
-            .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?,
-    )?;
+    let author_id = post_author_id(&post, post_id)?;
     let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
     if author_id != user_id {
         return Ok(StatusCode::FORBIDDEN.into_response());
@@ -944,10 +1074,7 @@ pub async fn post_publish(
     let allow_relay = form.allow_relay == Some("on".to_string());
 
     // Parse community_id if present, otherwise None for personal posts
-    let community_id = post
-        .get("community_id")
-        .and_then(|v| v.as_ref())
-        .and_then(|s| Uuid::parse_str(s).ok());
+    let community_id = post_community_id(&post, post_id)?;
 
     // Determine redirect URL based on whether post has community
     let redirect_url = if let Some(cid) = community_id {
@@ -960,23 +1087,265 @@ pub async fn post_publish(
         format!("/@{}", user.login_name)
     };
 
-    let _ = publish_post(
+    // Communities may only accept posts during a daily window; owners and moderators are exempt
+    if let Some(cid) = community_id {
+        let now = chrono::Utc::now();
+        if let Some(window) = find_closed_posting_window(&mut tx, cid, user_id, now).await? {
+            let accept_language = headers
+                .get(axum::http::header::ACCEPT_LANGUAGE)
+                .cloned()
+                .unwrap_or_else(|| HeaderValue::from_static(""));
+            let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
+            let mut args = FluentArgs::new();
+            args.set(
+                "opens_at",
+                window
+                    .next_opening(now)
+                    .format("%Y-%m-%d %H:%M %Z")
+                    .to_string(),
+            );
+            messages.error(safe_format_message(
+                &bundle,
+                "community-posting-window-closed",
+                Some(&args),
+            ));
+            return Ok(Redirect::to(&format!("/posts/{}/publish", post_id)).into_response());
+        }
+    }
+
+    // Communities that review submissions hold the post back, unpublished and
+    // unfederated, until their owner or a moderator approves it
+    let needs_approval = match community_id {
+        Some(cid) => post_needs_approval(&mut tx, cid, user_id).await?,
+        None => false,
+    };
+
+    // A repeated submit with the same key (double click, retry on a flaky connection)
+    // must not publish and federate the post a second time
+    let idempotency_key = headers
+        .get("Idempotency-Key")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string())
+        .or(form.idempotency_key.clone())
+        .filter(|key| IdempotencyStore::is_valid_key(key));
+    let performed = perform_once(
+        &IdempotencyStore::new(state.redis_pool.clone()),
+        &format!("publish:{}", user_id),
+        idempotency_key.as_deref(),
+        &redirect_url,
+        || async move {
+            let _ = if needs_approval {
+                submit_post_for_approval(
+                    &mut tx,
+                    post_id,
+                    form.title,
+                    form.content,
+                    is_sensitive,
+                    allow_relay,
+                )
+                .await
+            } else {
+                publish_post(
+                    &mut tx,
+                    post_id,
+                    form.title,
+                    form.content,
+                    is_sensitive,
+                    allow_relay,
+                )
+                .await
+            };
+
+            // Handle hashtags if provided, plus any the community applies to every post
+            let mut hashtag_names =
+                parse_hashtag_input(form.hashtags.as_deref().unwrap_or_default());
+            if let Err(e) =
+                add_community_default_hashtags(&mut tx, post_id, &mut hashtag_names).await
+            {
+                tracing::warn!(
+                    "Failed to add community default hashtags to {}: {:?}",
+                    post_id,
+                    e
+                );
+            }
+            if !hashtag_names.is_empty() {
+                let _ = link_post_to_hashtags(&mut tx, post_id, &hashtag_names).await;
+            }
+
+            tx.commit().await
+        },
+    )
+    .await?;
+    if let Performed::Earlier(previous_redirect_url) = performed {
+        return Ok(Redirect::to(&previous_redirect_url).into_response());
+    }
+
+    if needs_approval {
+        let accept_language = headers
+            .get(axum::http::header::ACCEPT_LANGUAGE)
+            .cloned()
+            .unwrap_or_else(|| HeaderValue::from_static(""));
+        let bundle = get_user_bundle(&accept_language, auth_session.user.as_ref());
+        messages.success(safe_get_message(&bundle, "post-submitted-for-approval"));
+        return Ok(Redirect::to(&redirect_url).into_response());
+    }
+
+    notify_and_federate_published_post(&state, post_id, user_id).await?;
+
+    Ok(Redirect::to(&redirect_url).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct PostScheduleForm {
+    post_id: String,
+    title: String,
+    content: String,
+    is_sensitive: Option<String>,
+    allow_relay: Option<String>,
+    hashtags: Option<String>,
+    scheduled_for: String,
+}
+
+pub async fn do_schedule_post(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Form(form): Form<PostScheduleForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let post_id = Uuid::parse_str(&id)?;
+    if form.post_id != id {
+        return Err(AppError::InvalidFormData("Post ID mismatch".to_string()));
+    }
+
+    // datetime-local inputs carry no offset; times are entered in KST like everything we display
+    let scheduled_for = {
+        use chrono::TimeZone;
+        let naive = chrono::NaiveDateTime::parse_from_str(&form.scheduled_for, "%Y-%m-%dT%H:%M")
+            .map_err(|e| AppError::InvalidFormData(format!("Invalid scheduled_for: {}", e)))?;
+        chrono_tz::Asia::Seoul
+            .from_local_datetime(&naive)
+            .single()
+            .ok_or_else(|| AppError::InvalidFormData("Ambiguous scheduled_for".to_string()))?
+            .with_timezone(&chrono::Utc)
+    };
+    if scheduled_for <= chrono::Utc::now() {
+        return Err(AppError::InvalidFormData(
+            "scheduled_for must be in the future".to_string(),
+        ));
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let post = find_post_by_id(&mut tx, post_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Post".to_string()))?;
+
+    let author_id = post_author_id(&post, post_id)?;
+    let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
+    if author_id != user_id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    if post.get("published_at").and_then(|v| v.as_ref()).is_some() {
+        return Ok(Redirect::to(&format!("/posts/{}", id)).into_response());
+    }
+
+    schedule_post(
         &mut tx,
         post_id,
-        form.title.clone(),
-        form.content.clone(),
-        is_sensitive,
-        allow_relay,
+        form.title,
+        form.content,
+        form.is_sensitive == Some("on".to_string()),
+        form.allow_relay == Some("on".to_string()),
+        scheduled_for,
     )
-    .await;
+    .await?;
 
-    // Handle hashtags if provided
-    if let Some(hashtags_input) = &form.hashtags {
-        if !hashtags_input.trim().is_empty() {
-            let hashtag_names = parse_hashtag_input(hashtags_input);
-            let _ = link_post_to_hashtags(&mut tx, post_id, &hashtag_names).await;
+    let mut hashtag_names = parse_hashtag_input(form.hashtags.as_deref().unwrap_or_default());
+    if let Err(e) = add_community_default_hashtags(&mut tx, post_id, &mut hashtag_names).await {
+        tracing::warn!(
+            "Failed to add community default hashtags to {}: {:?}",
+            post_id,
+            e
+        );
+    }
+    if !hashtag_names.is_empty() {
+        let _ = link_post_to_hashtags(&mut tx, post_id, &hashtag_names).await;
+    }
+
+    tx.commit().await?;
+
+    Ok(Redirect::to("/posts/drafts").into_response())
+}
+
+/// Send the Create for a published post to the author's followers, and for community posts
+/// to the community's followers too. Delivery failures are only logged.
+pub async fn federate_published_post(
+    actor: &Actor,
+    post_id: Uuid,
+    community_id: Option<Uuid>,
+    title: String,
+    content: String,
+    state: &AppState,
+) {
+    // Send to user's followers first and get the Note object
+    match send_post_to_followers(actor, post_id, title, content, state).await {
+        Ok(note) => {
+            // For community posts, also send to community's followers
+            if let Some(cid) = community_id {
+                if let Err(e) = send_post_to_community_followers(actor, cid, &note, state).await {
+                    tracing::error!(
+                        "Failed to send post to community's ActivityPub followers: {:?}",
+                        e
+                    );
+                    // Don't fail the entire operation if ActivityPub sending fails
+                }
+            } else {
+                tracing::info!("Personal post - sent to user's followers only");
+            }
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to send post to user's ActivityPub followers: {:?}",
+                e
+            );
+            // Don't fail the entire operation if ActivityPub sending fails
         }
     }
+}
+
+/// Send notifications and ActivityPub activities for a post that has just been published.
+/// Shared by immediate publishing and the scheduled post publisher.
+pub async fn notify_and_federate_published_post(
+    state: &AppState,
+    post_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let post = find_post_by_id(&mut tx, post_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Post".to_string()))?;
+
+    let community_id = post_community_id(&post, post_id)?;
+    // Publishing reorders the public community listing and changes its counts
+    if community_id.is_some() {
+        CommunityListingCache::new(state.redis_pool.clone())
+            .invalidate()
+            .await;
+    }
+    // The post no longer counts as the author's draft
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(user_id)
+        .await;
+    let title = post
+        .get("title")
+        .and_then(|v| v.clone())
+        .unwrap_or_default();
+    let content = post
+        .get("content")
+        .and_then(|v| v.clone())
+        .unwrap_or_default();
 
     // Find the actor for this user to send ActivityPub activities
     let actor = Actor::find_by_user_id(&mut tx, user_id).await?;
@@ -1025,6 +1394,7 @@ pub async fn post_publish(
                     if should_notify {
                         if let Ok(notification) = create_notification(
                             &mut tx,
+                            &state.notification_events,
                             CreateNotificationParams {
                                 recipient_id: parent_author_id,
                                 actor_id: actor.id,
@@ -1098,6 +1468,7 @@ pub async fn post_publish(
                             if participant_id != user_id {
                                 if let Ok(notification) = create_notification(
                                     &mut tx,
+                                    &state.notification_events,
                                     CreateNotificationParams {
                                         recipient_id: participant_id,
                                         actor_id: actor.id,
@@ -1135,13 +1506,29 @@ pub async fn post_publish(
         (true, None)
     };
 
+    // Posts by accounts too new to federate are held back until the account qualifies
+    let is_federation_deferred = if should_federate {
+        let author = find_user_by_id(&mut tx, user_id).await?;
+        let is_ready = author.as_ref().is_some_and(|author| {
+            state
+                .config
+                .is_account_federation_ready(author.created_at, author.email_verified_at.is_some())
+        });
+        if !is_ready {
+            defer_post_federation(&mut tx, post_id).await?;
+        }
+        !is_ready
+    } else {
+        false
+    };
+
     let _ = tx.commit().await;
 
     // Send push notifications for created notifications
     if !notification_info.is_empty() {
         let push_service = state.push_service.clone();
         let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
+        let send_notifications = async move {
             for (notification_id, recipient_id) in notification_info {
                 let mut tx = match db_pool.begin().await {
                     Ok(tx) => tx,
@@ -1169,48 +1556,22 @@ pub async fn post_publish(
                 }
                 let _ = tx.commit().await;
             }
-        });
+        };
+        tokio::spawn(send_notifications.in_current_span());
     }
 
     // Send ActivityPub Create activity to followers if actor exists
     // For personal posts: send to user's followers only
     // For community posts: send to both user's and community's followers (if not private)
     if let Some(actor) = actor {
-        if should_federate {
-            // Send to user's followers first and get the Note object
-            match send_post_to_followers(
-                &actor,
+        if is_federation_deferred {
+            tracing::info!(
+                "Deferring ActivityPub federation of post {} until user {}'s account qualifies",
                 post_id,
-                form.title.clone(),
-                form.content.clone(),
-                &state,
-            )
-            .await
-            {
-                Ok(note) => {
-                    // For community posts, also send to community's followers
-                    if let Some(cid) = community_id {
-                        if let Err(e) =
-                            send_post_to_community_followers(&actor, cid, &note, &state).await
-                        {
-                            tracing::error!(
-                                "Failed to send post to community's ActivityPub followers: {:?}",
-                                e
-                            );
-                            // Don't fail the entire operation if ActivityPub sending fails
-                        }
-                    } else {
-                        tracing::info!("Personal post - sent to user's followers only");
-                    }
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "Failed to send post to user's ActivityPub followers: {:?}",
-                        e
-                    );
-                    // Don't fail the entire operation if ActivityPub sending fails
-                }
-            }
+                user_id
+            );
+        } else if should_federate {
+            federate_published_post(&actor, post_id, community_id, title, content, state).await;
         } else {
             tracing::info!(
                 "Skipping ActivityPub federation for private community post (visibility: {:?})",
@@ -1221,7 +1582,7 @@ pub async fn post_publish(
         tracing::warn!("No actor found for user {}, skipping ActivityPub", user_id);
     }
 
-    Ok(Redirect::to(&redirect_url).into_response())
+    Ok(())
 }
 
 pub async fn draft_posts(
@@ -1232,8 +1593,12 @@ pub async fn draft_posts(
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let posts = find_draft_posts_by_author_id(
         &mut tx,
@@ -1249,6 +1614,7 @@ pub async fn draft_posts(
         posts => posts,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         r2_public_endpoint_url => state.config.r2_public_endpoint_url.clone(),
         ftl_lang,
     })?;
@@ -1261,7 +1627,11 @@ pub struct DraftPostJson {
     pub id: Uuid,
     pub title: Option<String>,
     pub image_url: String,
+    /// Downscaled variant for the drafts grid; the original's URL if none was generated
+    pub thumbnail_url: String,
     pub created_at: String,
+    /// When the drawing editor last saved a snapshot of the canvas
+    pub autosaved_at: Option<String>,
     pub community_id: Option<Uuid>,
     pub width: i32,
     pub height: i32,
@@ -1275,6 +1645,7 @@ pub struct DraftPostsResponse {
 pub async fn draft_posts_api(
     auth_session: AuthSession,
     State(state): State<AppState>,
+    Query(thumbnail_query): Query<ThumbnailQuery>,
 ) -> Result<Json<DraftPostsResponse>, AppError> {
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
@@ -1282,6 +1653,16 @@ pub async fn draft_posts_api(
     let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
 
     let posts = find_draft_posts_by_author_id(&mut tx, user.id).await?;
+    let image_filenames: Vec<String> = posts
+        .iter()
+        .map(|post| post.image_filename.clone())
+        .collect();
+    let thumbnails = find_thumbnails_by_image_filenames(
+        &mut tx,
+        &image_filenames,
+        thumbnail_query.thumbnail_width,
+    )
+    .await?;
 
     tx.commit().await?;
 
@@ -1296,7 +1677,13 @@ pub async fn draft_posts_api(
                     "{}/image/{}/{}",
                     state.config.r2_public_endpoint_url, image_prefix, post.image_filename
                 ),
+                thumbnail_url: thumbnail_or_image_url(
+                    &state.config.r2_public_endpoint_url,
+                    &thumbnails,
+                    &post.image_filename,
+                ),
                 created_at: post.updated_at.to_rfc3339(),
+                autosaved_at: post.autosaved_at.map(|dt| dt.to_rfc3339()),
                 community_id: post.community_id,
                 width: post.image_width,
                 height: post.image_height,
@@ -1342,10 +1729,7 @@ pub async fn do_create_comment(
 
     let post_community = if let Some(ref post_data) = post {
         // Check if post is in a private/unlisted community and if user has access
-        let community_id = post_data
-            .get("community_id")
-            .and_then(|v| v.as_ref())
-            .and_then(|s| Uuid::parse_str(s).ok());
+        let community_id = post_community_id(post_data, post_id)?;
 
         if let Some(cid) = community_id {
             let community = find_community_by_id(&mut tx, cid).await?;
@@ -1428,6 +1812,7 @@ pub async fn do_create_comment(
                     if parent_user_id != user_id {
                         if let Ok(notification) = create_notification(
                             &mut tx,
+                            &state.notification_events,
                             CreateNotificationParams {
                                 recipient_id: parent_user_id,
                                 actor_id: actor.id,
@@ -1453,6 +1838,7 @@ pub async fn do_create_comment(
             if post_author_id != user_id {
                 if let Ok(notification) = create_notification(
                     &mut tx,
+                    &state.notification_events,
                     CreateNotificationParams {
                         recipient_id: post_author_id,
                         actor_id: actor.id,
@@ -1473,90 +1859,30 @@ pub async fn do_create_comment(
 
     // Extract @mentions from comment content and create notifications
     let mentioned_login_names = extract_mentions(comment.content.as_deref().unwrap_or(""));
-    if !mentioned_login_names.is_empty() {
-        let mentioned_users = find_users_by_login_names(&mut tx, &mentioned_login_names).await?;
-        for (mentioned_user_id, _login_name) in mentioned_users {
-            // Don't notify the commenter themselves
-            if mentioned_user_id != user_id {
-                // For private communities, only notify if mentioned user is a member
-                let should_notify = if let Some(ref community) = post_community {
-                    if community.visibility
-                        == crate::models::community::CommunityVisibility::Private
-                    {
-                        // Check if mentioned user is a member
-                        is_user_member(&mut tx, mentioned_user_id, community.id)
-                            .await
-                            .unwrap_or(false)
-                    } else {
-                        // Public or unlisted community - always notify
-                        true
-                    }
-                } else {
-                    // No community info - notify anyway
-                    true
-                };
-
-                if should_notify {
-                    if let Ok(notification) = create_notification(
-                        &mut tx,
-                        CreateNotificationParams {
-                            recipient_id: mentioned_user_id,
-                            actor_id: actor.id,
-                            notification_type: NotificationType::Mention,
-                            post_id: Some(post_id),
-                            comment_id: Some(comment.id),
-                            reaction_iri: None,
-                            guestbook_entry_id: None,
-                        },
-                    )
-                    .await
-                    {
-                        notification_info.push((notification.id, mentioned_user_id));
-                    }
-                }
-            }
-        }
-    }
+    notification_info.extend(
+        notify_mentioned_users(
+            &mut tx,
+            &state.notification_events,
+            &mentioned_login_names,
+            MentioningComment {
+                actor_id: actor.id,
+                user_id,
+                post_id,
+                comment_id: comment.id,
+                post_community: post_community.as_ref(),
+            },
+        )
+        .await?,
+    );
 
     // At this point post is guaranteed to be Some (would have returned 404 otherwise)
     // post is not used after this point, no need to unwrap
 
-    let comments = build_comment_thread_tree(&mut tx, post_id).await?;
+    let comments = build_comment_thread_tree(&mut tx, post_id, Some(user_id)).await?;
     let _ = tx.commit().await;
 
     // Send push notifications for created notifications
-    if !notification_info.is_empty() {
-        let push_service = state.push_service.clone();
-        let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
-            for (notification_id, recipient_id) in notification_info {
-                let mut tx = match db_pool.begin().await {
-                    Ok(tx) => tx,
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to begin transaction for push notification: {:?}",
-                            e
-                        );
-                        continue;
-                    }
-                };
-
-                if let Ok(Some(notification)) =
-                    get_notification_by_id(&mut tx, notification_id, recipient_id).await
-                {
-                    // Get unread count for badge
-                    let badge_count = get_unread_count(&mut tx, recipient_id)
-                        .await
-                        .ok()
-                        .and_then(|count| u32::try_from(count).ok());
-
-                    send_push_for_notification(&push_service, &db_pool, &notification, badge_count)
-                        .await;
-                }
-                let _ = tx.commit().await;
-            }
-        });
-    }
+    spawn_push_notifications(&state, notification_info);
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("post_comments.jinja")?;
     let rendered = template.render(context! {
@@ -1567,7 +1893,381 @@ pub async fn do_create_comment(
     Ok(Html(rendered).into_response())
 }
 
-pub async fn post_edit_community(
+/// The comment whose mentions are being notified
+struct MentioningComment<'a> {
+    actor_id: Uuid,
+    /// The commenter, who is never notified of their own mentions
+    user_id: Uuid,
+    post_id: Uuid,
+    comment_id: Uuid,
+    post_community: Option<&'a crate::models::community::Community>,
+}
+
+/// Create Mention notifications for the given login names on a comment.
+/// Returns (notification_id, recipient_id) pairs for push delivery after commit.
+async fn notify_mentioned_users(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    events: &NotificationEvents,
+    login_names: &[String],
+    comment: MentioningComment<'_>,
+) -> Result<Vec<(Uuid, Uuid)>, AppError> {
+    let MentioningComment {
+        actor_id,
+        user_id,
+        post_id,
+        comment_id,
+        post_community,
+    } = comment;
+
+    let mut notification_info = Vec::new();
+    if login_names.is_empty() {
+        return Ok(notification_info);
+    }
+
+    let mentioned_users = find_users_by_login_names(tx, login_names).await?;
+    for (mentioned_user_id, _login_name) in mentioned_users {
+        // Don't notify the commenter themselves
+        if mentioned_user_id == user_id {
+            continue;
+        }
+
+        // For private communities, only notify if mentioned user is a member
+        let should_notify = if let Some(community) = post_community {
+            if community.visibility == crate::models::community::CommunityVisibility::Private {
+                // Check if mentioned user is a member
+                is_user_member(tx, mentioned_user_id, community.id)
+                    .await
+                    .unwrap_or(false)
+            } else {
+                // Public or unlisted community - always notify
+                true
+            }
+        } else {
+            // No community info - notify anyway
+            true
+        };
+
+        if should_notify {
+            if let Ok(notification) = create_notification(
+                tx,
+                events,
+                CreateNotificationParams {
+                    recipient_id: mentioned_user_id,
+                    actor_id,
+                    notification_type: NotificationType::Mention,
+                    post_id: Some(post_id),
+                    comment_id: Some(comment_id),
+                    reaction_iri: None,
+                    guestbook_entry_id: None,
+                },
+            )
+            .await
+            {
+                notification_info.push((notification.id, mentioned_user_id));
+            }
+        }
+    }
+
+    Ok(notification_info)
+}
+
+/// Deliver push notifications in the background once their rows are committed
+pub(crate) fn spawn_push_notifications(state: &AppState, notification_info: Vec<(Uuid, Uuid)>) {
+    if notification_info.is_empty() {
+        return;
+    }
+
+    let push_service = state.push_service.clone();
+    let db_pool = state.db_pool.clone();
+    let send_notifications = async move {
+        for (notification_id, recipient_id) in notification_info {
+            let mut tx = match db_pool.begin().await {
+                Ok(tx) => tx,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to begin transaction for push notification: {:?}",
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if let Ok(Some(notification)) =
+                get_notification_by_id(&mut tx, notification_id, recipient_id).await
+            {
+                // Get unread count for badge
+                let badge_count = get_unread_count(&mut tx, recipient_id)
+                    .await
+                    .ok()
+                    .and_then(|count| u32::try_from(count).ok());
+
+                send_push_for_notification(&push_service, &db_pool, &notification, badge_count)
+                    .await;
+            }
+            let _ = tx.commit().await;
+        }
+    };
+    tokio::spawn(send_notifications.in_current_span());
+}
+
+/// Render a comment as it appears in the post's thread, with its replies collapsed.
+/// Deleted comments without replies render as nothing.
+async fn render_comment_block(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    auth_session: &AuthSession,
+    state: &AppState,
+    post_id: Uuid,
+    comment_id: Uuid,
+    ftl_lang: &str,
+) -> Result<String, AppError> {
+    let viewer_user_id = auth_session.user.as_ref().map(|u| u.id);
+    let parent_comment_id = find_comment_by_id(tx, comment_id)
+        .await?
+        .and_then(|comment| comment.parent_comment_id);
+    let siblings = match parent_comment_id {
+        Some(parent_id) => find_comment_replies(tx, post_id, parent_id, viewer_user_id).await?,
+        None => build_comment_thread_tree(tx, post_id, viewer_user_id).await?,
+    };
+    let comment = find_comment_in_thread(siblings, comment_id);
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("comment_item.jinja")?;
+    let rendered = template.render(context! {
+        comment,
+        current_user => auth_session.user,
+        ftl_lang
+    })?;
+    Ok(rendered)
+}
+
+/// Replies to a comment, loaded when its thread is expanded
+pub async fn hx_comment_replies(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path((post_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let post = match find_post_by_id(&mut tx, post_id).await? {
+        Some(post) => post,
+        None => return Ok(StatusCode::NOT_FOUND.into_response()),
+    };
+
+    // Comments in a private community are only visible to its members
+    if let Some(cid) = post_community_id(&post, post_id)? {
+        if let Some(community) = find_community_by_id(&mut tx, cid).await? {
+            if community.visibility == crate::models::community::CommunityVisibility::Private {
+                let is_member = match &auth_session.user {
+                    Some(user) => is_user_member(&mut tx, user.id, community.id).await?,
+                    None => false,
+                };
+                if !is_member {
+                    return Ok(StatusCode::NOT_FOUND.into_response());
+                }
+            }
+        }
+    }
+
+    let replies = find_comment_replies(
+        &mut tx,
+        post_id,
+        comment_id,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("post_comments.jinja")?;
+    let rendered = template.render(context! {
+        comments => replies,
+        current_user => auth_session.user,
+        ftl_lang
+    })?;
+    Ok(Html(rendered).into_response())
+}
+
+/// Load a comment and the current user's actor, checking the comment belongs to them
+async fn find_own_comment(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    auth_session: &AuthSession,
+    comment_id: Uuid,
+) -> Result<Result<(Comment, Actor), StatusCode>, AppError> {
+    let user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let actor = Actor::find_by_user_id(tx, user.id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No actor found for user"))?;
+
+    let comment = match find_comment_by_id(tx, comment_id).await? {
+        Some(comment) => comment,
+        None => return Ok(Err(StatusCode::NOT_FOUND)),
+    };
+    if comment.actor_id != actor.id {
+        return Ok(Err(StatusCode::FORBIDDEN));
+    }
+    if comment.deleted_at.is_some() {
+        return Ok(Err(StatusCode::GONE));
+    }
+
+    Ok(Ok((comment, actor)))
+}
+
+pub async fn hx_comment(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    // Only used to cancel an edit, so restrict it to the comment's author
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let (comment, _actor) = match find_own_comment(&mut tx, &auth_session, id).await? {
+        Ok(found) => found,
+        Err(status) => return Ok(status.into_response()),
+    };
+
+    let rendered =
+        render_comment_block(&mut tx, &auth_session, &state, comment.post_id, id, &ftl_lang)
+            .await?;
+    tx.commit().await?;
+
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn hx_edit_comment(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let (comment, _actor) = match find_own_comment(&mut tx, &auth_session, id).await? {
+        Ok(found) => found,
+        Err(status) => return Ok(status.into_response()),
+    };
+    tx.commit().await?;
+    if !is_comment_editable(comment.created_at) {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("comment_edit.jinja")?;
+    let rendered = template.render(context! {
+        comment,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct EditCommentForm {
+    content: String,
+}
+
+pub async fn do_edit_comment(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Form(form): Form<EditCommentForm>,
+) -> Result<impl IntoResponse, AppError> {
+    if form.content.trim().is_empty() {
+        return Err(AppError::InvalidFormData(
+            "Comment content cannot be empty".to_string(),
+        ));
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
+    let (comment, actor) = match find_own_comment(&mut tx, &auth_session, id).await? {
+        Ok(found) => found,
+        Err(status) => return Ok(status.into_response()),
+    };
+    // Comments can only be corrected shortly after posting
+    if !is_comment_editable(comment.created_at) {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let previous_mentions = extract_mentions(comment.content.as_deref().unwrap_or(""));
+    let edited = edit_comment(&mut tx, id, form.content)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Comment".to_string()))?;
+
+    // Only notify users who were not already mentioned before the edit
+    let added_mentions: Vec<String> = extract_mentions(edited.content.as_deref().unwrap_or(""))
+        .into_iter()
+        .filter(|login_name| !previous_mentions.contains(login_name))
+        .collect();
+    let post_community_id = match find_post_by_id(&mut tx, edited.post_id).await? {
+        Some(post) => post_community_id(&post, edited.post_id)?,
+        None => None,
+    };
+    let post_community = match post_community_id {
+        Some(cid) => find_community_by_id(&mut tx, cid).await?,
+        None => None,
+    };
+    let notification_info = notify_mentioned_users(
+        &mut tx,
+        &state.notification_events,
+        &added_mentions,
+        MentioningComment {
+            actor_id: actor.id,
+            user_id,
+            post_id: edited.post_id,
+            comment_id: edited.id,
+            post_community: post_community.as_ref(),
+        },
+    )
+    .await?;
+
+    let rendered =
+        render_comment_block(&mut tx, &auth_session, &state, edited.post_id, id, &ftl_lang)
+            .await?;
+    tx.commit().await?;
+
+    spawn_push_notifications(&state, notification_info);
+
+    // Send Update activity - don't fail if this fails
+    if let Err(e) = send_update_activity_for_comment(&actor, &edited, &state).await {
+        tracing::warn!("Failed to send Update activity for comment {}: {:?}", id, e);
+    }
+
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn do_delete_comment(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let (comment, actor) = match find_own_comment(&mut tx, &auth_session, id).await? {
+        Ok(found) => found,
+        Err(status) => return Ok(status.into_response()),
+    };
+
+    let deleted = soft_delete_comment(&mut tx, id).await?;
+
+    // Replies keep the thread together under a tombstone; a leaf comment just disappears
+    let rendered =
+        render_comment_block(&mut tx, &auth_session, &state, comment.post_id, id, &ftl_lang)
+            .await?;
+    tx.commit().await?;
+
+    if deleted {
+        // Send Delete activity - don't fail if this fails
+        if let Err(e) = send_delete_activity_for_comment(&actor, &comment, &state).await {
+            tracing::warn!("Failed to send Delete activity for comment {}: {:?}", id, e);
+        }
+    }
+
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn post_edit_community(
     auth_session: AuthSession,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
@@ -1583,16 +2283,8 @@ pub async fn post_edit_community(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    if *post
-        .get("author_id")
-        .and_then(|v| v.as_ref())
-        .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?
-        != auth_session
-            .user
-            .as_ref()
-            .ok_or(AppError::Unauthorized)?
-            .id
-            .to_string()
+    if post_author_id(&post, post_uuid)?
+        != auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id
     {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
@@ -1605,10 +2297,7 @@ pub async fn post_edit_community(
     }
 
     let post_data = post.clone();
-    let current_community_id = post_data
-        .get("community_id")
-        .and_then(|id| id.as_ref())
-        .and_then(|id_str| Uuid::parse_str(id_str).ok());
+    let current_community_id = post_community_id(&post_data, post_uuid)?;
 
     // Get current community details with owner info (if post is in a community)
     let (current_community_result, current_community_recent_posts) = if let Some(comm_id) = current_community_id {
@@ -1866,8 +2555,12 @@ pub async fn post_edit_community(
     public_participated_communities.sort_by(sort_by_name);
     public_other_communities.sort_by(sort_by_name);
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     tx.commit().await?;
 
@@ -1883,6 +2576,7 @@ pub async fn post_edit_community(
         public_other_communities,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         r2_public_endpoint_url => state.config.r2_public_endpoint_url.clone(),
         base_url => state.config.base_url.clone(),
         ftl_lang
@@ -1928,16 +2622,8 @@ pub async fn do_post_edit_community(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    if *post
-        .get("author_id")
-        .and_then(|v| v.as_ref())
-        .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?
-        != auth_session
-            .user
-            .as_ref()
-            .ok_or(AppError::Unauthorized)?
-            .id
-            .to_string()
+    if post_author_id(&post, post_uuid)?
+        != auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id
     {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
@@ -1972,16 +2658,8 @@ pub async fn hx_edit_post(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    if *post
-        .get("author_id")
-        .and_then(|v| v.as_ref())
-        .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?
-        != auth_session
-            .user
-            .as_ref()
-            .ok_or(AppError::Unauthorized)?
-            .id
-            .to_string()
+    if post_author_id(&post, post_uuid)?
+        != auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id
     {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
@@ -2036,16 +2714,8 @@ pub async fn hx_do_edit_post(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    if *post
-        .get("author_id")
-        .and_then(|v| v.as_ref())
-        .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?
-        != auth_session
-            .user
-            .as_ref()
-            .ok_or(AppError::Unauthorized)?
-            .id
-            .to_string()
+    if post_author_id(&post, post_uuid)?
+        != auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id
     {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
@@ -2085,16 +2755,12 @@ pub async fn hx_do_edit_post(
 
     // Check community visibility before federating updates
     let should_federate = if let Some(ref post_data) = post {
-        if let Some(community_id_str) = post_data.get("community_id").and_then(|v| v.as_ref()) {
-            if let Ok(community_id) = Uuid::parse_str(community_id_str) {
-                let community = find_community_by_id(&mut tx, community_id).await?;
-                community
-                    .as_ref()
-                    .map(|c| c.visibility != crate::models::community::CommunityVisibility::Private)
-                    .unwrap_or(false)
-            } else {
-                false
-            }
+        if let Some(community_id) = post_community_id(post_data, post_uuid)? {
+            let community = find_community_by_id(&mut tx, community_id).await?;
+            community
+                .as_ref()
+                .map(|c| c.visibility != crate::models::community::CommunityVisibility::Private)
+                .unwrap_or(false)
         } else {
             false
         }
@@ -2146,6 +2812,188 @@ pub async fn hx_do_edit_post(
     Ok(Html(rendered).into_response())
 }
 
+/// One line of a diff between two versions of a post's content
+#[derive(Serialize)]
+struct DiffLine {
+    /// "added", "removed" or "unchanged"
+    kind: &'static str,
+    text: String,
+}
+
+/// What a single edit changed in a post
+#[derive(Serialize)]
+struct PostEdit {
+    edited_at: String,
+    title_before: Option<String>,
+    title_after: Option<String>,
+    content_diff: Vec<DiffLine>,
+}
+
+/// Largest table the line diff may build, in cells. Longer texts are shown
+/// as a whole-text replacement instead.
+const MAX_DIFF_CELLS: usize = 250_000;
+
+/// Line-by-line diff of two texts, built from the longest common subsequence
+/// of their lines
+fn diff_lines(before: &str, after: &str) -> Vec<DiffLine> {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+
+    let line = |kind, text: &str| DiffLine {
+        kind,
+        text: text.to_string(),
+    };
+    if (before.len() + 1).saturating_mul(after.len() + 1) > MAX_DIFF_CELLS {
+        return before
+            .iter()
+            .map(|text| line("removed", text))
+            .chain(after.iter().map(|text| line("added", text)))
+            .collect();
+    }
+
+    // lengths[i][j] is the length of the longest common subsequence of
+    // before[i..] and after[j..]
+    let mut lengths = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lengths[i][j] = if before[i] == after[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(before.len().max(after.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() && j < after.len() {
+        if before[i] == after[j] {
+            lines.push(line("unchanged", before[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(line("removed", before[i]));
+            i += 1;
+        } else {
+            lines.push(line("added", after[j]));
+            j += 1;
+        }
+    }
+    lines.extend(before[i..].iter().map(|text| line("removed", text)));
+    lines.extend(after[j..].iter().map(|text| line("added", text)));
+    lines
+}
+
+/// GET /posts/:id/history
+///
+/// Lists the edits made to a post's title and content, newest first, each
+/// with a diff against the version that replaced it. Only the author can see
+/// it unless `post_history_public` is set.
+pub async fn post_history(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    use crate::models::post::find_post_revisions;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let Some(post) = find_post_by_id(&mut tx, post_id).await? else {
+        return Ok((
+            StatusCode::NOT_FOUND,
+            handler_404(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
+        )
+            .into_response());
+    };
+
+    let author_id = post_author_id(&post, post_id)?;
+    let is_author = auth_session
+        .user
+        .as_ref()
+        .is_some_and(|user| user.id == author_id);
+    if !is_author {
+        if !state.config.post_history_public {
+            return Ok(StatusCode::FORBIDDEN.into_response());
+        }
+        if post.get("published_at").and_then(|v| v.as_ref()).is_none() {
+            return Err(AppError::NotFound("Post".to_string()));
+        }
+        if let Some(community_id) = post_community_id(&post, post_id)? {
+            if let Some(community) = find_community_by_id(&mut tx, community_id).await? {
+                if community.visibility == crate::models::community::CommunityVisibility::Private {
+                    let Some(user) = auth_session.user.as_ref() else {
+                        return Ok(redirect_to_login(&format!("/posts/{}/history", post_id)));
+                    };
+                    if get_user_role_in_community(&mut tx, user.id, community.id)
+                        .await?
+                        .is_none()
+                    {
+                        return Ok(StatusCode::FORBIDDEN.into_response());
+                    }
+                }
+            }
+            if !can_view_post_pending_approval(
+                &mut tx,
+                &post,
+                post_id,
+                community_id,
+                auth_session.user.as_ref(),
+            )
+            .await?
+            {
+                return Err(AppError::NotFound("Post".to_string()));
+            }
+        }
+    }
+
+    let revisions = find_post_revisions(&mut tx, post_id).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+    tx.commit().await?;
+
+    // Walk back from the current version, comparing each earlier version with
+    // the one that replaced it
+    let mut title_after = post.get("title").cloned().flatten();
+    let mut content_after = post.get("content").cloned().flatten();
+    let mut edits = Vec::with_capacity(revisions.len());
+    for revision in revisions {
+        edits.push(PostEdit {
+            edited_at: revision
+                .edited_at
+                .with_timezone(&chrono_tz::Asia::Seoul)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            title_before: revision.title.clone(),
+            title_after,
+            content_diff: diff_lines(
+                revision.content.as_deref().unwrap_or_default(),
+                content_after.as_deref().unwrap_or_default(),
+            ),
+        });
+        title_after = revision.title;
+        content_after = revision.content;
+    }
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("post_history.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        post,
+        post_id,
+        edits,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
 pub async fn hx_delete_post(
     auth_session: AuthSession,
     State(state): State<AppState>,
@@ -2161,63 +3009,53 @@ pub async fn hx_delete_post(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    if *post
-        .get("author_id")
-        .and_then(|v| v.as_ref())
-        .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?
-        != auth_session
-            .user
-            .as_ref()
-            .ok_or(AppError::Unauthorized)?
-            .id
-            .to_string()
+    if post_author_id(&post, post_uuid)?
+        != auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id
     {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
-    let image_id = post
-        .get("image_id")
-        .and_then(|v| v.as_ref())
-        .ok_or_else(|| AppError::InvalidFormData("Missing image_id".to_string()))
-        .and_then(|id_str| {
-            Uuid::parse_str(id_str).map_err(|e| AppError::InvalidUuid(format!("{}: {}", id_str, e)))
-        })?;
-    let image = find_image_by_id(&mut tx, image_id).await?;
-
-    let mut keys = vec![format!(
-        "image/{}{}/{}",
-        image
-            .image_filename
-            .chars()
-            .next()
-            .ok_or_else(|| AppError::InvalidFormData("Image filename too short".to_string()))?,
-        image
-            .image_filename
-            .chars()
-            .nth(1)
-            .ok_or_else(|| AppError::InvalidFormData("Image filename too short".to_string()))?,
-        image.image_filename
-    )];
-
-    // Only add replay file to deletion if it exists
-    if let Some(ref replay_filename) = image.replay_filename {
+    // Every image in the gallery, the cover included, goes with the post
+    let images = find_post_images(&mut tx, post_uuid).await?;
+
+    let mut keys = Vec::new();
+    for image in &images {
+        if image.image_filename.len() < 2 {
+            return Err(AppError::InvalidFormData(
+                "Image filename too short".to_string(),
+            ));
+        }
         keys.push(format!(
-            "replay/{}{}/{}",
-            replay_filename
-                .chars()
-                .next()
-                .ok_or_else(|| AppError::InvalidFormData(
-                    "Replay filename too short".to_string()
-                ))?,
-            replay_filename
-                .chars()
-                .nth(1)
-                .ok_or_else(|| AppError::InvalidFormData(
-                    "Replay filename too short".to_string()
-                ))?,
-            replay_filename
+            "image/{}/{}",
+            &image.image_filename[..2],
+            image.image_filename
         ));
+        if let Some(ref webp_filename) = image.webp_filename {
+            keys.push(format!(
+                "image/{}/{}",
+                webp_filename.get(..2).unwrap_or_default(),
+                webp_filename
+            ));
+        }
+
+        // Only add replay file to deletion if it exists
+        if let Some(ref replay_filename) = image.replay_filename {
+            if replay_filename.len() < 2 {
+                return Err(AppError::InvalidFormData(
+                    "Replay filename too short".to_string(),
+                ));
+            }
+            keys.push(replay_object_key(
+                replay_filename,
+                image.replay_archived_at.is_some(),
+            ));
+        }
     }
+    let image_filenames: Vec<String> = images
+        .iter()
+        .map(|image| image.image_filename.clone())
+        .collect();
+    keys.extend(delete_image_thumbnails(&mut tx, &image_filenames).await?);
 
     let credentials: AwsCredentials = AwsCredentials::new(
         state.config.aws_access_key_id.clone(),
@@ -2254,34 +3092,117 @@ pub async fn hx_delete_post(
         .send()
         .await?;
     let post_data = post.clone();
-    let redirect_url =
-        if let Some(community_id_str) = post_data.get("community_id").and_then(|id| id.clone()) {
-            let community_id = Uuid::parse_str(&community_id_str)?;
-            get_community_slug_url(&mut tx, community_id).await?
-        } else {
-            // For personal posts, redirect to user's profile
-            let author_id = post_data
-                .get("author_id")
-                .and_then(|v| v.as_ref())
-                .ok_or_else(|| AppError::InvalidFormData("Missing author_id".to_string()))?;
-            let author = find_user_by_id(&mut tx, Uuid::parse_str(author_id)?).await?;
-            format!(
-                "/@{}",
-                author
-                    .ok_or_else(|| AppError::NotFound("Author".to_string()))?
-                    .login_name
-            )
-        };
+    let redirect_url = if let Some(community_id) = post_community_id(&post_data, post_uuid)? {
+        get_community_slug_url(&mut tx, community_id).await?
+    } else {
+        // For personal posts, redirect to user's profile
+        let author_id = post_author_id(&post_data, post_uuid)?;
+        let author = find_user_by_id(&mut tx, author_id).await?;
+        format!(
+            "/@{}",
+            author
+                .ok_or_else(|| AppError::NotFound("Author".to_string()))?
+                .login_name
+        )
+    };
 
     // Unlink hashtags before deleting post to properly decrement post_count
     let _ = unlink_post_hashtags(&mut tx, post_uuid).await;
 
     delete_post_with_activity(&mut tx, post_uuid, Some(&state)).await?;
     tx.commit().await?;
+    // Deleting a draft lowers the author's draft count
+    CommonContextCache::new(state.redis_pool.clone())
+        .invalidate(post_author_id(&post, post_uuid)?)
+        .await;
 
     Ok(([("HX-Redirect", &redirect_url)],).into_response())
 }
 
+fn render_profile_pin_button(
+    state: &AppState,
+    ftl_lang: String,
+    post_id: Uuid,
+    is_pinned: bool,
+    pin_error: bool,
+) -> Result<Html<String>, AppError> {
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("profile_pin_button.jinja")?;
+    let rendered = template.render(context! {
+        post_id => post_id.to_string(),
+        is_pinned,
+        pin_error,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered))
+}
+
+/// HTMX: show the pin/unpin toggle for the author of an eligible post
+pub async fn hx_profile_pin_button(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let is_pinned = is_post_pinned(&mut tx, user.id, id).await?;
+    if !is_pinned && !is_post_pinnable(&mut tx, user.id, id).await? {
+        return Ok(Html(String::new()).into_response());
+    }
+
+    tx.commit().await?;
+
+    Ok(render_profile_pin_button(&state, ftl_lang, id, is_pinned, false)?.into_response())
+}
+
+/// HTMX: pin a post to the author's profile
+pub async fn hx_pin_post(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if !is_post_pinnable(&mut tx, user.id, id).await? {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    // Pinning only fails here once the pin limit is reached
+    let pinned = pin_post(&mut tx, user.id, id).await.is_ok();
+
+    tx.commit().await?;
+
+    Ok(render_profile_pin_button(&state, ftl_lang, id, pinned, !pinned)?.into_response())
+}
+
+/// HTMX: unpin a post from the author's profile
+pub async fn hx_unpin_post(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    unpin_post(&mut tx, user.id, id).await?;
+
+    tx.commit().await?;
+
+    Ok(render_profile_pin_button(&state, ftl_lang, id, false, false)?.into_response())
+}
+
 pub async fn post_view_by_login_name(
     auth_session: AuthSession,
     headers: HeaderMap,
@@ -2311,10 +3232,7 @@ pub async fn post_view_by_login_name(
                 .ok_or_else(|| AppError::InvalidFormData("Missing login_name".to_string()))?;
 
             // Check if post is in a private community and if user has access
-            let community_id = post_data
-                .get("community_id")
-                .and_then(|v| v.as_ref())
-                .and_then(|s| Uuid::parse_str(s).ok());
+            let community_id = post_community_id(post_data, uuid)?;
 
             let community = if let Some(cid) = community_id {
                 find_community_by_id(&mut tx, cid).await?
@@ -2347,7 +3265,7 @@ pub async fn post_view_by_login_name(
                                 get_user_role_in_community(&mut tx, user.id, community.id).await?;
                             if user_role.is_none() {
                                 // User is not a member of this private community
-                                return Ok(flash_error_and_redirect(&headers, user.preferred_language.clone(), messages, "private-community-no-access", "/"));
+                                return Ok(flash_error_and_redirect(&headers, Some(user), messages, "private-community-no-access", "/"));
                             }
                         }
                         None => {
@@ -2356,6 +3274,21 @@ pub async fn post_view_by_login_name(
                         }
                     }
                 }
+                if !can_view_post_pending_approval(
+                    &mut tx,
+                    post_data,
+                    uuid,
+                    community.id,
+                    auth_session.user.as_ref(),
+                )
+                .await?
+                {
+                    return Ok((
+                        StatusCode::NOT_FOUND,
+                        handler_404(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
+                    )
+                        .into_response());
+                }
                 post_community = Some(community);
             } else {
                 post_community = None;
@@ -2364,6 +3297,13 @@ pub async fn post_view_by_login_name(
             increment_post_viewer_count(&mut tx, uuid).await?;
         }
         None => {
+            if is_post_deleted(&mut tx, uuid).await? {
+                return Ok((
+                    StatusCode::GONE,
+                    handler_410(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
+                )
+                    .into_response());
+            }
             return Ok((
                 StatusCode::NOT_FOUND,
                 handler_404(auth_session, ExtractFtlLang(ftl_lang), State(state)).await?,
@@ -2375,7 +3315,8 @@ pub async fn post_view_by_login_name(
     // At this point post is guaranteed to be Some (would have returned 404 otherwise)
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    let comments = build_comment_thread_tree(&mut tx, uuid).await?;
+    let comments =
+        build_comment_thread_tree(&mut tx, uuid, auth_session.user.as_ref().map(|u| u.id)).await?;
 
     // Get parent post data if it exists
     let (parent_post_author_login_name, parent_post_data) =
@@ -2454,13 +3395,14 @@ pub async fn post_view_by_login_name(
             (String::new(), None)
         };
 
-    let community_id = post
-        .get("community_id")
-        .and_then(|id| id.as_ref())
-        .and_then(|id_str| Uuid::parse_str(id_str).ok());
+    let community_id = post_community_id(&post, uuid)?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     // Get collaborative session participants if this post is from a collaborative session
     let collaborative_participants: Vec<CollaborativeParticipant> = sqlx::query!(
@@ -2506,6 +3448,8 @@ pub async fn post_view_by_login_name(
     // Get child posts (threaded replies)
     let child_posts = build_thread_tree(&mut tx, uuid).await.unwrap_or_default();
 
+    let images = find_post_images(&mut tx, uuid).await?;
+
     tx.commit().await?;
 
     let community_id = community_id.map(|id| id.to_string());
@@ -2526,6 +3470,7 @@ pub async fn post_view_by_login_name(
             .map_err(|e| AppError::from(anyhow::anyhow!("Template render error: {}", e)))?;
         Ok(Html(rendered).into_response())
     } else {
+        let link_preview = post_link_preview(&state, &post, uuid);
         let rendered = template
             .render(context! {
                 current_user => auth_session.user,
@@ -2544,13 +3489,21 @@ pub async fn post_view_by_login_name(
                 community_id,
                 draft_post_count => common_ctx.draft_post_count,
                 unread_notification_count => common_ctx.unread_notification_count,
+                announcement => common_ctx.announcement,
                 base_url => state.config.base_url.clone(),
                 domain => state.config.domain.clone(),
+                translation_enabled => crate::translation::translation_enabled(&state.config),
+                post_history_public => state.config.post_history_public,
+                og_title => link_preview.title,
+                og_description => link_preview.description,
+                og_image_url => link_preview.image_url,
+                og_url => link_preview.url,
                 comments,
                 collaborative_participants,
                 reaction_counts,
                 hashtags,
                 child_posts,
+                images,
                 post_community,
                 ftl_lang
             })
@@ -2608,6 +3561,7 @@ async fn send_post_update_to_followers(
         actor,
         &state.config.domain,
         &state.config.r2_public_endpoint_url,
+        &state.config.sensitive_content_warning,
     )
     .await?;
 
@@ -2691,10 +3645,7 @@ pub async fn post_relay_view_by_login_name(
                 .ok_or_else(|| AppError::InvalidFormData("Missing login_name".to_string()))?;
 
             // Check if post is in a private community and if user has access
-            let community_id = post_data
-                .get("community_id")
-                .and_then(|v| v.as_ref())
-                .and_then(|s| Uuid::parse_str(s).ok());
+            let community_id = post_community_id(post_data, uuid)?;
 
             let community = if let Some(cid) = community_id {
                 find_community_by_id(&mut tx, cid).await?
@@ -2722,7 +3673,7 @@ pub async fn post_relay_view_by_login_name(
                                 get_user_role_in_community(&mut tx, user.id, comm.id).await?;
                             if user_role.is_none() {
                                 // User is not a member of this private community
-                                return Ok(flash_error_and_redirect(&headers, user.preferred_language.clone(), messages, "private-community-no-access", "/"));
+                                return Ok(flash_error_and_redirect(&headers, Some(user), messages, "private-community-no-access", "/"));
                             }
                         }
                         None => {
@@ -2747,10 +3698,7 @@ pub async fn post_relay_view_by_login_name(
 
     // Relay only works for community posts, not personal posts
     let post_data = post.clone();
-    let community_id = post_data
-        .get("community_id")
-        .and_then(|id| id.as_ref())
-        .and_then(|id_str| Uuid::parse_str(id_str).ok());
+    let community_id = post_community_id(&post_data, uuid)?;
 
     let community_id = match community_id {
         Some(id) => id,
@@ -2768,8 +3716,12 @@ pub async fn post_relay_view_by_login_name(
         .await?
         .ok_or_else(|| AppError::NotFound("Resource".to_string()))?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let rendered = template
         .render(context! {
@@ -2790,6 +3742,7 @@ pub async fn post_relay_view_by_login_name(
             is_relay => true,
             draft_post_count => common_ctx.draft_post_count,
             unread_notification_count => common_ctx.unread_notification_count,
+            announcement => common_ctx.announcement,
             ftl_lang
         })
         .map_err(|e| AppError::from(anyhow::anyhow!("Template render error: {}", e)))?;
@@ -2822,10 +3775,7 @@ pub async fn post_replay_view_by_login_name(
                 .ok_or_else(|| AppError::InvalidFormData("Missing login_name".to_string()))?;
 
             // Check if post is in a private community and if user has access
-            let community_id = post_data
-                .get("community_id")
-                .and_then(|v| v.as_ref())
-                .and_then(|s| Uuid::parse_str(s).ok());
+            let community_id = post_community_id(post_data, uuid)?;
 
             let community = if let Some(cid) = community_id {
                 find_community_by_id(&mut tx, cid).await?
@@ -2853,7 +3803,7 @@ pub async fn post_replay_view_by_login_name(
                                 get_user_role_in_community(&mut tx, user.id, comm.id).await?;
                             if user_role.is_none() {
                                 // User is not a member of this private community
-                                return Ok(flash_error_and_redirect(&headers, user.preferred_language.clone(), messages, "private-community-no-access", "/"));
+                                return Ok(flash_error_and_redirect(&headers, Some(user), messages, "private-community-no-access", "/"));
                             }
                         }
                         None => {
@@ -2870,13 +3820,14 @@ pub async fn post_replay_view_by_login_name(
     }
     let post = post.ok_or_else(|| AppError::NotFound("Post".to_string()))?;
 
-    let community_id = post
-        .get("community_id")
-        .and_then(|id| id.as_ref())
-        .and_then(|id_str| Uuid::parse_str(id_str).ok());
+    let community_id = post_community_id(&post, uuid)?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let community_id = community_id.map(|id| id.to_string());
 
@@ -2889,6 +3840,8 @@ pub async fn post_replay_view_by_login_name(
                 "post_replay_view_pch.jinja"
             } else if replay_filename.ends_with(".tgkr") {
                 "post_replay_view_tgkr.jinja"
+            } else if replay_filename.ends_with(".ncr") {
+                "post_replay_view_ncr.jinja"
             } else {
                 "post_replay_view_pch.jinja"
             }
@@ -2905,6 +3858,7 @@ pub async fn post_replay_view_by_login_name(
             community_id,
             draft_post_count => common_ctx.draft_post_count,
             unread_notification_count => common_ctx.unread_notification_count,
+            announcement => common_ctx.announcement,
             ftl_lang
         })
         .map_err(|e| AppError::from(anyhow::anyhow!("Template render error: {}", e)))?;
@@ -2937,10 +3891,7 @@ pub async fn add_reaction(
 
     if let Some(ref post_data) = post {
         // Check if post is in a private/unlisted community and if user has access
-        let community_id = post_data
-            .get("community_id")
-            .and_then(|v| v.as_ref())
-            .and_then(|s| Uuid::parse_str(s).ok());
+        let community_id = post_community_id(post_data, post_id)?;
 
         if let Some(cid) = community_id {
             let community = find_community_by_id(&mut tx, cid).await?;
@@ -2993,6 +3944,7 @@ pub async fn add_reaction(
         if post_author_id != user_id {
             if let Ok(notification) = create_notification(
                 &mut tx,
+                &state.notification_events,
                 CreateNotificationParams {
                     recipient_id: post_author_id,
                     actor_id: actor.id,
@@ -3010,6 +3962,21 @@ pub async fn add_reaction(
         }
     }
 
+    // ❤️ reactions are also federated as a Like for servers that don't understand EmojiReact
+    let like_iri = if form.emoji == LIKE_EMOJI {
+        let like = create_ap_like(
+            &mut tx,
+            generate_like_iri(&state.config.domain, post_id),
+            reaction.iri.clone(),
+            post_id,
+            actor.id,
+        )
+        .await?;
+        like.map(|like| like.iri)
+    } else {
+        None
+    };
+
     let user_actor_id = Some(actor.id);
     let reaction_counts = get_reaction_counts(&mut tx, post_id, user_actor_id).await?;
     tx.commit().await?;
@@ -3018,7 +3985,7 @@ pub async fn add_reaction(
     if !notification_info.is_empty() {
         let push_service = state.push_service.clone();
         let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
+        let send_notifications = async move {
             for (notification_id, recipient_id) in notification_info {
                 let mut tx = match db_pool.begin().await {
                     Ok(tx) => tx,
@@ -3045,7 +4012,8 @@ pub async fn add_reaction(
                 }
                 let _ = tx.commit().await;
             }
-        });
+        };
+        tokio::spawn(send_notifications.in_current_span());
     }
 
     // Send EmojiReact activity to post author if they're remote or local with followers
@@ -3099,6 +4067,29 @@ pub async fn add_reaction(
                     tracing::error!("Failed to send EmojiReact activity: {:?}", e);
                     // Don't fail the request if ActivityPub sending fails
                 }
+
+                if let Some(like_iri) = like_iri {
+                    use crate::web::handlers::activitypub::Like;
+
+                    let like = Like::new(
+                        ObjectId::parse(&actor.iri)?,
+                        post_url.parse()?,
+                        like_iri.parse()?,
+                        vec![post_author_actor.iri.clone()],
+                    );
+
+                    if let Err(e) = actor
+                        .send(
+                            like,
+                            vec![post_author_actor.shared_inbox_or_inbox()],
+                            state.config.use_activitypub_queue(),
+                            &federation_data,
+                        )
+                        .await
+                    {
+                        tracing::error!("Failed to send Like activity: {:?}", e);
+                    }
+                }
             }
         }
     }
@@ -3139,10 +4130,7 @@ pub async fn remove_reaction(
 
     if let Some(ref post_data) = post {
         // Check if post is in a private/unlisted community and if user has access
-        let community_id = post_data
-            .get("community_id")
-            .and_then(|v| v.as_ref())
-            .and_then(|s| Uuid::parse_str(s).ok());
+        let community_id = post_community_id(post_data, post_id)?;
 
         if let Some(cid) = community_id {
             let community = find_community_by_id(&mut tx, cid).await?;
@@ -3167,6 +4155,12 @@ pub async fn remove_reaction(
     use crate::models::reaction::find_user_reaction;
     let existing_reaction = find_user_reaction(&mut tx, post_id, actor.id, &form.emoji).await?;
 
+    // The Like row is removed along with the reaction, so look it up first
+    let existing_like = match &existing_reaction {
+        Some(reaction) => find_ap_like_by_reaction_iri(&mut tx, &reaction.iri).await?,
+        None => None,
+    };
+
     let _ = delete_reaction(&mut tx, post_id, actor.id, &form.emoji).await;
     let login_name = post
         .as_ref()
@@ -3252,6 +4246,36 @@ pub async fn remove_reaction(
                         tracing::error!("Failed to send Undo(EmojiReact) activity: {:?}", e);
                         // Don't fail the request if ActivityPub sending fails
                     }
+
+                    if let Some(existing_like) = existing_like {
+                        use crate::web::handlers::activitypub::Like;
+
+                        let like = Like::new(
+                            ObjectId::parse(&actor.iri)?,
+                            post_url.parse()?,
+                            existing_like.iri.parse()?,
+                            vec![post_author_actor.iri.clone()],
+                        );
+
+                        let undo = Undo {
+                            actor: ObjectId::parse(&actor.iri)?,
+                            object: UndoObject::Like(Box::new(like)),
+                            r#type: activitystreams_kinds::activity::UndoType::Undo,
+                            id: generate_object_id(&state.config.domain)?,
+                        };
+
+                        if let Err(e) = actor
+                            .send(
+                                undo,
+                                vec![post_author_actor.shared_inbox_or_inbox()],
+                                state.config.use_activitypub_queue(),
+                                &federation_data,
+                            )
+                            .await
+                        {
+                            tracing::error!("Failed to send Undo(Like) activity: {:?}", e);
+                        }
+                    }
                 }
             }
         }
@@ -3267,6 +4291,225 @@ pub async fn remove_reaction(
     Ok(Html(rendered).into_response())
 }
 
+/// Load a comment the user may react to: it must still exist and the user must
+/// be able to see the post it belongs to
+async fn find_reactable_comment(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: Uuid,
+    comment_id: Uuid,
+) -> Result<Result<Comment, StatusCode>, AppError> {
+    let comment = match find_comment_by_id(tx, comment_id).await? {
+        Some(comment) if comment.deleted_at.is_none() => comment,
+        _ => return Ok(Err(StatusCode::NOT_FOUND)),
+    };
+
+    let post = match find_post_by_id(tx, comment.post_id).await? {
+        Some(post) => post,
+        None => return Ok(Err(StatusCode::NOT_FOUND)),
+    };
+
+    let community_id = post_community_id(&post, comment.post_id)?;
+
+    if let Some(cid) = community_id {
+        if let Some(community) = find_community_by_id(tx, cid).await? {
+            // If community is private, check if user is a member
+            if community.visibility == crate::models::community::CommunityVisibility::Private
+                && get_user_role_in_community(tx, user_id, community.id)
+                    .await?
+                    .is_none()
+            {
+                return Ok(Err(StatusCode::FORBIDDEN));
+            }
+        }
+    }
+
+    Ok(Ok(comment))
+}
+
+/// Send an EmojiReact, or its Undo, to the author of a comment that arrived over ActivityPub
+async fn federate_comment_reaction(
+    state: &AppState,
+    actor: &Actor,
+    comment: &Comment,
+    reaction_iri: &str,
+    emoji: &str,
+    undo: bool,
+) -> Result<(), AppError> {
+    // Only federated comments carry an IRI; local authors see the reaction on the page
+    let Some(comment_iri) = comment.iri.as_ref() else {
+        return Ok(());
+    };
+
+    let mut tx = state.db_pool.begin().await?;
+    let comment_author_actor = Actor::find_by_id(&mut tx, comment.actor_id).await?;
+    tx.commit().await?;
+
+    let Some(comment_author_actor) = comment_author_actor else {
+        return Ok(());
+    };
+
+    use crate::web::handlers::activitypub::{EmojiReact, Undo, UndoObject};
+
+    let emoji_react = EmojiReact {
+        actor: Some(ObjectId::parse(&actor.iri)?),
+        object: comment_iri.parse()?,
+        content: emoji.to_string(),
+        r#type: "EmojiReact".to_string(),
+        id: reaction_iri.parse()?,
+        to: vec![comment_author_actor.iri.clone()],
+        cc: vec![],
+        signature: None,
+    };
+
+    // Create federation config
+    let federation_config = activitypub_federation::config::FederationConfig::builder()
+        .domain(&state.config.domain)
+        .app_data(state.clone())
+        .build()
+        .await?;
+    let federation_data = federation_config.to_request_data();
+    let inboxes = vec![comment_author_actor.shared_inbox_or_inbox()];
+
+    let result = if undo {
+        let undo = Undo {
+            actor: ObjectId::parse(&actor.iri)?,
+            object: UndoObject::EmojiReact(Box::new(emoji_react)),
+            r#type: activitystreams_kinds::activity::UndoType::Undo,
+            id: generate_object_id(&state.config.domain)?,
+        };
+        actor
+            .send(
+                undo,
+                inboxes,
+                state.config.use_activitypub_queue(),
+                &federation_data,
+            )
+            .await
+    } else {
+        actor
+            .send(
+                emoji_react,
+                inboxes,
+                state.config.use_activitypub_queue(),
+                &federation_data,
+            )
+            .await
+    };
+
+    if let Err(e) = result {
+        tracing::error!("Failed to send comment reaction activity: {:?}", e);
+        // Don't fail the request if ActivityPub sending fails
+    }
+
+    Ok(())
+}
+
+fn render_comment_reactions(
+    state: &AppState,
+    auth_session: &AuthSession,
+    comment_id: Uuid,
+    reaction_counts: Vec<ReactionCount>,
+) -> Result<axum::response::Response, AppError> {
+    let template: minijinja::Template<'_, '_> =
+        state.env.get_template("comment_reactions.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        comment_id => comment_id.to_string(),
+        reaction_counts,
+    })?;
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct CommentReactionForm {
+    pub emoji: String,
+}
+
+pub async fn add_comment_reaction(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(comment_id): Path<Uuid>,
+    Form(form): Form<CommentReactionForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
+
+    // Get the actor for this user
+    let actor = Actor::find_by_user_id(&mut tx, user_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No actor found for user"))?;
+
+    let comment = match find_reactable_comment(&mut tx, user_id, comment_id).await? {
+        Ok(comment) => comment,
+        Err(status) => return Ok(status.into_response()),
+    };
+
+    // Reacting again with the same emoji leaves the existing reaction in place
+    let reaction =
+        match find_user_comment_reaction(&mut tx, comment_id, actor.id, &form.emoji).await? {
+            Some(_) => None,
+            None => Some(
+                create_comment_reaction(
+                    &mut tx,
+                    CommentReactionDraft {
+                        comment_id,
+                        actor_id: actor.id,
+                        emoji: form.emoji.clone(),
+                    },
+                    &state.config.domain,
+                )
+                .await?,
+            ),
+        };
+
+    let reaction_counts = get_comment_reaction_counts(&mut tx, comment_id, Some(actor.id)).await?;
+    tx.commit().await?;
+
+    if let Some(reaction) = reaction {
+        federate_comment_reaction(&state, &actor, &comment, &reaction.iri, &form.emoji, false)
+            .await?;
+    }
+
+    render_comment_reactions(&state, &auth_session, comment_id, reaction_counts)
+}
+
+pub async fn remove_comment_reaction(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(comment_id): Path<Uuid>,
+    Query(form): Query<CommentReactionForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let user_id = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?.id;
+
+    // Get the actor for this user
+    let actor = Actor::find_by_user_id(&mut tx, user_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No actor found for user"))?;
+
+    let comment = match find_reactable_comment(&mut tx, user_id, comment_id).await? {
+        Ok(comment) => comment,
+        Err(status) => return Ok(status.into_response()),
+    };
+
+    // Find the reaction before deleting (need IRI for Undo activity)
+    let existing_reaction =
+        find_user_comment_reaction(&mut tx, comment_id, actor.id, &form.emoji).await?;
+    delete_comment_reaction(&mut tx, comment_id, actor.id, &form.emoji).await?;
+
+    let reaction_counts = get_comment_reaction_counts(&mut tx, comment_id, Some(actor.id)).await?;
+    tx.commit().await?;
+
+    if let Some(reaction) = existing_reaction {
+        federate_comment_reaction(&state, &actor, &comment, &reaction.iri, &form.emoji, true)
+            .await?;
+    }
+
+    render_comment_reactions(&state, &auth_session, comment_id, reaction_counts)
+}
+
 pub async fn post_reactions_detail(
     auth_session: AuthSession,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
@@ -3299,10 +4542,7 @@ pub async fn post_reactions_detail(
         .ok_or_else(|| AppError::InvalidFormData("Missing login_name".to_string()))?;
 
     // Check if post has community and verify correct slug
-    let community_id = post_data
-        .get("community_id")
-        .and_then(|v| v.as_ref())
-        .and_then(|s| Uuid::parse_str(s).ok());
+    let community_id = post_community_id(&post_data, uuid)?;
 
     if let Some(cid) = community_id {
         let community = find_community_by_id(&mut tx, cid).await?;
@@ -3323,8 +4563,12 @@ pub async fn post_reactions_detail(
     // Get all reactions for this post
     let reactions = find_reactions_by_post_id(&mut tx, uuid).await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     tx.commit().await?;
 
@@ -3386,6 +4630,7 @@ pub async fn post_reactions_detail(
         grouped_reactions => grouped_reactions,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang
     })?;
 
@@ -3550,3 +4795,263 @@ pub async fn move_post_community_api(
     // Return success with updated post data
     Ok(StatusCode::OK.into_response())
 }
+
+/// Lists how each remote inbox answered the activities sent for a post.
+/// Only the author can see this.
+pub async fn get_post_deliveries_api(
+    State(state): State<AppState>,
+    auth_session: AuthSession,
+    Path(post_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    use crate::models::activity_delivery::find_deliveries_by_object_iri;
+    use crate::web::responses::post::PostDeliveriesResponse;
+
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let post = sqlx::query!(
+        r#"SELECT id, author_id FROM posts WHERE id = $1 AND deleted_at IS NULL"#,
+        post_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Post".to_string()))?;
+
+    if post.author_id != user.id {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let object_iri = format!("https://{}/ap/posts/{}", state.config.domain, post.id);
+    let deliveries = find_deliveries_by_object_iri(&mut tx, &object_iri).await?;
+    tx.commit().await?;
+
+    Ok(Json(PostDeliveriesResponse { deliveries }).into_response())
+}
+
+const POST_TRANSLATION_RATE_LIMIT_SCOPE: &str = "translation:user";
+const POST_TRANSLATION_RATE_LIMIT_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+#[derive(Deserialize)]
+pub struct PostTranslationQuery {
+    pub target_lang: String,
+}
+
+/// GET /posts/:id/translation?target_lang=en
+///
+/// Machine-translates a post's content. Translations are cached per language,
+/// and only cache misses count against the user's daily limit.
+pub async fn post_translation(
+    auth_session: AuthSession,
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+    Query(query): Query<PostTranslationQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    use crate::app_error::error_codes;
+    use crate::models::post_translation::{find_post_translation, save_post_translation};
+    use crate::models::user::Language;
+    use crate::rate_limit::{RateLimit, RateLimiter};
+    use crate::translation::{translate, translation_enabled};
+    use crate::web::responses::post::PostTranslationResponse;
+    use crate::web::responses::ErrorResponse;
+    use axum::http::header::RETRY_AFTER;
+
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !translation_enabled(&state.config) {
+        return Err(AppError::NotFound("Translation".to_string()));
+    }
+    let target_lang = Language::from_code(&query.target_lang).ok_or_else(|| {
+        AppError::InvalidFormData(format!("Unsupported language: {}", query.target_lang))
+    })?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let post = find_post_by_id(&mut tx, post_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Post".to_string()))?;
+    if post.get("published_at").and_then(|v| v.as_ref()).is_none() {
+        return Err(AppError::NotFound("Post".to_string()));
+    }
+    if let Some(community_id) = post_community_id(&post, post_id)? {
+        if let Some(community) = find_community_by_id(&mut tx, community_id).await? {
+            if community.visibility == crate::models::community::CommunityVisibility::Private
+                && get_user_role_in_community(&mut tx, user.id, community.id)
+                    .await?
+                    .is_none()
+            {
+                return Ok(StatusCode::FORBIDDEN.into_response());
+            }
+        }
+    }
+    let content = post
+        .get("content")
+        .and_then(|v| v.clone())
+        .unwrap_or_default();
+    if content.trim().is_empty() {
+        return Err(AppError::NotFound("Post content".to_string()));
+    }
+
+    let translated_content =
+        match find_post_translation(&mut tx, post_id, target_lang.clone(), &content).await? {
+            Some(translated_content) => translated_content,
+            None => {
+                let limit = RateLimit::new(
+                    state.config.translation_daily_limit_per_user,
+                    POST_TRANSLATION_RATE_LIMIT_WINDOW_SECONDS,
+                );
+                let allowed = match RateLimiter::new(state.redis_pool.clone())
+                    .hit(
+                        POST_TRANSLATION_RATE_LIMIT_SCOPE,
+                        &user.id.to_string(),
+                        limit,
+                    )
+                    .await
+                {
+                    Ok(allowed) => allowed,
+                    Err(e) => {
+                        tracing::warn!("Failed to check translation rate limit: {:?}", e);
+                        true
+                    }
+                };
+                if !allowed {
+                    return Ok((
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [(
+                            RETRY_AFTER,
+                            POST_TRANSLATION_RATE_LIMIT_WINDOW_SECONDS.to_string(),
+                        )],
+                        Json(ErrorResponse::new(
+                            error_codes::RATE_LIMITED,
+                            "Daily translation limit reached",
+                        )),
+                    )
+                        .into_response());
+                }
+
+                let translated_content =
+                    match translate(&state.config, &content, &target_lang).await {
+                        Ok(translated_content) => translated_content,
+                        Err(e) => {
+                            tracing::error!("Failed to translate post {}: {:?}", post_id, e);
+                            let accept_language = headers
+                                .get(axum::http::header::ACCEPT_LANGUAGE)
+                                .cloned()
+                                .unwrap_or_else(|| HeaderValue::from_static(""));
+                            let bundle = get_user_bundle(&accept_language, Some(&user));
+                            return Ok((
+                                StatusCode::BAD_GATEWAY,
+                                safe_get_message(&bundle, "post-translation-failed"),
+                            )
+                                .into_response());
+                        }
+                    };
+                save_post_translation(&mut tx, post_id, target_lang, &content, &translated_content)
+                    .await?;
+                translated_content
+            }
+        };
+
+    tx.commit().await?;
+
+    if headers.get("HX-Request") == Some(&HeaderValue::from_static("true")) {
+        let template: minijinja::Template<'_, '_> =
+            state.env.get_template("post_translation.jinja")?;
+        let rendered = template.render(context! { translated_content })?;
+        return Ok(Html(rendered).into_response());
+    }
+
+    Ok(Json(PostTranslationResponse { translated_content }).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post_row(
+        fields: &[(&str, Option<&str>)],
+    ) -> std::collections::HashMap<String, Option<String>> {
+        fields
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.map(str::to_string)))
+            .collect()
+    }
+
+    #[test]
+    fn reads_author_and_community_ids() {
+        let post_id = Uuid::new_v4();
+        let (author_id, community_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let post = post_row(&[
+            ("author_id", Some(author_id.to_string().as_str())),
+            ("community_id", Some(community_id.to_string().as_str())),
+        ]);
+
+        assert_eq!(post_author_id(&post, post_id).unwrap(), author_id);
+        assert_eq!(
+            post_community_id(&post, post_id).unwrap(),
+            Some(community_id)
+        );
+    }
+
+    #[test]
+    fn personal_posts_have_no_community() {
+        let post = post_row(&[("community_id", None)]);
+        assert_eq!(post_community_id(&post, Uuid::new_v4()).unwrap(), None);
+    }
+
+    #[test]
+    fn missing_or_corrupt_ids_are_data_integrity_errors() {
+        let post_id = Uuid::new_v4();
+        let post = post_row(&[("author_id", None), ("community_id", Some("not-a-uuid"))]);
+
+        for err in [
+            post_author_id(&post, post_id).unwrap_err(),
+            post_community_id(&post, post_id).unwrap_err(),
+        ] {
+            assert!(
+                matches!(&err, AppError::DataIntegrity(msg) if msg.contains(&post_id.to_string())),
+                "unexpected error: {}",
+                err
+            );
+            assert_eq!(
+                err.into_response().status(),
+                StatusCode::INTERNAL_SERVER_ERROR
+            );
+        }
+    }
+
+    fn kinds(lines: &[DiffLine]) -> Vec<(&str, &str)> {
+        lines
+            .iter()
+            .map(|line| (line.kind, line.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn diff_keeps_unchanged_lines() {
+        let lines = diff_lines("a\nb\nc", "a\nx\nc");
+
+        assert_eq!(
+            kinds(&lines),
+            vec![
+                ("unchanged", "a"),
+                ("removed", "b"),
+                ("added", "x"),
+                ("unchanged", "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn long_texts_are_diffed_as_a_whole_replacement() {
+        let before = "line\n".repeat(1000);
+        let after = format!("{}changed\n", before);
+
+        let lines = diff_lines(&before, &after);
+
+        assert_eq!(lines.len(), 2001);
+        assert!(lines[..1000].iter().all(|line| line.kind == "removed"));
+        assert!(lines[1000..].iter().all(|line| line.kind == "added"));
+    }
+}
@@ -3,14 +3,16 @@ use crate::models::actor::Actor;
 use crate::models::banner::{
     activate_banner, delete_banner, find_banner_by_id, list_user_banners,
 };
+use crate::models::federated_post::find_federated_posts_by_community_id;
 use crate::models::follow::{
     count_followings_by_user_id, find_followings_by_user_id, follow_user, is_following,
     unfollow_user,
 };
 use crate::models::guestbook_entry::{
     add_guestbook_entry_reply, create_guestbook_entry, delete_guestbook_entry,
-    find_guestbook_entries_by_recipient_id, find_guestbook_entry_by_id, GuestbookEntryDraft,
+    find_guestbook_entries_for_user, find_guestbook_entry_by_id, GuestbookEntryDraft,
 };
+use crate::models::image::{create_uploaded_image, image_media_type, image_url};
 use crate::models::link::{
     create_link, delete_link, find_links_by_user_id, update_link_order, LinkDraft,
 };
@@ -20,33 +22,47 @@ use crate::models::notification::{
 };
 use crate::models::comment::find_latest_comments_in_community;
 use crate::models::post::{
-    find_published_posts_by_author_id, find_published_public_posts_by_author_id,
-    find_published_posts_by_community_id,
+    find_pinned_posts_by_user, find_published_posts_by_author_id,
+    find_published_public_posts_by_author_id, find_published_posts_by_community_id,
 };
 use crate::models::community::{
-    find_community_by_slug, get_community_stats, is_user_member, CommunityVisibility,
+    find_community_by_slug, get_community_stats, get_pinned_posts_for_community,
+    get_user_role_in_community, is_community_slug_deleted, is_user_member, CommunityMemberRole,
+    CommunityVisibility,
+};
+use crate::models::user::{
+    find_user_avatar_filename, find_user_by_id, find_user_by_login_name, get_user_activity,
+    update_user_avatar, ActivityCursor, AuthSession,
 };
-use crate::models::user::{find_user_by_id, find_user_by_login_name, AuthSession};
 use crate::web::context::CommonContext;
+use crate::web::handlers::community::{
+    community_link_preview, render_sensitive_interstitial, SensitiveConsentQuery,
+};
+use crate::web::handlers::draw::upload_object;
 use crate::web::handlers::home::LoadMoreQuery;
+use crate::web::handlers::{render_403, safe_decode_hash};
 use crate::web::responses::{
     PaginationMeta, ProfileBanner, ProfileFollowing, ProfileFollowingsListResponse, ProfileLink,
-    ProfilePost, ProfileResponse, ProfileUser,
+    ProfilePost, ProfileResponse, ProfileUser, UserActivityResponse,
 };
-use crate::web::handlers::render_403;
 use crate::web::state::AppState;
 use anyhow::Error;
 use aws_sdk_s3::config::{Credentials as AwsCredentials, Region, SharedCredentialsProvider};
 use aws_sdk_s3::types::{Delete, ObjectIdentifier};
 use aws_sdk_s3::Client;
-use axum::extract::{Path, Query};
+use axum::extract::{Multipart, Path, Query};
 use axum::http::{uri::Uri, HeaderMap, HeaderValue};
 use axum::response::{IntoResponse, Redirect};
 use axum::{extract::State, http::StatusCode, response::Html, response::Json, Form};
 
+use data_encoding::BASE64;
+use image::{ImageFormat, ImageReader, Limits};
 use minijinja::context;
 use serde::{Deserialize, Serialize};
+use sha256::digest;
 use std::collections::HashMap;
+use std::io::Cursor;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use super::ExtractFtlLang;
@@ -76,6 +92,7 @@ pub async fn do_follow_profile(
         let recipient_id = user.id;
         match create_notification(
             &mut tx,
+            &state.notification_events,
             CreateNotificationParams {
                 recipient_id,
                 actor_id: follower_actor.id,
@@ -102,7 +119,7 @@ pub async fn do_follow_profile(
     if !notification_info.is_empty() {
         let push_service = state.push_service.clone();
         let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
+        let send_notifications = async move {
             for (notification_id, recipient_id) in notification_info {
                 let mut tx = match db_pool.begin().await {
                     Ok(tx) => tx,
@@ -129,7 +146,8 @@ pub async fn do_follow_profile(
                 }
                 let _ = tx.commit().await;
             }
-        });
+        };
+        tokio::spawn(send_notifications.in_current_span());
     }
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("unfollow_button.jinja")?;
@@ -220,8 +238,12 @@ pub async fn profile(
         })
         .collect::<Vec<_>>();
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let mut is_current_user_following = false;
     if let Some(current_user) = auth_session.user.clone() {
@@ -261,6 +283,7 @@ pub async fn profile(
         private_community_posts,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang,
     })?;
 
@@ -273,8 +296,11 @@ pub async fn profile_or_community(
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
     State(state): State<AppState>,
     Path(slug): Path<String>,
+    Query(consent): Query<SensitiveConsentQuery>,
     uri: Uri,
 ) -> Result<impl IntoResponse, AppError> {
+    let is_htmx = headers.get("HX-Request") == Some(&HeaderValue::from_static("true"));
+
     let db = &state.db_pool;
     let mut tx = db.begin().await?;
 
@@ -297,8 +323,14 @@ pub async fn profile_or_community(
             })
             .collect::<Vec<_>>();
 
-        let common_ctx =
-            CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+        let pinned_posts = find_pinned_posts_by_user(&mut tx, user.id).await?;
+
+        let common_ctx = CommonContext::build(
+            &mut tx,
+            &state.redis_pool,
+            auth_session.user.as_ref().map(|u| u.id),
+        )
+        .await?;
 
         let mut is_current_user_following = false;
         if let Some(current_user) = auth_session.user.clone() {
@@ -312,6 +344,10 @@ pub async fn profile_or_community(
             None => None,
         };
 
+        let avatar_url = find_user_avatar_filename(&mut tx, user.id)
+            .await?
+            .map(|f| image_url(&state.config.r2_public_endpoint_url, &f));
+
         let links = find_links_by_user_id(&mut tx, user.id).await?;
         let links = links
             .iter()
@@ -330,14 +366,17 @@ pub async fn profile_or_community(
             current_user => auth_session.user,
             links,
             banner,
+            avatar_url,
             is_following => is_current_user_following,
             followings,
             user => Some(user),
             domain => state.config.domain.clone(),
+            pinned_posts,
             public_community_posts,
             private_community_posts,
             draft_post_count => common_ctx.draft_post_count,
             unread_notification_count => common_ctx.unread_notification_count,
+            announcement => common_ctx.announcement,
             ftl_lang,
         })?;
 
@@ -380,6 +419,23 @@ pub async fn profile_or_community(
             (None, false)
         };
 
+        // Viewers who haven't opted into sensitive content must pass the interstitial,
+        // after which the community's posts are shown blurred
+        let should_blur = community.is_sensitive && !viewer_show_sensitive;
+        if should_blur && !is_htmx && !consent.accept_sensitive {
+            return Ok(render_sensitive_interstitial(
+                &mut tx,
+                &auth_session,
+                &state,
+                &community,
+                &uri,
+                ftl_lang,
+            )
+            .await?
+            .into_response());
+        }
+        let viewer_show_sensitive = viewer_show_sensitive || community.is_sensitive;
+
         let posts = find_published_posts_by_community_id(
             &mut tx,
             community_uuid,
@@ -389,14 +445,35 @@ pub async fn profile_or_community(
             viewer_show_sensitive,
         )
         .await?;
+        let pinned_posts = get_pinned_posts_for_community(
+            &mut tx,
+            community_uuid,
+            viewer_user_id,
+            viewer_show_sensitive,
+        )
+        .await?;
+        let can_moderate = match viewer_user_id {
+            Some(user_id) => matches!(
+                get_user_role_in_community(&mut tx, user_id, community_uuid).await?,
+                Some(CommunityMemberRole::Owner) | Some(CommunityMemberRole::Moderator)
+            ),
+            None => false,
+        };
+        let federated_posts =
+            find_federated_posts_by_community_id(&mut tx, community_uuid, 20, viewer_show_sensitive)
+                .await?;
         let comments = find_latest_comments_in_community(&mut tx, community_uuid, 5).await?;
         let stats = get_community_stats(&mut tx, community_uuid).await?;
-        let common_ctx =
-            CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+        let common_ctx = CommonContext::build(
+            &mut tx,
+            &state.redis_pool,
+            auth_session.user.as_ref().map(|u| u.id),
+        )
+        .await?;
 
         let template: minijinja::Template<'_, '_> = state.env.get_template("community.jinja")?;
 
-        if headers.get("HX-Request") == Some(&HeaderValue::from_static("true")) {
+        if is_htmx {
             let rendered = template
                 .eval_to_state(context! {
                     current_user => auth_session.user,
@@ -408,14 +485,24 @@ pub async fn profile_or_community(
                 .render_block("community_edit_block")?;
             return Ok(Html(rendered).into_response());
         } else {
+            let link_preview = community_link_preview(&state, &community, !posts.is_empty());
             let rendered = template.render(context! {
                 current_user => auth_session.user,
                 community => Some(community),
                 community_id => community_uuid.to_string(),
                 domain => state.config.domain.clone(),
+                og_title => link_preview.title,
+                og_description => link_preview.description,
+                og_image_url => link_preview.image_url,
+                og_url => link_preview.url,
                 unread_notification_count => common_ctx.unread_notification_count,
+                announcement => common_ctx.announcement,
                 comments => comments,
                 stats => stats,
+                pinned_posts => pinned_posts,
+                federated_posts => federated_posts,
+                can_moderate => can_moderate,
+                should_blur => should_blur,
                 posts => posts.iter().map(|post| {
                     HashMap::<String, String>::from_iter(vec![
                         ("id".to_string(), post.id.to_string()),
@@ -437,16 +524,27 @@ pub async fn profile_or_community(
         }
     }
 
-    // Neither user nor community found - render 404 page
-    let common_ctx = CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
-    let template: minijinja::Template<'_, '_> = state.env.get_template("404.jinja")?;
+    // Neither user nor community found - render 410 page for a deleted community, 404 otherwise
+    let (status, template_name) = if is_community_slug_deleted(&mut tx, &slug).await? {
+        (StatusCode::GONE, "410.jinja")
+    } else {
+        (StatusCode::NOT_FOUND, "404.jinja")
+    };
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+    let template: minijinja::Template<'_, '_> = state.env.get_template(template_name)?;
     let rendered: String = template.render(context! {
         current_user => auth_session.user,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         ftl_lang,
     })?;
-    Ok((StatusCode::NOT_FOUND, Html(rendered)).into_response())
+    Ok((status, Html(rendered)).into_response())
 }
 
 pub async fn profile_iframe(
@@ -694,17 +792,26 @@ pub async fn profile_settings(
 
     // User is already the current user from auth, no need for ownership check
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let links = find_links_by_user_id(&mut tx, user.id).await?;
+    let avatar_url = find_user_avatar_filename(&mut tx, user.id)
+        .await?
+        .map(|f| image_url(&state.config.r2_public_endpoint_url, &f));
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("profile_settings.jinja")?;
     let rendered = template.render(context! {
         current_user => auth_session.user,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         links,
+        avatar_url,
         user => Some(user),
         ftl_lang,
     })?;
@@ -712,6 +819,95 @@ pub async fn profile_settings(
     Ok(Html(rendered).into_response())
 }
 
+const MAX_AVATAR_SIZE: usize = 1024 * 1024;
+// A small file can still claim a huge canvas, so cap what gets decoded
+const MAX_AVATAR_DIMENSION: u32 = 4096;
+
+pub async fn do_upload_avatar(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(login_name): Path<String>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = auth_session.user.as_ref().ok_or(AppError::Unauthorized)?;
+    if current_user.login_name != login_name {
+        return Ok(StatusCode::FORBIDDEN.into_response());
+    }
+
+    let mut avatar = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() != Some("avatar") {
+            continue;
+        }
+        let (format, extension) = match field.content_type() {
+            Some("image/png") => (ImageFormat::Png, "png"),
+            Some("image/jpeg") => (ImageFormat::Jpeg, "jpg"),
+            _ => {
+                return Err(AppError::InvalidFormData(
+                    "Avatar must be a PNG or JPEG image".to_string(),
+                ))
+            }
+        };
+        let data = field.bytes().await?;
+        if data.len() > MAX_AVATAR_SIZE {
+            return Err(AppError::InvalidFormData(format!(
+                "Avatar must be at most {} bytes",
+                MAX_AVATAR_SIZE
+            )));
+        }
+        avatar = Some((format, extension, data.to_vec()));
+    }
+    let (format, extension, data) =
+        avatar.ok_or_else(|| AppError::InvalidFormData("No avatar uploaded".to_string()))?;
+
+    // Decode to make sure the declared content type matches the bytes
+    let mut reader = ImageReader::with_format(Cursor::new(&data), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_AVATAR_DIMENSION);
+    limits.max_image_height = Some(MAX_AVATAR_DIMENSION);
+    reader.limits(limits);
+    let decoded = reader
+        .decode()
+        .map_err(|e| AppError::InvalidFormData(format!("Invalid avatar image: {}", e)))?;
+    let (width, height) = (decoded.width() as i32, decoded.height() as i32);
+
+    let image_sha256 = digest(&data);
+    let image_filename = format!("{}.{}", image_sha256, extension);
+
+    let credentials = AwsCredentials::new(
+        state.config.aws_access_key_id.clone(),
+        state.config.aws_secret_access_key.clone(),
+        None,
+        None,
+        "",
+    );
+    let credentials_provider = SharedCredentialsProvider::new(credentials);
+    let config = aws_sdk_s3::Config::builder()
+        .endpoint_url(state.config.r2_endpoint_url.clone())
+        .region(Region::new(state.config.aws_region.clone()))
+        .credentials_provider(credentials_provider)
+        .behavior_version_latest()
+        .build();
+    let client = Client::from_conf(config);
+    upload_object(
+        &client,
+        &state.config.aws_s3_bucket,
+        data,
+        &format!("image/{}/{}", &image_sha256[..2], image_filename),
+        &BASE64.encode(&safe_decode_hash(&image_sha256)?),
+        image_media_type(&image_filename),
+    )
+    .await?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let image_id = create_uploaded_image(&mut tx, width, height, &image_filename).await?;
+    update_user_avatar(&mut tx, current_user.id, Some(image_id)).await?;
+    tx.commit().await?;
+
+    Ok(Redirect::to(&format!("/@{}/settings", login_name)).into_response())
+}
+
 pub async fn banner_management(
     auth_session: AuthSession,
     ExtractFtlLang(ftl_lang): ExtractFtlLang,
@@ -726,8 +922,12 @@ pub async fn banner_management(
         .await?
         .ok_or_else(|| AppError::NotFound("User".to_string()))?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let banners = list_user_banners(&mut tx, user.id).await?;
 
@@ -751,6 +951,7 @@ pub async fn banner_management(
         current_user => auth_session.user,
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         user => Some(user),
         banners => banners_with_urls,
         ftl_lang,
@@ -802,12 +1003,15 @@ pub async fn do_reply_guestbook_entry(
     // Collect notification info (id, recipient_id) to send push notifications after commit
     let mut notification_info: Vec<(Uuid, Uuid)> = Vec::new();
 
-    // Create notification for the guestbook entry author (person who originally wrote the entry)
+    // Create notification for the guestbook entry author (person who originally wrote the entry).
+    // Remote authors have no local account to notify.
     let replier_actor = Actor::find_by_user_id(&mut tx, current_user.id).await?;
-    if let Some(replier_actor) = replier_actor {
-        let recipient_id = guestbook_entry.author_id;
+    if let (Some(replier_actor), Some(recipient_id)) =
+        (replier_actor, guestbook_entry.author_user_id)
+    {
         match create_notification(
             &mut tx,
+            &state.notification_events,
             CreateNotificationParams {
                 recipient_id,
                 actor_id: replier_actor.id,
@@ -834,7 +1038,7 @@ pub async fn do_reply_guestbook_entry(
     if !notification_info.is_empty() {
         let push_service = state.push_service.clone();
         let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
+        let send_notifications = async move {
             for (notification_id, recipient_id) in notification_info {
                 let mut tx = match db_pool.begin().await {
                     Ok(tx) => tx,
@@ -861,7 +1065,8 @@ pub async fn do_reply_guestbook_entry(
                 }
                 let _ = tx.commit().await;
             }
-        });
+        };
+        tokio::spawn(send_notifications.in_current_span());
     }
 
     let template: minijinja::Template<'_, '_> =
@@ -894,7 +1099,7 @@ pub async fn do_delete_guestbook_entry(
         .await?
         .ok_or_else(|| AppError::NotFound("Guestbook entry".to_string()))?;
 
-    if entry.author_id != current_user.id && entry.recipient_id != current_user.id {
+    if entry.author_user_id != Some(current_user.id) && entry.recipient_id != current_user.id {
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
@@ -933,12 +1138,18 @@ pub async fn do_write_guestbook_entry(
         return Ok(StatusCode::FORBIDDEN.into_response());
     }
 
+    let author_actor = Actor::find_by_user_id(&mut tx, current_user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+
     let guestbook_entry = create_guestbook_entry(
         &mut tx,
         GuestbookEntryDraft {
-            author_id: current_user_id,
+            author_actor_id: author_actor.id,
             recipient_id,
             content: form.content,
+            content_html: None,
+            iri: None,
         },
     )
     .await;
@@ -948,28 +1159,26 @@ pub async fn do_write_guestbook_entry(
 
     // Create notification for the guestbook owner
     if let Ok(ref entry) = guestbook_entry {
-        let author_actor = Actor::find_by_user_id(&mut tx, current_user_id).await?;
-        if let Some(author_actor) = author_actor {
-            match create_notification(
-                &mut tx,
-                CreateNotificationParams {
-                    recipient_id,
-                    actor_id: author_actor.id,
-                    notification_type: NotificationType::GuestbookEntry,
-                    post_id: None,
-                    comment_id: None,
-                    reaction_iri: None,
-                    guestbook_entry_id: Some(entry.id),
-                },
-            )
-            .await
-            {
-                Ok(notification) => {
-                    tracing::info!("Created guestbook entry notification");
-                    notification_info.push((notification.id, recipient_id));
-                }
-                Err(e) => tracing::warn!("Failed to create guestbook entry notification: {:?}", e),
+        match create_notification(
+            &mut tx,
+            &state.notification_events,
+            CreateNotificationParams {
+                recipient_id,
+                actor_id: author_actor.id,
+                notification_type: NotificationType::GuestbookEntry,
+                post_id: None,
+                comment_id: None,
+                reaction_iri: None,
+                guestbook_entry_id: Some(entry.id),
+            },
+        )
+        .await
+        {
+            Ok(notification) => {
+                tracing::info!("Created guestbook entry notification");
+                notification_info.push((notification.id, recipient_id));
             }
+            Err(e) => tracing::warn!("Failed to create guestbook entry notification: {:?}", e),
         }
     }
 
@@ -979,7 +1188,7 @@ pub async fn do_write_guestbook_entry(
     if !notification_info.is_empty() {
         let push_service = state.push_service.clone();
         let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
+        let send_notifications = async move {
             for (notification_id, recipient_id) in notification_info {
                 let mut tx = match db_pool.begin().await {
                     Ok(tx) => tx,
@@ -1006,7 +1215,8 @@ pub async fn do_write_guestbook_entry(
                 }
                 let _ = tx.commit().await;
             }
-        });
+        };
+        tokio::spawn(send_notifications.in_current_span());
     }
 
     let template: minijinja::Template<'_, '_> = state.env.get_template("guestbook_entry.jinja")?;
@@ -1031,10 +1241,14 @@ pub async fn guestbook(
         .await?
         .ok_or_else(|| AppError::NotFound("User".to_string()))?;
 
-    let guestbook_entries = find_guestbook_entries_by_recipient_id(&mut tx, user.id).await?;
+    let guestbook_entries = find_guestbook_entries_for_user(&mut tx, user.id).await?;
 
-    let common_ctx =
-        CommonContext::build(&mut tx, auth_session.user.as_ref().map(|u| u.id)).await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
 
     let banner = match user.banner_id {
         Some(banner_id) => Some(find_banner_by_id(&mut tx, banner_id).await?),
@@ -1053,6 +1267,7 @@ pub async fn guestbook(
         user => Some(user),
         draft_post_count => common_ctx.draft_post_count,
         unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
         is_following => is_current_user_following,
         guestbook_entries,
         ftl_lang,
@@ -1061,6 +1276,48 @@ pub async fn guestbook(
     Ok(Html(rendered).into_response())
 }
 
+#[derive(Deserialize)]
+pub struct ActivityQuery {
+    #[serde(default = "default_activity_limit")]
+    pub limit: i64,
+    pub cursor: Option<String>,
+}
+
+fn default_activity_limit() -> i64 {
+    20
+}
+
+/// Activity feed of the current user's posts, comments and reactions (JSON API for mobile)
+pub async fn my_activity_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<UserActivityResponse>, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let cursor = match query.cursor.as_deref() {
+        Some(cursor) => Some(
+            ActivityCursor::decode(cursor)
+                .ok_or_else(|| AppError::InvalidFormData("Invalid cursor".to_string()))?,
+        ),
+        None => None,
+    };
+    let limit = query.limit.clamp(1, 100);
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let (items, next_cursor) = get_user_activity(&mut tx, user.id, limit, cursor).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(UserActivityResponse {
+        items,
+        has_more: next_cursor.is_some(),
+        next_cursor: next_cursor.map(|cursor| cursor.encode()),
+    }))
+}
+
 pub async fn profile_json(
     auth_session: AuthSession,
     State(state): State<AppState>,
@@ -1082,6 +1339,10 @@ pub async fn profile_json(
         None => false,
     };
 
+    let avatar_url = find_user_avatar_filename(&mut tx, user.id)
+        .await?
+        .map(|f| image_url(&state.config.r2_public_endpoint_url, &f));
+
     // Get only public posts
     let public_posts =
         find_published_public_posts_by_author_id(&mut tx, user.id, query.limit, query.offset)
@@ -1173,6 +1434,7 @@ pub async fn profile_json(
             id: user.id,
             login_name: user.login_name,
             display_name: user.display_name,
+            avatar_url,
             is_following: is_following_profile,
         },
         banner,
@@ -1279,6 +1541,7 @@ pub async fn follow_profile_api(
         let recipient_id = target_user.id;
         match create_notification(
             &mut tx,
+            &state.notification_events,
             CreateNotificationParams {
                 recipient_id,
                 actor_id: follower_actor.id,
@@ -1305,7 +1568,7 @@ pub async fn follow_profile_api(
     if !notification_info.is_empty() {
         let push_service = state.push_service.clone();
         let db_pool = state.db_pool.clone();
-        tokio::spawn(async move {
+        let send_notifications = async move {
             for (notification_id, recipient_id) in notification_info {
                 let mut tx = match db_pool.begin().await {
                     Ok(tx) => tx,
@@ -1332,7 +1595,8 @@ pub async fn follow_profile_api(
                 }
                 let _ = tx.commit().await;
             }
-        });
+        };
+        tokio::spawn(send_notifications.in_current_span());
     }
 
     Ok(StatusCode::OK.into_response())
@@ -0,0 +1,104 @@
+use crate::models::image::{find_replays_to_archive, mark_replay_archived, replay_object_key};
+use crate::web::state::AppState;
+use aws_sdk_s3::config::{Credentials as AwsCredentials, Region, SharedCredentialsProvider};
+use aws_sdk_s3::types::StorageClass;
+use aws_sdk_s3::Client;
+use chrono::{TimeDelta, Utc};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+const ARCHIVE_INTERVAL_SECONDS: u64 = 3600;
+const ARCHIVE_BATCH_SIZE: i64 = 100;
+
+/// Periodically move aged replays to cold storage. Drawings themselves are never moved.
+pub async fn archive_old_replays(state: AppState) {
+    info!(
+        "Starting replay archiver (interval: {}s, after: {} days, class: {})",
+        ARCHIVE_INTERVAL_SECONDS,
+        state.config.replay_archive_after_days,
+        state.config.replay_archive_storage_class
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(ARCHIVE_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = archive_due_replays(&state).await {
+            error!("Failed to archive replays: {:?}", e);
+        }
+    }
+}
+
+async fn archive_due_replays(state: &AppState) -> anyhow::Result<()> {
+    let older_than = Utc::now()
+        - TimeDelta::try_days(state.config.replay_archive_after_days)
+            .ok_or_else(|| anyhow::anyhow!("Invalid replay_archive_after_days"))?;
+
+    let mut tx = state.db_pool.begin().await?;
+    let replays = find_replays_to_archive(&mut tx, older_than, ARCHIVE_BATCH_SIZE).await?;
+    tx.commit().await?;
+
+    if replays.is_empty() {
+        return Ok(());
+    }
+
+    let credentials = AwsCredentials::new(
+        state.config.aws_access_key_id.clone(),
+        state.config.aws_secret_access_key.clone(),
+        None,
+        None,
+        "",
+    );
+    let config = aws_sdk_s3::Config::builder()
+        .endpoint_url(state.config.r2_endpoint_url.clone())
+        .region(Region::new(state.config.aws_region.clone()))
+        .credentials_provider(SharedCredentialsProvider::new(credentials))
+        .behavior_version_latest()
+        .build();
+    let client = Client::from_conf(config);
+    let bucket = &state.config.aws_s3_bucket;
+    let storage_class = StorageClass::from(state.config.replay_archive_storage_class.as_str());
+
+    let mut archived = 0;
+    for replay in replays {
+        let hot_key = replay_object_key(&replay.replay_filename, false);
+        let cold_key = replay_object_key(&replay.replay_filename, true);
+
+        // Copy first so the replay stays reachable at one of the two keys throughout
+        if let Err(e) = client
+            .copy_object()
+            .bucket(bucket)
+            .copy_source(format!("{}/{}", bucket, hot_key))
+            .key(&cold_key)
+            .storage_class(storage_class.clone())
+            .send()
+            .await
+        {
+            warn!("Failed to copy replay {} to cold storage: {:?}", hot_key, e);
+            continue;
+        }
+
+        let mut tx = state.db_pool.begin().await?;
+        mark_replay_archived(&mut tx, replay.image_id).await?;
+        tx.commit().await?;
+
+        if let Err(e) = client
+            .delete_object()
+            .bucket(bucket)
+            .key(&hot_key)
+            .send()
+            .await
+        {
+            warn!(
+                "Archived replay {} but failed to delete it: {:?}",
+                hot_key, e
+            );
+        }
+        archived += 1;
+    }
+
+    debug!("Archived {} replays", archived);
+
+    Ok(())
+}
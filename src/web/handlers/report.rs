@@ -1,20 +1,37 @@
 use crate::app_error::AppError;
+use crate::models::actor::Actor;
+use crate::models::comment::{find_comment_by_id, find_comment_by_iri};
+use crate::models::federated_post::find_federated_post_by_iri;
 use crate::models::post::find_post_by_id;
+use crate::models::report::{
+    create_object_report, create_report, get_open_object_reports, get_pending_reports,
+    resolve_object_report, resolve_report, PostReport, PostReportReason, PostReportStatus, Report,
+};
 use crate::models::user::{find_user_by_id, find_user_by_login_name, AuthSession};
-use crate::web::handlers::{get_bundle, safe_get_message, ExtractAcceptLanguage};
+use crate::web::context::CommonContext;
+use crate::web::handlers::activitypub::{
+    federation_config, generate_object_id, send_flag_activity, verify_federated_object,
+    StoredFederatedObject,
+};
+use crate::web::handlers::{
+    get_bundle, render_403, safe_get_message, ExtractAcceptLanguage, ExtractFtlLang,
+};
 use crate::web::state::AppState;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::response::IntoResponse;
-use axum::Json;
+use axum::response::{Html, IntoResponse, Redirect};
+use axum::{Form, Json};
 use lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
 use lettre::{Message, SmtpTransport, Transport};
+use minijinja::context;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[derive(Deserialize)]
 pub struct ReportPostRequest {
     pub description: String,
+    #[serde(default)]
+    pub reason: Option<PostReportReason>,
 }
 
 #[derive(Serialize)]
@@ -72,12 +89,34 @@ pub async fn report_post_api(
         ));
     }
 
+    // Record the report in the moderation queue
+    let report = create_report(
+        &mut tx,
+        user.id,
+        post_id,
+        request.reason.unwrap_or(PostReportReason::Other),
+        request.description.clone(),
+    )
+    .await?;
+
+    // Reporting the same post again succeeds without alerting anyone twice
+    let Some(report) = report else {
+        tx.commit().await?;
+        return Ok((
+            StatusCode::OK,
+            Json(ReportPostResponse {
+                message: "Post reported successfully".to_string(),
+            }),
+        ));
+    };
+
     // Fetch post author details
     let post_author = find_user_by_id(&mut tx, post_author_id)
         .await?
         .ok_or(AppError::DatabaseError("Post author not found".to_string()))?;
 
     tx.commit().await?;
+    notify_admins_of_report(&report);
 
     // Prepare email content
     let post_url = format!("https://{}/@{}/{}", state.config.domain, post_author.login_name, post_id);
@@ -303,3 +342,368 @@ This is an automated report notification from oeee.cafe",
         }),
     ))
 }
+
+/// Leave a Sentry breadcrumb and event so admins are alerted to new reports in real time
+fn notify_admins_of_report(report: &PostReport) {
+    sentry::add_breadcrumb(sentry::Breadcrumb {
+        category: Some("moderation".to_string()),
+        message: Some(format!(
+            "Post {} reported for {:?}",
+            report.post_id, report.reason
+        )),
+        level: sentry::Level::Warning,
+        ..Default::default()
+    });
+    sentry::capture_message(
+        &format!("New post report {} awaiting moderation", report.id),
+        sentry::Level::Warning,
+    );
+}
+
+#[derive(Deserialize)]
+pub struct ReportPostForm {
+    pub reason: PostReportReason,
+    #[serde(default)]
+    pub detail: String,
+}
+
+/// Form endpoint: POST /posts/:id/report
+pub async fn do_report_post(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+    Form(form): Form<ReportPostForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let post = find_post_by_id(&mut tx, post_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Post".to_string()))?;
+
+    if post.get("deleted_at").and_then(|v| v.as_ref()).is_some() {
+        return Err(AppError::InvalidFormData("Cannot report deleted post".to_string()));
+    }
+
+    let post_author_id = post
+        .get("author_id")
+        .and_then(|v| v.as_ref())
+        .and_then(|s| Uuid::parse_str(s).ok());
+    if post_author_id == Some(user.id) {
+        return Err(AppError::InvalidFormData(
+            "You cannot report your own post".to_string(),
+        ));
+    }
+
+    let report = create_report(&mut tx, user.id, post_id, form.reason, form.detail).await?;
+
+    tx.commit().await?;
+
+    match report {
+        Some(report) => {
+            notify_admins_of_report(&report);
+            Ok(Redirect::to(&format!("/posts/{}", post_id)).into_response())
+        }
+        None => Ok((StatusCode::CONFLICT, "You have already reported this post").into_response()),
+    }
+}
+
+/// Admin page: GET /admin/reports
+pub async fn admin_reports(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let reports = get_pending_reports(&mut tx).await?;
+    let object_reports = get_open_object_reports(&mut tx).await?;
+    let common_ctx = CommonContext::build(&mut tx, &state.redis_pool, Some(user.id)).await?;
+
+    tx.commit().await?;
+
+    let template: minijinja::Template<'_, '_> = state.env.get_template("admin_reports.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        reports,
+        object_reports,
+        domain => state.config.domain.clone(),
+        r2_public_endpoint_url => state.config.r2_public_endpoint_url.clone(),
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang,
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ResolveReportForm {
+    pub status: PostReportStatus,
+}
+
+/// Admin endpoint: POST /admin/reports/:id/resolve
+pub async fn do_resolve_report(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(report_id): Path<Uuid>,
+    Form(form): Form<ResolveReportForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    if form.status == PostReportStatus::Pending {
+        return Err(AppError::InvalidFormData(
+            "Reports can only be resolved or dismissed".to_string(),
+        ));
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if !resolve_report(&mut tx, report_id, user.id, form.status).await? {
+        return Err(AppError::NotFound("Pending report".to_string()));
+    }
+
+    tx.commit().await?;
+
+    Ok(Redirect::to("/admin/reports").into_response())
+}
+
+/// Admin endpoint: POST /admin/reports/objects/:id/resolve
+pub async fn do_resolve_object_report(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(report_id): Path<Uuid>,
+    Form(form): Form<ResolveReportForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    if form.status == PostReportStatus::Pending {
+        return Err(AppError::InvalidFormData(
+            "Reports can only be resolved or dismissed".to_string(),
+        ));
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    if !resolve_object_report(&mut tx, report_id, user.id, form.status).await? {
+        return Err(AppError::NotFound("Pending report".to_string()));
+    }
+
+    tx.commit().await?;
+
+    Ok(Redirect::to("/admin/reports").into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ReportObjectForm {
+    /// ActivityPub IRI of a remote post or comment
+    pub object_iri: String,
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(Deserialize)]
+pub struct ReportCommentForm {
+    #[serde(default)]
+    pub comment: String,
+}
+
+/// Record a report about an object by `author`. Content from other instances
+/// is also reported to their moderators with a Flag.
+async fn file_object_report(
+    state: &AppState,
+    reporter_id: Uuid,
+    object_iri: &str,
+    author: &Actor,
+    comment: &str,
+) -> Result<Option<Report>, AppError> {
+    if author.user_id == Some(reporter_id) {
+        return Err(AppError::InvalidFormData(
+            "You cannot report your own content".to_string(),
+        ));
+    }
+    let is_remote = author.user_id.is_none() && author.community_id.is_none();
+    let flag_iri = match is_remote {
+        true => Some(generate_object_id(&state.config.domain)?),
+        false => None,
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let reporter = Actor::find_by_user_id(&mut tx, reporter_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+    let report = create_object_report(
+        &mut tx,
+        flag_iri.as_ref().map(|iri| iri.as_str()),
+        reporter.id,
+        &[object_iri.to_string()],
+        comment,
+    )
+    .await?;
+    tx.commit().await?;
+
+    // The report is recorded either way, so a failed delivery is only logged
+    if let Some(flag_iri) = flag_iri {
+        if let Err(e) = send_flag_activity(flag_iri, author, object_iri, comment, state).await {
+            tracing::warn!("Failed to send Flag for {}: {:?}", object_iri, e);
+        }
+    }
+
+    Ok(report)
+}
+
+fn render_report_sent(state: &AppState, ftl_lang: String) -> Result<Html<String>, AppError> {
+    let template: minijinja::Template<'_, '_> = state.env.get_template("report_sent.jinja")?;
+    Ok(Html(template.render(context! { ftl_lang })?))
+}
+
+/// HTMX: POST /comments/:id/report
+pub async fn do_report_comment(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Path(comment_id): Path<Uuid>,
+    Form(form): Form<ReportCommentForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let comment = find_comment_by_id(&mut tx, comment_id)
+        .await?
+        .filter(|comment| comment.deleted_at.is_none())
+        .ok_or_else(|| AppError::NotFound("Comment".to_string()))?;
+    let author = Actor::find_by_id(&mut tx, comment.actor_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+    tx.commit().await?;
+
+    // Local comments are federated under /comments/:id
+    let object_iri = comment
+        .iri
+        .unwrap_or_else(|| format!("https://{}/comments/{}", state.config.domain, comment.id));
+    file_object_report(&state, user.id, &object_iri, &author, form.comment.trim()).await?;
+
+    Ok(render_report_sent(&state, ftl_lang)?.into_response())
+}
+
+/// HTMX: POST /reports, for remote posts and comments known by their IRI.
+/// Local posts are reported through /posts/:id/report.
+pub async fn do_report_object(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Form(form): Form<ReportObjectForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.ok_or(AppError::Unauthorized)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let actor_id = match find_federated_post_by_iri(&mut tx, &form.object_iri).await? {
+        Some(post) => Some(post.actor_id),
+        None => find_comment_by_iri(&mut tx, &form.object_iri)
+            .await?
+            .map(|comment| comment.actor_id),
+    };
+    let actor_id = actor_id.ok_or_else(|| AppError::NotFound("Reported object".to_string()))?;
+    let author = Actor::find_by_id(&mut tx, actor_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Actor".to_string()))?;
+    tx.commit().await?;
+
+    file_object_report(
+        &state,
+        user.id,
+        &form.object_iri,
+        &author,
+        form.comment.trim(),
+    )
+    .await?;
+
+    Ok(render_report_sent(&state, ftl_lang)?.into_response())
+}
+
+#[derive(Deserialize)]
+pub struct VerifyFederatedObjectQuery {
+    pub iri: String,
+}
+
+/// Admin endpoint: GET /admin/federation/verify?iri=...
+///
+/// Re-fetches a stored federated post or comment from its origin and reports
+/// whether it still exists, still matches our copy, and who signs for it.
+pub async fn admin_verify_federated_object(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Query(query): Query<VerifyFederatedObjectQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = auth_session.user.clone().ok_or(AppError::Unauthorized)?;
+    if !user.is_admin {
+        return Ok(render_403(&auth_session, &state, ftl_lang)
+            .await?
+            .into_response());
+    }
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let stored = if let Some(post) = find_federated_post_by_iri(&mut tx, &query.iri).await? {
+        Actor::find_by_id(&mut tx, post.actor_id)
+            .await?
+            .map(|actor| StoredFederatedObject {
+                kind: "post",
+                iri: post.iri,
+                content_html: post.content_html,
+                image_url: post.image_url,
+                actor,
+            })
+    } else if let Some(comment) = find_comment_by_iri(&mut tx, &query.iri).await? {
+        Actor::find_by_id(&mut tx, comment.actor_id)
+            .await?
+            .map(|actor| StoredFederatedObject {
+                kind: "comment",
+                iri: query.iri.clone(),
+                content_html: comment.content_html.unwrap_or_default(),
+                image_url: None,
+                actor,
+            })
+    } else {
+        None
+    };
+    tx.commit().await?;
+
+    let stored = stored.ok_or_else(|| AppError::NotFound("Federated object".to_string()))?;
+
+    let federation_data = federation_config(&state).await?.to_request_data();
+
+    let verification = verify_federated_object(stored, &federation_data).await?;
+
+    Ok(Json(verification).into_response())
+}
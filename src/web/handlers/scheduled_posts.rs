@@ -0,0 +1,209 @@
+use crate::models::community::{find_closed_posting_window, post_needs_approval};
+use crate::models::post::{
+    defer_post_until, lock_next_due_scheduled_post, mark_scheduled_post_failed, publish_post,
+    submit_post_for_approval, ScheduledPost,
+};
+use crate::web::handlers::post::notify_and_federate_published_post;
+use crate::web::state::AppState;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+const PUBLISH_INTERVAL_SECONDS: u64 = 60;
+
+pub async fn publish_scheduled_posts(state: AppState) {
+    info!(
+        "Starting scheduled post publisher (interval: {}s)",
+        PUBLISH_INTERVAL_SECONDS
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(PUBLISH_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = publish_due_posts(&state).await {
+            error!("Failed to publish scheduled posts: {:?}", e);
+        }
+    }
+}
+
+async fn publish_due_posts(state: &AppState) -> anyhow::Result<()> {
+    let mut published_count = 0;
+
+    // Each post gets its own transaction so one that can't be published
+    // doesn't hold back the rest
+    loop {
+        let mut tx = state.db_pool.begin().await?;
+        let Some(post) = lock_next_due_scheduled_post(&mut tx).await? else {
+            tx.commit().await?;
+            break;
+        };
+        let (post_id, author_id) = (post.id, post.author_id);
+
+        match publish_due_post(&mut tx, post).await {
+            Ok(published) => {
+                tx.commit().await?;
+                if !published {
+                    continue;
+                }
+                published_count += 1;
+
+                // Notifications and federation run after the post is visible
+                if let Err(e) = notify_and_federate_published_post(state, post_id, author_id).await
+                {
+                    error!(
+                        "Failed to deliver scheduled post {} to followers: {}",
+                        post_id, e
+                    );
+                }
+            }
+            Err(e) => {
+                error!("Failed to publish scheduled post {}: {:?}", post_id, e);
+                tx.rollback().await?;
+
+                let mut tx = state.db_pool.begin().await?;
+                mark_scheduled_post_failed(&mut tx, post_id).await?;
+                tx.commit().await?;
+            }
+        }
+    }
+
+    if published_count > 0 {
+        debug!("Published {} scheduled posts", published_count);
+    }
+
+    Ok(())
+}
+
+/// Publish a due post, send it to the review queue for communities that
+/// review submissions, or hold it until the community's posting window opens.
+/// Returns whether it was published.
+async fn publish_due_post(
+    tx: &mut Transaction<'_, Postgres>,
+    post: ScheduledPost,
+) -> anyhow::Result<bool> {
+    if let Some(community_id) = post.community_id {
+        let now = Utc::now();
+        if let Some(window) =
+            find_closed_posting_window(tx, community_id, post.author_id, now).await?
+        {
+            let opens_at = window.next_opening(now).with_timezone(&Utc);
+            defer_post_until(tx, post.id, opens_at, post.approved).await?;
+            return Ok(false);
+        }
+    }
+
+    let needs_approval = match post.community_id {
+        Some(community_id) if !post.approved => {
+            post_needs_approval(tx, community_id, post.author_id).await?
+        }
+        _ => false,
+    };
+    if needs_approval {
+        submit_post_for_approval(
+            tx,
+            post.id,
+            post.title.unwrap_or_default(),
+            post.content.unwrap_or_default(),
+            post.is_sensitive,
+            post.allow_relay,
+        )
+        .await?;
+        return Ok(false);
+    }
+
+    publish_post(
+        tx,
+        post.id,
+        post.title.unwrap_or_default(),
+        post.content.unwrap_or_default(),
+        post.is_sensitive,
+        post.allow_relay,
+    )
+    .await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::community::{set_community_posting_window, CommunityVisibility};
+    use crate::models::post::schedule_post;
+    use crate::models::test_fixtures;
+    use chrono::{DateTime, TimeDelta};
+    use uuid::Uuid;
+
+    async fn schedule(
+        tx: &mut Transaction<'_, Postgres>,
+        author_id: Uuid,
+        community_id: Uuid,
+        scheduled_for: DateTime<Utc>,
+    ) -> Uuid {
+        let post = test_fixtures::draft_post(tx, author_id, Some(community_id)).await;
+        schedule_post(
+            tx,
+            post.id,
+            "title".to_string(),
+            String::new(),
+            false,
+            false,
+            scheduled_for,
+        )
+        .await
+        .unwrap();
+        post.id
+    }
+
+    #[sqlx::test]
+    async fn due_posts_are_published_unless_the_window_is_closed(pool: sqlx::PgPool) {
+        let mut tx = pool.begin().await.unwrap();
+        let owner = test_fixtures::user(&mut tx, "owner").await;
+        let author = test_fixtures::user(&mut tx, "author").await;
+        let open =
+            test_fixtures::community(&mut tx, owner.id, "open", CommunityVisibility::Public).await;
+        let closed =
+            test_fixtures::community(&mut tx, owner.id, "closed", CommunityVisibility::Public)
+                .await;
+        let now = Utc::now();
+        let opens_at = now + TimeDelta::hours(1);
+        set_community_posting_window(
+            &mut tx,
+            closed.id,
+            "UTC",
+            opens_at.time(),
+            (now + TimeDelta::hours(2)).time(),
+        )
+        .await
+        .unwrap();
+
+        let due = now - TimeDelta::minutes(1);
+        let published = schedule(&mut tx, author.id, open.id, due).await;
+        let held = schedule(&mut tx, author.id, closed.id, due).await;
+        let not_yet = schedule(&mut tx, author.id, open.id, now + TimeDelta::hours(1)).await;
+
+        let mut handled = 0;
+        while let Some(post) = lock_next_due_scheduled_post(&mut tx).await.unwrap() {
+            publish_due_post(&mut tx, post).await.unwrap();
+            handled += 1;
+            assert!(handled <= 2, "a handled post came due again");
+        }
+
+        let state = |id: Uuid| {
+            sqlx::query!(
+                "SELECT published_at, scheduled_for FROM posts WHERE id = $1",
+                id
+            )
+        };
+        let row = state(published).fetch_one(&mut *tx).await.unwrap();
+        assert!(row.published_at.is_some());
+        let row = state(held).fetch_one(&mut *tx).await.unwrap();
+        assert!(row.published_at.is_none());
+        // Held until the window opens, to the second
+        let rescheduled = row.scheduled_for.unwrap();
+        assert!((rescheduled - opens_at).num_seconds().abs() < 1);
+        let row = state(not_yet).fetch_one(&mut *tx).await.unwrap();
+        assert!(row.published_at.is_none());
+    }
+}
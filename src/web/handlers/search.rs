@@ -1,9 +1,18 @@
 use crate::app_error::AppError;
+use crate::models::community::search_public_communities;
+use crate::models::post::{count_search_published_posts, search_published_posts};
 use crate::models::user::AuthSession;
-use crate::web::responses::{SearchPostResult, SearchResponse, SearchUserResult};
+use crate::web::context::CommonContext;
+use crate::web::handlers::ExtractFtlLang;
+use crate::web::responses::{
+    PaginationMeta, PostSearchItem, PostSearchResponse, SearchCommunityResult, SearchPostResult,
+    SearchResponse, SearchResultItem, SearchUserResult,
+};
 use crate::web::state::AppState;
 use axum::extract::Query;
+use axum::response::{Html, IntoResponse};
 use axum::{extract::State, response::Json};
+use minijinja::context;
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -19,10 +28,10 @@ pub async fn search_json(
     Query(query): Query<SearchQuery>,
 ) -> Result<Json<SearchResponse>, AppError> {
     let db = &state.db_pool;
-    let mut tx = db.begin().await?;
 
-    let search_term = format!("%{}%", query.q);
-    let limit = query.limit.unwrap_or(20).min(50);
+    let search_query = query.q.trim();
+    let search_term = format!("%{}%", search_query);
+    let limit = query.limit.unwrap_or(20).clamp(1, 50);
 
     // Get viewer preferences for sensitive content filtering
     let (viewer_user_id, viewer_show_sensitive) = if let Some(user) = auth_session.user {
@@ -31,67 +40,71 @@ pub async fn search_json(
         (None, false)
     };
 
-    // Search for users by login_name or display_name
-    let users = sqlx::query!(
-        r#"
-        SELECT
-            id,
-            login_name,
-            display_name
-        FROM users
-        WHERE login_name ILIKE $1
-           OR display_name ILIKE $1
-        ORDER BY
-            CASE
-                WHEN login_name ILIKE $2 THEN 0
-                WHEN display_name ILIKE $2 THEN 1
-                ELSE 2
-            END,
-            login_name
-        LIMIT $3
-        "#,
-        search_term,
-        query.q,
-        limit
-    )
-    .fetch_all(&mut *tx)
-    .await?;
+    if search_query.is_empty() {
+        return Ok(Json(SearchResponse {
+            users: vec![],
+            posts: vec![],
+            communities: vec![],
+            results: vec![],
+        }));
+    }
 
-    // Search for posts by title or content (only from public communities)
-    let posts = sqlx::query!(
-        r#"
-        SELECT
-            posts.id,
-            posts.title,
-            posts.content,
-            posts.author_id,
-            users.login_name AS user_login_name,
-            images.image_filename AS "image_filename?",
-            images.width AS "image_width?",
-            images.height AS "image_height?",
-            posts.published_at,
-            posts.is_sensitive
-        FROM posts
-        LEFT JOIN users ON posts.author_id = users.id
-        LEFT JOIN images ON posts.image_id = images.id
-        LEFT JOIN communities ON posts.community_id = communities.id
-        WHERE (posts.title ILIKE $1 OR posts.content ILIKE $1)
-          AND posts.published_at IS NOT NULL
-          AND posts.deleted_at IS NULL
-          AND (communities.visibility = 'public' OR posts.community_id IS NULL)
-          AND (posts.is_sensitive = false OR $3 = true OR posts.author_id = $4)
-        ORDER BY posts.published_at DESC
-        LIMIT $2
-        "#,
-        search_term,
-        limit,
-        viewer_show_sensitive,
-        viewer_user_id
-    )
-    .fetch_all(&mut *tx)
-    .await?;
+    // Each search gets its own transaction so the three queries can run concurrently
+    let users_search = async {
+        let mut tx = db.begin().await?;
+        // Search for users by login_name or display_name
+        let users = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                login_name,
+                display_name
+            FROM users
+            WHERE login_name ILIKE $1
+               OR display_name ILIKE $1
+            ORDER BY
+                CASE
+                    WHEN login_name ILIKE $2 THEN 0
+                    WHEN display_name ILIKE $2 THEN 1
+                    ELSE 2
+                END,
+                login_name
+            LIMIT $3
+            "#,
+            search_term,
+            search_query,
+            limit
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok::<_, AppError>(users)
+    };
 
-    tx.commit().await?;
+    let posts_search = async {
+        let mut tx = db.begin().await?;
+        let posts = search_published_posts(
+            &mut tx,
+            search_query,
+            limit,
+            0,
+            viewer_user_id,
+            viewer_show_sensitive,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok::<_, AppError>(posts)
+    };
+
+    let communities_search = async {
+        let mut tx = db.begin().await?;
+        let communities = search_public_communities(&mut tx, search_query, limit, 0).await?;
+        tx.commit().await?;
+        Ok::<_, AppError>(communities)
+    };
+
+    let (users, posts, communities) =
+        tokio::try_join!(users_search, posts_search, communities_search)?;
 
     // Convert users to typed structs
     let users_typed: Vec<SearchUserResult> = users
@@ -107,28 +120,215 @@ pub async fn search_json(
     let posts_typed: Vec<SearchPostResult> = posts
         .into_iter()
         .map(|post| {
-            let image_url = if let Some(ref filename) = post.image_filename {
-                let image_prefix = &filename[..2];
-                format!(
-                    "{}/image/{}/{}",
-                    state.config.r2_public_endpoint_url, image_prefix, filename
-                )
-            } else {
-                String::new()
-            };
-
+            let image_prefix = &post.image_filename[..2];
             SearchPostResult {
                 id: post.id,
-                image_url,
-                image_width: post.image_width,
-                image_height: post.image_height,
+                image_url: format!(
+                    "{}/image/{}/{}",
+                    state.config.r2_public_endpoint_url, image_prefix, post.image_filename
+                ),
+                image_width: Some(post.image_width),
+                image_height: Some(post.image_height),
                 is_sensitive: post.is_sensitive,
+                rank: post.rank,
             }
         })
         .collect();
 
+    let communities_typed: Vec<SearchCommunityResult> = communities
+        .into_iter()
+        .map(|community| SearchCommunityResult {
+            id: community.id,
+            name: community.name,
+            slug: community.slug,
+            description: community.description,
+            posts_count: community.posts_count,
+            rank: community.rank,
+        })
+        .collect();
+
+    let mut results: Vec<SearchResultItem> = posts_typed
+        .iter()
+        .cloned()
+        .map(SearchResultItem::Post)
+        .chain(
+            communities_typed
+                .iter()
+                .cloned()
+                .map(SearchResultItem::Community),
+        )
+        .collect();
+    results.sort_by(|a, b| b.rank().total_cmp(&a.rank()));
+    results.truncate(limit as usize);
+
     Ok(Json(SearchResponse {
         users: users_typed,
         posts: posts_typed,
+        communities: communities_typed,
+        results,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct PostSearchQuery {
+    #[serde(default)]
+    q: String,
+    #[serde(default)]
+    offset: i64,
+    #[serde(default = "default_post_search_limit")]
+    limit: i64,
+}
+
+fn default_post_search_limit() -> i64 {
+    20
+}
+
+pub async fn search_posts(
+    auth_session: AuthSession,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+    State(state): State<AppState>,
+    Query(query): Query<PostSearchQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+
+    let search_query = query.q.trim();
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+
+    let (viewer_user_id, viewer_show_sensitive) = if let Some(ref user) = auth_session.user {
+        (Some(user.id), user.show_sensitive_content)
+    } else {
+        (None, false)
+    };
+
+    let (posts, total) = if search_query.is_empty() {
+        (Vec::new(), 0)
+    } else {
+        let posts = search_published_posts(
+            &mut tx,
+            search_query,
+            limit,
+            offset,
+            viewer_user_id,
+            viewer_show_sensitive,
+        )
+        .await?;
+        let total = count_search_published_posts(
+            &mut tx,
+            search_query,
+            viewer_user_id,
+            viewer_show_sensitive,
+        )
+        .await?;
+        (posts, total)
+    };
+
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let template = state.env.get_template("search_posts.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        search_query,
+        posts,
+        pagination => PaginationMeta {
+            offset,
+            limit,
+            total: Some(total),
+            has_more: offset + limit < total,
+        },
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        r2_public_endpoint_url => state.config.r2_public_endpoint_url.clone(),
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
+
+pub async fn search_posts_json(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    Query(query): Query<PostSearchQuery>,
+) -> Result<Json<PostSearchResponse>, AppError> {
+    let search_query = query.q.trim();
+    if search_query.is_empty() {
+        // Return empty results for empty queries
+        return Ok(Json(PostSearchResponse {
+            posts: vec![],
+            pagination: PaginationMeta {
+                offset: query.offset,
+                limit: query.limit,
+                total: Some(0),
+                has_more: false,
+            },
+        }));
+    }
+
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+
+    let (viewer_user_id, viewer_show_sensitive) = if let Some(ref user) = auth_session.user {
+        (Some(user.id), user.show_sensitive_content)
+    } else {
+        (None, false)
+    };
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let posts = search_published_posts(
+        &mut tx,
+        search_query,
+        limit,
+        offset,
+        viewer_user_id,
+        viewer_show_sensitive,
+    )
+    .await?;
+    let total = count_search_published_posts(
+        &mut tx,
+        search_query,
+        viewer_user_id,
+        viewer_show_sensitive,
+    )
+    .await?;
+    tx.commit().await?;
+
+    let posts = posts
+        .into_iter()
+        .map(|post| PostSearchItem {
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            author_login_name: post.user_login_name,
+            image_url: format!(
+                "{}/image/{}/{}",
+                state.config.r2_public_endpoint_url,
+                &post.image_filename[..2],
+                post.image_filename
+            ),
+            image_width: post.image_width,
+            image_height: post.image_height,
+            is_sensitive: post.is_sensitive,
+            published_at: post.published_at,
+        })
+        .collect();
+
+    Ok(Json(PostSearchResponse {
+        posts,
+        pagination: PaginationMeta {
+            offset,
+            limit,
+            total: Some(total),
+            has_more: offset + limit < total,
+        },
     }))
 }
@@ -0,0 +1,141 @@
+use crate::app_error::AppError;
+use crate::models::hashtag::{find_trending_hashtags_in_last_day, TrendingHashtag};
+use crate::models::user::AuthSession;
+use crate::web::context::CommonContext;
+use crate::web::handlers::ExtractFtlLang;
+use crate::web::state::AppState;
+use axum::extract::State;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse};
+use chrono::{DateTime, Utc};
+use minijinja::context;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sha256::digest;
+use std::time::Duration;
+use tracing::{error, info};
+
+const TRENDING_HASHTAGS_KEY: &str = "trending_hashtags";
+const TRENDING_HASHTAGS_LIMIT: i64 = 50;
+const REFRESH_INTERVAL_SECONDS: u64 = 900;
+// Outlives a few missed refreshes, so a stalled task eventually empties the list
+const TRENDING_HASHTAGS_TTL: u64 = 3600;
+
+#[derive(Serialize, Deserialize)]
+pub struct TrendingHashtags {
+    pub generated_at: DateTime<Utc>,
+    pub hashtags: Vec<TrendingHashtag>,
+}
+
+/// Periodically recount hashtag usage over the last 24 hours and cache the top
+/// hashtags in Redis, where the trending endpoints read them from
+pub async fn refresh_trending_hashtags(state: AppState) {
+    info!(
+        "Starting trending hashtags refresher (interval: {}s)",
+        REFRESH_INTERVAL_SECONDS
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(REFRESH_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = store_trending_hashtags(&state).await {
+            error!("Failed to refresh trending hashtags: {:?}", e);
+        }
+    }
+}
+
+/// Recount trending hashtags and cache them, returning the cached JSON
+async fn store_trending_hashtags(state: &AppState) -> anyhow::Result<String> {
+    let mut tx = state.db_pool.begin().await?;
+    let hashtags = find_trending_hashtags_in_last_day(&mut tx, TRENDING_HASHTAGS_LIMIT).await?;
+    tx.commit().await?;
+
+    let blob = serde_json::to_string(&TrendingHashtags {
+        generated_at: Utc::now(),
+        hashtags,
+    })?;
+
+    let mut conn = state.redis_pool.get().await?;
+    conn.set_ex::<_, _, ()>(TRENDING_HASHTAGS_KEY, &blob, TRENDING_HASHTAGS_TTL)
+        .await?;
+    Ok(blob)
+}
+
+/// The cached trending hashtags JSON, counted on the spot if the refresher
+/// hasn't stored any yet
+async fn load_trending_hashtags(state: &AppState) -> anyhow::Result<String> {
+    let mut conn = state.redis_pool.get().await?;
+    let value: Option<String> = conn.get(TRENDING_HASHTAGS_KEY).await?;
+    drop(conn);
+
+    match value {
+        Some(blob) => Ok(blob),
+        None => store_trending_hashtags(state).await,
+    }
+}
+
+/// Trending hashtags as JSON. The ETag changes only when the refresher stores a new list.
+pub async fn trending_hashtags_json(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let blob = load_trending_hashtags(&state).await?;
+    let etag = format!("\"{}\"", digest(blob.as_str()));
+
+    let cache_control = format!("public, max-age={}", REFRESH_INTERVAL_SECONDS);
+    let if_none_match = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok());
+    if if_none_match == Some(etag.as_str()) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(ETAG, etag), (CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+
+    Ok((
+        [
+            (CONTENT_TYPE, "application/json".to_string()),
+            (ETAG, etag),
+            (CACHE_CONTROL, cache_control),
+        ],
+        blob,
+    )
+        .into_response())
+}
+
+/// Page listing the hashtags trending over the last 24 hours
+pub async fn trending_hashtags_page(
+    auth_session: AuthSession,
+    State(state): State<AppState>,
+    ExtractFtlLang(ftl_lang): ExtractFtlLang,
+) -> Result<impl IntoResponse, AppError> {
+    let trending: TrendingHashtags = serde_json::from_str(&load_trending_hashtags(&state).await?)?;
+
+    let db = &state.db_pool;
+    let mut tx = db.begin().await?;
+    let common_ctx = CommonContext::build(
+        &mut tx,
+        &state.redis_pool,
+        auth_session.user.as_ref().map(|u| u.id),
+    )
+    .await?;
+    tx.commit().await?;
+
+    let template = state.env.get_template("trending_hashtags.jinja")?;
+    let rendered = template.render(context! {
+        current_user => auth_session.user,
+        hashtags => trending.hashtags,
+        generated_at => trending.generated_at,
+        draft_post_count => common_ctx.draft_post_count,
+        unread_notification_count => common_ctx.unread_notification_count,
+        announcement => common_ctx.announcement,
+        ftl_lang
+    })?;
+
+    Ok(Html(rendered).into_response())
+}
@@ -1,6 +1,9 @@
+use axum::extract::State;
 use axum::{http::StatusCode, response::IntoResponse, Json};
 use serde_json::json;
 
+use crate::web::state::AppState;
+
 /// Handler for Apple App Site Association (Universal Links)
 /// This endpoint is used by iOS to verify the app's association with the domain
 /// More info: https://developer.apple.com/documentation/xcode/supporting-associated-domains
@@ -37,3 +40,18 @@ pub async fn android_assetlinks() -> impl IntoResponse {
 
     (StatusCode::OK, Json(assetlinks))
 }
+
+/// Handler for NodeInfo discovery
+/// Points fediverse crawlers at the NodeInfo 2.1 document served by `nodeinfo_2_1`
+pub async fn nodeinfo_well_known(State(state): State<AppState>) -> impl IntoResponse {
+    let links = json!({
+        "links": [
+            {
+                "rel": "http://nodeinfo.diaspora.software/ns/schema/2.1",
+                "href": format!("{}/nodeinfo/2.1", state.config.base_url),
+            }
+        ]
+    });
+
+    (StatusCode::OK, Json(links))
+}
@@ -0,0 +1,78 @@
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::app_error::AppError;
+use crate::metrics::{
+    COLLABORATE_WEBSOCKET_SESSIONS, DB_POOL_CONNECTIONS, DB_POOL_IDLE_CONNECTIONS,
+    DB_POOL_MAX_CONNECTIONS, HTTP_REQUESTS_TOTAL, HTTP_REQUEST_DURATION_SECONDS,
+};
+use crate::web::state::AppState;
+
+/// Counts and times every request by its route pattern rather than its path,
+/// so IDs in URLs don't give each request its own series
+pub async fn track_http_metrics(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_owned());
+    let method = request.method().to_string();
+
+    let started_at = Instant::now();
+    let response = next.run(request).await;
+
+    HTTP_REQUEST_DURATION_SECONDS
+        .with_label_values(&[&method, &route])
+        .observe(started_at.elapsed().as_secs_f64());
+    HTTP_REQUESTS_TOTAL
+        .with_label_values(&[&method, &route, response.status().as_str()])
+        .inc();
+    response
+}
+
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    let Some(provided) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    provided.len() == token.len() && openssl::memcmp::eq(provided.as_bytes(), token.as_bytes())
+}
+
+pub async fn metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    // Hide the endpoint entirely unless an operator configured a token
+    if state.config.metrics_token.is_empty() {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+    if !is_authorized(&headers, &state.config.metrics_token) {
+        return Ok(StatusCode::UNAUTHORIZED.into_response());
+    }
+
+    DB_POOL_CONNECTIONS.set(state.db_pool.size() as i64);
+    DB_POOL_IDLE_CONNECTIONS.set(state.db_pool.num_idle() as i64);
+    DB_POOL_MAX_CONNECTIONS.set(state.db_pool.options().get_max_connections() as i64);
+    // Keep the last value if Redis is unreachable rather than failing the scrape
+    match state.redis_state.count_connections().await {
+        Ok(count) => COLLABORATE_WEBSOCKET_SESSIONS.set(count as i64),
+        Err(e) => tracing::warn!("Failed to count collaborative sessions: {:?}", e),
+    }
+
+    let body = crate::metrics::render()?;
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response())
+}
@@ -1,5 +1,7 @@
 pub mod app;
 pub mod context;
+pub mod correlation;
 pub mod handlers;
+pub mod metrics;
 pub mod responses;
 pub mod state;
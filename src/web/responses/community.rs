@@ -3,7 +3,8 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use super::PaginationMeta;
-use crate::models::community::CommunityVisibility;
+use crate::models::community::{CommunityLeaderboardEntry, CommunityVisibility, LeaderboardPeriod};
+use crate::models::community_category::CommunityCategory;
 
 /// Response for active communities list (home page - public only)
 #[derive(Serialize, Debug)]
@@ -35,22 +36,30 @@ pub struct CommunityWithPosts {
     pub posts_count: Option<i64>,
     pub members_count: Option<i64>,
     pub recent_posts: Vec<CommunityPostThumbnail>,
+    pub categories: Vec<CommunityCategory>,
 }
 
 #[derive(Serialize, Debug, Clone)]
 pub struct CommunityPostThumbnail {
     pub id: Uuid,
     pub image_url: String,
+    /// Downscaled variant for grids; the original's URL if none was generated
+    pub thumbnail_url: String,
     pub image_width: i32,
     pub image_height: i32,
     pub is_sensitive: bool,
+    /// Images in the post's gallery, at least 1
+    pub image_count: i64,
 }
 
 /// Response for community detail endpoint
 #[derive(Serialize, Debug)]
 pub struct CommunityDetailResponse {
     pub community: CommunityInfo,
+    /// Whether clients should blur posts (or gate the community) for this viewer
+    pub should_blur: bool,
     pub stats: CommunityStats,
+    pub pinned_posts: Vec<CommunityPostThumbnail>,
     pub posts: Vec<CommunityPostThumbnail>,
     pub pagination: PaginationMeta,
     pub comments: Vec<CommunityComment>,
@@ -67,6 +76,7 @@ pub struct CommunityInfo {
     pub owner_id: Uuid,
     pub background_color: Option<String>,
     pub foreground_color: Option<String>,
+    pub is_sensitive: bool,
 }
 
 /// Community statistics
@@ -122,6 +132,37 @@ pub struct CommunityInvitationsListResponse {
     pub invitations: Vec<CommunityInvitationResponse>,
 }
 
+/// What happened to one of the names in a bulk invitation
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InviteOutcome {
+    Invited,
+    AlreadyMember,
+    AlreadyInvited,
+    NotFound,
+}
+
+#[derive(Serialize, Debug)]
+pub struct InviteUserResult {
+    pub login_name: String,
+    pub result: InviteOutcome,
+}
+
+/// Response for inviting several users at once
+#[derive(Serialize, Debug)]
+pub struct InviteUsersResponse {
+    pub results: Vec<InviteUserResult>,
+}
+
+/// Top contributors of a community, plus the viewer's own standing
+#[derive(Serialize, Debug)]
+pub struct CommunityLeaderboardResponse {
+    pub period: LeaderboardPeriod,
+    pub entries: Vec<CommunityLeaderboardEntry>,
+    /// Present whenever the viewer is ranked at all, even outside `entries`
+    pub viewer_entry: Option<CommunityLeaderboardEntry>,
+}
+
 /// Community invitation with invitee details
 #[derive(Serialize, Debug)]
 pub struct CommunityInvitationResponse {
@@ -130,6 +171,7 @@ pub struct CommunityInvitationResponse {
     pub invitee: InvitationUserInfo,
     pub inviter: InvitationUserInfo,
     pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 /// User information for invitations
@@ -154,6 +196,7 @@ pub struct UserInvitationResponse {
     pub community: InvitationCommunityInfo,
     pub inviter: InvitationUserInfo,
     pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
 }
 
 /// Community information for invitations
@@ -1,6 +1,7 @@
 pub mod common;
 pub mod community;
 pub mod notification;
+pub mod oauth;
 pub mod post;
 pub mod profile;
 pub mod search;
@@ -8,6 +9,7 @@ pub mod search;
 pub use common::*;
 pub use community::*;
 pub use notification::*;
+pub use oauth::*;
 pub use post::*;
 pub use profile::*;
 pub use search::*;
@@ -14,8 +14,28 @@ pub struct CommentListResponse {
 #[derive(Serialize, Debug)]
 pub struct NotificationsListResponse {
     pub notifications: Vec<NotificationItem>,
+    /// Unread notifications in total, not just on this page
     pub total: usize,
     pub has_more: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Response for the grouped notifications list
+#[derive(Serialize, Debug)]
+pub struct GroupedNotificationsListResponse {
+    pub groups: Vec<GroupedNotificationItem>,
+    pub has_more: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Notifications of one kind on one post, represented by the most recent one
+#[derive(Serialize, Debug)]
+pub struct GroupedNotificationItem {
+    pub notification: NotificationItem,
+    pub others_count: i64,
+    pub unread_count: i64,
 }
 
 /// Individual notification item
@@ -56,6 +76,7 @@ pub struct MarkNotificationReadResponse {
 #[derive(Serialize, Debug)]
 pub struct MarkAllReadResponse {
     pub count: i64,
+    pub unread_count: i64,
 }
 
 /// Response for unread notification count
@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+/// Successful token response (RFC 6749 section 5.1)
+#[derive(Serialize, Debug)]
+pub struct OAuthTokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    pub expires_in: i64,
+    pub refresh_token: String,
+}
+
+/// Error response of the token endpoints (RFC 6749 section 5.2). Clients
+/// expect this shape rather than the usual `ErrorResponse`.
+#[derive(Serialize, Debug)]
+pub struct OAuthErrorResponse {
+    pub error: &'static str,
+    pub error_description: String,
+}
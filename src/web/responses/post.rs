@@ -3,11 +3,15 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use super::PaginationMeta;
+use crate::models::activity_delivery::ActivityDelivery;
 
 /// Nested image information (from images table)
 #[derive(Serialize, Debug)]
 pub struct ImageInfo {
     pub filename: String,
+    /// WebP variant of `filename`, preferred by clients that can decode it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webp_filename: Option<String>,
     pub width: i32,
     pub height: i32,
     pub tool: String,
@@ -152,11 +156,14 @@ pub struct ThreadedCommentResponse {
     pub content_html: Option<String>,
     pub actor_name: String,
     pub actor_handle: String,
+    /// Profile page of the author, local or remote
+    pub actor_url: String,
     pub actor_login_name: Option<String>,
     pub is_local: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub reactions: Vec<ReactionCount>,
     pub children: Vec<ThreadedCommentResponse>,
 }
 
@@ -173,6 +180,18 @@ pub struct MovableCommunitiesResponse {
     pub communities: Vec<MovableCommunity>,
 }
 
+/// Response for post federation delivery status endpoint
+#[derive(Serialize, Debug)]
+pub struct PostDeliveriesResponse {
+    pub deliveries: Vec<ActivityDelivery>,
+}
+
+/// Response for post translation endpoint
+#[derive(Serialize, Debug)]
+pub struct PostTranslationResponse {
+    pub translated_content: String,
+}
+
 /// Community that a post can be moved to
 #[derive(Serialize, Debug)]
 pub struct MovableCommunity {
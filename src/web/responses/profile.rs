@@ -2,6 +2,7 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use super::PaginationMeta;
+use crate::models::user::ActivityItem;
 
 /// Response for profile endpoint
 #[derive(Serialize, Debug)]
@@ -22,6 +23,7 @@ pub struct ProfileUser {
     pub id: Uuid,
     pub login_name: String,
     pub display_name: String,
+    pub avatar_url: Option<String>,
     pub is_following: bool,
 }
 
@@ -68,3 +70,12 @@ pub struct ProfileFollowingsListResponse {
     pub followings: Vec<ProfileFollowing>,
     pub pagination: PaginationMeta,
 }
+
+/// Response for the current user's activity feed
+#[derive(Serialize, Debug)]
+pub struct UserActivityResponse {
+    pub items: Vec<ActivityItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
@@ -1,11 +1,17 @@
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use uuid::Uuid;
 
+use super::PaginationMeta;
+
 /// Response for search endpoint
 #[derive(Serialize, Debug)]
 pub struct SearchResponse {
     pub users: Vec<SearchUserResult>,
     pub posts: Vec<SearchPostResult>,
+    pub communities: Vec<SearchCommunityResult>,
+    /// Posts and communities merged and ordered by rank
+    pub results: Vec<SearchResultItem>,
 }
 
 /// User search result
@@ -17,11 +23,61 @@ pub struct SearchUserResult {
 }
 
 /// Post search result (thumbnail format)
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
 pub struct SearchPostResult {
     pub id: Uuid,
     pub image_url: String,
     pub image_width: Option<i32>,
     pub image_height: Option<i32>,
     pub is_sensitive: bool,
+    pub rank: f32,
+}
+
+/// Community search result
+#[derive(Serialize, Debug, Clone)]
+pub struct SearchCommunityResult {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub posts_count: Option<i64>,
+    pub rank: f32,
+}
+
+/// Entry in the merged search results
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchResultItem {
+    Post(SearchPostResult),
+    Community(SearchCommunityResult),
+}
+
+impl SearchResultItem {
+    pub fn rank(&self) -> f32 {
+        match self {
+            SearchResultItem::Post(post) => post.rank,
+            SearchResultItem::Community(community) => community.rank,
+        }
+    }
+}
+
+/// Full-text post search result
+#[derive(Serialize, Debug)]
+pub struct PostSearchItem {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub author_login_name: String,
+    pub image_url: String,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub is_sensitive: bool,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Response for full-text post search
+#[derive(Serialize, Debug)]
+pub struct PostSearchResponse {
+    pub posts: Vec<PostSearchItem>,
+    pub pagination: PaginationMeta,
 }
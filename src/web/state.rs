@@ -3,6 +3,8 @@ use sqlx::PgPool;
 use std::sync::Arc;
 
 use super::handlers::collaborate::redis_state::RedisStateManager;
+use crate::delivery_queue::DeliveryQueue;
+use crate::models::notification::NotificationEvents;
 use crate::push::PushService;
 use crate::redis::RedisPool;
 use crate::AppConfig;
@@ -15,4 +17,8 @@ pub struct AppState {
     pub redis_pool: RedisPool,
     pub redis_state: RedisStateManager,
     pub push_service: Arc<PushService>,
+    pub notification_events: NotificationEvents,
+    pub delivery_queue: DeliveryQueue,
+    /// Appended to static asset URLs; fixed for the lifetime of the process
+    pub asset_version: String,
 }